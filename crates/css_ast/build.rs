@@ -1,7 +1,12 @@
 use std::fmt::Write;
 use std::io;
 use std::str::from_utf8;
-use std::{collections::HashSet, env, fs::write, path::Path};
+use std::{
+	collections::{BTreeMap, BTreeSet},
+	env,
+	fs::write,
+	path::Path,
+};
 
 use glob::glob;
 use grep_matcher::{Captures, Matcher};
@@ -30,10 +35,20 @@ pub fn snake(str: String) -> String {
 	snake
 }
 
+pub fn property_name(prop: &str) -> String {
+	let name = kebab(prop.trim_end_matches("<'a>").trim_end_matches("StyleValue").to_string());
+	if name.starts_with("webkit") {
+		format!("-{}", name)
+	} else {
+		name
+	}
+}
+
 pub struct NodeMatcher<'a> {
 	matcher: &'a RegexMatcher,
-	visit_matches: &'a mut HashSet<String>,
-	stylevalue_matches: &'a mut HashSet<String>,
+	visit_matches: &'a mut BTreeSet<String>,
+	stylevalue_matches: &'a mut BTreeSet<String>,
+	initial_matches: &'a mut BTreeMap<String, String>,
 }
 
 impl Sink for NodeMatcher<'_> {
@@ -60,6 +75,12 @@ impl Sink for NodeMatcher<'_> {
 			if !capture.is_empty() {
 				if value_or_visit == "value" {
 					self.stylevalue_matches.insert(capture.to_string());
+					let definition = &line[captures.get(0).unwrap()];
+					if let Some((_, rest)) = definition.split_once("#[initial(\"") {
+						if let Some((initial, _)) = rest.split_once("\")]") {
+							self.initial_matches.insert(capture.to_string(), initial.to_string());
+						}
+					}
 				}
 				self.visit_matches.insert(capture.to_string());
 			} else {
@@ -72,6 +93,33 @@ impl Sink for NodeMatcher<'_> {
 	}
 }
 
+// Properties which are specified but not parsed yet are commented out, yet their initial values are still useful, for
+// example to expand a shorthand which sets them.
+pub struct UnparsedInitialMatcher<'a> {
+	matcher: &'a RegexMatcher,
+	initial_matches: &'a mut BTreeMap<String, String>,
+}
+
+impl Sink for UnparsedInitialMatcher<'_> {
+	type Error = io::Error;
+
+	fn matched(&mut self, _searcher: &Searcher, mat: &SinkMatch<'_>) -> Result<bool, io::Error> {
+		let mut captures = self.matcher.new_captures()?;
+		let line = match from_utf8(mat.bytes()) {
+			Ok(matched) => matched,
+			Err(err) => return Err(io::Error::error_message(err)),
+		};
+		self.matcher.captures_iter(mat.bytes(), &mut captures, |captures| -> bool {
+			let initial = &line[captures.get(1).unwrap()];
+			let capture = &line[captures.get(2).unwrap()];
+			// Some properties are specified more than once, so the first definition wins.
+			self.initial_matches.entry(capture.to_string()).or_insert_with(|| initial.to_string());
+			true
+		})?;
+		Ok(true)
+	}
+}
+
 fn main() {
 	println!("cargo::rerun-if-changed=build.rs");
 	use std::time::Instant;
@@ -103,8 +151,27 @@ fn main() {
 			\s*(\w*(:?<'a>)?)"#,
 		)
 		.unwrap();
-	let mut visit_matches = HashSet::new();
-	let mut stylevalue_matches = HashSet::new();
+	let unparsed_matcher = RegexMatcherBuilder::new()
+		.multi_line(true)
+		.ignore_whitespace(true)
+		.build(
+			r#"
+			# match a commented out #[value] attribute
+			^//\s*\#\[value\([^\n]*\n
+			# munch the commented lines between the attribute and the initial value
+			(?://[^\n]*\n)*?
+			# grab the initial value
+			//\s*\#\[initial\("([^"]*)"\)\]\n
+			# munch the commented lines between the initial value and the definition
+			(?://[^\n]*\n)*?
+			# finally grab the name
+			//\s*pub\s*(?:struct|enum)\s*(\w*)"#,
+		)
+		.unwrap();
+	let mut visit_matches = BTreeSet::new();
+	let mut stylevalue_matches = BTreeSet::new();
+	let mut initial_matches = BTreeMap::new();
+	let mut unparsed_initial_matches = BTreeMap::new();
 	let mut searcher = SearcherBuilder::new().line_number(false).multi_line(true).build();
 	for entry in glob("src/**/*.rs").unwrap() {
 		let str = &entry.as_ref().unwrap().display();
@@ -113,8 +180,12 @@ fn main() {
 			matcher: &matcher,
 			visit_matches: &mut visit_matches,
 			stylevalue_matches: &mut stylevalue_matches,
+			initial_matches: &mut initial_matches,
 		};
-		searcher.search_path(&matcher, entry.unwrap(), context).unwrap();
+		searcher.search_path(&matcher, entry.as_ref().unwrap(), context).unwrap();
+		let context =
+			UnparsedInitialMatcher { matcher: &unparsed_matcher, initial_matches: &mut unparsed_initial_matches };
+		searcher.search_path(&unparsed_matcher, entry.unwrap(), context).unwrap();
 	}
 
 	let source = format!(
@@ -152,18 +223,42 @@ fn main() {
 			}}
 		}}",
 		stylevalue_matches.iter().fold(String::new(), |mut out, prop| {
-			let variant_name = prop.trim_end_matches("<'a>").trim_end_matches("StyleValue").to_string();
-			let mut variant_str = kebab(variant_name.to_owned());
-			if variant_str.starts_with("webkit") {
-				variant_str = format!("-{}", variant_str);
-			}
-			writeln!(out, "\t\t\t\t\t{}: {} = \"{}\",", variant_name, prop, variant_str).unwrap();
+			let variant_name = prop.trim_end_matches("<'a>").trim_end_matches("StyleValue");
+			writeln!(out, "\t\t\t\t\t{}: {} = \"{}\",", variant_name, prop, property_name(prop)).unwrap();
 			out
 		})
 	);
 
 	let _ = write(Path::new(&env::var("OUT_DIR").unwrap()).join("css_apply_properties.rs"), source);
 
+	// The initial values of parsed properties take precedence over those of unparsed properties with the same name.
+	// Shorthands have an initial of "see individual properties", which isn't a value, and neither are a few others.
+	let mut initials = BTreeMap::new();
+	for (prop, initial) in unparsed_initial_matches.iter().chain(&initial_matches) {
+		initials.insert(property_name(prop), initial);
+	}
+	let source = format!(
+		r"macro_rules! apply_property_initials {{
+			($macro: ident) => {{
+				$macro! {{
+{}				}}
+			}}
+		}}",
+		initials
+			.iter()
+			.filter(|(_, initial)| {
+				!["see individual properties", "depends on user agent", "n/a"]
+					.iter()
+					.any(|prose| initial.eq_ignore_ascii_case(prose))
+			})
+			.fold(String::new(), |mut out, (prop, initial)| {
+				writeln!(out, "\t\t\t\t\t\"{}\" = \"{}\",", prop, initial).unwrap();
+				out
+			})
+	);
+
+	let _ = write(Path::new(&env::var("OUT_DIR").unwrap()).join("css_apply_property_initials.rs"), source);
+
 	let elapsed = now.elapsed();
 	println!("cargo::warning=Built in {:.?}", &elapsed);
 }
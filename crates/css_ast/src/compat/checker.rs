@@ -0,0 +1,191 @@
+use css_lexer::{Cursor, Kind, Span};
+use css_parse::ToCursors;
+use miette::Diagnostic;
+use thiserror::Error;
+
+use super::{Compat, CompatStatus, Targets};
+use crate::{FunctionalPseudoClass, FunctionalPseudoElement, Property, PseudoClass, PseudoElement, StyleSheet, Visit};
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("'{0}' is deprecated")]
+#[diagnostic(help("Browsers may drop support for it, so consider replacing it."), code(css_ast::DeprecatedFeature))]
+pub struct DeprecatedFeature(pub String, #[label("This is deprecated")] pub Span);
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("'{0}' is experimental")]
+#[diagnostic(
+	help("Its specification may still change, along with how browsers handle it."),
+	code(css_ast::ExperimentalFeature)
+)]
+pub struct ExperimentalFeature(pub String, #[label("This is experimental")] pub Span);
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("'{0}' is non-standard")]
+#[diagnostic(
+	help("It only exists with a vendor prefix, so only some browsers have it."),
+	code(css_ast::VendorOnlyFeature)
+)]
+pub struct VendorOnlyFeature(pub String, #[label("This is non-standard")] pub Span);
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("'{0}' isn't supported by {1}")]
+#[diagnostic(help("Consider providing a fallback for these browsers."), code(css_ast::UnsupportedFeature))]
+pub struct UnsupportedFeature(pub String, pub String, #[label("This isn't supported")] pub Span);
+
+fn cursors(node: &impl ToCursors) -> Vec<Cursor> {
+	let mut cursors = vec![];
+	node.to_cursors(&mut cursors);
+	cursors
+}
+
+/// A [Visit]or which checks each property, value keyword, pseudo class, pseudo element and at-rule of a stylesheet
+/// against the embedded [Compat] data, collecting a diagnostic for each one that is deprecated, experimental,
+/// non-standard, or not supported by one of the [Targets].
+#[derive(Default)]
+pub struct CompatChecker<'a> {
+	source_text: &'a str,
+	targets: Targets,
+	pub diagnostics: Vec<miette::Error>,
+}
+
+impl<'a> CompatChecker<'a> {
+	pub fn new(source_text: &'a str, targets: Targets) -> Self {
+		Self { source_text, targets, diagnostics: vec![] }
+	}
+
+	fn name(&self, span: Span) -> String {
+		span.span_contents(self.source_text).contents().to_ascii_lowercase()
+	}
+
+	fn check(&mut self, name: String, compat: Option<&Compat>, span: Span) {
+		let Some(compat) = compat else { return };
+		if compat.status.contains(CompatStatus::Deprecated) {
+			self.diagnostics.push(DeprecatedFeature(name.clone(), span).into());
+		}
+		if compat.status.contains(CompatStatus::Experimental) {
+			self.diagnostics.push(ExperimentalFeature(name.clone(), span).into());
+		}
+		if compat.status.contains(CompatStatus::VendorOnly) {
+			self.diagnostics.push(VendorOnlyFeature(name.clone(), span).into());
+		}
+		let browsers = compat
+			.unsupported_by(&self.targets)
+			.map(|browser| format!("{} {}", browser, self.targets.version(browser).unwrap()))
+			.collect::<Vec<_>>();
+		if !browsers.is_empty() {
+			self.diagnostics.push(UnsupportedFeature(name, browsers.join(", "), span).into());
+		}
+	}
+
+	// Functional pseudo classes & elements are named by their colons & function token, without the open paren.
+	fn check_function_selector(&mut self, cursors: &[Cursor]) {
+		if let Some(function) = cursors.iter().position(|c| *c == Kind::Function) {
+			let span = cursors[0].span() + cursors[function].span();
+			let name = self.name(span).trim_end_matches('(').to_string();
+			let compat = Compat::selector(&name);
+			self.check(name, compat, span);
+		}
+	}
+}
+
+impl<'a> Visit<'a> for CompatChecker<'a> {
+	// At-rules are checked by their at-keywords, wherever they appear in the stylesheet.
+	fn visit_style_sheet(&mut self, stylesheet: &StyleSheet<'a>) {
+		for c in cursors(stylesheet) {
+			if c == Kind::AtKeyword {
+				let name = self.name(c.span()).trim_start_matches('@').to_string();
+				let compat = Compat::at_rule(&name);
+				self.check(format!("@{}", name), compat, c.span());
+			}
+		}
+	}
+
+	fn visit_property(&mut self, property: &Property<'a>) {
+		let name = self.name(property.name.into());
+		if name.starts_with("--") {
+			return;
+		}
+		self.check(name.clone(), Compat::property(&name), property.name.into());
+		for c in cursors(&property.value) {
+			if c == Kind::Ident {
+				let keyword = self.name(c.span());
+				let compat = Compat::value(&name, &keyword);
+				self.check(keyword, compat, c.span());
+			}
+		}
+	}
+
+	fn visit_pseudo_class(&mut self, class: &PseudoClass) {
+		let span: Span = class.into();
+		let name = self.name(span);
+		let compat = Compat::selector(&name);
+		self.check(name, compat, span);
+	}
+
+	fn visit_pseudo_element(&mut self, element: &PseudoElement) {
+		let cursors = cursors(element);
+		if let (Some(first), Some(last)) = (cursors.first(), cursors.last()) {
+			let span = first.span() + last.span();
+			let name = self.name(span);
+			let compat = Compat::selector(&name);
+			self.check(name, compat, span);
+		}
+	}
+
+	fn visit_functional_pseudo_class(&mut self, class: &FunctionalPseudoClass<'a>) {
+		self.check_function_selector(&cursors(class));
+	}
+
+	fn visit_functional_pseudo_element(&mut self, element: &FunctionalPseudoElement<'a>) {
+		self.check_function_selector(&cursors(element));
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{Browser, Version, Visitable};
+	use bumpalo::Bump;
+	use css_parse::Parser;
+
+	fn check(source_text: &str, targets: Targets) -> Vec<String> {
+		let bump = Bump::default();
+		let stylesheet = Parser::new(&bump, source_text).parse_entirely::<StyleSheet>().output.unwrap();
+		let mut checker = CompatChecker::new(source_text, targets);
+		stylesheet.accept(&mut checker);
+		checker.diagnostics.iter().map(|diagnostic| diagnostic.to_string()).collect()
+	}
+
+	#[test]
+	fn test_status() {
+		assert_eq!(
+			check("a { color: red; page-break-after: always }", Targets::default()),
+			["'page-break-after' is deprecated"]
+		);
+		assert_eq!(
+			check("a { display: -webkit-box }", Targets::default()),
+			["'-webkit-box' is deprecated", "'-webkit-box' is non-standard"]
+		);
+		assert_eq!(check("::-WEBKIT-scrollbar {}", Targets::default()), ["'::-webkit-scrollbar' is non-standard"]);
+		assert_eq!(
+			check("@custom-media --narrow (max-width: 30em);", Targets::default()),
+			["'@custom-media' is experimental"]
+		);
+	}
+
+	#[test]
+	fn test_targets() {
+		let targets = Targets::default().with_browser(Browser::Firefox, Version(115, 0));
+		assert_eq!(check("a:has(b) { display: contents }", targets), ["':has' isn't supported by firefox 115"]);
+		let targets = targets.with_browser(Browser::Safari, Version(15, 0));
+		assert!(check("@media screen { a { aspect-ratio: 1 } }", targets).is_empty());
+		assert_eq!(
+			check("@layer a { a { accent-color: red } }", targets),
+			["'@layer' isn't supported by safari 15", "'accent-color' isn't supported by safari 15"]
+		);
+		assert_eq!(
+			check("a::backdrop, a:focus-visible {}", targets),
+			["'::backdrop' isn't supported by safari 15", "':focus-visible' isn't supported by safari 15"]
+		);
+	}
+}
@@ -0,0 +1,167 @@
+use super::{Browser, Compat, CompatStatus, Version};
+
+// Each entry lists the feature's status flags, followed by the first version of each browser to support it (without
+// a vendor prefix, unless the feature is itself prefixed). Browsers which are left out don't support the feature.
+//
+// Versions follow https://github.com/mdn/browser-compat-data.
+macro_rules! compat {
+	([$( $status: ident )*] $( $browser: ident $version: literal )*) => {{
+		const COMPAT: Compat = {
+			// Features no browser supports have no versions to set.
+			#[allow(unused_mut)]
+			let mut versions = [None; 4];
+			$(
+				versions[Browser::$browser as usize] = Version::parse(stringify!($version));
+			)*
+			Compat { status: CompatStatus::none()$(.or(CompatStatus::$status))*, versions }
+		};
+		Some(&COMPAT)
+	}};
+}
+
+macro_rules! define_compat {
+	(
+		properties { $( $property: literal: $property_compat: tt, )+ }
+		values { $( ($value_property: literal, $value: literal): $value_compat: tt, )+ }
+		selectors { $( $selector: literal: $selector_compat: tt, )+ }
+		at_rules { $( $at_rule: literal: $at_rule_compat: tt, )+ }
+	) => {
+		pub(crate) fn property(name: &str) -> Option<&'static Compat> {
+			match name {
+				$( $property => compat! $property_compat, )+
+				_ => None,
+			}
+		}
+
+		pub(crate) fn value(property: &str, keyword: &str) -> Option<&'static Compat> {
+			match (property, keyword) {
+				$( ($value_property, $value) => compat! $value_compat, )+
+				_ => None,
+			}
+		}
+
+		pub(crate) fn selector(name: &str) -> Option<&'static Compat> {
+			match name {
+				$( $selector => compat! $selector_compat, )+
+				_ => None,
+			}
+		}
+
+		pub(crate) fn at_rule(name: &str) -> Option<&'static Compat> {
+			match name {
+				$( $at_rule => compat! $at_rule_compat, )+
+				_ => None,
+			}
+		}
+	};
+}
+
+define_compat! {
+	properties {
+		"-moz-osx-font-smoothing": ([VendorOnly] Firefox 25),
+		"-webkit-box-reflect": ([VendorOnly] Chrome 4 Edge 79 Safari 4),
+		"-webkit-font-smoothing": ([VendorOnly] Chrome 5 Edge 79 Safari 4),
+		"-webkit-line-clamp": ([VendorOnly] Chrome 6 Edge 17 Firefox 68 Safari 5),
+		"-webkit-tap-highlight-color": ([VendorOnly] Chrome 16 Edge 12),
+		"-webkit-text-stroke": ([VendorOnly] Chrome 4 Edge 15 Firefox 49 Safari 3),
+		"accent-color": ([] Chrome 93 Edge 93 Firefox 92 Safari 15.4),
+		"anchor-name": ([Experimental] Chrome 125 Edge 125),
+		"appearance": ([] Chrome 84 Edge 84 Firefox 80 Safari 15.4),
+		"aspect-ratio": ([] Chrome 88 Edge 88 Firefox 89 Safari 15),
+		"backdrop-filter": ([] Chrome 76 Edge 79 Firefox 103 Safari 18),
+		"clip": ([Deprecated] Chrome 1 Edge 12 Firefox 1 Safari 1),
+		"color-scheme": ([] Chrome 81 Edge 81 Firefox 96 Safari 13),
+		"contain": ([] Chrome 52 Edge 79 Firefox 69 Safari 15.4),
+		"container": ([] Chrome 105 Edge 105 Firefox 110 Safari 16),
+		"container-name": ([] Chrome 105 Edge 105 Firefox 110 Safari 16),
+		"container-type": ([] Chrome 105 Edge 105 Firefox 110 Safari 16),
+		"content-visibility": ([] Chrome 85 Edge 85 Firefox 125 Safari 18),
+		"field-sizing": ([Experimental] Chrome 123 Edge 123),
+		"grid-column-gap": ([Deprecated] Chrome 57 Edge 16 Firefox 52 Safari 10.1),
+		"grid-gap": ([Deprecated] Chrome 57 Edge 16 Firefox 52 Safari 10.1),
+		"grid-row-gap": ([Deprecated] Chrome 57 Edge 16 Firefox 52 Safari 10.1),
+		"hanging-punctuation": ([] Safari 10),
+		"ime-mode": ([Deprecated] Edge 12 Firefox 3),
+		"initial-letter": ([Experimental] Chrome 110 Edge 110 Safari 17),
+		"inset": ([] Chrome 87 Edge 87 Firefox 66 Safari 14.1),
+		"math-depth": ([] Chrome 109 Edge 109 Firefox 117),
+		"overflow-clip-margin": ([] Chrome 90 Edge 90 Firefox 102),
+		"overscroll-behavior": ([] Chrome 63 Edge 18 Firefox 59 Safari 16),
+		"page-break-after": ([Deprecated] Chrome 1 Edge 12 Firefox 1 Safari 1.2),
+		"page-break-before": ([Deprecated] Chrome 1 Edge 12 Firefox 1 Safari 1.2),
+		"page-break-inside": ([Deprecated] Chrome 1 Edge 12 Firefox 19 Safari 1.3),
+		"position-anchor": ([Experimental] Chrome 125 Edge 125),
+		"rotate": ([] Chrome 104 Edge 104 Firefox 72 Safari 14.1),
+		"scale": ([] Chrome 104 Edge 104 Firefox 72 Safari 14.1),
+		"scrollbar-color": ([] Chrome 121 Edge 121 Firefox 64),
+		"scrollbar-gutter": ([] Chrome 94 Edge 94 Firefox 97 Safari 18.2),
+		"scrollbar-width": ([] Chrome 121 Edge 121 Firefox 64 Safari 18.2),
+		"text-size-adjust": ([Experimental] Chrome 54 Edge 79),
+		"text-wrap": ([] Chrome 114 Edge 114 Firefox 121 Safari 17.4),
+		"translate": ([] Chrome 104 Edge 104 Firefox 72 Safari 14.1),
+	}
+	values {
+		("display", "-webkit-box"): ([Deprecated VendorOnly] Chrome 1 Edge 12 Firefox 49 Safari 3),
+		("display", "contents"): ([] Chrome 65 Edge 79 Firefox 37 Safari 11.1),
+		("display", "flow-root"): ([] Chrome 58 Edge 79 Firefox 53 Safari 13),
+		("display", "grid"): ([] Chrome 57 Edge 16 Firefox 52 Safari 10.1),
+		("display", "inline-grid"): ([] Chrome 57 Edge 16 Firefox 52 Safari 10.1),
+		("font-size", "xxx-large"): ([] Chrome 79 Edge 79 Firefox 70 Safari 16.4),
+		("image-rendering", "pixelated"): ([] Chrome 41 Edge 79 Firefox 93 Safari 10),
+		("overflow", "clip"): ([] Chrome 90 Edge 90 Firefox 81 Safari 16),
+		("overflow", "overlay"): ([Deprecated] Chrome 1 Edge 79 Safari 6),
+		("position", "-webkit-sticky"): ([Deprecated VendorOnly] Safari 6.1),
+		("position", "sticky"): ([] Chrome 56 Edge 16 Firefox 32 Safari 13),
+		("text-wrap", "balance"): ([] Chrome 114 Edge 114 Firefox 121 Safari 17.5),
+		("text-wrap", "pretty"): ([] Chrome 117 Edge 117),
+		("width", "fit-content"): ([] Chrome 46 Edge 79 Firefox 94 Safari 11),
+		("word-break", "break-word"): ([Deprecated] Chrome 1 Edge 79 Firefox 67 Safari 3),
+	}
+	selectors {
+		"::-moz-selection": ([Deprecated VendorOnly] Firefox 1),
+		"::-webkit-scrollbar": ([VendorOnly] Chrome 1 Edge 79 Safari 3),
+		"::backdrop": ([] Chrome 37 Edge 79 Firefox 47 Safari 15.4),
+		"::file-selector-button": ([] Chrome 89 Edge 89 Firefox 82 Safari 14.1),
+		"::marker": ([] Chrome 86 Edge 86 Firefox 68 Safari 11.1),
+		"::part": ([] Chrome 73 Edge 79 Firefox 72 Safari 13.1),
+		"::placeholder": ([] Chrome 57 Edge 79 Firefox 51 Safari 10.1),
+		"::selection": ([] Chrome 1 Edge 12 Firefox 62 Safari 1.1),
+		"::slotted": ([] Chrome 50 Edge 79 Firefox 63 Safari 10),
+		"::spelling-error": ([Experimental] Chrome 121 Edge 121),
+		"::target-text": ([] Chrome 89 Edge 89 Firefox 131 Safari 18.2),
+		":-moz-focusring": ([VendorOnly] Firefox 4),
+		":-ms-input-placeholder": ([Deprecated VendorOnly]),
+		":-webkit-autofill": ([VendorOnly] Chrome 1 Edge 79 Firefox 86 Safari 3),
+		":any-link": ([] Chrome 65 Edge 79 Firefox 50 Safari 9),
+		":autofill": ([] Chrome 110 Edge 110 Firefox 86 Safari 15),
+		":blank": ([Experimental]),
+		":dir": ([] Chrome 120 Edge 120 Firefox 49 Safari 16.4),
+		":focus-visible": ([] Chrome 86 Edge 86 Firefox 85 Safari 15.4),
+		":focus-within": ([] Chrome 60 Edge 79 Firefox 52 Safari 10.1),
+		":has": ([] Chrome 105 Edge 105 Firefox 121 Safari 15.4),
+		":host": ([] Chrome 54 Edge 79 Firefox 63 Safari 10),
+		":is": ([] Chrome 88 Edge 88 Firefox 78 Safari 14),
+		":modal": ([] Chrome 105 Edge 105 Firefox 103 Safari 15.6),
+		":popover-open": ([] Chrome 114 Edge 114 Firefox 125 Safari 17),
+		":state": ([] Chrome 125 Edge 125 Firefox 126 Safari 17.4),
+		":user-invalid": ([] Chrome 119 Edge 119 Firefox 88 Safari 16.5),
+		":user-valid": ([] Chrome 119 Edge 119 Firefox 88 Safari 16.5),
+		":where": ([] Chrome 88 Edge 88 Firefox 78 Safari 14),
+	}
+	at_rules {
+		"-moz-document": ([Deprecated VendorOnly]),
+		"-webkit-keyframes": ([Deprecated VendorOnly] Chrome 1 Edge 12 Firefox 16 Safari 4),
+		"container": ([] Chrome 105 Edge 105 Firefox 110 Safari 16),
+		"counter-style": ([] Chrome 91 Edge 91 Firefox 33 Safari 17),
+		"custom-media": ([Experimental]),
+		"custom-selector": ([Experimental]),
+		"document": ([Deprecated]),
+		"font-palette-values": ([] Chrome 101 Edge 101 Firefox 107 Safari 15.4),
+		"layer": ([] Chrome 99 Edge 99 Firefox 97 Safari 15.4),
+		"position-try": ([Experimental] Chrome 125 Edge 125),
+		"property": ([] Chrome 85 Edge 85 Firefox 128 Safari 16.4),
+		"scope": ([] Chrome 118 Edge 118 Safari 17.4),
+		"starting-style": ([] Chrome 117 Edge 117 Firefox 129 Safari 17.5),
+		"view-transition": ([] Chrome 126 Edge 126 Safari 18.2),
+	}
+}
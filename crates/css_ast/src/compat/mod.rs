@@ -0,0 +1,198 @@
+mod checker;
+mod data;
+mod targets;
+
+pub use checker::*;
+pub use targets::*;
+
+use bitmask_enum::bitmask;
+use core::fmt;
+
+/// A browser which compatibility data is recorded for.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Browser {
+	Chrome,
+	Edge,
+	Firefox,
+	Safari,
+}
+
+impl Browser {
+	pub const ALL: [Browser; 4] = [Self::Chrome, Self::Edge, Self::Firefox, Self::Safari];
+
+	/// The browser with the given (lowercase) name.
+	pub fn from_name(name: &str) -> Option<Self> {
+		match name {
+			"chrome" => Some(Self::Chrome),
+			"edge" => Some(Self::Edge),
+			"firefox" => Some(Self::Firefox),
+			"safari" => Some(Self::Safari),
+			_ => None,
+		}
+	}
+
+	pub fn name(&self) -> &'static str {
+		match self {
+			Self::Chrome => "chrome",
+			Self::Edge => "edge",
+			Self::Firefox => "firefox",
+			Self::Safari => "safari",
+		}
+	}
+}
+
+impl fmt::Display for Browser {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(self.name())
+	}
+}
+
+/// A browser version, as a major & minor number; `15.4` is `Version(15, 4)`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Version(pub u16, pub u16);
+
+impl Version {
+	/// Parse a version such as `88` or `15.4`, returning None if it isn't one.
+	pub const fn parse(str: &str) -> Option<Self> {
+		let bytes = str.as_bytes();
+		let mut parts = [0u16; 2];
+		let mut part = 0;
+		let mut digits = 0;
+		let mut i = 0;
+		while i < bytes.len() {
+			match bytes[i] {
+				b'0'..=b'9' => {
+					parts[part] = parts[part] * 10 + (bytes[i] - b'0') as u16;
+					digits += 1;
+				}
+				b'.' if part == 0 && digits > 0 => {
+					part = 1;
+					digits = 0;
+				}
+				_ => return None,
+			}
+			i += 1;
+		}
+		if digits == 0 {
+			return None;
+		}
+		Some(Self(parts[0], parts[1]))
+	}
+}
+
+impl fmt::Display for Version {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		if self.1 == 0 {
+			write!(f, "{}", self.0)
+		} else {
+			write!(f, "{}.{}", self.0, self.1)
+		}
+	}
+}
+
+/// The standards status of a feature.
+#[bitmask(u8)]
+pub enum CompatStatus {
+	/// The feature is discouraged, having been replaced by something else or removed from its specification.
+	Deprecated,
+	/// The feature's specification is not yet stable, so its behaviour may change.
+	Experimental,
+	/// The feature is not standard, and only exists with a vendor prefix.
+	VendorOnly,
+}
+
+/// What is known about the compatibility of a feature: its [CompatStatus], and the first version of each [Browser]
+/// to support it.
+///
+/// Compatibility data is embedded for a curated set of properties, value keywords, selectors and at-rules; those
+/// widely supported for a long time are mostly left out, so a feature with no data can be assumed to work anywhere.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Compat {
+	pub status: CompatStatus,
+	versions: [Option<Version>; 4],
+}
+
+impl Compat {
+	/// The data for the property with the given (lowercase) name, such as `aspect-ratio`.
+	pub fn property(name: &str) -> Option<&'static Compat> {
+		data::property(name)
+	}
+
+	/// The data for a keyword in the value of the given property, both lowercase, such as `contents` for `display`.
+	pub fn value(property: &str, keyword: &str) -> Option<&'static Compat> {
+		data::value(property, keyword)
+	}
+
+	/// The data for the pseudo class or element with the given (lowercase) name, including its colons but not any
+	/// arguments, such as `:has` or `::backdrop`.
+	pub fn selector(name: &str) -> Option<&'static Compat> {
+		data::selector(name)
+	}
+
+	/// The data for the at-rule with the given (lowercase) name, without the `@`, such as `container`.
+	pub fn at_rule(name: &str) -> Option<&'static Compat> {
+		data::at_rule(name)
+	}
+
+	/// The first version of the browser to support the feature, or None if it isn't supported at all.
+	pub fn version(&self, browser: Browser) -> Option<Version> {
+		self.versions[browser as usize]
+	}
+
+	/// The browsers of the [Targets] which don't support the feature, in the order of [Browser::ALL].
+	pub fn unsupported_by(&self, targets: &Targets) -> impl Iterator<Item = Browser> + '_ {
+		let targets = *targets;
+		Browser::ALL.into_iter().filter(move |browser| match (targets.version(*browser), self.version(*browser)) {
+			(Some(target), Some(version)) => target < version,
+			(Some(_), None) => true,
+			(None, _) => false,
+		})
+	}
+
+	pub fn is_supported_by(&self, targets: &Targets) -> bool {
+		self.unsupported_by(targets).next().is_none()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_version() {
+		assert_eq!(Version::parse("88"), Some(Version(88, 0)));
+		assert_eq!(Version::parse("15.4"), Some(Version(15, 4)));
+		assert_eq!(Version::parse(""), None);
+		assert_eq!(Version::parse(".4"), None);
+		assert_eq!(Version::parse("15."), None);
+		assert_eq!(Version::parse("15.4.1"), None);
+		assert_eq!(Version::parse("latest"), None);
+		assert!(Version(15, 4) > Version(15, 0));
+		assert_eq!(Version(15, 4).to_string(), "15.4");
+		assert_eq!(Version(88, 0).to_string(), "88");
+	}
+
+	#[test]
+	fn test_lookup() {
+		let has = Compat::selector(":has").unwrap();
+		assert_eq!(has.version(Browser::Firefox), Some(Version(121, 0)));
+		assert!(has.status.is_none());
+		assert!(Compat::property("page-break-after").unwrap().status.contains(CompatStatus::Deprecated));
+		assert!(Compat::selector("::-webkit-scrollbar").unwrap().status.contains(CompatStatus::VendorOnly));
+		assert!(Compat::value("display", "contents").is_some());
+		assert!(Compat::at_rule("container").is_some());
+		assert_eq!(Compat::property("color"), None);
+	}
+
+	#[test]
+	fn test_unsupported_by() {
+		let has = Compat::selector(":has").unwrap();
+		let targets = Targets::default().with_browser(Browser::Chrome, Version(110, 0));
+		assert!(has.is_supported_by(&targets));
+		let targets = targets.with_browser(Browser::Firefox, Version(115, 0));
+		assert_eq!(has.unsupported_by(&targets).collect::<Vec<_>>(), vec![Browser::Firefox]);
+		let scrollbar = Compat::selector("::-webkit-scrollbar").unwrap();
+		assert_eq!(scrollbar.unsupported_by(&targets).collect::<Vec<_>>(), vec![Browser::Firefox]);
+		assert!(scrollbar.is_supported_by(&Targets::default()));
+	}
+}
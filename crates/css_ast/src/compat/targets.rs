@@ -0,0 +1,87 @@
+use core::{fmt, str::FromStr};
+use miette::Diagnostic;
+use thiserror::Error;
+
+use super::{Browser, Version};
+
+#[derive(Debug, Error, Diagnostic, PartialEq)]
+#[error("'{0}' isn't a browser target")]
+#[diagnostic(
+	help("Targets are a comma separated list of browsers and their oldest version to support, like 'chrome 100, safari 15.4'"),
+	code(css_ast::InvalidTarget)
+)]
+pub struct InvalidTarget(pub String);
+
+/// The browsers a stylesheet needs to work in, each with the oldest version that needs to be supported. Browsers
+/// without a version aren't targeted at all, so by default nothing is.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Targets {
+	versions: [Option<Version>; 4],
+}
+
+impl Targets {
+	pub fn with_browser(mut self, browser: Browser, version: Version) -> Self {
+		self.versions[browser as usize] = Some(version);
+		self
+	}
+
+	pub fn version(&self, browser: Browser) -> Option<Version> {
+		self.versions[browser as usize]
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.versions.iter().all(Option::is_none)
+	}
+}
+
+// Targets are written like `chrome 100, firefox >= 115, safari 15.4`.
+impl FromStr for Targets {
+	type Err = InvalidTarget;
+
+	fn from_str(str: &str) -> Result<Self, Self::Err> {
+		let mut targets = Self::default();
+		for target in str.split(',').map(str::trim).filter(|target| !target.is_empty()) {
+			let (name, version) = target.split_once(' ').ok_or_else(|| InvalidTarget(target.into()))?;
+			let browser = Browser::from_name(&name.to_ascii_lowercase()).ok_or_else(|| InvalidTarget(target.into()))?;
+			let version = version.trim().trim_start_matches(">=").trim_start();
+			let version = Version::parse(version).ok_or_else(|| InvalidTarget(target.into()))?;
+			targets = targets.with_browser(browser, version);
+		}
+		Ok(targets)
+	}
+}
+
+impl fmt::Display for Targets {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let mut first = true;
+		for browser in Browser::ALL {
+			if let Some(version) = self.version(browser) {
+				if !first {
+					f.write_str(", ")?;
+				}
+				write!(f, "{} {}", browser, version)?;
+				first = false;
+			}
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_targets() {
+		let targets: Targets = "chrome 100, Firefox >= 115,safari 15.4".parse().unwrap();
+		assert_eq!(targets.version(Browser::Chrome), Some(Version(100, 0)));
+		assert_eq!(targets.version(Browser::Firefox), Some(Version(115, 0)));
+		assert_eq!(targets.version(Browser::Safari), Some(Version(15, 4)));
+		assert_eq!(targets.version(Browser::Edge), None);
+		assert_eq!(targets.to_string(), "chrome 100, firefox 115, safari 15.4");
+		assert!("".parse::<Targets>().unwrap().is_empty());
+		assert_eq!("netscape 4".parse::<Targets>(), Err(InvalidTarget("netscape 4".into())));
+		assert_eq!("chrome".parse::<Targets>(), Err(InvalidTarget("chrome".into())));
+		assert_eq!("chrome latest".parse::<Targets>(), Err(InvalidTarget("chrome latest".into())));
+	}
+}
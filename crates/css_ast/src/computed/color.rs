@@ -0,0 +1,313 @@
+use css_lexer::Cursor;
+
+use super::{ComputeContext, ToComputedValue};
+use crate::{
+	types::{AbsoluteColor, Channel, Color, ColorFunction, ColorSpaceKind, Hue, RelativeOrigin, SystemColor},
+	CalcNode,
+};
+
+/// A computed `<color>`.
+///
+/// Colors specified with the legacy sRGB syntaxes (hex, named colors, `rgb()`, `hsl()`, `hwb()`) compute to an sRGB
+/// [AbsoluteColor] with channels clamped to the `0.0..=1.0` range. Colors in other color spaces (`lab()`, `oklch()`,
+/// `color()` and so on) compute to an [AbsoluteColor] in that space. `color-mix()` and relative colors are resolved,
+/// and `currentcolor` is resolved from [`ComputeContext::color`]. System colors compute to themselves, as their value
+/// depends on the user agent.
+///
+/// Colors which cannot be resolved, such as a malformed hex color or a relative color whose origin is a system color,
+/// compute to [AbsoluteColor::TRANSPARENT].
+///
+/// https://drafts.csswg.org/css-color/#resolving-color-values
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type", rename_all = "kebab-case"))]
+pub enum ComputedColor {
+	Absolute(AbsoluteColor),
+	System(SystemColor),
+}
+
+impl ComputedColor {
+	pub const BLACK: ComputedColor = ComputedColor::Absolute(AbsoluteColor::BLACK);
+	pub const TRANSPARENT: ComputedColor = ComputedColor::Absolute(AbsoluteColor::TRANSPARENT);
+
+	pub fn from_rgba8(red: u8, green: u8, blue: u8, alpha: f32) -> Self {
+		Self::Absolute(AbsoluteColor::from_rgba8(red, green, blue, alpha))
+	}
+
+	/// The resolved color, or [None] for a system color.
+	pub fn absolute(&self) -> Option<AbsoluteColor> {
+		match self {
+			Self::Absolute(color) => Some(*color),
+			Self::System(_) => None,
+		}
+	}
+
+	fn from_hex(hex: &str) -> Option<Self> {
+		let digit = |i: usize| u8::from_str_radix(hex.get(i..i + 1)?, 16).ok();
+		let pair = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+		let (r, g, b, a) = match hex.len() {
+			3 => (digit(0)? * 17, digit(1)? * 17, digit(2)? * 17, 255),
+			4 => (digit(0)? * 17, digit(1)? * 17, digit(2)? * 17, digit(3)? * 17),
+			6 => (pair(0)?, pair(2)?, pair(4)?, 255),
+			8 => (pair(0)?, pair(2)?, pair(4)?, pair(6)?),
+			_ => return None,
+		};
+		Some(Self::from_rgba8(r, g, b, a as f32 / 255.0))
+	}
+}
+
+// Resolves the channels of a color function, which may refer to the channels of a relative color's origin by their
+// keywords. Channel values are resolved into the "number" form of the function's syntax, so `rgb()` channels range
+// over `0..=255`, and percentages resolve against the given reference range.
+// https://drafts.csswg.org/css-color-5/#relative-colors
+struct Channels<'c> {
+	ctx: &'c ComputeContext<'c>,
+	names: [&'static str; 3],
+	origin: Option<([f32; 3], f32)>,
+}
+
+impl<'c> Channels<'c> {
+	fn new(
+		ctx: &'c ComputeContext<'c>,
+		origin: &Option<&RelativeOrigin>,
+		space: ColorSpaceKind,
+		names: [&'static str; 3],
+		scale: f32,
+	) -> Option<Self> {
+		let origin = match origin {
+			Some(origin) => {
+				let color = origin.color.to_computed_value(ctx).absolute()?.to_space(space);
+				// Missing components of the origin resolve to zero.
+				// https://drafts.csswg.org/css-color-5/#relative-colors
+				Some((color.components.map(|c| c.unwrap_or(0.0) * scale), color.alpha.unwrap_or(0.0)))
+			}
+			None => None,
+		};
+		Some(Self { ctx, names, origin })
+	}
+
+	fn keyword(&self, name: &str) -> Option<f32> {
+		let (components, alpha) = self.origin?;
+		if name == "alpha" {
+			return Some(alpha);
+		}
+		self.names.iter().position(|n| *n == name).map(|i| components[i])
+	}
+
+	fn channel(&self, channel: &Channel, percent_range: f32) -> Option<f32> {
+		match channel {
+			Channel::None(_) => None,
+			Channel::Number(n) => Some((*n).into()),
+			Channel::Percent(p) => Some(f32::from(*p) * percent_range / 100.0),
+			Channel::Keyword(keyword) => self.keyword(keyword.name()),
+			Channel::Math(math) => CalcNode::from(*math)
+				.resolve_channels(&|name| self.keyword(name))
+				.evaluate(self.ctx, Some(percent_range)),
+		}
+	}
+
+	fn hue(&self, hue: &Hue) -> Option<f32> {
+		match hue {
+			Hue::None(_) => None,
+			Hue::Number(n) => Some((*n).into()),
+			Hue::Angle(angle) => Some(angle.to_computed_value(self.ctx)),
+			Hue::Keyword(keyword) => self.keyword(keyword.name()),
+			Hue::Math(math) => {
+				CalcNode::from(*math).resolve_channels(&|name| self.keyword(name)).evaluate(self.ctx, None)
+			}
+		}
+		.map(|hue| hue.rem_euclid(360.0))
+	}
+
+	// When omitted, alpha is 100% (or the origin's alpha for relative colors).
+	fn alpha(&self, alpha: &Option<Channel>) -> Option<f32> {
+		match alpha {
+			Some(alpha) => self.channel(alpha, 1.0).map(|alpha| alpha.clamp(0.0, 1.0)),
+			None => Some(self.origin.map_or(1.0, |(_, alpha)| alpha)),
+		}
+	}
+}
+
+// Legacy colors treat `none` as zero, and clamp into the sRGB gamut.
+// https://drafts.csswg.org/css-color/#rgb-functions
+fn legacy(color: AbsoluteColor) -> AbsoluteColor {
+	let srgb = color.to_space(ColorSpaceKind::Srgb);
+	AbsoluteColor {
+		space: ColorSpaceKind::Srgb,
+		components: srgb.components.map(|c| Some(c.unwrap_or(0.0).clamp(0.0, 1.0))),
+		alpha: Some(srgb.alpha.unwrap_or(0.0)),
+	}
+}
+
+fn resolve_function(function: &ColorFunction, ctx: &ComputeContext) -> Option<AbsoluteColor> {
+	use ColorSpaceKind as Space;
+	const RGB: [&str; 3] = ["r", "g", "b"];
+	const LAB: [&str; 3] = ["l", "a", "b"];
+	const LCH: [&str; 3] = ["l", "c", "h"];
+	Some(match function {
+		ColorFunction::Rgb(_, origin, r, _, g, _, b, _, _, a, _)
+		| ColorFunction::Rgba(_, origin, r, _, g, _, b, _, _, a, _) => {
+			let ch = Channels::new(ctx, origin, Space::Srgb, RGB, 255.0)?;
+			let channel = |c| ch.channel(c, 255.0).map(|c| c / 255.0);
+			legacy(AbsoluteColor {
+				space: Space::Srgb,
+				components: [channel(r), channel(g), channel(b)],
+				alpha: ch.alpha(a),
+			})
+		}
+		ColorFunction::Hsl(_, origin, h, _, s, _, l, _, _, a, _)
+		| ColorFunction::Hsla(_, origin, h, _, s, _, l, _, _, a, _) => {
+			let ch = Channels::new(ctx, origin, Space::Hsl, ["h", "s", "l"], 1.0)?;
+			let components = [ch.hue(h), ch.channel(s, 100.0), ch.channel(l, 100.0)];
+			legacy(AbsoluteColor { space: Space::Hsl, components, alpha: ch.alpha(a) })
+		}
+		ColorFunction::Hwb(_, origin, h, w, b, _, a, _) => {
+			let ch = Channels::new(ctx, origin, Space::Hwb, ["h", "w", "b"], 1.0)?;
+			let components = [ch.hue(h), ch.channel(w, 100.0), ch.channel(b, 100.0)];
+			legacy(AbsoluteColor { space: Space::Hwb, components, alpha: ch.alpha(a) })
+		}
+		// https://drafts.csswg.org/css-color/#specifying-lab-lch
+		ColorFunction::Lab(_, origin, l, a, b, _, alpha, _) => {
+			let ch = Channels::new(ctx, origin, Space::Lab, LAB, 1.0)?;
+			let l = ch.channel(l, 100.0).map(|l| l.clamp(0.0, 100.0));
+			let components = [l, ch.channel(a, 125.0), ch.channel(b, 125.0)];
+			AbsoluteColor { space: Space::Lab, components, alpha: ch.alpha(alpha) }
+		}
+		ColorFunction::Lch(_, origin, l, c, h, _, alpha, _) => {
+			let ch = Channels::new(ctx, origin, Space::Lch, LCH, 1.0)?;
+			let l = ch.channel(l, 100.0).map(|l| l.clamp(0.0, 100.0));
+			let components = [l, ch.channel(c, 150.0).map(|c| c.max(0.0)), ch.hue(h)];
+			AbsoluteColor { space: Space::Lch, components, alpha: ch.alpha(alpha) }
+		}
+		// https://drafts.csswg.org/css-color/#specifying-oklab-oklch
+		ColorFunction::Oklab(_, origin, l, a, b, _, alpha, _) => {
+			let ch = Channels::new(ctx, origin, Space::Oklab, LAB, 1.0)?;
+			let l = ch.channel(l, 1.0).map(|l| l.clamp(0.0, 1.0));
+			let components = [l, ch.channel(a, 0.4), ch.channel(b, 0.4)];
+			AbsoluteColor { space: Space::Oklab, components, alpha: ch.alpha(alpha) }
+		}
+		ColorFunction::Oklch(_, origin, l, c, h, _, alpha, _) => {
+			let ch = Channels::new(ctx, origin, Space::Oklch, LCH, 1.0)?;
+			let l = ch.channel(l, 1.0).map(|l| l.clamp(0.0, 1.0));
+			let components = [l, ch.channel(c, 0.4).map(|c| c.max(0.0)), ch.hue(h)];
+			AbsoluteColor { space: Space::Oklch, components, alpha: ch.alpha(alpha) }
+		}
+		// https://drafts.csswg.org/css-color/#color-function
+		ColorFunction::Color(_, origin, space, c0, c1, c2, _, alpha, _) => {
+			let space = Space::from(*space);
+			let names = if matches!(space, Space::XyzD50 | Space::XyzD65) { ["x", "y", "z"] } else { RGB };
+			let ch = Channels::new(ctx, origin, space, names, 1.0)?;
+			let components = [ch.channel(c0, 1.0), ch.channel(c1, 1.0), ch.channel(c2, 1.0)];
+			AbsoluteColor { space, components, alpha: ch.alpha(alpha) }
+		}
+	})
+}
+
+impl<'a> ToComputedValue for Color<'a> {
+	type ComputedValue = ComputedColor;
+
+	fn to_computed_value(&self, ctx: &ComputeContext) -> ComputedColor {
+		match self {
+			Self::Currentcolor(_) => ctx.color,
+			Self::Transparent(_) => ComputedColor::TRANSPARENT,
+			Self::System(system) => ComputedColor::System(*system),
+			Self::Named(named) => {
+				let (r, g, b) = named.to_rgb();
+				ComputedColor::from_rgba8(r, g, b, 1.0)
+			}
+			Self::Hex(hash) => {
+				let hex = Cursor::from(*hash).str_slice(ctx.source_text).trim_start_matches('#');
+				ComputedColor::from_hex(hex).unwrap_or(ComputedColor::TRANSPARENT)
+			}
+			Self::Function(function) => {
+				ComputedColor::Absolute(resolve_function(function, ctx).unwrap_or(AbsoluteColor::TRANSPARENT))
+			}
+			// https://drafts.csswg.org/css-color-5/#color-mix-result
+			Self::Mix(mix) => {
+				let first = mix.first.color.to_computed_value(ctx).absolute();
+				let second = mix.second.color.to_computed_value(ctx).absolute();
+				let percentages = (mix.first.percentage(), mix.second.percentage());
+				let (space, hue) = (mix.method.color_space(), mix.method.hue_interpolation());
+				let mixed = first.zip(second).and_then(|(first, second)| first.mix(&second, percentages, space, hue));
+				ComputedColor::Absolute(mixed.unwrap_or(AbsoluteColor::TRANSPARENT))
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use bumpalo::Bump;
+	use css_parse::Parser;
+
+	fn compute(source_text: &str, current_color: ComputedColor) -> ComputedColor {
+		let bump = Bump::default();
+		let color = Parser::new(&bump, source_text).parse_entirely::<Color>().output.unwrap();
+		let mut ctx = ComputeContext::new(source_text);
+		ctx.color = current_color;
+		color.to_computed_value(&ctx)
+	}
+
+	fn serialize(source_text: &str) -> String {
+		compute(source_text, ComputedColor::BLACK).absolute().unwrap().to_shortest_css()
+	}
+
+	#[test]
+	fn test_srgb_colors() {
+		let red = ComputedColor::from_rgba8(255, 0, 0, 1.0);
+		assert_eq!(compute("red", ComputedColor::BLACK), red);
+		assert_eq!(compute("#f00", ComputedColor::BLACK), red);
+		assert_eq!(compute("#ff0000ff", ComputedColor::BLACK), red);
+		assert_eq!(compute("rgb(255 0 0)", ComputedColor::BLACK), red);
+		assert_eq!(compute("rgba(100%,0%,0%,1)", ComputedColor::BLACK), red);
+		assert_eq!(compute("hsl(0deg 100% 50%)", ComputedColor::BLACK), red);
+		assert_eq!(compute("hsl(360 100 50)", ComputedColor::BLACK), red);
+		assert_eq!(compute("hwb(0 0% 0%)", ComputedColor::BLACK), red);
+		assert_eq!(compute("transparent", red), ComputedColor::TRANSPARENT);
+		assert_eq!(compute("rgb(0 0 0 / 50%)", red), ComputedColor::Absolute(AbsoluteColor::srgb(0.0, 0.0, 0.0, 0.5)));
+		assert_eq!(compute("rgb(none 0 0)", red), ComputedColor::BLACK);
+	}
+
+	#[test]
+	fn test_currentcolor() {
+		let red = ComputedColor::from_rgba8(255, 0, 0, 1.0);
+		assert_eq!(compute("currentcolor", red), red);
+	}
+
+	#[test]
+	fn test_other_color_spaces() {
+		assert_eq!(serialize("lab(63.673% 51.577 5.811)"), "lab(63.673 51.577 5.811)");
+		assert_eq!(serialize("oklch(50% 100% none / 0.5)"), "oklch(.5 .4 none/.5)");
+		assert_eq!(serialize("lch(50% 100% 30deg)"), "lch(50 150 30)");
+		assert_eq!(serialize("color(display-p3 100% 0 0.5)"), "color(display-p3 1 0 .5)");
+		assert_eq!(serialize("color(xyz 0.5 none 1)"), "color(xyz-d65 .5 none 1)");
+		assert!(matches!(compute("canvastext", ComputedColor::BLACK), ComputedColor::System(_)));
+	}
+
+	#[test]
+	fn test_relative_colors() {
+		assert_eq!(serialize("rgb(from red r g b)"), "red");
+		assert_eq!(serialize("rgb(from red b g r)"), "#00f");
+		assert_eq!(serialize("rgb(from #123456 r g b / 0.5)"), "rgb(18 52 86/.5)");
+		assert_eq!(serialize("rgb(from red calc(r / 2) g b)"), "rgb(127.5 0 0)");
+		assert_eq!(serialize("hsl(from red calc(h + 120) s l)"), "#0f0");
+		assert_eq!(serialize("oklab(from oklab(0.5 0.1 0.1) l a calc(b * -1) / alpha)"), "oklab(.5 .1 -.1)");
+		assert_eq!(serialize("lch(from lch(50 30 20) l c calc(h + 360))"), "lch(50 30 20)");
+		// The origin may itself be relative, or be currentcolor.
+		assert_eq!(serialize("rgb(from rgb(from blue b g r) r g b)"), "red");
+		let red = ComputedColor::from_rgba8(255, 0, 0, 1.0);
+		assert_eq!(compute("rgb(from currentcolor r r r)", red), ComputedColor::from_rgba8(255, 255, 255, 1.0));
+		assert_eq!(compute("rgb(from canvastext r g b)", red), ComputedColor::TRANSPARENT);
+	}
+
+	#[test]
+	fn test_color_mix() {
+		assert_eq!(serialize("color-mix(in srgb, red, blue)"), "rgb(127.5 0 127.5)");
+		assert_eq!(serialize("color-mix(in srgb, red 25%, blue)"), "rgb(63.75 0 191.25)");
+		assert_eq!(serialize("color-mix(in srgb, red 50%, transparent 50%)"), "rgb(255 0 0/.5)");
+		assert_eq!(serialize("color-mix(in hsl longer hue, red, lime)"), "#00f");
+		assert_eq!(serialize("color-mix(in lab, white, black)"), "lab(50 0 0)");
+		assert_eq!(serialize("color-mix(in srgb, color-mix(in srgb, red, blue), currentcolor)"), "rgb(63.75 0 63.75)");
+		assert_eq!(compute("color-mix(in srgb, red 0%, blue 0%)", ComputedColor::BLACK), ComputedColor::TRANSPARENT);
+	}
+}
@@ -0,0 +1,187 @@
+use css_lexer::{DimensionUnit, Token};
+
+use super::{ComputeContext, ToComputedValue};
+use crate::units::{
+	Angle, Length, LengthPercentage, Time, DEG_GRAD, DEG_RAD, DEG_TURN, PX_CM, PX_IN, PX_MM, PX_PC, PX_PT, PX_Q,
+};
+
+/// A computed `<length-percentage>`. Lengths are absolute (in `px`), while percentages are left for layout to
+/// resolve against whatever they refer to.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize),
+	serde(tag = "type", content = "value", rename_all = "kebab-case")
+)]
+pub enum ComputedLengthPercentage {
+	Length(f32),
+	Percent(f32),
+}
+
+fn to_px(token: Token, ctx: &ComputeContext) -> f32 {
+	length_to_px(token.value(), token.dimension_unit(), ctx)
+}
+
+// https://drafts.csswg.org/css-values/#lengths
+pub(crate) fn length_to_px(value: f32, unit: DimensionUnit, ctx: &ComputeContext) -> f32 {
+	let em = ctx.font_size;
+	let rem = ctx.root_font_size;
+	let metrics = ctx.font_metrics;
+	let (vw, vh) = (ctx.viewport_width, ctx.viewport_height);
+	match unit {
+		DimensionUnit::Px => value,
+		DimensionUnit::Cm => value * PX_CM,
+		DimensionUnit::Mm => value * PX_MM,
+		DimensionUnit::Q => value * PX_Q,
+		DimensionUnit::In => value * PX_IN,
+		DimensionUnit::Pc => value * PX_PC,
+		DimensionUnit::Pt => value * PX_PT,
+		DimensionUnit::Em => value * em,
+		DimensionUnit::Rem => value * rem,
+		DimensionUnit::Ex => value * em * metrics.x_height,
+		DimensionUnit::Rex => value * rem * metrics.x_height,
+		DimensionUnit::Cap => value * em * metrics.cap_height,
+		DimensionUnit::Rcap => value * rem * metrics.cap_height,
+		DimensionUnit::Ch => value * em * metrics.ch_width,
+		DimensionUnit::Rch => value * rem * metrics.ch_width,
+		DimensionUnit::Ic => value * em * metrics.ic_width,
+		DimensionUnit::Ric => value * rem * metrics.ic_width,
+		DimensionUnit::Lh => value * ctx.line_height,
+		DimensionUnit::Rlh => value * ctx.root_line_height,
+		// The small, large and dynamic viewport sizes are all considered to be the same size. Inline and block axes
+		// assume a horizontal writing mode. Container units fall back to the small viewport when there is no
+		// eligible query container, which is always the case here.
+		// https://drafts.csswg.org/css-contain-3/#container-lengths
+		DimensionUnit::Vw
+		| DimensionUnit::Svw
+		| DimensionUnit::Lvw
+		| DimensionUnit::Dvw
+		| DimensionUnit::Vi
+		| DimensionUnit::Svi
+		| DimensionUnit::Lvi
+		| DimensionUnit::Dvi
+		| DimensionUnit::Cqw
+		| DimensionUnit::Cqi => value * vw / 100.0,
+		DimensionUnit::Vh
+		| DimensionUnit::Svh
+		| DimensionUnit::Lvh
+		| DimensionUnit::Dvh
+		| DimensionUnit::Vb
+		| DimensionUnit::Svb
+		| DimensionUnit::Lvb
+		| DimensionUnit::Dvb
+		| DimensionUnit::Cqh
+		| DimensionUnit::Cqb => value * vh / 100.0,
+		DimensionUnit::Vmin
+		| DimensionUnit::Svmin
+		| DimensionUnit::Lvmin
+		| DimensionUnit::Dvmin
+		| DimensionUnit::Cqmin => value * vw.min(vh) / 100.0,
+		DimensionUnit::Vmax
+		| DimensionUnit::Svmax
+		| DimensionUnit::Lvmax
+		| DimensionUnit::Dvmax
+		| DimensionUnit::Cqmax => value * vw.max(vh) / 100.0,
+		// Unitless zero
+		_ => value,
+	}
+}
+
+impl ToComputedValue for Length {
+	type ComputedValue = f32;
+
+	fn to_computed_value(&self, ctx: &ComputeContext) -> f32 {
+		to_px(self.into(), ctx)
+	}
+}
+
+impl ToComputedValue for LengthPercentage {
+	type ComputedValue = ComputedLengthPercentage;
+
+	fn to_computed_value(&self, ctx: &ComputeContext) -> ComputedLengthPercentage {
+		match self {
+			Self::Percent(percent) => ComputedLengthPercentage::Percent((*percent).into()),
+			length => ComputedLengthPercentage::Length(to_px(length.into(), ctx)),
+		}
+	}
+}
+
+/// Angles compute to degrees.
+impl ToComputedValue for Angle {
+	type ComputedValue = f32;
+
+	fn to_computed_value(&self, _: &ComputeContext) -> f32 {
+		match self {
+			Self::Deg(deg) => (*deg).into(),
+			Self::Grad(grad) => f32::from(*grad) * DEG_GRAD,
+			Self::Rad(rad) => f32::from(*rad) * DEG_RAD,
+			Self::Turn(turn) => f32::from(*turn) * DEG_TURN,
+		}
+	}
+}
+
+/// Times compute to seconds.
+impl ToComputedValue for Time {
+	type ComputedValue = f32;
+
+	fn to_computed_value(&self, _: &ComputeContext) -> f32 {
+		match self {
+			Self::Zero(_) => 0.0,
+			Self::S(s) => (*s).into(),
+			Self::Ms(ms) => f32::from(*ms) / 1000.0,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use bumpalo::Bump;
+	use css_parse::Parser;
+
+	fn compute<'a, T: css_parse::Parse<'a> + css_parse::ToCursors + ToComputedValue>(
+		bump: &'a Bump,
+		ctx: &ComputeContext,
+		source_text: &'a str,
+	) -> T::ComputedValue {
+		let value = Parser::new(bump, source_text).parse_entirely::<T>().output.unwrap();
+		value.to_computed_value(ctx)
+	}
+
+	#[test]
+	fn test_absolute_lengths() {
+		let bump = Bump::default();
+		let ctx = ComputeContext::new("");
+		assert_eq!(compute::<Length>(&bump, &ctx, "10px"), 10.0);
+		assert_eq!(compute::<Length>(&bump, &ctx, "1in"), 96.0);
+		assert_eq!(compute::<Length>(&bump, &ctx, "72pt"), 96.0);
+		assert_eq!(compute::<Length>(&bump, &ctx, "6pc"), 96.0);
+		assert_eq!(compute::<Length>(&bump, &ctx, "2.54cm"), 96.0);
+		assert_eq!(compute::<Length>(&bump, &ctx, "0"), 0.0);
+	}
+
+	#[test]
+	fn test_relative_lengths() {
+		let bump = Bump::default();
+		let mut ctx = ComputeContext::new("").with_viewport(1000.0, 500.0).with_root_font_size(10.0);
+		ctx.font_size = 20.0;
+		assert_eq!(compute::<Length>(&bump, &ctx, "2em"), 40.0);
+		assert_eq!(compute::<Length>(&bump, &ctx, "2rem"), 20.0);
+		assert_eq!(compute::<Length>(&bump, &ctx, "1ex"), 10.0);
+		assert_eq!(compute::<Length>(&bump, &ctx, "10vw"), 100.0);
+		assert_eq!(compute::<Length>(&bump, &ctx, "10dvh"), 50.0);
+		assert_eq!(compute::<Length>(&bump, &ctx, "10vmax"), 100.0);
+		assert_eq!(compute::<Length>(&bump, &ctx, "10cqmin"), 50.0);
+		assert_eq!(compute::<LengthPercentage>(&bump, &ctx, "50%"), ComputedLengthPercentage::Percent(50.0));
+		assert_eq!(compute::<LengthPercentage>(&bump, &ctx, "1em"), ComputedLengthPercentage::Length(20.0));
+	}
+
+	#[test]
+	fn test_angles_and_times() {
+		let bump = Bump::default();
+		let ctx = ComputeContext::new("");
+		assert_eq!(compute::<Angle>(&bump, &ctx, "0.5turn"), 180.0);
+		assert_eq!(compute::<Angle>(&bump, &ctx, "100grad"), 90.0);
+		assert_eq!(compute::<Time>(&bump, &ctx, "250ms"), 0.25);
+	}
+}
@@ -0,0 +1,90 @@
+mod color;
+mod length;
+mod style;
+
+pub use color::*;
+pub use length::*;
+pub use style::*;
+
+// https://drafts.csswg.org/css-fonts/#absolute-size-mapping
+pub(crate) const MEDIUM_FONT_SIZE: f32 = 16.0;
+// https://drafts.csswg.org/css-inline/#valdef-line-height-normal
+pub(crate) const NORMAL_LINE_HEIGHT: f32 = 1.2;
+
+/// Ratios of the first available font's metrics to its em size, used to resolve `ex`, `cap`, `ch` & `ic` units.
+///
+/// The defaults are the fallbacks suggested in https://drafts.csswg.org/css-values/#font-relative-lengths for when
+/// font metrics cannot be determined.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct FontMetrics {
+	pub x_height: f32,
+	pub cap_height: f32,
+	pub ch_width: f32,
+	pub ic_width: f32,
+}
+
+impl Default for FontMetrics {
+	fn default() -> Self {
+		Self { x_height: 0.5, cap_height: 0.7, ch_width: 0.5, ic_width: 1.0 }
+	}
+}
+
+/// The environment needed to turn specified values into computed values.
+///
+/// Font-relative units resolve against `font_size`/`line_height` (and their `root_*` counterparts for `rem`, `rlh`
+/// etc.), viewport units against `viewport_width`/`viewport_height`, and `currentcolor` against `color`. The
+/// `source_text` is needed to read tokens (such as hex colors) back out of the AST.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ComputeContext<'a> {
+	pub source_text: &'a str,
+	pub font_size: f32,
+	pub root_font_size: f32,
+	pub line_height: f32,
+	pub root_line_height: f32,
+	pub viewport_width: f32,
+	pub viewport_height: f32,
+	pub font_metrics: FontMetrics,
+	pub color: ComputedColor,
+}
+
+impl<'a> ComputeContext<'a> {
+	/// Create a context with an initial font size of `medium`, a `normal` line height, black text, and a zero
+	/// sized viewport.
+	pub fn new(source_text: &'a str) -> Self {
+		Self {
+			source_text,
+			font_size: MEDIUM_FONT_SIZE,
+			root_font_size: MEDIUM_FONT_SIZE,
+			line_height: MEDIUM_FONT_SIZE * NORMAL_LINE_HEIGHT,
+			root_line_height: MEDIUM_FONT_SIZE * NORMAL_LINE_HEIGHT,
+			viewport_width: 0.0,
+			viewport_height: 0.0,
+			font_metrics: FontMetrics::default(),
+			color: ComputedColor::BLACK,
+		}
+	}
+
+	pub fn with_viewport(mut self, width: f32, height: f32) -> Self {
+		self.viewport_width = width;
+		self.viewport_height = height;
+		self
+	}
+
+	pub fn with_root_font_size(mut self, font_size: f32) -> Self {
+		self.root_font_size = font_size;
+		self.root_line_height = font_size * NORMAL_LINE_HEIGHT;
+		self
+	}
+
+	pub fn with_font_metrics(mut self, font_metrics: FontMetrics) -> Self {
+		self.font_metrics = font_metrics;
+		self
+	}
+}
+
+/// Resolve a specified value into its [computed value](https://drafts.csswg.org/css-cascade/#computed).
+pub trait ToComputedValue {
+	type ComputedValue;
+
+	fn to_computed_value(&self, ctx: &ComputeContext) -> Self::ComputedValue;
+}
@@ -0,0 +1,391 @@
+use std::collections::HashMap;
+
+use css_lexer::Cursor;
+
+use super::{
+	ComputeContext, ComputedColor, ComputedLengthPercentage, ToComputedValue, MEDIUM_FONT_SIZE, NORMAL_LINE_HEIGHT,
+};
+use crate::{values, AbsoluteSize, BaseType, CalcNode, Computed, MathFunction, Property, RelativeSize, StyleValue};
+
+/// The computed value of a single property.
+///
+/// Values which contain lengths or colors are resolved into their absolute, typed forms, as are math functions which
+/// evaluate to a length, percentage or number. Everything else (keywords, lists, custom properties, and values which
+/// cannot yet be resolved such as `var()` or `calc(100% - 1em)`) computes to the specified value.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize),
+	serde(tag = "type", content = "value", rename_all = "kebab-case")
+)]
+pub enum ComputedValue<'a> {
+	Length(f32),
+	LengthPercentage(ComputedLengthPercentage),
+	Number(f32),
+	Color(ComputedColor),
+	Specified(StyleValue<'a>),
+}
+
+impl<'a> ComputedValue<'a> {
+	pub fn new(value: &StyleValue<'a>, ctx: &ComputeContext) -> Self {
+		use values::*;
+		match value {
+			StyleValue::Color(ColorStyleValue(color))
+			| StyleValue::BackgroundColor(BackgroundColorStyleValue(color))
+			| StyleValue::ColumnRuleColor(ColumnRuleColorStyleValue(color))
+			| StyleValue::TextDecorationColor(TextDecorationColorStyleValue(color))
+			| StyleValue::TextEmphasisColor(TextEmphasisColorStyleValue(color))
+			| StyleValue::CaretColor(CaretColorStyleValue::Color(color))
+			| StyleValue::OutlineColor(OutlineColorStyleValue::Color(color))
+			| StyleValue::BorderTopColor(BorderTopColorStyleValue::Color(color))
+			| StyleValue::BorderRightColor(BorderRightColorStyleValue::Color(color))
+			| StyleValue::BorderBottomColor(BorderBottomColorStyleValue::Color(color))
+			| StyleValue::BorderLeftColor(BorderLeftColorStyleValue::Color(color))
+			| StyleValue::BorderBlockStartColor(BorderBlockStartColorStyleValue::Color(color))
+			| StyleValue::BorderBlockEndColor(BorderBlockEndColorStyleValue::Color(color))
+			| StyleValue::BorderInlineStartColor(BorderInlineStartColorStyleValue::Color(color))
+			| StyleValue::BorderInlineEndColor(BorderInlineEndColorStyleValue::Color(color)) => {
+				Self::Color(color.to_computed_value(ctx))
+			}
+			StyleValue::PaddingTop(PaddingTopStyleValue(lp))
+			| StyleValue::PaddingRight(PaddingRightStyleValue(lp))
+			| StyleValue::PaddingBottom(PaddingBottomStyleValue(lp))
+			| StyleValue::PaddingLeft(PaddingLeftStyleValue(lp))
+			| StyleValue::MarginTop(MarginTopStyleValue::LengthPercentage(lp))
+			| StyleValue::MarginRight(MarginRightStyleValue::LengthPercentage(lp))
+			| StyleValue::MarginBottom(MarginBottomStyleValue::LengthPercentage(lp))
+			| StyleValue::MarginLeft(MarginLeftStyleValue::LengthPercentage(lp))
+			| StyleValue::Top(TopStyleValue::LengthPercentage(lp))
+			| StyleValue::Right(RightStyleValue::LengthPercentage(lp))
+			| StyleValue::Bottom(BottomStyleValue::LengthPercentage(lp))
+			| StyleValue::Left(LeftStyleValue::LengthPercentage(lp))
+			| StyleValue::Width(WidthStyleValue::LengthPercentage(lp))
+			| StyleValue::Height(HeightStyleValue::LengthPercentage(lp))
+			| StyleValue::MinWidth(MinWidthStyleValue::LengthPercentage(lp))
+			| StyleValue::MinHeight(MinHeightStyleValue::LengthPercentage(lp))
+			| StyleValue::MaxWidth(MaxWidthStyleValue::LengthPercentage(lp))
+			| StyleValue::MaxHeight(MaxHeightStyleValue::LengthPercentage(lp)) => {
+				Self::LengthPercentage(lp.to_computed_value(ctx))
+			}
+			StyleValue::OutlineOffset(OutlineOffsetStyleValue(length))
+			| StyleValue::ColumnWidth(ColumnWidthStyleValue::Length(length)) => Self::Length(length.to_computed_value(ctx)),
+			// https://drafts.csswg.org/css-inline/#line-height-property
+			StyleValue::LineHeight(LineHeightStyleValue::Number(number)) => Self::Number((*number).into()),
+			StyleValue::LineHeight(LineHeightStyleValue::LengthPercentage(lp)) => {
+				Self::Length(match lp.to_computed_value(ctx) {
+					ComputedLengthPercentage::Length(px) => px,
+					ComputedLengthPercentage::Percent(percent) => ctx.font_size * percent / 100.0,
+				})
+			}
+			StyleValue::Computed(Computed::Math(math)) => {
+				compute_math(math, ctx).unwrap_or_else(|| Self::Specified(value.clone()))
+			}
+			value => Self::Specified(value.clone()),
+		}
+	}
+}
+
+// Math functions which mix percentages with other units need the percentage basis, which is only known at layout
+// time, so only those which are entirely a length, a percentage or a number are computed.
+fn compute_math<'a>(math: &MathFunction, ctx: &ComputeContext) -> Option<ComputedValue<'a>> {
+	let node = CalcNode::from(math);
+	let ty = node.resolve_type()?;
+	let value = node.evaluate(ctx, None)?;
+	if ty.matches(BaseType::Length) {
+		Some(ComputedValue::Length(value))
+	} else if ty.matches(BaseType::Percent) {
+		Some(ComputedValue::LengthPercentage(ComputedLengthPercentage::Percent(value)))
+	} else if ty.is_number() {
+		Some(ComputedValue::Number(value))
+	} else {
+		None
+	}
+}
+
+// How a cascaded value should be resolved, after taking CSS-wide keywords into account.
+enum Resolved<'b, 'a> {
+	Value(&'b StyleValue<'a>),
+	Inherit,
+	Initial,
+}
+
+// https://drafts.csswg.org/css-cascade/#defaulting-keywords
+// There is no user or user-agent origin to roll back to, so `revert` & `revert-layer` behave like `unset`.
+fn resolve<'b, 'a>(name: &str, value: &'b StyleValue<'a>) -> Resolved<'b, 'a> {
+	match value {
+		StyleValue::Inherit(_) => Resolved::Inherit,
+		StyleValue::Initial(_) => Resolved::Initial,
+		StyleValue::Unset(_) | StyleValue::Revert(_) | StyleValue::RevertLayer(_) => {
+			if StyleValue::inherits_by_name(name) {
+				Resolved::Inherit
+			} else {
+				Resolved::Initial
+			}
+		}
+		value => Resolved::Value(value),
+	}
+}
+
+// https://drafts.csswg.org/css-fonts/#absolute-size-mapping
+fn absolute_font_size(size: &AbsoluteSize) -> f32 {
+	MEDIUM_FONT_SIZE
+		* match size {
+			AbsoluteSize::XxSmall(_) => 3.0 / 5.0,
+			AbsoluteSize::XSmall(_) => 3.0 / 4.0,
+			AbsoluteSize::Small(_) => 8.0 / 9.0,
+			AbsoluteSize::Medium(_) => 1.0,
+			AbsoluteSize::Large(_) => 6.0 / 5.0,
+			AbsoluteSize::XLarge(_) => 3.0 / 2.0,
+			AbsoluteSize::XxLarge(_) => 2.0,
+			AbsoluteSize::XxxLarge(_) => 3.0,
+		}
+}
+
+// Font-relative units and percentages in `font-size` refer to the parent's font, so `ctx` here is the parent's.
+fn compute_font_size(value: &StyleValue, ctx: &ComputeContext) -> f32 {
+	match value {
+		StyleValue::FontSize(values::FontSizeStyleValue::AbsoluteSize(size)) => absolute_font_size(size),
+		// https://drafts.csswg.org/css-fonts/#valdef-font-size-relative-size
+		StyleValue::FontSize(values::FontSizeStyleValue::RelativeSize(RelativeSize::Larger(_))) => ctx.font_size * 1.2,
+		StyleValue::FontSize(values::FontSizeStyleValue::RelativeSize(RelativeSize::Smaller(_))) => ctx.font_size / 1.2,
+		StyleValue::FontSize(values::FontSizeStyleValue::LengthPercentage(lp)) => match lp.to_computed_value(ctx) {
+			ComputedLengthPercentage::Length(px) => px,
+			ComputedLengthPercentage::Percent(percent) => ctx.font_size * percent / 100.0,
+		},
+		StyleValue::Computed(Computed::Math(math)) => {
+			let node = CalcNode::from(math);
+			let ty = node.resolve_type();
+			if ty.is_some_and(|ty| ty.matches(BaseType::Length) || ty.matches_percentage(BaseType::Length)) {
+				node.evaluate(ctx, Some(ctx.font_size)).unwrap_or(ctx.font_size)
+			} else {
+				ctx.font_size
+			}
+		}
+		_ => ctx.font_size,
+	}
+}
+
+/// The computed style of a single element: the result of cascading a list of declarations, resolving CSS-wide
+/// keywords, inheriting from a parent style, and computing each value.
+///
+/// Properties absent from a `ComputedStyle` have their initial value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComputedStyle<'a> {
+	values: HashMap<String, ComputedValue<'a>>,
+	font_size: f32,
+	line_height: f32,
+	color: ComputedColor,
+}
+
+impl Default for ComputedStyle<'_> {
+	fn default() -> Self {
+		Self {
+			values: HashMap::new(),
+			font_size: MEDIUM_FONT_SIZE,
+			line_height: MEDIUM_FONT_SIZE * NORMAL_LINE_HEIGHT,
+			// The initial value of `color` is `CanvasText`, assumed here to be black.
+			color: ComputedColor::BLACK,
+		}
+	}
+}
+
+impl<'a> ComputedStyle<'a> {
+	/// Compute the style for an element given the declarations that apply to it, in cascade order (later
+	/// declarations win, unless an earlier one is `!important`), and the computed style of its parent (or `None` for
+	/// the root element).
+	///
+	/// The viewport and root font sizes are taken from `ctx`; the font size, line height & color used to resolve
+	/// relative values come from the parent style.
+	pub fn compute<'b>(
+		declarations: impl IntoIterator<Item = &'b Property<'a>>,
+		parent: Option<&ComputedStyle<'a>>,
+		ctx: &ComputeContext,
+	) -> Self
+	where
+		'a: 'b,
+	{
+		let default = ComputedStyle::default();
+		let parent_style = parent.unwrap_or(&default);
+		let parent_ctx = ComputeContext {
+			font_size: parent_style.font_size,
+			line_height: parent_style.line_height,
+			color: parent_style.color,
+			..*ctx
+		};
+
+		// https://drafts.csswg.org/css-cascade/#cascade-sort
+		let mut cascaded: HashMap<String, &'b Property<'a>> = HashMap::new();
+		for property in declarations {
+			let name = Cursor::from(property.name).str_slice(ctx.source_text).to_ascii_lowercase();
+			if cascaded.get(&name).is_some_and(|prev| prev.important.is_some() && property.important.is_none()) {
+				continue;
+			}
+			cascaded.insert(name, property);
+		}
+		let declared = |name: &str| cascaded.get(name).map(|property| resolve(name, &property.value));
+		let inherited = |name: &str| parent.and_then(|parent| parent.values.get(name)).cloned();
+
+		let mut style = Self { values: HashMap::new(), ..default.clone() };
+
+		// `font-size` and `color` must be computed first, as other properties are relative to them.
+		style.font_size = match declared("font-size") {
+			Some(Resolved::Value(value)) => compute_font_size(value, &parent_ctx),
+			Some(Resolved::Initial) => MEDIUM_FONT_SIZE,
+			_ => parent_style.font_size,
+		};
+		style.color = match declared("color") {
+			Some(Resolved::Value(StyleValue::Color(values::ColorStyleValue(color)))) => {
+				color.to_computed_value(&parent_ctx)
+			}
+			Some(Resolved::Initial) => default.color,
+			_ => parent_style.color,
+		};
+		// Line height depends on the font size, and in turn the `lh` unit depends on the line height.
+		let line_height = match declared("line-height") {
+			Some(Resolved::Value(value)) => {
+				Some(ComputedValue::new(value, &ComputeContext { font_size: style.font_size, ..parent_ctx }))
+			}
+			Some(Resolved::Initial) => None,
+			_ => inherited("line-height"),
+		};
+		style.line_height = match line_height {
+			Some(ComputedValue::Length(px)) => px,
+			Some(ComputedValue::Number(number)) => style.font_size * number,
+			_ => style.font_size * NORMAL_LINE_HEIGHT,
+		};
+		let element_ctx =
+			ComputeContext { font_size: style.font_size, line_height: style.line_height, color: style.color, ..*ctx };
+
+		for (name, property) in &cascaded {
+			let value = match resolve(name, &property.value) {
+				Resolved::Value(_) if name == "font-size" => Some(ComputedValue::Length(style.font_size)),
+				Resolved::Value(_) if name == "color" => Some(ComputedValue::Color(style.color)),
+				Resolved::Value(value) => Some(ComputedValue::new(value, &element_ctx)),
+				Resolved::Inherit => inherited(name),
+				Resolved::Initial => None,
+			};
+			if let Some(value) = value {
+				style.values.insert(name.clone(), value);
+			}
+		}
+		if let Some(parent) = parent {
+			for (name, value) in &parent.values {
+				if !cascaded.contains_key(name) && StyleValue::inherits_by_name(name) {
+					style.values.insert(name.clone(), value.clone());
+				}
+			}
+		}
+		style
+	}
+
+	/// The computed value of the named property, or `None` if it has its initial value.
+	pub fn get(&self, name: &str) -> Option<&ComputedValue<'a>> {
+		self.values.get(&name.to_ascii_lowercase())
+	}
+
+	pub fn font_size(&self) -> f32 {
+		self.font_size
+	}
+
+	pub fn line_height(&self) -> f32 {
+		self.line_height
+	}
+
+	pub fn color(&self) -> ComputedColor {
+		self.color
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::StyleRule;
+	use bumpalo::Bump;
+	use css_parse::Parser;
+
+	fn compute<'a>(
+		bump: &'a Bump,
+		source_text: &'a str,
+		parent: Option<&ComputedStyle<'a>>,
+		ctx: &ComputeContext,
+	) -> ComputedStyle<'a> {
+		let rule = Parser::new(bump, source_text).parse_entirely::<StyleRule>().output.unwrap();
+		let declarations = bump.alloc(rule.style.declarations);
+		ComputedStyle::compute(declarations.iter().map(|(property, _)| property), parent, ctx)
+	}
+
+	#[test]
+	fn test_font_relative_lengths() {
+		let bump = Bump::default();
+		let source_text = "a{font-size:2em;padding-top:2em;margin-left:1rem;line-height:1.5;width:2lh}";
+		let ctx = ComputeContext::new(source_text).with_root_font_size(10.0);
+		let style = compute(&bump, source_text, None, &ctx);
+		assert_eq!(style.font_size(), 32.0);
+		assert_eq!(style.line_height(), 48.0);
+		assert_eq!(
+			style.get("padding-top"),
+			Some(&ComputedValue::LengthPercentage(ComputedLengthPercentage::Length(64.0)))
+		);
+		assert_eq!(
+			style.get("margin-left"),
+			Some(&ComputedValue::LengthPercentage(ComputedLengthPercentage::Length(10.0)))
+		);
+		assert_eq!(style.get("width"), Some(&ComputedValue::LengthPercentage(ComputedLengthPercentage::Length(96.0))));
+		assert_eq!(style.get("line-height"), Some(&ComputedValue::Number(1.5)));
+	}
+
+	#[test]
+	fn test_inheritance() {
+		let bump = Bump::default();
+		let parent_source = "a{font-size:20px;color:red;padding-top:5px;line-height:1.25}";
+		let ctx = ComputeContext::new(parent_source);
+		let parent = compute(&bump, parent_source, None, &ctx);
+		let child_source = "b{font-size:50%;padding-left:inherit;margin-top:inherit;padding-top:unset}";
+		let ctx = ComputeContext::new(child_source);
+		let child = compute(&bump, child_source, Some(&parent), &ctx);
+		assert_eq!(child.font_size(), 10.0);
+		// `color` & `line-height` are inherited properties
+		assert_eq!(child.color(), ComputedColor::from_rgba8(255, 0, 0, 1.0));
+		assert_eq!(child.get("line-height"), Some(&ComputedValue::Number(1.25)));
+		assert_eq!(child.line_height(), 12.5);
+		// `padding-top` is not inherited, so `unset` leaves it at its initial value
+		assert_eq!(child.get("padding-top"), None);
+		assert_eq!(child.get("margin-top"), None);
+		// `padding-left` is explicitly inherited, but the parent has its initial value
+		assert_eq!(child.get("padding-left"), None);
+	}
+
+	#[test]
+	fn test_currentcolor_and_keywords() {
+		let bump = Bump::default();
+		let parent_source = "a{color:#00f}";
+		let ctx = ComputeContext::new(parent_source);
+		let parent = compute(&bump, parent_source, None, &ctx);
+		let child_source =
+			"b{color:currentcolor;background-color:currentcolor;color:initial!important;font-size:larger}";
+		let ctx = ComputeContext::new(child_source);
+		let child = compute(&bump, child_source, Some(&parent), &ctx);
+		assert_eq!(child.color(), ComputedColor::BLACK);
+		assert_eq!(child.get("color"), None);
+		assert_eq!(child.get("background-color"), Some(&ComputedValue::Color(ComputedColor::BLACK)));
+		assert_eq!(child.font_size(), 19.2);
+	}
+
+	#[test]
+	fn test_math_functions() {
+		let bump = Bump::default();
+		let parent_source = "a{font-size:20px}";
+		let ctx = ComputeContext::new(parent_source);
+		let parent = compute(&bump, parent_source, None, &ctx);
+		let child_source = "b{font-size:calc(50% + 2px);padding-top:calc(1em + 4px);width:min(10%, 20%);\
+			line-height:calc(1.5 * 2);height:calc(100% - 1em);z-index:calc(1 + var(--x))}";
+		let ctx = ComputeContext::new(child_source).with_viewport(1000.0, 500.0);
+		let child = compute(&bump, child_source, Some(&parent), &ctx);
+		assert_eq!(child.font_size(), 12.0);
+		assert_eq!(child.get("padding-top"), Some(&ComputedValue::Length(16.0)));
+		assert_eq!(child.get("width"), Some(&ComputedValue::LengthPercentage(ComputedLengthPercentage::Percent(10.0))));
+		assert_eq!(child.get("line-height"), Some(&ComputedValue::Number(3.0)));
+		assert!(matches!(child.get("height"), Some(ComputedValue::Specified(_))));
+		assert!(matches!(child.get("z-index"), Some(ComputedValue::Specified(_))));
+	}
+}
@@ -0,0 +1,580 @@
+//! Structural comparison of two [StyleSheets][StyleSheet], for reviewing what changed between two builds of a
+//! stylesheet.
+//!
+//! Rules are matched by their selector list or at-rule prelude, and declarations within them by property name. Where
+//! more than one rule (or declaration) share a key, they are matched in the order they appear. Everything is compared
+//! by its tokens rather than its text, so changes to whitespace (other than where it is significant, such as a
+//! descendant combinator) or comments aren't reported.
+
+use crate::{
+	ContainerRule, DocumentRule, FontFaceRule, Keyframe, KeyframesRule, LayerRule, MarginRule, MediaRule,
+	MozDocumentRule, NestedGroupRule, OptionalLayerRuleBlock, PageRule, Property, PropertyRule, Rule, StyleRule,
+	StyleSheet, SupportsRule, WebkitKeyframesRule,
+};
+use css_lexer::{Cursor, Kind, SourceOffset, Span, Token};
+use css_parse::{syntax, ToCursors, T};
+use std::collections::{HashMap, VecDeque};
+
+/// How a rule or declaration differs between the two stylesheets.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(rename_all = "kebab-case"))]
+pub enum ChangeKind {
+	/// Only in the second stylesheet.
+	Added,
+	/// Only in the first stylesheet.
+	Removed,
+	/// In both stylesheets, but different.
+	Changed,
+}
+
+/// The differences between two stylesheets. An empty diff means the two are equivalent.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct StyleSheetDiff {
+	pub rules: Vec<RuleChange>,
+}
+
+/// A rule which differs between the two stylesheets.
+///
+/// A [ChangeKind::Changed] rule has the same key in both, and lists the declarations & nested rules within it that
+/// differ. [ChangeKind::Added] and [ChangeKind::Removed] rules list nothing within them.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct RuleChange {
+	pub kind: ChangeKind,
+	/// The selector list or at-rule prelude (for example `.a > b` or `@media (min-width:10px)`). Rules without a block
+	/// (such as `@import`) are keyed by the entire rule.
+	pub key: String,
+	/// Where the rule is in the first stylesheet.
+	pub before: Option<Span>,
+	/// Where the rule is in the second stylesheet.
+	pub after: Option<Span>,
+	pub declarations: Vec<DeclarationChange>,
+	pub rules: Vec<RuleChange>,
+}
+
+/// A declaration which differs between the two stylesheets.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct DeclarationChange {
+	pub kind: ChangeKind,
+	/// The property name, lowercased unless it is a custom property.
+	pub name: String,
+	/// Where the declaration is in the first stylesheet.
+	pub before: Option<Span>,
+	/// Where the declaration is in the second stylesheet.
+	pub after: Option<Span>,
+	/// The value (including any `!important`) in the first stylesheet.
+	pub before_value: Option<String>,
+	/// The value (including any `!important`) in the second stylesheet.
+	pub after_value: Option<String>,
+}
+
+impl StyleSheetDiff {
+	/// Compare `before` (parsed from `before_source`) with `after` (parsed from `after_source`).
+	pub fn new(before: &StyleSheet, before_source: &str, after: &StyleSheet, after_source: &str) -> Self {
+		let before_rules: Vec<_> = before.rules.iter().map(|rule| rule.item(before_source)).collect();
+		let after_rules: Vec<_> = after.rules.iter().map(|rule| rule.item(after_source)).collect();
+		Self { rules: diff_rules(&before_rules, &after_rules) }
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.rules.is_empty()
+	}
+}
+
+// The cursors of a node, dropping comments, as they never make a difference.
+fn cursors(node: &impl ToCursors) -> Vec<Cursor> {
+	let mut cursors: Vec<Cursor> = vec![];
+	node.to_cursors(&mut cursors);
+	cursors.retain(|c| *c != Kind::Comment);
+	cursors
+}
+
+// The text of the given cursors, with any run of whitespace collapsed to a single space, and leading & trailing
+// whitespace removed.
+fn text(cursors: &[Cursor], source: &str) -> String {
+	let mut str = String::new();
+	let mut last: Option<Token> = None;
+	let mut space = false;
+	for c in cursors {
+		if *c == Kind::Whitespace {
+			space = last.is_some();
+			continue;
+		}
+		if space || last.is_some_and(|last| last.needs_separator_for(c.token())) {
+			str.push(' ');
+		}
+		space = false;
+		last = Some(c.token());
+		c.write_str(source, &mut str).unwrap();
+	}
+	str
+}
+
+fn span(cursors: &[Cursor]) -> Option<Span> {
+	let mut iter = cursors.iter().filter(|c| c.offset() != SourceOffset::DUMMY);
+	let first = iter.next()?.span();
+	Some(iter.last().map_or(first, |last| first + last.span()))
+}
+
+struct RuleItem {
+	key: String,
+	span: Option<Span>,
+	// The declarations & rules within the block, or `None` for rules without a block.
+	block: Option<BlockItems>,
+}
+
+impl RuleItem {
+	// A rule with a block is keyed by its tokens before the block opens, otherwise by the entire rule.
+	fn new(rule: &impl ToCursors, block: Option<(Cursor, BlockItems)>, source: &str) -> Self {
+		let cursors = cursors(rule);
+		let span = span(&cursors);
+		match block {
+			Some((open, block)) => {
+				let open = cursors.iter().position(|c| c.offset() == open.offset()).unwrap_or(cursors.len());
+				Self { key: text(&cursors[..open], source), span, block: Some(block) }
+			}
+			None => Self { key: text(&cursors, source), span, block: None },
+		}
+	}
+}
+
+struct DeclarationItem {
+	name: String,
+	span: Option<Span>,
+	value: String,
+}
+
+impl DeclarationItem {
+	// The value is everything after the colon, including any `!important`.
+	fn new(declaration: &impl ToCursors, name: Cursor, colon: Cursor, source: &str) -> Self {
+		let cursors = cursors(declaration);
+		let colon = cursors.iter().position(|c| c.offset() == colon.offset()).map_or(cursors.len(), |i| i + 1);
+		let mut name = name.str_slice(source).to_owned();
+		if !name.starts_with("--") {
+			name.make_ascii_lowercase();
+		}
+		Self { name, span: span(&cursors), value: text(&cursors[colon..], source) }
+	}
+}
+
+struct BlockItems {
+	declarations: Vec<DeclarationItem>,
+	rules: Vec<RuleItem>,
+}
+
+impl BlockItems {
+	fn new<'a: 'b, 'b>(
+		properties: impl IntoIterator<Item = &'b (Property<'a>, Option<T![;]>)>,
+		rules: Vec<RuleItem>,
+		source: &str,
+	) -> Self {
+		let declarations = properties
+			.into_iter()
+			.map(|(property, _)| DeclarationItem::new(property, property.name.into(), property.colon.into(), source))
+			.collect();
+		Self { declarations, rules }
+	}
+
+	fn rules<'b, R: Item + 'b>(rules: impl IntoIterator<Item = &'b R>, source: &str) -> Self {
+		Self { declarations: vec![], rules: rules.into_iter().map(|rule| rule.item(source)).collect() }
+	}
+}
+
+// Each kind of rule, read into the key it is matched by and the declarations & rules within its block.
+trait Item {
+	fn item(&self, source: &str) -> RuleItem;
+}
+
+impl Item for Rule<'_> {
+	fn item(&self, source: &str) -> RuleItem {
+		match self {
+			Self::Style(rule) => rule.item(source),
+			Self::ContainerRule(rule) => rule.item(source),
+			Self::DocumentRule(rule) => rule.item(source),
+			Self::FontFaceRule(rule) => rule.item(source),
+			Self::KeyframesRule(rule) => rule.item(source),
+			Self::LayerRule(rule) => rule.item(source),
+			Self::MediaRule(rule) => rule.item(source),
+			Self::MozDocumentRule(rule) => rule.item(source),
+			Self::PageRule(rule) => rule.item(source),
+			Self::PropertyRule(rule) => rule.item(source),
+			Self::SupportsRule(rule) => rule.item(source),
+			Self::WebkitKeyframesRule(rule) => rule.item(source),
+			Self::UnknownAt(rule) => rule.0.item(source),
+			Self::Unknown(rule) => rule.0.item(source),
+			rule => RuleItem::new(rule, None, source),
+		}
+	}
+}
+
+impl Item for NestedGroupRule<'_> {
+	fn item(&self, source: &str) -> RuleItem {
+		match self {
+			Self::Style(rule) => rule.item(source),
+			Self::ContainerRule(rule) => rule.item(source),
+			Self::LayerRule(rule) => rule.item(source),
+			Self::MediaRule(rule) => rule.item(source),
+			Self::SupportsRule(rule) => rule.item(source),
+			Self::UnknownAt(rule) => rule.0.item(source),
+			Self::Unknown(rule) => rule.0.item(source),
+			rule => RuleItem::new(rule, None, source),
+		}
+	}
+}
+
+impl Item for StyleRule<'_> {
+	fn item(&self, source: &str) -> RuleItem {
+		let rules = self.style.rules.iter().map(|rule| rule.item(source)).collect();
+		let block = BlockItems::new(&self.style.declarations, rules, source);
+		RuleItem::new(self, Some((self.style.open.into(), block)), source)
+	}
+}
+
+impl Item for MediaRule<'_> {
+	fn item(&self, source: &str) -> RuleItem {
+		let rules = self.block.rules.iter().map(|rule| rule.item(source)).collect();
+		let block = BlockItems::new(&self.block.properties, rules, source);
+		RuleItem::new(self, Some((self.block.open.into(), block)), source)
+	}
+}
+
+impl Item for PageRule<'_> {
+	fn item(&self, source: &str) -> RuleItem {
+		let rules = self.block.rules.iter().map(|rule| rule.item(source)).collect();
+		let block = BlockItems::new(&self.block.properties, rules, source);
+		RuleItem::new(self, Some((self.block.open.into(), block)), source)
+	}
+}
+
+impl Item for MarginRule<'_> {
+	fn item(&self, source: &str) -> RuleItem {
+		let block = BlockItems::new(&self.block.properties, vec![], source);
+		RuleItem::new(self, Some((self.block.open.into(), block)), source)
+	}
+}
+
+impl Item for Keyframe<'_> {
+	fn item(&self, source: &str) -> RuleItem {
+		let block = BlockItems::new(&self.block.properties, vec![], source);
+		RuleItem::new(self, Some((self.block.open.into(), block)), source)
+	}
+}
+
+impl Item for FontFaceRule<'_> {
+	fn item(&self, source: &str) -> RuleItem {
+		let declarations = self
+			.block
+			.properties
+			.iter()
+			.map(|property| DeclarationItem::new(property, property.name.into(), property.colon.into(), source));
+		let block = BlockItems { declarations: declarations.collect(), rules: vec![] };
+		RuleItem::new(self, Some((self.block.open.into(), block)), source)
+	}
+}
+
+impl Item for PropertyRule<'_> {
+	fn item(&self, source: &str) -> RuleItem {
+		let declarations =
+			self.block.properties.iter().map(|(property, _)| {
+				DeclarationItem::new(property, property.name.into(), property.colon.into(), source)
+			});
+		let block = BlockItems { declarations: declarations.collect(), rules: vec![] };
+		RuleItem::new(self, Some((self.block.open.into(), block)), source)
+	}
+}
+
+impl Item for LayerRule<'_> {
+	fn item(&self, source: &str) -> RuleItem {
+		match &self.block {
+			OptionalLayerRuleBlock::Block(block) => {
+				RuleItem::new(self, Some((block.open.into(), BlockItems::rules(&block.rules, source))), source)
+			}
+			OptionalLayerRuleBlock::None(_) => RuleItem::new(self, None, source),
+		}
+	}
+}
+
+macro_rules! rule_list_items {
+	( $( $name: ident: $rules: ident, )+ ) => {
+		$(
+			impl Item for $name<'_> {
+				fn item(&self, source: &str) -> RuleItem {
+					let block = BlockItems::rules(&self.block.$rules, source);
+					RuleItem::new(self, Some((self.block.open.into(), block)), source)
+				}
+			}
+		)+
+	};
+}
+
+rule_list_items! {
+	ContainerRule: rules,
+	DocumentRule: rules,
+	KeyframesRule: keyframes,
+	MozDocumentRule: rules,
+	SupportsRule: rules,
+	WebkitKeyframesRule: keyframes,
+}
+
+// Rules which aren't understood are still compared by the declarations & rules within their block.
+impl Item for syntax::Rule<'_> {
+	fn item(&self, source: &str) -> RuleItem {
+		match self {
+			Self::AtRule(rule) => rule.item(source),
+			Self::QualifiedRule(rule) => rule.item(source),
+			Self::BadRule(rule) => RuleItem::new(rule, None, source),
+		}
+	}
+}
+
+impl Item for syntax::AtRule<'_> {
+	fn item(&self, source: &str) -> RuleItem {
+		match &self.block {
+			syntax::OptionalBlock::Block(block) => block_item(self, block, source),
+			syntax::OptionalBlock::None(_) => RuleItem::new(self, None, source),
+		}
+	}
+}
+
+impl Item for syntax::QualifiedRule<'_> {
+	fn item(&self, source: &str) -> RuleItem {
+		block_item(self, &self.block, source)
+	}
+}
+
+fn block_item(rule: &impl ToCursors, block: &syntax::Block, source: &str) -> RuleItem {
+	let declarations = block.declarations.iter().map(|(declaration, _)| {
+		DeclarationItem::new(declaration, declaration.name.into(), declaration.colon.into(), source)
+	});
+	let rules = block.rules.iter().map(|rule| rule.item(source));
+	let items = BlockItems { declarations: declarations.collect(), rules: rules.collect() };
+	RuleItem::new(rule, Some((block.open_curly.into(), items)), source)
+}
+
+// Pair up the items of `before` and `after` by key, in order of appearance, returning the index within `before` that
+// each item of `after` pairs with.
+fn pair<T>(before: &[T], after: &[T], key: impl Fn(&T) -> &str) -> Vec<Option<usize>> {
+	let mut unpaired: HashMap<&str, VecDeque<usize>> = HashMap::new();
+	for (i, item) in before.iter().enumerate() {
+		unpaired.entry(key(item)).or_default().push_back(i);
+	}
+	after.iter().map(|item| unpaired.get_mut(key(item)).and_then(|indexes| indexes.pop_front())).collect()
+}
+
+// Walk through the pairs in the order of `after`, placing each unpaired item of `before` ahead of whatever followed it
+// (much like a line based diff places removed lines ahead of added ones).
+fn merge<R>(
+	before_len: usize,
+	pairs: &[Option<usize>],
+	mut removed: impl FnMut(usize) -> R,
+	mut paired: impl FnMut(usize, usize) -> Option<R>,
+	mut added: impl FnMut(usize) -> R,
+) -> Vec<R> {
+	let mut is_paired = vec![false; before_len];
+	for index in pairs.iter().flatten() {
+		is_paired[*index] = true;
+	}
+	// The index of the next paired item of `before`, from each item of `after` onwards.
+	let mut next_paired = vec![before_len; pairs.len() + 1];
+	for (after_index, before_index) in pairs.iter().enumerate().rev() {
+		next_paired[after_index] = before_index.unwrap_or(next_paired[after_index + 1]);
+	}
+	let mut changes = vec![];
+	let mut next = 0;
+	for (after_index, before_index) in pairs.iter().enumerate() {
+		// Unpaired items of `before` up until the next paired one have been removed.
+		while next < next_paired[after_index] {
+			if !is_paired[next] {
+				changes.push(removed(next));
+			}
+			next += 1;
+		}
+		match before_index {
+			Some(before_index) => {
+				next = next.max(*before_index + 1);
+				changes.extend(paired(*before_index, after_index));
+			}
+			None => changes.push(added(after_index)),
+		}
+	}
+	changes.extend((next..before_len).filter(|i| !is_paired[*i]).map(removed));
+	changes
+}
+
+fn diff_rules(before: &[RuleItem], after: &[RuleItem]) -> Vec<RuleChange> {
+	let pairs = pair(before, after, |rule| &rule.key);
+	let change = |kind, rule: &RuleItem, before, after| RuleChange {
+		kind,
+		key: rule.key.clone(),
+		before,
+		after,
+		declarations: vec![],
+		rules: vec![],
+	};
+	merge(
+		before.len(),
+		&pairs,
+		|i| change(ChangeKind::Removed, &before[i], before[i].span, None),
+		|b, a| {
+			let (before, after) = (&before[b], &after[a]);
+			let (before_block, after_block) = match (&before.block, &after.block) {
+				(None, None) => return None,
+				(Some(before), Some(after)) => (before, after),
+				// One has a block and the other doesn't, which is a change with nothing to compare within it.
+				_ => return Some(change(ChangeKind::Changed, before, before.span, after.span)),
+			};
+			let declarations = diff_declarations(&before_block.declarations, &after_block.declarations);
+			let rules = diff_rules(&before_block.rules, &after_block.rules);
+			if declarations.is_empty() && rules.is_empty() {
+				return None;
+			}
+			Some(RuleChange { declarations, rules, ..change(ChangeKind::Changed, before, before.span, after.span) })
+		},
+		|i| change(ChangeKind::Added, &after[i], None, after[i].span),
+	)
+}
+
+fn diff_declarations(before: &[DeclarationItem], after: &[DeclarationItem]) -> Vec<DeclarationChange> {
+	let pairs = pair(before, after, |declaration| &declaration.name);
+	merge(
+		before.len(),
+		&pairs,
+		|i| DeclarationChange {
+			kind: ChangeKind::Removed,
+			name: before[i].name.clone(),
+			before: before[i].span,
+			after: None,
+			before_value: Some(before[i].value.clone()),
+			after_value: None,
+		},
+		|b, a| {
+			let (before, after) = (&before[b], &after[a]);
+			(before.value != after.value).then(|| DeclarationChange {
+				kind: ChangeKind::Changed,
+				name: after.name.clone(),
+				before: before.span,
+				after: after.span,
+				before_value: Some(before.value.clone()),
+				after_value: Some(after.value.clone()),
+			})
+		},
+		|i| DeclarationChange {
+			kind: ChangeKind::Added,
+			name: after[i].name.clone(),
+			before: None,
+			after: after[i].span,
+			before_value: None,
+			after_value: Some(after[i].value.clone()),
+		},
+	)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use bumpalo::Bump;
+	use css_parse::Parser;
+
+	fn diff(before: &str, after: &str) -> StyleSheetDiff {
+		let bump = Bump::default();
+		let before_sheet = Parser::new(&bump, before).parse_entirely::<StyleSheet>().output.unwrap();
+		let after_sheet = Parser::new(&bump, after).parse_entirely::<StyleSheet>().output.unwrap();
+		StyleSheetDiff::new(&before_sheet, before, &after_sheet, after)
+	}
+
+	// A compact description of the changes, to keep the assertions readable.
+	fn describe(changes: &StyleSheetDiff) -> Vec<String> {
+		fn rules(changes: &[RuleChange], out: &mut Vec<String>, depth: usize) {
+			for rule in changes {
+				out.push(format!("{}{:?} {}", "  ".repeat(depth), rule.kind, rule.key));
+				for declaration in &rule.declarations {
+					out.push(format!(
+						"{}{:?} {}: {} -> {}",
+						"  ".repeat(depth + 1),
+						declaration.kind,
+						declaration.name,
+						declaration.before_value.as_deref().unwrap_or("_"),
+						declaration.after_value.as_deref().unwrap_or("_"),
+					));
+				}
+				rules(&rule.rules, out, depth + 1);
+			}
+		}
+		let mut out = vec![];
+		rules(&changes.rules, &mut out, 0);
+		out
+	}
+
+	#[test]
+	fn test_equivalent() {
+		assert!(diff("a{color:red}", "a {\n\tcolor: red; /* red */\n}").is_empty());
+		assert!(
+			diff("@media (min-width:1px){a b{color:red}}", "@media (min-width: 1px) { a  b { color:red } }").is_empty()
+		);
+		assert!(diff("@import \"a.css\";", "@import \"a.css\";").is_empty());
+	}
+
+	#[test]
+	fn test_declarations() {
+		let changes = diff("a{color:red;width:1px}b{top:0}", "a{COLOR:blue;height:1px}b{top:0}");
+		assert_eq!(
+			describe(&changes),
+			["Changed a", "  Changed color: red -> blue", "  Removed width: 1px -> _", "  Added height: _ -> 1px"]
+		);
+		let change = &changes.rules[0].declarations[0];
+		assert_eq!(change.before, Some(Span::new(SourceOffset(2), SourceOffset(11))));
+		assert_eq!(change.after, Some(Span::new(SourceOffset(2), SourceOffset(12))));
+		assert_eq!(
+			describe(&diff("a{color:red}", "a{color:red!important}")),
+			["Changed a", "  Changed color: red -> red!important"]
+		);
+		assert_eq!(describe(&diff("a{--x:{a:b}}", "a{--x:{a:c}}")), ["Changed a", "  Changed --x: {a:b} -> {a:c}"]);
+	}
+
+	#[test]
+	fn test_rules() {
+		let changes = diff("a{}b{}@media print{c{}}", "b{}d{}@media print{c{color:red}}@import \"x.css\";");
+		assert_eq!(
+			describe(&changes),
+			[
+				"Removed a",
+				"Added d",
+				"Changed @media print",
+				"  Changed c",
+				"    Added color: _ -> red",
+				"Added @import\"x.css\";"
+			]
+		);
+		assert_eq!(changes.rules[0].before, Some(Span::new(SourceOffset(0), SourceOffset(3))));
+		assert_eq!(changes.rules[1].after, Some(Span::new(SourceOffset(3), SourceOffset(6))));
+		// Rules with the same key pair up in order.
+		assert_eq!(describe(&diff("a{top:0}a{top:1}", "a{top:0}a{top:2}")), ["Changed a", "  Changed top: 1 -> 2"]);
+		assert_eq!(describe(&diff("a{}a{}", "a{}")), ["Removed a"]);
+		// Nested style rules
+		assert_eq!(
+			describe(&diff("a{b{top:0}}", "a{b{top:1}&:hover{}}")),
+			["Changed a", "  Changed b", "    Changed top: 0 -> 1", "  Added &:hover"]
+		);
+	}
+
+	#[test]
+	fn test_at_rule_blocks() {
+		assert_eq!(
+			describe(&diff("@keyframes x{from{top:0}to{top:1px}}", "@keyframes x{from{top:0}to{top:2px}}")),
+			["Changed @keyframes x", "  Changed to", "    Changed top: 1px -> 2px"]
+		);
+		assert_eq!(
+			describe(&diff("@font-face{font-display:swap}", "@font-face{font-display:block}")),
+			["Changed @font-face", "  Changed font-display: swap -> block"]
+		);
+		assert_eq!(
+			describe(&diff("@page{margin:0;@top-left{content:'a'}}", "@page{margin:0;@top-left{content:'b'}}")),
+			["Changed @page", "  Changed @top-left", "    Changed content: 'a' -> 'b'"]
+		);
+		assert_eq!(describe(&diff("@layer a;@layer b{}", "@layer a;@layer b{c{}}")), ["Changed @layer b", "  Added c"]);
+		// Rules which aren't understood are compared by what is within their block all the same.
+		assert_eq!(describe(&diff("@foo bar{a:b}", "@foo bar{a:c}")), ["Changed @foo bar", "  Changed a: b -> c"]);
+	}
+}
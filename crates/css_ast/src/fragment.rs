@@ -0,0 +1,135 @@
+//! Entry points for parsing the fragments of CSS which appear outside of a stylesheet, such as the value of a `style`
+//! attribute or the argument to `matchMedia()`.
+//!
+//! Each parses the whole of the given text, reporting any errors alongside the node in a [ParserReturn], just as
+//! [Parser::parse_entirely()] would for a [StyleSheet][crate::StyleSheet]. These fragments often come from untrusted
+//! sources, so each also takes the [ParserLimits] to parse them with.
+
+use crate::{MediaQueryList, SelectorList, StyleAttribute, StyleValue, SupportsCondition};
+use bumpalo::Bump;
+use css_lexer::Span;
+use css_parse::{diagnostics, Feature, Parser, ParserLimits, ParserReturn};
+
+/// Parse the contents of a `style` attribute, for example `color: red; width: 100%`.
+pub fn parse_style_attribute<'a>(
+	bump: &'a Bump,
+	source_text: &'a str,
+	features: Feature,
+	limits: ParserLimits,
+) -> ParserReturn<'a, StyleAttribute<'a>> {
+	Parser::new_with_features(bump, source_text, features).with_limits(limits).parse_entirely::<StyleAttribute>()
+}
+
+/// Parse the value of the named property, for example `1px solid` for `border`. The property name is matched
+/// ASCII case-insensitively; an unknown property's value is parsed as [StyleValue::Unknown]. A value which isn't valid
+/// for a known property is also kept as [StyleValue::Unknown], but is reported as an error.
+pub fn parse_property_value<'a>(
+	bump: &'a Bump,
+	property: &str,
+	source_text: &'a str,
+	features: Feature,
+	limits: ParserLimits,
+) -> ParserReturn<'a, StyleValue<'a>> {
+	let name = if property.starts_with("--") { property.into() } else { property.to_ascii_lowercase() };
+	let mut parser = Parser::new_with_features(bump, source_text, features).with_limits(limits);
+	parser.parse_entirely_with(|p| {
+		let start = p.offset();
+		let value = StyleValue::parse_by_name(p, &name)?;
+		if matches!(value, StyleValue::Unknown(_)) && StyleValue::parses_by_name(&name) {
+			p.push_error(diagnostics::UnknownValue(Span::new(start, p.offset())).into());
+		}
+		Ok(value)
+	})
+}
+
+/// Parse a list of selectors, for example `a, .b > c`, as given to `querySelectorAll()`.
+pub fn parse_selector_list<'a>(
+	bump: &'a Bump,
+	source_text: &'a str,
+	features: Feature,
+	limits: ParserLimits,
+) -> ParserReturn<'a, SelectorList<'a>> {
+	Parser::new_with_features(bump, source_text, features).with_limits(limits).parse_entirely::<SelectorList>()
+}
+
+/// Parse a list of media queries, for example `screen and (min-width: 600px)`, as given to `matchMedia()`.
+pub fn parse_media_query_list<'a>(
+	bump: &'a Bump,
+	source_text: &'a str,
+	features: Feature,
+	limits: ParserLimits,
+) -> ParserReturn<'a, MediaQueryList<'a>> {
+	Parser::new_with_features(bump, source_text, features).with_limits(limits).parse_entirely::<MediaQueryList>()
+}
+
+/// Parse a `@supports` condition, for example `(display: grid) and (not (gap: 1px))`, as given to `CSS.supports()`.
+pub fn parse_supports_condition<'a>(
+	bump: &'a Bump,
+	source_text: &'a str,
+	features: Feature,
+	limits: ParserLimits,
+) -> ParserReturn<'a, SupportsCondition<'a>> {
+	Parser::new_with_features(bump, source_text, features).with_limits(limits).parse_entirely::<SupportsCondition>()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use css_parse::{CursorFmtSink, ToCursors};
+
+	fn to_css<T: ToCursors>(result: ParserReturn<T>) -> String {
+		assert!(result.errors.is_empty(), "{:?}", result.errors);
+		let mut css = String::new();
+		result.to_cursors(&mut CursorFmtSink::new(result.source_text, &mut css));
+		css
+	}
+
+	#[test]
+	fn test_fragments() {
+		let bump = Bump::default();
+		let none = Feature::none();
+		let limits = ParserLimits::UNTRUSTED;
+		assert_eq!(to_css(parse_style_attribute(&bump, "color: red; width: 1px", none, limits)), "color:red;width:1px");
+		assert_eq!(to_css(parse_selector_list(&bump, "a, .b > c", none, limits)), "a,.b > c");
+		assert_eq!(
+			to_css(parse_media_query_list(&bump, "screen and (min-width: 600px)", none, limits)),
+			"screen and (min-width:600px)"
+		);
+		assert_eq!(
+			to_css(parse_supports_condition(&bump, "(display:grid) and (gap:1px)", none, limits)),
+			"(display:grid)and (gap:1px)"
+		);
+		assert_eq!(to_css(parse_supports_condition(&bump, "not (display:grid)", none, limits)), "not (display:grid)");
+	}
+
+	#[test]
+	fn test_property_value() {
+		let bump = Bump::default();
+		let none = Feature::none();
+		let limits = ParserLimits::UNTRUSTED;
+		let value = parse_property_value(&bump, "Width", "10px", none, limits).output.unwrap();
+		assert!(matches!(value, StyleValue::Width(_)));
+		let value = parse_property_value(&bump, "width", "inherit", none, limits).output.unwrap();
+		assert!(matches!(value, StyleValue::Inherit(_)));
+		let value = parse_property_value(&bump, "--Foo", "{ a }", none, limits).output.unwrap();
+		assert!(matches!(value, StyleValue::Custom(_)));
+		let result = parse_property_value(&bump, "width", "red", none, limits);
+		assert!(matches!(result.output, Some(StyleValue::Unknown(_))));
+		assert_eq!(result.errors.len(), 1);
+		assert_eq!(result.errors[0].to_string(), "Unknown Value");
+		// Properties which aren't parsed (yet) have Unknown values without an error
+		let result = parse_property_value(&bump, "not-a-property", "red", none, limits);
+		assert!(matches!(result.output, Some(StyleValue::Unknown(_))));
+		assert!(result.errors.is_empty());
+		assert!(!parse_property_value(&bump, "width", "1px; color: red", none, limits).errors.is_empty());
+	}
+
+	#[test]
+	fn test_limits() {
+		let bump = Bump::default();
+		let source_text = ":is(".repeat(100_000);
+		let result = parse_selector_list(&bump, &source_text, Feature::none(), ParserLimits::UNTRUSTED);
+		let error = result.errors.last().unwrap();
+		assert_eq!(error.code().unwrap().to_string(), "css_parse::MaxDepthExceeded");
+	}
+}
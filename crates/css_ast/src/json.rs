@@ -0,0 +1,221 @@
+//! The JSON representation of the AST, available with the `serde` feature.
+//!
+//! Any node can be written out with [serde::Serialize], and read back (allocating into a [Bump]) with
+//! [FromJson][css_parse::FromJson]. As nodes only hold [Cursors][css_lexer::Cursor] into the source text, the
+//! source must travel alongside the node; a [JsonDocument] is the pairing of the two, and is the unit of exchange for
+//! tools outside of Rust:
+//!
+//! ```json
+//! { "version": 1, "source": "a{color:red}", "node": { "type": "stylesheet", "rules": [...] } }
+//! ```
+//!
+//! # Schema
+//!
+//! The shape of every node follows from a handful of rules, and the `*_snapshots` tests pin the shape of each node in
+//! practice. Any change to the shape of a node must increment [SCHEMA_VERSION].
+//!
+//! - Tokens are objects of `{ "kind", "offset", "len" }`, plus a `"unit"` for dimensions. `kind` is the name of the
+//!   [Kind][css_lexer::Kind], and `offset` & `len` are in bytes of the `source`. When reading, each token is lexed
+//!   again from the source at `offset`, and must agree with its `kind`, `len` & `unit`. The few brackets which are
+//!   stored without an offset are `{ "kind", "len" }`.
+//! - Nodes which are structs are objects, one key per field, usually with a `"type"` naming the node (for example
+//!   `"stylerule"` or `"property"`). Nodes which wrap a single value are written as that value, and nodes made of
+//!   several unnamed values are arrays. No object has the same key twice; a style rule's declaration block, for
+//!   example, is nested under its `"style"`.
+//! - Nodes which are one of a set of alternatives are either tagged with a `"type"` (and, where the alternative isn't
+//!   itself an object, a `"value"`), an object with one key naming the alternative (such as `{ "Auto": token }` for
+//!   keywords), or, where the alternatives can't be confused, written as the alternative itself.
+//! - The value of a declaration is an object of `{ "type", "value" }`. Where the value was parsed as the value of the
+//!   property, the `type` is the name of that property (such as `"width"`), and must agree with the name of the
+//!   declaration when read. Otherwise the `type` is one of `"initial"`, `"inherit"`, `"unset"`, `"revert"`,
+//!   `"revert-layer"`, `"custom"` (for custom properties), `"computed"` (for values only known at computed-value time,
+//!   such as those using `var()`), `"unknown"` (for values which couldn't be parsed), or one of the non-standard
+//!   values.
+//! - Lists are arrays, and missing optional values are `null`.
+//!
+//! To generate CSS from a document, the `source` need only contain the text of each token; the AST decides the order
+//! in which they are written, and whitespace is added where tokens would otherwise run together. The
+//! [CursorFmtSink][css_parse::CursorFmtSink] will write a document's node back out as CSS.
+
+use bumpalo::Bump;
+use css_parse::{Feature, FromJson, JsonError, JsonValue, Parser};
+use serde::{
+	de::{DeserializeSeed, Error as _},
+	ser::SerializeStruct,
+	Deserialize, Deserializer, Serialize, Serializer,
+};
+use std::marker::PhantomData;
+
+/// The version of the JSON representation of the AST, written into (and checked when reading) each [JsonDocument].
+///
+/// - `1`: Nodes are as they were serialized before documents were versioned, except that unknown declaration values
+///   are tagged with `"type": "unknown"`, as they were otherwise written in the same way as computed values.
+/// - `2`: The declaration block of a style rule is nested under `"style"`, rather than its fields sitting alongside
+///   (and its `"type"` repeating) those of the rule, and every declaration value is tagged with a `"type"`.
+pub const SCHEMA_VERSION: u32 = 2;
+
+/// An AST node alongside the source text that its [Cursors][css_lexer::Cursor] point into.
+///
+/// ```
+/// use css_ast::{JsonDocument, StyleSheet};
+/// use css_parse::{CursorFmtSink, Parser, ToCursors};
+/// use bumpalo::Bump;
+/// let bump = Bump::default();
+/// let source = "a { color: red }";
+/// let stylesheet = Parser::new(&bump, source).parse_entirely::<StyleSheet>().output.unwrap();
+/// let json = serde_json::to_string(&JsonDocument::new(source, stylesheet)).unwrap();
+///
+/// let document = JsonDocument::<StyleSheet>::from_str(&bump, &json).unwrap();
+/// let mut css = String::new();
+/// document.node.to_cursors(&mut CursorFmtSink::new(document.source, &mut css));
+/// assert_eq!(css, "a{color:red}");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonDocument<'a, T> {
+	pub source: &'a str,
+	pub node: T,
+}
+
+impl<'a, T> JsonDocument<'a, T> {
+	pub fn new(source: &'a str, node: T) -> Self {
+		Self { source, node }
+	}
+}
+
+impl<T: Serialize> Serialize for JsonDocument<'_, T> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		let mut state = serializer.serialize_struct("JsonDocument", 3)?;
+		state.serialize_field("version", &SCHEMA_VERSION)?;
+		state.serialize_field("source", self.source)?;
+		state.serialize_field("node", &self.node)?;
+		state.end()
+	}
+}
+
+impl<'a, T: FromJson<'a>> JsonDocument<'a, T> {
+	/// Read a document from its JSON, allocating the source text and the node into the given [Bump].
+	pub fn from_json(bump: &'a Bump, value: &JsonValue) -> Result<Self, JsonError> {
+		Self::from_json_with_features(bump, value, Feature::none())
+	}
+
+	/// Read a document from its JSON, checking its tokens as a [Parser] with the given [Feature]s would.
+	pub fn from_json_with_features(bump: &'a Bump, value: &JsonValue, features: Feature) -> Result<Self, JsonError> {
+		match value.get("version").and_then(|version| version.as_u64()) {
+			Some(version) if version == SCHEMA_VERSION as u64 => {}
+			version => Err(JsonError::custom(format!(
+				"expected a document of schema version {SCHEMA_VERSION}, saw version {version:?}"
+			)))?,
+		}
+		let source = value
+			.get("source")
+			.and_then(|source| source.as_str())
+			.ok_or_else(|| JsonError::custom("expected a document to have a source string"))?;
+		let source = bump.alloc_str(source);
+		let parser = Parser::new_with_features(bump, source, features);
+		let node = T::from_json(&parser, value.get("node").unwrap_or(&JsonValue::Null))?;
+		Ok(Self { source, node })
+	}
+
+	/// Read a document from a string of JSON, allocating the source text and the node into the given [Bump].
+	pub fn from_str(bump: &'a Bump, json: &str) -> Result<Self, JsonError> {
+		JsonDocumentSeed::new(bump).deserialize(&mut serde_json::Deserializer::from_str(json))
+	}
+}
+
+/// A [DeserializeSeed] for reading a [JsonDocument] from any [Deserializer], allocating into the given [Bump].
+pub struct JsonDocumentSeed<'a, T> {
+	bump: &'a Bump,
+	features: Feature,
+	node: PhantomData<T>,
+}
+
+impl<'a, T> JsonDocumentSeed<'a, T> {
+	pub fn new(bump: &'a Bump) -> Self {
+		Self { bump, features: Feature::none(), node: PhantomData }
+	}
+
+	pub fn with_features(mut self, features: Feature) -> Self {
+		self.features = features;
+		self
+	}
+}
+
+impl<'a, 'de, T: FromJson<'a>> DeserializeSeed<'de> for JsonDocumentSeed<'a, T> {
+	type Value = JsonDocument<'a, T>;
+
+	fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+		let value = JsonValue::deserialize(deserializer)?;
+		JsonDocument::from_json_with_features(self.bump, &value, self.features).map_err(D::Error::custom)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::StyleSheet;
+	use css_parse::{CursorFmtSink, ToCursors};
+
+	fn to_css(node: &StyleSheet, source_text: &str) -> String {
+		let mut css = String::new();
+		node.to_cursors(&mut CursorFmtSink::new(source_text, &mut css));
+		css
+	}
+
+	fn assert_round_trip(source_text: &str) {
+		let bump = Bump::default();
+		let stylesheet = Parser::new(&bump, source_text).parse_entirely::<StyleSheet>().output.unwrap();
+		let json = serde_json::to_string(&JsonDocument::new(source_text, &stylesheet)).unwrap();
+		let document = JsonDocument::<StyleSheet>::from_str(&bump, &json).unwrap();
+		assert_eq!(document.node, stylesheet);
+		assert_eq!(to_css(&document.node, document.source), to_css(&stylesheet, source_text));
+	}
+
+	#[test]
+	fn test_round_trip() {
+		assert_round_trip("a { color: red }");
+		assert_round_trip("@media (min-width: 10px) { .a > b { width: calc(1px + 2%) !important } }");
+		assert_round_trip(":root { --x: { a: b } } a { margin: var(--x) auto; foo: bar }");
+		assert_round_trip("@supports (display: grid) { @layer a, b; } @keyframes x { from { opacity: 0 } }");
+	}
+
+	#[test]
+	fn test_shape() {
+		let bump = Bump::default();
+		let source_text = "a{width:1px;foo:bar;color:var(--x)}";
+		let stylesheet = Parser::new(&bump, source_text).parse_entirely::<StyleSheet>().output.unwrap();
+		let json = serde_json::to_value(&stylesheet).unwrap();
+		let rule = &json["rules"][0];
+		assert_eq!(rule["type"], "stylerule");
+		assert_eq!(rule["style"]["type"], "style-declaration");
+		assert!(rule["selectors"].is_array() && rule["style"]["declarations"].is_array());
+		let declarations = &rule["style"]["declarations"];
+		assert_eq!(declarations[0][0]["value"]["type"], "width");
+		assert_eq!(declarations[0][0]["value"]["value"]["LengthPercentage"]["type"], "px");
+		assert_eq!(declarations[1][0]["value"]["type"], "unknown");
+		assert_eq!(declarations[2][0]["value"]["type"], "computed");
+		// No object has a key twice, so none are dropped when read back
+		let string = serde_json::to_string(&stylesheet).unwrap();
+		assert_eq!(serde_json::from_str::<JsonValue>(&string).unwrap().to_string().len(), string.len());
+	}
+
+	#[test]
+	fn test_errors() {
+		let bump = Bump::default();
+		let read = |json: &JsonValue| JsonDocument::<StyleSheet>::from_json(&bump, json).map(|_| ()).unwrap_err();
+		let stylesheet = Parser::new(&bump, "a{}").parse_entirely::<StyleSheet>().output.unwrap();
+		let mut json = serde_json::to_value(JsonDocument::new("a{}", stylesheet)).unwrap();
+		json["version"] = 0.into();
+		assert!(read(&json).to_string().contains("schema version"));
+		json["version"] = SCHEMA_VERSION.into();
+		json["source"] = JsonValue::Null;
+		assert!(read(&json).to_string().contains("source string"));
+		// The tokens no longer match the source text
+		json["source"] = "1{}".into();
+		read(&json);
+		// A value must be tagged with the name of its property
+		let stylesheet = Parser::new(&bump, "a{width:1px}").parse_entirely::<StyleSheet>().output.unwrap();
+		let mut json = serde_json::to_value(JsonDocument::new("a{width:1px}", stylesheet)).unwrap();
+		json["node"]["rules"][0]["style"]["declarations"][0][0]["value"]["type"] = "height".into();
+		assert!(read(&json).to_string().contains(r#"expected a value of "width""#));
+	}
+}
@@ -1,3 +1,10 @@
+mod compat;
+mod computed;
+mod diff;
+mod fragment;
+#[cfg(feature = "serde")]
+mod json;
+mod math;
 mod properties;
 mod rules;
 mod selector;
@@ -10,9 +17,17 @@ mod units;
 mod values;
 mod visit;
 
+pub use compat::*;
+pub use computed::*;
+pub use diff::*;
+pub use fragment::*;
+#[cfg(feature = "serde")]
+pub use json::*;
+pub use math::*;
 pub use properties::*;
 pub use rules::*;
 pub use selector::*;
+pub use specificity::*;
 pub use stylerule::*;
 pub use stylesheet::*;
 pub use types::*;
@@ -25,7 +40,7 @@ use css_parse::{diagnostics, CursorSink, Parse, Parser, Result as ParserResult,
 
 // TODO! - delete this when we're done ;)
 #[derive(Default, Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(untagged))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde(untagged))]
 pub enum Todo {
 	#[default]
 	Todo,
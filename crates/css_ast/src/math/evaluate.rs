@@ -0,0 +1,137 @@
+use css_lexer::DimensionUnit;
+
+use super::{to_canonical, BaseType, CalcNode, CalcOperation};
+use crate::{length_to_px, ComputeContext};
+
+impl CalcNode {
+	/// Evaluate the expression down to a single number in the [canonical unit][BaseType::canonical_unit] of its
+	/// [type][CalcNode::resolve_type], so a length evaluates to `px` and an angle to `deg`. Relative lengths are resolved
+	/// against the [ComputeContext], and percentages against `percentage_basis`.
+	///
+	/// Returns [None] if the expression is invalid, or if it contains percentages which need resolving against a
+	/// length (or other type) but no `percentage_basis` was given. Expressions made only of percentages evaluate to the
+	/// percentage itself when there is no basis, so `calc(10% * 2)` evaluates to `20`.
+	pub fn evaluate(&self, ctx: &ComputeContext, percentage_basis: Option<f32>) -> Option<f32> {
+		let ty = self.resolve_type()?;
+		if ty.percent_hint().is_some() && percentage_basis.is_none() {
+			return None;
+		}
+		self.evaluate_node(ctx, percentage_basis)
+	}
+
+	/// Replace each [CalcNode::Channel] with the value `channel` gives for it. Channels which `channel` returns [None]
+	/// for are left in place, and so will fail to evaluate.
+	pub fn resolve_channels(self, channel: &impl Fn(&str) -> Option<f32>) -> CalcNode {
+		let resolve_all = |children: Vec<CalcNode>| children.into_iter().map(|c| c.resolve_channels(channel)).collect();
+		match self {
+			Self::Channel(name) => channel(name).map_or(self, Self::Number),
+			Self::Number(_) | Self::Dimension(_, _) => self,
+			Self::Sum(children) => Self::Sum(resolve_all(children)),
+			Self::Product(children) => Self::Product(resolve_all(children)),
+			Self::Negate(child) => Self::Negate(Box::new(child.resolve_channels(channel))),
+			Self::Invert(child) => Self::Invert(Box::new(child.resolve_channels(channel))),
+			Self::Function(op, args) => Self::Function(op, resolve_all(args)),
+		}
+	}
+
+	fn evaluate_node(&self, ctx: &ComputeContext, basis: Option<f32>) -> Option<f32> {
+		Some(match self {
+			Self::Number(value) => *value,
+			Self::Channel(_) => return None,
+			Self::Dimension(value, DimensionUnit::Percent) => match basis {
+				Some(basis) => basis * value / 100.0,
+				None => *value,
+			},
+			Self::Dimension(value, unit) => match BaseType::from_unit(*unit)? {
+				BaseType::Length => length_to_px(*value, *unit, ctx),
+				_ => to_canonical(*value, *unit).map_or(*value, |(value, _)| value),
+			},
+			Self::Sum(children) => children.iter().map(|child| child.evaluate_node(ctx, basis)).sum::<Option<f32>>()?,
+			Self::Product(children) => {
+				children.iter().map(|child| child.evaluate_node(ctx, basis)).product::<Option<f32>>()?
+			}
+			Self::Negate(child) => -child.evaluate_node(ctx, basis)?,
+			Self::Invert(child) => 1.0 / child.evaluate_node(ctx, basis)?,
+			Self::Function(op, args) => {
+				let values = args.iter().map(|arg| arg.evaluate_node(ctx, basis)).collect::<Option<Vec<f32>>>()?;
+				match op {
+					// Angles evaluate to degrees, but the trigonometric functions work in radians.
+					CalcOperation::Sin | CalcOperation::Cos | CalcOperation::Tan => {
+						let is_angle = args[0].resolve_type()?.matches(BaseType::Angle);
+						op.apply(&[if is_angle { values[0].to_radians() } else { values[0] }])?
+					}
+					CalcOperation::Asin | CalcOperation::Acos | CalcOperation::Atan | CalcOperation::Atan2 => {
+						op.apply(&values)?.to_degrees()
+					}
+					_ => op.apply(&values)?,
+				}
+			}
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::MathFunction;
+	use bumpalo::Bump;
+	use css_parse::{Parser, State};
+
+	fn evaluate(ctx: &ComputeContext, source_text: &str, percentage_basis: Option<f32>) -> Option<f32> {
+		let bump = Bump::default();
+		let function = Parser::new(&bump, source_text).parse_entirely::<MathFunction>().output.unwrap();
+		CalcNode::from(&function).evaluate(ctx, percentage_basis)
+	}
+
+	#[test]
+	fn test_lengths() {
+		let ctx = ComputeContext::new("").with_viewport(1000.0, 500.0).with_root_font_size(10.0);
+		assert_eq!(evaluate(&ctx, "calc(1in + 4px)", None), Some(100.0));
+		assert_eq!(evaluate(&ctx, "calc(2rem + 10vw)", None), Some(120.0));
+		assert_eq!(evaluate(&ctx, "calc(100% - 10px)", Some(200.0)), Some(190.0));
+		assert_eq!(evaluate(&ctx, "calc(100% - 10px)", None), None);
+		assert_eq!(evaluate(&ctx, "min(50%, 1rem * 30)", Some(400.0)), Some(200.0));
+		assert_eq!(evaluate(&ctx, "clamp(1rem, 2.5vw, 2rem)", None), Some(20.0));
+		assert_eq!(evaluate(&ctx, "calc(10px + 1s)", None), None);
+	}
+
+	#[test]
+	fn test_numbers_and_percentages() {
+		let ctx = ComputeContext::new("");
+		assert_eq!(evaluate(&ctx, "calc(2 * 3 + 1)", None), Some(7.0));
+		assert_eq!(evaluate(&ctx, "calc(10% * 2)", None), Some(20.0));
+		assert_eq!(evaluate(&ctx, "calc(10% * 2)", Some(50.0)), Some(10.0));
+		assert_eq!(evaluate(&ctx, "calc(1px / 2px)", None), Some(0.5));
+		assert_eq!(evaluate(&ctx, "pow(2, 3)", None), Some(8.0));
+	}
+
+	#[test]
+	fn test_channels() {
+		let ctx = ComputeContext::new("");
+		let bump = Bump::default();
+		let mut parser = Parser::new(&bump, "calc(r * 2 + alpha)");
+		parser.set_state(State::RelativeColor);
+		let function = parser.parse_entirely::<MathFunction>().output.unwrap();
+		let node = CalcNode::from(&function);
+		assert_eq!(node.resolve_type(), Some(crate::CalcType::NUMBER));
+		assert_eq!(node.evaluate(&ctx, None), None);
+		let channel = |name: &str| match name {
+			"r" => Some(0.25),
+			_ => None,
+		};
+		let node = node.resolve_channels(&channel);
+		assert_eq!(node.to_string(), "calc(0.25 * 2 + alpha)");
+		assert_eq!(node.resolve_channels(&|_| Some(1.0)).evaluate(&ctx, None), Some(1.5));
+	}
+
+	#[test]
+	fn test_angles() {
+		let ctx = ComputeContext::new("");
+		assert_eq!(evaluate(&ctx, "calc(0.25turn + 10deg)", None), Some(100.0));
+		assert_eq!(evaluate(&ctx, "sin(90deg)", None), Some(1.0));
+		assert_eq!(evaluate(&ctx, "cos(0.5turn)", None), Some(-1.0));
+		assert_eq!(evaluate(&ctx, "atan2(1px, 1px)", None), Some(45.0));
+		assert_eq!(evaluate(&ctx, "asin(1)", None), Some(90.0));
+		assert_eq!(evaluate(&ctx, "calc(500ms * 3)", None), Some(1.5));
+	}
+}
@@ -0,0 +1,504 @@
+use bumpalo::collections::Vec;
+use css_parse::{
+	diagnostics, function_set, keyword_set, Build, CursorSink, Parse, Parser, Peek, Result as ParserResult, State,
+	ToCursors, T,
+};
+
+use crate::types::ChannelKeyword;
+
+function_set!(MathFunctionName {
+	Calc: "calc",
+	Min: "min",
+	Max: "max",
+	Clamp: "clamp",
+	Round: "round",
+	Mod: "mod",
+	Rem: "rem",
+	Sin: "sin",
+	Cos: "cos",
+	Tan: "tan",
+	Asin: "asin",
+	Acos: "acos",
+	Atan: "atan",
+	Atan2: "atan2",
+	Pow: "pow",
+	Sqrt: "sqrt",
+	Hypot: "hypot",
+	Log: "log",
+	Exp: "exp",
+	Abs: "abs",
+	Sign: "sign",
+});
+
+// https://drafts.csswg.org/css-values/#typedef-rounding-strategy
+keyword_set!(RoundingStrategyKeyword { Nearest: "nearest", Up: "up", Down: "down", ToZero: "to-zero" });
+
+// https://drafts.csswg.org/css-values/#calc-constants
+keyword_set!(CalcConstant { E: "e", Pi: "pi", Infinity: "infinity", NegativeInfinity: "-infinity", NaN: "nan" });
+
+impl CalcConstant {
+	pub fn value(&self) -> f32 {
+		match self {
+			Self::E(_) => std::f32::consts::E,
+			Self::Pi(_) => std::f32::consts::PI,
+			Self::Infinity(_) => f32::INFINITY,
+			Self::NegativeInfinity(_) => f32::NEG_INFINITY,
+			Self::NaN(_) => f32::NAN,
+		}
+	}
+}
+
+/// A comma separated list of [CalcSums][CalcSum], as used by the variadic math functions such as `min()`.
+pub type CalcSumList<'a> = Vec<'a, (CalcSum<'a>, Option<T![,]>)>;
+
+// https://drafts.csswg.org/css-values/#math
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
+pub enum MathFunction<'a> {
+	// https://drafts.csswg.org/css-values/#funcdef-calc
+	// calc() = calc( <calc-sum> )
+	Calc(T![Function], CalcSum<'a>, Option<T![')']>),
+
+	// https://drafts.csswg.org/css-values/#comp-func
+	// min() = min( <calc-sum># )
+	// max() = max( <calc-sum># )
+	// clamp() = clamp( <calc-sum>, <calc-sum>, <calc-sum> )
+	Min(T![Function], CalcSumList<'a>, Option<T![')']>),
+	Max(T![Function], CalcSumList<'a>, Option<T![')']>),
+	Clamp(T![Function], CalcSum<'a>, T![,], CalcSum<'a>, T![,], CalcSum<'a>, Option<T![')']>),
+
+	// https://drafts.csswg.org/css-values/#round-func
+	// round() = round( <rounding-strategy>?, <calc-sum>, <calc-sum>? )
+	// mod() = mod( <calc-sum>, <calc-sum> )
+	// rem() = rem( <calc-sum>, <calc-sum> )
+	Round(
+		T![Function],
+		Option<(RoundingStrategyKeyword, T![,])>,
+		CalcSum<'a>,
+		Option<(T![,], CalcSum<'a>)>,
+		Option<T![')']>,
+	),
+	Mod(T![Function], CalcSum<'a>, T![,], CalcSum<'a>, Option<T![')']>),
+	Rem(T![Function], CalcSum<'a>, T![,], CalcSum<'a>, Option<T![')']>),
+
+	// https://drafts.csswg.org/css-values/#trig-funcs
+	// sin() = sin( <calc-sum> )
+	// atan2() = atan2( <calc-sum>, <calc-sum> )
+	Sin(T![Function], CalcSum<'a>, Option<T![')']>),
+	Cos(T![Function], CalcSum<'a>, Option<T![')']>),
+	Tan(T![Function], CalcSum<'a>, Option<T![')']>),
+	Asin(T![Function], CalcSum<'a>, Option<T![')']>),
+	Acos(T![Function], CalcSum<'a>, Option<T![')']>),
+	Atan(T![Function], CalcSum<'a>, Option<T![')']>),
+	Atan2(T![Function], CalcSum<'a>, T![,], CalcSum<'a>, Option<T![')']>),
+
+	// https://drafts.csswg.org/css-values/#exponent-funcs
+	// pow() = pow( <calc-sum>, <calc-sum> )
+	// sqrt() = sqrt( <calc-sum> )
+	// hypot() = hypot( <calc-sum># )
+	// log() = log( <calc-sum>, <calc-sum>? )
+	// exp() = exp( <calc-sum> )
+	Pow(T![Function], CalcSum<'a>, T![,], CalcSum<'a>, Option<T![')']>),
+	Sqrt(T![Function], CalcSum<'a>, Option<T![')']>),
+	Hypot(T![Function], CalcSumList<'a>, Option<T![')']>),
+	Log(T![Function], CalcSum<'a>, Option<(T![,], CalcSum<'a>)>, Option<T![')']>),
+	Exp(T![Function], CalcSum<'a>, Option<T![')']>),
+
+	// https://drafts.csswg.org/css-values/#sign-funcs
+	// abs() = abs( <calc-sum> )
+	// sign() = sign( <calc-sum> )
+	Abs(T![Function], CalcSum<'a>, Option<T![')']>),
+	Sign(T![Function], CalcSum<'a>, Option<T![')']>),
+}
+
+fn parse_sum_list<'a>(p: &mut Parser<'a>) -> ParserResult<CalcSumList<'a>> {
+	let mut args = Vec::new_in(p.bump());
+	loop {
+		let arg = p.parse::<CalcSum>()?;
+		let comma = p.parse_if_peek::<T![,]>()?;
+		let done = comma.is_none();
+		args.push((arg, comma));
+		if done {
+			return Ok(args);
+		}
+	}
+}
+
+impl<'a> Peek<'a> for MathFunction<'a> {
+	fn peek(p: &Parser<'a>, c: css_lexer::Cursor) -> bool {
+		MathFunctionName::peek(p, c)
+	}
+}
+
+impl<'a> Parse<'a> for MathFunction<'a> {
+	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
+		let name = p.parse::<MathFunctionName>()?;
+		let function = <T![Function]>::build(p, name.into());
+		macro_rules! one {
+			($variant: ident) => {{
+				let a = p.parse::<CalcSum>()?;
+				Self::$variant(function, a, p.parse_if_peek::<T![')']>()?)
+			}};
+		}
+		macro_rules! two {
+			($variant: ident) => {{
+				let a = p.parse::<CalcSum>()?;
+				let comma = p.parse::<T![,]>()?;
+				let b = p.parse::<CalcSum>()?;
+				Self::$variant(function, a, comma, b, p.parse_if_peek::<T![')']>()?)
+			}};
+		}
+		Ok(match name {
+			MathFunctionName::Calc(_) => one!(Calc),
+			MathFunctionName::Min(_) => Self::Min(function, parse_sum_list(p)?, p.parse_if_peek::<T![')']>()?),
+			MathFunctionName::Max(_) => Self::Max(function, parse_sum_list(p)?, p.parse_if_peek::<T![')']>()?),
+			MathFunctionName::Clamp(_) => {
+				let min = p.parse::<CalcSum>()?;
+				let first_comma = p.parse::<T![,]>()?;
+				let value = p.parse::<CalcSum>()?;
+				let second_comma = p.parse::<T![,]>()?;
+				let max = p.parse::<CalcSum>()?;
+				Self::Clamp(function, min, first_comma, value, second_comma, max, p.parse_if_peek::<T![')']>()?)
+			}
+			MathFunctionName::Round(_) => {
+				let strategy = if let Some(strategy) = p.parse_if_peek::<RoundingStrategyKeyword>()? {
+					Some((strategy, p.parse::<T![,]>()?))
+				} else {
+					None
+				};
+				let a = p.parse::<CalcSum>()?;
+				let b = if let Some(comma) = p.parse_if_peek::<T![,]>()? {
+					Some((comma, p.parse::<CalcSum>()?))
+				} else {
+					None
+				};
+				Self::Round(function, strategy, a, b, p.parse_if_peek::<T![')']>()?)
+			}
+			MathFunctionName::Mod(_) => two!(Mod),
+			MathFunctionName::Rem(_) => two!(Rem),
+			MathFunctionName::Sin(_) => one!(Sin),
+			MathFunctionName::Cos(_) => one!(Cos),
+			MathFunctionName::Tan(_) => one!(Tan),
+			MathFunctionName::Asin(_) => one!(Asin),
+			MathFunctionName::Acos(_) => one!(Acos),
+			MathFunctionName::Atan(_) => one!(Atan),
+			MathFunctionName::Atan2(_) => two!(Atan2),
+			MathFunctionName::Pow(_) => two!(Pow),
+			MathFunctionName::Sqrt(_) => one!(Sqrt),
+			MathFunctionName::Hypot(_) => Self::Hypot(function, parse_sum_list(p)?, p.parse_if_peek::<T![')']>()?),
+			MathFunctionName::Log(_) => {
+				let a = p.parse::<CalcSum>()?;
+				let base = if let Some(comma) = p.parse_if_peek::<T![,]>()? {
+					Some((comma, p.parse::<CalcSum>()?))
+				} else {
+					None
+				};
+				Self::Log(function, a, base, p.parse_if_peek::<T![')']>()?)
+			}
+			MathFunctionName::Exp(_) => one!(Exp),
+			MathFunctionName::Abs(_) => one!(Abs),
+			MathFunctionName::Sign(_) => one!(Sign),
+		})
+	}
+}
+
+impl<'a> ToCursors for MathFunction<'a> {
+	fn to_cursors(&self, s: &mut impl CursorSink) {
+		let close = match self {
+			Self::Calc(function, a, close)
+			| Self::Sin(function, a, close)
+			| Self::Cos(function, a, close)
+			| Self::Tan(function, a, close)
+			| Self::Asin(function, a, close)
+			| Self::Acos(function, a, close)
+			| Self::Atan(function, a, close)
+			| Self::Sqrt(function, a, close)
+			| Self::Exp(function, a, close)
+			| Self::Abs(function, a, close)
+			| Self::Sign(function, a, close) => {
+				s.append(function.into());
+				ToCursors::to_cursors(a, s);
+				close
+			}
+			Self::Min(function, args, close)
+			| Self::Max(function, args, close)
+			| Self::Hypot(function, args, close) => {
+				s.append(function.into());
+				for (arg, comma) in args {
+					ToCursors::to_cursors(arg, s);
+					if let Some(comma) = comma {
+						s.append(comma.into());
+					}
+				}
+				close
+			}
+			Self::Clamp(function, min, first_comma, value, second_comma, max, close) => {
+				s.append(function.into());
+				ToCursors::to_cursors(min, s);
+				s.append(first_comma.into());
+				ToCursors::to_cursors(value, s);
+				s.append(second_comma.into());
+				ToCursors::to_cursors(max, s);
+				close
+			}
+			Self::Round(function, strategy, a, b, close) => {
+				s.append(function.into());
+				if let Some((strategy, comma)) = strategy {
+					s.append(strategy.into());
+					s.append(comma.into());
+				}
+				ToCursors::to_cursors(a, s);
+				if let Some((comma, b)) = b {
+					s.append(comma.into());
+					ToCursors::to_cursors(b, s);
+				}
+				close
+			}
+			Self::Mod(function, a, comma, b, close)
+			| Self::Rem(function, a, comma, b, close)
+			| Self::Atan2(function, a, comma, b, close)
+			| Self::Pow(function, a, comma, b, close) => {
+				s.append(function.into());
+				ToCursors::to_cursors(a, s);
+				s.append(comma.into());
+				ToCursors::to_cursors(b, s);
+				close
+			}
+			Self::Log(function, a, base, close) => {
+				s.append(function.into());
+				ToCursors::to_cursors(a, s);
+				if let Some((comma, base)) = base {
+					s.append(comma.into());
+					ToCursors::to_cursors(base, s);
+				}
+				close
+			}
+		};
+		if let Some(close) = close {
+			s.append(close.into());
+		}
+	}
+}
+
+/// A `+` or `-` in a [CalcSum]. These must be surrounded by whitespace, as `1px -2px` would otherwise be two
+/// dimensions, so the whitespace on either side is kept alongside the operator.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
+pub enum CalcSumOperator {
+	Plus(T![' '], T![+], T![' ']),
+	Minus(T![' '], T![-], T![' ']),
+}
+
+impl<'a> Parse<'a> for CalcSumOperator {
+	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
+		let before = p.parse::<T![' ']>()?;
+		if let Some(plus) = p.parse_if_peek::<T![+]>()? {
+			Ok(Self::Plus(before, plus, p.parse::<T![' ']>()?))
+		} else {
+			let minus = p.parse::<T![-]>()?;
+			Ok(Self::Minus(before, minus, p.parse::<T![' ']>()?))
+		}
+	}
+}
+
+impl ToCursors for CalcSumOperator {
+	fn to_cursors(&self, s: &mut impl CursorSink) {
+		let (before, operator, after) = match self {
+			Self::Plus(before, plus, after) => (before.into(), plus.into(), after.into()),
+			Self::Minus(before, minus, after) => (before.into(), minus.into(), after.into()),
+		};
+		s.append(before);
+		s.append(operator);
+		s.append(after);
+	}
+}
+
+/// Represents a `<calc-sum>`: one or more [CalcProducts][CalcProduct], separated by `+` or `-`. The first product
+/// never has an operator.
+///
+/// ```md
+/// <calc-sum>
+///  │├─ <calc-product> ─╭──────────────────────────────────╮─┤│
+///                      ╰─╭─ "+" ─╮─ <calc-product> ─╮─────╯
+///                        ╰─ "-" ─╯                  │
+///                      ╭────────────────────────────╯
+///                      ╰─ (repeats) ─╯
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
+pub struct CalcSum<'a>(pub Vec<'a, (Option<CalcSumOperator>, CalcProduct<'a>)>);
+
+impl<'a> Parse<'a> for CalcSum<'a> {
+	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
+		let mut terms = Vec::new_in(p.bump());
+		terms.push((None, p.parse::<CalcProduct>()?));
+		while let Ok(operator) = p.try_parse::<CalcSumOperator>() {
+			terms.push((Some(operator), p.parse::<CalcProduct>()?));
+		}
+		Ok(Self(terms))
+	}
+}
+
+impl<'a> ToCursors for CalcSum<'a> {
+	fn to_cursors(&self, s: &mut impl CursorSink) {
+		for (operator, product) in &self.0 {
+			if let Some(operator) = operator {
+				ToCursors::to_cursors(operator, s);
+			}
+			ToCursors::to_cursors(product, s);
+		}
+	}
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
+pub enum CalcProductOperator {
+	Multiply(T![*]),
+	Divide(T![/]),
+}
+
+/// Represents a `<calc-product>`: one or more [CalcValues][CalcValue], separated by `*` or `/`. The first value never
+/// has an operator.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
+pub struct CalcProduct<'a>(pub Vec<'a, (Option<CalcProductOperator>, CalcValue<'a>)>);
+
+impl<'a> Parse<'a> for CalcProduct<'a> {
+	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
+		let mut values = Vec::new_in(p.bump());
+		values.push((None, p.parse::<CalcValue>()?));
+		loop {
+			let operator = if let Some(star) = p.parse_if_peek::<T![*]>()? {
+				CalcProductOperator::Multiply(star)
+			} else if let Some(slash) = p.parse_if_peek::<T![/]>()? {
+				CalcProductOperator::Divide(slash)
+			} else {
+				return Ok(Self(values));
+			};
+			values.push((Some(operator), p.parse::<CalcValue>()?));
+		}
+	}
+}
+
+impl<'a> ToCursors for CalcProduct<'a> {
+	fn to_cursors(&self, s: &mut impl CursorSink) {
+		for (operator, value) in &self.0 {
+			match operator {
+				Some(CalcProductOperator::Multiply(c)) => s.append(c.into()),
+				Some(CalcProductOperator::Divide(c)) => s.append(c.into()),
+				None => {}
+			}
+			ToCursors::to_cursors(value, s);
+		}
+	}
+}
+
+// https://drafts.csswg.org/css-values/#typedef-calc-value
+// <calc-value> = <number> | <dimension> | <percentage> | <calc-keyword> | ( <calc-sum> )
+//
+// Channel keywords (such as `r` or `alpha`) are also accepted within the channels of a relative color, where they can
+// be used in math functions: https://drafts.csswg.org/css-color-5/#relative-colors
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
+pub enum CalcValue<'a> {
+	Number(T![Number]),
+	Dimension(T![Dimension]),
+	Constant(CalcConstant),
+	Channel(ChannelKeyword),
+	Parenthesized(T!['('], CalcSum<'a>, Option<T![')']>),
+	Function(MathFunction<'a>),
+}
+
+impl<'a> Parse<'a> for CalcValue<'a> {
+	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
+		if let Some(number) = p.parse_if_peek::<T![Number]>()? {
+			Ok(Self::Number(number))
+		} else if let Some(dimension) = p.parse_if_peek::<T![Dimension]>()? {
+			Ok(Self::Dimension(dimension))
+		} else if let Some(constant) = p.parse_if_peek::<CalcConstant>()? {
+			Ok(Self::Constant(constant))
+		} else if p.is(State::RelativeColor) && p.peek::<ChannelKeyword>() {
+			p.parse::<ChannelKeyword>().map(Self::Channel)
+		} else if let Some(open) = p.parse_if_peek::<T!['(']>()? {
+			let sum = p.parse::<CalcSum>()?;
+			Ok(Self::Parenthesized(open, sum, p.parse_if_peek::<T![')']>()?))
+		} else if p.peek::<MathFunction>() {
+			p.parse::<MathFunction>().map(Self::Function)
+		} else {
+			let c = p.peek_n(1);
+			Err(diagnostics::Unexpected(c.into(), c.into()))?
+		}
+	}
+}
+
+impl<'a> ToCursors for CalcValue<'a> {
+	fn to_cursors(&self, s: &mut impl CursorSink) {
+		match self {
+			Self::Number(c) => s.append(c.into()),
+			Self::Dimension(c) => s.append(c.into()),
+			Self::Constant(c) => s.append(c.into()),
+			Self::Channel(c) => s.append(c.into()),
+			Self::Parenthesized(open, sum, close) => {
+				s.append(open.into());
+				ToCursors::to_cursors(sum, s);
+				if let Some(close) = close {
+					s.append(close.into());
+				}
+			}
+			Self::Function(function) => ToCursors::to_cursors(function, s),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use css_parse::{assert_parse, assert_parse_error};
+
+	#[test]
+	fn size_test() {
+		assert_eq!(std::mem::size_of::<MathFunction>(), 152);
+		assert_eq!(std::mem::size_of::<CalcSum>(), 32);
+		assert_eq!(std::mem::size_of::<CalcValue>(), 152);
+	}
+
+	#[test]
+	fn test_writes() {
+		assert_parse!(MathFunction, "calc(1px)");
+		assert_parse!(MathFunction, "calc(1px + 2px)");
+		assert_parse!(MathFunction, "calc(1px  -  2px)");
+		assert_parse!(MathFunction, "calc(100% - 2em * 3)", "calc(100% - 2em*3)");
+		assert_parse!(MathFunction, "calc((1px + 2px)/3)");
+		assert_parse!(MathFunction, "calc(pi*2)");
+		assert_parse!(MathFunction, "calc(-infinity)");
+		assert_parse!(MathFunction, "calc(1px*min(2,3))");
+		assert_parse!(MathFunction, "min(1px,2em,3%)");
+		assert_parse!(MathFunction, "max(1px)");
+		assert_parse!(MathFunction, "clamp(1rem,2.5vw,2rem)");
+		assert_parse!(MathFunction, "round(up,10.5px,1px)");
+		assert_parse!(MathFunction, "round(10.5)");
+		assert_parse!(MathFunction, "mod(-18px,5px)");
+		assert_parse!(MathFunction, "rem(18px,5px)");
+		assert_parse!(MathFunction, "sin(45deg)");
+		assert_parse!(MathFunction, "atan2(1,-1)");
+		assert_parse!(MathFunction, "pow(2,10)");
+		assert_parse!(MathFunction, "hypot(3px,4px)");
+		assert_parse!(MathFunction, "log(8,2)");
+		assert_parse!(MathFunction, "abs(-1px)");
+		assert_parse!(MathFunction, "sign(-1px)");
+		assert_parse!(MathFunction, "CALC(1px)");
+	}
+
+	#[test]
+	fn test_errors() {
+		assert_parse_error!(MathFunction, "calc()");
+		assert_parse_error!(MathFunction, "calc(1px +)");
+		assert_parse_error!(MathFunction, "calc(1px +2px)");
+		assert_parse_error!(MathFunction, "calc(1px -2px)");
+		assert_parse_error!(MathFunction, "calc(var(--x))");
+		assert_parse_error!(MathFunction, "clamp(1px, 2px)");
+		assert_parse_error!(MathFunction, "foo(1px)");
+		// Channel keywords are only valid within relative colors
+		assert_parse_error!(MathFunction, "calc(l + 0.1)");
+	}
+}
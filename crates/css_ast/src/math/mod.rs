@@ -0,0 +1,9 @@
+mod evaluate;
+mod function;
+mod node;
+mod simplify;
+mod ty;
+
+pub use function::*;
+pub use node::*;
+pub use ty::*;
@@ -0,0 +1,468 @@
+use core::fmt;
+
+use css_lexer::{Cursor, DimensionUnit};
+
+use super::{
+	BaseType, CalcProduct, CalcProductOperator, CalcSum, CalcSumList, CalcSumOperator, CalcType, CalcValue,
+	MathFunction, RoundingStrategyKeyword,
+};
+
+// https://drafts.csswg.org/css-values/#typedef-rounding-strategy
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(rename_all = "kebab-case"))]
+pub enum RoundingStrategy {
+	#[default]
+	Nearest,
+	Up,
+	Down,
+	ToZero,
+}
+
+impl From<RoundingStrategyKeyword> for RoundingStrategy {
+	fn from(value: RoundingStrategyKeyword) -> Self {
+		match value {
+			RoundingStrategyKeyword::Nearest(_) => Self::Nearest,
+			RoundingStrategyKeyword::Up(_) => Self::Up,
+			RoundingStrategyKeyword::Down(_) => Self::Down,
+			RoundingStrategyKeyword::ToZero(_) => Self::ToZero,
+		}
+	}
+}
+
+/// The math functions other than `calc()`, as they appear in a [CalcNode::Function].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(rename_all = "kebab-case"))]
+pub enum CalcOperation {
+	Min,
+	Max,
+	Clamp,
+	Round(RoundingStrategy),
+	Mod,
+	Rem,
+	Sin,
+	Cos,
+	Tan,
+	Asin,
+	Acos,
+	Atan,
+	Atan2,
+	Pow,
+	Sqrt,
+	Hypot,
+	Log,
+	Exp,
+	Abs,
+	Sign,
+}
+
+impl CalcOperation {
+	pub fn name(&self) -> &'static str {
+		match self {
+			Self::Min => "min",
+			Self::Max => "max",
+			Self::Clamp => "clamp",
+			Self::Round(_) => "round",
+			Self::Mod => "mod",
+			Self::Rem => "rem",
+			Self::Sin => "sin",
+			Self::Cos => "cos",
+			Self::Tan => "tan",
+			Self::Asin => "asin",
+			Self::Acos => "acos",
+			Self::Atan => "atan",
+			Self::Atan2 => "atan2",
+			Self::Pow => "pow",
+			Self::Sqrt => "sqrt",
+			Self::Hypot => "hypot",
+			Self::Log => "log",
+			Self::Exp => "exp",
+			Self::Abs => "abs",
+			Self::Sign => "sign",
+		}
+	}
+}
+
+/// A [calculation tree](https://drafts.csswg.org/css-values/#calculation-tree): the result of parsing a
+/// [MathFunction], with the source tokens thrown away.
+///
+/// Subtraction is represented as the addition of a [CalcNode::Negate], and division as the multiplication of a
+/// [CalcNode::Invert]. Nested `calc()`s and parentheses disappear, as they only group their contents.
+///
+/// A [CalcNode::Channel] is a channel keyword of a relative color, such as `r` or `alpha`, which must be
+/// [resolved][CalcNode::resolve_channels] before the tree can be evaluated.
+///
+/// A tree can be [type checked][CalcNode::resolve_type], [simplified][CalcNode::simplify] and
+/// [evaluated][CalcNode::evaluate], and is written back out as CSS with its [Display][fmt::Display] implementation.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize),
+	serde(tag = "type", content = "value", rename_all = "kebab-case")
+)]
+pub enum CalcNode {
+	Number(f32),
+	Dimension(f32, DimensionUnit),
+	Channel(&'static str),
+	Sum(Vec<CalcNode>),
+	Product(Vec<CalcNode>),
+	Negate(Box<CalcNode>),
+	Invert(Box<CalcNode>),
+	Function(CalcOperation, Vec<CalcNode>),
+}
+
+impl<'a> From<&MathFunction<'a>> for CalcNode {
+	fn from(value: &MathFunction<'a>) -> Self {
+		let list = |args: &CalcSumList| args.iter().map(|(arg, _)| CalcNode::from(arg)).collect();
+		let function =
+			|op, args: &[&CalcSum]| Self::Function(op, args.iter().map(|arg| CalcNode::from(*arg)).collect());
+		match value {
+			MathFunction::Calc(_, a, _) => a.into(),
+			MathFunction::Min(_, args, _) => Self::Function(CalcOperation::Min, list(args)),
+			MathFunction::Max(_, args, _) => Self::Function(CalcOperation::Max, list(args)),
+			MathFunction::Clamp(_, min, _, value, _, max, _) => function(CalcOperation::Clamp, &[min, value, max]),
+			MathFunction::Round(_, strategy, a, b, _) => {
+				let op = CalcOperation::Round(strategy.map(|(s, _)| s.into()).unwrap_or_default());
+				match b {
+					Some((_, b)) => function(op, &[a, b]),
+					None => function(op, &[a]),
+				}
+			}
+			MathFunction::Mod(_, a, _, b, _) => function(CalcOperation::Mod, &[a, b]),
+			MathFunction::Rem(_, a, _, b, _) => function(CalcOperation::Rem, &[a, b]),
+			MathFunction::Sin(_, a, _) => function(CalcOperation::Sin, &[a]),
+			MathFunction::Cos(_, a, _) => function(CalcOperation::Cos, &[a]),
+			MathFunction::Tan(_, a, _) => function(CalcOperation::Tan, &[a]),
+			MathFunction::Asin(_, a, _) => function(CalcOperation::Asin, &[a]),
+			MathFunction::Acos(_, a, _) => function(CalcOperation::Acos, &[a]),
+			MathFunction::Atan(_, a, _) => function(CalcOperation::Atan, &[a]),
+			MathFunction::Atan2(_, a, _, b, _) => function(CalcOperation::Atan2, &[a, b]),
+			MathFunction::Pow(_, a, _, b, _) => function(CalcOperation::Pow, &[a, b]),
+			MathFunction::Sqrt(_, a, _) => function(CalcOperation::Sqrt, &[a]),
+			MathFunction::Hypot(_, args, _) => Self::Function(CalcOperation::Hypot, list(args)),
+			MathFunction::Log(_, a, base, _) => match base {
+				Some((_, base)) => function(CalcOperation::Log, &[a, base]),
+				None => function(CalcOperation::Log, &[a]),
+			},
+			MathFunction::Exp(_, a, _) => function(CalcOperation::Exp, &[a]),
+			MathFunction::Abs(_, a, _) => function(CalcOperation::Abs, &[a]),
+			MathFunction::Sign(_, a, _) => function(CalcOperation::Sign, &[a]),
+		}
+	}
+}
+
+impl<'a> From<&CalcSum<'a>> for CalcNode {
+	fn from(value: &CalcSum<'a>) -> Self {
+		let mut children: Vec<CalcNode> = value
+			.0
+			.iter()
+			.map(|(operator, product)| match operator {
+				Some(CalcSumOperator::Minus(..)) => Self::Negate(Box::new(product.into())),
+				_ => product.into(),
+			})
+			.collect();
+		if children.len() == 1 {
+			children.pop().unwrap()
+		} else {
+			Self::Sum(children)
+		}
+	}
+}
+
+impl<'a> From<&CalcProduct<'a>> for CalcNode {
+	fn from(value: &CalcProduct<'a>) -> Self {
+		let mut children: Vec<CalcNode> = value
+			.0
+			.iter()
+			.map(|(operator, value)| match operator {
+				Some(CalcProductOperator::Divide(_)) => Self::Invert(Box::new(value.into())),
+				_ => value.into(),
+			})
+			.collect();
+		if children.len() == 1 {
+			children.pop().unwrap()
+		} else {
+			Self::Product(children)
+		}
+	}
+}
+
+impl<'a> From<&CalcValue<'a>> for CalcNode {
+	fn from(value: &CalcValue<'a>) -> Self {
+		match value {
+			CalcValue::Number(number) => Self::Number(Cursor::from(*number).token().value()),
+			CalcValue::Dimension(dimension) => {
+				let token = Cursor::from(*dimension).token();
+				Self::Dimension(token.value(), token.dimension_unit())
+			}
+			CalcValue::Constant(constant) => Self::Number(constant.value()),
+			CalcValue::Channel(channel) => Self::Channel(channel.name()),
+			CalcValue::Parenthesized(_, sum, _) => sum.into(),
+			CalcValue::Function(function) => function.into(),
+		}
+	}
+}
+
+impl CalcNode {
+	/// Determine the [type][CalcType] of this expression, returning [None] if the expression is invalid, for example
+	/// because it adds a length to a time, or uses a unit which is not known.
+	///
+	/// https://drafts.csswg.org/css-values/#calc-type-checking
+	pub fn resolve_type(&self) -> Option<CalcType> {
+		match self {
+			// Channel keywords always resolve to a <number>.
+			Self::Number(_) | Self::Channel(_) => Some(CalcType::NUMBER),
+			Self::Dimension(_, unit) => CalcType::from_unit(*unit),
+			Self::Sum(children) => children.iter().try_fold(None, |ty: Option<CalcType>, child| {
+				let child = child.resolve_type()?;
+				match ty {
+					Some(ty) => ty.try_add(child).map(Some),
+					None => Some(Some(child)),
+				}
+			})?,
+			Self::Product(children) => {
+				children.iter().try_fold(CalcType::NUMBER, |ty, child| ty.try_multiply(child.resolve_type()?))
+			}
+			Self::Negate(child) => child.resolve_type(),
+			Self::Invert(child) => child.resolve_type()?.invert(),
+			Self::Function(op, args) => {
+				let mut types = args.iter().map(CalcNode::resolve_type);
+				let first = types.next()??;
+				let same = types.try_fold(first, |ty, arg| ty.try_add(arg?))?;
+				match op {
+					CalcOperation::Min
+					| CalcOperation::Max
+					| CalcOperation::Clamp
+					| CalcOperation::Round(_)
+					| CalcOperation::Mod
+					| CalcOperation::Rem
+					| CalcOperation::Hypot
+					| CalcOperation::Abs => Some(same),
+					CalcOperation::Sign => Some(CalcType::NUMBER),
+					CalcOperation::Sin | CalcOperation::Cos | CalcOperation::Tan => {
+						(same.is_number() || same.matches(BaseType::Angle)).then_some(CalcType::NUMBER)
+					}
+					CalcOperation::Asin | CalcOperation::Acos | CalcOperation::Atan => {
+						same.is_number().then_some(CalcType::new(BaseType::Angle))
+					}
+					CalcOperation::Atan2 => Some(CalcType::new(BaseType::Angle)),
+					CalcOperation::Pow | CalcOperation::Sqrt | CalcOperation::Log | CalcOperation::Exp => {
+						same.is_number().then_some(CalcType::NUMBER)
+					}
+				}
+			}
+		}
+	}
+
+	pub(crate) fn is_leaf(&self) -> bool {
+		matches!(self, Self::Number(_) | Self::Dimension(_, _))
+	}
+
+	fn write(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Number(value) => write_number(*value, f),
+			Self::Channel(name) => f.write_str(name),
+			Self::Dimension(value, unit) => {
+				let unit: &'static str = (*unit).into();
+				if value.is_finite() {
+					write!(f, "{}{}", value, unit)
+				} else {
+					write_number(*value, f)?;
+					write!(f, " * 1{}", unit)
+				}
+			}
+			Self::Sum(children) => {
+				for (i, child) in children.iter().enumerate() {
+					match child {
+						_ if i == 0 => child.write_operand(f, false)?,
+						Self::Negate(inner) => {
+							f.write_str(" - ")?;
+							inner.write_operand(f, false)?;
+						}
+						Self::Number(value) if value.is_sign_negative() => {
+							f.write_str(" - ")?;
+							Self::Number(-value).write(f)?;
+						}
+						Self::Dimension(value, unit) if value.is_sign_negative() => {
+							f.write_str(" - ")?;
+							Self::Dimension(-value, *unit).write(f)?;
+						}
+						_ => {
+							f.write_str(" + ")?;
+							child.write_operand(f, false)?;
+						}
+					}
+				}
+				Ok(())
+			}
+			Self::Product(children) => {
+				for (i, child) in children.iter().enumerate() {
+					match child {
+						Self::Invert(inner) => {
+							f.write_str(if i == 0 { "1 / " } else { " / " })?;
+							inner.write_operand(f, true)?;
+						}
+						_ => {
+							if i != 0 {
+								f.write_str(" * ")?;
+							}
+							child.write_operand(f, true)?;
+						}
+					}
+				}
+				Ok(())
+			}
+			Self::Negate(inner) => {
+				f.write_str("-1 * ")?;
+				inner.write_operand(f, true)
+			}
+			Self::Invert(inner) => {
+				f.write_str("1 / ")?;
+				inner.write_operand(f, true)
+			}
+			Self::Function(op, args) => {
+				f.write_str(op.name())?;
+				f.write_str("(")?;
+				match op {
+					CalcOperation::Round(RoundingStrategy::Up) => f.write_str("up, ")?,
+					CalcOperation::Round(RoundingStrategy::Down) => f.write_str("down, ")?,
+					CalcOperation::Round(RoundingStrategy::ToZero) => f.write_str("to-zero, ")?,
+					_ => {}
+				}
+				for (i, arg) in args.iter().enumerate() {
+					if i != 0 {
+						f.write_str(", ")?;
+					}
+					arg.write(f)?;
+				}
+				f.write_str(")")
+			}
+		}
+	}
+
+	// Sums always need parentheses when they are an operand, while products (and the negations & inversions which are
+	// written as products) only need them when they are an operand of another product.
+	fn write_operand(&self, f: &mut fmt::Formatter<'_>, in_product: bool) -> fmt::Result {
+		let parenthesize = match self {
+			Self::Sum(_) => true,
+			Self::Product(_) | Self::Negate(_) | Self::Invert(_) => in_product,
+			Self::Number(_) | Self::Dimension(_, _) | Self::Channel(_) | Self::Function(_, _) => false,
+		};
+		if parenthesize {
+			f.write_str("(")?;
+			self.write(f)?;
+			f.write_str(")")
+		} else {
+			self.write(f)
+		}
+	}
+}
+
+// https://drafts.csswg.org/css-values/#calc-serialize
+fn write_number(value: f32, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+	if value.is_nan() {
+		f.write_str("NaN")
+	} else if value == f32::INFINITY {
+		f.write_str("infinity")
+	} else if value == f32::NEG_INFINITY {
+		f.write_str("-infinity")
+	} else {
+		write!(f, "{}", value)
+	}
+}
+
+/// Writes the tree as CSS. Finite numbers and dimensions are written on their own, other math functions are written
+/// as themselves, and everything else is wrapped in `calc()`.
+///
+/// Note that dropping the `calc()` around a single value is not always safe: `width: calc(-1px)` is valid (and clamped
+/// to `0px`) while `width: -1px` is not, so the value should be checked against the property's range first.
+impl fmt::Display for CalcNode {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Number(value) | Self::Dimension(value, _) if value.is_finite() => self.write(f),
+			Self::Function(_, _) => self.write(f),
+			_ => {
+				f.write_str("calc(")?;
+				self.write(f)?;
+				f.write_str(")")
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use bumpalo::Bump;
+	use css_parse::Parser;
+
+	fn node(source_text: &str) -> CalcNode {
+		let bump = Bump::default();
+		let function = Parser::new(&bump, source_text).parse_entirely::<MathFunction>().output.unwrap();
+		CalcNode::from(&function)
+	}
+
+	fn ty(source_text: &str) -> Option<CalcType> {
+		node(source_text).resolve_type()
+	}
+
+	#[test]
+	fn test_from_math_function() {
+		assert_eq!(node("calc(1px)"), CalcNode::Dimension(1.0, DimensionUnit::Px));
+		assert_eq!(
+			node("calc(1px - 2px)"),
+			CalcNode::Sum(vec![
+				CalcNode::Dimension(1.0, DimensionUnit::Px),
+				CalcNode::Negate(Box::new(CalcNode::Dimension(2.0, DimensionUnit::Px)))
+			])
+		);
+		assert_eq!(
+			node("calc(pi / (2))"),
+			CalcNode::Product(vec![
+				CalcNode::Number(std::f32::consts::PI),
+				CalcNode::Invert(Box::new(CalcNode::Number(2.0)))
+			])
+		);
+		assert_eq!(
+			node("round(down, 1.5)"),
+			CalcNode::Function(CalcOperation::Round(RoundingStrategy::Down), vec![CalcNode::Number(1.5)])
+		);
+	}
+
+	#[test]
+	fn test_resolve_type() {
+		assert_eq!(ty("calc(1px + 2em)"), Some(CalcType::new(BaseType::Length)));
+		assert_eq!(ty("calc(1px * 2)"), Some(CalcType::new(BaseType::Length)));
+		assert_eq!(ty("calc(10px / 2px)"), Some(CalcType::NUMBER));
+		assert_eq!(ty("calc(1px + 1s)"), None);
+		assert_eq!(ty("calc(1px + 1)"), None);
+		assert_eq!(ty("calc(1px + 1foo)"), None);
+		assert!(ty("calc(100% - 1px)").unwrap().matches_percentage(BaseType::Length));
+		assert!(ty("min(10%, 5%)").unwrap().matches(BaseType::Percent));
+		assert_eq!(ty("sin(1px)"), None);
+		assert_eq!(ty("sin(45deg)"), Some(CalcType::NUMBER));
+		assert_eq!(ty("atan2(1px, 2px)"), Some(CalcType::new(BaseType::Angle)));
+		assert_eq!(ty("sign(-2em)"), Some(CalcType::NUMBER));
+		assert_eq!(ty("pow(2px, 2)"), None);
+	}
+
+	#[test]
+	fn test_resolve_type_overflow() {
+		// Exponents are an i8, so 200 lengths multiplied or divided together can't be typed.
+		assert_eq!(ty(&format!("calc(1px{})", "*1px".repeat(199))), None);
+		assert_eq!(ty(&format!("calc(1{})", "/1px".repeat(200))), None);
+		assert_eq!(ty(&format!("calc(1px{}/1px)", "*1px".repeat(126))).unwrap().exponent(BaseType::Length), 126);
+	}
+
+	#[test]
+	fn test_display() {
+		assert_eq!(node("calc(1px)").to_string(), "1px");
+		assert_eq!(node("calc(1px + 2em - 3%)").to_string(), "calc(1px + 2em - 3%)");
+		assert_eq!(node("calc((1px + 2em) * 3)").to_string(), "calc((1px + 2em) * 3)");
+		assert_eq!(node("calc(1px / (2 * 3))").to_string(), "calc(1px / (2 * 3))");
+		assert_eq!(node("calc(1px - (2em + 3%))").to_string(), "calc(1px - (2em + 3%))");
+		assert_eq!(node("min(1px, calc(2em + 1px))").to_string(), "min(1px, 2em + 1px)");
+		assert_eq!(node("round(up, 1.5, 1)").to_string(), "round(up, 1.5, 1)");
+		assert_eq!(node("calc(infinity * 1px)").to_string(), "calc(infinity * 1px)");
+		assert_eq!(node("calc(-infinity)").to_string(), "calc(-infinity)");
+	}
+}
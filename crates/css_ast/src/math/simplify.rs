@@ -0,0 +1,353 @@
+use css_lexer::DimensionUnit;
+
+use super::{to_canonical, BaseType, CalcNode, CalcOperation, RoundingStrategy};
+
+impl CalcOperation {
+	/// Apply this operation to already resolved arguments. All arguments must be in the same unit, other than the
+	/// trigonometric functions which take and return radians.
+	pub(crate) fn apply(&self, args: &[f32]) -> Option<f32> {
+		let arg = |i: usize| args.get(i).copied();
+		Some(match self {
+			Self::Min => args.iter().copied().reduce(f32::min)?,
+			Self::Max => args.iter().copied().reduce(f32::max)?,
+			// https://drafts.csswg.org/css-values/#funcdef-clamp
+			Self::Clamp => arg(0)?.max(arg(1)?.min(arg(2)?)),
+			// https://drafts.csswg.org/css-values/#funcdef-round
+			Self::Round(strategy) => {
+				let (a, b) = (arg(0)?, arg(1).unwrap_or(1.0));
+				if b == 0.0 {
+					return Some(f32::NAN);
+				}
+				b * match strategy {
+					// Ties round towards positive infinity.
+					RoundingStrategy::Nearest => (a / b + 0.5).floor(),
+					RoundingStrategy::Up => (a / b).ceil(),
+					RoundingStrategy::Down => (a / b).floor(),
+					RoundingStrategy::ToZero => (a / b).trunc(),
+				}
+			}
+			// mod() takes the sign of the divisor, while rem() takes the sign of the dividend.
+			Self::Mod => {
+				let (a, b) = (arg(0)?, arg(1)?);
+				a - b * (a / b).floor()
+			}
+			Self::Rem => arg(0)? % arg(1)?,
+			Self::Sin => arg(0)?.sin(),
+			Self::Cos => arg(0)?.cos(),
+			Self::Tan => arg(0)?.tan(),
+			Self::Asin => arg(0)?.asin(),
+			Self::Acos => arg(0)?.acos(),
+			Self::Atan => arg(0)?.atan(),
+			Self::Atan2 => arg(0)?.atan2(arg(1)?),
+			Self::Pow => arg(0)?.powf(arg(1)?),
+			Self::Sqrt => arg(0)?.sqrt(),
+			Self::Hypot => args.iter().map(|a| a * a).sum::<f32>().sqrt(),
+			Self::Log => match arg(1) {
+				Some(base) => arg(0)?.ln() / base.ln(),
+				None => arg(0)?.ln(),
+			},
+			Self::Exp => arg(0)?.exp(),
+			Self::Abs => arg(0)?.abs(),
+			Self::Sign => {
+				let a = arg(0)?;
+				if a == 0.0 || a.is_nan() {
+					a
+				} else {
+					a.signum()
+				}
+			}
+		})
+	}
+}
+
+// Find the unit all of the given leaves can be expressed in: the unit they share, or the canonical unit if they can
+// all be converted to it. Numbers are unitless and so only share a "unit" with other numbers.
+fn common_unit(leaves: &[CalcNode]) -> Option<Option<DimensionUnit>> {
+	let unit = |node: &CalcNode| match node {
+		CalcNode::Number(_) => Some(None),
+		CalcNode::Dimension(_, unit) => Some(Some(*unit)),
+		_ => None,
+	};
+	let first = unit(leaves.first()?)?;
+	if leaves.iter().all(|leaf| unit(leaf) == Some(first)) {
+		return Some(first);
+	}
+	let canonical = |node: &CalcNode| match node {
+		CalcNode::Dimension(value, unit) => to_canonical(*value, *unit).map(|(_, unit)| unit),
+		_ => None,
+	};
+	let first = canonical(&leaves[0])?;
+	leaves.iter().all(|leaf| canonical(leaf) == Some(first)).then_some(Some(first))
+}
+
+// The value of a leaf in the given unit, which must have come from `common_unit`.
+fn value_in(leaf: &CalcNode, unit: Option<DimensionUnit>) -> f32 {
+	match (leaf, unit) {
+		(CalcNode::Dimension(value, from), Some(to)) if *from != to => to_canonical(*value, *from).unwrap().0,
+		(CalcNode::Number(value) | CalcNode::Dimension(value, _), _) => *value,
+		_ => unreachable!(),
+	}
+}
+
+fn leaf(value: f32, unit: Option<DimensionUnit>) -> CalcNode {
+	match unit {
+		Some(unit) => CalcNode::Dimension(value, unit),
+		None => CalcNode::Number(value),
+	}
+}
+
+impl CalcNode {
+	/// Simplify the tree as far as possible without any outside information: folding constants, combining values
+	/// which share a unit (or can be converted to a common unit), and resolving math functions whose arguments are all
+	/// known. For example `calc(10px + 5px)` simplifies to `15px`, and `calc(1in + 2em + 4px)` to
+	/// `calc(100px + 2em)`.
+	///
+	/// https://drafts.csswg.org/css-values/#calc-simplification
+	pub fn simplify(self) -> CalcNode {
+		match self {
+			Self::Number(_) | Self::Dimension(_, _) | Self::Channel(_) => self,
+			Self::Negate(child) => match child.simplify() {
+				Self::Number(value) => Self::Number(-value),
+				Self::Dimension(value, unit) => Self::Dimension(-value, unit),
+				Self::Negate(inner) => *inner,
+				child => Self::Negate(Box::new(child)),
+			},
+			Self::Invert(child) => match child.simplify() {
+				Self::Number(value) => Self::Number(1.0 / value),
+				Self::Invert(inner) => *inner,
+				child => Self::Invert(Box::new(child)),
+			},
+			Self::Sum(children) => simplify_sum(children),
+			Self::Product(children) => simplify_product(children),
+			Self::Function(op, args) => simplify_function(op, args),
+		}
+	}
+}
+
+fn simplify_sum(children: Vec<CalcNode>) -> CalcNode {
+	let mut terms: Vec<CalcNode> = vec![];
+	for child in children.into_iter().map(CalcNode::simplify) {
+		let flattened = match child {
+			CalcNode::Sum(inner) => inner,
+			child => vec![child],
+		};
+		'next: for child in flattened {
+			if child.is_leaf() {
+				for term in terms.iter_mut().filter(|term| term.is_leaf()) {
+					let pair = [term.clone(), child.clone()];
+					if let Some(unit) = common_unit(&pair) {
+						*term = leaf(value_in(&pair[0], unit) + value_in(&pair[1], unit), unit);
+						continue 'next;
+					}
+				}
+			}
+			terms.push(child);
+		}
+	}
+	if terms.len() == 1 {
+		terms.pop().unwrap()
+	} else {
+		CalcNode::Sum(terms)
+	}
+}
+
+fn simplify_product(children: Vec<CalcNode>) -> CalcNode {
+	let mut factor = 1.0;
+	let mut units: Vec<(DimensionUnit, i8)> = vec![];
+	let mut rest: Vec<CalcNode> = vec![];
+	for child in children.into_iter().map(CalcNode::simplify) {
+		let flattened = match child {
+			CalcNode::Product(inner) => inner,
+			child => vec![child],
+		};
+		for child in flattened {
+			match child {
+				CalcNode::Number(value) => factor *= value,
+				CalcNode::Dimension(value, unit) if unit != DimensionUnit::Unknown => {
+					factor *= value;
+					units.push((unit, 1));
+				}
+				CalcNode::Invert(inner) if matches!(*inner, CalcNode::Dimension(_, unit) if unit != DimensionUnit::Unknown) => {
+					if let CalcNode::Dimension(value, unit) = *inner {
+						factor /= value;
+						units.push((unit, -1));
+					}
+				}
+				child => rest.push(child),
+			}
+		}
+	}
+
+	// Cancel out units which appear in both the numerator and denominator, first as written and then, for units
+	// which can be converted, in their canonical form.
+	let cancel = |units: &mut Vec<(DimensionUnit, i8)>| {
+		let mut i = 0;
+		while i < units.len() {
+			if let Some(j) = units.iter().position(|(unit, exp)| *unit == units[i].0 && *exp == -units[i].1) {
+				units.remove(i.max(j));
+				units.remove(i.min(j));
+				i = 0;
+			} else {
+				i += 1;
+			}
+		}
+	};
+	cancel(&mut units);
+	if units.iter().all(|(unit, _)| to_canonical(1.0, *unit).is_some()) {
+		for (unit, exp) in units.iter_mut() {
+			let (scale, canonical) = to_canonical(1.0, *unit).unwrap();
+			factor *= if *exp > 0 { scale } else { 1.0 / scale };
+			*unit = canonical;
+		}
+		cancel(&mut units);
+	}
+
+	let value = match units.as_slice() {
+		[] => CalcNode::Number(factor),
+		[(unit, 1)] => CalcNode::Dimension(factor, *unit),
+		// The units cannot be resolved into a single dimension, so leave them be (though still combining any numbers).
+		_ => {
+			for (unit, exp) in units {
+				rest.push(if exp > 0 {
+					CalcNode::Dimension(1.0, unit)
+				} else {
+					CalcNode::Invert(Box::new(CalcNode::Dimension(1.0, unit)))
+				});
+			}
+			CalcNode::Number(factor)
+		}
+	};
+	match (value, rest.len()) {
+		(value, 0) => value,
+		// A number multiplied into a sum of values can be distributed across it.
+		(CalcNode::Number(factor), 1) if matches!(&rest[0], CalcNode::Sum(terms) if terms.iter().all(CalcNode::is_leaf)) =>
+		{
+			let Some(CalcNode::Sum(terms)) = rest.pop() else { unreachable!() };
+			simplify_sum(
+				terms.into_iter().map(|term| CalcNode::Product(vec![CalcNode::Number(factor), term])).collect(),
+			)
+		}
+		(CalcNode::Number(factor), 1) if factor == 1.0 => rest.pop().unwrap(),
+		(CalcNode::Number(factor), _) if factor == 1.0 => CalcNode::Product(rest),
+		(value, _) => {
+			rest.insert(0, value);
+			CalcNode::Product(rest)
+		}
+	}
+}
+
+fn simplify_function(op: CalcOperation, args: Vec<CalcNode>) -> CalcNode {
+	let args: Vec<CalcNode> = args.into_iter().map(CalcNode::simplify).collect();
+	// Percentages could resolve against a negative basis, so functions of them cannot be resolved ahead of time.
+	let unit = common_unit(&args).filter(|unit| *unit != Some(DimensionUnit::Percent));
+	let Some(unit) = unit else {
+		// min() & max() with a single argument are the same as that argument.
+		if matches!(op, CalcOperation::Min | CalcOperation::Max) && args.len() == 1 {
+			return args.into_iter().next().unwrap();
+		}
+		return CalcNode::Function(op, args);
+	};
+	let values: Vec<f32> = args.iter().map(|arg| value_in(arg, unit)).collect();
+	let is_angle = unit.and_then(BaseType::from_unit) == Some(BaseType::Angle);
+	let result = match op {
+		CalcOperation::Sin | CalcOperation::Cos | CalcOperation::Tan if is_angle => {
+			op.apply(&values.iter().map(|v| v.to_radians()).collect::<Vec<_>>()).map(CalcNode::Number)
+		}
+		CalcOperation::Sin | CalcOperation::Cos | CalcOperation::Tan if unit.is_none() => {
+			op.apply(&values).map(CalcNode::Number)
+		}
+		CalcOperation::Asin | CalcOperation::Acos | CalcOperation::Atan if unit.is_none() => {
+			op.apply(&values).map(|rad| CalcNode::Dimension(rad.to_degrees(), DimensionUnit::Deg))
+		}
+		CalcOperation::Atan2 => op.apply(&values).map(|rad| CalcNode::Dimension(rad.to_degrees(), DimensionUnit::Deg)),
+		CalcOperation::Pow | CalcOperation::Sqrt | CalcOperation::Log | CalcOperation::Exp if unit.is_none() => {
+			op.apply(&values).map(CalcNode::Number)
+		}
+		CalcOperation::Sign => op.apply(&values).map(CalcNode::Number),
+		// round() without an interval can only round numbers.
+		CalcOperation::Round(_) if args.len() == 1 && unit.is_some() => None,
+		CalcOperation::Min
+		| CalcOperation::Max
+		| CalcOperation::Clamp
+		| CalcOperation::Round(_)
+		| CalcOperation::Mod
+		| CalcOperation::Rem
+		| CalcOperation::Hypot
+		| CalcOperation::Abs => op.apply(&values).map(|value| leaf(value, unit)),
+		_ => None,
+	};
+	result.unwrap_or(CalcNode::Function(op, args))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::MathFunction;
+	use bumpalo::Bump;
+	use css_parse::Parser;
+
+	fn simplify(source_text: &str) -> String {
+		let bump = Bump::default();
+		let function = Parser::new(&bump, source_text).parse_entirely::<MathFunction>().output.unwrap();
+		CalcNode::from(&function).simplify().to_string()
+	}
+
+	#[test]
+	fn test_sums() {
+		assert_eq!(simplify("calc(10px + 5px)"), "15px");
+		assert_eq!(simplify("calc(10px - 15px)"), "-5px");
+		assert_eq!(simplify("calc(1 + 2 + 3)"), "6");
+		assert_eq!(simplify("calc(1in + 2em + 4px)"), "calc(100px + 2em)");
+		assert_eq!(simplify("calc(100% - 10px - 10px)"), "calc(100% - 20px)");
+		assert_eq!(simplify("calc(1em + (2em - 3px))"), "calc(3em - 3px)");
+		assert_eq!(simplify("calc(1s + 500ms)"), "1.5s");
+		assert_eq!(simplify("calc(calc(1px + 1px) + 1px)"), "3px");
+	}
+
+	#[test]
+	fn test_products() {
+		assert_eq!(simplify("calc(2 * 3)"), "6");
+		assert_eq!(simplify("calc(10px * 2)"), "20px");
+		assert_eq!(simplify("calc(10px / 4)"), "2.5px");
+		assert_eq!(simplify("calc(10px / 2px)"), "5");
+		assert_eq!(simplify("calc(1in / 1px)"), "96");
+		assert_eq!(simplify("calc(2 * (10px + 5%))"), "calc(20px + 10%)");
+		assert_eq!(simplify("calc(-1 * (10px + 5%))"), "calc(-10px - 5%)");
+		assert_eq!(simplify("calc((100% - 10px) / 2)"), "calc(50% - 5px)");
+		assert_eq!(simplify("calc(2 * 3em / 6)"), "1em");
+		assert_eq!(simplify("calc(1em * 2vw / 1px)"), "calc(2 * 1em * 1vw / 1px)");
+		assert_eq!(simplify("calc(1em / 2em)"), "0.5");
+	}
+
+	#[test]
+	fn test_functions() {
+		assert_eq!(simplify("min(10px, 5px, 2px)"), "2px");
+		assert_eq!(simplify("max(1in, 10px)"), "96px");
+		assert_eq!(simplify("min(10px, 5em)"), "min(10px, 5em)");
+		assert_eq!(simplify("min(1px + 2px, 5em)"), "min(3px, 5em)");
+		assert_eq!(simplify("max(10%)"), "10%");
+		assert_eq!(simplify("min(10%, 20%)"), "min(10%, 20%)");
+		assert_eq!(simplify("clamp(10px, 5px, 20px)"), "10px");
+		assert_eq!(simplify("clamp(10px, 50px, 20px)"), "20px");
+		assert_eq!(simplify("clamp(1rem, 2.5vw, 2rem)"), "clamp(1rem, 2.5vw, 2rem)");
+		assert_eq!(simplify("round(2.5)"), "3");
+		assert_eq!(simplify("round(-2.5)"), "-2");
+		assert_eq!(simplify("round(up, 11px, 5px)"), "15px");
+		assert_eq!(simplify("round(down, 11px, 5px)"), "10px");
+		assert_eq!(simplify("round(to-zero, -11px, 5px)"), "-10px");
+		assert_eq!(simplify("mod(-18px, 5px)"), "2px");
+		assert_eq!(simplify("rem(-18px, 5px)"), "-3px");
+		assert_eq!(simplify("abs(-2em)"), "2em");
+		assert_eq!(simplify("sign(-2em)"), "-1");
+		assert_eq!(simplify("sin(90deg)"), "1");
+		assert_eq!(simplify("cos(0)"), "1");
+		assert_eq!(simplify("atan2(1px, 1px)"), "45deg");
+		assert_eq!(simplify("pow(2, 10)"), "1024");
+		assert_eq!(simplify("sqrt(16)"), "4");
+		assert_eq!(simplify("hypot(3px, 4px)"), "5px");
+		assert_eq!(simplify("log(8, 2)"), "3");
+		assert_eq!(simplify("calc(10px * sign(-5%))"), "calc(10px * sign(-5%))");
+		assert_eq!(simplify("calc(1px * infinity)"), "calc(infinity * 1px)");
+		assert_eq!(simplify("round(5px, 0px)"), "calc(NaN * 1px)");
+	}
+}
@@ -0,0 +1,252 @@
+use css_lexer::DimensionUnit;
+
+use crate::units::{DEG_GRAD, DEG_RAD, DEG_TURN, DPPX_CM, DPPX_IN, PX_CM, PX_IN, PX_MM, PX_PC, PX_PT, PX_Q};
+
+/// The base types a math expression's [CalcType] is made up of.
+///
+/// https://drafts.css-houdini.org/css-typed-om/#cssnumericvalue-base-type
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(rename_all = "kebab-case"))]
+pub enum BaseType {
+	Length,
+	Angle,
+	Time,
+	Frequency,
+	Resolution,
+	Flex,
+	Percent,
+}
+
+impl BaseType {
+	const ALL: [BaseType; 7] = [
+		BaseType::Length,
+		BaseType::Angle,
+		BaseType::Time,
+		BaseType::Frequency,
+		BaseType::Resolution,
+		BaseType::Flex,
+		BaseType::Percent,
+	];
+
+	pub fn from_unit(unit: DimensionUnit) -> Option<Self> {
+		Some(match unit {
+			DimensionUnit::Unknown => return None,
+			DimensionUnit::Percent => Self::Percent,
+			DimensionUnit::Deg | DimensionUnit::Grad | DimensionUnit::Rad | DimensionUnit::Turn => Self::Angle,
+			DimensionUnit::S | DimensionUnit::Ms => Self::Time,
+			DimensionUnit::Hz | DimensionUnit::Khz => Self::Frequency,
+			DimensionUnit::Dpi | DimensionUnit::Dpcm | DimensionUnit::Dppx | DimensionUnit::X => Self::Resolution,
+			DimensionUnit::Fr => Self::Flex,
+			_ => Self::Length,
+		})
+	}
+
+	/// The unit values of this type are converted to when they are combined or evaluated.
+	///
+	/// https://drafts.csswg.org/css-values/#canonical-unit
+	pub fn canonical_unit(&self) -> DimensionUnit {
+		match self {
+			Self::Length => DimensionUnit::Px,
+			Self::Angle => DimensionUnit::Deg,
+			Self::Time => DimensionUnit::S,
+			Self::Frequency => DimensionUnit::Hz,
+			Self::Resolution => DimensionUnit::Dppx,
+			Self::Flex => DimensionUnit::Fr,
+			Self::Percent => DimensionUnit::Percent,
+		}
+	}
+}
+
+/// Convert a value to the [canonical unit][BaseType::canonical_unit] of its type, if that can be done without any
+/// outside information. Relative lengths (such as `em` or `vw`) and percentages cannot.
+pub(crate) fn to_canonical(value: f32, unit: DimensionUnit) -> Option<(f32, DimensionUnit)> {
+	let (value, unit) = match unit {
+		DimensionUnit::Px | DimensionUnit::Deg | DimensionUnit::S | DimensionUnit::Hz | DimensionUnit::Dppx => {
+			(value, unit)
+		}
+		DimensionUnit::Cm => (value * PX_CM, DimensionUnit::Px),
+		DimensionUnit::Mm => (value * PX_MM, DimensionUnit::Px),
+		DimensionUnit::Q => (value * PX_Q, DimensionUnit::Px),
+		DimensionUnit::In => (value * PX_IN, DimensionUnit::Px),
+		DimensionUnit::Pc => (value * PX_PC, DimensionUnit::Px),
+		DimensionUnit::Pt => (value * PX_PT, DimensionUnit::Px),
+		DimensionUnit::Grad => (value * DEG_GRAD, DimensionUnit::Deg),
+		DimensionUnit::Rad => (value * DEG_RAD, DimensionUnit::Deg),
+		DimensionUnit::Turn => (value * DEG_TURN, DimensionUnit::Deg),
+		DimensionUnit::Ms => (value / 1000.0, DimensionUnit::S),
+		DimensionUnit::Khz => (value * 1000.0, DimensionUnit::Hz),
+		DimensionUnit::Dpi => (value / DPPX_IN, DimensionUnit::Dppx),
+		DimensionUnit::Dpcm => (value / DPPX_CM, DimensionUnit::Dppx),
+		DimensionUnit::X => (value, DimensionUnit::Dppx),
+		_ => return None,
+	};
+	Some((value, unit))
+}
+
+/// The type of a math expression: a map of each [BaseType] to its exponent, plus an optional percent hint recording
+/// what percentages in the expression resolve against. For example `10px * 2` has the type `length¹`, `1px / 1s`
+/// has the type `length¹ time⁻¹`, and `100% - 10px` has the type `length¹` with a percent hint of `length`.
+///
+/// https://drafts.csswg.org/css-values/#calc-type-checking
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct CalcType {
+	exponents: [i8; 7],
+	percent_hint: Option<BaseType>,
+}
+
+impl CalcType {
+	/// The type of a plain `<number>`, which has no base types.
+	pub const NUMBER: CalcType = CalcType { exponents: [0; 7], percent_hint: None };
+
+	pub fn new(base: BaseType) -> Self {
+		let mut ty = Self::NUMBER;
+		ty.exponents[base as usize] = 1;
+		ty
+	}
+
+	pub fn from_unit(unit: DimensionUnit) -> Option<Self> {
+		BaseType::from_unit(unit).map(Self::new)
+	}
+
+	pub fn exponent(&self, base: BaseType) -> i8 {
+		self.exponents[base as usize]
+	}
+
+	pub fn percent_hint(&self) -> Option<BaseType> {
+		self.percent_hint
+	}
+
+	pub fn is_number(&self) -> bool {
+		self.exponents == [0; 7] && self.percent_hint.is_none()
+	}
+
+	/// Whether this type's only base type is `base` (with an exponent of 1), and it contains no percentages. Use
+	/// [CalcType::matches_percentage] for properties that also accept percentages.
+	pub fn matches(&self, base: BaseType) -> bool {
+		self.percent_hint.is_none() && self.is_only(base)
+	}
+
+	/// Whether this type matches `<base-percentage>`, for example `<length-percentage>`.
+	pub fn matches_percentage(&self, base: BaseType) -> bool {
+		match self.percent_hint {
+			None => self.is_only(base) || self.is_only(BaseType::Percent),
+			Some(hint) => hint == base && self.is_only(base),
+		}
+	}
+
+	fn is_only(&self, base: BaseType) -> bool {
+		BaseType::ALL.iter().all(|b| self.exponent(*b) == if *b == base { 1 } else { 0 })
+	}
+
+	// https://drafts.css-houdini.org/css-typed-om/#apply-the-percent-hint
+	fn apply_percent_hint(&mut self, hint: BaseType) -> Option<()> {
+		let exponent = self.exponents[hint as usize].checked_add(self.exponents[BaseType::Percent as usize])?;
+		self.exponents[hint as usize] = exponent;
+		self.exponents[BaseType::Percent as usize] = 0;
+		self.percent_hint = Some(hint);
+		Some(())
+	}
+
+	fn align_percent_hints(&mut self, other: &mut Self) -> Option<()> {
+		match (self.percent_hint, other.percent_hint) {
+			(Some(a), Some(b)) if a != b => return None,
+			(Some(hint), None) => other.apply_percent_hint(hint)?,
+			(None, Some(hint)) => self.apply_percent_hint(hint)?,
+			_ => {}
+		}
+		Some(())
+	}
+
+	/// The type of the sum of two values of these types, or [None] if they cannot be added together.
+	///
+	/// https://drafts.css-houdini.org/css-typed-om/#cssnumericvalue-add-two-types
+	pub fn try_add(mut self, mut other: Self) -> Option<Self> {
+		self.align_percent_hints(&mut other)?;
+		if self.exponents == other.exponents {
+			return Some(self);
+		}
+		let has_percent = self.exponent(BaseType::Percent) != 0 || other.exponent(BaseType::Percent) != 0;
+		let has_other = BaseType::ALL
+			.iter()
+			.any(|b| *b != BaseType::Percent && (self.exponent(*b) != 0 || other.exponent(*b) != 0));
+		if has_percent && has_other {
+			for hint in BaseType::ALL.iter().filter(|b| **b != BaseType::Percent) {
+				let (mut a, mut b) = (self, other);
+				if a.apply_percent_hint(*hint).is_some()
+					&& b.apply_percent_hint(*hint).is_some()
+					&& a.exponents == b.exponents
+				{
+					return Some(a);
+				}
+			}
+		}
+		None
+	}
+
+	/// The type of the product of two values of these types, or [None] if their percent hints conflict, or an exponent
+	/// grows too large to be represented (such as by multiplying a hundred lengths together).
+	///
+	/// https://drafts.css-houdini.org/css-typed-om/#cssnumericvalue-multiply-two-types
+	pub fn try_multiply(mut self, mut other: Self) -> Option<Self> {
+		self.align_percent_hints(&mut other)?;
+		for (a, b) in self.exponents.iter_mut().zip(other.exponents) {
+			*a = a.checked_add(b)?;
+		}
+		Some(self)
+	}
+
+	/// The type of the reciprocal of a value of this type, or [None] if an exponent can't be negated.
+	///
+	/// https://drafts.css-houdini.org/css-typed-om/#cssnumericvalue-invert-a-type
+	pub fn invert(mut self) -> Option<Self> {
+		for exponent in self.exponents.iter_mut() {
+			*exponent = exponent.checked_neg()?;
+		}
+		Some(self)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_try_add() {
+		let length = CalcType::new(BaseType::Length);
+		let percent = CalcType::new(BaseType::Percent);
+		assert_eq!(length.try_add(length), Some(length));
+		assert_eq!(CalcType::NUMBER.try_add(length), None);
+		assert_eq!(CalcType::new(BaseType::Angle).try_add(length), None);
+		let length_percentage = percent.try_add(length).unwrap();
+		assert!(length_percentage.matches_percentage(BaseType::Length));
+		assert!(!length_percentage.matches(BaseType::Length));
+		assert_eq!(length_percentage.percent_hint(), Some(BaseType::Length));
+		assert_eq!(length_percentage.try_add(CalcType::new(BaseType::Time)), None);
+	}
+
+	#[test]
+	fn test_try_multiply() {
+		let length = CalcType::new(BaseType::Length);
+		let time = CalcType::new(BaseType::Time);
+		assert_eq!(length.try_multiply(CalcType::NUMBER), Some(length));
+		assert!(length.try_multiply(length.invert().unwrap()).unwrap().is_number());
+		let speed = length.try_multiply(time.invert().unwrap()).unwrap();
+		assert_eq!(speed.exponent(BaseType::Length), 1);
+		assert_eq!(speed.exponent(BaseType::Time), -1);
+		assert!(speed.try_multiply(time).unwrap().matches(BaseType::Length));
+		let mut huge = length;
+		huge.exponents[BaseType::Length as usize] = i8::MAX;
+		assert_eq!(huge.try_multiply(length), None);
+		huge.exponents[BaseType::Length as usize] = i8::MIN;
+		assert_eq!(huge.invert(), None);
+	}
+
+	#[test]
+	fn test_to_canonical() {
+		assert_eq!(to_canonical(1.0, DimensionUnit::In), Some((96.0, DimensionUnit::Px)));
+		assert_eq!(to_canonical(500.0, DimensionUnit::Ms), Some((0.5, DimensionUnit::S)));
+		assert_eq!(to_canonical(0.5, DimensionUnit::Turn), Some((180.0, DimensionUnit::Deg)));
+		assert_eq!(to_canonical(1.0, DimensionUnit::Em), None);
+		assert_eq!(to_canonical(1.0, DimensionUnit::Percent), None);
+	}
+}
@@ -1,19 +1,30 @@
-use crate::values;
+use crate::{values, MathFunction};
 use css_lexer::{Cursor, Kind, KindSet};
 use css_parse::{
 	keyword_set, syntax::BangImportant, syntax::ComponentValues, Build, CursorSink, Declaration, DeclarationValue,
-	Parse, Parser, Peek, Result as ParserResult, State, ToCursors, T,
+	Feature, Parse, Parser, Peek, Result as ParserResult, State, ToCursors, T,
 };
+#[cfg(feature = "serde")]
+use css_parse::{FromJson, JsonError, JsonObject, JsonValue};
 use csskit_proc_macro::visit;
+#[cfg(feature = "serde")]
+use serde::de::Error as _;
 use std::{fmt::Debug, hash::Hash};
 
 use super::{Visit, Visitable};
 
+mod nonstandard;
+mod shorthands;
+pub use nonstandard::*;
+pub use shorthands::*;
+
 // The build.rs generates a list of CSS properties from the value mods
 include!(concat!(env!("OUT_DIR"), "/css_apply_properties.rs"));
+// ...along with the initial value of each of those properties
+include!(concat!(env!("OUT_DIR"), "/css_apply_property_initials.rs"));
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub struct Custom<'a>(pub ComponentValues<'a>);
 
 impl<'a> Parse<'a> for Custom<'a> {
@@ -33,9 +44,18 @@ impl<'a> ToCursors for Custom<'a> {
 	}
 }
 
+/// A declaration value which can only be known at computed-value time. A value made of a single math function (such
+/// as `calc(100% - 10px)`) is parsed into a [MathFunction] so that it can be simplified or evaluated, while anything
+/// else (for example values using `var()`, or math functions mixed with other values) is kept as
+/// [ComponentValues].
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
-pub struct Computed<'a>(pub ComponentValues<'a>);
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
+pub enum Computed<'a> {
+	#[cfg_attr(feature = "serde", serde(untagged))]
+	Math(MathFunction<'a>),
+	#[cfg_attr(feature = "serde", serde(untagged))]
+	Values(ComponentValues<'a>),
+}
 
 impl<'a> Peek<'a> for Computed<'a> {
 	fn peek(p: &Parser<'a>, c: Cursor) -> bool {
@@ -48,33 +68,46 @@ impl<'a> Peek<'a> for Computed<'a> {
 					| "round" | "mod"
 					| "rem" | "sin" | "cos"
 					| "tan" | "asin"
-					| "atan" | "atan2"
-					| "pow" | "sqrt"
-					| "hypot" | "log"
-					| "exp" | "abs" | "sign"
+					| "acos" | "atan"
+					| "atan2" | "pow"
+					| "sqrt" | "hypot"
+					| "log" | "exp" | "abs"
+					| "sign"
 			)
 	}
 }
 
 impl<'a> Parse<'a> for Computed<'a> {
 	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
+		if p.peek::<MathFunction>() {
+			let checkpoint = p.checkpoint();
+			if let Ok(math) = p.try_parse::<MathFunction>() {
+				if p.at_end() || p.peek_n(1) == KindSet::RIGHT_CURLY_OR_SEMICOLON || p.peek::<T![!]>() {
+					return Ok(Self::Math(math));
+				}
+			}
+			p.rewind(checkpoint);
+		}
 		let state = p.set_state(State::Nested);
 		let stop = p.set_stop(KindSet::RIGHT_CURLY_OR_SEMICOLON);
 		let values = p.parse::<ComponentValues>();
 		p.set_state(state);
 		p.set_stop(stop);
-		Ok(Self(values?))
+		Ok(Self::Values(values?))
 	}
 }
 
 impl<'a> ToCursors for Computed<'a> {
 	fn to_cursors(&self, s: &mut impl CursorSink) {
-		ToCursors::to_cursors(&self.0, s);
+		match self {
+			Self::Math(math) => ToCursors::to_cursors(math, s),
+			Self::Values(values) => ToCursors::to_cursors(values, s),
+		}
 	}
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub struct Unknown<'a>(pub ComponentValues<'a>);
 
 impl<'a> Parse<'a> for Unknown<'a> {
@@ -98,6 +131,9 @@ impl<'a> ToCursors for Unknown<'a> {
 #[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type", rename = "property"))]
 #[visit]
 pub struct Property<'a> {
+	/// The `*` of a `*prop: value` hack, only parsed when [Feature::PropertyHacks] is enabled.
+	#[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+	pub hack: Option<T![*]>,
 	pub name: T![Ident],
 	pub colon: T![:],
 	pub value: StyleValue<'a>,
@@ -106,14 +142,19 @@ pub struct Property<'a> {
 
 impl<'a> Peek<'a> for Property<'a> {
 	fn peek(p: &Parser<'a>, c: Cursor) -> bool {
+		if p.enabled(Feature::PropertyHacks) && <T![*]>::peek(p, c) {
+			let name = p.peek_n(2);
+			return name == Kind::Ident && name.offset() == c.end_offset() && p.peek_n(3) == Kind::Colon;
+		}
 		<T![Ident]>::peek(p, c) && p.peek_n(2) == Kind::Colon
 	}
 }
 
 impl<'a> Parse<'a> for Property<'a> {
 	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
+		let hack = if p.enabled(Feature::PropertyHacks) { p.parse_if_peek::<T![*]>()? } else { None };
 		let (name, colon, value, important) = Self::parse_declaration(p)?;
-		Ok(Self { name, colon, value, important })
+		Ok(Self { hack, name, colon, value, important })
 	}
 }
 
@@ -123,6 +164,9 @@ impl<'a> Declaration<'a> for Property<'a> {
 
 impl ToCursors for Property<'_> {
 	fn to_cursors(&self, s: &mut impl CursorSink) {
+		if let Some(hack) = self.hack {
+			s.append(hack.into());
+		}
 		s.append(self.name.into());
 		s.append(self.colon.into());
 		ToCursors::to_cursors(&self.value, s);
@@ -132,6 +176,33 @@ impl ToCursors for Property<'_> {
 	}
 }
 
+#[cfg(feature = "serde")]
+impl<'a> FromJson<'a> for Property<'a> {
+	fn from_json(p: &Parser<'a>, value: &JsonValue) -> Result<Self, JsonError> {
+		let object = JsonObject::new("Property", value)?;
+		object.expect_tag("type", "property")?;
+		object.deny_unknown_fields(&["type", "hack", "name", "colon", "value", "important"])?;
+		let name: T![Ident] = object.field(p, "name")?;
+		let value = StyleValue::from_json_by_name(p, p.parse_str_lower(name.into()), object.get("value"))
+			.map_err(|err| JsonError::custom(format!("Property.value: {err}")))?;
+		Ok(Self {
+			hack: object.field(p, "hack")?,
+			name,
+			colon: object.field(p, "colon")?,
+			value,
+			important: object.field(p, "important")?,
+		})
+	}
+}
+
+// With [Feature::PropertyHacks], the value of a `_prop` declaration (the IE6 hack) is parsed as the value of `prop`.
+fn strip_property_hack<'n>(p: &Parser, name: &'n str) -> &'n str {
+	match name.strip_prefix('_') {
+		Some(name) if p.enabled(Feature::PropertyHacks) => name,
+		_ => name,
+	}
+}
+
 impl<'a> Visitable<'a> for Property<'a> {
 	fn accept<V: Visit<'a>>(&self, v: &mut V) {
 		v.visit_property(self);
@@ -142,7 +213,13 @@ impl<'a> Visitable<'a> for Property<'a> {
 macro_rules! style_value {
 	( $( $name: ident: $ty: ident$(<$a: lifetime>)? = $str: tt,)+ ) => {
 		#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-		#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type", rename_all = "kebab-case"))]
+		// Every value is tagged, and the value of a property is tagged with the name of that property, so a value is
+		// never read as something other than what it was parsed as.
+		#[cfg_attr(
+			feature = "serde",
+			derive(serde::Serialize),
+			serde(tag = "type", content = "value", rename_all = "kebab-case")
+		)]
 		#[visit]
 		pub enum StyleValue<'a> {
 			Initial(T![Ident]),
@@ -150,14 +227,14 @@ macro_rules! style_value {
 			Unset(T![Ident]),
 			Revert(T![Ident]),
 			RevertLayer(T![Ident]),
-			#[cfg_attr(feature = "serde", serde(untagged))]
+			Unknown(Unknown<'a>),
 			Custom(Custom<'a>),
-			#[cfg_attr(feature = "serde", serde(untagged))]
 			Computed(Computed<'a>),
-			#[cfg_attr(feature = "serde", serde(untagged))]
-			Unknown(Unknown<'a>),
+			ProgIdFilters(ProgIdFilters<'a>),
+			DynamicExpression(DynamicExpression<'a>),
+			Composes(Composes<'a>),
 			$(
-				#[cfg_attr(feature = "serde", serde(untagged))]
+				#[cfg_attr(feature = "serde", serde(rename = $str))]
 				$name(values::$ty$(<$a>)?),
 			)+
 		}
@@ -183,9 +260,106 @@ macro_rules! define_property_id {
 }
 apply_properties!(define_property_id);
 
+macro_rules! define_inherits_by_name {
+	( $( $name: ident: $ty: ident$(<$a: lifetime>)? = $str: tt,)+ ) => {
+		impl<'a> StyleValue<'a> {
+			/// Whether the property with the given (lowercase) name is
+			/// [inherited](https://drafts.csswg.org/css-cascade/#inherited-property). Custom properties always inherit,
+			/// while unknown properties never do.
+			pub fn inherits_by_name(name: &str) -> bool {
+				if name.starts_with("--") {
+					return true;
+				}
+				match name {
+					$($str => <values::$ty$(<$a>)? as crate::traits::StyleValue>::inherits(),)+
+					_ => false,
+				}
+			}
+		}
+	}
+}
+apply_properties!(define_inherits_by_name);
+
+macro_rules! define_parses_by_name {
+	( $( $name: ident: $ty: ident$(<$a: lifetime>)? = $str: tt,)+ ) => {
+		impl<'a> StyleValue<'a> {
+			/// Whether values of the property with the given (lowercase) name are parsed. Values of other properties,
+			/// including those which are specified but not yet implemented, are kept as [Unknown].
+			pub fn parses_by_name(name: &str) -> bool {
+				matches!(name, $($str)|+)
+			}
+		}
+	}
+}
+apply_properties!(define_parses_by_name);
+
+macro_rules! define_from_json_by_name {
+	( $( $name: ident: $ty: ident$(<$a: lifetime>)? = $str: tt,)+ ) => {
+		#[cfg(feature = "serde")]
+		impl<'a> StyleValue<'a> {
+			/// Read the value of the property with the given (lowercase) name from its JSON. Each value is tagged with a
+			/// `"type"`, which for the value of a property is the name of that property, so must agree with the name
+			/// given.
+			pub fn from_json_by_name(p: &Parser<'a>, name: &str, value: &JsonValue) -> Result<Self, JsonError> {
+				let name = strip_property_hack(p, name);
+				let object = JsonObject::new("StyleValue", value)?;
+				object.deny_unknown_fields(&["type", "value"])?;
+				let ty = object.get("type").as_str().unwrap_or_default();
+				let value = object.get("value");
+				match ty {
+					"initial" => return FromJson::from_json(p, value).map(Self::Initial),
+					"inherit" => return FromJson::from_json(p, value).map(Self::Inherit),
+					"unset" => return FromJson::from_json(p, value).map(Self::Unset),
+					"revert" => return FromJson::from_json(p, value).map(Self::Revert),
+					"revert-layer" => return FromJson::from_json(p, value).map(Self::RevertLayer),
+					"unknown" => return FromJson::from_json(p, value).map(Self::Unknown),
+					"custom" if name.starts_with("--") => return FromJson::from_json(p, value).map(Self::Custom),
+					"computed" => return FromJson::from_json(p, value).map(Self::Computed),
+					"progid-filters" => return FromJson::from_json(p, value).map(Self::ProgIdFilters),
+					"dynamic-expression" => return FromJson::from_json(p, value).map(Self::DynamicExpression),
+					"composes" => return FromJson::from_json(p, value).map(Self::Composes),
+					_ => {}
+				}
+				match name {
+					$($str if ty == $str => values::$ty::from_json(p, value).map(Self::$name),)+
+					_ => Err(JsonError::custom(format!("expected a value of {name:?}, saw a value of type {ty:?}"))),
+				}
+			}
+		}
+	}
+}
+apply_properties!(define_from_json_by_name);
+
+macro_rules! define_initial_by_name {
+	( $( $str: literal = $initial: literal,)+ ) => {
+		impl<'a> StyleValue<'a> {
+			/// The [initial value](https://drafts.csswg.org/css-cascade/#initial-values) of the property with the given
+			/// (lowercase) name, as written in its definition. Returns `None` for custom & unknown properties, and for
+			/// shorthands defined to take the initial values of their individual longhands.
+			pub fn initial_by_name(name: &str) -> Option<&'static str> {
+				match name {
+					$($str => Some($initial),)+
+					_ => None,
+				}
+			}
+		}
+	}
+}
+apply_property_initials!(define_initial_by_name);
+
 impl<'a> DeclarationValue<'a> for StyleValue<'a> {
 	fn parse_declaration_value(p: &mut Parser<'a>, name: Cursor) -> ParserResult<Self> {
-		if name.token().is_dashed_ident() {
+		Self::parse_by_name(p, p.parse_str_lower(name))
+	}
+}
+
+impl<'a> StyleValue<'a> {
+	/// Parse the value of the property with the given (lowercase) name, as it would be parsed in a declaration. This
+	/// allows a value to be parsed without its declaration, for example to validate the value given to
+	/// `element.style.setProperty()`.
+	pub fn parse_by_name(p: &mut Parser<'a>, name: &str) -> ParserResult<Self> {
+		let name = strip_property_hack(p, name);
+		if name.starts_with("--") {
 			return Ok(Self::Custom(p.parse::<Custom>()?));
 		}
 		if let Some(kw) = p.parse_if_peek::<CSSWideKeyword>()? {
@@ -197,24 +371,36 @@ impl<'a> DeclarationValue<'a> for StyleValue<'a> {
 				CSSWideKeyword::RevertLayer(c) => return Ok(Self::RevertLayer(<T![Ident]>::build(p, c))),
 			}
 		}
+		if p.enabled(Feature::ProgIdFilters) && p.peek::<ProgIdFilters>() {
+			if let Ok(filters) = p.try_parse::<ProgIdFilters>() {
+				return Ok(Self::ProgIdFilters(filters));
+			}
+		}
+		if p.enabled(Feature::Expressions) && p.peek::<DynamicExpression>() {
+			if let Ok(expression) = p.try_parse::<DynamicExpression>() {
+				return Ok(Self::DynamicExpression(expression));
+			}
+		}
+		if p.enabled(Feature::CSSModules) && name == "composes" {
+			return p.parse::<Composes>().map(Self::Composes);
+		}
 		if p.peek::<Computed>() {
 			return p.parse::<Computed>().map(Self::Computed);
 		}
 		let checkpoint = p.checkpoint();
 		macro_rules! parse_declaration_value {
 			( $( $name: ident: $ty: ident$(<$a: lifetime>)? = $str: tt,)+ ) => {
-				if PropertyId::peek(p, name) {
-					match PropertyId::build(p, name) {
-						$(
-							PropertyId::$name(_) => {
-								if let Ok(val) = p.parse::<values::$ty>() {
-									if p.at_end() || p.peek_n(1) == KindSet::RIGHT_CURLY_OR_SEMICOLON || p.peek::<T![!]>() {
-										return Ok(Self::$name(val))
-									}
+				match name {
+					$(
+						$str => {
+							if let Ok(val) = p.parse::<values::$ty>() {
+								if p.at_end() || p.peek_n(1) == KindSet::RIGHT_CURLY_OR_SEMICOLON || p.peek::<T![!]>() {
+									return Ok(Self::$name(val))
 								}
-							},
-						)+
-					}
+							}
+						},
+					)+
+					_ => {}
 				}
 			}
 		}
@@ -242,6 +428,9 @@ impl<'a> ToCursors for StyleValue<'a> {
 					Self::Custom(custom) => ToCursors::to_cursors(custom, s),
 					Self::Computed(computed) => ToCursors::to_cursors(computed, s),
 					Self::Unknown(unknown) => ToCursors::to_cursors(unknown, s),
+					Self::ProgIdFilters(filters) => ToCursors::to_cursors(filters, s),
+					Self::DynamicExpression(expression) => ToCursors::to_cursors(expression, s),
+					Self::Composes(composes) => ToCursors::to_cursors(composes, s),
 					$( Self::$name(value) => ToCursors::to_cursors(value, s), )+
 				}
 			}
@@ -263,15 +452,17 @@ mod tests {
 
 	#[test]
 	fn size_test() {
-		assert_eq!(std::mem::size_of::<Property>(), 384);
-		assert_eq!(std::mem::size_of::<StyleValue>(), 328);
+		assert_eq!(std::mem::size_of::<Property>(), 432);
+		assert_eq!(std::mem::size_of::<StyleValue>(), 360);
 	}
 
 	#[test]
 	fn test_writes() {
 		assert_parse!(Property, "float:none!important");
 		assert_parse!(Property, "width:1px");
-		assert_parse!(Property, "width:min(1px, 2px)");
+		assert_parse!(Property, "width:min(1px, 2px)", "width:min(1px,2px)");
+		assert_parse!(Property, "width:calc(100% - 2px)!important");
+		assert_parse!(Property, "width:calc(100% - 2px) 1px");
 		assert_parse!(Property, "border:1px solid var(--red)");
 		// Should still parse unknown properties
 		assert_parse!(Property, "dunno:like whatever");
@@ -279,4 +470,44 @@ mod tests {
 		assert_parse!(Property, "_background:black");
 		assert_parse!(Property, "--custom:{foo:{bar};baz:(bing);}");
 	}
+
+	#[test]
+	fn test_property_hacks() {
+		let bump = bumpalo::Bump::default();
+		let result = Parser::new_with_features(&bump, "*zoom:1", Feature::PropertyHacks).parse_entirely::<Property>();
+		assert!(result.output.unwrap().hack.is_some());
+		let result = Parser::new(&bump, "*zoom:1").parse_entirely::<Property>();
+		assert!(result.output.is_none());
+		let result =
+			Parser::new_with_features(&bump, "_width:1px", Feature::PropertyHacks).parse_entirely::<Property>();
+		assert!(matches!(result.output.unwrap().value, StyleValue::Width(_)));
+		let result = Parser::new(&bump, "_width:1px").parse_entirely::<Property>();
+		assert!(matches!(result.output.unwrap().value, StyleValue::Unknown(_)));
+		// Without the feature these values are unknown.
+		let result = Parser::new(&bump, "composes:a from global").parse_entirely::<Property>();
+		assert!(matches!(result.output.unwrap().value, StyleValue::Unknown(_)));
+		let result = Parser::new_with_features(&bump, "composes:a from global", Feature::CSSModules)
+			.parse_entirely::<Property>();
+		assert!(matches!(result.output.unwrap().value, StyleValue::Composes(_)));
+		// A malformed filter is kept as an unknown value.
+		let result = Parser::new_with_features(&bump, "filter:progid:Alpha", Feature::ProgIdFilters)
+			.parse_entirely::<Property>();
+		assert!(result.errors.is_empty(), "{:?}", result.errors);
+		assert!(matches!(result.output.unwrap().value, StyleValue::Unknown(_)));
+	}
+
+	#[test]
+	fn test_build_initial_value() {
+		let bump = bumpalo::Bump::default();
+		let source_text = "min-width:initial!important";
+		let mut property = Parser::new(&bump, source_text).parse_entirely::<Property>().output.unwrap();
+		assert!(matches!(property.value, StyleValue::Initial(_)));
+		let mut builder = css_parse::NodeBuilder::new(&bump, source_text);
+		let initial = StyleValue::initial_by_name("min-width").unwrap();
+		property.value = builder.parse::<Property>(&format!("min-width:{initial}")).unwrap().value;
+		assert!(matches!(property.value, StyleValue::MinWidth(_)));
+		let mut css = String::new();
+		property.to_cursors(&mut css_parse::CursorFmtSink::new(source_text, &mut css).with_builder(&builder));
+		assert_eq!(css, "min-width:auto!important");
+	}
 }
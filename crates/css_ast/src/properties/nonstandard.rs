@@ -0,0 +1,256 @@
+use bumpalo::collections::Vec;
+use css_lexer::{Cursor, Kind, KindSet};
+use css_parse::{
+	diagnostics, syntax::FunctionBlock, CursorSink, Parse, Parser, Peek, Result as ParserResult, ToCursors, T,
+};
+
+// Non-standard values which are only parsed when the relevant [Feature][css_parse::Feature] is enabled. They all
+// occupy the whole of a declaration's value.
+fn at_value_end(p: &Parser) -> bool {
+	p.at_end() || p.peek_n(1) == KindSet::RIGHT_CURLY_OR_SEMICOLON || p.peek::<T![!]>()
+}
+
+/// The value of an IE `filter` (or `-ms-filter`) declaration made of one or more
+/// [procedural surfaces](https://learn.microsoft.com/en-us/previous-versions/ms532847(v=vs.85)), such as
+/// `progid:DXImageTransform.Microsoft.Alpha(Opacity=80)`. Only parsed when
+/// [Feature::ProgIdFilters][css_parse::Feature::ProgIdFilters] is enabled.
+///
+/// ```md
+/// <progid-filters>
+///  │├─╭─ <progid-filter> ─╮─┤│
+///     ╰───────────────────╯
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, css_parse::FromJson),
+	serde(tag = "type", rename = "progid-filters")
+)]
+pub struct ProgIdFilters<'a> {
+	pub filters: Vec<'a, ProgIdFilter<'a>>,
+}
+
+impl<'a> Peek<'a> for ProgIdFilters<'a> {
+	fn peek(p: &Parser<'a>, c: Cursor) -> bool {
+		<ProgIdFilter>::peek(p, c)
+	}
+}
+
+impl<'a> Parse<'a> for ProgIdFilters<'a> {
+	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
+		let mut filters = Vec::new_in(p.bump());
+		loop {
+			filters.push(p.parse::<ProgIdFilter>()?);
+			if !p.peek::<ProgIdFilter>() {
+				break;
+			}
+		}
+		Ok(Self { filters })
+	}
+}
+
+impl<'a> ToCursors for ProgIdFilters<'a> {
+	fn to_cursors(&self, s: &mut impl CursorSink) {
+		for filter in &self.filters {
+			ToCursors::to_cursors(filter, s);
+		}
+	}
+}
+
+/// A single procedural surface within [ProgIdFilters], e.g. `progid:DXImageTransform.Microsoft.Alpha(Opacity=80)`.
+/// The dotted name leading up to the function is kept in `path`, while the arguments (which use `=` rather than
+/// any CSS syntax) are left as a [FunctionBlock].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, css_parse::FromJson),
+	serde(tag = "type", rename = "progid-filter")
+)]
+pub struct ProgIdFilter<'a> {
+	pub progid: T![Ident],
+	pub colon: T![:],
+	pub path: Vec<'a, (T![Ident], T![.])>,
+	pub function: FunctionBlock<'a>,
+}
+
+impl<'a> Peek<'a> for ProgIdFilter<'a> {
+	fn peek(p: &Parser<'a>, c: Cursor) -> bool {
+		<T![Ident]>::peek(p, c) && p.eq_ignore_ascii_case(c, "progid") && p.peek_n(2) == Kind::Colon
+	}
+}
+
+impl<'a> Parse<'a> for ProgIdFilter<'a> {
+	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
+		let progid = p.parse::<T![Ident]>()?;
+		let c: Cursor = progid.into();
+		if !p.eq_ignore_ascii_case(c, "progid") {
+			Err(diagnostics::UnexpectedIdent(p.parse_str(c).into(), c.into()))?
+		}
+		let colon = p.parse::<T![:]>()?;
+		let mut path = Vec::new_in(p.bump());
+		while p.peek::<T![Ident]>() {
+			path.push((p.parse::<T![Ident]>()?, p.parse::<T![.]>()?));
+		}
+		let function = p.parse::<FunctionBlock>()?;
+		Ok(Self { progid, colon, path, function })
+	}
+}
+
+impl<'a> ToCursors for ProgIdFilter<'a> {
+	fn to_cursors(&self, s: &mut impl CursorSink) {
+		s.append(self.progid.into());
+		s.append(self.colon.into());
+		for (ident, dot) in &self.path {
+			s.append(ident.into());
+			s.append(dot.into());
+		}
+		ToCursors::to_cursors(&self.function, s);
+	}
+}
+
+/// An IE [dynamic property](https://learn.microsoft.com/en-us/previous-versions/ms537634(v=vs.85)) value, such as
+/// `expression(document.body.clientWidth > 800 ? "800px" : "auto")`. The arguments are JavaScript, so they're kept as
+/// a [FunctionBlock]. Only parsed when [Feature::Expressions][css_parse::Feature::Expressions] is enabled, and only
+/// when the `expression()` is the whole value.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, css_parse::FromJson),
+	serde(tag = "type", rename = "dynamic-expression")
+)]
+pub struct DynamicExpression<'a> {
+	pub function: FunctionBlock<'a>,
+}
+
+impl<'a> Peek<'a> for DynamicExpression<'a> {
+	fn peek(p: &Parser<'a>, c: Cursor) -> bool {
+		<T![Function]>::peek(p, c) && p.eq_ignore_ascii_case(c, "expression")
+	}
+}
+
+impl<'a> Parse<'a> for DynamicExpression<'a> {
+	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
+		if !p.peek::<Self>() {
+			let c = p.peek_n(1);
+			Err(diagnostics::Unexpected(c.into(), c.into()))?
+		}
+		let function = p.parse::<FunctionBlock>()?;
+		if !at_value_end(p) {
+			let c = p.peek_n(1);
+			Err(diagnostics::Unexpected(c.into(), c.into()))?
+		}
+		Ok(Self { function })
+	}
+}
+
+impl<'a> ToCursors for DynamicExpression<'a> {
+	fn to_cursors(&self, s: &mut impl CursorSink) {
+		ToCursors::to_cursors(&self.function, s);
+	}
+}
+
+/// The value of a [CSS Modules](https://github.com/css-modules/css-modules#composition) `composes` declaration, such
+/// as `composes: button primary from "./buttons.css"`. Only parsed when
+/// [Feature::CSSModules][css_parse::Feature::CSSModules] is enabled.
+///
+/// ```md
+/// <composes>
+///  │├─╭─ <ident> ─╮─╮─────────────────────────────────────────╭─┤│
+///     ╰───────────╯ ╰─ <ident "from"> ─╮─ <ident "global"> ─╭─╯
+///                                      ╰─ <string> ─────────╯
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde(tag = "type", rename = "composes"))]
+pub struct Composes<'a> {
+	pub names: Vec<'a, T![Ident]>,
+	pub from: Option<(T![Ident], ComposesSource)>,
+}
+
+/// Where the class names of [Composes] come from; either the global scope, or another file.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde(untagged))]
+pub enum ComposesSource {
+	Global(T![Ident]),
+	File(T![String]),
+}
+
+impl<'a> Parse<'a> for Composes<'a> {
+	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
+		let mut names = Vec::new_in(p.bump());
+		let mut from = None;
+		while p.peek::<T![Ident]>() {
+			let ident = p.parse::<T![Ident]>()?;
+			let c: Cursor = ident.into();
+			if !names.is_empty() && p.eq_ignore_ascii_case(c, "from") {
+				if let Some(file) = p.parse_if_peek::<T![String]>()? {
+					from = Some((ident, ComposesSource::File(file)));
+				} else {
+					let global = p.parse::<T![Ident]>()?;
+					let g: Cursor = global.into();
+					if !p.eq_ignore_ascii_case(g, "global") {
+						Err(diagnostics::UnexpectedIdent(p.parse_str(g).into(), g.into()))?
+					}
+					from = Some((ident, ComposesSource::Global(global)));
+				}
+				break;
+			}
+			names.push(ident);
+		}
+		if names.is_empty() || !at_value_end(p) {
+			let c = p.peek_n(1);
+			Err(diagnostics::Unexpected(c.into(), c.into()))?
+		}
+		Ok(Self { names, from })
+	}
+}
+
+impl<'a> ToCursors for Composes<'a> {
+	fn to_cursors(&self, s: &mut impl CursorSink) {
+		for name in &self.names {
+			s.append(name.into());
+		}
+		if let Some((from, source)) = &self.from {
+			s.append(from.into());
+			match source {
+				ComposesSource::Global(global) => s.append(global.into()),
+				ComposesSource::File(file) => s.append(file.into()),
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use css_parse::{assert_parse, assert_parse_error};
+
+	#[test]
+	fn size_test() {
+		assert_eq!(std::mem::size_of::<ProgIdFilters>(), 32);
+		assert_eq!(std::mem::size_of::<ProgIdFilter>(), 120);
+		assert_eq!(std::mem::size_of::<DynamicExpression>(), 64);
+		assert_eq!(std::mem::size_of::<Composes>(), 64);
+	}
+
+	#[test]
+	fn test_writes() {
+		assert_parse!(ProgIdFilters, "progid:DXImageTransform.Microsoft.Alpha(Opacity=80)");
+		assert_parse!(
+			ProgIdFilters,
+			"progid:DXImageTransform.Microsoft.gradient(startColorstr='#80000000', endColorstr='#80000000') progid:DXImageTransform.Microsoft.Shadow(color='#000')",
+			"progid:DXImageTransform.Microsoft.gradient(startColorstr='#80000000', endColorstr='#80000000')progid:DXImageTransform.Microsoft.Shadow(color='#000')"
+		);
+		assert_parse!(DynamicExpression, "expression(document.body.clientWidth > 800 ? \"800px\" : \"auto\")");
+		assert_parse!(Composes, "button");
+		assert_parse!(Composes, "button primary from \"./buttons.css\"", "button primary from\"./buttons.css\"");
+		assert_parse!(Composes, "button from global");
+	}
+
+	#[test]
+	fn test_errors() {
+		assert_parse_error!(ProgIdFilters, "progid:Alpha");
+		assert_parse_error!(DynamicExpression, "expression(1) 2px");
+		assert_parse_error!(Composes, "1px");
+		assert_parse_error!(Composes, "button from local");
+	}
+}
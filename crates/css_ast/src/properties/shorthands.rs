@@ -0,0 +1,1269 @@
+use bumpalo::Bump;
+use core::fmt;
+use css_lexer::{Cursor, Kind};
+use css_parse::{CursorFmtSink, CursorSink, Parser, ToCursors};
+use std::cmp::Reverse;
+
+use super::{Property, StyleValue};
+
+/// How the values of a [Shorthand] are distributed over its longhands.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ShorthandSyntax {
+	/// One to four values, assigned clockwise to the top, right, bottom & left longhands, like `margin`.
+	Sides,
+	/// One or two values, where an omitted second value copies the first, like `gap`.
+	Pair,
+	/// Each longhand's value in any order (`<a> || <b>`), where omitted longhands are reset to their initial value,
+	/// like `flex-flow`.
+	AnyOrder,
+	/// The values of one side's longhands in any order, as with [AnyOrder][ShorthandSyntax::AnyOrder], which are set on
+	/// every side, like `border`. The longhands are those of the top, right, bottom & left in turn.
+	EverySide,
+	/// One to four grid lines separated by `/`, where an omitted line copies an earlier one if that is a custom ident,
+	/// and is otherwise `auto`, like `grid-area`.
+	GridLines,
+	/// Rows & columns separated by `/`, like `grid-template`, or (with the implicit grid longhands) `grid`. Values
+	/// using grid template areas aren't distributed.
+	GridTemplate,
+	/// The flex factors & basis of `flex`.
+	Flex,
+	/// The position, image & type of `list-style`, where `none` sets whichever of the image & type aren't otherwise
+	/// given.
+	ListStyle,
+	/// The values of `font`, where the style, variant, weight & width come before the size, line height & family.
+	Font,
+	/// A single layer of `background`.
+	Background,
+}
+
+macro_rules! define_shorthands {
+	( $( $name: ident: $str: literal = $syntax: ident[$( $longhand: literal ),+] $(+ [$( $reset: literal ),+])?, )+ ) => {
+		/// A [shorthand property](https://drafts.csswg.org/css-cascade/#shorthand), which sets several longhand
+		/// properties at once.
+		#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+		pub enum Shorthand {
+			$($name,)+
+		}
+
+		impl Shorthand {
+			pub const ALL: &'static [Shorthand] = &[$(Self::$name,)+];
+
+			/// The shorthand with the given (lowercase) property name.
+			pub fn from_name(name: &str) -> Option<Self> {
+				match name {
+					$($str => Some(Self::$name),)+
+					_ => None,
+				}
+			}
+
+			pub fn name(&self) -> &'static str {
+				match self {
+					$(Self::$name => $str,)+
+				}
+			}
+
+			pub fn syntax(&self) -> ShorthandSyntax {
+				match self {
+					$(Self::$name => ShorthandSyntax::$syntax,)+
+				}
+			}
+
+			/// The longhands this shorthand sets, in the order their values are written in the shorthand, followed by
+			/// those it [resets][Shorthand::resets].
+			pub fn longhands(&self) -> &'static [&'static str] {
+				match self {
+					$(Self::$name => &[$($longhand),+ $($(, $reset)+)?],)+
+				}
+			}
+
+			/// The longhands this shorthand can't be given a value for, but which it resets to their initial value,
+			/// like `border` does to `border-image`.
+			pub fn resets(&self) -> &'static [&'static str] {
+				match self {
+					$(Self::$name => &[$($($reset),+)?],)+
+				}
+			}
+		}
+	}
+}
+
+define_shorthands! {
+	Margin: "margin" = Sides["margin-top", "margin-right", "margin-bottom", "margin-left"],
+	Padding: "padding" = Sides["padding-top", "padding-right", "padding-bottom", "padding-left"],
+	Inset: "inset" = Sides["top", "right", "bottom", "left"],
+	BorderColor: "border-color" = Sides["border-top-color", "border-right-color", "border-bottom-color", "border-left-color"],
+	BorderStyle: "border-style" = Sides["border-top-style", "border-right-style", "border-bottom-style", "border-left-style"],
+	BorderWidth: "border-width" = Sides["border-top-width", "border-right-width", "border-bottom-width", "border-left-width"],
+	ScrollMargin: "scroll-margin" = Sides["scroll-margin-top", "scroll-margin-right", "scroll-margin-bottom", "scroll-margin-left"],
+	ScrollPadding: "scroll-padding" = Sides["scroll-padding-top", "scroll-padding-right", "scroll-padding-bottom", "scroll-padding-left"],
+	MarginBlock: "margin-block" = Pair["margin-block-start", "margin-block-end"],
+	MarginInline: "margin-inline" = Pair["margin-inline-start", "margin-inline-end"],
+	PaddingBlock: "padding-block" = Pair["padding-block-start", "padding-block-end"],
+	PaddingInline: "padding-inline" = Pair["padding-inline-start", "padding-inline-end"],
+	InsetBlock: "inset-block" = Pair["inset-block-start", "inset-block-end"],
+	InsetInline: "inset-inline" = Pair["inset-inline-start", "inset-inline-end"],
+	BorderBlockColor: "border-block-color" = Pair["border-block-start-color", "border-block-end-color"],
+	BorderBlockStyle: "border-block-style" = Pair["border-block-start-style", "border-block-end-style"],
+	BorderBlockWidth: "border-block-width" = Pair["border-block-start-width", "border-block-end-width"],
+	BorderInlineColor: "border-inline-color" = Pair["border-inline-start-color", "border-inline-end-color"],
+	BorderInlineStyle: "border-inline-style" = Pair["border-inline-start-style", "border-inline-end-style"],
+	BorderInlineWidth: "border-inline-width" = Pair["border-inline-start-width", "border-inline-end-width"],
+	ScrollMarginBlock: "scroll-margin-block" = Pair["scroll-margin-block-start", "scroll-margin-block-end"],
+	ScrollMarginInline: "scroll-margin-inline" = Pair["scroll-margin-inline-start", "scroll-margin-inline-end"],
+	ScrollPaddingBlock: "scroll-padding-block" = Pair["scroll-padding-block-start", "scroll-padding-block-end"],
+	ScrollPaddingInline: "scroll-padding-inline" = Pair["scroll-padding-inline-start", "scroll-padding-inline-end"],
+	Gap: "gap" = Pair["row-gap", "column-gap"],
+	Overflow: "overflow" = Pair["overflow-x", "overflow-y"],
+	PlaceContent: "place-content" = Pair["align-content", "justify-content"],
+	PlaceItems: "place-items" = Pair["align-items", "justify-items"],
+	PlaceSelf: "place-self" = Pair["align-self", "justify-self"],
+	FlexFlow: "flex-flow" = AnyOrder["flex-direction", "flex-wrap"],
+	Columns: "columns" = AnyOrder["column-width", "column-count"],
+	ColumnRule: "column-rule" = AnyOrder["column-rule-width", "column-rule-style", "column-rule-color"],
+	BorderTop: "border-top" = AnyOrder["border-top-width", "border-top-style", "border-top-color"],
+	BorderRight: "border-right" = AnyOrder["border-right-width", "border-right-style", "border-right-color"],
+	BorderBottom: "border-bottom" = AnyOrder["border-bottom-width", "border-bottom-style", "border-bottom-color"],
+	BorderLeft: "border-left" = AnyOrder["border-left-width", "border-left-style", "border-left-color"],
+	BorderBlockStart: "border-block-start" = AnyOrder["border-block-start-width", "border-block-start-style", "border-block-start-color"],
+	BorderBlockEnd: "border-block-end" = AnyOrder["border-block-end-width", "border-block-end-style", "border-block-end-color"],
+	BorderInlineStart: "border-inline-start" = AnyOrder["border-inline-start-width", "border-inline-start-style", "border-inline-start-color"],
+	BorderInlineEnd: "border-inline-end" = AnyOrder["border-inline-end-width", "border-inline-end-style", "border-inline-end-color"],
+	Outline: "outline" = AnyOrder["outline-color", "outline-style", "outline-width"],
+	TextWrap: "text-wrap" = AnyOrder["text-wrap-mode", "text-wrap-style"],
+	Border: "border" = EverySide[
+		"border-top-width", "border-top-style", "border-top-color",
+		"border-right-width", "border-right-style", "border-right-color",
+		"border-bottom-width", "border-bottom-style", "border-bottom-color",
+		"border-left-width", "border-left-style", "border-left-color"
+	] + ["border-image-source", "border-image-slice", "border-image-width", "border-image-outset", "border-image-repeat"],
+	GridRow: "grid-row" = GridLines["grid-row-start", "grid-row-end"],
+	GridColumn: "grid-column" = GridLines["grid-column-start", "grid-column-end"],
+	GridArea: "grid-area" = GridLines["grid-row-start", "grid-column-start", "grid-row-end", "grid-column-end"],
+	GridTemplate: "grid-template" = GridTemplate["grid-template-rows", "grid-template-columns", "grid-template-areas"],
+	Grid: "grid" = GridTemplate[
+		"grid-template-rows", "grid-template-columns", "grid-template-areas",
+		"grid-auto-rows", "grid-auto-columns", "grid-auto-flow"
+	],
+	Flex: "flex" = Flex["flex-grow", "flex-shrink", "flex-basis"],
+	ListStyle: "list-style" = ListStyle["list-style-position", "list-style-image", "list-style-type"],
+	Font: "font" = Font["font-style", "font-variant-caps", "font-weight", "font-width", "font-size", "line-height", "font-family"] + [
+		"font-size-adjust", "font-kerning", "font-language-override", "font-optical-sizing", "font-feature-settings",
+		"font-variation-settings", "font-variant-alternates", "font-variant-east-asian", "font-variant-emoji",
+		"font-variant-ligatures", "font-variant-numeric", "font-variant-position"
+	],
+	Background: "background" = Background[
+		"background-image", "background-position", "background-size", "background-repeat", "background-attachment",
+		"background-origin", "background-clip", "background-color"
+	],
+}
+
+/// A declaration produced by [expanding][Property::expand] or [collapsing][Shorthand::collapse] a shorthand. As these
+/// are assembled from parts of other declarations (or from initial values) they are held as text rather than tokens.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DeclarationText {
+	pub name: &'static str,
+	pub value: String,
+	pub important: bool,
+}
+
+impl fmt::Display for DeclarationText {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}:{}", self.name, self.value)?;
+		if self.important {
+			f.write_str("!important")?;
+		}
+		Ok(())
+	}
+}
+
+fn wide_keyword(value: &StyleValue) -> Option<&'static str> {
+	match value {
+		StyleValue::Initial(_) => Some("initial"),
+		StyleValue::Inherit(_) => Some("inherit"),
+		StyleValue::Unset(_) => Some("unset"),
+		StyleValue::Revert(_) => Some("revert"),
+		StyleValue::RevertLayer(_) => Some("revert-layer"),
+		_ => None,
+	}
+}
+
+// Split a value into the text of each of its top level component values, with each top level comma as a component of
+// its own. Values using `var()` or `env()` can't be distributed over longhands, as the number of components is only
+// known once they are substituted.
+fn components(value: &impl ToCursors, source_text: &str) -> Option<Vec<String>> {
+	let mut cursors: Vec<Cursor> = vec![];
+	value.to_cursors(&mut cursors);
+	let mut groups: Vec<Vec<Cursor>> = vec![];
+	let mut depth = 0usize;
+	for c in cursors {
+		let kind = c.token().kind();
+		match kind {
+			Kind::Whitespace | Kind::Comment if depth == 0 => continue,
+			Kind::Semicolon if depth == 0 => return None,
+			Kind::Function => {
+				let name = c.str_slice(source_text).trim_end_matches('(');
+				if name.eq_ignore_ascii_case("var") || name.eq_ignore_ascii_case("env") {
+					return None;
+				}
+			}
+			_ => {}
+		}
+		if depth == 0 {
+			groups.push(vec![]);
+		}
+		match kind {
+			Kind::Function | Kind::LeftParen | Kind::LeftSquare | Kind::LeftCurly => depth += 1,
+			Kind::RightParen | Kind::RightSquare | Kind::RightCurly => depth = depth.saturating_sub(1),
+			_ => {}
+		}
+		groups.last_mut()?.push(c);
+	}
+	let mut strings = vec![];
+	for group in groups {
+		let mut string = String::new();
+		let mut sink = CursorFmtSink::new(source_text, &mut string);
+		for c in group {
+			sink.append(c);
+		}
+		strings.push(string);
+	}
+	Some(strings)
+}
+
+// Join component values with a space between each, except around a `/` and before a `,`.
+fn join(values: &[String]) -> String {
+	let mut string = String::new();
+	for (i, value) in values.iter().enumerate() {
+		if i > 0 && value != "," && value != "/" && values[i - 1] != "/" {
+			string.push(' ');
+		}
+		string.push_str(value);
+	}
+	string
+}
+
+fn initial(longhand: &str) -> Option<String> {
+	StyleValue::initial_by_name(longhand).map(String::from)
+}
+
+fn is_initial(longhand: &str, value: &str) -> bool {
+	StyleValue::initial_by_name(longhand).is_some_and(|initial| initial.eq_ignore_ascii_case(value))
+}
+
+fn is_number(value: &str) -> bool {
+	value.parse::<f32>().is_ok()
+}
+
+fn is_length_percentage(value: &str) -> bool {
+	value.starts_with(|c: char| c.is_ascii_digit() || matches!(c, '.' | '+' | '-')) && !value.starts_with("--")
+		|| value.to_ascii_lowercase().starts_with("calc(")
+}
+
+fn is_keyword(value: &str, keywords: &[&str]) -> bool {
+	keywords.iter().any(|keyword| value.eq_ignore_ascii_case(keyword))
+}
+
+// https://drafts.csswg.org/css-grid-2/#placement-shorthands
+fn is_custom_ident(value: &str) -> bool {
+	value.starts_with(|c: char| c.is_ascii_alphabetic() || matches!(c, '_' | '-' | '\\'))
+		&& !is_number(value)
+		&& !value.contains(' ')
+		&& !is_keyword(value, &["auto", "span"])
+}
+
+// Whether the value is valid for the named longhand by its own grammar, rather than by being a CSS-wide keyword,
+// or a value (like `calc()`) which is only checked at computed-value time.
+fn accepts(longhand: &str, value: &str) -> bool {
+	let bump = Bump::default();
+	let source_text = format!("{}:{}", longhand, value);
+	let result = Parser::new(&bump, &source_text).parse_entirely::<Property>();
+	result.errors.is_empty()
+		&& result.output.is_some_and(|property| {
+			wide_keyword(&property.value).is_none()
+				&& !matches!(property.value, StyleValue::Custom(_) | StyleValue::Computed(_) | StyleValue::Unknown(_))
+		})
+}
+
+// Assign each run of values to the first longhand without a value which accepts it, trying the longest runs first so
+// that a value of several components stays together. Omitted longhands are reset to their initial value.
+fn any_order(longhands: &[&str], values: &[String]) -> Option<Vec<String>> {
+	let mut assigned = vec![None; longhands.len()];
+	let mut rest = values;
+	while !rest.is_empty() {
+		let (index, len) = (1..=rest.len()).rev().find_map(|len| {
+			let value = join(&rest[..len]);
+			(0..longhands.len()).find(|&i| assigned[i].is_none() && accepts(longhands[i], &value)).map(|i| (i, len))
+		})?;
+		assigned[index] = Some(join(&rest[..len]));
+		rest = &rest[len..];
+	}
+	assigned.into_iter().zip(longhands).map(|(value, longhand)| value.or_else(|| initial(longhand))).collect()
+}
+
+// The values which aren't the initial value of their longhand, though the shorthand needs at least one of them.
+fn any_order_shortest(longhands: &[&str], values: Vec<String>) -> Vec<String> {
+	let first = values[0].clone();
+	let explicit = values
+		.into_iter()
+		.zip(longhands)
+		.filter(|(value, longhand)| !is_initial(longhand, value))
+		.map(|(value, _)| value)
+		.collect::<Vec<_>>();
+	if explicit.is_empty() {
+		vec![first]
+	} else {
+		explicit
+	}
+}
+
+// The alignment longhands of `place-*` have values of two components, where `first` & `last` qualify a `baseline`, and
+// `safe` & `unsafe` qualify a position.
+fn merge_alignment_qualifiers(values: Vec<String>) -> Vec<String> {
+	let mut merged: Vec<String> = vec![];
+	for value in values {
+		match merged.last_mut() {
+			Some(last) if is_keyword(last, &["first", "last", "safe", "unsafe"]) => {
+				last.push(' ');
+				last.push_str(&value);
+			}
+			_ => merged.push(value),
+		}
+	}
+	merged
+}
+
+// The line an omitted grid line copies: the start line for any other line, and the column start for the column end.
+fn grid_line_default(lines: &[String], index: usize, count: usize) -> String {
+	let from = &lines[index.saturating_sub(count / 2)];
+	if is_custom_ident(from) {
+		from.clone()
+	} else {
+		"auto".into()
+	}
+}
+
+// https://drafts.csswg.org/css-grid-2/#explicit-grid-shorthand
+// https://drafts.csswg.org/css-grid-2/#grid-shorthand
+fn grid_template(longhands: &[&str], values: &[String]) -> Option<Vec<String>> {
+	if values.iter().any(|value| value.starts_with(['"', '\''])) {
+		return None;
+	}
+	let mut assigned: Vec<Option<String>> = vec![None; longhands.len()];
+	if let [value] = values {
+		if !value.eq_ignore_ascii_case("none") {
+			return None;
+		}
+	} else {
+		let slash = values.iter().position(|value| value == "/")?;
+		let (rows, columns) = (&values[..slash], &values[slash + 1..]);
+		let auto_flow = |values: &[String]| values.iter().any(|value| value.eq_ignore_ascii_case("auto-flow"));
+		// Within `grid`, `auto-flow` on either side makes that side the implicit grid's tracks.
+		let (flow, implicit, explicit, side) = match (auto_flow(rows), auto_flow(columns)) {
+			(false, false) => {
+				assigned[0] = Some(join(rows)).filter(|rows| !rows.is_empty());
+				assigned[1] = Some(join(columns)).filter(|columns| !columns.is_empty());
+				if assigned[0].is_none() || assigned[1].is_none() {
+					return None;
+				}
+				(None, &[][..], &[][..], 0)
+			}
+			(true, false) if longhands.len() > 3 => (Some("row"), rows, columns, 0),
+			(false, true) if longhands.len() > 3 => (Some("column"), columns, rows, 1),
+			_ => return None,
+		};
+		if let Some(flow) = flow {
+			let dense = implicit.iter().any(|value| value.eq_ignore_ascii_case("dense"));
+			let tracks = implicit
+				.iter()
+				.filter(|value| !is_keyword(value, &["auto-flow", "dense"]))
+				.cloned()
+				.collect::<Vec<_>>();
+			if explicit.is_empty() || implicit.len() - tracks.len() != 1 + dense as usize {
+				return None;
+			}
+			assigned[1 - side] = Some(join(explicit));
+			if !tracks.is_empty() {
+				assigned[3 + side] = Some(join(&tracks));
+			}
+			assigned[5] = Some(if dense { format!("{} dense", flow) } else { flow.into() });
+		}
+	}
+	assigned.into_iter().zip(longhands).map(|(value, longhand)| value.or_else(|| initial(longhand))).collect()
+}
+
+// https://drafts.csswg.org/css-flexbox-1/#flex-property
+fn flex(values: &[String]) -> Option<Vec<String>> {
+	if let [value] = values {
+		if value.eq_ignore_ascii_case("none") {
+			return Some(vec!["0".into(), "0".into(), "auto".into()]);
+		}
+		if value.eq_ignore_ascii_case("auto") {
+			return Some(vec!["1".into(), "1".into(), "auto".into()]);
+		}
+	}
+	let (mut grow, mut shrink, mut basis) = (None, None, None);
+	let mut after_grow = false;
+	for value in values {
+		let number = is_number(value);
+		if number && grow.is_none() {
+			grow = Some(value.clone());
+			after_grow = true;
+		} else if number && shrink.is_none() && after_grow {
+			shrink = Some(value.clone());
+			after_grow = false;
+		// A unitless zero is a flex factor, unless both flex factors are already given.
+		} else if basis.is_none() && (!number || (shrink.is_some() && value == "0")) && accepts("flex-basis", value) {
+			basis = Some(value.clone());
+			after_grow = false;
+		} else {
+			return None;
+		}
+	}
+	// An omitted basis is `0`, rather than the initial value of `flex-basis`.
+	Some(vec![grow.unwrap_or("1".into()), shrink.unwrap_or("1".into()), basis.unwrap_or("0".into())])
+}
+
+// https://drafts.csswg.org/css-lists-3/#list-style-property
+fn list_style(longhands: &[&str], values: &[String]) -> Option<Vec<String>> {
+	let mut assigned: Vec<Option<String>> = vec![None; longhands.len()];
+	let mut nones = 0;
+	for value in values {
+		let index = if value.eq_ignore_ascii_case("none") {
+			nones += 1;
+			continue;
+		} else if accepts(longhands[0], value) {
+			0
+		} else if accepts(longhands[1], value) {
+			1
+		} else {
+			2
+		};
+		if assigned[index].replace(value.clone()).is_some() {
+			return None;
+		}
+	}
+	let omitted = assigned[1..].iter().filter(|value| value.is_none()).count();
+	if nones > omitted {
+		return None;
+	}
+	if nones > 0 {
+		for value in assigned[1..].iter_mut().filter(|value| value.is_none()) {
+			*value = Some("none".into());
+		}
+	}
+	assigned.into_iter().zip(longhands).map(|(value, longhand)| value.or_else(|| initial(longhand))).collect()
+}
+
+// https://drafts.csswg.org/css-fonts-4/#font-prop
+fn font(longhands: &[&str], values: &[String]) -> Option<Vec<String>> {
+	let mut assigned: Vec<Option<String>> = vec![None; longhands.len()];
+	let mut i = 0;
+	// Up to four of the style, variant, weight & width, any of which may be `normal`.
+	let mut count = 0;
+	while count < 4 && i < values.len() {
+		let value = &values[i];
+		let index = if value.eq_ignore_ascii_case("normal") {
+			None
+		} else if is_keyword(value, &["italic", "oblique"]) {
+			Some(0)
+		} else if value.eq_ignore_ascii_case("small-caps") {
+			Some(1)
+		} else if is_keyword(value, &["bold", "bolder", "lighter"])
+			|| value.parse::<f32>().is_ok_and(|weight| (1.0..=1000.0).contains(&weight))
+		{
+			Some(2)
+		} else if !value.ends_with('%') && accepts(longhands[3], value) {
+			Some(3)
+		} else {
+			break;
+		};
+		let mut value = value.clone();
+		// `oblique` may be followed by an angle.
+		if let Some(angle) = values.get(i + 1).filter(|angle| {
+			value.eq_ignore_ascii_case("oblique")
+				&& is_length_percentage(angle)
+				&& ["deg", "grad", "rad", "turn"].iter().any(|unit| angle.to_ascii_lowercase().ends_with(unit))
+		}) {
+			value = join(&[value, angle.clone()]);
+			i += 1;
+		}
+		if let Some(index) = index {
+			if assigned[index].replace(value).is_some() {
+				return None;
+			}
+		}
+		count += 1;
+		i += 1;
+	}
+	assigned[4] = Some(values.get(i).filter(|size| accepts(longhands[4], size))?.clone());
+	i += 1;
+	if values.get(i).is_some_and(|value| value == "/") {
+		assigned[5] = Some(values.get(i + 1).filter(|line_height| accepts(longhands[5], line_height))?.clone());
+		i += 2;
+	}
+	let family = values.get(i..)?;
+	if family.is_empty() || family.first() == Some(&",".into()) || family.last() == Some(&",".into()) {
+		return None;
+	}
+	if family.iter().any(|value| value == "/") {
+		return None;
+	}
+	assigned[6] = Some(join(family));
+	assigned.into_iter().zip(longhands).map(|(value, longhand)| value.or_else(|| initial(longhand))).collect()
+}
+
+// https://drafts.csswg.org/css-backgrounds-3/#background
+fn background(longhands: &[&str], values: &[String]) -> Option<Vec<String>> {
+	let is_image = |value: &str| {
+		let value = value.to_ascii_lowercase();
+		value == "none"
+			|| value.contains("gradient(")
+			|| ["url(", "image(", "image-set(", "-webkit-image-set(", "cross-fade(", "element("]
+				.iter()
+				.any(|function| value.starts_with(function))
+	};
+	let is_position =
+		|value: &str| is_keyword(value, &["left", "right", "top", "bottom", "center"]) || is_length_percentage(value);
+	let mut assigned: Vec<Option<String>> = vec![None; longhands.len()];
+	let mut boxes = vec![];
+	let mut previous = None;
+	let mut i = 0;
+	while i < values.len() {
+		let value = &values[i];
+		let (index, len) = if is_image(value) {
+			(0, 1)
+		} else if is_position(value) {
+			(1, values[i..].iter().take(4).take_while(|value| is_position(value)).count())
+		} else if value == "/" && previous == Some(1) {
+			// The size follows the position, after a `/`.
+			i += 1;
+			let sizes = values.get(i..)?;
+			let len = if sizes.first().is_some_and(|size| is_keyword(size, &["cover", "contain"])) {
+				1
+			} else {
+				sizes
+					.iter()
+					.take(2)
+					.take_while(|size| is_keyword(size, &["auto"]) || is_length_percentage(size))
+					.count()
+			};
+			(2, len)
+		} else if let Some(len) =
+			(1..=2).rev().find(|&len| values.get(i..i + len).is_some_and(|repeat| accepts(longhands[3], &join(repeat))))
+		{
+			(3, len)
+		} else if accepts(longhands[4], value) {
+			(4, 1)
+		} else if accepts(longhands[5], value) && boxes.len() < 2 {
+			boxes.push(value.clone());
+			previous = Some(5);
+			i += 1;
+			continue;
+		} else if accepts(longhands[7], value) {
+			(7, 1)
+		} else {
+			return None;
+		};
+		if len == 0 || assigned[index].replace(join(&values[i..i + len])).is_some() {
+			return None;
+		}
+		previous = Some(index);
+		i += len;
+	}
+	// A single box sets both the origin & the clip.
+	match boxes.as_slice() {
+		[] => {}
+		[both] => {
+			assigned[5] = Some(both.clone());
+			assigned[6] = Some(both.clone());
+		}
+		[origin, clip, ..] => {
+			assigned[5] = Some(origin.clone());
+			assigned[6] = Some(clip.clone());
+		}
+	}
+	assigned.into_iter().zip(longhands).map(|(value, longhand)| value.or_else(|| initial(longhand))).collect()
+}
+
+impl Shorthand {
+	/// Every shorthand which sets the given (lowercase) longhand.
+	pub fn for_longhand(longhand: &str) -> impl Iterator<Item = Shorthand> + '_ {
+		Self::ALL.iter().copied().filter(move |shorthand| shorthand.longhands().contains(&longhand))
+	}
+
+	// The longhands this shorthand is given values for, without those it resets.
+	fn valued_longhands(&self) -> &'static [&'static str] {
+		let longhands = self.longhands();
+		&longhands[..longhands.len() - self.resets().len()]
+	}
+
+	// Distribute the component values of this shorthand over each of its longhands.
+	fn distribute(&self, values: Vec<String>) -> Option<Vec<String>> {
+		let longhands = self.valued_longhands();
+		// Only a font family is a comma separated list; other commas separate layers, which can't be distributed.
+		if values.is_empty() || (self.syntax() != ShorthandSyntax::Font && values.iter().any(|value| value == ",")) {
+			return None;
+		}
+		let mut values = match (self.syntax(), values.len()) {
+			// https://drafts.csswg.org/css-box-4/#margin-shorthand
+			(ShorthandSyntax::Sides, 1..=4) => {
+				let mut values = values;
+				while values.len() < 4 {
+					values.push(values[values.len().saturating_sub(2)].clone());
+				}
+				values
+			}
+			(ShorthandSyntax::Pair, _) => {
+				let mut values = merge_alignment_qualifiers(values);
+				match values.len() {
+					// https://drafts.csswg.org/css-align-3/#place-content
+					1 if *self == Shorthand::PlaceContent && values[0].to_ascii_lowercase().ends_with("baseline") => {
+						values.push("start".into())
+					}
+					1 => values.push(values[0].clone()),
+					2 => {}
+					_ => return None,
+				}
+				values
+			}
+			(ShorthandSyntax::AnyOrder, _) => any_order(longhands, &values)?,
+			(ShorthandSyntax::EverySide, _) => {
+				let side = any_order(&longhands[..longhands.len() / 4], &values)?;
+				side.iter().cycle().take(longhands.len()).cloned().collect()
+			}
+			// https://drafts.csswg.org/css-grid-2/#placement-shorthands
+			(ShorthandSyntax::GridLines, _) => {
+				let mut lines = values
+					.split(|value| value == "/")
+					.map(|line| (!line.is_empty()).then(|| join(line)))
+					.collect::<Option<Vec<_>>>()?;
+				if lines.len() > longhands.len() {
+					return None;
+				}
+				for index in lines.len()..longhands.len() {
+					lines.push(grid_line_default(&lines, index, longhands.len()));
+				}
+				lines
+			}
+			(ShorthandSyntax::GridTemplate, _) => grid_template(longhands, &values)?,
+			(ShorthandSyntax::Flex, _) => flex(&values)?,
+			(ShorthandSyntax::ListStyle, _) => list_style(longhands, &values)?,
+			(ShorthandSyntax::Font, _) => font(longhands, &values)?,
+			(ShorthandSyntax::Background, _) => background(longhands, &values)?,
+			_ => return None,
+		};
+		for longhand in self.resets() {
+			values.push(initial(longhand)?);
+		}
+		Some(values)
+	}
+
+	// The fewest values needed to express each of the longhand values, or `None` if the shorthand can't express them.
+	fn shortest(&self, mut values: Vec<String>) -> Option<Vec<String>> {
+		let same = |a: &String, b: &String| a.eq_ignore_ascii_case(b);
+		let longhands = self.valued_longhands();
+		match self.syntax() {
+			ShorthandSyntax::Sides => {
+				if same(&values[1], &values[3]) {
+					values.pop();
+					if same(&values[0], &values[2]) {
+						values.pop();
+						if same(&values[0], &values[1]) {
+							values.pop();
+						}
+					}
+				}
+				Some(values)
+			}
+			ShorthandSyntax::Pair => {
+				if same(&values[0], &values[1]) {
+					values.pop();
+				}
+				Some(values)
+			}
+			ShorthandSyntax::AnyOrder => Some(any_order_shortest(longhands, values)),
+			ShorthandSyntax::EverySide => {
+				let side = longhands.len() / 4;
+				if values.chunks(side).any(|other| !other.iter().zip(&values[..side]).all(|(a, b)| same(a, b))) {
+					return None;
+				}
+				values.truncate(side);
+				Some(any_order_shortest(&longhands[..side], values))
+			}
+			ShorthandSyntax::GridLines => {
+				while values.len() > 1 {
+					let index = values.len() - 1;
+					if !same(&values[index], &grid_line_default(&values, index, longhands.len())) {
+						break;
+					}
+					values.pop();
+				}
+				Some(values.into_iter().flat_map(|line| ["/".into(), line]).skip(1).collect())
+			}
+			ShorthandSyntax::GridTemplate => {
+				let none = |index: usize| is_initial(longhands[index], &values[index]);
+				let implicit = values.len() > 3 && !(3..6).all(none);
+				if !none(2) {
+					// Template areas are written as strings, which aren't distributed.
+					None
+				} else if !implicit && none(0) && none(1) {
+					Some(vec!["none".into()])
+				} else if !implicit {
+					Some(vec![values[0].clone(), "/".into(), values[1].clone()])
+				} else {
+					let dense = values[5].to_ascii_lowercase().ends_with(" dense");
+					let flow = if dense { "auto-flow dense" } else { "auto-flow" };
+					let row = values[5].to_ascii_lowercase().starts_with("row");
+					let (side, explicit) = if row { (0, 1) } else { (1, 0) };
+					if !none(side) || !none(4 - side) {
+						return None;
+					}
+					let mut implicit = vec![flow.into()];
+					if !is_initial(longhands[3 + side], &values[3 + side]) {
+						implicit.push(values[3 + side].clone());
+					}
+					let mut shortest = vec![values[explicit].clone(), "/".into()];
+					if row {
+						shortest.truncate(0);
+						shortest.extend(implicit);
+						shortest.extend(["/".into(), values[explicit].clone()]);
+					} else {
+						shortest.extend(implicit);
+					}
+					Some(shortest)
+				}
+			}
+			ShorthandSyntax::Flex => {
+				let [grow, shrink, basis] = <[String; 3]>::try_from(values).ok()?;
+				let one = |value: &String| value == "1";
+				Some(match (grow.as_str(), shrink.as_str(), basis.as_str()) {
+					("0", "0", basis) if basis.eq_ignore_ascii_case("auto") => vec!["none".into()],
+					("1", "1", basis) if basis.eq_ignore_ascii_case("auto") => vec!["auto".into()],
+					(_, _, "0") if one(&shrink) => vec![grow],
+					(_, _, "0") => vec![grow, shrink],
+					_ if one(&shrink) && one(&grow) => vec![basis],
+					_ if one(&shrink) => vec![grow, basis],
+					_ => vec![grow, shrink, basis],
+				})
+			}
+			ShorthandSyntax::ListStyle => Some(any_order_shortest(longhands, values)),
+			ShorthandSyntax::Font => {
+				let mut shortest = values[..4]
+					.iter()
+					.filter(|value| !value.eq_ignore_ascii_case("normal"))
+					.cloned()
+					.collect::<Vec<_>>();
+				shortest.push(values[4].clone());
+				if !is_initial(longhands[5], &values[5]) {
+					shortest.extend(["/".into(), values[5].clone()]);
+				}
+				shortest.push(values[6].clone());
+				Some(shortest)
+			}
+			ShorthandSyntax::Background => {
+				let explicit = |index: usize| !is_initial(longhands[index], &values[index]);
+				let mut shortest = vec![];
+				if explicit(0) {
+					shortest.push(values[0].clone());
+				}
+				if explicit(2) {
+					shortest.extend([values[1].clone(), "/".into(), values[2].clone()]);
+				} else if explicit(1) {
+					shortest.push(values[1].clone());
+				}
+				shortest.extend((3..5).filter(|&index| explicit(index)).map(|index| values[index].clone()));
+				if same(&values[5], &values[6]) {
+					shortest.push(values[5].clone());
+				} else if explicit(5) || explicit(6) {
+					shortest.extend([values[5].clone(), values[6].clone()]);
+				}
+				if explicit(7) || shortest.is_empty() {
+					shortest.push(values[7].clone());
+				}
+				Some(shortest)
+			}
+		}
+	}
+
+	// Whether giving this shorthand the value sets each of its longhands to the given values.
+	fn expands_to(&self, value: &str, longhands: &[String]) -> bool {
+		let bump = Bump::default();
+		let source_text = format!("{}:{}", self.name(), value);
+		let Some(property) = Parser::new(&bump, &source_text).parse_entirely::<Property>().output else {
+			return false;
+		};
+		components(&property.value, &source_text).and_then(|values| self.distribute(values)).is_some_and(|values| {
+			values.len() == longhands.len() && values.iter().zip(longhands).all(|(a, b)| a.eq_ignore_ascii_case(b))
+		})
+	}
+
+	// Whether a declaration of the named property, besides one of this shorthand's longhands, also sets any of them.
+	fn overlaps(&self, name: &str) -> bool {
+		name == "all"
+			|| self.longhands().iter().any(|longhand| {
+				longhand.strip_prefix(name).is_some_and(|rest| rest.starts_with('-'))
+					|| Shorthand::from_name(name).is_some_and(|other| other.longhands().contains(longhand))
+			})
+	}
+
+	/// Merge the longhands of this shorthand declared in a rule into a single declaration, using the shortest form of
+	/// the shorthand.
+	///
+	/// Returns `None` unless every longhand is declared, with the same importance, and with values the shorthand can
+	/// express (or the same CSS-wide keyword). Longhands the shorthand [resets][Shorthand::resets] must have their
+	/// initial value. To avoid changing the cascade, it also returns `None` if some other declaration in the rule
+	/// (such as another shorthand) sets any of the longhands.
+	pub fn collapse<'b, 'a: 'b>(
+		&self,
+		declarations: impl IntoIterator<Item = &'b Property<'a>>,
+		source_text: &str,
+	) -> Option<DeclarationText> {
+		let longhands = self.longhands();
+		let mut cascaded: Vec<Option<&'b Property<'a>>> = vec![None; longhands.len()];
+		for property in declarations {
+			let name = Cursor::from(property.name).str_slice(source_text).to_ascii_lowercase();
+			if let Some(index) = longhands.iter().position(|longhand| *longhand == name) {
+				if !cascaded[index].is_some_and(|prev| prev.important.is_some() && property.important.is_none()) {
+					cascaded[index] = Some(property);
+				}
+			} else if self.overlaps(&name) {
+				return None;
+			}
+		}
+		let cascaded = cascaded.into_iter().collect::<Option<Vec<_>>>()?;
+		let important = cascaded[0].important.is_some();
+		if cascaded.iter().any(|property| property.important.is_some() != important) {
+			return None;
+		}
+		let keywords = cascaded.iter().map(|property| wide_keyword(&property.value)).collect::<Vec<_>>();
+		let value = match keywords[0] {
+			Some(keyword) if keywords.iter().all(|other| *other == Some(keyword)) => keyword.to_string(),
+			None if keywords.iter().all(Option::is_none) => {
+				// Values of longhands which aren't parsed yet can't be checked, so are taken as they are.
+				let values = cascaded
+					.iter()
+					.zip(longhands)
+					.map(|(property, longhand)| match &property.value {
+						StyleValue::Unknown(_) if StyleValue::parses_by_name(longhand) => None,
+						value => components(value, source_text).filter(|values| !values.is_empty()).map(|v| join(&v)),
+					})
+					.collect::<Option<Vec<_>>>()?;
+				let (valued, resets) = values.split_at(values.len() - self.resets().len());
+				if resets.iter().zip(self.resets()).any(|(value, longhand)| !is_initial(longhand, value)) {
+					return None;
+				}
+				let value = join(&self.shortest(valued.to_vec())?);
+				// The shortest form is checked against the longhands, so that a value which would be distributed
+				// differently (such as a keyword a different longhand also accepts) is never collapsed.
+				if !self.expands_to(&value, &values) {
+					return None;
+				}
+				value
+			}
+			_ => return None,
+		};
+		Some(DeclarationText { name: self.name(), value, important })
+	}
+
+	/// [Collapse][Shorthand::collapse] every shorthand whose longhands are all declared in a rule. Shorthands which
+	/// replace more longhands are preferred (so `margin` wins over `margin-block`), and no two of the returned
+	/// shorthands set the same longhand.
+	pub fn collapse_all<'b, 'a: 'b>(
+		declarations: impl IntoIterator<Item = &'b Property<'a>>,
+		source_text: &str,
+	) -> Vec<(Shorthand, DeclarationText)> {
+		let declarations = declarations.into_iter().collect::<Vec<_>>();
+		let mut shorthands = Self::ALL.to_vec();
+		shorthands.sort_by_key(|shorthand| Reverse(shorthand.longhands().len()));
+		let mut collapsed: Vec<(Shorthand, DeclarationText)> = vec![];
+		for shorthand in shorthands {
+			let longhands = shorthand.longhands();
+			if collapsed.iter().any(|(other, _)| other.longhands().iter().any(|longhand| longhands.contains(longhand)))
+			{
+				continue;
+			}
+			if let Some(declaration) = shorthand.collapse(declarations.iter().copied(), source_text) {
+				collapsed.push((shorthand, declaration));
+			}
+		}
+		collapsed
+	}
+}
+
+impl<'a> Property<'a> {
+	/// Expand this declaration into the longhands it sets, if it is a [Shorthand]. Longhands omitted from the value are
+	/// set to their initial value.
+	///
+	/// Returns `None` for properties which aren't shorthands, and for values which can't be distributed over the
+	/// longhands before computed-value time, such as those using `var()`.
+	pub fn expand(&self, source_text: &str) -> Option<Vec<DeclarationText>> {
+		let name = Cursor::from(self.name).str_slice(source_text).to_ascii_lowercase();
+		let shorthand = Shorthand::from_name(&name)?;
+		let longhands = shorthand.longhands();
+		let values = match wide_keyword(&self.value) {
+			Some(keyword) => vec![keyword.to_string(); longhands.len()],
+			None => shorthand.distribute(components(&self.value, source_text)?)?,
+		};
+		let important = self.important.is_some();
+		Some(longhands.iter().zip(values).map(|(name, value)| DeclarationText { name, value, important }).collect())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::StyleRule;
+
+	fn expand(source_text: &str) -> Option<Vec<String>> {
+		let bump = Bump::default();
+		let property = Parser::new(&bump, source_text).parse_entirely::<Property>().output.unwrap();
+		property.expand(source_text).map(|longhands| longhands.iter().map(ToString::to_string).collect())
+	}
+
+	fn collapse(source_text: &str) -> Vec<String> {
+		let bump = Bump::default();
+		let rule = Parser::new(&bump, source_text).parse_entirely::<StyleRule>().output.unwrap();
+		Shorthand::collapse_all(rule.style.declarations.iter().map(|(property, _)| property), source_text)
+			.iter()
+			.map(|(_, declaration)| declaration.to_string())
+			.collect()
+	}
+
+	#[test]
+	fn test_metadata() {
+		for shorthand in Shorthand::ALL {
+			assert_eq!(Shorthand::from_name(shorthand.name()), Some(*shorthand));
+			// Omitted longhands are reset to their initial value, except for those of `flex` & the grid lines.
+			if !matches!(
+				shorthand.syntax(),
+				ShorthandSyntax::Sides | ShorthandSyntax::Pair | ShorthandSyntax::GridLines | ShorthandSyntax::Flex
+			) {
+				// The font family can't be omitted, so its initial value (which depends on the user agent) isn't needed.
+				for longhand in shorthand.longhands().iter().filter(|longhand| **longhand != "font-family") {
+					assert!(StyleValue::initial_by_name(longhand).is_some(), "{longhand} has no initial value");
+				}
+			}
+			assert!(shorthand.longhands().ends_with(shorthand.resets()));
+		}
+		assert_eq!(Shorthand::for_longhand("margin-top").collect::<Vec<_>>(), vec![Shorthand::Margin]);
+		assert_eq!(
+			Shorthand::for_longhand("border-top-color").collect::<Vec<_>>(),
+			vec![Shorthand::BorderColor, Shorthand::BorderTop, Shorthand::Border]
+		);
+		assert_eq!(
+			Shorthand::for_longhand("grid-row-start").collect::<Vec<_>>(),
+			vec![Shorthand::GridRow, Shorthand::GridArea]
+		);
+		assert_eq!(Shorthand::for_longhand("color").count(), 0);
+	}
+
+	#[test]
+	fn test_expand() {
+		assert_eq!(
+			expand("margin:1px 2px").unwrap(),
+			["margin-top:1px", "margin-right:2px", "margin-bottom:1px", "margin-left:2px"]
+		);
+		assert_eq!(
+			expand("padding:1px 2px 3px!important").unwrap(),
+			[
+				"padding-top:1px!important",
+				"padding-right:2px!important",
+				"padding-bottom:3px!important",
+				"padding-left:2px!important"
+			]
+		);
+		assert_eq!(
+			expand("inset:calc(100% - 1px) auto 0 auto").unwrap(),
+			["top:calc(100% - 1px)", "right:auto", "bottom:0", "left:auto"]
+		);
+		assert_eq!(expand("gap:1em").unwrap(), ["row-gap:1em", "column-gap:1em"]);
+		assert_eq!(expand("overflow:hidden auto").unwrap(), ["overflow-x:hidden", "overflow-y:auto"]);
+		assert_eq!(expand("flex-flow:wrap").unwrap(), ["flex-direction:row", "flex-wrap:wrap"]);
+		assert_eq!(
+			expand("border-top:red 1px").unwrap(),
+			["border-top-width:1px", "border-top-style:none", "border-top-color:red"]
+		);
+		assert_eq!(expand("columns:3 10em").unwrap(), ["column-width:10em", "column-count:3"]);
+		assert_eq!(expand("margin-block:inherit").unwrap(), ["margin-block-start:inherit", "margin-block-end:inherit"]);
+	}
+
+	#[test]
+	fn test_expand_border() {
+		let longhands = expand("border:1px solid red").unwrap();
+		assert_eq!(longhands.len(), 17);
+		assert_eq!(&longhands[..3], ["border-top-width:1px", "border-top-style:solid", "border-top-color:red"]);
+		assert_eq!(&longhands[9..12], ["border-left-width:1px", "border-left-style:solid", "border-left-color:red"]);
+		assert_eq!(
+			&longhands[12..],
+			[
+				"border-image-source:none",
+				"border-image-slice:100%",
+				"border-image-width:1",
+				"border-image-outset:0",
+				"border-image-repeat:stretch"
+			]
+		);
+		assert_eq!(
+			expand("border:none").unwrap()[..3],
+			["border-top-width:medium", "border-top-style:none", "border-top-color:currentcolor"]
+		);
+	}
+
+	#[test]
+	fn test_expand_place() {
+		assert_eq!(expand("place-content:center").unwrap(), ["align-content:center", "justify-content:center"]);
+		assert_eq!(
+			expand("place-content:first baseline").unwrap(),
+			["align-content:first baseline", "justify-content:start"]
+		);
+		assert_eq!(
+			expand("place-items:safe center stretch").unwrap(),
+			["align-items:safe center", "justify-items:stretch"]
+		);
+		assert_eq!(expand("place-self:auto end").unwrap(), ["align-self:auto", "justify-self:end"]);
+	}
+
+	#[test]
+	fn test_expand_flex() {
+		assert_eq!(expand("flex:none").unwrap(), ["flex-grow:0", "flex-shrink:0", "flex-basis:auto"]);
+		assert_eq!(expand("flex:auto").unwrap(), ["flex-grow:1", "flex-shrink:1", "flex-basis:auto"]);
+		assert_eq!(expand("flex:2").unwrap(), ["flex-grow:2", "flex-shrink:1", "flex-basis:0"]);
+		assert_eq!(expand("flex:2 3").unwrap(), ["flex-grow:2", "flex-shrink:3", "flex-basis:0"]);
+		assert_eq!(expand("flex:10px").unwrap(), ["flex-grow:1", "flex-shrink:1", "flex-basis:10px"]);
+		assert_eq!(expand("flex:2 10%").unwrap(), ["flex-grow:2", "flex-shrink:1", "flex-basis:10%"]);
+		assert_eq!(expand("flex:content 2 0").unwrap(), ["flex-grow:2", "flex-shrink:0", "flex-basis:content"]);
+		assert_eq!(expand("flex:2 3 0").unwrap(), ["flex-grow:2", "flex-shrink:3", "flex-basis:0"]);
+		assert_eq!(expand("flex:1 2 3"), None);
+	}
+
+	#[test]
+	fn test_expand_grid() {
+		assert_eq!(expand("grid-row:1").unwrap(), ["grid-row-start:1", "grid-row-end:auto"]);
+		assert_eq!(expand("grid-row:main").unwrap(), ["grid-row-start:main", "grid-row-end:main"]);
+		assert_eq!(expand("grid-column:1 / span 2").unwrap(), ["grid-column-start:1", "grid-column-end:span 2"]);
+		assert_eq!(
+			expand("grid-area:a / b").unwrap(),
+			["grid-row-start:a", "grid-column-start:b", "grid-row-end:a", "grid-column-end:b"]
+		);
+		assert_eq!(
+			expand("grid-area:1 / 2 / 3").unwrap(),
+			["grid-row-start:1", "grid-column-start:2", "grid-row-end:3", "grid-column-end:auto"]
+		);
+		assert_eq!(
+			expand("grid-template:100px 1fr / 50px").unwrap(),
+			["grid-template-rows:100px 1fr", "grid-template-columns:50px", "grid-template-areas:none"]
+		);
+		assert_eq!(
+			expand("grid-template:none").unwrap(),
+			["grid-template-rows:none", "grid-template-columns:none", "grid-template-areas:none"]
+		);
+		assert_eq!(
+			expand("grid:auto-flow dense 1fr / 100px").unwrap(),
+			[
+				"grid-template-rows:none",
+				"grid-template-columns:100px",
+				"grid-template-areas:none",
+				"grid-auto-rows:1fr",
+				"grid-auto-columns:auto",
+				"grid-auto-flow:row dense"
+			]
+		);
+		assert_eq!(
+			expand("grid:100px / auto-flow").unwrap(),
+			[
+				"grid-template-rows:100px",
+				"grid-template-columns:none",
+				"grid-template-areas:none",
+				"grid-auto-rows:auto",
+				"grid-auto-columns:auto",
+				"grid-auto-flow:column"
+			]
+		);
+		assert_eq!(expand("grid-area:1 / 2 / 3 / 4 / 5"), None);
+		assert_eq!(expand("grid-template:\"a b\" 1fr / 1fr 1fr"), None);
+	}
+
+	#[test]
+	fn test_expand_list_style() {
+		assert_eq!(
+			expand("list-style:square inside").unwrap(),
+			["list-style-position:inside", "list-style-image:none", "list-style-type:square"]
+		);
+		assert_eq!(
+			expand("list-style:none").unwrap(),
+			["list-style-position:outside", "list-style-image:none", "list-style-type:none"]
+		);
+		assert_eq!(
+			expand("list-style:url(a.png) none").unwrap(),
+			["list-style-position:outside", "list-style-image:url(a.png)", "list-style-type:none"]
+		);
+		assert_eq!(expand("list-style:none none none"), None);
+	}
+
+	#[test]
+	fn test_expand_font() {
+		let longhands = expand("font:italic bold 12px/1.5 \"Helvetica Neue\", serif").unwrap();
+		assert_eq!(longhands.len(), 19);
+		assert_eq!(
+			&longhands[..7],
+			[
+				"font-style:italic",
+				"font-variant-caps:normal",
+				"font-weight:bold",
+				"font-width:normal",
+				"font-size:12px",
+				"line-height:1.5",
+				"font-family:\"Helvetica Neue\", serif"
+			]
+		);
+		assert_eq!(
+			&expand("font:small-caps 1em sans-serif").unwrap()[..7],
+			[
+				"font-style:normal",
+				"font-variant-caps:small-caps",
+				"font-weight:normal",
+				"font-width:normal",
+				"font-size:1em",
+				"line-height:normal",
+				"font-family:sans-serif"
+			]
+		);
+		assert_eq!(expand("font:12px"), None);
+		assert_eq!(expand("font:bold bold 12px serif"), None);
+	}
+
+	#[test]
+	fn test_expand_background() {
+		assert_eq!(
+			expand("background:red").unwrap(),
+			[
+				"background-image:none",
+				"background-position:0% 0%",
+				"background-size:auto",
+				"background-repeat:repeat",
+				"background-attachment:scroll",
+				"background-origin:padding-box",
+				"background-clip:border-box",
+				"background-color:red"
+			]
+		);
+		assert_eq!(
+			expand("background:url(a.png) center / cover no-repeat fixed content-box #fff").unwrap(),
+			[
+				"background-image:url(a.png)",
+				"background-position:center",
+				"background-size:cover",
+				"background-repeat:no-repeat",
+				"background-attachment:fixed",
+				"background-origin:content-box",
+				"background-clip:content-box",
+				"background-color:#fff"
+			]
+		);
+		assert_eq!(expand("background:url(a.png), url(b.png)"), None);
+		assert_eq!(expand("background:red blue"), None);
+	}
+
+	#[test]
+	fn test_expand_unsupported() {
+		assert_eq!(expand("margin-top:1px"), None);
+		assert_eq!(expand("margin:1px 2px 3px 4px 5px"), None);
+		assert_eq!(expand("margin:var(--gutter)"), None);
+		assert_eq!(expand("flex-flow:row row"), None);
+		assert_eq!(expand("border-top:1px solid red blue"), None);
+	}
+
+	#[test]
+	fn test_collapse() {
+		assert_eq!(
+			collapse("a{margin-top:1px;margin-right:2px;margin-bottom:1px;margin-left:2px}"),
+			["margin:1px 2px"]
+		);
+		assert_eq!(collapse("a{margin-top:0;margin-right:0;margin-bottom:0;margin-left:0}"), ["margin:0"]);
+		assert_eq!(
+			collapse("a{padding-top:1px;padding-right:2px;padding-bottom:3px;padding-left:4px}"),
+			["padding:1px 2px 3px 4px"]
+		);
+		assert_eq!(collapse("a{row-gap:1px;column-gap:2px}"), ["gap:1px 2px"]);
+		assert_eq!(collapse("a{flex-direction:row;flex-wrap:nowrap}"), ["flex-flow:row"]);
+		assert_eq!(collapse("a{flex-direction:column;flex-wrap:nowrap}"), ["flex-flow:column"]);
+		assert_eq!(
+			collapse("a{border-top-width:medium;border-top-style:solid;border-top-color:red}"),
+			["border-top:solid red"]
+		);
+		assert_eq!(collapse("a{overflow-x:inherit;overflow-y:inherit}"), ["overflow:inherit"]);
+		assert_eq!(collapse("a{row-gap:1px!important;column-gap:1px!important}"), ["gap:1px!important"]);
+		assert_eq!(
+			collapse(concat!(
+				"a{border-top-width:1px;border-top-style:solid;border-top-color:red;",
+				"border-right-width:1px;border-right-style:solid;border-right-color:red;",
+				"border-bottom-width:1px;border-bottom-style:solid;border-bottom-color:red;",
+				"border-left-width:1px;border-left-style:solid;border-left-color:red;",
+				"border-image-source:none;border-image-slice:100%;border-image-width:1;border-image-outset:0;",
+				"border-image-repeat:stretch}"
+			)),
+			["border:1px solid red"]
+		);
+		assert_eq!(collapse("a{align-items:center;justify-items:center}"), ["place-items:center"]);
+		assert_eq!(collapse("a{align-self:flex-start;justify-self:end}"), ["place-self:flex-start end"]);
+		assert_eq!(collapse("a{flex-grow:0.5;flex-shrink:1.5;flex-basis:10px}"), ["flex:0.5 1.5 10px"]);
+		assert_eq!(collapse("a{flex-grow:2.5;flex-shrink:1.5;flex-basis:0}"), ["flex:2.5 1.5"]);
+		assert_eq!(collapse("a{grid-row-start:1;grid-row-end:auto}"), ["grid-row:1"]);
+		assert_eq!(collapse("a{grid-column-start:1;grid-column-end:3}"), ["grid-column:1/3"]);
+		assert_eq!(
+			collapse("a{grid-template-rows:1fr;grid-template-columns:1fr 1fr;grid-template-areas:none}"),
+			["grid-template:1fr/1fr 1fr"]
+		);
+		assert_eq!(
+			collapse("a{list-style-position:outside;list-style-image:none;list-style-type:square}"),
+			["list-style:square"]
+		);
+		assert_eq!(
+			collapse(concat!(
+				"a{background-image:none;background-position:0% 0%;background-size:auto;background-repeat:no-repeat;",
+				"background-attachment:scroll;background-origin:padding-box;background-clip:border-box;",
+				"background-color:red}"
+			)),
+			["background:no-repeat red"]
+		);
+	}
+
+	#[test]
+	fn test_collapse_prefers_larger_shorthands() {
+		assert_eq!(
+			collapse(concat!(
+				"a{border-top-width:1px;border-right-width:1px;border-bottom-width:1px;border-left-width:1px;",
+				"border-top-style:solid;border-top-color:red}"
+			)),
+			["border-width:1px"]
+		);
+	}
+
+	#[test]
+	fn test_collapse_unsafe() {
+		// Incomplete
+		assert_eq!(collapse("a{margin-top:1px;margin-right:2px;margin-bottom:1px}"), Vec::<String>::new());
+		// Mixed importance
+		assert_eq!(collapse("a{row-gap:1px!important;column-gap:1px}"), Vec::<String>::new());
+		// Mixed CSS-wide keywords
+		assert_eq!(collapse("a{row-gap:inherit;column-gap:1px}"), Vec::<String>::new());
+		// Another declaration sets a longhand
+		assert_eq!(collapse("a{row-gap:1px;column-gap:1px;gap:2px}"), Vec::<String>::new());
+		assert_eq!(
+			collapse("a{border-top-width:1px;border-top-style:solid;border-top-color:red;border:none}"),
+			Vec::<String>::new()
+		);
+		// A reset longhand isn't its initial value
+		assert_eq!(
+			collapse(concat!(
+				"a{border-top-width:1px;border-top-style:solid;border-top-color:red;",
+				"border-right-width:1px;border-right-style:solid;border-right-color:red;",
+				"border-bottom-width:1px;border-bottom-style:solid;border-bottom-color:red;",
+				"border-left-width:1px;border-left-style:solid;border-left-color:red;",
+				"border-image-source:url(a.png);border-image-slice:100%;border-image-width:1;border-image-outset:0;",
+				"border-image-repeat:stretch}"
+			)),
+			["border-color:red", "border-style:solid", "border-width:1px"]
+		);
+	}
+}
@@ -6,7 +6,7 @@ use crate::{Visit, Visitable};
 
 // https://drafts.csswg.org/css-syntax-3/#charset-rule
 #[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 #[visit]
 pub struct CharsetRule {
 	at_keyword: T![AtKeyword],
@@ -17,6 +17,23 @@ pub struct CharsetRule {
 
 // CharsetRule is a special rule which means it cannot use standard AtRule parsing... comments below
 // https://drafts.csswg.org/css-syntax-3/#determine-the-fallback-encoding
+impl CharsetRule {
+	/// The encoding label within the string, for example `utf-8` for `@charset "utf-8";`.
+	pub fn label<'a>(&self, source: &'a str) -> &'a str {
+		let c: Cursor = self.string.into();
+		let str = c.str_slice(source);
+		let end = if c.token().has_close_quote() { str.len() - 1 } else { str.len() };
+		&str[1..end]
+	}
+
+	/// The [Encoding][css_lexer::Encoding] the label refers to, if it is a known label. Note that the encoding the
+	/// stylesheet was actually decoded with may differ; see [css_lexer::decode()].
+	#[cfg(feature = "encoding")]
+	pub fn encoding(&self, source: &str) -> Option<&'static css_lexer::Encoding> {
+		css_lexer::charset_encoding(self.label(source))
+	}
+}
+
 impl<'a> Parse<'a> for CharsetRule {
 	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
 		let at_keyword = p.parse::<T![AtKeyword]>()?;
@@ -69,4 +86,14 @@ mod tests {
 		assert_parse!(CharsetRule, "@charset \"utf-8\";", "@charset \"utf-8\";");
 		assert_parse!(CharsetRule, "@charset \"UTF-8\";", "@charset \"UTF-8\";");
 	}
+
+	#[test]
+	fn test_label() {
+		let bump = bumpalo::Bump::default();
+		let source_text = "@charset \"iso-8859-15\";";
+		let rule = css_parse::Parser::new(&bump, source_text).parse_entirely::<CharsetRule>().output.unwrap();
+		assert_eq!(rule.label(source_text), "iso-8859-15");
+		#[cfg(feature = "encoding")]
+		assert_eq!(rule.encoding(source_text).map(|e| e.name()), Some("ISO-8859-15"));
+	}
 }
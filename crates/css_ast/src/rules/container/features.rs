@@ -79,7 +79,7 @@ impl<'a> Visitable<'a> for OrientationContainerFeature {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type", content = "value"))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde(tag = "type", content = "value"))]
 pub enum StyleQuery<'a> {
 	Is(Property<'a>),
 	Not(ConditionKeyword, Property<'a>),
@@ -157,7 +157,7 @@ impl<'a> Visitable<'a> for StyleQuery<'a> {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type", content = "value"))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde(tag = "type", content = "value"))]
 pub enum ScrollStateQuery<'a> {
 	Is(ScrollStateFeature),
 	Not(ConditionKeyword, ScrollStateFeature),
@@ -235,7 +235,7 @@ impl<'a> Visitable<'a> for ScrollStateQuery<'a> {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub enum ScrollStateFeature {
 	Scrollable(ScrollableScrollStateFeature),
 	Snapped(SnappedScrollStateFeature),
@@ -361,7 +361,7 @@ mod tests {
 		assert_eq!(std::mem::size_of::<BlockSizeContainerFeature>(), 124);
 		assert_eq!(std::mem::size_of::<AspectRatioContainerFeature>(), 188);
 		assert_eq!(std::mem::size_of::<OrientationContainerFeature>(), 64);
-		assert_eq!(std::mem::size_of::<StyleQuery>(), 408);
+		assert_eq!(std::mem::size_of::<StyleQuery>(), 448);
 		assert_eq!(std::mem::size_of::<ScrollStateQuery>(), 88);
 		assert_eq!(std::mem::size_of::<ScrollStateFeature>(), 68);
 		assert_eq!(std::mem::size_of::<ScrollableScrollStateFeature>(), 64);
@@ -13,7 +13,7 @@ pub use features::*;
 
 // https://drafts.csswg.org/css-contain-3/#container-rule
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type"))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde(tag = "type"))]
 #[visit]
 pub struct ContainerRule<'a> {
 	pub at_keyword: T![AtKeyword],
@@ -61,7 +61,7 @@ impl<'a> Visitable<'a> for ContainerRule<'a> {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub struct ContainerRules<'a> {
 	pub open: T!['{'],
 	pub rules: Vec<'a, Rule<'a>>,
@@ -92,7 +92,7 @@ impl<'a> ToCursors for ContainerRules<'a> {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub struct ContainerConditionList<'a>(pub Vec<'a, ContainerCondition<'a>>);
 
 impl<'a> PreludeList<'a> for ContainerConditionList<'a> {
@@ -114,7 +114,7 @@ impl<'a> ToCursors for ContainerConditionList<'a> {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub struct ContainerCondition<'a> {
 	pub name: Option<T![Ident]>,
 	pub condition: Option<ContainerQuery<'a>>,
@@ -158,7 +158,7 @@ impl<'a> Visitable<'a> for ContainerCondition<'a> {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub enum ContainerQuery<'a> {
 	Is(ContainerFeature<'a>),
 	Not(ConditionKeyword, ContainerFeature<'a>),
@@ -244,7 +244,7 @@ impl<'a> Visitable<'a> for ContainerQuery<'a> {
 macro_rules! container_feature {
 	( $($name: ident($typ: ident): $str: tt,)+ ) => {
 		#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-		#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+		#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 		pub enum ContainerFeature<'a> {
 			$($name($typ),)+
 			Style(StyleQuery<'a>),
@@ -354,8 +354,8 @@ mod tests {
 	fn size_test() {
 		assert_eq!(std::mem::size_of::<ContainerRule>(), 112);
 		assert_eq!(std::mem::size_of::<ContainerConditionList>(), 32);
-		assert_eq!(std::mem::size_of::<ContainerCondition>(), 440);
-		assert_eq!(std::mem::size_of::<ContainerQuery>(), 424);
+		assert_eq!(std::mem::size_of::<ContainerCondition>(), 480);
+		assert_eq!(std::mem::size_of::<ContainerQuery>(), 464);
 	}
 
 	#[test]
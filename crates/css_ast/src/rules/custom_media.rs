@@ -0,0 +1,115 @@
+use css_lexer::{Cursor, Kind};
+use css_parse::{diagnostics, CursorSink, Feature, Parse, Parser, Result as ParserResult, ToCursors, T};
+use csskit_proc_macro::visit;
+
+use crate::{MediaQueryList, Visit, Visitable};
+
+// https://drafts.csswg.org/mediaqueries-5/#custom-mq
+/// A `@custom-media` rule, which names a media query so that it can be referred to in other media queries, e.g.
+/// `@custom-media --narrow-window (max-width: 30em);`. Only parsed when [Feature::CustomMedia] is enabled, otherwise
+/// it is an [UnknownAtRule][crate::UnknownAtRule].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde(tag = "type"))]
+#[visit]
+pub struct CustomMediaRule<'a> {
+	pub at_keyword: T![AtKeyword],
+	pub name: T![Ident],
+	pub query: CustomMediaQuery<'a>,
+	pub semicolon: Option<T![;]>,
+}
+
+/// The value of a [CustomMediaRule]; either a [MediaQueryList], or one of the keywords `true` or `false`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde(untagged))]
+pub enum CustomMediaQuery<'a> {
+	True(T![Ident]),
+	False(T![Ident]),
+	Queries(MediaQueryList<'a>),
+}
+
+impl<'a> Parse<'a> for CustomMediaRule<'a> {
+	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
+		let at_keyword = p.parse::<T![AtKeyword]>()?;
+		let c: Cursor = at_keyword.into();
+		if !p.enabled(Feature::CustomMedia) || !p.eq_ignore_ascii_case(c, "custom-media") {
+			Err(diagnostics::UnexpectedAtRule(p.parse_str(c).into(), c.into()))?
+		}
+		let name = p.parse::<T![Ident]>()?;
+		let c: Cursor = name.into();
+		if !c.token().is_dashed_ident() {
+			Err(diagnostics::UnexpectedIdent(p.parse_str(c).into(), c.into()))?
+		}
+		let c = p.peek_n(1);
+		let query = if c == Kind::Ident && p.eq_ignore_ascii_case(c, "true") {
+			CustomMediaQuery::True(p.parse::<T![Ident]>()?)
+		} else if c == Kind::Ident && p.eq_ignore_ascii_case(c, "false") {
+			CustomMediaQuery::False(p.parse::<T![Ident]>()?)
+		} else {
+			CustomMediaQuery::Queries(p.parse::<MediaQueryList>()?)
+		};
+		let semicolon = p.parse_if_peek::<T![;]>()?;
+		Ok(Self { at_keyword, name, query, semicolon })
+	}
+}
+
+impl<'a> ToCursors for CustomMediaRule<'a> {
+	fn to_cursors(&self, s: &mut impl CursorSink) {
+		s.append(self.at_keyword.into());
+		s.append(self.name.into());
+		match &self.query {
+			CustomMediaQuery::True(ident) | CustomMediaQuery::False(ident) => s.append(ident.into()),
+			CustomMediaQuery::Queries(queries) => ToCursors::to_cursors(queries, s),
+		}
+		if let Some(semicolon) = self.semicolon {
+			s.append(semicolon.into());
+		}
+	}
+}
+
+impl<'a> Visitable<'a> for CustomMediaRule<'a> {
+	fn accept<V: Visit<'a>>(&self, v: &mut V) {
+		v.visit_custom_media_rule(self);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use css_parse::CursorFmtSink;
+
+	#[test]
+	fn size_test() {
+		assert_eq!(std::mem::size_of::<CustomMediaRule>(), 80);
+	}
+
+	#[test]
+	fn test_writes() {
+		let bump = bumpalo::Bump::default();
+		for (source_text, expected) in [
+			("@custom-media --narrow (max-width: 30em);", "@custom-media --narrow (max-width:30em);"),
+			("@custom-media --enabled true;", "@custom-media --enabled true;"),
+			(
+				"@custom-media --mobile screen and (max-width: 30em);",
+				"@custom-media --mobile screen and (max-width:30em);",
+			),
+		] {
+			let result =
+				Parser::new_with_features(&bump, source_text, Feature::CustomMedia).parse_entirely::<CustomMediaRule>();
+			assert!(result.errors.is_empty(), "{:?}", result.errors);
+			let mut actual = String::new();
+			result.to_cursors(&mut CursorFmtSink::new(source_text, &mut actual));
+			assert_eq!(actual, expected);
+		}
+	}
+
+	#[test]
+	fn test_errors() {
+		let bump = bumpalo::Bump::default();
+		let result =
+			Parser::new(&bump, "@custom-media --narrow (max-width: 30em);").parse_entirely::<CustomMediaRule>();
+		assert!(result.output.is_none());
+		let result = Parser::new_with_features(&bump, "@custom-media narrow true;", Feature::CustomMedia)
+			.parse_entirely::<CustomMediaRule>();
+		assert!(result.output.is_none());
+	}
+}
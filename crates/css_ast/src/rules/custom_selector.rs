@@ -0,0 +1,99 @@
+use css_lexer::{Cursor, Kind, KindSet};
+use css_parse::{diagnostics, CursorSink, Feature, Parse, Parser, Result as ParserResult, ToCursors, T};
+use csskit_proc_macro::visit;
+
+use crate::{SelectorList, Visit, Visitable};
+
+// https://drafts.csswg.org/css-extensions/#custom-selectors
+/// A `@custom-selector` rule, which names a selector list so that it can be referred to in other selectors as a
+/// pseudo class, e.g. `@custom-selector :--heading h1, h2, h3;`. Only parsed when [Feature::CustomSelectors] is
+/// enabled, otherwise it is an [UnknownAtRule][crate::UnknownAtRule].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde(tag = "type"))]
+#[visit]
+pub struct CustomSelectorRule<'a> {
+	pub at_keyword: T![AtKeyword],
+	pub colon: T![:],
+	pub name: T![Ident],
+	pub selectors: SelectorList<'a>,
+	pub semicolon: Option<T![;]>,
+}
+
+impl<'a> Parse<'a> for CustomSelectorRule<'a> {
+	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
+		let at_keyword = p.parse::<T![AtKeyword]>()?;
+		let c: Cursor = at_keyword.into();
+		if !p.enabled(Feature::CustomSelectors) || !p.eq_ignore_ascii_case(c, "custom-selector") {
+			Err(diagnostics::UnexpectedAtRule(p.parse_str(c).into(), c.into()))?
+		}
+		// The name is a single `:--name` token pair, so whitespace between the colon and the name isn't allowed.
+		let colon = p.parse::<T![:]>()?;
+		let skip = p.set_skip(KindSet::NONE);
+		let name = p.parse::<T![Ident]>();
+		p.set_skip(skip);
+		let name = name?;
+		let c: Cursor = name.into();
+		if c != Kind::Ident || !c.token().is_dashed_ident() {
+			Err(diagnostics::UnexpectedIdent(p.parse_str(c).into(), c.into()))?
+		}
+		let stop = p.set_stop(KindSet::new(&[Kind::Semicolon]));
+		let selectors = p.parse::<SelectorList>();
+		p.set_stop(stop);
+		let selectors = selectors?;
+		let semicolon = p.parse_if_peek::<T![;]>()?;
+		Ok(Self { at_keyword, colon, name, selectors, semicolon })
+	}
+}
+
+impl<'a> ToCursors for CustomSelectorRule<'a> {
+	fn to_cursors(&self, s: &mut impl CursorSink) {
+		s.append(self.at_keyword.into());
+		s.append(self.colon.into());
+		s.append(self.name.into());
+		ToCursors::to_cursors(&self.selectors, s);
+		if let Some(semicolon) = self.semicolon {
+			s.append(semicolon.into());
+		}
+	}
+}
+
+impl<'a> Visitable<'a> for CustomSelectorRule<'a> {
+	fn accept<V: Visit<'a>>(&self, v: &mut V) {
+		v.visit_custom_selector_rule(self);
+		Visitable::accept(&self.selectors, v);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use css_parse::CursorFmtSink;
+
+	#[test]
+	fn size_test() {
+		assert_eq!(std::mem::size_of::<CustomSelectorRule>(), 88);
+	}
+
+	#[test]
+	fn test_writes() {
+		let bump = bumpalo::Bump::default();
+		for (source_text, expected) in [
+			("@custom-selector :--heading h1, h2, h3;", "@custom-selector:--heading h1,h2,h3;"),
+			("@custom-selector :--enter :hover, :focus-visible;", "@custom-selector:--enter:hover,:focus-visible;"),
+		] {
+			let result = Parser::new_with_features(&bump, source_text, Feature::CustomSelectors)
+				.parse_entirely::<CustomSelectorRule>();
+			assert!(result.errors.is_empty(), "{:?}", result.errors);
+			let mut actual = String::new();
+			result.to_cursors(&mut CursorFmtSink::new(source_text, &mut actual));
+			assert_eq!(actual, expected);
+		}
+	}
+
+	#[test]
+	fn test_errors() {
+		let bump = bumpalo::Bump::default();
+		let result = Parser::new(&bump, "@custom-selector :--heading h1;").parse_entirely::<CustomSelectorRule>();
+		assert!(result.output.is_none());
+	}
+}
@@ -10,7 +10,7 @@ use crate::{stylesheet::Rule, Visit, Visitable};
 
 // https://www.w3.org/TR/2012/WD-css3-conditional-20120911/#at-document
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type"))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde(tag = "type"))]
 #[visit]
 pub struct DocumentRule<'a> {
 	pub at_keyword: T![AtKeyword],
@@ -53,7 +53,7 @@ impl<'a> Visitable<'a> for DocumentRule<'a> {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub struct DocumentMatcherList<'a>(pub Vec<'a, (DocumentMatcher, Option<T![,]>)>);
 
 impl<'a> CommaSeparatedPreludeList<'a> for DocumentMatcherList<'a> {
@@ -94,7 +94,7 @@ function_set!(DocumentMatcherFunctionKeyword {
 });
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 #[visit]
 pub enum DocumentMatcher {
 	Url(T![Url]),
@@ -168,7 +168,7 @@ impl<'a> Visitable<'a> for DocumentMatcher {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type"))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde(tag = "type"))]
 pub struct DocumentRuleBlock<'a> {
 	pub open: T!['{'],
 	#[cfg_attr(feature = "serde", serde(borrow))]
@@ -4,13 +4,17 @@ use css_parse::{
 	keyword_set, syntax::BangImportant, AtRule, CursorSink, Declaration, NoPreludeAllowed, Parse, Parser, Peek,
 	Result as ParserResult, RuleList, ToCursors, T,
 };
+#[cfg(feature = "serde")]
+use css_parse::{FromJson, JsonError, JsonObject, JsonValue};
 use csskit_proc_macro::visit;
+#[cfg(feature = "serde")]
+use serde::de::Error as _;
 
 use crate::{properties::StyleValue, Visit, Visitable};
 
 // https://drafts.csswg.org/css-fonts/#font-face-rule
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 #[visit]
 pub struct FontFaceRule<'a> {
 	pub at_keyword: T![AtKeyword],
@@ -45,7 +49,7 @@ impl<'a> Visitable<'a> for FontFaceRule<'a> {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub struct FontFaceRuleBlock<'a> {
 	pub open: T!['{'],
 	pub properties: Vec<'a, FontFaceRuleProperty<'a>>,
@@ -135,6 +139,19 @@ impl<'a> ToCursors for FontFaceRuleProperty<'a> {
 	}
 }
 
+#[cfg(feature = "serde")]
+impl<'a> FromJson<'a> for FontFaceRuleProperty<'a> {
+	fn from_json(p: &Parser<'a>, value: &JsonValue) -> Result<Self, JsonError> {
+		let object = JsonObject::new("FontFaceRuleProperty", value)?;
+		object.expect_tag("type", "property")?;
+		object.deny_unknown_fields(&["type", "name", "colon", "value", "important"])?;
+		let name: T![Ident] = object.field(p, "name")?;
+		let value = StyleValue::from_json_by_name(p, p.parse_str_lower(name.into()), object.get("value"))
+			.map_err(|err| JsonError::custom(format!("FontFaceRuleProperty.value: {err}")))?;
+		Ok(Self { name, colon: object.field(p, "colon")?, value, important: object.field(p, "important")? })
+	}
+}
+
 impl<'a> Visitable<'a> for FontFaceRuleProperty<'a> {
 	fn accept<V: Visit<'a>>(&self, v: &mut V) {
 		v.visit_font_face_rule_property(self);
@@ -1,19 +1,162 @@
-use crate::Todo;
+use css_lexer::Cursor;
+use css_parse::{
+	diagnostics, syntax::ComponentValues, CursorSink, Parse, Parser, Result as ParserResult, ToCursors, T,
+};
+use csskit_proc_macro::visit;
+
+use crate::{LayerName, MediaQueryList, Url, Visit, Visitable};
 
 // https://drafts.csswg.org/css-cascade-5/#at-ruledef-import
-pub type ImportRule = Todo;
+///
+/// ```md
+/// @import [ <url> | <string> ]
+///         [ layer | layer(<layer-name>) ]?
+///         [ supports( [ <supports-condition> | <declaration> ] ) ]?
+///         <media-query-list>? ;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
+#[visit]
+pub struct ImportRule<'a> {
+	pub at_keyword: T![AtKeyword],
+	pub url: Url,
+	pub layer: Option<ImportLayer<'a>>,
+	pub supports: Option<ImportSupports<'a>>,
+	pub media: Option<MediaQueryList<'a>>,
+	pub semicolon: Option<T![;]>,
+}
+
+// @import is a statement at-rule, with a prelude too particular to be parsed by the AtRule trait.
+impl<'a> Parse<'a> for ImportRule<'a> {
+	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
+		let at_keyword = p.parse::<T![AtKeyword]>()?;
+		let c: Cursor = at_keyword.into();
+		if !p.eq_ignore_ascii_case(c, "import") {
+			Err(diagnostics::UnexpectedAtRule(p.parse_str(c).into(), c.into()))?;
+		}
+		let url = p.parse::<Url>()?;
+		let layer = if p.peek::<T![Ident]>() && p.eq_ignore_ascii_case(p.peek_n(1), "layer") {
+			Some(ImportLayer::Anonymous(p.parse::<T![Ident]>()?))
+		} else if p.peek::<T![Function]>() && p.eq_ignore_ascii_case(p.peek_n(1), "layer") {
+			let function = p.parse::<T![Function]>()?;
+			let name = p.parse::<LayerName>()?;
+			let close = p.parse::<T![')']>()?;
+			Some(ImportLayer::Named(function, name, close))
+		} else {
+			None
+		};
+		let supports = if p.peek::<T![Function]>() && p.eq_ignore_ascii_case(p.peek_n(1), "supports") {
+			let function = p.parse::<T![Function]>()?;
+			let condition = p.parse::<ComponentValues>()?;
+			let close = p.parse::<T![')']>()?;
+			Some(ImportSupports(function, condition, close))
+		} else {
+			None
+		};
+		let media = if p.at_end() || p.peek::<T![;]>() { None } else { Some(p.parse::<MediaQueryList>()?) };
+		let semicolon = p.parse_if_peek::<T![;]>()?;
+		Ok(Self { at_keyword, url, layer, supports, media, semicolon })
+	}
+}
+
+impl<'a> ToCursors for ImportRule<'a> {
+	fn to_cursors(&self, s: &mut impl CursorSink) {
+		s.append(self.at_keyword.into());
+		ToCursors::to_cursors(&self.url, s);
+		if let Some(layer) = &self.layer {
+			ToCursors::to_cursors(layer, s);
+		}
+		if let Some(supports) = &self.supports {
+			ToCursors::to_cursors(supports, s);
+		}
+		if let Some(media) = &self.media {
+			ToCursors::to_cursors(media, s);
+		}
+		if let Some(semicolon) = self.semicolon {
+			s.append(semicolon.into());
+		}
+	}
+}
+
+impl<'a> Visitable<'a> for ImportRule<'a> {
+	fn accept<V: Visit<'a>>(&self, v: &mut V) {
+		v.visit_import_rule(self);
+		if let Some(ImportLayer::Named(_, name, _)) = &self.layer {
+			Visitable::accept(name, v);
+		}
+	}
+}
+
+/// The cascade layer an `@import`ed stylesheet is placed into; `layer` alone puts it into a new anonymous layer.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
+pub enum ImportLayer<'a> {
+	Anonymous(T![Ident]),
+	Named(T![Function], LayerName<'a>, T![')']),
+}
+
+impl<'a> ToCursors for ImportLayer<'a> {
+	fn to_cursors(&self, s: &mut impl CursorSink) {
+		match self {
+			Self::Anonymous(ident) => s.append(ident.into()),
+			Self::Named(function, name, close) => {
+				s.append(function.into());
+				ToCursors::to_cursors(name, s);
+				s.append(close.into());
+			}
+		}
+	}
+}
+
+/// The `supports()` condition an `@import` is conditional on. As it may be either a supports condition or a bare
+/// declaration, it is kept as the component values it's written with.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
+pub struct ImportSupports<'a>(pub T![Function], pub ComponentValues<'a>, pub T![')']);
+
+impl<'a> ToCursors for ImportSupports<'a> {
+	fn to_cursors(&self, s: &mut impl CursorSink) {
+		s.append(self.0.into());
+		ToCursors::to_cursors(&self.1, s);
+		s.append(self.2.into());
+	}
+}
 
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use css_parse::assert_parse;
 
 	#[test]
 	fn size_test() {
-		assert_eq!(std::mem::size_of::<ImportRule>(), 0);
+		assert_eq!(std::mem::size_of::<ImportRule>(), 240);
 	}
 
 	#[test]
 	fn test_writes() {
-		//assert_parse!(ImportRule, "@import \"foo.css\"");
+		assert_parse!(ImportRule, "@import \"foo.css\";", "@import\"foo.css\";");
+		assert_parse!(ImportRule, "@import url(foo.css);");
+		assert_parse!(ImportRule, "@import url(\"foo.css\") screen;", "@import url(\"foo.css\")screen;");
+		assert_parse!(ImportRule, "@import \"foo.css\" layer;", "@import\"foo.css\"layer;");
+		assert_parse!(
+			ImportRule,
+			"@import \"foo.css\" layer(base.reset) print;",
+			"@import\"foo.css\"layer(base.reset)print;"
+		);
+		assert_parse!(
+			ImportRule,
+			"@import \"foo.css\" supports(display:grid) screen and (min-width:400px);",
+			"@import\"foo.css\"supports(display:grid)screen and (min-width:400px);"
+		);
+		assert_parse!(ImportRule, "@import \"foo.css\"", "@import\"foo.css\"");
+	}
+
+	#[test]
+	fn test_url() {
+		let bump = bumpalo::Bump::default();
+		let source_text = "@import url( ./a.css ) layer;";
+		let rule = Parser::new(&bump, source_text).parse_entirely::<ImportRule>().output.unwrap();
+		assert_eq!(rule.url.value(source_text), "./a.css");
+		assert!(matches!(rule.layer, Some(ImportLayer::Anonymous(_))));
 	}
 }
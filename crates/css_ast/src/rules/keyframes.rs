@@ -10,12 +10,12 @@ use crate::{properties::Property, Visit, Visitable};
 
 // https://drafts.csswg.org/css-animations/#at-ruledef-keyframes
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type"))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde(tag = "type"))]
 #[visit]
 pub struct KeyframesRule<'a> {
-	at_keyword: T![AtKeyword],
-	name: Option<KeyframesName>,
-	block: KeyframesBlock<'a>,
+	pub at_keyword: T![AtKeyword],
+	pub name: Option<KeyframesName>,
+	pub block: KeyframesBlock<'a>,
 }
 
 impl<'a> AtRule<'a> for KeyframesRule<'a> {
@@ -49,7 +49,7 @@ impl<'a> Visitable<'a> for KeyframesRule<'a> {
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub enum KeyframesName {
 	Ident(T![Ident]),
 	String(T![String]),
@@ -93,7 +93,7 @@ impl From<KeyframesName> for Cursor {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub struct KeyframesBlock<'a> {
 	pub open: T!['{'],
 	pub keyframes: Vec<'a, Keyframe<'a>>,
@@ -132,11 +132,11 @@ impl<'a> Visitable<'a> for KeyframesBlock<'a> {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 #[visit]
 pub struct Keyframe<'a> {
-	selectors: KeyframeSelectors<'a>,
-	block: KeyframeBlock<'a>,
+	pub selectors: KeyframeSelectors<'a>,
+	pub block: KeyframeBlock<'a>,
 }
 
 impl<'a> QualifiedRule<'a> for Keyframe<'a> {
@@ -168,7 +168,7 @@ impl<'a> Visitable<'a> for Keyframe<'a> {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub struct KeyframeSelectors<'a>(pub Vec<'a, (KeyframeSelector, Option<T![,]>)>);
 
 impl<'a> CommaSeparatedPreludeList<'a> for KeyframeSelectors<'a> {
@@ -201,11 +201,11 @@ impl<'a> Visitable<'a> for KeyframeSelectors<'a> {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub struct KeyframeBlock<'a> {
-	open: T!['{'],
-	properties: Vec<'a, (Property<'a>, Option<T![;]>)>,
-	close: Option<T!['}']>,
+	pub open: T!['{'],
+	pub properties: Vec<'a, (Property<'a>, Option<T![;]>)>,
+	pub close: Option<T!['}']>,
 }
 
 impl<'a> DeclarationList<'a> for KeyframeBlock<'a> {
@@ -243,7 +243,7 @@ impl<'a> Visitable<'a> for KeyframeBlock<'a> {
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 #[visit]
 pub enum KeyframeSelector {
 	From(T![Ident]),
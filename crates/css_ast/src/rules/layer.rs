@@ -10,7 +10,7 @@ use crate::{stylesheet::Rule, Visit, Visitable};
 
 // https://drafts.csswg.org/css-cascade-5/#layering
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 #[visit]
 pub struct LayerRule<'a> {
 	pub at_keyword: T![AtKeyword],
@@ -59,7 +59,7 @@ impl<'a> Visitable<'a> for LayerRule<'a> {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub struct LayerNameList<'a>(pub Vec<'a, (LayerName<'a>, Option<T![,]>)>);
 
 impl<'a> CommaSeparatedPreludeList<'a> for LayerNameList<'a> {
@@ -92,7 +92,7 @@ impl<'a> Visitable<'a> for LayerNameList<'a> {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 #[visit]
 pub struct LayerName<'a>(T![Ident], Vec<'a, (T![.], T![Ident])>);
 
@@ -129,7 +129,7 @@ impl<'a> Visitable<'a> for LayerName<'a> {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub enum OptionalLayerRuleBlock<'a> {
 	None(T![;]),
 	Block(LayerRuleBlock<'a>),
@@ -165,7 +165,7 @@ impl<'a> Visitable<'a> for OptionalLayerRuleBlock<'a> {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type"))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde(tag = "type"))]
 pub struct LayerRuleBlock<'a> {
 	pub open: T!['{'],
 	#[cfg_attr(feature = "serde", serde(borrow))]
@@ -0,0 +1,53 @@
+use css_lexer::Cursor;
+use css_parse::{diagnostics, CursorSink, Parse, Parser, Result as ParserResult, ToCursors, T};
+
+// https://drafts.csswg.org/mediaqueries-5/#custom-mq
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde(tag = "type"))]
+pub struct CustomMediaFeature {
+	pub open: T!['('],
+	pub name: T![Ident],
+	pub close: T![')'],
+}
+
+impl<'a> Parse<'a> for CustomMediaFeature {
+	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
+		let open = p.parse::<T!['(']>()?;
+		let name = p.parse::<T![Ident]>()?;
+		let c: Cursor = name.into();
+		if !c.token().is_dashed_ident() {
+			Err(diagnostics::UnexpectedIdent(p.parse_str(c).into(), c.into()))?
+		}
+		let close = p.parse::<T![')']>()?;
+		Ok(Self { open, name, close })
+	}
+}
+
+impl<'a> ToCursors for CustomMediaFeature {
+	fn to_cursors(&self, s: &mut impl CursorSink) {
+		s.append(self.open.into());
+		s.append(self.name.into());
+		s.append(self.close.into());
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use css_parse::{assert_parse, assert_parse_error};
+
+	#[test]
+	fn size_test() {
+		assert_eq!(std::mem::size_of::<CustomMediaFeature>(), 36);
+	}
+
+	#[test]
+	fn test_writes() {
+		assert_parse!(CustomMediaFeature, "(--narrow-window)");
+	}
+
+	#[test]
+	fn test_errors() {
+		assert_parse_error!(CustomMediaFeature, "(width)");
+	}
+}
@@ -2,7 +2,7 @@ use css_lexer::Cursor;
 use css_parse::{diagnostics, CursorSink, Parse, Parser, Result as ParserResult, ToCursors, T};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type", content = "value"))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson))]
 pub enum HackMediaFeature {
 	IEBackslashZero(T!['('], T![Ident], T![:], T![Dimension], T![')']),
 }
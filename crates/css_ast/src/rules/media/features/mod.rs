@@ -4,6 +4,7 @@ mod aspect_ratio;
 mod color;
 mod color_gamut;
 mod color_index;
+mod custom;
 mod device_aspect_ratio;
 mod device_height;
 mod device_width;
@@ -47,6 +48,7 @@ pub use aspect_ratio::*;
 pub use color::*;
 pub use color_gamut::*;
 pub use color_index::*;
+pub use custom::*;
 pub use device_aspect_ratio::*;
 pub use device_height::*;
 pub use device_width::*;
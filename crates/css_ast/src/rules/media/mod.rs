@@ -1,10 +1,12 @@
 use bumpalo::collections::Vec;
 use css_lexer::{Cursor, Kind, Span};
 use css_parse::{
-	diagnostics, keyword_set, AtRule, Block, Build, ConditionKeyword, CursorSink, FeatureConditionList, Parse, Parser,
-	Peek, PreludeList, Result as ParserResult, ToCursors, T,
+	diagnostics, keyword_set, AtRule, Block, Build, ConditionKeyword, CursorSink, Feature, FeatureConditionList, Parse,
+	Parser, Peek, PreludeList, Result as ParserResult, ToCursors, T,
 };
 
+use csskit_proc_macro::visit;
+
 use crate::{stylesheet::Rule, Property, Visit, Visitable};
 
 mod features;
@@ -12,7 +14,8 @@ use features::*;
 
 // https://drafts.csswg.org/mediaqueries-4/
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type"))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde(tag = "type"))]
+#[visit]
 pub struct MediaRule<'a> {
 	pub at_keyword: T![AtKeyword],
 	pub query: MediaQueryList<'a>,
@@ -48,12 +51,13 @@ impl<'a> ToCursors for MediaRule<'a> {
 
 impl<'a> Visitable<'a> for MediaRule<'a> {
 	fn accept<V: Visit<'a>>(&self, v: &mut V) {
-		todo!();
+		v.visit_media_rule(self);
+		Visitable::accept(&self.block, v);
 	}
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub struct MediaRules<'a> {
 	pub open: T!['{'],
 	pub properties: Vec<'a, (Property<'a>, Option<T![;]>)>,
@@ -92,7 +96,7 @@ impl<'a> ToCursors for MediaRules<'a> {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub struct MediaQueryList<'a>(pub Vec<'a, MediaQuery<'a>>);
 
 impl<'a> PreludeList<'a> for MediaQueryList<'a> {
@@ -105,6 +109,17 @@ impl<'a> Parse<'a> for MediaQueryList<'a> {
 	}
 }
 
+impl<'a> Visitable<'a> for MediaRules<'a> {
+	fn accept<V: Visit<'a>>(&self, v: &mut V) {
+		for (property, _) in &self.properties {
+			Visitable::accept(property, v);
+		}
+		for rule in &self.rules {
+			Visitable::accept(rule, v);
+		}
+	}
+}
+
 impl<'a> ToCursors for MediaQueryList<'a> {
 	fn to_cursors(&self, s: &mut impl CursorSink) {
 		for query in &self.0 {
@@ -116,7 +131,7 @@ impl<'a> ToCursors for MediaQueryList<'a> {
 keyword_set!(MediaPreCondition { Not: "not", Only: "only" });
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type"))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde(tag = "type"))]
 pub enum MediaType {
 	All(T![Ident]),
 	Print(T![Ident]),
@@ -177,7 +192,7 @@ impl From<&MediaType> for Cursor {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub struct MediaQuery<'a> {
 	precondition: Option<MediaPreCondition>,
 	media_type: Option<MediaType>,
@@ -240,7 +255,7 @@ impl<'a> ToCursors for MediaQuery<'a> {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type", content = "value"))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde(tag = "type", content = "value"))]
 pub enum MediaCondition<'a> {
 	Is(MediaFeature),
 	Not(ConditionKeyword, MediaFeature),
@@ -302,10 +317,11 @@ macro_rules! media_feature {
 	( $($name: ident($typ: ident): $pat: pat,)+) => {
 		// https://drafts.csswg.org/mediaqueries-5/#media-descriptor-table
 		#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-		#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type"))]
+		#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde(tag = "type"))]
 		pub enum MediaFeature {
 			$($name($typ),)+
 			Hack(HackMediaFeature),
+			Custom(CustomMediaFeature),
 		}
 	}
 }
@@ -316,6 +332,9 @@ impl<'a> Parse<'a> for MediaFeature {
 	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
 		let checkpoint = p.checkpoint();
 		let mut c = p.peek_n(2);
+		if p.enabled(Feature::CustomMedia) && c == Kind::Ident && c.token().is_dashed_ident() {
+			return p.parse::<CustomMediaFeature>().map(Self::Custom);
+		}
 		macro_rules! match_media {
 			( $($name: ident($typ: ident): $pat: pat,)+) => {
 				// Only peek at the token as the underlying media feature parser needs to parse the leading ident.
@@ -359,6 +378,7 @@ impl ToCursors for MediaFeature {
 				match self {
 					$(Self::$name(c) => ToCursors::to_cursors(c, s),)+
 					Self::Hack(hack) => ToCursors::to_cursors(hack, s),
+					Self::Custom(custom) => ToCursors::to_cursors(custom, s),
 				}
 			};
 		}
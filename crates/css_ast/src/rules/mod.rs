@@ -2,6 +2,8 @@ mod charset;
 mod color_profile;
 mod container;
 mod counter_style;
+mod custom_media;
+mod custom_selector;
 mod document;
 mod font_face;
 mod font_feature_values;
@@ -23,6 +25,8 @@ pub use charset::*;
 pub use color_profile::*;
 pub use container::*;
 pub use counter_style::*;
+pub use custom_media::*;
+pub use custom_selector::*;
 pub use document::*;
 pub use font_face::*;
 pub use font_feature_values::*;
@@ -7,7 +7,7 @@ use crate::{Visit, Visitable};
 use super::{DocumentMatcherList, DocumentRuleBlock};
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type"))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde(tag = "type"))]
 #[visit]
 pub struct MozDocumentRule<'a> {
 	pub at_keyword: T![AtKeyword],
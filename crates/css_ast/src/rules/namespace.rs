@@ -1,19 +1,74 @@
-use crate::Todo;
+use css_lexer::Cursor;
+use css_parse::{diagnostics, CursorSink, Parse, Parser, Result as ParserResult, ToCursors, T};
+use csskit_proc_macro::visit;
+
+use crate::{Url, Visit, Visitable};
 
 // https://drafts.csswg.org/css-namespaces/#at-ruledef-namespace
-pub type NamespaceRule = Todo;
+///
+/// ```md
+/// @namespace <namespace-prefix>? [ <string> | <url> ] ;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
+#[visit]
+pub struct NamespaceRule {
+	pub at_keyword: T![AtKeyword],
+	pub prefix: Option<T![Ident]>,
+	pub url: Url,
+	pub semicolon: Option<T![;]>,
+}
+
+impl<'a> Parse<'a> for NamespaceRule {
+	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
+		let at_keyword = p.parse::<T![AtKeyword]>()?;
+		let c: Cursor = at_keyword.into();
+		if !p.eq_ignore_ascii_case(c, "namespace") {
+			Err(diagnostics::UnexpectedAtRule(p.parse_str(c).into(), c.into()))?;
+		}
+		let prefix = p.parse_if_peek::<T![Ident]>()?;
+		let url = p.parse::<Url>()?;
+		let semicolon = p.parse_if_peek::<T![;]>()?;
+		Ok(Self { at_keyword, prefix, url, semicolon })
+	}
+}
+
+impl ToCursors for NamespaceRule {
+	fn to_cursors(&self, s: &mut impl CursorSink) {
+		s.append(self.at_keyword.into());
+		if let Some(prefix) = self.prefix {
+			s.append(prefix.into());
+		}
+		ToCursors::to_cursors(&self.url, s);
+		if let Some(semicolon) = self.semicolon {
+			s.append(semicolon.into());
+		}
+	}
+}
+
+impl<'a> Visitable<'a> for NamespaceRule {
+	fn accept<V: Visit<'a>>(&self, v: &mut V) {
+		v.visit_namespace_rule(self);
+	}
+}
 
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use css_parse::assert_parse;
 
 	#[test]
 	fn size_test() {
-		assert_eq!(std::mem::size_of::<NamespaceRule>(), 0);
+		assert_eq!(std::mem::size_of::<NamespaceRule>(), 84);
 	}
 
 	#[test]
 	fn test_writes() {
-		//assert_parse!(NamespaceRule, "@namespace");
+		assert_parse!(NamespaceRule, "@namespace url(http://www.w3.org/1999/xhtml);");
+		assert_parse!(
+			NamespaceRule,
+			"@namespace svg \"http://www.w3.org/2000/svg\";",
+			"@namespace svg\"http://www.w3.org/2000/svg\";"
+		);
 	}
 }
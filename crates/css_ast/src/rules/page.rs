@@ -15,7 +15,7 @@ use crate::{
 // https://drafts.csswg.org/cssom-1/#csspagerule
 // https://drafts.csswg.org/css-page-3/#at-page-rule
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type"))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde(tag = "type"))]
 #[visit]
 pub struct PageRule<'a> {
 	pub at_keyword: T![AtKeyword],
@@ -58,7 +58,7 @@ impl<'a> Visitable<'a> for PageRule<'a> {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub struct PageSelectorList<'a>(pub Vec<'a, (PageSelector<'a>, Option<T![,]>)>);
 
 impl<'a> CommaSeparatedPreludeList<'a> for PageSelectorList<'a> {
@@ -91,7 +91,7 @@ impl<'a> Visitable<'a> for PageSelectorList<'a> {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type"))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde(tag = "type"))]
 #[visit]
 pub struct PageSelector<'a> {
 	pub page_type: Option<T![Ident]>,
@@ -141,7 +141,7 @@ impl<'a> Visitable<'a> for PageSelector<'a> {
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(rename_all = "kebab-case"))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde(rename_all = "kebab-case"))]
 pub enum PagePseudoClass {
 	Left(T![:], T![Ident]),
 	Right(T![:], T![Ident]),
@@ -204,7 +204,7 @@ impl ToSpecificity for PagePseudoClass {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type"))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde(tag = "type"))]
 pub struct PageRuleBlock<'a> {
 	pub open: T!['{'],
 	#[cfg_attr(feature = "serde", serde(borrow))]
@@ -263,7 +263,7 @@ impl<'a> Visitable<'a> for PageRuleBlock<'a> {
 
 // https://drafts.csswg.org/cssom-1/#cssmarginrule
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type"))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde(tag = "type"))]
 #[visit]
 pub struct MarginRule<'a> {
 	pub at_keyword: T![AtKeyword],
@@ -320,7 +320,7 @@ impl<'a> Visitable<'a> for MarginRule<'a> {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type"))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde(tag = "type"))]
 pub struct MarginRuleBlock<'a> {
 	pub open: T!['{'],
 	#[cfg_attr(feature = "serde", serde(borrow))]
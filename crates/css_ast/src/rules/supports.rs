@@ -9,7 +9,7 @@ use csskit_proc_macro::visit;
 
 // https://drafts.csswg.org/css-conditional-3/#at-supports
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type"))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde(tag = "type"))]
 #[visit]
 pub struct SupportsRule<'a> {
 	pub at_keyword: T![AtKeyword],
@@ -81,7 +81,7 @@ impl<'a> Visitable<'a> for SupportsRule<'a> {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub struct SupportsRuleBlock<'a> {
 	pub open: T!['{'],
 	pub rules: Vec<'a, Rule<'a>>,
@@ -120,7 +120,7 @@ impl<'a> Visitable<'a> for SupportsRuleBlock<'a> {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type", content = "value"))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde(tag = "type", content = "value"))]
 pub enum SupportsCondition<'a> {
 	Is(SupportsFeature<'a>),
 	Not(ConditionKeyword, SupportsFeature<'a>),
@@ -146,9 +146,6 @@ impl<'a> FeatureConditionList<'a> for SupportsCondition<'a> {
 
 impl<'a> Parse<'a> for SupportsCondition<'a> {
 	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
-		if p.peek::<T![Function]>() || p.peek::<T!['(']>() {
-			return Ok(Self::Is(p.parse::<SupportsFeature>()?));
-		}
 		Self::parse_condition(p)
 	}
 }
@@ -201,7 +198,7 @@ impl<'a> Visitable<'a> for SupportsCondition<'a> {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub enum SupportsFeature<'a> {
 	FontTech(Option<T!['(']>, T![Function], ComponentValues<'a>, T![')'], Option<T![')']>),
 	FontFormat(Option<T!['(']>, T![Function], ComponentValues<'a>, T![')'], Option<T![')']>),
@@ -294,8 +291,7 @@ impl<'a> ToCursors for SupportsFeature<'a> {
 impl<'a> Visitable<'a> for SupportsFeature<'a> {
 	fn accept<V: Visit<'a>>(&self, v: &mut V) {
 		match self {
-			Self::FontTech(_, _, _, _, _) => todo!(),
-			Self::FontFormat(_, _, _, _, _) => todo!(),
+			Self::FontTech(_, _, _, _, _) | Self::FontFormat(_, _, _, _, _) => {}
 			Self::Selector(_, _, selector, _, _) => Visitable::accept(selector, v),
 			Self::Property(_, property, _) => Visitable::accept(property, v),
 		}
@@ -309,8 +305,8 @@ mod tests {
 
 	#[test]
 	fn size_test() {
-		assert_eq!(std::mem::size_of::<SupportsRule>(), 512);
-		assert_eq!(std::mem::size_of::<SupportsCondition>(), 432);
+		assert_eq!(std::mem::size_of::<SupportsRule>(), 560);
+		assert_eq!(std::mem::size_of::<SupportsCondition>(), 480);
 		assert_eq!(std::mem::size_of::<SupportsRuleBlock>(), 64);
 	}
 
@@ -319,6 +315,9 @@ mod tests {
 		assert_parse!(SupportsRule, "@supports(color:black){}");
 		assert_parse!(SupportsRule, "@supports(width:1px){body{width:1px}}");
 		// assert_parse!(SupportsRule, "@supports not (width:1--foo){}");
+		assert_parse!(SupportsRule, "@supports not (width:1px){}");
+		assert_parse!(SupportsRule, "@supports(width:1px)or (width:1em){}");
+		assert_parse!(SupportsRule, "@supports(width:1px)and (width:1em)and (width:1ex){}");
 		// assert_parse!(SupportsRule, "@supports(width: 1--foo) or (width: 1foo) {\n\n}");
 		// assert_parse!(SupportsRule, "@supports(width: 1--foo) and (width: 1foo) {\n\n}");
 		// assert_parse!(SupportsRule, "@supports(width: 100vw) {\n\tbody {\n\t\twidth: 100vw;\n\t}\n}");
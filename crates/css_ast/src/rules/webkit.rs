@@ -8,12 +8,12 @@ use super::{KeyframesBlock, KeyframesName};
 
 // https://drafts.csswg.org/css-animations/#at-ruledef-keyframes
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type"))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde(tag = "type"))]
 #[visit]
 pub struct WebkitKeyframesRule<'a> {
-	at_keyword: T![AtKeyword],
-	name: KeyframesName,
-	block: KeyframesBlock<'a>,
+	pub at_keyword: T![AtKeyword],
+	pub name: KeyframesName,
+	pub block: KeyframesBlock<'a>,
 }
 
 impl<'a> Parse<'a> for WebkitKeyframesRule<'a> {
@@ -44,7 +44,8 @@ impl<'a> ToCursors for WebkitKeyframesRule<'a> {
 
 impl<'a> Visitable<'a> for WebkitKeyframesRule<'a> {
 	fn accept<V: Visit<'a>>(&self, v: &mut V) {
-		todo!();
+		v.visit_webkit_keyframes_rule(self);
+		Visitable::accept(&self.block, v);
 	}
 }
 
@@ -6,7 +6,7 @@ use crate::{Visit, Visitable};
 
 // https://drafts.csswg.org/selectors/#combinators
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(rename_all = "kebab-case"))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde(rename_all = "kebab-case"))]
 #[visit]
 pub enum Combinator {
 	Child(T![>]),
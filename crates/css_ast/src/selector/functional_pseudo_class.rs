@@ -1,8 +1,13 @@
 use bumpalo::collections::Vec;
 use css_lexer::{Cursor, KindSet};
-use css_parse::{function_set, keyword_set, Build, CursorSink, Parse, Parser, Result as ParserResult, ToCursors, T};
+use css_parse::{
+	diagnostics, function_set, keyword_set, Build, CursorSink, Feature, Parse, Parser, Result as ParserResult,
+	ToCursors, T,
+};
 
-use crate::{Visit, Visitable};
+use csskit_proc_macro::visit;
+
+use crate::{Specificity, ToSpecificity, Visit, Visitable};
 
 use super::{ForgivingSelector, Nth, RelativeSelector, SelectorList};
 
@@ -10,15 +15,17 @@ macro_rules! apply_functional_pseudo_class {
 	($macro: ident) => {
 		$macro! {
 			Dir: "dir": DirPseudoFunction: DirValue,
+			Global: "global": GlobalPseudoFunction<'a>: SelectorList,
 			Has: "has": HasPseudoFunction<'a>: RelativeSelector,
 			Host: "host": HostPseudoFunction<'a>: SelectorList,
 			HostContext: "host-context": HostContextPseudoFunction<'a>: SelectorList,
 			Is: "is": IsPseudoFunction<'a>: ForgivingSelector,
 			Lang: "lang": LangPseudoFunction<'a>: LangValues,
+			Local: "local": LocalPseudoFunction<'a>: SelectorList,
 			Not: "not": NotPseudoFunction<'a>: SelectorList,
-			NthChild: "nth-child": NthChildPseudoFunction<'a>: Nth,
+			NthChild: "nth-child": NthChildPseudoFunction<'a>: NthChildValue,
 			NthCol: "nth-col": NthColPseudoFunction<'a>: Nth,
-			NthLastChild: "nth-last-child": NthLastChildPseudoFunction<'a>: Nth,
+			NthLastChild: "nth-last-child": NthLastChildPseudoFunction<'a>: NthChildValue,
 			NthLastCol: "nth-last-col": NthLastColPseudoFunction<'a>: Nth,
 			NthLastOfType: "nth-last-of-type": NthLastOfTypePseudoFunction<'a>: Nth,
 			NthOfType: "nth-of-type": NthOfTypePseudoFunction<'a>: Nth,
@@ -33,9 +40,10 @@ macro_rules! define_functional_pseudo_class {
 		#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 		#[cfg_attr(
 			feature = "serde",
-			derive(serde::Serialize),
+			derive(serde::Serialize, css_parse::FromJson),
 			serde(tag = "type", content = "value", rename_all = "kebab-case")
 		)]
+		#[visit]
 		pub enum FunctionalPseudoClass<'a> {
 			$($ident($ty),)+
 		}
@@ -61,6 +69,12 @@ impl<'a> Parse<'a> for FunctionalPseudoClass<'a> {
 		let colon = colon?;
 		let keyword = keyword?;
 		let c: Cursor = keyword.into();
+		// `:global()` & `:local()` are CSS Modules syntax, so they are only valid with the feature enabled.
+		if matches!(keyword, FunctionalPseudoClassKeyword::Global(_) | FunctionalPseudoClassKeyword::Local(_))
+			&& !p.enabled(Feature::CSSModules)
+		{
+			Err(diagnostics::UnexpectedPseudoClassFunction(p.parse_str_lower(c).into(), c.into()))?
+		}
 		let function = <T![Function]>::build(p, c);
 		macro_rules! match_keyword {
 			( $($ident: ident: $str: tt: $ty: ident$(<'a>)?: $val_ty: ty $(,)*)+ ) => {
@@ -90,21 +104,52 @@ impl<'a> ToCursors for FunctionalPseudoClass<'a> {
 	}
 }
 
+// https://drafts.csswg.org/selectors/#specificity-rules
+impl<'a> ToSpecificity for FunctionalPseudoClass<'a> {
+	fn specificity(&self) -> Specificity {
+		match self {
+			Self::Is(c) => c.value.specificity(),
+			Self::Not(c) => c.value.specificity(),
+			Self::Has(c) => c.value.specificity(),
+			Self::Where(_) => Specificity::ZERO,
+			// These only scope the class names within them.
+			Self::Global(c) => c.value.specificity(),
+			Self::Local(c) => c.value.specificity(),
+			Self::NthChild(c) => Specificity::CLASS + c.value.specificity(),
+			Self::NthLastChild(c) => Specificity::CLASS + c.value.specificity(),
+			// https://drafts.csswg.org/css-scoping/#host-selector
+			Self::Host(c) => Specificity::CLASS + c.value.specificity(),
+			Self::HostContext(c) => Specificity::CLASS + c.value.specificity(),
+			Self::Dir(_)
+			| Self::Lang(_)
+			| Self::NthCol(_)
+			| Self::NthLastCol(_)
+			| Self::NthLastOfType(_)
+			| Self::NthOfType(_)
+			| Self::State(_) => Specificity::CLASS,
+		}
+	}
+}
+
 impl<'a> Visitable<'a> for FunctionalPseudoClass<'a> {
 	fn accept<V: Visit<'a>>(&self, v: &mut V) {
-		// macro_rules! match_keyword {
-		// 	( $($ident: ident: $str: tt: $ty: ty: $val_ty: ty $(,)*)+ ) => {
-		// 		match self {
-		// 			$(Self::$ident(c) => Visitable::accept(c, v),)+
-		// 		}
-		// 	}
-		// }
-		// apply_functional_pseudo_class!(match_keyword);
+		v.visit_functional_pseudo_class(self);
+		match self {
+			Self::Global(c) => Visitable::accept(&c.value, v),
+			Self::Has(c) => Visitable::accept(&c.value, v),
+			Self::Host(c) => Visitable::accept(&c.value, v),
+			Self::HostContext(c) => Visitable::accept(&c.value, v),
+			Self::Is(c) => Visitable::accept(&c.value, v),
+			Self::Local(c) => Visitable::accept(&c.value, v),
+			Self::Not(c) => Visitable::accept(&c.value, v),
+			Self::Where(c) => Visitable::accept(&c.value, v),
+			_ => {}
+		}
 	}
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub struct DirPseudoFunction {
 	pub colon: T![:],
 	pub function: T![Function],
@@ -126,7 +171,27 @@ impl<'a> ToCursors for DirPseudoFunction {
 keyword_set!(DirValue { Rtl: "rtl", Ltr: "ltr" });
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
+pub struct GlobalPseudoFunction<'a> {
+	pub colon: T![:],
+	pub function: T![Function],
+	pub value: SelectorList<'a>,
+	pub close: Option<T![')']>,
+}
+
+impl<'a> ToCursors for GlobalPseudoFunction<'a> {
+	fn to_cursors(&self, s: &mut impl CursorSink) {
+		s.append(self.colon.into());
+		s.append(self.function.into());
+		ToCursors::to_cursors(&self.value, s);
+		if let Some(close) = self.close {
+			s.append(close.into());
+		}
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub struct HasPseudoFunction<'a> {
 	pub colon: T![:],
 	pub function: T![Function],
@@ -146,7 +211,7 @@ impl<'a> ToCursors for HasPseudoFunction<'a> {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub struct HostPseudoFunction<'a> {
 	pub colon: T![:],
 	pub function: T![Function],
@@ -166,7 +231,7 @@ impl<'a> ToCursors for HostPseudoFunction<'a> {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub struct HostContextPseudoFunction<'a> {
 	pub colon: T![:],
 	pub function: T![Function],
@@ -186,7 +251,7 @@ impl<'a> ToCursors for HostContextPseudoFunction<'a> {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub struct IsPseudoFunction<'a> {
 	pub colon: T![:],
 	pub function: T![Function],
@@ -206,7 +271,7 @@ impl<'a> ToCursors for IsPseudoFunction<'a> {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub struct LangPseudoFunction<'a> {
 	pub colon: T![:],
 	pub function: T![Function],
@@ -227,7 +292,7 @@ impl<'a> ToCursors for LangPseudoFunction<'a> {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub struct LangValues<'a>(Vec<'a, LangValue>);
 
 impl<'a> Parse<'a> for LangValues<'a> {
@@ -252,7 +317,7 @@ impl<'a> ToCursors for LangValues<'a> {
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub enum LangValue {
 	Ident(T![Ident], Option<T![,]>),
 	String(T![String], Option<T![,]>),
@@ -292,7 +357,27 @@ impl<'a> ToCursors for LangValue {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
+pub struct LocalPseudoFunction<'a> {
+	pub colon: T![:],
+	pub function: T![Function],
+	pub value: SelectorList<'a>,
+	pub close: Option<T![')']>,
+}
+
+impl<'a> ToCursors for LocalPseudoFunction<'a> {
+	fn to_cursors(&self, s: &mut impl CursorSink) {
+		s.append(self.colon.into());
+		s.append(self.function.into());
+		ToCursors::to_cursors(&self.value, s);
+		if let Some(close) = self.close {
+			s.append(close.into());
+		}
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub struct NotPseudoFunction<'a> {
 	pub colon: T![:],
 	pub function: T![Function],
@@ -312,11 +397,11 @@ impl<'a> ToCursors for NotPseudoFunction<'a> {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub struct NthChildPseudoFunction<'a> {
 	pub colon: T![:],
 	pub function: T![Function],
-	pub value: Nth<'a>,
+	pub value: NthChildValue<'a>,
 	pub close: Option<T![')']>,
 }
 
@@ -331,8 +416,52 @@ impl<'a> ToCursors for NthChildPseudoFunction<'a> {
 	}
 }
 
+/// The argument to `:nth-child()` & `:nth-last-child()`, which is an [Nth] optionally followed by `of` and a selector
+/// list to filter the children by.
+///
+/// ```md
+/// <nth-child-value>
+///  │├─ <an+b> ─╭─────────────────────────────────╮─┤│
+///              ╰─ "of" ─ <complex-selector-list> ─╯
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
+pub struct NthChildValue<'a> {
+	pub nth: Nth<'a>,
+	pub of: Option<(T![Ident], SelectorList<'a>)>,
+}
+
+impl<'a> Parse<'a> for NthChildValue<'a> {
+	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
+		let nth = p.parse::<Nth>()?;
+		if p.peek::<T![Ident]>() && p.eq_ignore_ascii_case(p.peek_n(1), "of") {
+			let of = p.parse::<T![Ident]>()?;
+			let selector = p.parse::<SelectorList>()?;
+			return Ok(Self { nth, of: Some((of, selector)) });
+		}
+		Ok(Self { nth, of: None })
+	}
+}
+
+impl<'a> ToCursors for NthChildValue<'a> {
+	fn to_cursors(&self, s: &mut impl CursorSink) {
+		ToCursors::to_cursors(&self.nth, s);
+		if let Some((of, selector)) = &self.of {
+			s.append(of.into());
+			ToCursors::to_cursors(selector, s);
+		}
+	}
+}
+
+/// The `of S` selector list adds its specificity (that of its most specific selector) to that of the pseudo-class.
+impl<'a> ToSpecificity for NthChildValue<'a> {
+	fn specificity(&self) -> Specificity {
+		self.of.as_ref().map(|(_, selector)| selector.specificity()).unwrap_or_default()
+	}
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub struct NthColPseudoFunction<'a> {
 	pub colon: T![:],
 	pub function: T![Function],
@@ -352,11 +481,11 @@ impl<'a> ToCursors for NthColPseudoFunction<'a> {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub struct NthLastChildPseudoFunction<'a> {
 	pub colon: T![:],
 	pub function: T![Function],
-	pub value: Nth<'a>,
+	pub value: NthChildValue<'a>,
 	pub close: Option<T![')']>,
 }
 
@@ -372,7 +501,7 @@ impl<'a> ToCursors for NthLastChildPseudoFunction<'a> {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub struct NthLastColPseudoFunction<'a> {
 	pub colon: T![:],
 	pub function: T![Function],
@@ -392,7 +521,7 @@ impl<'a> ToCursors for NthLastColPseudoFunction<'a> {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub struct NthLastOfTypePseudoFunction<'a> {
 	pub colon: T![:],
 	pub function: T![Function],
@@ -412,7 +541,7 @@ impl<'a> ToCursors for NthLastOfTypePseudoFunction<'a> {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub struct NthOfTypePseudoFunction<'a> {
 	pub colon: T![:],
 	pub function: T![Function],
@@ -432,7 +561,7 @@ impl<'a> ToCursors for NthOfTypePseudoFunction<'a> {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub struct WherePseudoFunction<'a> {
 	pub colon: T![:],
 	pub function: T![Function],
@@ -452,7 +581,7 @@ impl<'a> ToCursors for WherePseudoFunction<'a> {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub struct StatePseudoFunction {
 	pub colon: T![:],
 	pub function: T![Function],
@@ -477,7 +606,7 @@ mod tests {
 
 	#[test]
 	fn size_test() {
-		assert_eq!(std::mem::size_of::<FunctionalPseudoClass>(), 96);
+		assert_eq!(std::mem::size_of::<FunctionalPseudoClass>(), 144);
 		assert_eq!(std::mem::size_of::<DirValue>(), 16);
 	}
 }
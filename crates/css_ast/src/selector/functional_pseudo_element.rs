@@ -3,12 +3,16 @@ use css_lexer::Cursor;
 use css_parse::{diagnostics, CursorSink, Parse, Parser, Result as ParserResult, ToCursors, T};
 use csskit_proc_macro::visit;
 
-use crate::{Visit, Visitable};
+use crate::{Specificity, ToSpecificity, Visit, Visitable};
 
 use super::CompoundSelector;
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type", rename_all = "kebab-case"))]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, css_parse::FromJson),
+	serde(tag = "type", rename_all = "kebab-case")
+)]
 #[visit]
 pub enum FunctionalPseudoElement<'a> {
 	// https://drafts.csswg.org/css-highlight-api/#custom-highlight-pseudo
@@ -63,6 +67,17 @@ impl<'a> ToCursors for FunctionalPseudoElement<'a> {
 	}
 }
 
+impl<'a> ToSpecificity for FunctionalPseudoElement<'a> {
+	fn specificity(&self) -> Specificity {
+		match self {
+			Self::Highlight(_) | Self::Part(_) => Specificity::TYPE,
+			// https://drafts.csswg.org/css-scoping/#slotted-pseudo
+			// The specificity of ::slotted() is that of a pseudo-element, plus the specificity of its argument.
+			Self::Slotted(c) => Specificity::TYPE + c.value.specificity(),
+		}
+	}
+}
+
 impl<'a> Visitable<'a> for FunctionalPseudoElement<'a> {
 	fn accept<V: Visit<'a>>(&self, v: &mut V) {
 		v.visit_functional_pseudo_element(self);
@@ -70,7 +85,7 @@ impl<'a> Visitable<'a> for FunctionalPseudoElement<'a> {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub struct HighlightPseudoElement {
 	pub colons: T![::],
 	pub function: T![Function],
@@ -90,7 +105,7 @@ impl<'a> ToCursors for HighlightPseudoElement {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub struct SlottedPseudoElement<'a> {
 	pub colons: T![::],
 	pub function: T![Function],
@@ -110,7 +125,7 @@ impl<'a> ToCursors for SlottedPseudoElement<'a> {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub struct PartPseudoElement<'a> {
 	pub colons: T![::],
 	pub function: T![Function],
@@ -36,7 +36,7 @@ pub use pseudo_element::*;
 pub use tag::*;
 pub use webkit::*;
 
-use super::{Visit, Visitable};
+use super::{Specificity, ToSpecificity, Visit, Visitable};
 
 /// Represents a list of [CompoundSelectors][CompoundSelector], such as `body, dialog:modal`.
 ///
@@ -47,10 +47,26 @@ use super::{Visit, Visitable};
 ///     ╰─────────────────────────────────╯
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 #[visit]
 pub struct SelectorList<'a>(pub Vec<'a, (CompoundSelector<'a>, Option<T![,]>)>);
 
+impl<'a> SelectorList<'a> {
+	/// The specificity of each selector in the list, in source order. Each selector in a list is matched (and so
+	/// cascades) independently, so they each carry their own specificity.
+	pub fn specificities(&self) -> impl Iterator<Item = Specificity> + '_ {
+		self.0.iter().map(|(selector, _)| selector.specificity())
+	}
+}
+
+/// The specificity of the most specific selector in the list, which is how `:is()`, `:not()` & `:has()` count their
+/// arguments. Use [SelectorList::specificities] for the specificity of each individual selector.
+impl<'a> ToSpecificity for SelectorList<'a> {
+	fn specificity(&self) -> Specificity {
+		self.specificities().max().unwrap_or_default()
+	}
+}
+
 impl<'a> SelectorListTrait<'a> for SelectorList<'a> {
 	type CompoundSelector = CompoundSelector<'a>;
 }
@@ -82,10 +98,16 @@ impl<'a> Visitable<'a> for SelectorList<'a> {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 #[visit]
 pub struct CompoundSelector<'a>(pub Vec<'a, SelectorComponent<'a>>);
 
+impl<'a> ToSpecificity for CompoundSelector<'a> {
+	fn specificity(&self) -> Specificity {
+		self.0.iter().map(ToSpecificity::specificity).sum()
+	}
+}
+
 impl<'a> CompoundSelectorTrait<'a> for CompoundSelector<'a> {
 	type SelectorComponent = SelectorComponent<'a>;
 }
@@ -118,7 +140,7 @@ pub type ForgivingSelector<'a> = SelectorList<'a>;
 pub type RelativeSelector<'a> = SelectorList<'a>;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 #[visit]
 pub struct Id(T![Hash]);
 
@@ -147,7 +169,7 @@ impl<'a> Visitable<'a> for Id {
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 #[visit]
 pub struct Wildcard(T![*]);
 
@@ -181,7 +203,7 @@ impl<'a> Visitable<'a> for Wildcard {
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(
 	feature = "serde",
-	derive(serde::Serialize),
+	derive(serde::Serialize, css_parse::FromJson),
 	serde(tag = "type", content = "value", rename_all = "kebab-case")
 )]
 pub enum SelectorComponent<'a> {
@@ -243,6 +265,30 @@ impl<'a> Visitable<'a> for SelectorComponent<'a> {
 	}
 }
 
+// https://drafts.csswg.org/selectors/#specificity-rules
+impl<'a> ToSpecificity for SelectorComponent<'a> {
+	fn specificity(&self) -> Specificity {
+		match self {
+			Self::Id(_) => Specificity::ID,
+			// CSS Modules' scoping pseudo classes don't match anything themselves. Custom selectors take the specificity
+			// of their definition, which (like the nesting selector) is not known here.
+			Self::PseudoClass(PseudoClass::Global(..) | PseudoClass::Local(..) | PseudoClass::Custom(..)) => {
+				Specificity::ZERO
+			}
+			Self::Class(_) | Self::Attribute(_) | Self::PseudoClass(_) => Specificity::CLASS,
+			Self::Tag(_) | Self::PseudoElement(_) | Self::LegacyPseudoElement(_) => Specificity::TYPE,
+			Self::Namespace(ns) => match ns.tag {
+				NamespaceTag::Tag(_) => Specificity::TYPE,
+				NamespaceTag::Wildcard(_) => Specificity::ZERO,
+			},
+			// The nesting selector takes the specificity of the parent rule's selectors, which is not known here.
+			Self::Wildcard(_) | Self::Combinator(_) => Specificity::ZERO,
+			Self::FunctionalPseudoElement(c) => c.specificity(),
+			Self::FunctionalPseudoClass(c) => c.specificity(),
+		}
+	}
+}
+
 impl<'a> SelectorComponentTrait<'a> for SelectorComponent<'a> {
 	type Wildcard = Wildcard;
 	type Id = Id;
@@ -309,7 +355,7 @@ impl<'a> SelectorComponentTrait<'a> for SelectorComponent<'a> {
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use css_parse::assert_parse;
+	use css_parse::{assert_parse, Feature};
 
 	#[test]
 	fn size_test() {
@@ -317,7 +363,7 @@ mod tests {
 		assert_eq!(std::mem::size_of::<ComplexSelector>(), 32);
 		assert_eq!(std::mem::size_of::<ForgivingSelector>(), 32);
 		assert_eq!(std::mem::size_of::<RelativeSelector>(), 32);
-		assert_eq!(std::mem::size_of::<SelectorComponent>(), 128);
+		assert_eq!(std::mem::size_of::<SelectorComponent>(), 144);
 		assert_eq!(std::mem::size_of::<LegacyPseudoElement>(), 28);
 		assert_eq!(std::mem::size_of::<Combinator>(), 28);
 	}
@@ -346,6 +392,8 @@ mod tests {
 		assert_parse!(SelectorList, "::before:focus:target:right:playing:popover-open:blank");
 		assert_parse!(SelectorList, ":dir(ltr)");
 		assert_parse!(SelectorList, "tr:nth-child(n-1):state(foo)");
+		assert_parse!(SelectorList, "li:nth-child(2n+1 of .important)", "li:nth-child(2n+1 of.important)");
+		assert_parse!(SelectorList, ":nth-last-child(even of li,.foo)");
 		assert_parse!(SelectorList, " /**/ .foo", ".foo");
 		assert_parse!(SelectorList, ":lang(en-gb,en-us)");
 		assert_parse!(SelectorList, "& .foo");
@@ -364,4 +412,20 @@ mod tests {
 		);
 		assert_parse!(SelectorList, "button:-moz-focusring");
 	}
+
+	#[test]
+	fn test_nonstandard_features() {
+		let bump = bumpalo::Bump::default();
+		let features = Feature::CSSModules | Feature::CustomSelectors;
+		for source_text in [":global(.a) .b", ":local .a", ":global .a :local(.b)", ":--heading", "a:--heading + b"] {
+			let result = Parser::new_with_features(&bump, source_text, features).parse_entirely::<SelectorList>();
+			assert!(result.errors.is_empty(), "{source_text}: {:?}", result.errors);
+			let result = Parser::new(&bump, source_text).parse_entirely::<SelectorList>();
+			assert!(result.output.is_none(), "{source_text} should not parse without features");
+		}
+		let result =
+			Parser::new_with_features(&bump, ":global(.a#b) :local", features).parse_entirely::<SelectorList>();
+		let selector = &result.output.unwrap().0[0].0;
+		assert_eq!(selector.specificity(), Specificity(1, 1, 0));
+	}
 }
@@ -8,7 +8,7 @@ use super::Tag;
 
 // https://drafts.csswg.org/selectors/#combinators
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(rename_all = "kebab-case"))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde(rename_all = "kebab-case"))]
 #[visit]
 pub struct Namespace {
 	pub prefix: Option<NamespacePrefix>,
@@ -68,7 +68,7 @@ impl<'a> Visitable<'a> for Namespace {
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub enum NamespacePrefix {
 	None(T![|]),
 	Name(T![Ident], T![|]),
@@ -127,7 +127,7 @@ impl From<&NamespacePrefix> for Span {
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub enum NamespaceTag {
 	Tag(Tag),
 	Wildcard(T![*]),
@@ -5,7 +5,7 @@ use css_parse::{diagnostics, CursorSink, Parse, Parser, Result as ParserResult,
 use crate::units::CSSInt;
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub enum Nth<'a> {
 	Odd(T![Ident]),
 	Even(T![Ident]),
@@ -1,5 +1,5 @@
-use css_lexer::Span;
-use css_parse::{diagnostics, Parse, Parser, Result as ParserResult, ToCursors, T};
+use css_lexer::{Kind, Span};
+use css_parse::{diagnostics, Feature, Parse, Parser, Result as ParserResult, ToCursors, T};
 use csskit_proc_macro::visit;
 
 use crate::{Visit, Visitable};
@@ -66,7 +66,7 @@ macro_rules! apply_pseudo_class {
 macro_rules! define_pseudo_class {
 	( $($ident: ident: $str: tt $(,)*)+ ) => {
 		#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-		#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(rename_all = "kebab-case"))]
+		#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde(rename_all = "kebab-case"))]
 		#[visit]
 		pub enum PseudoClass {
 			$($ident(T![:], T![Ident]),)+
@@ -74,6 +74,13 @@ macro_rules! define_pseudo_class {
 			Moz(MozPseudoClass),
 			Ms(MsPseudoClass),
 			O(OPseudoClass),
+			/// The CSS Modules `:global` pseudo class, only parsed when [Feature::CSSModules] is enabled.
+			Global(T![:], T![Ident]),
+			/// The CSS Modules `:local` pseudo class, only parsed when [Feature::CSSModules] is enabled.
+			Local(T![:], T![Ident]),
+			/// A reference to a [CustomSelectorRule][crate::CustomSelectorRule], e.g. `:--heading`, only parsed when
+			/// [Feature::CustomSelectors] is enabled.
+			Custom(T![:], T![Ident]),
 		}
 	};
 }
@@ -114,6 +121,15 @@ impl<'a> Parse<'a> for PseudoClass {
 						if let Ok(psuedo) = p.try_parse::<OPseudoClass>() {
 							return Ok(Self::O(psuedo));
 						}
+						if p.enabled(Feature::CSSModules) && p.eq_ignore_ascii_case(c, "global") {
+							return Ok(Self::Global(p.parse::<T![:]>()?, p.parse::<T![Ident]>()?));
+						}
+						if p.enabled(Feature::CSSModules) && p.eq_ignore_ascii_case(c, "local") {
+							return Ok(Self::Local(p.parse::<T![:]>()?, p.parse::<T![Ident]>()?));
+						}
+						if p.enabled(Feature::CustomSelectors) && c == Kind::Ident && c.token().is_dashed_ident() {
+							return Ok(Self::Custom(p.parse::<T![:]>()?, p.parse::<T![Ident]>()?));
+						}
 						Err(diagnostics::UnexpectedPseudoClass(p.parse_str(c).into(), c.into()))?
 					}
 				}
@@ -128,7 +144,7 @@ impl<'a> ToCursors for PseudoClass {
 		macro_rules! match_keyword {
 			( $($ident: ident: $str: tt $(,)*)+ ) => {
 				match self {
-					$(Self::$ident(colon, ident))|+ => {
+					$(Self::$ident(colon, ident))|+ | Self::Global(colon, ident) | Self::Local(colon, ident) | Self::Custom(colon, ident) => {
 						s.append(colon.into());
 						s.append(ident.into());
 					}
@@ -148,7 +164,10 @@ impl<'a> From<&PseudoClass> for Span {
 		macro_rules! match_keyword {
 			( $($ident: ident: $str: tt $(,)*)+ ) => {
 				match value {
-					$(PseudoClass::$ident(colon, ident))|+ => Into::<Span>::into(colon) + ident.into(),
+					$(PseudoClass::$ident(colon, ident))|+
+					| PseudoClass::Global(colon, ident)
+					| PseudoClass::Local(colon, ident)
+					| PseudoClass::Custom(colon, ident) => Into::<Span>::into(colon) + ident.into(),
 					PseudoClass::Webkit(c) => c.into(),
 					PseudoClass::Moz(c) => c.into(),
 					PseudoClass::Ms(c) => c.into(),
@@ -5,30 +5,132 @@ pub trait ToSpecificity: Sized {
 	fn specificity(&self) -> Specificity;
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct Specificity(pub u8, pub u8, pub u8);
+/// The specificity of a selector, as an `(A, B, C)` triple of counts of ID selectors; class selectors, attribute
+/// selectors & pseudo-classes; and type selectors & pseudo-elements respectively.
+///
+/// Specificities are compared component by component, which is what the derived [Ord] implementation does. Adding two
+/// specificities together saturates rather than overflowing, so a pathologically long selector will clamp each
+/// component to [u32::MAX].
+///
+/// https://drafts.csswg.org/selectors/#specificity-rules
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Specificity(pub u32, pub u32, pub u32);
+
+impl Specificity {
+	pub const ZERO: Specificity = Specificity(0, 0, 0);
+	pub const ID: Specificity = Specificity(1, 0, 0);
+	pub const CLASS: Specificity = Specificity(0, 1, 0);
+	pub const TYPE: Specificity = Specificity(0, 0, 1);
+}
 
 impl ops::AddAssign for Specificity {
 	fn add_assign(&mut self, other: Self) {
-		self.0 |= other.0;
-		self.1 |= other.1;
-		self.2 |= other.2;
+		*self = *self + other;
 	}
 }
 
 impl ops::Add for Specificity {
 	type Output = Self;
 	fn add(self, other: Self) -> Self {
-		Self(self.0 | other.0, self.1 | other.1, self.2 | other.2)
+		Self(self.0.saturating_add(other.0), self.1.saturating_add(other.1), self.2.saturating_add(other.2))
 	}
 }
 
 impl Sum for Specificity {
 	fn sum<I: Iterator<Item = Specificity>>(iter: I) -> Specificity {
-		let mut out = Specificity(0, 0, 0);
+		let mut out = Specificity::ZERO;
 		for specificity in iter {
 			out += specificity
 		}
 		out
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::SelectorList;
+	use bumpalo::Bump;
+	use css_parse::Parser;
+
+	fn specificities(source_text: &str) -> std::vec::Vec<Specificity> {
+		let bump = Bump::default();
+		let list = Parser::new(&bump, source_text).parse_entirely::<SelectorList>().output.unwrap();
+		list.specificities().collect()
+	}
+
+	fn specificity(source_text: &str) -> Specificity {
+		let specificities = specificities(source_text);
+		assert_eq!(specificities.len(), 1, "expected {source_text:?} to be a single selector");
+		specificities[0]
+	}
+
+	#[test]
+	fn test_add_saturates() {
+		assert_eq!(Specificity(0, 1, 0) + Specificity(0, 1, 0), Specificity(0, 2, 0));
+		assert_eq!(Specificity(0, 3, 0) + Specificity(0, 1, 1), Specificity(0, 4, 1));
+		assert_eq!(Specificity(u32::MAX, 0, 0) + Specificity::ID, Specificity(u32::MAX, 0, 0));
+		assert_eq!(
+			[Specificity::ID, Specificity::CLASS, Specificity::CLASS].into_iter().sum::<Specificity>(),
+			Specificity(1, 2, 0)
+		);
+	}
+
+	#[test]
+	fn test_ordering() {
+		assert!(Specificity(1, 0, 0) > Specificity(0, 255, 255));
+		assert!(Specificity(0, 1, 0) > Specificity(0, 0, 300));
+		assert!(Specificity(0, 1, 2) > Specificity(0, 1, 1));
+	}
+
+	#[test]
+	fn test_simple_selectors() {
+		assert_eq!(specificity("*"), Specificity(0, 0, 0));
+		assert_eq!(specificity("li"), Specificity(0, 0, 1));
+		assert_eq!(specificity("ul li"), Specificity(0, 0, 2));
+		assert_eq!(specificity("ul ol+li"), Specificity(0, 0, 3));
+		assert_eq!(specificity("*|li"), Specificity(0, 0, 1));
+		assert_eq!(specificity("*|*"), Specificity(0, 0, 0));
+		assert_eq!(specificity(".a.b.c"), Specificity(0, 3, 0));
+		assert_eq!(specificity("h1 + *[rel=up]"), Specificity(0, 1, 1));
+		assert_eq!(specificity("ul ol li.red"), Specificity(0, 1, 3));
+		assert_eq!(specificity("li.red.level"), Specificity(0, 2, 1));
+		assert_eq!(specificity("#x34y"), Specificity(1, 0, 0));
+		assert_eq!(specificity("#s12:hover"), Specificity(1, 1, 0));
+		assert_eq!(specificity("#a #b .c"), Specificity(2, 1, 0));
+	}
+
+	#[test]
+	fn test_pseudo_elements() {
+		assert_eq!(specificity("p::before"), Specificity(0, 0, 2));
+		assert_eq!(specificity("p:before"), Specificity(0, 0, 2));
+		assert_eq!(specificity("a:first-line"), Specificity(0, 0, 2));
+		assert_eq!(specificity("::-webkit-scrollbar"), Specificity(0, 0, 1));
+		assert_eq!(specificity("::slotted(.foo)"), Specificity(0, 1, 1));
+		assert_eq!(specificity("::part(label)"), Specificity(0, 0, 1));
+	}
+
+	#[test]
+	fn test_functional_pseudo_classes() {
+		assert_eq!(specificity(":is(em, #foo)"), Specificity(1, 0, 0));
+		assert_eq!(specificity("div:is(.a, .b.c)"), Specificity(0, 2, 1));
+		assert_eq!(specificity(":not(em, strong#foo)"), Specificity(1, 0, 1));
+		assert_eq!(specificity(":has(> img, .a .b)"), Specificity(0, 2, 0));
+		assert_eq!(specificity(":where(#a, .b)"), Specificity(0, 0, 0));
+		assert_eq!(specificity("a:where(#a):hover"), Specificity(0, 1, 1));
+		assert_eq!(specificity(":nth-child(2n+1)"), Specificity(0, 1, 0));
+		assert_eq!(specificity(":nth-child(even of li.important)"), Specificity(0, 2, 1));
+		assert_eq!(specificity(":nth-last-child(2n of #a, .b)"), Specificity(1, 1, 0));
+		assert_eq!(specificity(":nth-of-type(2)"), Specificity(0, 1, 0));
+		assert_eq!(specificity(":dir(ltr):lang(en)"), Specificity(0, 2, 0));
+		assert_eq!(specificity(":host(.a)"), Specificity(0, 2, 0));
+	}
+
+	#[test]
+	fn test_selector_list() {
+		assert_eq!(
+			specificities("a, .b, #c, :is(#d, e)"),
+			vec![Specificity(0, 0, 1), Specificity(0, 1, 0), Specificity(1, 0, 0), Specificity(1, 0, 0)]
+		);
+	}
+}
@@ -2,8 +2,9 @@ use crate::{properties::Property, selector::SelectorList};
 use bumpalo::collections::Vec;
 use css_lexer::Cursor;
 use css_parse::{
-	syntax::BadDeclaration, Block, CursorSink, Parse, Parser, QualifiedRule, Result as ParserResult, State, ToCursors,
-	T,
+	diagnostics,
+	syntax::{BadDeclaration, BadRule},
+	Block, CursorSink, Parse, Parser, QualifiedRule, Result as ParserResult, State, ToCursors, T,
 };
 use csskit_proc_macro::visit;
 
@@ -22,12 +23,11 @@ use super::{rules, UnknownAtRule, UnknownQualifiedRule, Visit, Visitable};
 ///
 /// [1]: https://drafts.csswg.org/cssom-1/#the-cssstylerule-interface
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type", rename = "stylerule"))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde(tag = "type", rename = "stylerule"))]
 #[visit]
 pub struct StyleRule<'a> {
 	/// The selectors this rule applies to.
 	pub selectors: SelectorList<'a>,
-	#[cfg_attr(feature = "serde", serde(flatten))]
 	/// The declaration of this rule.
 	pub style: StyleDeclaration<'a>,
 }
@@ -62,7 +62,11 @@ impl<'a> Visitable<'a> for StyleRule<'a> {
 
 // https://drafts.csswg.org/cssom-1/#the-cssstylerule-interface
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type", rename = "style-declaration"))]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, css_parse::FromJson),
+	serde(tag = "type", rename = "style-declaration")
+)]
 #[visit]
 pub struct StyleDeclaration<'a> {
 	pub open: T!['{'],
@@ -113,6 +117,98 @@ impl<'a> Visitable<'a> for StyleDeclaration<'a> {
 	}
 }
 
+/// Represents the contents of a `style` attribute, such as `color: red; width: 100%`: a list of declarations, without
+/// the surrounding `{` and `}` of a [StyleDeclaration]. See also the [CSS Style Attributes][1] specification.
+///
+/// A declaration which fails to parse does not fail the list: its error is recorded with [Parser::push_error()] and it
+/// is kept, up to the next `;`, as a [StyleAttributeDeclaration::Bad].
+///
+/// [1]: https://drafts.csswg.org/css-style-attr/#syntax
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, css_parse::FromJson),
+	serde(tag = "type", rename = "style-attribute")
+)]
+#[visit]
+pub struct StyleAttribute<'a> {
+	pub declarations: Vec<'a, StyleAttributeDeclaration<'a>>,
+}
+
+impl<'a> Parse<'a> for StyleAttribute<'a> {
+	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
+		let mut declarations = Vec::new_in(p.bump());
+		loop {
+			if p.parse_if_peek::<T![' ']>()?.is_some() || p.parse_if_peek::<T![;]>()?.is_some() {
+				continue;
+			}
+			if p.at_end() {
+				return Ok(Self { declarations });
+			}
+			let checkpoint = p.checkpoint();
+			let old_state = p.set_state(State::Nested);
+			let declaration = p.parse::<Property>();
+			p.set_state(old_state);
+			match declaration {
+				Ok(declaration) => {
+					declarations.push(StyleAttributeDeclaration::Property(declaration, p.parse_if_peek::<T![;]>()?))
+				}
+				Err(error) => {
+					p.rewind(checkpoint);
+					declarations.push(StyleAttributeDeclaration::Bad(p.parse::<BadDeclaration>()?));
+					p.push_error(error);
+				}
+			}
+		}
+	}
+}
+
+impl<'a> ToCursors for StyleAttribute<'a> {
+	fn to_cursors(&self, s: &mut impl CursorSink) {
+		for declaration in &self.declarations {
+			ToCursors::to_cursors(declaration, s);
+		}
+	}
+}
+
+impl<'a> Visitable<'a> for StyleAttribute<'a> {
+	fn accept<V: Visit<'a>>(&self, v: &mut V) {
+		v.visit_style_attribute(self);
+		for declaration in &self.declarations {
+			if let StyleAttributeDeclaration::Property(declaration, _) = declaration {
+				Visitable::accept(declaration, v);
+			}
+		}
+	}
+}
+
+/// A declaration within a [StyleAttribute], along with its `;`. A declaration which failed to parse is kept as the
+/// component values of a [BadDeclaration], so that the attribute is written back as it was given.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, css_parse::FromJson),
+	serde(tag = "type", content = "value", rename_all = "kebab-case")
+)]
+pub enum StyleAttributeDeclaration<'a> {
+	Property(Property<'a>, Option<T![;]>),
+	Bad(BadDeclaration<'a>),
+}
+
+impl<'a> ToCursors for StyleAttributeDeclaration<'a> {
+	fn to_cursors(&self, s: &mut impl CursorSink) {
+		match self {
+			Self::Property(declaration, semicolon) => {
+				ToCursors::to_cursors(declaration, s);
+				if let Some(semicolon) = semicolon {
+					s.append(semicolon.into());
+				}
+			}
+			Self::Bad(bad) => ToCursors::to_cursors(bad, s),
+		}
+	}
+}
+
 // https://drafts.csswg.org/css-nesting/#conditionals
 macro_rules! apply_rules {
 	($macro: ident) => {
@@ -132,7 +228,7 @@ macro_rules! nested_group_rule {
     )+ ) => {
 		// https://drafts.csswg.org/cssom-1/#the-cssrule-interface
 		#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-		#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(untagged))]
+		#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde(untagged))]
 		pub enum NestedGroupRule<'a> {
 			$(
 				$name(rules::$name$(<$a>)?),
@@ -140,7 +236,7 @@ macro_rules! nested_group_rule {
 			UnknownAt(UnknownAtRule<'a>),
 			Style(StyleRule<'a>),
 			Unknown(UnknownQualifiedRule<'a>),
-			BadDeclaration(BadDeclaration<'a>),
+			BadRule(BadRule<'a>),
 		}
 	}
 }
@@ -177,16 +273,18 @@ impl<'a> Parse<'a> for NestedGroupRule<'a> {
 			if let Ok(rule) = p.parse::<UnknownQualifiedRule>() {
 				Ok(Self::Unknown(rule))
 			} else {
-				p.rewind(checkpoint);
-				let state = p.set_state(State::Nested);
-				let declaration = p.parse::<BadDeclaration>();
-				p.set_state(state);
-				Ok(Self::BadDeclaration(declaration?))
+				Err(diagnostics::BadDeclaration(checkpoint.into()))?
 			}
 		}
 	}
 }
 
+impl<'a> From<BadRule<'a>> for NestedGroupRule<'a> {
+	fn from(value: BadRule<'a>) -> Self {
+		Self::BadRule(value)
+	}
+}
+
 impl<'a> ToCursors for NestedGroupRule<'a> {
 	fn to_cursors(&self, s: &mut impl CursorSink) {
 		macro_rules! match_rule {
@@ -198,7 +296,7 @@ impl<'a> ToCursors for NestedGroupRule<'a> {
 					Self::UnknownAt(r) => ToCursors::to_cursors(r, s),
 					Self::Style(r) => ToCursors::to_cursors(r, s),
 					Self::Unknown(r) => ToCursors::to_cursors(r, s),
-					Self::BadDeclaration(r) => ToCursors::to_cursors(r, s),
+					Self::BadRule(r) => ToCursors::to_cursors(r, s),
 				}
 			}
 		}
@@ -217,7 +315,7 @@ impl<'a> Visitable<'a> for NestedGroupRule<'a> {
 						Self::UnknownAt(r) => Visitable::accept(r, v),
 						Self::Style(r) => Visitable::accept(r, v),
 						Self::Unknown(r) => Visitable::accept(r, v),
-						Self::BadDeclaration(_) => {},
+						Self::BadRule(_) => {},
 					};
 				}
 			}
@@ -228,7 +326,7 @@ impl<'a> Visitable<'a> for NestedGroupRule<'a> {
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use css_parse::assert_parse;
+	use css_parse::{assert_parse, assert_parse_error, CursorFmtSink};
 
 	#[test]
 	fn size_test() {
@@ -249,7 +347,29 @@ mod tests {
 		assert_parse!(StyleRule, ":root{--custom:{width:0;height:0;};}");
 		// Semicolons are "allowed" in geneirc preludes
 		assert_parse!(StyleRule, ":root{a;b{}}");
-		// Bad Declarations should be parsable.
-		assert_parse!(StyleRule, ":root{$(var)-size: 100%;}");
+		// Bad Declarations are recovered from, but reported.
+		assert_parse_error!(StyleRule, ":root{$(var)-size: 100%;}");
+	}
+
+	#[test]
+	fn test_style_attribute() {
+		assert_parse!(StyleAttribute, "");
+		assert_parse!(StyleAttribute, "color:red");
+		assert_parse!(StyleAttribute, "color:red;width:1px;");
+		assert_parse!(StyleAttribute, "--foo:{a;b}");
+	}
+
+	#[test]
+	fn test_style_attribute_errors() {
+		let bump = bumpalo::Bump::default();
+		let result = Parser::new(&bump, "color: red; 10px; width: 1px").parse_entirely::<StyleAttribute>();
+		assert_eq!(result.errors.len(), 1);
+		let attribute = result.output.as_ref().unwrap();
+		assert_eq!(attribute.declarations.len(), 3);
+		assert!(matches!(attribute.declarations[1], StyleAttributeDeclaration::Bad(_)));
+		// The bad declaration is written back as it was
+		let mut css = String::new();
+		result.to_cursors(&mut CursorFmtSink::new(result.source_text, &mut css));
+		assert_eq!(css, "color:red;10px;width:1px");
 	}
 }
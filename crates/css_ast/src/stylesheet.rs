@@ -1,7 +1,7 @@
 use bumpalo::collections::Vec;
 use css_lexer::Cursor;
 use css_parse::{
-	syntax::{AtRule, QualifiedRule},
+	syntax::{AtRule, BadRule, QualifiedRule},
 	CursorSink, Parse, Parser, Result as ParserResult, StyleSheet as StyleSheetTrait, ToCursors, T,
 };
 use csskit_proc_macro::visit;
@@ -10,7 +10,11 @@ use crate::{rules, stylerule::StyleRule, Visit, Visitable};
 
 // https://drafts.csswg.org/cssom-1/#the-cssstylesheet-interface
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type", rename = "stylesheet"))]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, css_parse::FromJson),
+	serde(tag = "type", rename = "stylesheet")
+)]
 #[visit]
 pub struct StyleSheet<'a> {
 	pub rules: Vec<'a, Rule<'a>>,
@@ -40,8 +44,8 @@ impl ToCursors for StyleSheet<'_> {
 
 impl<'a> Visitable<'a> for StyleSheet<'a> {
 	fn accept<V: Visit<'a>>(&self, v: &mut V) {
+		v.visit_style_sheet(self);
 		for rule in &self.rules {
-			v.visit_style_sheet(self);
 			Visitable::accept(rule, v);
 		}
 	}
@@ -57,7 +61,7 @@ macro_rules! apply_rules {
 			FontFaceRule<'a>: "font-face",
 			FontFeatureValuesRule: "font-feature-values",
 			FontPaletteValuesRule: "font-palette-values",
-			ImportRule: "import",
+			ImportRule<'a>: "import",
 			KeyframesRule<'a>: "keyframes",
 			LayerRule<'a>: "layer",
 			MediaRule<'a>: "media",
@@ -71,6 +75,10 @@ macro_rules! apply_rules {
 			// Deprecated Rules
 			DocumentRule<'a>: "document",
 
+			// Non-standard rules, only parsed when their Feature is enabled
+			CustomMediaRule<'a>: "custom-media",
+			CustomSelectorRule<'a>: "custom-selector",
+
 			// Vendor Prefixed
 			WebkitKeyframesRule<'a>: "-webkit-keyframes",
 
@@ -81,9 +89,9 @@ macro_rules! apply_rules {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 #[visit]
-pub struct UnknownAtRule<'a>(AtRule<'a>);
+pub struct UnknownAtRule<'a>(pub AtRule<'a>);
 
 impl<'a> Parse<'a> for UnknownAtRule<'a> {
 	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
@@ -104,9 +112,9 @@ impl<'a> Visitable<'a> for UnknownAtRule<'a> {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 #[visit]
-pub struct UnknownQualifiedRule<'a>(QualifiedRule<'a>);
+pub struct UnknownQualifiedRule<'a>(pub QualifiedRule<'a>);
 
 impl<'a> Parse<'a> for UnknownQualifiedRule<'a> {
 	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
@@ -132,14 +140,15 @@ macro_rules! rule {
     )+ ) => {
 		// https://drafts.csswg.org/cssom-1/#the-cssrule-interface
 		#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-		#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(untagged))]
+		#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde(untagged))]
 		pub enum Rule<'a> {
 			$(
 				$name(rules::$name$(<$a>)?),
 			)+
 			UnknownAt(UnknownAtRule<'a>),
 			Style(StyleRule<'a>),
-			Unknown(UnknownQualifiedRule<'a>)
+			Unknown(UnknownQualifiedRule<'a>),
+			BadRule(BadRule<'a>),
 		}
 	}
 }
@@ -179,6 +188,12 @@ impl<'a> Parse<'a> for Rule<'a> {
 	}
 }
 
+impl<'a> From<BadRule<'a>> for Rule<'a> {
+	fn from(value: BadRule<'a>) -> Self {
+		Self::BadRule(value)
+	}
+}
+
 impl ToCursors for Rule<'_> {
 	fn to_cursors(&self, s: &mut impl CursorSink) {
 		macro_rules! match_rule {
@@ -190,6 +205,7 @@ impl ToCursors for Rule<'_> {
 					Self::UnknownAt(r) => ToCursors::to_cursors(r, s),
 					Self::Style(r) => ToCursors::to_cursors(r, s),
 					Self::Unknown(r) => ToCursors::to_cursors(r, s),
+					Self::BadRule(r) => ToCursors::to_cursors(r, s),
 				}
 			}
 		}
@@ -208,6 +224,7 @@ impl<'a> Visitable<'a> for Rule<'a> {
 						Self::UnknownAt(r) => Visitable::accept(r, v),
 						Self::Style(r) => Visitable::accept(r, v),
 						Self::Unknown(r) => Visitable::accept(r, v),
+						Self::BadRule(_) => {},
 					};
 				}
 			}
@@ -218,12 +235,13 @@ impl<'a> Visitable<'a> for Rule<'a> {
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use css_parse::assert_parse;
+	use crate::{NestedGroupRule, StyleValue};
+	use css_parse::{assert_parse, Feature, ParserLimits};
 
 	#[test]
 	fn size_test() {
 		assert_eq!(std::mem::size_of::<StyleSheet>(), 32);
-		assert_eq!(std::mem::size_of::<Rule>(), 512);
+		assert_eq!(std::mem::size_of::<Rule>(), 560);
 	}
 
 	#[test]
@@ -234,4 +252,114 @@ mod tests {
 		assert_parse!(StyleSheet, "body{width:1px;}.a{width:2px;}");
 		assert_parse!(StyleSheet, "one:1;a{two:2}");
 	}
+
+	#[test]
+	fn test_recovery() {
+		let bump = bumpalo::Bump::default();
+		let source_text = "a{color:red}}b{color:blue}c{width:1px;)}";
+		let result = Parser::new(&bump, source_text).parse_entirely::<StyleSheet>();
+		assert_eq!(result.errors.len(), 2);
+		let stylesheet = result.output.as_ref().unwrap();
+		assert!(matches!(stylesheet.rules.as_slice(), [Rule::Style(_), Rule::BadRule(_), Rule::Style(_)]));
+		let Rule::Style(rule) = &stylesheet.rules[2] else { unreachable!() };
+		assert_eq!(rule.style.declarations.len(), 1);
+		assert!(matches!(rule.style.rules.as_slice(), [NestedGroupRule::BadRule(_)]));
+		let mut actual = String::new();
+		result.to_cursors(&mut css_parse::CursorFmtSink::new(source_text, &mut actual));
+		// The stray `}` and `)` are discarded, but everything else is kept.
+		assert_eq!(actual, "a{color:red}b{color:blue}c{width:1px;}");
+	}
+
+	#[test]
+	fn test_recovery_bad_declarations() {
+		let bump = bumpalo::Bump::default();
+		for source_text in ["a{1px:red;width:1px}", "a{color red;width:1px}", "a{@@@;width:1px}"] {
+			let result = Parser::new(&bump, source_text).parse_entirely::<StyleSheet>();
+			assert_eq!(result.errors.len(), 1, "{source_text}");
+			let stylesheet = result.output.as_ref().unwrap();
+			let [Rule::Style(rule)] = stylesheet.rules.as_slice() else { panic!("{source_text}") };
+			assert_eq!(rule.style.declarations.len(), 1, "{source_text}");
+			assert!(matches!(rule.style.rules.as_slice(), [NestedGroupRule::BadRule(_)]), "{source_text}");
+		}
+	}
+
+	#[test]
+	fn test_nonstandard_features() {
+		let bump = bumpalo::Bump::default();
+		let features = Feature::PropertyHacks
+			| Feature::ProgIdFilters
+			| Feature::Expressions
+			| Feature::CSSModules
+			| Feature::CustomMedia
+			| Feature::CustomSelectors;
+		let source_text = concat!(
+			"@custom-media --narrow (max-width:30em);",
+			"@custom-selector :--heading h1,h2;",
+			":global(.a) :local .b{composes:c d from \"./e.css\";*zoom:1}",
+			"@media (--narrow){:--heading{filter:progid:DXImageTransform.Microsoft.Alpha(Opacity=80)}}",
+			".f{width:expression(document.body.clientWidth)}",
+		);
+		let result = Parser::new_with_features(&bump, source_text, features).parse_entirely::<StyleSheet>();
+		assert!(result.errors.is_empty(), "{:?}", result.errors);
+		let stylesheet = result.output.as_ref().unwrap();
+		assert!(matches!(
+			stylesheet.rules.as_slice(),
+			[Rule::CustomMediaRule(_), Rule::CustomSelectorRule(_), Rule::Style(_), Rule::MediaRule(_), Rule::Style(_)]
+		));
+		let Rule::Style(rule) = &stylesheet.rules[2] else { unreachable!() };
+		assert!(matches!(rule.style.declarations[0].0.value, StyleValue::Composes(_)));
+		assert!(rule.style.declarations[1].0.hack.is_some());
+		let Rule::Style(rule) = &stylesheet.rules[4] else { unreachable!() };
+		assert!(matches!(rule.style.declarations[0].0.value, StyleValue::DynamicExpression(_)));
+		let mut actual = String::new();
+		result.to_cursors(&mut css_parse::CursorFmtSink::new(source_text, &mut actual));
+		assert_eq!(
+			actual,
+			source_text.replace("selector :", "selector:").replace("from \"", "from\"").replace("media (", "media(")
+		);
+
+		// Without the features these are parsed as unknown rules, unknown values, or not at all.
+		let result = Parser::new(&bump, source_text).parse_entirely::<StyleSheet>();
+		let stylesheet = result.output.as_ref().unwrap();
+		assert!(matches!(stylesheet.rules.as_slice(), [Rule::UnknownAt(_), Rule::UnknownAt(_), ..]));
+	}
+
+	#[test]
+	fn test_limits() {
+		let bump = bumpalo::Bump::default();
+		for source_text in [
+			"a{".repeat(100_000),
+			format!("{}{{}}", ":is(".repeat(100_000)),
+			format!("a{{width:{}", "calc(".repeat(100_000)),
+		] {
+			let result =
+				Parser::new(&bump, &source_text).with_limits(ParserLimits::UNTRUSTED).parse_entirely::<StyleSheet>();
+			assert!(result.output.is_some());
+			let error = result.errors.last().unwrap();
+			assert_eq!(error.code().unwrap().to_string(), "css_parse::MaxDepthExceeded");
+		}
+		// The deepest allowed nesting still parses.
+		let depth = ParserLimits::UNTRUSTED.max_depth as usize;
+		let source_text = format!("{}{}", "a{".repeat(depth), "}".repeat(depth));
+		let result =
+			Parser::new(&bump, &source_text).with_limits(ParserLimits::UNTRUSTED).parse_entirely::<StyleSheet>();
+		assert!(result.errors.is_empty(), "{:?}", result.errors);
+	}
+
+	#[test]
+	fn test_visit() {
+		#[derive(Default)]
+		struct Counter(usize);
+		impl<'a> Visit<'a> for Counter {
+			fn visit_style_sheet(&mut self, _: &StyleSheet<'a>) {
+				self.0 += 1;
+			}
+		}
+		let bump = bumpalo::Bump::default();
+		let result = Parser::new(&bump, "a{} b{} c{}").parse_entirely::<StyleSheet>();
+		let mut counter = Counter::default();
+		result.output.unwrap().accept(&mut counter);
+		// The stylesheet is visited once, rather than once for each of its rules.
+		assert_eq!(counter.0, 1);
+	}
 }
@@ -0,0 +1,671 @@
+use core::fmt;
+
+use super::ColorSpace;
+
+/// Each of the color spaces a resolved [AbsoluteColor] can be expressed in.
+///
+/// https://drafts.csswg.org/css-color/#color-type
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(rename_all = "kebab-case"))]
+pub enum ColorSpaceKind {
+	Srgb,
+	SrgbLinear,
+	DisplayP3,
+	A98Rgb,
+	ProphotoRgb,
+	Rec2020,
+	XyzD50,
+	XyzD65,
+	Lab,
+	Lch,
+	Oklab,
+	Oklch,
+	Hsl,
+	Hwb,
+}
+
+// The categories of "analogous components", used to carry missing components forward when converting a color for
+// interpolation.
+// https://drafts.csswg.org/css-color/#analogous-components
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum ComponentKind {
+	Red,
+	Green,
+	Blue,
+	Lightness,
+	Colorfulness,
+	Hue,
+	OpponentA,
+	OpponentB,
+	Other,
+}
+
+impl ColorSpaceKind {
+	pub fn name(&self) -> &'static str {
+		match self {
+			Self::Srgb => "srgb",
+			Self::SrgbLinear => "srgb-linear",
+			Self::DisplayP3 => "display-p3",
+			Self::A98Rgb => "a98-rgb",
+			Self::ProphotoRgb => "prophoto-rgb",
+			Self::Rec2020 => "rec2020",
+			Self::XyzD50 => "xyz-d50",
+			Self::XyzD65 => "xyz-d65",
+			Self::Lab => "lab",
+			Self::Lch => "lch",
+			Self::Oklab => "oklab",
+			Self::Oklch => "oklch",
+			Self::Hsl => "hsl",
+			Self::Hwb => "hwb",
+		}
+	}
+
+	/// Whether this space uses a hue angle (rather than rectangular coordinates), and so is interpolated using a
+	/// [hue interpolation method](https://drafts.csswg.org/css-color/#hue-interpolation).
+	pub fn is_polar(&self) -> bool {
+		self.hue_index().is_some()
+	}
+
+	/// The index of the hue component, for polar color spaces.
+	pub fn hue_index(&self) -> Option<usize> {
+		match self {
+			Self::Lch | Self::Oklch => Some(2),
+			Self::Hsl | Self::Hwb => Some(0),
+			_ => None,
+		}
+	}
+
+	/// Whether this space has a gamut (colors outside of `0..=1` in each RGB channel) that colors may need to be mapped
+	/// into. The Lab & XYZ spaces are unbounded.
+	pub fn is_bounded(&self) -> bool {
+		!matches!(self, Self::XyzD50 | Self::XyzD65 | Self::Lab | Self::Lch | Self::Oklab | Self::Oklch)
+	}
+
+	fn component_kinds(&self) -> [ComponentKind; 3] {
+		use ComponentKind::*;
+		match self {
+			Self::Srgb | Self::SrgbLinear | Self::DisplayP3 | Self::A98Rgb | Self::ProphotoRgb | Self::Rec2020 => {
+				[Red, Green, Blue]
+			}
+			Self::XyzD50 | Self::XyzD65 => [Red, Green, Blue],
+			Self::Lab | Self::Oklab => [Lightness, OpponentA, OpponentB],
+			Self::Lch | Self::Oklch => [Lightness, Colorfulness, Hue],
+			Self::Hsl => [Hue, Colorfulness, Lightness],
+			Self::Hwb => [Hue, Other, Other],
+		}
+	}
+
+	// The space this space is a transform of, which every conversion goes through: the cylindrical spaces are
+	// converted via their rectangular counterparts, and everything else via XYZ.
+	fn base(&self) -> Self {
+		match self {
+			Self::Hsl | Self::Hwb => Self::Srgb,
+			Self::Lch => Self::Lab,
+			Self::Oklch => Self::Oklab,
+			_ => *self,
+		}
+	}
+}
+
+impl From<ColorSpace> for ColorSpaceKind {
+	fn from(value: ColorSpace) -> Self {
+		match value {
+			ColorSpace::Srgb(_) => Self::Srgb,
+			ColorSpace::SrgbLinear(_) => Self::SrgbLinear,
+			ColorSpace::DisplayP3(_) => Self::DisplayP3,
+			ColorSpace::A98Rgb(_) => Self::A98Rgb,
+			ColorSpace::ProphotoRgb(_) => Self::ProphotoRgb,
+			ColorSpace::Rec2020(_) => Self::Rec2020,
+			ColorSpace::Xyz(_) | ColorSpace::XyzD65(_) => Self::XyzD65,
+			ColorSpace::XyzD50(_) => Self::XyzD50,
+		}
+	}
+}
+
+impl fmt::Display for ColorSpaceKind {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(self.name())
+	}
+}
+
+/// A fully resolved color: three components in a [ColorSpaceKind], plus alpha. Any of these may be missing (`none`).
+///
+/// Components use the same ranges as CSS: the RGB spaces use `0.0..=1.0`; `hsl` & `hwb` use degrees followed by two
+/// values in `0.0..=100.0`; `lab` & `lch` lightness ranges over `0.0..=100.0`, while `oklab` & `oklch` lightness
+/// ranges over `0.0..=1.0`; and hues are in degrees. Alpha ranges over `0.0..=1.0`.
+///
+/// https://drafts.csswg.org/css-color/#color-conversion
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+pub struct AbsoluteColor {
+	pub space: ColorSpaceKind,
+	pub components: [Option<f32>; 3],
+	pub alpha: Option<f32>,
+}
+
+type Triple = [f64; 3];
+type Matrix = [Triple; 3];
+
+fn multiply(m: &Matrix, v: Triple) -> Triple {
+	[
+		m[0][0] * v[0] + m[0][1] * v[1] + m[0][2] * v[2],
+		m[1][0] * v[0] + m[1][1] * v[1] + m[1][2] * v[2],
+		m[2][0] * v[0] + m[2][1] * v[1] + m[2][2] * v[2],
+	]
+}
+
+// The matrices below are those of the sample code in https://drafts.csswg.org/css-color/#color-conversion-code
+const LIN_SRGB_TO_XYZ: Matrix = [
+	[0.41239079926595934, 0.357584339383878, 0.1804807884018343],
+	[0.21263900587151027, 0.715168678767756, 0.07219231536073371],
+	[0.01933081871559182, 0.11919477979462598, 0.9505321522496607],
+];
+const XYZ_TO_LIN_SRGB: Matrix = [
+	[3.2409699419045226, -1.537383177570094, -0.4986107602930034],
+	[-0.9692436362808796, 1.8759675015077202, 0.04155505740717559],
+	[0.05563007969699366, -0.20397695888897652, 1.0569715142428786],
+];
+const LIN_P3_TO_XYZ: Matrix = [
+	[0.4865709486482162, 0.26566769316909306, 0.1982172852343625],
+	[0.2289745640697488, 0.6917385218365064, 0.079286914093745],
+	[0.0, 0.04511338185890264, 1.043944368900976],
+];
+const XYZ_TO_LIN_P3: Matrix = [
+	[2.493496911941425, -0.9313836179191239, -0.40271078445071684],
+	[-0.8294889695615747, 1.7626640603183463, 0.023624685841943577],
+	[0.03584583024378447, -0.07617238926804182, 0.9568845240076872],
+];
+const LIN_A98_TO_XYZ: Matrix = [
+	[0.5766690429101305, 0.1855582379065463, 0.1882286462349947],
+	[0.29734497525053605, 0.6273635662554661, 0.0752914584939978],
+	[0.02703136138641234, 0.07068885253582723, 0.9913375368376388],
+];
+const XYZ_TO_LIN_A98: Matrix = [
+	[2.0415879038107465, -0.5650069742788596, -0.34473135077832956],
+	[-0.9692436362808795, 1.8759675015077202, 0.04155505740717557],
+	[0.013444280632031142, -0.11836239223101838, 1.0151749943912054],
+];
+// ProPhoto is defined relative to a D50 white point.
+const LIN_PROPHOTO_TO_XYZ_D50: Matrix = [
+	[0.7977666449006423, 0.13518129740053308, 0.0313477341283922],
+	[0.2880748288194013, 0.711835234241873, 0.00008993693872564],
+	[0.0, 0.0, 0.8251046025104602],
+];
+const XYZ_D50_TO_LIN_PROPHOTO: Matrix = [
+	[1.3457868816471583, -0.25557208737979464, -0.05110186497554526],
+	[-0.5446307051249019, 1.5082477428451468, 0.02052744743642139],
+	[0.0, 0.0, 1.2119675456389452],
+];
+const LIN_REC2020_TO_XYZ: Matrix = [
+	[0.6369580483012914, 0.14461690358620832, 0.1688809751641721],
+	[0.2627002120112671, 0.6779980715188708, 0.05930171646986196],
+	[0.0, 0.028072693049087428, 1.060985057710791],
+];
+const XYZ_TO_LIN_REC2020: Matrix = [
+	[1.716651187971268, -0.355670783776392, -0.253366281373660],
+	[-0.666684351832489, 1.616481236634939, 0.0157685458139111],
+	[0.017639857445311, -0.042770613257809, 0.942103121235474],
+];
+// Bradford chromatic adaptation between the D65 & D50 white points.
+const D65_TO_D50: Matrix = [
+	[1.0479298208405488, 0.022946793341019088, -0.05019222954313557],
+	[0.029627815688159344, 0.990434484573249, -0.01707382502938514],
+	[-0.009243058152591178, 0.015055144896577895, 0.7518742899580008],
+];
+const D50_TO_D65: Matrix = [
+	[0.9554734527042182, -0.023098536874261423, 0.0632593086610217],
+	[-0.028369706963208136, 1.0099954580058226, 0.021041398966943008],
+	[0.012314001688319899, -0.020507696433477912, 1.3303659366080753],
+];
+#[allow(clippy::excessive_precision)]
+const XYZ_TO_LMS: Matrix = [
+	[0.8190224379967030, 0.3619062600528904, -0.1288737815209879],
+	[0.0329836539323885, 0.9292868615863434, 0.0361446663506424],
+	[0.0481771893596242, 0.2642395317527308, 0.6335478284694309],
+];
+#[allow(clippy::excessive_precision)]
+const LMS_TO_OKLAB: Matrix = [
+	[0.2104542683093140, 0.7936177747023054, -0.0040720430116193],
+	[1.9779985324311684, -2.4285922420485799, 0.4505937096174110],
+	[0.0259040424655478, 0.7827717124575296, -0.8086757549230774],
+];
+const OKLAB_TO_LMS: Matrix = [
+	[1.0, 0.3963377773761749, 0.2158037573099136],
+	[1.0, -0.1055613458156586, -0.0638541728258133],
+	[1.0, -0.0894841775298119, -1.2914855480194092],
+];
+#[allow(clippy::excessive_precision)]
+const LMS_TO_XYZ: Matrix = [
+	[1.2268798758459243, -0.5578149944602171, 0.2813910456659647],
+	[-0.0405757452148008, 1.1122868032803170, -0.0717110580655164],
+	[-0.0763729366746601, -0.4214933324022432, 1.5869240198367816],
+];
+
+const D50_WHITE: Triple = [0.3457 / 0.3585, 1.0, (1.0 - 0.3457 - 0.3585) / 0.3585];
+
+// Transfer functions, applied to each RGB channel. Negative values are mirrored so that out of gamut colors survive a
+// round trip.
+fn map_sign(v: f64, f: impl Fn(f64) -> f64) -> f64 {
+	v.signum() * f(v.abs())
+}
+
+fn srgb_to_linear(v: f64) -> f64 {
+	map_sign(v, |v| if v <= 0.04045 { v / 12.92 } else { ((v + 0.055) / 1.055).powf(2.4) })
+}
+
+fn linear_to_srgb(v: f64) -> f64 {
+	map_sign(v, |v| if v > 0.0031308 { 1.055 * v.powf(1.0 / 2.4) - 0.055 } else { 12.92 * v })
+}
+
+fn a98_to_linear(v: f64) -> f64 {
+	map_sign(v, |v| v.powf(563.0 / 256.0))
+}
+
+fn linear_to_a98(v: f64) -> f64 {
+	map_sign(v, |v| v.powf(256.0 / 563.0))
+}
+
+fn prophoto_to_linear(v: f64) -> f64 {
+	map_sign(v, |v| if v <= 16.0 / 512.0 { v / 16.0 } else { v.powf(1.8) })
+}
+
+fn linear_to_prophoto(v: f64) -> f64 {
+	map_sign(v, |v| if v >= 1.0 / 512.0 { v.powf(1.0 / 1.8) } else { 16.0 * v })
+}
+
+const REC2020_ALPHA: f64 = 1.09929682680944;
+const REC2020_BETA: f64 = 0.018053968510807;
+
+fn rec2020_to_linear(v: f64) -> f64 {
+	map_sign(v, |v| {
+		if v < REC2020_BETA * 4.5 {
+			v / 4.5
+		} else {
+			((v + REC2020_ALPHA - 1.0) / REC2020_ALPHA).powf(1.0 / 0.45)
+		}
+	})
+}
+
+fn linear_to_rec2020(v: f64) -> f64 {
+	map_sign(v, |v| if v > REC2020_BETA { REC2020_ALPHA * v.powf(0.45) - (REC2020_ALPHA - 1.0) } else { 4.5 * v })
+}
+
+// https://drafts.csswg.org/css-color/#color-conversion-code
+fn xyz_d50_to_lab(xyz: Triple) -> Triple {
+	const EPSILON: f64 = 216.0 / 24389.0;
+	const KAPPA: f64 = 24389.0 / 27.0;
+	let f = |i: usize| {
+		let v = xyz[i] / D50_WHITE[i];
+		if v > EPSILON {
+			v.cbrt()
+		} else {
+			(KAPPA * v + 16.0) / 116.0
+		}
+	};
+	let (f0, f1, f2) = (f(0), f(1), f(2));
+	[116.0 * f1 - 16.0, 500.0 * (f0 - f1), 200.0 * (f1 - f2)]
+}
+
+fn lab_to_xyz_d50(lab: Triple) -> Triple {
+	const EPSILON: f64 = 216.0 / 24389.0;
+	const KAPPA: f64 = 24389.0 / 27.0;
+	let f1 = (lab[0] + 16.0) / 116.0;
+	let f0 = lab[1] / 500.0 + f1;
+	let f2 = f1 - lab[2] / 200.0;
+	let x = if f0.powi(3) > EPSILON { f0.powi(3) } else { (116.0 * f0 - 16.0) / KAPPA };
+	let y = if lab[0] > KAPPA * EPSILON { f1.powi(3) } else { lab[0] / KAPPA };
+	let z = if f2.powi(3) > EPSILON { f2.powi(3) } else { (116.0 * f2 - 16.0) / KAPPA };
+	[x * D50_WHITE[0], y * D50_WHITE[1], z * D50_WHITE[2]]
+}
+
+fn xyz_d65_to_oklab(xyz: Triple) -> Triple {
+	multiply(&LMS_TO_OKLAB, multiply(&XYZ_TO_LMS, xyz).map(f64::cbrt))
+}
+
+fn oklab_to_xyz_d65(oklab: Triple) -> Triple {
+	multiply(&LMS_TO_XYZ, multiply(&OKLAB_TO_LMS, oklab).map(|v| v.powi(3)))
+}
+
+// Convert a color from one of the base spaces (see [ColorSpaceKind::base]) to XYZ with a D65 white point.
+fn base_to_xyz_d65(space: ColorSpaceKind, v: Triple) -> Triple {
+	match space {
+		ColorSpaceKind::Srgb => multiply(&LIN_SRGB_TO_XYZ, v.map(srgb_to_linear)),
+		ColorSpaceKind::SrgbLinear => multiply(&LIN_SRGB_TO_XYZ, v),
+		ColorSpaceKind::DisplayP3 => multiply(&LIN_P3_TO_XYZ, v.map(srgb_to_linear)),
+		ColorSpaceKind::A98Rgb => multiply(&LIN_A98_TO_XYZ, v.map(a98_to_linear)),
+		ColorSpaceKind::ProphotoRgb => {
+			multiply(&D50_TO_D65, multiply(&LIN_PROPHOTO_TO_XYZ_D50, v.map(prophoto_to_linear)))
+		}
+		ColorSpaceKind::Rec2020 => multiply(&LIN_REC2020_TO_XYZ, v.map(rec2020_to_linear)),
+		ColorSpaceKind::XyzD50 => multiply(&D50_TO_D65, v),
+		ColorSpaceKind::XyzD65 => v,
+		ColorSpaceKind::Lab => multiply(&D50_TO_D65, lab_to_xyz_d50(v)),
+		ColorSpaceKind::Oklab => oklab_to_xyz_d65(v),
+		ColorSpaceKind::Lch | ColorSpaceKind::Oklch | ColorSpaceKind::Hsl | ColorSpaceKind::Hwb => {
+			unreachable!("{space} is not a base color space")
+		}
+	}
+}
+
+fn xyz_d65_to_base(space: ColorSpaceKind, xyz: Triple) -> Triple {
+	match space {
+		ColorSpaceKind::Srgb => multiply(&XYZ_TO_LIN_SRGB, xyz).map(linear_to_srgb),
+		ColorSpaceKind::SrgbLinear => multiply(&XYZ_TO_LIN_SRGB, xyz),
+		ColorSpaceKind::DisplayP3 => multiply(&XYZ_TO_LIN_P3, xyz).map(linear_to_srgb),
+		ColorSpaceKind::A98Rgb => multiply(&XYZ_TO_LIN_A98, xyz).map(linear_to_a98),
+		ColorSpaceKind::ProphotoRgb => {
+			multiply(&XYZ_D50_TO_LIN_PROPHOTO, multiply(&D65_TO_D50, xyz)).map(linear_to_prophoto)
+		}
+		ColorSpaceKind::Rec2020 => multiply(&XYZ_TO_LIN_REC2020, xyz).map(linear_to_rec2020),
+		ColorSpaceKind::XyzD50 => multiply(&D65_TO_D50, xyz),
+		ColorSpaceKind::XyzD65 => xyz,
+		ColorSpaceKind::Lab => xyz_d50_to_lab(multiply(&D65_TO_D50, xyz)),
+		ColorSpaceKind::Oklab => xyz_d65_to_oklab(xyz),
+		ColorSpaceKind::Lch | ColorSpaceKind::Oklch | ColorSpaceKind::Hsl | ColorSpaceKind::Hwb => {
+			unreachable!("{space} is not a base color space")
+		}
+	}
+}
+
+// https://drafts.csswg.org/css-color/#hsl-to-rgb
+pub(crate) fn hsl_to_srgb(hsl: Triple) -> Triple {
+	let (hue, saturation, lightness) = (hsl[0].rem_euclid(360.0), hsl[1] / 100.0, hsl[2] / 100.0);
+	let f = |n: f64| {
+		let k = (n + hue / 30.0) % 12.0;
+		let a = saturation * lightness.min(1.0 - lightness);
+		lightness - a * (k - 3.0).min(9.0 - k).clamp(-1.0, 1.0)
+	};
+	[f(0.0), f(8.0), f(4.0)]
+}
+
+// https://drafts.csswg.org/css-color/#rgb-to-hsl
+fn srgb_to_hsl(rgb: Triple) -> [Option<f64>; 3] {
+	let max = rgb[0].max(rgb[1]).max(rgb[2]);
+	let min = rgb[0].min(rgb[1]).min(rgb[2]);
+	let lightness = (min + max) / 2.0;
+	let d = max - min;
+	let (mut hue, mut saturation) = (None, 0.0);
+	if d != 0.0 {
+		saturation =
+			if lightness == 0.0 || lightness == 1.0 { 0.0 } else { (max - lightness) / lightness.min(1.0 - lightness) };
+		let h = if max == rgb[0] {
+			(rgb[1] - rgb[2]) / d + if rgb[1] < rgb[2] { 6.0 } else { 0.0 }
+		} else if max == rgb[1] {
+			(rgb[2] - rgb[0]) / d + 2.0
+		} else {
+			(rgb[0] - rgb[1]) / d + 4.0
+		};
+		hue = Some(h * 60.0);
+	}
+	// Very out of gamut colors can produce a negative saturation, which is the same as the opposite hue.
+	if saturation < 0.0 {
+		hue = hue.map(|h| h + 180.0);
+		saturation = saturation.abs();
+	}
+	[hue.map(|h| h.rem_euclid(360.0)), Some(saturation * 100.0), Some(lightness * 100.0)]
+}
+
+// https://drafts.csswg.org/css-color/#hwb-to-rgb
+pub(crate) fn hwb_to_srgb(hwb: Triple) -> Triple {
+	let (whiteness, blackness) = (hwb[1] / 100.0, hwb[2] / 100.0);
+	if whiteness + blackness >= 1.0 {
+		let gray = whiteness / (whiteness + blackness);
+		return [gray, gray, gray];
+	}
+	hsl_to_srgb([hwb[0], 100.0, 50.0]).map(|v| v * (1.0 - whiteness - blackness) + whiteness)
+}
+
+fn srgb_to_hwb(rgb: Triple) -> [Option<f64>; 3] {
+	let hsl = srgb_to_hsl(rgb);
+	let whiteness = rgb[0].min(rgb[1]).min(rgb[2]);
+	let blackness = 1.0 - rgb[0].max(rgb[1]).max(rgb[2]);
+	// The hue is powerless for achromatic colors.
+	let hue = if whiteness + blackness >= 1.0 { None } else { hsl[0] };
+	[hue, Some(whiteness * 100.0), Some(blackness * 100.0)]
+}
+
+fn to_polar(v: Triple, epsilon: f64) -> [Option<f64>; 3] {
+	let chroma = (v[1] * v[1] + v[2] * v[2]).sqrt();
+	// The hue is powerless for achromatic colors.
+	let hue = if chroma < epsilon { None } else { Some(v[2].atan2(v[1]).to_degrees().rem_euclid(360.0)) };
+	[Some(v[0]), Some(chroma), hue]
+}
+
+fn from_polar(v: Triple) -> Triple {
+	let hue = v[2].to_radians();
+	[v[0], v[1] * hue.cos(), v[1] * hue.sin()]
+}
+
+impl AbsoluteColor {
+	pub const BLACK: AbsoluteColor = AbsoluteColor::srgb(0.0, 0.0, 0.0, 1.0);
+	pub const WHITE: AbsoluteColor = AbsoluteColor::srgb(1.0, 1.0, 1.0, 1.0);
+	pub const TRANSPARENT: AbsoluteColor = AbsoluteColor::srgb(0.0, 0.0, 0.0, 0.0);
+
+	pub const fn new(space: ColorSpaceKind, c0: f32, c1: f32, c2: f32, alpha: f32) -> Self {
+		Self { space, components: [Some(c0), Some(c1), Some(c2)], alpha: Some(alpha) }
+	}
+
+	pub const fn srgb(red: f32, green: f32, blue: f32, alpha: f32) -> Self {
+		Self::new(ColorSpaceKind::Srgb, red, green, blue, alpha)
+	}
+
+	pub fn from_rgba8(red: u8, green: u8, blue: u8, alpha: f32) -> Self {
+		Self::srgb(red as f32 / 255.0, green as f32 / 255.0, blue as f32 / 255.0, alpha)
+	}
+
+	/// The alpha of this color, treating a missing alpha as zero.
+	pub fn alpha(&self) -> f32 {
+		self.alpha.unwrap_or(0.0)
+	}
+
+	fn values(&self) -> Triple {
+		self.components.map(|c| c.unwrap_or(0.0) as f64)
+	}
+
+	/// Convert this color to another color space. Missing components are treated as zero, other than a hue which is
+	/// [powerless](https://drafts.csswg.org/css-color/#powerless) in the new space, which becomes missing.
+	///
+	/// https://drafts.csswg.org/css-color/#color-conversion
+	pub fn to_space(&self, space: ColorSpaceKind) -> Self {
+		if self.space == space {
+			return *self;
+		}
+		let values = self.values();
+		let (from_base, to_base) = (self.space.base(), space.base());
+		let base = match self.space {
+			ColorSpaceKind::Hsl => hsl_to_srgb(values),
+			ColorSpaceKind::Hwb => hwb_to_srgb(values),
+			ColorSpaceKind::Lch | ColorSpaceKind::Oklch => from_polar(values),
+			_ => values,
+		};
+		let base = if from_base == to_base { base } else { xyz_d65_to_base(to_base, base_to_xyz_d65(from_base, base)) };
+		let components = match space {
+			ColorSpaceKind::Hsl => srgb_to_hsl(base),
+			ColorSpaceKind::Hwb => srgb_to_hwb(base),
+			ColorSpaceKind::Lch => to_polar(base, 0.0015),
+			ColorSpaceKind::Oklch => to_polar(base, 0.000004),
+			_ => base.map(Some),
+		};
+		Self { space, components: components.map(|c| c.map(|c| c as f32)), alpha: self.alpha }
+	}
+
+	/// Convert this color to another color space for [interpolation][AbsoluteColor::mix]. Unlike
+	/// [AbsoluteColor::to_space], components which were missing stay missing if the new space has an analogous
+	/// component, for example the hue when converting from `hsl` to `oklch`.
+	///
+	/// https://drafts.csswg.org/css-color/#interpolation-missing
+	pub fn to_space_for_interpolation(&self, space: ColorSpaceKind) -> Self {
+		let mut converted = self.to_space(space);
+		let (from, to) = (self.space.component_kinds(), space.component_kinds());
+		for (i, component) in self.components.iter().enumerate() {
+			if component.is_none() && from[i] != ComponentKind::Other {
+				if let Some(j) = to.iter().position(|kind| *kind == from[i]) {
+					converted.components[j] = None;
+				}
+			}
+		}
+		converted
+	}
+
+	/// Whether this color can be displayed in the given (bounded) color space without clipping.
+	pub fn in_gamut(&self, space: ColorSpaceKind) -> bool {
+		if !space.is_bounded() {
+			return true;
+		}
+		// hsl() & hwb() share the sRGB gamut.
+		let rgb_space = space.base();
+		const EPSILON: f32 = 0.000075;
+		self.to_space(rgb_space).components.iter().all(|c| c.is_none_or(|c| (-EPSILON..=1.0 + EPSILON).contains(&c)))
+	}
+
+	/// Clamp each channel into the gamut of this color's own (bounded) color space.
+	pub fn clip(&self) -> Self {
+		if !self.space.is_bounded() {
+			return *self;
+		}
+		let rgb = self.to_space(self.space.base());
+		let clipped = Self { components: rgb.components.map(|c| c.map(|c| c.clamp(0.0, 1.0))), ..rgb };
+		clipped.to_space(self.space)
+	}
+
+	/// The perceptual distance between two colors, as the euclidean distance between them in Oklab.
+	///
+	/// https://drafts.csswg.org/css-color/#color-difference-OK
+	pub fn delta_e_ok(&self, other: &Self) -> f32 {
+		let (a, b) = (self.to_space(ColorSpaceKind::Oklab).values(), other.to_space(ColorSpaceKind::Oklab).values());
+		((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)).sqrt() as f32
+	}
+
+	/// Map this color into the gamut of the given color space, by reducing its chroma in OkLCh until it is in gamut
+	/// (or close enough that clipping it is not noticeable). The result is in the given space.
+	///
+	/// https://drafts.csswg.org/css-color/#binsearch
+	pub fn to_gamut(&self, space: ColorSpaceKind) -> Self {
+		const JND: f32 = 0.02;
+		const EPSILON: f32 = 0.0001;
+		if !space.is_bounded() {
+			return self.to_space(space);
+		}
+		let origin = self.to_space(ColorSpaceKind::Oklch);
+		let lightness = origin.components[0].unwrap_or(0.0);
+		if lightness >= 1.0 {
+			return Self { alpha: self.alpha, ..Self::WHITE }.to_space(space);
+		}
+		if lightness <= 0.0 {
+			return Self { alpha: self.alpha, ..Self::BLACK }.to_space(space);
+		}
+		if self.in_gamut(space) {
+			return self.to_space(space);
+		}
+		let mut current = origin;
+		let mut clipped = current.to_space(space).clip();
+		if clipped.delta_e_ok(&current) < JND {
+			return clipped;
+		}
+		let (mut min, mut max) = (0.0, origin.components[1].unwrap_or(0.0));
+		let mut min_in_gamut = true;
+		while max - min > EPSILON {
+			let chroma = (min + max) / 2.0;
+			current.components[1] = Some(chroma);
+			if min_in_gamut && current.in_gamut(space) {
+				min = chroma;
+				continue;
+			}
+			clipped = current.to_space(space).clip();
+			let delta = clipped.delta_e_ok(&current);
+			if delta < JND {
+				if JND - delta < EPSILON {
+					break;
+				}
+				min_in_gamut = false;
+				min = chroma;
+			} else {
+				max = chroma;
+			}
+		}
+		clipped
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn assert_close(color: AbsoluteColor, expected: [f32; 3]) {
+		for (actual, expected) in color.components.iter().zip(expected) {
+			let actual = actual.unwrap();
+			assert!((actual - expected).abs() < 0.001, "expected {expected} but got {actual} in {color:?}");
+		}
+	}
+
+	#[test]
+	fn test_srgb_conversions() {
+		let red = AbsoluteColor::srgb(1.0, 0.0, 0.0, 1.0);
+		assert_close(red.to_space(ColorSpaceKind::Hsl), [0.0, 100.0, 50.0]);
+		assert_close(red.to_space(ColorSpaceKind::Hwb), [0.0, 0.0, 0.0]);
+		assert_close(red.to_space(ColorSpaceKind::SrgbLinear), [1.0, 0.0, 0.0]);
+		assert_close(red.to_space(ColorSpaceKind::XyzD65), [0.41239, 0.21264, 0.01933]);
+		assert_close(red.to_space(ColorSpaceKind::Lab), [54.2905, 80.8049, 69.891]);
+		assert_close(red.to_space(ColorSpaceKind::Lch), [54.2905, 106.8372, 40.8577]);
+		assert_close(red.to_space(ColorSpaceKind::Oklab), [0.62796, 0.22486, 0.12585]);
+		assert_close(red.to_space(ColorSpaceKind::Oklch), [0.62796, 0.25768, 29.2339]);
+		assert_close(red.to_space(ColorSpaceKind::DisplayP3), [0.91749, 0.20029, 0.13856]);
+	}
+
+	#[test]
+	fn test_round_trips() {
+		let color = AbsoluteColor::srgb(0.2, 0.4, 0.8, 0.5);
+		for space in [
+			ColorSpaceKind::SrgbLinear,
+			ColorSpaceKind::DisplayP3,
+			ColorSpaceKind::A98Rgb,
+			ColorSpaceKind::ProphotoRgb,
+			ColorSpaceKind::Rec2020,
+			ColorSpaceKind::XyzD50,
+			ColorSpaceKind::XyzD65,
+			ColorSpaceKind::Lab,
+			ColorSpaceKind::Lch,
+			ColorSpaceKind::Oklab,
+			ColorSpaceKind::Oklch,
+			ColorSpaceKind::Hsl,
+			ColorSpaceKind::Hwb,
+		] {
+			let converted = color.to_space(space);
+			assert_eq!(converted.alpha, Some(0.5));
+			assert_close(converted.to_space(ColorSpaceKind::Srgb), [0.2, 0.4, 0.8]);
+		}
+	}
+
+	#[test]
+	fn test_powerless_hues() {
+		let gray = AbsoluteColor::srgb(0.5, 0.5, 0.5, 1.0);
+		assert_eq!(gray.to_space(ColorSpaceKind::Hsl).components[0], None);
+		assert_eq!(gray.to_space(ColorSpaceKind::Hwb).components[0], None);
+		assert_eq!(gray.to_space(ColorSpaceKind::Lch).components[2], None);
+		assert_eq!(gray.to_space(ColorSpaceKind::Oklch).components[2], None);
+	}
+
+	#[test]
+	fn test_carry_forward() {
+		let color = AbsoluteColor { components: [None, Some(50.0), Some(50.0)], ..AbsoluteColor::BLACK };
+		let color = AbsoluteColor { space: ColorSpaceKind::Hsl, ..color };
+		let converted = color.to_space_for_interpolation(ColorSpaceKind::Oklch);
+		assert_eq!(converted.components[2], None);
+		assert!(converted.components[0].is_some());
+	}
+
+	#[test]
+	fn test_gamut_mapping() {
+		let p3_red = AbsoluteColor::new(ColorSpaceKind::DisplayP3, 1.0, 0.0, 0.0, 1.0);
+		assert!(!p3_red.in_gamut(ColorSpaceKind::Srgb));
+		assert!(AbsoluteColor::srgb(1.0, 0.0, 0.0, 1.0).in_gamut(ColorSpaceKind::DisplayP3));
+		let mapped = p3_red.to_gamut(ColorSpaceKind::Srgb);
+		assert_eq!(mapped.space, ColorSpaceKind::Srgb);
+		assert!(mapped.in_gamut(ColorSpaceKind::Srgb));
+		assert!(mapped.delta_e_ok(&p3_red) < 0.1);
+		let bright = AbsoluteColor::new(ColorSpaceKind::Oklch, 1.2, 0.3, 120.0, 1.0);
+		assert_close(bright.to_gamut(ColorSpaceKind::Srgb), [1.0, 1.0, 1.0]);
+		let in_gamut = AbsoluteColor::srgb(0.2, 0.4, 0.8, 1.0);
+		assert_close(in_gamut.to_gamut(ColorSpaceKind::Srgb), [0.2, 0.4, 0.8]);
+	}
+}
@@ -1,6 +1,10 @@
-use crate::units::Angle;
+use super::Color;
+use crate::{units::Angle, MathFunction};
 use css_lexer::Cursor;
-use css_parse::{function_set, keyword_set, Build, Parse, Parser, Peek, Result as ParserResult, ToCursors, T};
+use css_parse::{
+	diagnostics, function_set, keyword_set, Build, CursorSink, Parse, Parser, Peek, Result as ParserResult, State,
+	ToCursors, T,
+};
 
 function_set!(ColorFunctionName {
 	Color: "color",
@@ -15,90 +19,179 @@ function_set!(ColorFunctionName {
 	Oklch: "oklch",
 });
 
+// https://drafts.csswg.org/css-color-5/#relative-colors
+keyword_set!(ChannelKeyword {
+	R: "r",
+	G: "g",
+	B: "b",
+	Alpha: "alpha",
+	H: "h",
+	S: "s",
+	L: "l",
+	W: "w",
+	C: "c",
+	A: "a",
+	X: "x",
+	Y: "y",
+	Z: "z",
+});
+
+impl ChannelKeyword {
+	pub fn name(&self) -> &'static str {
+		match self {
+			Self::R(_) => "r",
+			Self::G(_) => "g",
+			Self::B(_) => "b",
+			Self::Alpha(_) => "alpha",
+			Self::H(_) => "h",
+			Self::S(_) => "s",
+			Self::L(_) => "l",
+			Self::W(_) => "w",
+			Self::C(_) => "c",
+			Self::A(_) => "a",
+			Self::X(_) => "x",
+			Self::Y(_) => "y",
+			Self::Z(_) => "z",
+		}
+	}
+}
+
+// https://drafts.csswg.org/css-color-5/#relative-colors
+// from <color>
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
+pub struct RelativeOrigin<'a> {
+	pub from: T![Ident],
+	pub color: Color<'a>,
+}
+
+impl<'a> Peek<'a> for RelativeOrigin<'a> {
+	fn peek(p: &Parser<'a>, c: Cursor) -> bool {
+		<T![Ident]>::peek(p, c) && p.eq_ignore_ascii_case(c, "from")
+	}
+}
+
+impl<'a> Parse<'a> for RelativeOrigin<'a> {
+	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
+		let from = p.parse::<T![Ident]>()?;
+		let color = p.parse::<Color>()?;
+		Ok(Self { from, color })
+	}
+}
+
+impl<'a> ToCursors for RelativeOrigin<'a> {
+	fn to_cursors(&self, s: &mut impl CursorSink) {
+		s.append(self.from.into());
+		ToCursors::to_cursors(&self.color, s);
+	}
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
-pub enum Hue {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
+pub enum Hue<'a> {
 	None(T![Ident]),
 	Number(T![Number]),
 	Angle(Angle),
+	Keyword(ChannelKeyword),
+	Math(&'a MathFunction<'a>),
 }
 
-impl<'a> Peek<'a> for Hue {
+impl<'a> Peek<'a> for Hue<'a> {
 	fn peek(p: &Parser<'a>, c: css_lexer::Cursor) -> bool {
-		<T![Number]>::peek(p, c) || Angle::peek(p, c) || (<T![Ident]>::peek(p, c) && p.eq_ignore_ascii_case(c, "none"))
+		<T![Number]>::peek(p, c)
+			|| Angle::peek(p, c)
+			|| (<T![Ident]>::peek(p, c) && p.eq_ignore_ascii_case(c, "none"))
+			|| (p.is(State::RelativeColor) && ChannelKeyword::peek(p, c))
+			|| MathFunction::peek(p, c)
 	}
 }
 
-impl<'a> Build<'a> for Hue {
-	fn build(p: &Parser<'a>, c: css_lexer::Cursor) -> Self {
-		if <T![Number]>::peek(p, c) {
-			Self::Number(<T![Number]>::build(p, c))
-		} else if Angle::peek(p, c) {
-			Self::Angle(Angle::build(p, c))
+impl<'a> Parse<'a> for Hue<'a> {
+	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
+		if p.peek::<MathFunction>() {
+			let math = p.parse::<MathFunction>()?;
+			Ok(Self::Math(p.bump().alloc(math)))
+		} else if p.is(State::RelativeColor) && p.peek::<ChannelKeyword>() {
+			p.parse::<ChannelKeyword>().map(Self::Keyword)
+		} else if let Some(number) = p.parse_if_peek::<T![Number]>()? {
+			Ok(Self::Number(number))
+		} else if let Some(angle) = p.parse_if_peek::<Angle>()? {
+			Ok(Self::Angle(angle))
 		} else {
-			Self::None(<T![Ident]>::build(p, c))
+			let c = p.peek_n(1);
+			if !p.eq_ignore_ascii_case(c, "none") {
+				Err(diagnostics::Unexpected(c.into(), c.into()))?
+			}
+			Ok(Self::None(p.parse::<T![Ident]>()?))
 		}
 	}
 }
-impl From<Hue> for Cursor {
-	fn from(value: Hue) -> Self {
-		match value {
-			Hue::None(c) => c.into(),
-			Hue::Number(c) => c.into(),
-			Hue::Angle(c) => c.into(),
+
+impl<'a> ToCursors for Hue<'a> {
+	fn to_cursors(&self, s: &mut impl CursorSink) {
+		match self {
+			Self::None(c) => s.append(c.into()),
+			Self::Number(c) => s.append(c.into()),
+			Self::Angle(c) => s.append(c.into()),
+			Self::Keyword(c) => s.append(c.into()),
+			Self::Math(math) => ToCursors::to_cursors(*math, s),
 		}
 	}
 }
-impl From<&Hue> for Cursor {
-	fn from(value: &Hue) -> Self {
-		(*value).into()
-	}
-}
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
-pub enum Channel {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
+pub enum Channel<'a> {
 	None(T![Ident]),
 	Number(T![Number]),
 	Percent(T![Dimension::%]),
+	Keyword(ChannelKeyword),
+	Math(&'a MathFunction<'a>),
 }
 
-impl<'a> Peek<'a> for Channel {
+impl<'a> Peek<'a> for Channel<'a> {
 	fn peek(p: &Parser<'a>, c: css_lexer::Cursor) -> bool {
 		<T![Number]>::peek(p, c)
 			|| <T![Dimension::%]>::peek(p, c)
 			|| (<T![Ident]>::peek(p, c) && p.eq_ignore_ascii_case(c, "none"))
+			|| (p.is(State::RelativeColor) && ChannelKeyword::peek(p, c))
+			|| MathFunction::peek(p, c)
 	}
 }
 
-impl<'a> Build<'a> for Channel {
-	fn build(p: &Parser<'a>, c: css_lexer::Cursor) -> Self {
-		if <T![Number]>::peek(p, c) {
-			Self::Number(<T![Number]>::build(p, c))
-		} else if <T![Dimension::%]>::peek(p, c) {
-			Self::Percent(<T![Dimension::%]>::build(p, c))
+impl<'a> Parse<'a> for Channel<'a> {
+	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
+		if p.peek::<MathFunction>() {
+			let math = p.parse::<MathFunction>()?;
+			Ok(Self::Math(p.bump().alloc(math)))
+		} else if p.is(State::RelativeColor) && p.peek::<ChannelKeyword>() {
+			p.parse::<ChannelKeyword>().map(Self::Keyword)
+		} else if let Some(number) = p.parse_if_peek::<T![Number]>()? {
+			Ok(Self::Number(number))
+		} else if let Some(percent) = p.parse_if_peek::<T![Dimension::%]>()? {
+			Ok(Self::Percent(percent))
 		} else {
-			Self::None(<T![Ident]>::build(p, c))
+			let c = p.peek_n(1);
+			if !p.eq_ignore_ascii_case(c, "none") {
+				Err(diagnostics::Unexpected(c.into(), c.into()))?
+			}
+			Ok(Self::None(p.parse::<T![Ident]>()?))
 		}
 	}
 }
 
-impl From<Channel> for Cursor {
-	fn from(value: Channel) -> Self {
-		match value {
-			Channel::None(c) => c.into(),
-			Channel::Number(c) => c.into(),
-			Channel::Percent(c) => c.into(),
+impl<'a> ToCursors for Channel<'a> {
+	fn to_cursors(&self, s: &mut impl CursorSink) {
+		match self {
+			Self::None(c) => s.append(c.into()),
+			Self::Number(c) => s.append(c.into()),
+			Self::Percent(c) => s.append(c.into()),
+			Self::Keyword(c) => s.append(c.into()),
+			Self::Math(math) => ToCursors::to_cursors(*math, s),
 		}
 	}
 }
 
-impl From<&Channel> for Cursor {
-	fn from(value: &Channel) -> Self {
-		(*value).into()
-	}
-}
-
 keyword_set!(ColorSpace {
 	Srgb: "srgb",
 	SrgbLinear: "srgb-linear",
@@ -113,8 +206,8 @@ keyword_set!(ColorSpace {
 
 // https://drafts.csswg.org/css-color/#typedef-color-function
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
-pub enum ColorFunction {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
+pub enum ColorFunction<'a> {
 	// https://drafts.csswg.org/css-color/#funcdef-color
 	// color() = color( <colorspace-params> [ / [ <alpha-value> | none ] ]? )
 	// <colorspace-params> = [ <predefined-rgb-params> | <xyz-params>]
@@ -122,7 +215,17 @@ pub enum ColorFunction {
 	// <predefined-rgb> = srgb | srgb-linear | display-p3 | a98-rgb | prophoto-rgb | rec2020
 	// <xyz-params> = <xyz-space> [ <number> | <percentage> | none ]{3}
 	// <xyz-space> = xyz | xyz-d50 | xyz-d65
-	Color(T![Function], ColorSpace, Channel, Channel, Channel, Option<T![/]>, Option<Channel>, Option<T![')']>),
+	Color(
+		T![Function],
+		Option<&'a RelativeOrigin<'a>>,
+		ColorSpace,
+		Channel<'a>,
+		Channel<'a>,
+		Channel<'a>,
+		Option<T![/]>,
+		Option<Channel<'a>>,
+		Option<T![')']>,
+	),
 
 	// https://drafts.csswg.org/css-color/#funcdef-rgb
 	// rgb() = [ <legacy-rgb-syntax> | <modern-rgb-syntax> ]
@@ -139,26 +242,28 @@ pub enum ColorFunction {
 	//   [ / [<alpha-value> | none] ]?  )
 	Rgb(
 		T![Function],
-		Channel,
+		Option<&'a RelativeOrigin<'a>>,
+		Channel<'a>,
 		Option<T![,]>,
-		Channel,
+		Channel<'a>,
 		Option<T![,]>,
-		Channel,
+		Channel<'a>,
 		Option<T![,]>,
 		Option<T![/]>,
-		Option<Channel>,
+		Option<Channel<'a>>,
 		Option<T![')']>,
 	),
 	Rgba(
 		T![Function],
-		Channel,
+		Option<&'a RelativeOrigin<'a>>,
+		Channel<'a>,
 		Option<T![,]>,
-		Channel,
+		Channel<'a>,
 		Option<T![,]>,
-		Channel,
+		Channel<'a>,
 		Option<T![,]>,
 		Option<T![/]>,
-		Option<Channel>,
+		Option<Channel<'a>>,
 		Option<T![')']>,
 	),
 
@@ -179,26 +284,28 @@ pub enum ColorFunction {
 	// <legacy-hsla-syntax> = hsla( <hue>, <percentage>, <percentage>, <alpha-value>? )
 	Hsl(
 		T![Function],
-		Hue,
+		Option<&'a RelativeOrigin<'a>>,
+		Hue<'a>,
 		Option<T![,]>,
-		Channel,
+		Channel<'a>,
 		Option<T![,]>,
-		Channel,
+		Channel<'a>,
 		Option<T![,]>,
 		Option<T![/]>,
-		Option<Channel>,
+		Option<Channel<'a>>,
 		Option<T![')']>,
 	),
 	Hsla(
 		T![Function],
-		Hue,
+		Option<&'a RelativeOrigin<'a>>,
+		Hue<'a>,
 		Option<T![,]>,
-		Channel,
+		Channel<'a>,
 		Option<T![,]>,
-		Channel,
+		Channel<'a>,
 		Option<T![,]>,
 		Option<T![/]>,
-		Option<Channel>,
+		Option<Channel<'a>>,
 		Option<T![')']>,
 	),
 
@@ -208,55 +315,100 @@ pub enum ColorFunction {
 	//  [<percentage> | <number> | none]
 	//  [<percentage> | <number> | none]
 	//  [ / [<alpha-value> | none] ]? )
-	Hwb(T![Function], Hue, Channel, Channel, Option<T![/]>, Option<Channel>, Option<T![')']>),
+	Hwb(
+		T![Function],
+		Option<&'a RelativeOrigin<'a>>,
+		Hue<'a>,
+		Channel<'a>,
+		Channel<'a>,
+		Option<T![/]>,
+		Option<Channel<'a>>,
+		Option<T![')']>,
+	),
 
 	// https://drafts.csswg.org/css-color/#funcdef-lab
 	// lab() = lab( [<percentage> | <number> | none]
 	//  [ <percentage> | <number> | none]
 	//  [ <percentage> | <number> | none]
 	//  [ / [<alpha-value> | none] ]? )
-	Lab(T![Function], Channel, Channel, Channel, Option<T![/]>, Option<Channel>, Option<T![')']>),
+	Lab(
+		T![Function],
+		Option<&'a RelativeOrigin<'a>>,
+		Channel<'a>,
+		Channel<'a>,
+		Channel<'a>,
+		Option<T![/]>,
+		Option<Channel<'a>>,
+		Option<T![')']>,
+	),
 
 	// https://drafts.csswg.org/css-color/#funcdef-lch
 	// lch() = lch( [<percentage> | <number> | none]
 	//  [ <percentage> | <number> | none]
 	//  [ <hue> | none]
 	//  [ / [<alpha-value> | none] ]? )
-	Lch(T![Function], Channel, Channel, Hue, Option<T![/]>, Option<Channel>, Option<T![')']>),
+	Lch(
+		T![Function],
+		Option<&'a RelativeOrigin<'a>>,
+		Channel<'a>,
+		Channel<'a>,
+		Hue<'a>,
+		Option<T![/]>,
+		Option<Channel<'a>>,
+		Option<T![')']>,
+	),
 
 	// https://drafts.csswg.org/css-color/#funcdef-oklab
 	// oklab() = oklab( [ <percentage> | <number> | none]
 	//  [ <percentage> | <number> | none]
 	//  [ <percentage> | <number> | none]
 	//  [ / [<alpha-value> | none] ]? )
-	Oklab(T![Function], Channel, Channel, Channel, Option<T![/]>, Option<Channel>, Option<T![')']>),
+	Oklab(
+		T![Function],
+		Option<&'a RelativeOrigin<'a>>,
+		Channel<'a>,
+		Channel<'a>,
+		Channel<'a>,
+		Option<T![/]>,
+		Option<Channel<'a>>,
+		Option<T![')']>,
+	),
 
 	// https://drafts.csswg.org/css-color/#funcdef-oklch
 	// oklch() = oklch( [ <percentage> | <number> | none]
 	//  [ <percentage> | <number> | none]
 	//  [ <hue> | none]
 	//  [ / [<alpha-value> | none] ]? )
-	Oklch(T![Function], Channel, Channel, Hue, Option<T![/]>, Option<Channel>, Option<T![')']>),
+	Oklch(
+		T![Function],
+		Option<&'a RelativeOrigin<'a>>,
+		Channel<'a>,
+		Channel<'a>,
+		Hue<'a>,
+		Option<T![/]>,
+		Option<Channel<'a>>,
+		Option<T![')']>,
+	),
 }
 
-impl<'a> Peek<'a> for ColorFunction {
+impl<'a> Peek<'a> for ColorFunction<'a> {
 	fn peek(p: &Parser<'a>, c: Cursor) -> bool {
 		ColorFunctionName::peek(p, c)
 	}
 }
 
-impl<'a> ColorFunction {
+impl<'a> ColorFunction<'a> {
 	fn parse_rgb(
 		p: &mut Parser<'a>,
 	) -> ParserResult<(
-		Channel,
+		Channel<'a>,
 		Option<T![,]>,
-		Channel,
+		Channel<'a>,
 		Option<T![,]>,
-		Channel,
+		Channel<'a>,
 		Option<T![,]>,
 		Option<T![/]>,
-		Option<Channel>,
+		Option<Channel<'a>>,
 	)> {
 		let a = p.parse::<Channel>()?;
 		let b = p.parse_if_peek::<T![,]>()?;
@@ -272,14 +424,14 @@ impl<'a> ColorFunction {
 	fn parse_hsl(
 		p: &mut Parser<'a>,
 	) -> ParserResult<(
-		Hue,
+		Hue<'a>,
 		Option<T![,]>,
-		Channel,
+		Channel<'a>,
 		Option<T![,]>,
-		Channel,
+		Channel<'a>,
 		Option<T![,]>,
 		Option<T![/]>,
-		Option<Channel>,
+		Option<Channel<'a>>,
 	)> {
 		let a = p.parse::<Hue>()?;
 		let b = p.parse_if_peek::<T![,]>()?;
@@ -292,7 +444,9 @@ impl<'a> ColorFunction {
 		Ok((a, b, c, d, e, f, g, h))
 	}
 
-	fn parse_hwb(p: &mut Parser<'a>) -> ParserResult<(Hue, Channel, Channel, Option<T![/]>, Option<Channel>)> {
+	fn parse_hwb(
+		p: &mut Parser<'a>,
+	) -> ParserResult<(Hue<'a>, Channel<'a>, Channel<'a>, Option<T![/]>, Option<Channel<'a>>)> {
 		let a = p.parse::<Hue>()?;
 		let b = p.parse::<Channel>()?;
 		let c = p.parse::<Channel>()?;
@@ -301,7 +455,9 @@ impl<'a> ColorFunction {
 		Ok((a, b, c, d, e))
 	}
 
-	fn parse_lch(p: &mut Parser<'a>) -> ParserResult<(Channel, Channel, Hue, Option<T![/]>, Option<Channel>)> {
+	fn parse_lch(
+		p: &mut Parser<'a>,
+	) -> ParserResult<(Channel<'a>, Channel<'a>, Hue<'a>, Option<T![/]>, Option<Channel<'a>>)> {
 		let a = p.parse::<Channel>()?;
 		let b = p.parse::<Channel>()?;
 		let c = p.parse::<Hue>()?;
@@ -312,7 +468,7 @@ impl<'a> ColorFunction {
 
 	fn parse_three_channel(
 		p: &mut Parser<'a>,
-	) -> ParserResult<(Channel, Channel, Channel, Option<T![/]>, Option<Channel>)> {
+	) -> ParserResult<(Channel<'a>, Channel<'a>, Channel<'a>, Option<T![/]>, Option<Channel<'a>>)> {
 		let a = p.parse::<Channel>()?;
 		let b = p.parse::<Channel>()?;
 		let c = p.parse::<Channel>()?;
@@ -322,85 +478,170 @@ impl<'a> ColorFunction {
 	}
 }
 
-impl<'a> Parse<'a> for ColorFunction {
+impl<'a> Parse<'a> for ColorFunction<'a> {
 	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
 		let function = ColorFunctionName::parse(p)?;
+		// Channel keywords refer to the origin color, so they can only be used in the channels that follow it, and not
+		// within the origin color itself.
+		let state = p.set_state(p.state() & !State::RelativeColor);
+		let color = p.parse_if_peek::<RelativeOrigin>().and_then(|origin| {
+			let origin = origin.map(|origin| &*p.bump().alloc(origin));
+			if origin.is_some() {
+				p.set_state(state | State::RelativeColor);
+			}
+			Self::parse_channels(p, function, origin)
+		});
+		p.set_state(state);
+		color
+	}
+}
+
+impl<'a> ColorFunction<'a> {
+	fn parse_channels(
+		p: &mut Parser<'a>,
+		function: ColorFunctionName,
+		origin: Option<&'a RelativeOrigin<'a>>,
+	) -> ParserResult<Self> {
 		match function {
 			ColorFunctionName::Color(cursor) => {
 				let space = p.parse::<ColorSpace>()?;
 				let (a, b, c, d, e) = Self::parse_three_channel(p)?;
-				Ok(Self::Color(<T![Function]>::build(p, cursor), space, a, b, c, d, e, p.parse_if_peek::<T![')']>()?))
+				Ok(Self::Color(
+					<T![Function]>::build(p, cursor),
+					origin,
+					space,
+					a,
+					b,
+					c,
+					d,
+					e,
+					p.parse_if_peek::<T![')']>()?,
+				))
 			}
 			ColorFunctionName::Rgb(cursor) => {
 				let (a, b, c, d, e, f, g, h) = Self::parse_rgb(p)?;
-				Ok(Self::Rgb(<T![Function]>::build(p, cursor), a, b, c, d, e, f, g, h, p.parse_if_peek::<T![')']>()?))
+				Ok(Self::Rgb(
+					<T![Function]>::build(p, cursor),
+					origin,
+					a,
+					b,
+					c,
+					d,
+					e,
+					f,
+					g,
+					h,
+					p.parse_if_peek::<T![')']>()?,
+				))
 			}
 			ColorFunctionName::Rgba(cursor) => {
 				let (a, b, c, d, e, f, g, h) = Self::parse_rgb(p)?;
-				Ok(Self::Rgba(<T![Function]>::build(p, cursor), a, b, c, d, e, f, g, h, p.parse_if_peek::<T![')']>()?))
+				Ok(Self::Rgba(
+					<T![Function]>::build(p, cursor),
+					origin,
+					a,
+					b,
+					c,
+					d,
+					e,
+					f,
+					g,
+					h,
+					p.parse_if_peek::<T![')']>()?,
+				))
 			}
 			ColorFunctionName::Hsl(cursor) => {
 				let (a, b, c, d, e, f, g, h) = Self::parse_hsl(p)?;
-				Ok(Self::Hsl(<T![Function]>::build(p, cursor), a, b, c, d, e, f, g, h, p.parse_if_peek::<T![')']>()?))
+				Ok(Self::Hsl(
+					<T![Function]>::build(p, cursor),
+					origin,
+					a,
+					b,
+					c,
+					d,
+					e,
+					f,
+					g,
+					h,
+					p.parse_if_peek::<T![')']>()?,
+				))
 			}
 			ColorFunctionName::Hsla(cursor) => {
 				let (a, b, c, d, e, f, g, h) = Self::parse_hsl(p)?;
-				Ok(Self::Hsla(<T![Function]>::build(p, cursor), a, b, c, d, e, f, g, h, p.parse_if_peek::<T![')']>()?))
+				Ok(Self::Hsla(
+					<T![Function]>::build(p, cursor),
+					origin,
+					a,
+					b,
+					c,
+					d,
+					e,
+					f,
+					g,
+					h,
+					p.parse_if_peek::<T![')']>()?,
+				))
 			}
 			ColorFunctionName::Hwb(cursor) => {
 				let (a, b, c, d, e) = Self::parse_hwb(p)?;
-				Ok(Self::Hwb(<T![Function]>::build(p, cursor), a, b, c, d, e, p.parse_if_peek::<T![')']>()?))
+				Ok(Self::Hwb(<T![Function]>::build(p, cursor), origin, a, b, c, d, e, p.parse_if_peek::<T![')']>()?))
 			}
 			ColorFunctionName::Lab(cursor) => {
 				let (a, b, c, d, e) = Self::parse_three_channel(p)?;
-				Ok(Self::Lab(<T![Function]>::build(p, cursor), a, b, c, d, e, p.parse_if_peek::<T![')']>()?))
+				Ok(Self::Lab(<T![Function]>::build(p, cursor), origin, a, b, c, d, e, p.parse_if_peek::<T![')']>()?))
 			}
 			ColorFunctionName::Lch(cursor) => {
 				let (a, b, c, d, e) = Self::parse_lch(p)?;
-				Ok(Self::Lch(<T![Function]>::build(p, cursor), a, b, c, d, e, p.parse_if_peek::<T![')']>()?))
+				Ok(Self::Lch(<T![Function]>::build(p, cursor), origin, a, b, c, d, e, p.parse_if_peek::<T![')']>()?))
 			}
 			ColorFunctionName::Oklab(cursor) => {
 				let (a, b, c, d, e) = Self::parse_three_channel(p)?;
-				Ok(Self::Oklab(<T![Function]>::build(p, cursor), a, b, c, d, e, p.parse_if_peek::<T![')']>()?))
+				Ok(Self::Oklab(<T![Function]>::build(p, cursor), origin, a, b, c, d, e, p.parse_if_peek::<T![')']>()?))
 			}
 			ColorFunctionName::Oklch(cursor) => {
 				let (a, b, c, d, e) = Self::parse_lch(p)?;
-				Ok(Self::Oklch(<T![Function]>::build(p, cursor), a, b, c, d, e, p.parse_if_peek::<T![')']>()?))
+				Ok(Self::Oklch(<T![Function]>::build(p, cursor), origin, a, b, c, d, e, p.parse_if_peek::<T![')']>()?))
 			}
 		}
 	}
 }
 
-impl<'a> ToCursors for ColorFunction {
+impl<'a> ToCursors for ColorFunction<'a> {
 	fn to_cursors(&self, s: &mut impl css_parse::CursorSink) {
 		match self {
-			Self::Color(function, space, ch1, ch2, ch3, slash, alpha, close) => {
+			Self::Color(function, origin, space, ch1, ch2, ch3, slash, alpha, close) => {
 				s.append(function.into());
+				if let Some(origin) = origin {
+					ToCursors::to_cursors(*origin, s);
+				}
 				s.append(space.into());
-				s.append(ch1.into());
-				s.append(ch2.into());
-				s.append(ch3.into());
+				ToCursors::to_cursors(ch1, s);
+				ToCursors::to_cursors(ch2, s);
+				ToCursors::to_cursors(ch3, s);
 				if let Some(slash) = slash {
 					s.append(slash.into());
 				}
 				if let Some(alpha) = alpha {
-					s.append(alpha.into());
+					ToCursors::to_cursors(alpha, s);
 				}
 				if let Some(close) = close {
 					s.append(close.into());
 				}
 			}
-			Self::Rgb(function, r, c1, g, c2, b, c3, slash, alpha, close) => {
+			Self::Rgb(function, origin, r, c1, g, c2, b, c3, slash, alpha, close) => {
 				s.append(function.into());
-				s.append(r.into());
+				if let Some(origin) = origin {
+					ToCursors::to_cursors(*origin, s);
+				}
+				ToCursors::to_cursors(r, s);
 				if let Some(c1) = c1 {
 					s.append(c1.into());
 				}
-				s.append(g.into());
+				ToCursors::to_cursors(g, s);
 				if let Some(c2) = c2 {
 					s.append(c2.into());
 				}
-				s.append(b.into());
+				ToCursors::to_cursors(b, s);
 				if let Some(c3) = c3 {
 					s.append(c3.into());
 				}
@@ -408,23 +649,26 @@ impl<'a> ToCursors for ColorFunction {
 					s.append(slash.into());
 				}
 				if let Some(alpha) = alpha {
-					s.append(alpha.into());
+					ToCursors::to_cursors(alpha, s);
 				}
 				if let Some(close) = close {
 					s.append(close.into());
 				}
 			}
-			Self::Rgba(function, r, c1, g, c2, b, c3, slash, alpha, close) => {
+			Self::Rgba(function, origin, r, c1, g, c2, b, c3, slash, alpha, close) => {
 				s.append(function.into());
-				s.append(r.into());
+				if let Some(origin) = origin {
+					ToCursors::to_cursors(*origin, s);
+				}
+				ToCursors::to_cursors(r, s);
 				if let Some(c1) = c1 {
 					s.append(c1.into());
 				}
-				s.append(g.into());
+				ToCursors::to_cursors(g, s);
 				if let Some(c2) = c2 {
 					s.append(c2.into());
 				}
-				s.append(b.into());
+				ToCursors::to_cursors(b, s);
 				if let Some(c3) = c3 {
 					s.append(c3.into());
 				}
@@ -432,23 +676,26 @@ impl<'a> ToCursors for ColorFunction {
 					s.append(slash.into());
 				}
 				if let Some(alpha) = alpha {
-					s.append(alpha.into());
+					ToCursors::to_cursors(alpha, s);
 				}
 				if let Some(close) = close {
 					s.append(close.into());
 				}
 			}
-			Self::Hsl(function, h, c1, sat, c2, l, c3, slash, alpha, close) => {
+			Self::Hsl(function, origin, h, c1, sat, c2, l, c3, slash, alpha, close) => {
 				s.append(function.into());
-				s.append(h.into());
+				if let Some(origin) = origin {
+					ToCursors::to_cursors(*origin, s);
+				}
+				ToCursors::to_cursors(h, s);
 				if let Some(c1) = c1 {
 					s.append(c1.into());
 				}
-				s.append(sat.into());
+				ToCursors::to_cursors(sat, s);
 				if let Some(c2) = c2 {
 					s.append(c2.into());
 				}
-				s.append(l.into());
+				ToCursors::to_cursors(l, s);
 				if let Some(c3) = c3 {
 					s.append(c3.into());
 				}
@@ -456,23 +703,26 @@ impl<'a> ToCursors for ColorFunction {
 					s.append(slash.into());
 				}
 				if let Some(alpha) = alpha {
-					s.append(alpha.into());
+					ToCursors::to_cursors(alpha, s);
 				}
 				if let Some(close) = close {
 					s.append(close.into());
 				}
 			}
-			Self::Hsla(function, h, c1, sat, c2, l, c3, slash, alpha, close) => {
+			Self::Hsla(function, origin, h, c1, sat, c2, l, c3, slash, alpha, close) => {
 				s.append(function.into());
-				s.append(h.into());
+				if let Some(origin) = origin {
+					ToCursors::to_cursors(*origin, s);
+				}
+				ToCursors::to_cursors(h, s);
 				if let Some(c1) = c1 {
 					s.append(c1.into());
 				}
-				s.append(sat.into());
+				ToCursors::to_cursors(sat, s);
 				if let Some(c2) = c2 {
 					s.append(c2.into());
 				}
-				s.append(l.into());
+				ToCursors::to_cursors(l, s);
 				if let Some(c3) = c3 {
 					s.append(c3.into());
 				}
@@ -480,82 +730,97 @@ impl<'a> ToCursors for ColorFunction {
 					s.append(slash.into());
 				}
 				if let Some(alpha) = alpha {
-					s.append(alpha.into());
+					ToCursors::to_cursors(alpha, s);
 				}
 				if let Some(close) = close {
 					s.append(close.into());
 				}
 			}
-			Self::Hwb(function, h, w, b, slash, alpha, close) => {
+			Self::Hwb(function, origin, h, w, b, slash, alpha, close) => {
 				s.append(function.into());
-				s.append(h.into());
-				s.append(w.into());
-				s.append(b.into());
+				if let Some(origin) = origin {
+					ToCursors::to_cursors(*origin, s);
+				}
+				ToCursors::to_cursors(h, s);
+				ToCursors::to_cursors(w, s);
+				ToCursors::to_cursors(b, s);
 				if let Some(slash) = slash {
 					s.append(slash.into());
 				}
 				if let Some(alpha) = alpha {
-					s.append(alpha.into());
+					ToCursors::to_cursors(alpha, s);
 				}
 				if let Some(close) = close {
 					s.append(close.into());
 				}
 			}
-			Self::Lab(function, l, a, b, slash, alpha, close) => {
+			Self::Lab(function, origin, l, a, b, slash, alpha, close) => {
 				s.append(function.into());
-				s.append(l.into());
-				s.append(a.into());
-				s.append(b.into());
+				if let Some(origin) = origin {
+					ToCursors::to_cursors(*origin, s);
+				}
+				ToCursors::to_cursors(l, s);
+				ToCursors::to_cursors(a, s);
+				ToCursors::to_cursors(b, s);
 				if let Some(slash) = slash {
 					s.append(slash.into());
 				}
 				if let Some(alpha) = alpha {
-					s.append(alpha.into());
+					ToCursors::to_cursors(alpha, s);
 				}
 				if let Some(close) = close {
 					s.append(close.into());
 				}
 			}
-			Self::Lch(function, l, c, h, slash, alpha, close) => {
+			Self::Lch(function, origin, l, c, h, slash, alpha, close) => {
 				s.append(function.into());
-				s.append(l.into());
-				s.append(c.into());
-				s.append(h.into());
+				if let Some(origin) = origin {
+					ToCursors::to_cursors(*origin, s);
+				}
+				ToCursors::to_cursors(l, s);
+				ToCursors::to_cursors(c, s);
+				ToCursors::to_cursors(h, s);
 				if let Some(slash) = slash {
 					s.append(slash.into());
 				}
 				if let Some(alpha) = alpha {
-					s.append(alpha.into());
+					ToCursors::to_cursors(alpha, s);
 				}
 				if let Some(close) = close {
 					s.append(close.into());
 				}
 			}
-			Self::Oklab(function, l, a, b, slash, alpha, close) => {
+			Self::Oklab(function, origin, l, a, b, slash, alpha, close) => {
 				s.append(function.into());
-				s.append(l.into());
-				s.append(a.into());
-				s.append(b.into());
+				if let Some(origin) = origin {
+					ToCursors::to_cursors(*origin, s);
+				}
+				ToCursors::to_cursors(l, s);
+				ToCursors::to_cursors(a, s);
+				ToCursors::to_cursors(b, s);
 				if let Some(slash) = slash {
 					s.append(slash.into());
 				}
 				if let Some(alpha) = alpha {
-					s.append(alpha.into());
+					ToCursors::to_cursors(alpha, s);
 				}
 				if let Some(close) = close {
 					s.append(close.into());
 				}
 			}
-			Self::Oklch(function, l, c, h, slash, alpha, close) => {
+			Self::Oklch(function, origin, l, c, h, slash, alpha, close) => {
 				s.append(function.into());
-				s.append(l.into());
-				s.append(c.into());
-				s.append(h.into());
+				if let Some(origin) = origin {
+					ToCursors::to_cursors(*origin, s);
+				}
+				ToCursors::to_cursors(l, s);
+				ToCursors::to_cursors(c, s);
+				ToCursors::to_cursors(h, s);
 				if let Some(slash) = slash {
 					s.append(slash.into());
 				}
 				if let Some(alpha) = alpha {
-					s.append(alpha.into());
+					ToCursors::to_cursors(alpha, s);
 				}
 				if let Some(close) = close {
 					s.append(close.into());
@@ -572,6 +837,6 @@ mod tests {
 	#[test]
 	fn size_test() {
 		assert_eq!(std::mem::size_of::<Channel>(), 16);
-		assert_eq!(std::mem::size_of::<ColorFunction>(), 160);
+		assert_eq!(std::mem::size_of::<ColorFunction>(), 176);
 	}
 }
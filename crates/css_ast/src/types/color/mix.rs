@@ -0,0 +1,392 @@
+use css_lexer::Cursor;
+use css_parse::{diagnostics, keyword_set, CursorSink, Parse, Parser, Peek, Result as ParserResult, ToCursors, T};
+
+use super::{AbsoluteColor, Color, ColorSpaceKind};
+
+// https://drafts.csswg.org/css-color-4/#interpolation-space
+// <rectangular-color-space> = srgb | srgb-linear | display-p3 | a98-rgb | prophoto-rgb | rec2020 | lab | oklab | xyz | xyz-d50 | xyz-d65
+// <polar-color-space> = hsl | hwb | lch | oklch
+keyword_set!(InterpolationColorSpace {
+	Srgb: "srgb",
+	SrgbLinear: "srgb-linear",
+	DisplayP3: "display-p3",
+	A98Rgb: "a98-rgb",
+	ProphotoRgb: "prophoto-rgb",
+	Rec2020: "rec2020",
+	Lab: "lab",
+	Oklab: "oklab",
+	Xyz: "xyz",
+	XyzD50: "xyz-d50",
+	XyzD65: "xyz-d65",
+	Hsl: "hsl",
+	Hwb: "hwb",
+	Lch: "lch",
+	Oklch: "oklch",
+});
+
+impl From<InterpolationColorSpace> for ColorSpaceKind {
+	fn from(value: InterpolationColorSpace) -> Self {
+		match value {
+			InterpolationColorSpace::Srgb(_) => Self::Srgb,
+			InterpolationColorSpace::SrgbLinear(_) => Self::SrgbLinear,
+			InterpolationColorSpace::DisplayP3(_) => Self::DisplayP3,
+			InterpolationColorSpace::A98Rgb(_) => Self::A98Rgb,
+			InterpolationColorSpace::ProphotoRgb(_) => Self::ProphotoRgb,
+			InterpolationColorSpace::Rec2020(_) => Self::Rec2020,
+			InterpolationColorSpace::Lab(_) => Self::Lab,
+			InterpolationColorSpace::Oklab(_) => Self::Oklab,
+			InterpolationColorSpace::Xyz(_) | InterpolationColorSpace::XyzD65(_) => Self::XyzD65,
+			InterpolationColorSpace::XyzD50(_) => Self::XyzD50,
+			InterpolationColorSpace::Hsl(_) => Self::Hsl,
+			InterpolationColorSpace::Hwb(_) => Self::Hwb,
+			InterpolationColorSpace::Lch(_) => Self::Lch,
+			InterpolationColorSpace::Oklch(_) => Self::Oklch,
+		}
+	}
+}
+
+// https://drafts.csswg.org/css-color-4/#typedef-hue-interpolation-method
+// <hue-interpolation-method> = [ shorter | longer | increasing | decreasing ] hue
+keyword_set!(HueInterpolationKeyword {
+	Shorter: "shorter",
+	Longer: "longer",
+	Increasing: "increasing",
+	Decreasing: "decreasing",
+});
+
+/// How the hues of two colors are interpolated in a polar color space.
+///
+/// https://drafts.csswg.org/css-color/#hue-interpolation
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde(rename_all = "kebab-case"))]
+pub enum HueInterpolation {
+	#[default]
+	Shorter,
+	Longer,
+	Increasing,
+	Decreasing,
+}
+
+impl From<HueInterpolationKeyword> for HueInterpolation {
+	fn from(value: HueInterpolationKeyword) -> Self {
+		match value {
+			HueInterpolationKeyword::Shorter(_) => Self::Shorter,
+			HueInterpolationKeyword::Longer(_) => Self::Longer,
+			HueInterpolationKeyword::Increasing(_) => Self::Increasing,
+			HueInterpolationKeyword::Decreasing(_) => Self::Decreasing,
+		}
+	}
+}
+
+impl HueInterpolation {
+	// Adjust a pair of hues (in degrees) so that interpolating linearly between them follows this method.
+	// https://drafts.csswg.org/css-color/#hue-interpolation
+	fn fixup(&self, a: f32, b: f32) -> (f32, f32) {
+		let (a, b) = (a.rem_euclid(360.0), b.rem_euclid(360.0));
+		let delta = b - a;
+		match self {
+			Self::Shorter if delta > 180.0 => (a + 360.0, b),
+			Self::Shorter if delta < -180.0 => (a, b + 360.0),
+			Self::Longer if 0.0 < delta && delta < 180.0 => (a + 360.0, b),
+			Self::Longer if -180.0 < delta && delta <= 0.0 => (a, b + 360.0),
+			Self::Increasing if b < a => (a, b + 360.0),
+			Self::Decreasing if a < b => (a + 360.0, b),
+			_ => (a, b),
+		}
+	}
+}
+
+// https://drafts.csswg.org/css-color-4/#color-interpolation-method
+// <color-interpolation-method> = in [ <rectangular-color-space> | <polar-color-space> <hue-interpolation-method>? ]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
+pub struct ColorInterpolationMethod {
+	pub in_keyword: T![Ident],
+	pub space: InterpolationColorSpace,
+	pub hue: Option<(HueInterpolationKeyword, T![Ident])>,
+}
+
+impl ColorInterpolationMethod {
+	pub fn color_space(&self) -> ColorSpaceKind {
+		self.space.into()
+	}
+
+	pub fn hue_interpolation(&self) -> HueInterpolation {
+		self.hue.map_or(HueInterpolation::default(), |(keyword, _)| keyword.into())
+	}
+}
+
+impl<'a> Peek<'a> for ColorInterpolationMethod {
+	fn peek(p: &Parser<'a>, c: Cursor) -> bool {
+		<T![Ident]>::peek(p, c) && p.eq_ignore_ascii_case(c, "in")
+	}
+}
+
+impl<'a> Parse<'a> for ColorInterpolationMethod {
+	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
+		let in_keyword = p.parse::<T![Ident]>()?;
+		let c: Cursor = in_keyword.into();
+		if !p.eq_ignore_ascii_case(c, "in") {
+			Err(diagnostics::UnexpectedIdent(p.parse_str(c).into(), c.into()))?
+		}
+		let space = p.parse::<InterpolationColorSpace>()?;
+		let mut hue = None;
+		if ColorSpaceKind::from(space).is_polar() {
+			if let Some(keyword) = p.parse_if_peek::<HueInterpolationKeyword>()? {
+				let ident = p.parse::<T![Ident]>()?;
+				let c: Cursor = ident.into();
+				if !p.eq_ignore_ascii_case(c, "hue") {
+					Err(diagnostics::UnexpectedIdent(p.parse_str(c).into(), c.into()))?
+				}
+				hue = Some((keyword, ident));
+			}
+		}
+		Ok(Self { in_keyword, space, hue })
+	}
+}
+
+impl ToCursors for ColorInterpolationMethod {
+	fn to_cursors(&self, s: &mut impl CursorSink) {
+		s.append(self.in_keyword.into());
+		s.append(self.space.into());
+		if let Some((keyword, ident)) = self.hue {
+			s.append(keyword.into());
+			s.append(ident.into());
+		}
+	}
+}
+
+// https://drafts.csswg.org/css-color-5/#color-mix
+// color-mix() = color-mix( <color-interpolation-method> , [ <color> && <percentage [0,100]>? ]#{2})
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
+pub struct ColorMix<'a> {
+	pub function: T![Function],
+	pub method: ColorInterpolationMethod,
+	pub comma: T![,],
+	pub first: ColorMixComponent<'a>,
+	pub second_comma: T![,],
+	pub second: ColorMixComponent<'a>,
+	pub close: Option<T![')']>,
+}
+
+impl<'a> Peek<'a> for ColorMix<'a> {
+	fn peek(p: &Parser<'a>, c: Cursor) -> bool {
+		<T![Function]>::peek(p, c) && p.eq_ignore_ascii_case(c, "color-mix")
+	}
+}
+
+impl<'a> Parse<'a> for ColorMix<'a> {
+	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
+		let function = p.parse::<T![Function]>()?;
+		let c: Cursor = function.into();
+		if !p.eq_ignore_ascii_case(c, "color-mix") {
+			Err(diagnostics::UnexpectedFunction(p.parse_str(c).into(), c.into()))?
+		}
+		let method = p.parse::<ColorInterpolationMethod>()?;
+		let comma = p.parse::<T![,]>()?;
+		let first = p.parse::<ColorMixComponent>()?;
+		let second_comma = p.parse::<T![,]>()?;
+		let second = p.parse::<ColorMixComponent>()?;
+		let close = p.parse_if_peek::<T![')']>()?;
+		Ok(Self { function, method, comma, first, second_comma, second, close })
+	}
+}
+
+impl<'a> ToCursors for ColorMix<'a> {
+	fn to_cursors(&self, s: &mut impl CursorSink) {
+		s.append(self.function.into());
+		ToCursors::to_cursors(&self.method, s);
+		s.append(self.comma.into());
+		ToCursors::to_cursors(&self.first, s);
+		s.append(self.second_comma.into());
+		ToCursors::to_cursors(&self.second, s);
+		if let Some(close) = self.close {
+			s.append(close.into());
+		}
+	}
+}
+
+// <color> && <percentage [0,100]>?
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
+pub struct ColorMixComponent<'a> {
+	pub percentage_before: Option<T![Dimension::%]>,
+	pub color: Color<'a>,
+	pub percentage_after: Option<T![Dimension::%]>,
+}
+
+impl<'a> ColorMixComponent<'a> {
+	pub fn percentage(&self) -> Option<f32> {
+		self.percentage_before.or(self.percentage_after).map(f32::from)
+	}
+}
+
+impl<'a> Parse<'a> for ColorMixComponent<'a> {
+	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
+		let percentage_before = p.parse_if_peek::<T![Dimension::%]>()?;
+		let color = p.parse::<Color>()?;
+		let percentage_after = if percentage_before.is_none() { p.parse_if_peek::<T![Dimension::%]>()? } else { None };
+		Ok(Self { percentage_before, color, percentage_after })
+	}
+}
+
+impl<'a> ToCursors for ColorMixComponent<'a> {
+	fn to_cursors(&self, s: &mut impl CursorSink) {
+		if let Some(percentage) = self.percentage_before {
+			s.append(percentage.into());
+		}
+		ToCursors::to_cursors(&self.color, s);
+		if let Some(percentage) = self.percentage_after {
+			s.append(percentage.into());
+		}
+	}
+}
+
+impl AbsoluteColor {
+	/// Mix two colors in the given color space, as `color-mix()` does. Each percentage (in `0.0..=100.0`) is how much
+	/// of that color to use; when only one is given the other makes up the rest, and when neither is given the colors
+	/// are mixed equally. Returns [None] if both percentages are zero.
+	///
+	/// https://drafts.csswg.org/css-color-5/#color-mix-result
+	pub fn mix(
+		&self,
+		other: &Self,
+		percentages: (Option<f32>, Option<f32>),
+		space: ColorSpaceKind,
+		hue: HueInterpolation,
+	) -> Option<Self> {
+		// https://drafts.csswg.org/css-color-5/#color-mix-percent-norm
+		let (p1, p2) = match percentages {
+			(None, None) => (50.0, 50.0),
+			(Some(p1), None) => (p1, 100.0 - p1),
+			(None, Some(p2)) => (100.0 - p2, p2),
+			(Some(p1), Some(p2)) => (p1, p2),
+		};
+		let sum = p1 + p2;
+		if sum <= 0.0 {
+			return None;
+		}
+		let alpha_multiplier = if sum < 100.0 { sum / 100.0 } else { 1.0 };
+		let (weight1, weight2) = (p1 / sum, p2 / sum);
+
+		let mut a = self.to_space_for_interpolation(space);
+		let mut b = other.to_space_for_interpolation(space);
+		// https://drafts.csswg.org/css-color/#interpolation-missing
+		for i in 0..3 {
+			a.components[i] = a.components[i].or(b.components[i]);
+			b.components[i] = b.components[i].or(a.components[i]);
+		}
+		a.alpha = a.alpha.or(b.alpha);
+		b.alpha = b.alpha.or(a.alpha);
+
+		// https://drafts.csswg.org/css-color/#interpolation-alpha
+		let hue_index = space.hue_index();
+		let premultiply = |color: &mut Self| {
+			let alpha = color.alpha.unwrap_or(1.0);
+			for (i, component) in color.components.iter_mut().enumerate() {
+				if Some(i) != hue_index {
+					*component = component.map(|c| c * alpha);
+				}
+			}
+		};
+		premultiply(&mut a);
+		premultiply(&mut b);
+
+		let mut components = [None; 3];
+		for (i, component) in components.iter_mut().enumerate() {
+			let (Some(mut c1), Some(mut c2)) = (a.components[i], b.components[i]) else {
+				continue;
+			};
+			if Some(i) == hue_index {
+				(c1, c2) = hue.fixup(c1, c2);
+			}
+			*component = Some(c1 * weight1 + c2 * weight2);
+		}
+		let alpha = a.alpha.zip(b.alpha).map(|(a1, a2)| a1 * weight1 + a2 * weight2);
+		let divisor = alpha.unwrap_or(1.0);
+		for (i, component) in components.iter_mut().enumerate() {
+			if Some(i) == hue_index {
+				*component = component.map(|c| c.rem_euclid(360.0));
+			} else if divisor != 0.0 {
+				*component = component.map(|c| c / divisor);
+			}
+		}
+		Some(Self { space, components, alpha: alpha.map(|alpha| alpha * alpha_multiplier) })
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use css_parse::{assert_parse, assert_parse_error};
+
+	#[test]
+	fn size_test() {
+		assert_eq!(std::mem::size_of::<ColorInterpolationMethod>(), 56);
+	}
+
+	#[test]
+	fn test_writes() {
+		assert_parse!(ColorMix, "color-mix(in srgb,red,blue)");
+		assert_parse!(ColorMix, "color-mix(in oklch longer hue,red 40%,blue)");
+		assert_parse!(ColorMix, "color-mix(in lab,25% red,blue 75%)");
+		assert_parse!(ColorMix, "color-mix(in srgb,color-mix(in srgb,red,blue),white)");
+	}
+
+	#[test]
+	fn test_errors() {
+		assert_parse_error!(ColorMix, "color-mix(srgb,red,blue)");
+		assert_parse_error!(ColorMix, "color-mix(in srgb longer hue,red,blue)");
+		assert_parse_error!(ColorMix, "color-mix(in srgb,red)");
+	}
+
+	fn assert_close(actual: AbsoluteColor, expected: AbsoluteColor) {
+		assert_eq!(actual.space, expected.space);
+		for (a, b) in actual.components.iter().zip(expected.components).chain([(&actual.alpha, expected.alpha)]) {
+			match (a, b) {
+				(Some(a), Some(b)) => assert!((a - b).abs() < 0.001, "{actual:?} != {expected:?}"),
+				_ => assert_eq!(*a, b, "{actual:?} != {expected:?}"),
+			}
+		}
+	}
+
+	#[test]
+	fn test_mix() {
+		let red = AbsoluteColor::srgb(1.0, 0.0, 0.0, 1.0);
+		let blue = AbsoluteColor::srgb(0.0, 0.0, 1.0, 1.0);
+		let srgb = ColorSpaceKind::Srgb;
+		let shorter = HueInterpolation::Shorter;
+		assert_close(red.mix(&blue, (None, None), srgb, shorter).unwrap(), AbsoluteColor::srgb(0.5, 0.0, 0.5, 1.0));
+		assert_close(
+			red.mix(&blue, (Some(25.0), None), srgb, shorter).unwrap(),
+			AbsoluteColor::srgb(0.25, 0.0, 0.75, 1.0),
+		);
+		// Percentages summing to less than 100% reduce the alpha.
+		assert_close(
+			red.mix(&blue, (Some(25.0), Some(25.0)), srgb, shorter).unwrap(),
+			AbsoluteColor::srgb(0.5, 0.0, 0.5, 0.5),
+		);
+		assert_eq!(red.mix(&blue, (Some(0.0), Some(0.0)), srgb, shorter), None);
+		// Alpha is premultiplied, so a transparent color does not darken the other.
+		assert_close(
+			red.mix(&AbsoluteColor::TRANSPARENT, (None, None), srgb, shorter).unwrap(),
+			AbsoluteColor::srgb(1.0, 0.0, 0.0, 0.5),
+		);
+	}
+
+	#[test]
+	fn test_mix_hues() {
+		let a = AbsoluteColor::new(ColorSpaceKind::Hsl, 350.0, 100.0, 50.0, 1.0);
+		let b = AbsoluteColor::new(ColorSpaceKind::Hsl, 30.0, 100.0, 50.0, 1.0);
+		let hsl = ColorSpaceKind::Hsl;
+		let hue = |method| a.mix(&b, (None, None), hsl, method).unwrap().components[0].unwrap();
+		assert_eq!(hue(HueInterpolation::Shorter), 10.0);
+		assert_eq!(hue(HueInterpolation::Longer), 190.0);
+		assert_eq!(hue(HueInterpolation::Increasing), 10.0);
+		assert_eq!(hue(HueInterpolation::Decreasing), 190.0);
+		// A missing hue takes the other color's hue.
+		let gray = AbsoluteColor { components: [None, Some(0.0), Some(50.0)], ..a };
+		assert_eq!(gray.mix(&b, (None, None), hsl, HueInterpolation::Shorter).unwrap().components[0], Some(30.0));
+	}
+}
@@ -1,29 +1,32 @@
+mod absolute;
 mod color_function;
+mod mix;
 mod named;
+mod serialize;
 mod system;
 
 use css_lexer::Cursor;
 use css_parse::{diagnostics, keyword_set, Build, Parse, Parser, Peek, Result as ParserResult, ToCursors, T};
 
+pub use absolute::*;
 pub use color_function::*;
+pub use mix::*;
 pub use named::*;
 pub use system::*;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
-pub enum Color {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
+pub enum Color<'a> {
 	Currentcolor(T![Ident]),
 	Transparent(T![Ident]),
 	System(SystemColor),
 	Hex(T![Hash]),
 	Named(NamedColor),
-	Function(ColorFunction),
-	// TODO: need bumpalo::Box PartialEq, or bumpalo::Box serde
-	// Relative(Box<'a, Color<'a>>, ColorFunction),
-	// Mix(ColorMixSyntax, Box<'a, Color<'a>>, u8, Box<'a, Color<'a>>),
+	Function(ColorFunction<'a>),
+	Mix(&'a ColorMix<'a>),
 }
 
-impl Color {
+impl<'a> Color<'a> {
 	// Alias CanvasText for #[initial()]
 	// #[allow(non_upper_case_globals)]
 	// pub const Canvastext: Color = Color::System(SystemColor::CanvasText);
@@ -31,7 +34,7 @@ impl Color {
 
 keyword_set!(ColorKeyword { Currentcolor: "currentcolor", Transparent: "transparent" });
 
-impl<'a> ToCursors for Color {
+impl<'a> ToCursors for Color<'a> {
 	fn to_cursors(&self, s: &mut impl css_parse::CursorSink) {
 		match self {
 			Self::Currentcolor(t) => s.append((*t).into()),
@@ -40,17 +43,18 @@ impl<'a> ToCursors for Color {
 			Self::Hex(t) => s.append((*t).into()),
 			Self::Named(t) => s.append((*t).into()),
 			Self::Function(func) => ToCursors::to_cursors(func, s),
+			Self::Mix(mix) => ToCursors::to_cursors(*mix, s),
 		}
 	}
 }
 
-impl<'a> Peek<'a> for Color {
+impl<'a> Peek<'a> for Color<'a> {
 	fn peek(p: &Parser<'a>, c: Cursor) -> bool {
 		<T![Hash]>::peek(p, c) || <T![Function]>::peek(p, c) || <T![Ident]>::peek(p, c)
 	}
 }
 
-impl<'a> Parse<'a> for Color {
+impl<'a> Parse<'a> for Color<'a> {
 	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
 		if p.peek::<T![Hash]>() {
 			Ok(Self::Hex(p.parse::<T![Hash]>()?))
@@ -71,6 +75,9 @@ impl<'a> Parse<'a> for Color {
 			}
 		} else if p.peek::<ColorFunction>() {
 			p.parse::<ColorFunction>().map(Color::Function)
+		} else if p.peek::<ColorMix>() {
+			let mix = p.parse::<ColorMix>()?;
+			Ok(Self::Mix(p.bump().alloc(mix)))
 		} else {
 			Err(diagnostics::Unimplemented(p.peek_n(1).into()))?
 		}
@@ -84,7 +91,7 @@ mod tests {
 
 	#[test]
 	fn size_test() {
-		assert_eq!(std::mem::size_of::<Color>(), 160);
+		assert_eq!(std::mem::size_of::<Color>(), 176);
 	}
 
 	#[test]
@@ -103,6 +110,11 @@ mod tests {
 		assert_parse!(Color, "lab(63.673% 51.577 5.811)");
 		assert_parse!(Color, "hwb(740deg 20% 30%/50%)");
 		assert_parse!(Color, "lch(20% 30% 740deg/50%)");
+		assert_parse!(Color, "rgb(from red r g b)");
+		assert_parse!(Color, "hsl(from #fff h s l/alpha)", "hsl(from#fff h s l/alpha)");
+		assert_parse!(Color, "oklch(from #123 calc(l + 0.1) c h)", "oklch(from#123 calc(l + 0.1)c h)");
+		assert_parse!(Color, "color(from red srgb r g calc(b*2))");
+		assert_parse!(Color, "color-mix(in oklch,red 10%,blue)");
 	}
 
 	#[test]
@@ -133,5 +145,10 @@ mod tests {
 		assert_parse_error!(Color, "hsl(250, 255deg, 255)");
 		// Using degrees for wrong component in lch
 		assert_parse_error!(Color, "lch(250, 255deg, 255)");
+		// Using channel keywords without an origin color
+		assert_parse_error!(Color, "rgb(r g b)");
+		assert_parse_error!(Color, "oklch(50% calc(c * 2) 120deg)");
+		// Using channel keywords within the origin color
+		assert_parse_error!(Color, "rgb(from rgb(r g b) r g b)");
 	}
 }
@@ -151,6 +151,162 @@ keyword_set!(NamedColor {
 	Yellowgreen: "yellowgreen",
 });
 
+impl NamedColor {
+	/// The sRGB channels of this named color, as listed in https://drafts.csswg.org/css-color/#named-colors.
+	pub fn to_rgb(&self) -> (u8, u8, u8) {
+		match self {
+			Self::Aliceblue(_) => (240, 248, 255),
+			Self::Antiquewhite(_) => (250, 235, 215),
+			Self::Aqua(_) => (0, 255, 255),
+			Self::Aquamarine(_) => (127, 255, 212),
+			Self::Azure(_) => (240, 255, 255),
+			Self::Beige(_) => (245, 245, 220),
+			Self::Bisque(_) => (255, 228, 196),
+			Self::Black(_) => (0, 0, 0),
+			Self::Blanchedalmond(_) => (255, 235, 205),
+			Self::Blue(_) => (0, 0, 255),
+			Self::Blueviolet(_) => (138, 43, 226),
+			Self::Brown(_) => (165, 42, 42),
+			Self::Burlywood(_) => (222, 184, 135),
+			Self::Cadetblue(_) => (95, 158, 160),
+			Self::Chartreuse(_) => (127, 255, 0),
+			Self::Chocolate(_) => (210, 105, 30),
+			Self::Coral(_) => (255, 127, 80),
+			Self::Cornflowerblue(_) => (100, 149, 237),
+			Self::Cornsilk(_) => (255, 248, 220),
+			Self::Crimson(_) => (220, 20, 60),
+			Self::Cyan(_) => (0, 255, 255),
+			Self::Darkblue(_) => (0, 0, 139),
+			Self::Darkcyan(_) => (0, 139, 139),
+			Self::Darkgoldenrod(_) => (184, 134, 11),
+			Self::Darkgray(_) => (169, 169, 169),
+			Self::Darkgreen(_) => (0, 100, 0),
+			Self::Darkgrey(_) => (169, 169, 169),
+			Self::Darkkhaki(_) => (189, 183, 107),
+			Self::Darkmagenta(_) => (139, 0, 139),
+			Self::Darkolivegreen(_) => (85, 107, 47),
+			Self::Darkorange(_) => (255, 140, 0),
+			Self::Darkorchid(_) => (153, 50, 204),
+			Self::Darkred(_) => (139, 0, 0),
+			Self::Darksalmon(_) => (233, 150, 122),
+			Self::Darkseagreen(_) => (143, 188, 143),
+			Self::Darkslateblue(_) => (72, 61, 139),
+			Self::Darkslategray(_) => (47, 79, 79),
+			Self::Darkslategrey(_) => (47, 79, 79),
+			Self::Darkturquoise(_) => (0, 206, 209),
+			Self::Darkviolet(_) => (148, 0, 211),
+			Self::Deeppink(_) => (255, 20, 147),
+			Self::Deepskyblue(_) => (0, 191, 255),
+			Self::Dimgray(_) => (105, 105, 105),
+			Self::Dimgrey(_) => (105, 105, 105),
+			Self::Dodgerblue(_) => (30, 144, 255),
+			Self::Firebrick(_) => (178, 34, 34),
+			Self::Floralwhite(_) => (255, 250, 240),
+			Self::Forestgreen(_) => (34, 139, 34),
+			Self::Fuchsia(_) => (255, 0, 255),
+			Self::Gainsboro(_) => (220, 220, 220),
+			Self::Ghostwhite(_) => (248, 248, 255),
+			Self::Gold(_) => (255, 215, 0),
+			Self::Goldenrod(_) => (218, 165, 32),
+			Self::Gray(_) => (128, 128, 128),
+			Self::Green(_) => (0, 128, 0),
+			Self::Greenyellow(_) => (173, 255, 47),
+			Self::Grey(_) => (128, 128, 128),
+			Self::Honeydew(_) => (240, 255, 240),
+			Self::Hotpink(_) => (255, 105, 180),
+			Self::Indianred(_) => (205, 92, 92),
+			Self::Indigo(_) => (75, 0, 130),
+			Self::Ivory(_) => (255, 255, 240),
+			Self::Khaki(_) => (240, 230, 140),
+			Self::Lavender(_) => (230, 230, 250),
+			Self::Lavenderblush(_) => (255, 240, 245),
+			Self::Lawngreen(_) => (124, 252, 0),
+			Self::Lemonchiffon(_) => (255, 250, 205),
+			Self::Lightblue(_) => (173, 216, 230),
+			Self::Lightcoral(_) => (240, 128, 128),
+			Self::Lightcyan(_) => (224, 255, 255),
+			Self::Lightgoldenrodyellow(_) => (250, 250, 210),
+			Self::Lightgray(_) => (211, 211, 211),
+			Self::Lightgreen(_) => (144, 238, 144),
+			Self::Lightgrey(_) => (211, 211, 211),
+			Self::Lightpink(_) => (255, 182, 193),
+			Self::Lightsalmon(_) => (255, 160, 122),
+			Self::Lightseagreen(_) => (32, 178, 170),
+			Self::Lightskyblue(_) => (135, 206, 250),
+			Self::Lightslategray(_) => (119, 136, 153),
+			Self::Lightslategrey(_) => (119, 136, 153),
+			Self::Lightsteelblue(_) => (176, 196, 222),
+			Self::Lightyellow(_) => (255, 255, 224),
+			Self::Lime(_) => (0, 255, 0),
+			Self::Limegreen(_) => (50, 205, 50),
+			Self::Linen(_) => (250, 240, 230),
+			Self::Magenta(_) => (255, 0, 255),
+			Self::Maroon(_) => (128, 0, 0),
+			Self::Mediumaquamarine(_) => (102, 205, 170),
+			Self::Mediumblue(_) => (0, 0, 205),
+			Self::Mediumorchid(_) => (186, 85, 211),
+			Self::Mediumpurple(_) => (147, 112, 219),
+			Self::Mediumseagreen(_) => (60, 179, 113),
+			Self::Mediumslateblue(_) => (123, 104, 238),
+			Self::Mediumspringgreen(_) => (0, 250, 154),
+			Self::Mediumturquoise(_) => (72, 209, 204),
+			Self::Mediumvioletred(_) => (199, 21, 133),
+			Self::Midnightblue(_) => (25, 25, 112),
+			Self::Mintcream(_) => (245, 255, 250),
+			Self::Mistyrose(_) => (255, 228, 225),
+			Self::Moccasin(_) => (255, 228, 181),
+			Self::Navajowhite(_) => (255, 222, 173),
+			Self::Navy(_) => (0, 0, 128),
+			Self::Oldlace(_) => (253, 245, 230),
+			Self::Olive(_) => (128, 128, 0),
+			Self::Olivedrab(_) => (107, 142, 35),
+			Self::Orange(_) => (255, 165, 0),
+			Self::Orangered(_) => (255, 69, 0),
+			Self::Orchid(_) => (218, 112, 214),
+			Self::Palegoldenrod(_) => (238, 232, 170),
+			Self::Palegreen(_) => (152, 251, 152),
+			Self::Paleturquoise(_) => (175, 238, 238),
+			Self::Palevioletred(_) => (219, 112, 147),
+			Self::Papayawhip(_) => (255, 239, 213),
+			Self::Peachpuff(_) => (255, 218, 185),
+			Self::Peru(_) => (205, 133, 63),
+			Self::Pink(_) => (255, 192, 203),
+			Self::Plum(_) => (221, 160, 221),
+			Self::Powderblue(_) => (176, 224, 230),
+			Self::Purple(_) => (128, 0, 128),
+			Self::Rebeccapurple(_) => (102, 51, 153),
+			Self::Red(_) => (255, 0, 0),
+			Self::Rosybrown(_) => (188, 143, 143),
+			Self::Royalblue(_) => (65, 105, 225),
+			Self::Saddlebrown(_) => (139, 69, 19),
+			Self::Salmon(_) => (250, 128, 114),
+			Self::Sandybrown(_) => (244, 164, 96),
+			Self::Seagreen(_) => (46, 139, 87),
+			Self::Seashell(_) => (255, 245, 238),
+			Self::Sienna(_) => (160, 82, 45),
+			Self::Silver(_) => (192, 192, 192),
+			Self::Skyblue(_) => (135, 206, 235),
+			Self::Slateblue(_) => (106, 90, 205),
+			Self::Slategray(_) => (112, 128, 144),
+			Self::Slategrey(_) => (112, 128, 144),
+			Self::Snow(_) => (255, 250, 250),
+			Self::Springgreen(_) => (0, 255, 127),
+			Self::Steelblue(_) => (70, 130, 180),
+			Self::Tan(_) => (210, 180, 140),
+			Self::Teal(_) => (0, 128, 128),
+			Self::Thistle(_) => (216, 191, 216),
+			Self::Tomato(_) => (255, 99, 71),
+			Self::Turquoise(_) => (64, 224, 208),
+			Self::Violet(_) => (238, 130, 238),
+			Self::Wheat(_) => (245, 222, 179),
+			Self::White(_) => (255, 255, 255),
+			Self::Whitesmoke(_) => (245, 245, 245),
+			Self::Yellow(_) => (255, 255, 0),
+			Self::Yellowgreen(_) => (154, 205, 50),
+		}
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
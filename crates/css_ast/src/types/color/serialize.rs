@@ -0,0 +1,171 @@
+use core::fmt;
+
+use super::{AbsoluteColor, ColorSpaceKind};
+
+// The named colors which are shorter than their hex notation, for example `red` rather than `#f00`.
+const SHORT_NAMES: [(&str, [u8; 3]); 31] = [
+	("azure", [240, 255, 255]),
+	("beige", [245, 245, 220]),
+	("bisque", [255, 228, 196]),
+	("brown", [165, 42, 42]),
+	("coral", [255, 127, 80]),
+	("gold", [255, 215, 0]),
+	("gray", [128, 128, 128]),
+	("green", [0, 128, 0]),
+	("indigo", [75, 0, 130]),
+	("ivory", [255, 255, 240]),
+	("khaki", [240, 230, 140]),
+	("linen", [250, 240, 230]),
+	("maroon", [128, 0, 0]),
+	("navy", [0, 0, 128]),
+	("olive", [128, 128, 0]),
+	("orange", [255, 165, 0]),
+	("orchid", [218, 112, 214]),
+	("peru", [205, 133, 63]),
+	("pink", [255, 192, 203]),
+	("plum", [221, 160, 221]),
+	("purple", [128, 0, 128]),
+	("red", [255, 0, 0]),
+	("salmon", [250, 128, 114]),
+	("sienna", [160, 82, 45]),
+	("silver", [192, 192, 192]),
+	("snow", [255, 250, 250]),
+	("tan", [210, 180, 140]),
+	("teal", [0, 128, 128]),
+	("tomato", [255, 99, 71]),
+	("violet", [238, 130, 238]),
+	("wheat", [245, 222, 179]),
+];
+
+// Convert a value in `0.0..=1.0` to a byte, if it is (very nearly) a whole number of 255ths.
+fn to_byte(value: f32) -> Option<u8> {
+	let scaled = value * 255.0;
+	let rounded = scaled.round();
+	((0.0..=255.0).contains(&rounded) && (scaled - rounded).abs() < 0.001).then_some(rounded as u8)
+}
+
+// Write a number with at most 5 decimal places, and without the leading zero of a fraction.
+fn write_number(value: f32, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+	let formatted = format!("{:.5}", value);
+	let trimmed = formatted.trim_end_matches('0').trim_end_matches('.');
+	match trimmed {
+		"-0" | "" => f.write_str("0"),
+		_ if trimmed.starts_with("0.") => f.write_str(&trimmed[1..]),
+		_ if trimmed.starts_with("-0.") => write!(f, "-{}", &trimmed[2..]),
+		_ => f.write_str(trimmed),
+	}
+}
+
+fn write_component(value: Option<f32>, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+	match value {
+		Some(value) => write_number(value, f),
+		None => f.write_str("none"),
+	}
+}
+
+impl AbsoluteColor {
+	/// The shortest CSS text which represents this color exactly, for example `red`, `#0000`, or `lab(50 20 -10/.5)`.
+	///
+	/// sRGB colors (including `hsl` & `hwb`) whose channels are whole bytes serialize as the shortest of a named color
+	/// or hex notation. Other colors use the functional notation of their color space.
+	pub fn to_shortest_css(&self) -> String {
+		self.to_string()
+	}
+
+	fn to_rgba8(self) -> Option<[u8; 4]> {
+		if !matches!(self.space, ColorSpaceKind::Srgb | ColorSpaceKind::Hsl | ColorSpaceKind::Hwb) {
+			return None;
+		}
+		let srgb = self.to_space(ColorSpaceKind::Srgb);
+		let [r, g, b] = srgb.components;
+		Some([to_byte(r?)?, to_byte(g?)?, to_byte(b?)?, to_byte(srgb.alpha?)?])
+	}
+}
+
+/// Writes the [shortest serialization][AbsoluteColor::to_shortest_css] of the color.
+impl fmt::Display for AbsoluteColor {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		if let Some(rgba @ [r, g, b, a]) = self.to_rgba8() {
+			if a == 255 {
+				if let Some((name, _)) = SHORT_NAMES.iter().find(|(_, rgb)| *rgb == [r, g, b]) {
+					return f.write_str(name);
+				}
+			}
+			let bytes = if a == 255 { &rgba[..3] } else { &rgba[..] };
+			f.write_str("#")?;
+			if bytes.iter().all(|byte| byte % 17 == 0) {
+				return bytes.iter().try_for_each(|byte| write!(f, "{:x}", byte / 17));
+			}
+			return bytes.iter().try_for_each(|byte| write!(f, "{:02x}", byte));
+		}
+		let mut components = self.components;
+		match self.space {
+			ColorSpaceKind::Srgb | ColorSpaceKind::Hsl | ColorSpaceKind::Hwb => {
+				components = self.to_space(ColorSpaceKind::Srgb).components.map(|c| c.map(|c| c * 255.0));
+				f.write_str("rgb(")?;
+			}
+			ColorSpaceKind::Lab | ColorSpaceKind::Lch | ColorSpaceKind::Oklab | ColorSpaceKind::Oklch => {
+				write!(f, "{}(", self.space)?;
+			}
+			_ => write!(f, "color({} ", self.space)?,
+		}
+		for (i, component) in components.iter().enumerate() {
+			if i > 0 {
+				f.write_str(" ")?;
+			}
+			write_component(*component, f)?;
+		}
+		if self.alpha != Some(1.0) {
+			f.write_str("/")?;
+			write_component(self.alpha, f)?;
+		}
+		f.write_str(")")
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::types::NamedColor;
+	use bumpalo::Bump;
+	use css_parse::Parser;
+
+	#[test]
+	fn test_short_names() {
+		let bump = Bump::default();
+		for (name, rgb) in SHORT_NAMES {
+			let named = Parser::new(&bump, name).parse_entirely::<NamedColor>().output.unwrap();
+			let (r, g, b) = named.to_rgb();
+			assert_eq!([r, g, b], rgb, "{name}");
+		}
+	}
+
+	#[test]
+	fn test_shortest_css() {
+		assert_eq!(AbsoluteColor::from_rgba8(255, 0, 0, 1.0).to_shortest_css(), "red");
+		assert_eq!(AbsoluteColor::from_rgba8(255, 255, 255, 1.0).to_shortest_css(), "#fff");
+		assert_eq!(AbsoluteColor::from_rgba8(18, 52, 86, 1.0).to_shortest_css(), "#123456");
+		assert_eq!(AbsoluteColor::TRANSPARENT.to_shortest_css(), "#0000");
+		assert_eq!(AbsoluteColor::from_rgba8(255, 0, 0, 0.0).to_shortest_css(), "#f000");
+		assert_eq!(AbsoluteColor::from_rgba8(18, 52, 86, 128.0 / 255.0).to_shortest_css(), "#12345680");
+		assert_eq!(
+			AbsoluteColor::new(ColorSpaceKind::Hsl, 120.0, 100.0, 128.0 / 255.0 * 50.0, 1.0).to_shortest_css(),
+			"green"
+		);
+		assert_eq!(AbsoluteColor::srgb(1.0, 0.0, 0.0, 0.5).to_shortest_css(), "rgb(255 0 0/.5)");
+		assert_eq!(AbsoluteColor::srgb(0.1, 0.2, 0.3, 1.0).to_shortest_css(), "rgb(25.5 51 76.5)");
+	}
+
+	#[test]
+	fn test_functional_notation() {
+		let lab = AbsoluteColor::new(ColorSpaceKind::Lab, 50.0, 20.0, -10.25, 1.0);
+		assert_eq!(lab.to_shortest_css(), "lab(50 20 -10.25)");
+		let oklch = AbsoluteColor { components: [Some(0.5), Some(0.1), None], ..lab };
+		assert_eq!(
+			AbsoluteColor { space: ColorSpaceKind::Oklch, alpha: Some(0.25), ..oklch }.to_shortest_css(),
+			"oklch(.5 .1 none/.25)"
+		);
+		let p3 = AbsoluteColor::new(ColorSpaceKind::DisplayP3, 1.0, 0.0, 0.0, 1.0);
+		assert_eq!(p3.to_shortest_css(), "color(display-p3 1 0 0)");
+	}
+}
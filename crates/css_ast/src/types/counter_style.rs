@@ -4,7 +4,7 @@ use css_parse::{keyword_set, Parse, Parser, Peek, Result as ParserResult, ToCurs
 use super::Symbols;
 
 #[derive(Debug, Clone, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub enum CounterStyle<'a> {
 	Predefined(PredefinedCounter),
 	Named(T![Ident]),
@@ -21,10 +21,16 @@ function_set!(GradientFunctionName {
 
 // https://drafts.csswg.org/css-images-3/#typedef-gradient
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub enum Gradient<'a> {
-	Linear(T![Function], Option<LinearDirection>, Option<T![,]>, Vec<'a, ColorStopOrHint>, Option<T![')']>),
-	RepeatingLinear(T![Function], Option<LinearDirection>, Option<T![,]>, Vec<'a, ColorStopOrHint>, Option<T![')']>),
+	Linear(T![Function], Option<LinearDirection>, Option<T![,]>, Vec<'a, ColorStopOrHint<'a>>, Option<T![')']>),
+	RepeatingLinear(
+		T![Function],
+		Option<LinearDirection>,
+		Option<T![,]>,
+		Vec<'a, ColorStopOrHint<'a>>,
+		Option<T![')']>,
+	),
 	Radial(
 		T![Function],
 		Option<RadialSize>,
@@ -32,7 +38,7 @@ pub enum Gradient<'a> {
 		Option<T![Ident]>,
 		Option<Position>,
 		Option<T![,]>,
-		Vec<'a, ColorStopOrHint>,
+		Vec<'a, ColorStopOrHint<'a>>,
 		Option<T![')']>,
 	),
 	RepeatingRadial(
@@ -42,13 +48,13 @@ pub enum Gradient<'a> {
 		Option<T![Ident]>,
 		Option<Position>,
 		Option<T![,]>,
-		Vec<'a, ColorStopOrHint>,
+		Vec<'a, ColorStopOrHint<'a>>,
 		Option<T![')']>,
 	),
 }
 
 impl<'a> Gradient<'a> {
-	fn parse_stops(p: &mut Parser<'a>) -> ParserResult<Vec<'a, ColorStopOrHint>> {
+	fn parse_stops(p: &mut Parser<'a>) -> ParserResult<Vec<'a, ColorStopOrHint<'a>>> {
 		let mut stops = Vec::new_in(p.bump());
 		let mut allow_hint = false;
 		loop {
@@ -240,7 +246,7 @@ impl<'a> ToCursors for Gradient<'a> {
 keyword_set!(NamedDirection { Bottom: "bottom", Top: "top", Left: "left", Right: "right" });
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub enum LinearDirection {
 	Angle(Angle),
 	Named(T![Ident], NamedDirection, Option<NamedDirection>),
@@ -286,7 +292,7 @@ impl<'a> ToCursors for LinearDirection {
 
 // https://drafts.csswg.org/css-images-3/#typedef-rg-size
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub enum RadialSize {
 	ClosestCorner(T![Ident]),
 	ClosestSide(T![Ident]),
@@ -354,13 +360,13 @@ impl<'a> ToCursors for RadialSize {
 keyword_set!(RadialShape { Circle: "circle", Ellipse: "ellipse" });
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
-pub enum ColorStopOrHint {
-	Stop(Color, Option<LengthPercentage>, Option<T![,]>),
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
+pub enum ColorStopOrHint<'a> {
+	Stop(Color<'a>, Option<LengthPercentage>, Option<T![,]>),
 	Hint(LengthPercentage, T![,]),
 }
 
-impl<'a> ToCursors for ColorStopOrHint {
+impl<'a> ToCursors for ColorStopOrHint<'a> {
 	fn to_cursors(&self, s: &mut impl CursorSink) {
 		match self {
 			Self::Stop(c, l, comma) => {
@@ -390,7 +396,7 @@ mod tests {
 		assert_eq!(std::mem::size_of::<Gradient>(), 208);
 		assert_eq!(std::mem::size_of::<LinearDirection>(), 44);
 		assert_eq!(std::mem::size_of::<RadialSize>(), 32);
-		assert_eq!(std::mem::size_of::<ColorStopOrHint>(), 192);
+		assert_eq!(std::mem::size_of::<ColorStopOrHint>(), 208);
 	}
 
 	#[test]
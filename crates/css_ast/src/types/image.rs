@@ -5,7 +5,7 @@ use super::Gradient;
 
 // https://drafts.csswg.org/css-images-3/#typedef-image
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub enum Image<'a> {
 	Url(T![Url]),
 	UrlFunction(T![Function], T![String], T![')']),
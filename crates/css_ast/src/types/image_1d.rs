@@ -9,10 +9,10 @@ use crate::{types::Color, units::LengthPercentageOrFlex};
 // <stripes()> = stripes( <color-stripe># )
 // <color-stripe> = <color> && [ <length-percentage> | <flex> ]?
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub struct Image1D<'a> {
 	pub function: T![Function],
-	pub stripes: Vec<'a, ColorStripe>,
+	pub stripes: Vec<'a, ColorStripe<'a>>,
 	pub close: T![')'],
 }
 
@@ -53,14 +53,14 @@ impl<'a> ToCursors for Image1D<'a> {
 // https://drafts.csswg.org/css-images-4/#typedef-color-stripe
 // <color-stripe> = <color> && [ <length-percentage> | <flex> ]?
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
-pub struct ColorStripe {
-	pub color: Color,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
+pub struct ColorStripe<'a> {
+	pub color: Color<'a>,
 	pub thickness: Option<LengthPercentageOrFlex>,
 	pub comma: Option<T![,]>,
 }
 
-impl<'a> Parse<'a> for ColorStripe {
+impl<'a> Parse<'a> for ColorStripe<'a> {
 	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
 		let mut color = p.parse_if_peek::<Color>()?;
 		let thickness = p.parse_if_peek::<LengthPercentageOrFlex>()?;
@@ -72,7 +72,7 @@ impl<'a> Parse<'a> for ColorStripe {
 	}
 }
 
-impl<'a> ToCursors for ColorStripe {
+impl<'a> ToCursors for ColorStripe<'a> {
 	fn to_cursors(&self, s: &mut impl css_parse::CursorSink) {
 		ToCursors::to_cursors(&self.color, s);
 		if let Some(thickness) = self.thickness {
@@ -92,7 +92,7 @@ mod tests {
 	#[test]
 	fn size_test() {
 		assert_eq!(std::mem::size_of::<Image1D>(), 56);
-		assert_eq!(std::mem::size_of::<ColorStripe>(), 192);
+		assert_eq!(std::mem::size_of::<ColorStripe>(), 208);
 	}
 
 	#[test]
@@ -8,6 +8,7 @@ mod opacity_value;
 mod position;
 mod ratio;
 mod symbols;
+mod url;
 pub use color::*;
 pub use counter_style::*;
 pub use gradient::*;
@@ -18,3 +19,4 @@ pub use opacity_value::*;
 pub use position::*;
 pub use ratio::*;
 pub use symbols::*;
+pub use url::*;
@@ -18,7 +18,7 @@ use crate::units::LengthPercentage;
 //   [ [ top | bottom ] <length-percentage> ]
 // ]
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub enum Position {
 	SingleValue(PositionSingleValue),
 	TwoValue(PositionHorizontal, PositionVertical),
@@ -116,7 +116,7 @@ impl<'a> ToCursors for Position {
 keyword_set!(PositionValueKeyword { Left: "left", Right: "right", Center: "center", Top: "top", Bottom: "bottom" });
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub enum PositionSingleValue {
 	Left(T![Ident]),
 	Right(T![Ident]),
@@ -227,7 +227,7 @@ impl<'a> Build<'a> for PositionSingleValue {
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub enum PositionHorizontal {
 	Left(T![Ident]),
 	Right(T![Ident]),
@@ -258,7 +258,7 @@ impl From<PositionHorizontal> for Cursor {
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub enum PositionVertical {
 	Top(T![Ident]),
 	Bottom(T![Ident]),
@@ -4,7 +4,7 @@ use css_parse::{Parse, Parser, Peek, Result as ParserResult, ToCursors, T};
 
 // https://drafts.csswg.org/css-values-4/#ratios
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub struct Ratio {
 	start: SourceOffset,
 	pub numerator: CSSInt,
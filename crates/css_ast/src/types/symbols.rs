@@ -6,7 +6,7 @@ use crate::types::Image;
 
 // https://drafts.csswg.org/css-counter-styles-3/#funcdef-symbols
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub struct Symbols<'a> {
 	pub function: T![Function],
 	pub symbols_type: Option<SymbolsType>,
@@ -65,7 +65,7 @@ impl<'a> ToCursors for Symbols<'a> {
 
 // https://drafts.csswg.org/css-counter-styles-3/#funcdef-symbols
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub enum Symbol<'a> {
 	String(T![String]),
 	Image(Image<'a>),
@@ -0,0 +1,117 @@
+use css_lexer::{Cursor, SourceOffset, Span};
+use css_parse::{diagnostics, CursorSink, Parse, Parser, Peek, Result as ParserResult, ToCursors, T};
+
+// https://drafts.csswg.org/css-values-4/#urls
+/// A URL, written as `url(foo.css)` or `url("foo.css")`, or as a bare string where the grammar allows `<string>` in
+/// place of `<url>`, as `@import` and `@namespace` do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
+pub enum Url {
+	Url(T![Url]),
+	UrlFunction(T![Function], T![String], T![')']),
+	String(T![String]),
+}
+
+impl<'a> Peek<'a> for Url {
+	fn peek(p: &Parser<'a>, c: Cursor) -> bool {
+		<T![Url]>::peek(p, c)
+			|| <T![String]>::peek(p, c)
+			|| (<T![Function]>::peek(p, c) && p.eq_ignore_ascii_case(c, "url"))
+	}
+}
+
+impl<'a> Parse<'a> for Url {
+	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
+		if p.peek::<T![Url]>() {
+			Ok(Self::Url(p.parse::<T![Url]>()?))
+		} else if p.peek::<T![String]>() {
+			Ok(Self::String(p.parse::<T![String]>()?))
+		} else {
+			let func = p.parse::<T![Function]>()?;
+			if !p.eq_ignore_ascii_case(func.into(), "url") {
+				Err(diagnostics::UnexpectedFunction(p.parse_str(func.into()).into(), func.into()))?
+			}
+			let string = p.parse::<T![String]>()?;
+			let close = p.parse::<T![')']>()?;
+			Ok(Self::UrlFunction(func, string, close))
+		}
+	}
+}
+
+impl ToCursors for Url {
+	fn to_cursors(&self, s: &mut impl CursorSink) {
+		match self {
+			Self::Url(c) => s.append(c.into()),
+			Self::UrlFunction(func, string, close) => {
+				s.append(func.into());
+				s.append(string.into());
+				s.append(close.into());
+			}
+			Self::String(c) => s.append(c.into()),
+		}
+	}
+}
+
+impl Url {
+	/// The span of the URL itself, without the surrounding `url(`, `)`, quotes or whitespace. Escapes aren't resolved,
+	/// so this is only an approximation for URLs which contain them.
+	pub fn value_span(&self, source: &str) -> Span {
+		match self {
+			Self::Url(c) => {
+				let c: Cursor = c.into();
+				let str = c.str_slice(source);
+				let open = str.find('(').map_or(0, |i| i + 1);
+				let close = if c.token().url_has_closing_paren() { str.len() - 1 } else { str.len() };
+				let inner = &str[open..close];
+				let start = open + (inner.len() - inner.trim_start().len());
+				let end = close - (inner.len() - inner.trim_end().len());
+				let offset = c.offset().0;
+				Span::new(SourceOffset(offset + start as u32), SourceOffset(offset + end.max(start) as u32))
+			}
+			Self::UrlFunction(_, string, _) | Self::String(string) => {
+				let c: Cursor = string.into();
+				let end = if c.token().has_close_quote() { c.end_offset().0 - 1 } else { c.end_offset().0 };
+				Span::new(SourceOffset(c.offset().0 + 1), SourceOffset(end))
+			}
+		}
+	}
+
+	/// The URL itself, for example `foo.css` for `url("foo.css")`.
+	pub fn value<'a>(&self, source: &'a str) -> &'a str {
+		self.value_span(source).span_contents(source).contents()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use css_parse::assert_parse;
+
+	#[test]
+	fn size_test() {
+		assert_eq!(std::mem::size_of::<Url>(), 40);
+	}
+
+	#[test]
+	fn test_writes() {
+		assert_parse!(Url, "url(foo.css)");
+		assert_parse!(Url, "url('foo.css')");
+		assert_parse!(Url, "\"foo.css\"");
+	}
+
+	#[test]
+	fn test_value() {
+		let bump = bumpalo::Bump::default();
+		for (source_text, value) in [
+			("url(foo.css)", "foo.css"),
+			("url(  foo.css  )", "foo.css"),
+			("url(\"a b.css\")", "a b.css"),
+			("'foo.css'", "foo.css"),
+			("\"foo.css", "foo.css"),
+			("url()", ""),
+		] {
+			let url = Parser::new(&bump, source_text).parse_entirely::<Url>().output.unwrap();
+			assert_eq!(url.value(source_text), value, "{source_text}");
+		}
+	}
+}
@@ -1,13 +1,13 @@
 use css_lexer::Cursor;
 use css_parse::{Build, Parser, Peek, T};
 
-const DEG_GRAD: f32 = 0.9;
-const DEG_RAD: f32 = 57.295_78;
-const DEG_TURN: f32 = 360.0;
+pub(crate) const DEG_GRAD: f32 = 0.9;
+pub(crate) const DEG_RAD: f32 = 57.295_78;
+pub(crate) const DEG_TURN: f32 = 360.0;
 
 // https://drafts.csswg.org/css-values/#angles
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub enum Angle {
 	Grad(T![Dimension::Grad]),
 	Rad(T![Dimension::Rad]),
@@ -2,7 +2,7 @@ use css_lexer::{Cursor, DimensionUnit};
 use css_parse::{Build, Parser, Peek, T};
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub struct CustomDimension(T![Dimension]);
 
 impl From<CustomDimension> for f32 {
@@ -3,7 +3,7 @@ use css_parse::{Build, Parser, Peek, T};
 
 // https://www.w3.org/TR/css-grid-2/#typedef-flex
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub struct Flex(T![Dimension::Fr]);
 
 impl From<Flex> for f32 {
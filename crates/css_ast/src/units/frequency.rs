@@ -3,7 +3,7 @@ use css_parse::{Build, Parser, Peek, T};
 
 // https://drafts.csswg.org/css-values/#resolution
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub enum Frequency {
 	Hz(T![Dimension::Hz]),
 	Khz(T![Dimension::Khz]),
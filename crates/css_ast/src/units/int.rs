@@ -2,7 +2,7 @@ use css_lexer::Cursor;
 use css_parse::{Build, Parser, Peek, T};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(transparent))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde(transparent))]
 pub struct CSSInt(T![Number]);
 
 impl CSSInt {
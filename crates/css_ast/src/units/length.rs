@@ -3,12 +3,12 @@ use css_parse::{Build, Parser, Peek, T};
 
 use super::Flex;
 
-// const PX_CM: f32 = PX_IN / 2.54;
-// const PX_MM: f32 = PX_IN / 25.4;
-// const PX_Q: f32 = PX_MM / 4.0;
-// const PX_IN: f32 = 96.0;
-// const PX_PC: f32 = PX_IN / 6.0;
-// const PX_PT: f32 = PX_IN / 72.0;
+pub(crate) const PX_CM: f32 = PX_IN / 2.54;
+pub(crate) const PX_MM: f32 = PX_IN / 25.4;
+pub(crate) const PX_Q: f32 = PX_MM / 4.0;
+pub(crate) const PX_IN: f32 = 96.0;
+pub(crate) const PX_PC: f32 = PX_IN / 6.0;
+pub(crate) const PX_PT: f32 = PX_IN / 72.0;
 
 macro_rules! apply_lengths {
 	($ident: ident) => {
@@ -76,7 +76,7 @@ macro_rules! apply_lengths {
 macro_rules! define_length {
 	( $($name: ident),+ $(,)* ) => {
 		#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-		#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type", content = "value", rename_all = "kebab-case"))]
+		#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde(tag = "type", content = "value", rename_all = "kebab-case"))]
 		pub enum Length {
 			Zero(T![Number]),
 			$($name(T![Dimension::$name]),)+
@@ -177,7 +177,7 @@ impl From<&Length> for Cursor {
 macro_rules! define_length_percentage {
 	( $($name: ident),+ $(,)* ) => {
 		#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-		#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type", content = "value", rename_all = "kebab-case"))]
+		#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde(tag = "type", content = "value", rename_all = "kebab-case"))]
 		pub enum LengthPercentage {
 			Zero(T![Number]),
 			$($name(T![Dimension::$name]),)+
@@ -284,7 +284,7 @@ impl<'a> Build<'a> for LengthPercentage {
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(rename_all = "kebab-case"))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde(rename_all = "kebab-case"))]
 pub enum LengthPercentageOrAuto {
 	Auto(T![Ident]),
 	LengthPercentage(LengthPercentage),
@@ -334,7 +334,7 @@ impl From<LengthPercentageOrAuto> for Cursor {
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(rename_all = "kebab-case"))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde(rename_all = "kebab-case"))]
 pub enum LengthPercentageOrFlex {
 	Flex(Flex),
 	LengthPercentage(LengthPercentage),
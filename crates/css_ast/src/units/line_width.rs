@@ -6,7 +6,7 @@ use super::Length;
 keyword_set!(LineWidthKeyword { Thin: "thin", Medium: "medium", Thick: "thick" });
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(rename_all = "kebab-case"))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde(rename_all = "kebab-case"))]
 pub enum LineWidth {
 	Thin(T![Ident]),
 	Medium(T![Ident]),
@@ -1,12 +1,12 @@
 use css_lexer::Cursor;
 use css_parse::{Build, Parser, Peek, T};
 
-const DPPX_IN: f32 = 96.0;
-const DPPX_CM: f32 = DPPX_IN / 2.54;
+pub(crate) const DPPX_IN: f32 = 96.0;
+pub(crate) const DPPX_CM: f32 = DPPX_IN / 2.54;
 
 // https://drafts.csswg.org/css-values/#resolution
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub enum Resolution {
 	Dpi(T![Dimension::Dpi]),
 	Dpcm(T![Dimension::Dpcm]),
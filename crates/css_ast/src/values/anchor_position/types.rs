@@ -40,7 +40,7 @@ keyword_set!(TrySize {
 //   [ self-start | center | self-end | span-self-start | span-self-end | span-all ]{1,2}
 // ]
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(rename_all = "kebab-case"))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde(rename_all = "kebab-case"))]
 pub enum PositionArea {
 	Physical(Option<PositionAreaPhsyicalHorizontal>, Option<PositionAreaPhsyicalVertical>),
 	Logical(Option<PositionAreaBlock>, Option<PositionAreaInline>),
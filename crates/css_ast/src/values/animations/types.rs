@@ -8,7 +8,7 @@ pub use crate::units::*;
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(
 	feature = "serde",
-	derive(serde::Serialize),
+	derive(serde::Serialize, css_parse::FromJson),
 	serde(tag = "type", content = "value", rename_all = "kebab-case")
 )]
 pub enum SingleAnimationIterationCount {
@@ -16,7 +16,7 @@ use impls::*;
 #[percentages("n/a")]
 #[canonical_order("per grammar")]
 #[animation_type("by computed value")]
-pub struct BackgroundColorStyleValue;
+pub struct BackgroundColorStyleValue<'a>;
 
 // // https://drafts.csswg.org/css-backgrounds-4/#background-image
 // #[value(" <bg-image># ")]
@@ -8,17 +8,17 @@ mod tests {
 
 	#[test]
 	pub fn size_test() {
-		assert_eq!(std::mem::size_of::<BorderTopColorStyleValue>(), 160);
-		assert_eq!(std::mem::size_of::<BorderRightColorStyleValue>(), 160);
-		assert_eq!(std::mem::size_of::<BorderBottomColorStyleValue>(), 160);
-		assert_eq!(std::mem::size_of::<BorderLeftColorStyleValue>(), 160);
-		assert_eq!(std::mem::size_of::<BorderBlockStartColorStyleValue>(), 160);
-		assert_eq!(std::mem::size_of::<BorderBlockEndColorStyleValue>(), 160);
-		assert_eq!(std::mem::size_of::<BorderInlineStartColorStyleValue>(), 160);
-		assert_eq!(std::mem::size_of::<BorderInlineEndColorStyleValue>(), 160);
+		assert_eq!(std::mem::size_of::<BorderTopColorStyleValue>(), 176);
+		assert_eq!(std::mem::size_of::<BorderRightColorStyleValue>(), 176);
+		assert_eq!(std::mem::size_of::<BorderBottomColorStyleValue>(), 176);
+		assert_eq!(std::mem::size_of::<BorderLeftColorStyleValue>(), 176);
+		assert_eq!(std::mem::size_of::<BorderBlockStartColorStyleValue>(), 176);
+		assert_eq!(std::mem::size_of::<BorderBlockEndColorStyleValue>(), 176);
+		assert_eq!(std::mem::size_of::<BorderInlineStartColorStyleValue>(), 176);
+		assert_eq!(std::mem::size_of::<BorderInlineEndColorStyleValue>(), 176);
 		// assert_eq!(std::mem::size_of::<BorderColorStyleValue>(), 1);
-		assert_eq!(std::mem::size_of::<BorderBlockColorStyleValue>(), 320);
-		assert_eq!(std::mem::size_of::<BorderInlineColorStyleValue>(), 320);
+		assert_eq!(std::mem::size_of::<BorderBlockColorStyleValue>(), 352);
+		assert_eq!(std::mem::size_of::<BorderInlineColorStyleValue>(), 352);
 		assert_eq!(std::mem::size_of::<BorderTopStyleStyleValue>(), 16);
 		assert_eq!(std::mem::size_of::<BorderRightStyleStyleValue>(), 16);
 		assert_eq!(std::mem::size_of::<BorderBottomStyleStyleValue>(), 16);
@@ -38,16 +38,16 @@ mod tests {
 		assert_eq!(std::mem::size_of::<BorderInlineEndWidthStyleValue>(), 16);
 		assert_eq!(std::mem::size_of::<BorderBlockWidthStyleValue>(), 32);
 		assert_eq!(std::mem::size_of::<BorderInlineWidthStyleValue>(), 32);
-		assert_eq!(std::mem::size_of::<BorderTopStyleValue>(), 192);
-		assert_eq!(std::mem::size_of::<BorderRightStyleValue>(), 192);
-		assert_eq!(std::mem::size_of::<BorderBottomStyleValue>(), 192);
-		assert_eq!(std::mem::size_of::<BorderLeftStyleValue>(), 192);
-		assert_eq!(std::mem::size_of::<BorderBlockStartStyleValue>(), 192);
-		assert_eq!(std::mem::size_of::<BorderBlockEndStyleValue>(), 192);
-		assert_eq!(std::mem::size_of::<BorderInlineStartStyleValue>(), 192);
-		assert_eq!(std::mem::size_of::<BorderInlineEndStyleValue>(), 192);
-		assert_eq!(std::mem::size_of::<BorderBlockStyleValue>(), 192);
-		assert_eq!(std::mem::size_of::<BorderInlineStyleValue>(), 192);
+		assert_eq!(std::mem::size_of::<BorderTopStyleValue>(), 208);
+		assert_eq!(std::mem::size_of::<BorderRightStyleValue>(), 208);
+		assert_eq!(std::mem::size_of::<BorderBottomStyleValue>(), 208);
+		assert_eq!(std::mem::size_of::<BorderLeftStyleValue>(), 208);
+		assert_eq!(std::mem::size_of::<BorderBlockStartStyleValue>(), 208);
+		assert_eq!(std::mem::size_of::<BorderBlockEndStyleValue>(), 208);
+		assert_eq!(std::mem::size_of::<BorderInlineStartStyleValue>(), 208);
+		assert_eq!(std::mem::size_of::<BorderInlineEndStyleValue>(), 208);
+		assert_eq!(std::mem::size_of::<BorderBlockStyleValue>(), 208);
+		assert_eq!(std::mem::size_of::<BorderInlineStyleValue>(), 208);
 		assert_eq!(std::mem::size_of::<BorderTopLeftRadiusStyleValue>(), 32);
 		assert_eq!(std::mem::size_of::<BorderTopRightRadiusStyleValue>(), 32);
 		assert_eq!(std::mem::size_of::<BorderBottomRightRadiusStyleValue>(), 32);
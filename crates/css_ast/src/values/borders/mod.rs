@@ -326,7 +326,7 @@ pub struct BorderInlineWidthStyleValue;
 #[percentages("n/a")]
 #[canonical_order("per grammar")]
 #[animation_type("see individual properties")]
-pub struct BorderTopStyleValue;
+pub struct BorderTopStyleValue<'a>;
 
 // https://drafts.csswg.org/css-borders-4/#border-right
 #[value(" <line-width> || <line-style> || <color> ")]
@@ -336,7 +336,7 @@ pub struct BorderTopStyleValue;
 #[percentages("n/a")]
 #[canonical_order("per grammar")]
 #[animation_type("see individual properties")]
-pub struct BorderRightStyleValue;
+pub struct BorderRightStyleValue<'a>;
 
 // https://drafts.csswg.org/css-borders-4/#border-bottom
 #[value(" <line-width> || <line-style> || <color> ")]
@@ -346,7 +346,7 @@ pub struct BorderRightStyleValue;
 #[percentages("n/a")]
 #[canonical_order("per grammar")]
 #[animation_type("see individual properties")]
-pub struct BorderBottomStyleValue;
+pub struct BorderBottomStyleValue<'a>;
 
 // https://drafts.csswg.org/css-borders-4/#border-left
 #[value(" <line-width> || <line-style> || <color> ")]
@@ -356,7 +356,7 @@ pub struct BorderBottomStyleValue;
 #[percentages("n/a")]
 #[canonical_order("per grammar")]
 #[animation_type("see individual properties")]
-pub struct BorderLeftStyleValue;
+pub struct BorderLeftStyleValue<'a>;
 
 // https://drafts.csswg.org/css-borders-4/#border-block-start
 #[value(" <line-width> || <line-style> || <color> ")]
@@ -366,7 +366,7 @@ pub struct BorderLeftStyleValue;
 #[percentages("n/a")]
 #[canonical_order("per grammar")]
 #[animation_type("see individual properties")]
-pub struct BorderBlockStartStyleValue;
+pub struct BorderBlockStartStyleValue<'a>;
 
 // https://drafts.csswg.org/css-borders-4/#border-block-end
 #[value(" <line-width> || <line-style> || <color> ")]
@@ -376,7 +376,7 @@ pub struct BorderBlockStartStyleValue;
 #[percentages("n/a")]
 #[canonical_order("per grammar")]
 #[animation_type("see individual properties")]
-pub struct BorderBlockEndStyleValue;
+pub struct BorderBlockEndStyleValue<'a>;
 
 // https://drafts.csswg.org/css-borders-4/#border-inline-start
 #[value(" <line-width> || <line-style> || <color> ")]
@@ -386,7 +386,7 @@ pub struct BorderBlockEndStyleValue;
 #[percentages("n/a")]
 #[canonical_order("per grammar")]
 #[animation_type("see individual properties")]
-pub struct BorderInlineStartStyleValue;
+pub struct BorderInlineStartStyleValue<'a>;
 
 // https://drafts.csswg.org/css-borders-4/#border-inline-end
 #[value(" <line-width> || <line-style> || <color> ")]
@@ -396,7 +396,7 @@ pub struct BorderInlineStartStyleValue;
 #[percentages("n/a")]
 #[canonical_order("per grammar")]
 #[animation_type("see individual properties")]
-pub struct BorderInlineEndStyleValue;
+pub struct BorderInlineEndStyleValue<'a>;
 
 // https://drafts.csswg.org/css-borders-4/#border-block
 #[value(" <'border-block-start'> ")]
@@ -406,7 +406,7 @@ pub struct BorderInlineEndStyleValue;
 #[percentages("see individual properties")]
 #[canonical_order("per grammar")]
 #[animation_type("see individual properties")]
-pub struct BorderBlockStyleValue;
+pub struct BorderBlockStyleValue<'a>;
 
 // https://drafts.csswg.org/css-borders-4/#border-inline
 #[value(" <'border-block-start'> ")]
@@ -416,7 +416,7 @@ pub struct BorderBlockStyleValue;
 #[percentages("see individual properties")]
 #[canonical_order("per grammar")]
 #[animation_type("see individual properties")]
-pub struct BorderInlineStyleValue;
+pub struct BorderInlineStyleValue<'a>;
 
 // https://drafts.csswg.org/css-borders-4/#border-top-left-radius
 #[value(" <length-percentage [0,∞]>{1,2} ")]
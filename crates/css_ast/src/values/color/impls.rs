@@ -8,7 +8,7 @@ mod tests {
 
 	#[test]
 	fn size_test() {
-		assert_eq!(std::mem::size_of::<ColorStyleValue>(), 160);
+		assert_eq!(std::mem::size_of::<ColorStyleValue>(), 176);
 		assert_eq!(std::mem::size_of::<OpacityStyleValue>(), 16);
 	}
 
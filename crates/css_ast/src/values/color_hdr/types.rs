@@ -3,7 +3,7 @@ use css_lexer::Cursor;
 use css_parse::{diagnostics, CursorSink, Parse, Parser, Peek, Result as ParserResult, ToCursors, T};
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub struct DynamicRangeLimitMix<'a> {
 	function: T![Function],
 	values: Vec<'a, (T![Ident], T![Dimension::%], Option<T![,]>)>,
@@ -2,6 +2,7 @@ mod impls;
 pub mod types;
 
 use impls::*;
+pub use types::{AbsoluteSize, RelativeSize};
 
 /*
  * https://drafts.csswg.org/css-fonts-5/
@@ -48,15 +49,15 @@ pub enum FontWidthStyleValue {}
 // #[animation_type("by computed value type;normal animates as oblique 0deg")]
 // pub enum FontStyleStyleValue {}
 
-// // https://drafts.csswg.org/css-fonts-5/#font-size
-// #[value(" <absolute-size> | <relative-size> | <length-percentage [0,∞]> | math ")]
-// #[initial("medium")]
-// #[applies_to("all elements and text")]
-// #[inherited("yes")]
-// #[percentages("refer to parent element’s font size")]
-// #[canonical_order("per grammar")]
-// #[animation_type("by computed value type")]
-// pub enum FontSizeStyleValue {}
+// https://drafts.csswg.org/css-fonts-5/#font-size
+#[value(" <absolute-size> | <relative-size> | <length-percentage [0,∞]> | math ")]
+#[initial("medium")]
+#[applies_to("all elements and text")]
+#[inherited("yes")]
+#[percentages("refer to parent element’s font size")]
+#[canonical_order("per grammar")]
+#[animation_type("by computed value type")]
+pub enum FontSizeStyleValue {}
 
 // // https://drafts.csswg.org/css-fonts-5/#font-size-adjust
 // #[value(" none | [ ex-height | cap-height | ch-width | ic-width | ic-height ]? [ from-font | <number [0,∞]> ] ")]
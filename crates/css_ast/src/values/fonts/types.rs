@@ -1 +1,19 @@
 pub(crate) use crate::units::*;
+use css_parse::keyword_set;
+
+// https://drafts.csswg.org/css-fonts/#absolute-size-mapping
+// <absolute-size> = xx-small | x-small | small | medium | large | x-large | xx-large | xxx-large
+keyword_set!(AbsoluteSize {
+	XxSmall: "xx-small",
+	XSmall: "x-small",
+	Small: "small",
+	Medium: "medium",
+	Large: "large",
+	XLarge: "x-large",
+	XxLarge: "xx-large",
+	XxxLarge: "xxx-large",
+});
+
+// https://drafts.csswg.org/css-fonts/#valdef-font-size-relative-size
+// <relative-size> = larger | smaller
+keyword_set!(RelativeSize { Larger: "larger", Smaller: "smaller" });
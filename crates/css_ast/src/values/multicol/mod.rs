@@ -46,7 +46,7 @@ pub struct ColumnsStyleValue;
 #[percentages("n/a")]
 #[canonical_order("per grammar")]
 #[animation_type("by computed value type")]
-pub struct ColumnRuleColorStyleValue;
+pub struct ColumnRuleColorStyleValue<'a>;
 
 // https://drafts.csswg.org/css-multicol-2/#column-rule-style
 #[value(" <line-style> ")]
@@ -76,7 +76,7 @@ pub struct ColumnRuleWidthStyleValue;
 #[percentages("see individual properties")]
 #[canonical_order("per grammar")]
 #[animation_type("see individual properties")]
-pub struct ColumnRuleStyleValue;
+pub struct ColumnRuleStyleValue<'a>;
 
 // https://drafts.csswg.org/css-multicol-2/#column-span
 #[value(" none | <integer [1,∞]> | all | auto ")]
@@ -8,7 +8,7 @@ mod tests {
 
 	#[test]
 	fn size_test() {
-		assert_eq!(std::mem::size_of::<ScrollbarColorStyleValue>(), 320);
+		assert_eq!(std::mem::size_of::<ScrollbarColorStyleValue>(), 352);
 	}
 
 	#[test]
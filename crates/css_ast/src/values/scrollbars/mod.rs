@@ -16,7 +16,7 @@ use impls::*;
 #[percentages("n/a")]
 #[canonical_order("per grammar")]
 #[animation_type("by computed value")]
-pub enum ScrollbarColorStyleValue {}
+pub enum ScrollbarColorStyleValue<'a> {}
 
 // https://drafts.csswg.org/css-scrollbars-1/#scrollbar-width
 #[value(" auto | thin | none ")]
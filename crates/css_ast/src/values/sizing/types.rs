@@ -4,7 +4,7 @@ use css_parse::{CursorSink, Parse, Parser, Peek, Result as ParserResult, ToCurso
 pub use crate::units::*;
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 pub struct CalcSize;
 
 impl<'a> Peek<'a> for CalcSize {
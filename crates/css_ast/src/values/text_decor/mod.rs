@@ -36,7 +36,7 @@ pub enum TextDecorationStyleStyleValue {}
 #[percentages("n/a")]
 #[canonical_order("per grammar")]
 #[animation_type("by computed value type")]
-pub struct TextDecorationColorStyleValue;
+pub struct TextDecorationColorStyleValue<'a>;
 
 // // https://drafts.csswg.org/css-text-decor-4/#text-decoration
 // #[value(" <'text-decoration-line'> || <'text-decoration-thickness'> || <'text-decoration-style'> || <'text-decoration-color'> ")]
@@ -76,7 +76,7 @@ pub struct TextDecorationColorStyleValue;
 #[percentages("n/a")]
 #[canonical_order("per grammar")]
 #[animation_type("by computed value type")]
-pub struct TextEmphasisColorStyleValue;
+pub struct TextEmphasisColorStyleValue<'a>;
 
 // // https://drafts.csswg.org/css-text-decor-4/#text-emphasis
 // #[value(" <'text-emphasis-style'> || <'text-emphasis-color'> ")]
@@ -88,7 +88,7 @@ pub enum ResizeStyleValue {}
 #[percentages("n/a")]
 #[canonical_order("per grammar")]
 #[animation_type("by computed value")]
-pub enum CaretColorStyleValue {}
+pub enum CaretColorStyleValue<'a> {}
 
 // https://drafts.csswg.org/css-ui-4/#caret-animation
 #[value(" auto | manual ")]
@@ -118,7 +118,7 @@ pub enum CaretShapeStyleValue {}
 #[percentages("n/a")]
 #[canonical_order("per grammar")]
 #[animation_type("see individual properties")]
-pub struct CaretStyleValue;
+pub struct CaretStyleValue<'a>;
 
 // // https://drafts.csswg.org/css-ui-4/#nav-up
 // #[value(" auto | <id> [ current | root | <target-name> ]? ")]
@@ -188,7 +188,7 @@ pub enum PointerEventsStyleValue {}
 #[percentages("n/a")]
 #[canonical_order("per grammar")]
 #[animation_type("by computed value type")]
-pub enum AccentColorStyleValue {}
+pub enum AccentColorStyleValue<'a> {}
 
 // https://drafts.csswg.org/css-ui-4/#appearance
 #[value(" none | auto | base | <compat-auto> | <compat-special> ")]
@@ -0,0 +1,32 @@
+use bumpalo::Bump;
+use css_ast::{JsonDocument, StyleSheet};
+use css_parse::{CursorFmtSink, Parser, ToCursors};
+use glob::glob;
+use std::fs::read_to_string;
+
+fn to_css(node: &StyleSheet, source_text: &str) -> String {
+	let mut css = String::new();
+	node.to_cursors(&mut CursorFmtSink::new(source_text, &mut css));
+	css
+}
+
+#[test]
+fn popular() {
+	for entry in glob("../../tasks/coverage/popular/*.css").unwrap() {
+		let path = entry.unwrap();
+		let source_text = read_to_string(&path).unwrap();
+		let bump = Bump::default();
+		let stylesheet = Parser::new(&bump, &source_text).parse_entirely::<StyleSheet>().output.unwrap();
+		let json = serde_json::to_string(&JsonDocument::new(&source_text, &stylesheet)).unwrap();
+		let document = match JsonDocument::<StyleSheet>::from_str(&bump, &json) {
+			Ok(document) => document,
+			Err(err) => panic!("\n\nReading the JSON of {path:?} failed: {err}"),
+		};
+		assert_eq!(
+			to_css(&document.node, document.source),
+			to_css(&stylesheet, &source_text),
+			"\n\nThe JSON of {path:?} did not write out the same CSS"
+		);
+		assert!(document.node == stylesheet, "\n\nThe JSON of {path:?} did not read back the same AST");
+	}
+}
@@ -0,0 +1,127 @@
+use crate::{SourceOffset, Span};
+use std::borrow::Cow;
+
+pub use encoding_rs::Encoding;
+use encoding_rs::{UTF_16BE, UTF_16LE, UTF_8};
+
+/// The bytes a stylesheet must start with for its `@charset` rule to be used when decoding it.
+const CHARSET_PREFIX: &[u8] = b"@charset \"";
+
+/// How far into the bytes to look for the end of the `@charset` label.
+const CHARSET_SNIFF_LEN: usize = 1024;
+
+/// Where the [Encoding] used by [decode()] came from.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum EncodingSource {
+	/// The bytes began with a byte order mark. This takes precedence over everything else.
+	Bom,
+	/// The bytes began with a `@charset` rule naming a known encoding.
+	Charset,
+	/// The environment encoding passed to [decode()], for example from the referring document or a command line flag.
+	Environment,
+	/// None of the above applied, so the bytes were decoded as utf-8.
+	Default,
+}
+
+/// The result of [decode()]; the decoded text, along with facts about how it was decoded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Decoded<'a> {
+	/// The decoded text, with any byte order mark removed. This is borrowed from the bytes where possible (i.e. when
+	/// they were already valid utf-8).
+	pub text: Cow<'a, str>,
+	/// The encoding used to decode the bytes.
+	pub encoding: &'static Encoding,
+	/// Where [Decoded::encoding] came from.
+	pub source: EncodingSource,
+	/// The encoding declared by a `@charset` rule at the start of the bytes, if there was one. This is set even if the
+	/// encoding wasn't used (for example because there was also a byte order mark).
+	pub declared: Option<&'static Encoding>,
+	/// The [Span] of the `@charset` rule within [Decoded::text], if there was one.
+	pub charset_span: Option<Span>,
+	/// Whether any bytes were malformed for [Decoded::encoding], and so were replaced with U+FFFD.
+	pub had_errors: bool,
+}
+
+impl Decoded<'_> {
+	/// Returns true if a `@charset` rule declared an encoding which disagrees with the bytes; either because a different
+	/// encoding was used to decode them (e.g. a byte order mark overrode it) or because the bytes were not valid in the
+	/// declared encoding.
+	pub fn charset_mismatch(&self) -> bool {
+		self.declared.is_some_and(|declared| declared != self.encoding || self.had_errors)
+	}
+}
+
+/// Returns the [Encoding] a `@charset` label refers to, if any. Per the specification, labels referring to utf-16 are
+/// treated as utf-8, as a stylesheet which could be sniffed for `@charset` cannot be utf-16.
+///
+/// ```
+/// use css_lexer::*;
+/// assert_eq!(charset_encoding("ISO-8859-1").map(|e| e.name()), Some("windows-1252"));
+/// assert_eq!(charset_encoding("utf-16le").map(|e| e.name()), Some("UTF-8"));
+/// assert_eq!(charset_encoding("klingon"), None);
+/// ```
+pub fn charset_encoding(label: &str) -> Option<&'static Encoding> {
+	Encoding::for_label(label.as_bytes()).map(|e| if e == UTF_16BE || e == UTF_16LE { UTF_8 } else { e })
+}
+
+// https://drafts.csswg.org/css-syntax-3/#determine-the-fallback-encoding
+fn sniff_charset(bytes: &[u8]) -> Option<(&[u8], usize)> {
+	let rest = bytes.strip_prefix(CHARSET_PREFIX)?;
+	let sniffed = &rest[..rest.len().min(CHARSET_SNIFF_LEN - CHARSET_PREFIX.len())];
+	let end = sniffed.iter().position(|b| *b == b'"')?;
+	if sniffed.get(end + 1) != Some(&b';') {
+		return None;
+	}
+	Some((&rest[..end], CHARSET_PREFIX.len() + end + 2))
+}
+
+/// Decodes a stylesheet's bytes into text, following the [CSS Syntax specification][1]:
+///
+/// - If the bytes begin with a byte order mark, it determines the encoding.
+/// - Otherwise, if the bytes begin with a `@charset` rule naming a known encoding, that encoding is used.
+/// - Otherwise, `environment` is used if given (for example the encoding of the referring document, or a user's
+///   preference).
+/// - Otherwise the bytes are decoded as utf-8.
+///
+/// Bytes which are malformed for the chosen encoding are replaced with U+FFFD, so decoding never fails. Whether that
+/// happened is recorded in [Decoded::had_errors], and [Decoded::charset_mismatch()] can be used to warn when a
+/// `@charset` rule disagrees with the bytes.
+///
+/// The resulting [Decoded::text] can be given to a [Lexer][crate::Lexer].
+///
+/// # Example
+///
+/// ```
+/// use css_lexer::*;
+/// let decoded = decode(b"@charset \"latin1\";a{content:'\xe9'}", None);
+/// assert_eq!(decoded.encoding.name(), "windows-1252");
+/// assert_eq!(decoded.source, EncodingSource::Charset);
+/// assert_eq!(decoded.text, "@charset \"latin1\";a{content:'é'}");
+/// assert!(!decoded.charset_mismatch());
+/// ```
+///
+/// [1]: https://drafts.csswg.org/css-syntax-3/#input-byte-stream
+pub fn decode<'a>(bytes: &'a [u8], environment: Option<&'static Encoding>) -> Decoded<'a> {
+	let (bom, bom_len) = match Encoding::for_bom(bytes) {
+		Some((encoding, len)) => (Some(encoding), len),
+		None => (None, 0),
+	};
+	let charset = sniff_charset(&bytes[bom_len..]);
+	let declared = charset.and_then(|(label, _)| std::str::from_utf8(label).ok()).and_then(charset_encoding);
+	let (encoding, source) = if let Some(bom) = bom {
+		(bom, EncodingSource::Bom)
+	} else if let Some(declared) = declared {
+		(declared, EncodingSource::Charset)
+	} else if let Some(environment) = environment {
+		(environment, EncodingSource::Environment)
+	} else {
+		(UTF_8, EncodingSource::Default)
+	};
+	let (text, had_errors) = encoding.decode_without_bom_handling(&bytes[bom_len..]);
+	// The sniffed `@charset` rule is ASCII, so it has the same length in the decoded text, so long as the encoding is
+	// ASCII compatible (if it isn't then the text won't start with `@charset` either).
+	let charset_span = charset
+		.filter(|_| text.starts_with("@charset"))
+		.map(|(_, len)| Span::new(SourceOffset(0), SourceOffset(len as u32)));
+	Decoded { text, encoding, source, declared, charset_span, had_errors }
+}
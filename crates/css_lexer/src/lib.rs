@@ -42,6 +42,12 @@
 //! Repeatedly calling [Lexer::advance()] will move the Lexer's internal position one [Token] forward, and return the
 //! newly lexed [Token], once the end of `&str` is reached [Lexer::advance()] will repeatedly return [Token::EOF].
 //!
+//! For very large documents, or sources which arrive incrementally (such as stdin), a [StreamLexer] can be used to
+//! lex from any [Read][std::io::Read] without first reading the whole source into memory.
+//!
+//! Sources which may not be utf-8 can be decoded into a `&str` with `decode()`, which sniffs the encoding from a byte
+//! order mark or `@charset` rule, per the CSS specification. This requires the `encoding` feature.
+//!
 //! # Example
 //!
 //! ```
@@ -77,6 +83,8 @@ mod comment_style;
 mod constants;
 mod cursor;
 mod dimension_unit;
+#[cfg(feature = "encoding")]
+mod encoding;
 mod feature;
 mod kind;
 mod kindset;
@@ -85,6 +93,7 @@ mod private;
 mod quote_style;
 mod source_offset;
 mod span;
+mod stream;
 mod syntax;
 mod token;
 mod whitespace_style;
@@ -92,6 +101,8 @@ mod whitespace_style;
 pub use comment_style::CommentStyle;
 pub use cursor::Cursor;
 pub use dimension_unit::DimensionUnit;
+#[cfg(feature = "encoding")]
+pub use encoding::{charset_encoding, decode, Decoded, Encoding, EncodingSource};
 pub use feature::Feature;
 pub use kind::Kind;
 pub use kindset::KindSet;
@@ -99,6 +110,7 @@ pub use pairwise::PairWise;
 pub use quote_style::QuoteStyle;
 pub use source_offset::SourceOffset;
 pub use span::{Span, SpanContents, Spanned};
+pub use stream::StreamLexer;
 pub use token::Token;
 pub use whitespace_style::Whitespace;
 
@@ -0,0 +1,154 @@
+use crate::{Cursor, Feature, Kind, Lexer, SourceOffset, Token};
+use std::io::{self, Read};
+
+/// How many bytes are read from the underlying reader at a time.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// How many bytes past the end of a token must be available before that token can be emitted. The tokenizer looks at
+/// most a handful of code points ahead of the current token to decide what the token is (for example to tell `1e` from
+/// `1e+5`, or `url(` from `url( "`), so a token which ends close to the end of the buffered text might change once more
+/// text has been read. 32 bytes comfortably covers that lookahead for the widest (4 byte) code points.
+const LOOKAHEAD: usize = 32;
+
+/// A [Lexer] which consumes its source incrementally from a [Read], rather than requiring the whole document as a
+/// `&str` up front. This is useful for very large documents, or for sources such as stdin, where holding both the
+/// bytes and the tokens in memory at once would be wasteful.
+///
+/// [StreamLexer] produces exactly the same [Tokens][Token] as a [Lexer] given the same text, and its
+/// [SourceOffsets][SourceOffset] are absolute (offsets from the start of the stream, not the start of the current
+/// chunk), so [Cursors][Cursor] produced by a [StreamLexer] are interchangeable with those from a [Lexer]. Tokens which
+/// span chunk boundaries (including multi-byte utf-8 code points split across reads) are handled by buffering more of
+/// the stream before emitting them.
+///
+/// Only text which has not yet been tokenized, plus the most recent token, is kept in memory. The text of the most
+/// recent token can be retrieved with [StreamLexer::str_slice()] until [StreamLexer::advance()] is next called.
+///
+/// The source must be utf-8. If the reader produces invalid utf-8, [StreamLexer::advance()] will return an
+/// [io::Error] of [io::ErrorKind::InvalidData].
+///
+/// # Example
+///
+/// ```
+/// use css_lexer::*;
+/// let mut lexer = StreamLexer::new("width: 1px".as_bytes());
+/// let cursor = lexer.advance().unwrap();
+/// assert_eq!(cursor, Kind::Ident);
+/// assert_eq!(lexer.str_slice(cursor), Some("width"));
+/// assert_eq!(lexer.advance().unwrap(), Kind::Colon);
+/// assert_eq!(lexer.advance().unwrap(), Kind::Whitespace);
+/// let cursor = lexer.advance().unwrap();
+/// assert_eq!(cursor, Kind::Dimension);
+/// assert_eq!(cursor.offset(), 7);
+/// assert_eq!(lexer.advance().unwrap(), Kind::Eof);
+/// ```
+#[derive(Debug)]
+pub struct StreamLexer<R: Read> {
+	reader: R,
+	features: Feature,
+	// Text read from the reader which starts at `buffer_offset`.
+	buffer: String,
+	buffer_offset: SourceOffset,
+	// Bytes of a code point which has been split across reads.
+	pending: Vec<u8>,
+	chunk: Vec<u8>,
+	// The position within `buffer` of the next token.
+	position: usize,
+	exhausted: bool,
+}
+
+impl<R: Read> StreamLexer<R> {
+	#[inline]
+	pub fn new(reader: R) -> Self {
+		Self::new_with_features(reader, Feature::default())
+	}
+
+	#[inline]
+	pub fn new_with_features(reader: R, features: Feature) -> Self {
+		Self {
+			reader,
+			features,
+			buffer: String::new(),
+			buffer_offset: SourceOffset(0),
+			pending: Vec::new(),
+			chunk: Vec::new(),
+			position: 0,
+			exhausted: false,
+		}
+	}
+
+	/// Current position in the stream
+	#[inline]
+	pub fn offset(&self) -> SourceOffset {
+		SourceOffset(self.buffer_offset.0 + self.position as u32)
+	}
+
+	/// Returns the text of the given [Cursor], if it is still buffered. The most recent [Cursor] returned from
+	/// [StreamLexer::advance()] is always buffered.
+	pub fn str_slice(&self, cursor: Cursor) -> Option<&str> {
+		let start = cursor.offset().0.checked_sub(self.buffer_offset.0)? as usize;
+		self.buffer.get(start..start + cursor.len() as usize)
+	}
+
+	/// Moves the lexer one token forward, returning a [Cursor] of that token. Once the end of the stream is reached this
+	/// will repeatedly return a [Cursor] of [Token::EOF].
+	pub fn advance(&mut self) -> io::Result<Cursor> {
+		self.compact();
+		loop {
+			let token = self.read_next_token();
+			let end = self.position + token.len() as usize;
+			if self.exhausted || (token != Kind::Eof && end + LOOKAHEAD <= self.buffer.len()) {
+				let cursor = Cursor::new(self.offset(), token);
+				self.position = end;
+				return Ok(cursor);
+			}
+			self.fill()?;
+		}
+	}
+
+	fn read_next_token(&self) -> Token {
+		Lexer::new_with_features(&self.buffer[self.position..], self.features).advance()
+	}
+
+	// Drop text which has already been tokenized, once it is a sizeable portion of the buffer, so that memory use stays
+	// proportional to the chunk size rather than the size of the stream.
+	fn compact(&mut self) {
+		if self.position > 0 && self.position >= self.buffer.len() / 2 {
+			self.buffer.drain(..self.position);
+			self.buffer_offset.0 += self.position as u32;
+			self.position = 0;
+		}
+	}
+
+	fn fill(&mut self) -> io::Result<()> {
+		self.chunk.resize(CHUNK_SIZE, 0);
+		let read = loop {
+			match self.reader.read(&mut self.chunk) {
+				Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+				result => break result?,
+			}
+		};
+		if read == 0 {
+			self.exhausted = true;
+			if !self.pending.is_empty() {
+				return Err(io::Error::new(io::ErrorKind::InvalidData, "stream ended within a utf-8 code point"));
+			}
+			return Ok(());
+		}
+		self.pending.extend_from_slice(&self.chunk[..read]);
+		let str = match std::str::from_utf8(&self.pending) {
+			Ok(str) => str,
+			// The chunk ended part way through a code point, keep the remaining bytes for the next read.
+			Err(e) if e.error_len().is_none() => std::str::from_utf8(&self.pending[..e.valid_up_to()]).unwrap(),
+			Err(e) => return Err(io::Error::new(io::ErrorKind::InvalidData, e)),
+		};
+		let valid = str.len();
+		self.buffer.push_str(str);
+		self.pending.drain(..valid);
+		Ok(())
+	}
+}
+
+#[test]
+fn size_test() {
+	assert_eq!(::std::mem::size_of::<StreamLexer<&[u8]>>(), 104);
+}
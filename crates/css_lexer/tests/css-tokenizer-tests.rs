@@ -1,11 +1,15 @@
 use bumpalo::Bump;
 use console::Style;
-use css_lexer::{Cursor, Kind, Lexer};
+use css_lexer::{Cursor, Kind, Lexer, StreamLexer};
 use glob::glob;
 use serde::{Deserialize, Serialize};
 use serde_json::{from_str, to_string_pretty};
 use similar::{ChangeTag, TextDiff};
-use std::{fs::read_to_string, path::PathBuf};
+use std::{
+	fs::read_to_string,
+	io::{self, Read},
+	path::PathBuf,
+};
 
 const FIXTURES_GLOB: &str = "../../tasks/coverage/css-tokenizer-tests/tests/**/source.css";
 
@@ -253,3 +257,55 @@ fn full_suite() {
 	}
 	assert_eq!(fails, 0, "Should have zero failures but {} tests failed", fails);
 }
+
+// A reader which returns at most `chunk_size` bytes per read, so that tokens (and code points) are split across reads.
+struct ChunkedReader<'a> {
+	source: &'a [u8],
+	chunk_size: usize,
+}
+
+impl Read for ChunkedReader<'_> {
+	fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+		let len = self.chunk_size.min(buf.len()).min(self.source.len());
+		buf[..len].copy_from_slice(&self.source[..len]);
+		self.source = &self.source[len..];
+		Ok(len)
+	}
+}
+
+fn stream_case(case: &CSSTokenizerTestCase, chunk_size: usize) -> u8 {
+	let mut lexer = Lexer::new(&case.source_text);
+	let mut stream = StreamLexer::new(ChunkedReader { source: case.source_text.as_bytes(), chunk_size });
+	loop {
+		let offset = lexer.offset();
+		let expected = lexer.advance().with_cursor(offset);
+		let actual = stream.advance().unwrap();
+		if expected != actual || expected.token() != actual.token() {
+			println!("{} {} (chunk size {})", Style::new().red().apply_to("✘ FAILED"), case.name, chunk_size);
+			println!("{}", Style::new().red().apply_to(format!("- {:?}", actual)));
+			println!("{}", Style::new().green().apply_to(format!("+ {:?}", expected)));
+			return 1;
+		}
+		assert_eq!(stream.str_slice(actual), Some(expected.str_slice(&case.source_text)));
+		if expected == Kind::Eof {
+			return 0;
+		}
+	}
+}
+
+#[test]
+fn stream_parity() {
+	let mut fails = 0;
+	for case in get_tests() {
+		for chunk_size in [1, 2, 3, 7, 64] {
+			fails += stream_case(&case, chunk_size);
+		}
+	}
+	// The tokenizer tests are all small, so also check some larger real-world files span many chunks.
+	for path in glob("../../tasks/coverage/popular/*.css").unwrap().flatten() {
+		let name = path.file_name().unwrap().to_str().unwrap().to_owned();
+		let case = CSSTokenizerTestCase { name, source_text: read_to_string(&path).unwrap(), desired: vec![] };
+		fails += stream_case(&case, 4093);
+	}
+	assert_eq!(fails, 0, "Should have zero failures but {} tests failed", fails);
+}
@@ -0,0 +1,117 @@
+#![cfg(feature = "encoding")]
+use css_lexer::{decode, Encoding, EncodingSource, Kind, Lexer, SourceOffset, Span};
+
+#[test]
+fn decodes_utf8_by_default() {
+	let decoded = decode("a{content:'é'}".as_bytes(), None);
+	assert_eq!(decoded.encoding.name(), "UTF-8");
+	assert_eq!(decoded.source, EncodingSource::Default);
+	assert_eq!(decoded.text, "a{content:'é'}");
+	assert_eq!(decoded.declared, None);
+	assert!(!decoded.had_errors);
+	assert!(!decoded.charset_mismatch());
+}
+
+#[test]
+fn replaces_invalid_utf8() {
+	let decoded = decode(b"a{content:'\xff'}", None);
+	assert_eq!(decoded.text, "a{content:'\u{FFFD}'}");
+	assert!(decoded.had_errors);
+	// Without a @charset there's nothing to disagree with.
+	assert!(!decoded.charset_mismatch());
+}
+
+#[test]
+fn sniffs_bom() {
+	let decoded = decode(b"\xEF\xBB\xBFa{}", None);
+	assert_eq!(decoded.encoding.name(), "UTF-8");
+	assert_eq!(decoded.source, EncodingSource::Bom);
+	assert_eq!(decoded.text, "a{}");
+
+	let decoded = decode(b"\xFF\xFEa\0{\0}\0", Encoding::for_label(b"latin1"));
+	assert_eq!(decoded.encoding.name(), "UTF-16LE");
+	assert_eq!(decoded.source, EncodingSource::Bom);
+	assert_eq!(decoded.text, "a{}");
+
+	let decoded = decode(b"\xFE\xFF\0a\0{\0}", None);
+	assert_eq!(decoded.encoding.name(), "UTF-16BE");
+	assert_eq!(decoded.text, "a{}");
+}
+
+#[test]
+fn sniffs_charset() {
+	let decoded = decode(b"@charset \"windows-1251\";a{content:'\xc6'}", None);
+	assert_eq!(decoded.encoding.name(), "windows-1251");
+	assert_eq!(decoded.source, EncodingSource::Charset);
+	assert_eq!(decoded.text, "@charset \"windows-1251\";a{content:'Ж'}");
+	assert_eq!(decoded.charset_span, Some(Span::new(SourceOffset(0), SourceOffset(24))));
+	assert!(!decoded.charset_mismatch());
+}
+
+#[test]
+fn sniffs_charset_strictly() {
+	// The @charset must be exactly `@charset "<label>";`, anything else is ignored.
+	for bytes in [&b"@charset 'latin1';"[..], b"@CHARSET \"latin1\";", b"@charset  \"latin1\";", b"@charset \"latin1\""]
+	{
+		let decoded = decode(bytes, None);
+		assert_eq!(decoded.source, EncodingSource::Default);
+		assert_eq!(decoded.declared, None);
+		assert_eq!(decoded.charset_span, None);
+	}
+	// Unknown labels fall back to the environment encoding.
+	let decoded = decode(b"@charset \"klingon\";", Encoding::for_label(b"latin1"));
+	assert_eq!(decoded.encoding.name(), "windows-1252");
+	assert_eq!(decoded.source, EncodingSource::Environment);
+	// UTF-16 labels mean utf-8, as the bytes can't be utf-16 if they were sniffed.
+	let decoded = decode(b"@charset \"utf-16\";", None);
+	assert_eq!(decoded.encoding.name(), "UTF-8");
+	assert_eq!(decoded.source, EncodingSource::Charset);
+}
+
+#[test]
+fn bom_overrides_charset() {
+	let decoded = decode(b"\xEF\xBB\xBF@charset \"latin1\";a{}", None);
+	assert_eq!(decoded.encoding.name(), "UTF-8");
+	assert_eq!(decoded.source, EncodingSource::Bom);
+	assert_eq!(decoded.declared.map(|e| e.name()), Some("windows-1252"));
+	assert_eq!(decoded.charset_span, Some(Span::new(SourceOffset(0), SourceOffset(18))));
+	assert!(decoded.charset_mismatch());
+}
+
+#[test]
+fn charset_overrides_environment() {
+	let decoded = decode(b"@charset \"utf-8\";a{}", Encoding::for_label(b"latin1"));
+	assert_eq!(decoded.encoding.name(), "UTF-8");
+	assert_eq!(decoded.source, EncodingSource::Charset);
+	let decoded = decode(b"a{content:'\xe9'}", Encoding::for_label(b"latin1"));
+	assert_eq!(decoded.source, EncodingSource::Environment);
+	assert_eq!(decoded.text, "a{content:'é'}");
+}
+
+#[test]
+fn detects_mismatched_charset() {
+	// Declared as utf-8, but the bytes are latin1.
+	let decoded = decode(b"@charset \"utf-8\";a{content:'\xe9'}", None);
+	assert!(decoded.had_errors);
+	assert!(decoded.charset_mismatch());
+}
+
+#[test]
+fn detects_mismatched_charset_without_span() {
+	// iso-2022-kr is a label for the replacement encoding, which decodes everything (including the @charset) to U+FFFD.
+	let decoded = decode(b"@charset \"iso-2022-kr\";a{}", None);
+	assert_eq!(decoded.encoding.name(), "replacement");
+	assert_eq!(decoded.text, "\u{FFFD}");
+	assert!(decoded.charset_mismatch());
+	assert_eq!(decoded.charset_span, None);
+}
+
+#[test]
+fn lexes_decoded_text() {
+	let decoded = decode(b"\xFF\xFEa\0{\0}\0", None);
+	let mut lexer = Lexer::new(&decoded.text);
+	assert_eq!(lexer.advance(), Kind::Ident);
+	assert_eq!(lexer.advance(), Kind::LeftCurly);
+	assert_eq!(lexer.advance(), Kind::RightCurly);
+	assert_eq!(lexer.advance(), Kind::Eof);
+}
@@ -1,5 +1,6 @@
 use bumpalo::Bump;
-use css_lexer::{CommentStyle, DimensionUnit, Feature, Kind, Lexer, QuoteStyle, SourceOffset};
+use css_lexer::{CommentStyle, DimensionUnit, Feature, Kind, Lexer, QuoteStyle, SourceOffset, StreamLexer};
+use std::io::{ErrorKind, Read, Result};
 
 #[test]
 fn tokenizes_empty() {
@@ -1170,3 +1171,43 @@ fn tokenizes_weird_url_function_names() {
 	assert_eq!(lexer.advance(), Kind::RightParen);
 	assert_eq!(lexer.advance(), Kind::Eof);
 }
+
+// Reads one byte at a time, so every token spans a chunk boundary.
+struct ByteReader<'a>(&'a [u8]);
+
+impl Read for ByteReader<'_> {
+	fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+		let Some((first, rest)) = self.0.split_first() else { return Ok(0) };
+		buf[0] = *first;
+		self.0 = rest;
+		Ok(1)
+	}
+}
+
+#[test]
+fn stream_tokenizes_across_chunks() {
+	let source = "a{width:1e+5px}/* é */url(  'a')url( b )\r\n-->é1.5%\\66 oo// end";
+	let mut lexer = Lexer::new_with_features(source, Feature::SingleLineComments);
+	let mut stream = StreamLexer::new_with_features(ByteReader(source.as_bytes()), Feature::SingleLineComments);
+	loop {
+		let offset = lexer.offset();
+		let expected = lexer.advance().with_cursor(offset);
+		let actual = stream.advance().unwrap();
+		assert_eq!(actual, expected);
+		assert_eq!(actual.token(), expected.token());
+		assert_eq!(stream.str_slice(actual), Some(expected.str_slice(source)));
+		assert_eq!(stream.offset(), lexer.offset());
+		if expected == Kind::Eof {
+			break;
+		}
+	}
+	assert_eq!(stream.advance().unwrap(), Kind::Eof);
+}
+
+#[test]
+fn stream_errors_on_invalid_utf8() {
+	let mut stream = StreamLexer::new(ByteReader(b"a{b:c\xff}"));
+	assert_eq!(stream.advance().unwrap_err().kind(), ErrorKind::InvalidData);
+	let mut stream = StreamLexer::new(ByteReader(&"é".as_bytes()[..1]));
+	assert_eq!(stream.advance().unwrap_err().kind(), ErrorKind::InvalidData);
+}
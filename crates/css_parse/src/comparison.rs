@@ -16,7 +16,7 @@ use crate::{diagnostics, Parse, Parser, Result, ToCursors, T};
 /// ```
 ///
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type"))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, crate::FromJson), serde(tag = "type"))]
 pub enum Comparison {
 	LessThan(T![<]),
 	GreaterThan(T![>]),
@@ -2,13 +2,17 @@ use core::fmt;
 
 use css_lexer::{Cursor, Token};
 
-use crate::CursorSink;
+use crate::{CursorSink, NodeBuilder};
 
 /// This is a [CursorSink] that wraps a Writer (`impl fmt::Write`) and on each [CursorSink::append()] call, will write
 /// the contents of the cursor [Cursor] given into the given Writer - using the given `&'a str` as the original source.
 /// This is useful as way to turn Cursors into Strings or [u8]s (or files or whatever else implements [fmt::Write]).
+///
+/// If the nodes include any made with a [NodeBuilder], then the sink will also need the builder, see
+/// [CursorFmtSink::with_builder()].
 pub struct CursorFmtSink<'a, T: fmt::Write> {
 	source_text: &'a str,
+	builder: Option<&'a NodeBuilder<'a>>,
 	writer: T,
 	last_token: Option<Token>,
 	err: Option<fmt::Error>,
@@ -16,7 +20,13 @@ pub struct CursorFmtSink<'a, T: fmt::Write> {
 
 impl<'a, T: fmt::Write> CursorFmtSink<'a, T> {
 	pub fn new(source_text: &'a str, writer: T) -> Self {
-		Self { source_text, writer, last_token: None, err: None }
+		Self { source_text, builder: None, writer, last_token: None, err: None }
+	}
+
+	/// Write the cursors of nodes made by the given [NodeBuilder] using its text, rather than the source text.
+	pub fn with_builder(mut self, builder: &'a NodeBuilder<'a>) -> Self {
+		self.builder = Some(builder);
+		self
 	}
 }
 
@@ -36,7 +46,11 @@ impl<'a, T: fmt::Write> CursorSink for CursorFmtSink<'a, T> {
 		if self.err.is_some() {
 			return;
 		}
-		if let Err(err) = c.write_str(self.source_text, &mut self.writer) {
+		let result = match self.builder {
+			Some(builder) if builder.contains(c) => builder.write_str(c, &mut self.writer),
+			_ => c.write_str(self.source_text, &mut self.writer),
+		};
+		if let Err(err) = result {
 			self.err = Some(err);
 		}
 	}
@@ -34,3 +34,11 @@ impl CursorSink for CursorVecSink<'_> {
 		self.cursors.push(c);
 	}
 }
+
+/// The simplest [CursorSink]: each [Cursor] is pushed onto the [Vec][std::vec::Vec] as it is given, without any
+/// separators. Useful for inspecting the tokens of a node, such as to find the [Span][css_lexer::Span] it covers.
+impl CursorSink for std::vec::Vec<Cursor> {
+	fn append(&mut self, c: Cursor) {
+		self.push(c);
+	}
+}
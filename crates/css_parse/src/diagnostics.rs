@@ -374,3 +374,35 @@ pub struct DisallowedLayerBlockWithMultipleNames(#[label("Remove most (or all) o
 #[error("!important cannot be used for this property")]
 #[diagnostic(help(""), code(css_parse::DisallowedImportant))]
 pub struct DisallowedImportant(#[label("Remove this.")] pub Span);
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("The @charset declares an encoding of {0}, which doesn't match the contents of the stylesheet")]
+#[diagnostic(
+	severity(Warning),
+	help("Either the @charset is wrong, or the file was saved with a different encoding (or a byte order mark), so non-ASCII characters may be garbled."),
+	code(css_parse::CharsetMismatch)
+)]
+// The span is missing when the declared encoding decodes the @charset rule itself into something else, as the
+// replacement encoding does.
+pub struct CharsetMismatch(pub String, #[label("This @charset")] pub Option<Span>);
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("The input is nested more than {0} levels deep, so parsing stopped.")]
+#[diagnostic(
+	help("Reduce the number of nested blocks and functions, or raise the parser's max_depth limit."),
+	code(css_parse::MaxDepthExceeded)
+)]
+pub struct MaxDepthExceeded(pub u16, #[label("This is nested too deeply")] pub Span);
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("The input has more than {0} tokens, so parsing stopped.")]
+#[diagnostic(help("Split the input up, or raise the parser's max_tokens limit."), code(css_parse::MaxTokensExceeded))]
+pub struct MaxTokensExceeded(pub u32, #[label("Parsing stopped here")] pub Span);
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("Parsing the input allocated more than {0} bytes, so parsing stopped.")]
+#[diagnostic(
+	help("Split the input up, or raise the parser's max_allocated_bytes limit."),
+	code(css_parse::MaxAllocationExceeded)
+)]
+pub struct MaxAllocationExceeded(pub usize, #[label("Parsing stopped here")] pub Span);
@@ -3,6 +3,9 @@ use bitmask_enum::bitmask;
 /// A set of runtime feature flags which can be enabled individually or in combination, which will change the way
 /// [Parser][crate::Parser] works.
 ///
+/// Only [SingleLineComments][Feature::SingleLineComments] & [SeparateWhitespace][Feature::SeparateWhitespace] are
+/// forwarded to the [Lexer][crate::Lexer]; the other flags only change how the [Parser][crate::Parser] works.
+///
 /// To build multiple features, use the bitwise OR operator.
 ///
 /// # Example
@@ -32,6 +35,33 @@ pub enum Feature {
 	/// should be cognizant that this feature could be enabled, meaning that adjacent whitespace tokens are possible. To
 	/// counter adjacent tokens, simply parse any whitespace in a loop.
 	SeparateWhitespace,
+
+	/// When enabled, declarations whose name is prefixed with a `*`, such as `*zoom: 1` (a hack which targets IE7 and
+	/// below), are parsed as declarations rather than failing. Declarations prefixed with `_` (the IE6 hack) are already
+	/// valid identifiers, but with this enabled their values are parsed as though the `_` were not there.
+	PropertyHacks,
+
+	/// When enabled, IE `filter` values such as `progid:DXImageTransform.Microsoft.Alpha(Opacity=80)` are parsed into
+	/// dedicated nodes, rather than an unknown value.
+	ProgIdFilters,
+
+	/// When enabled, IE's dynamic property values, such as `width: expression(document.body.clientWidth)`, are parsed
+	/// into dedicated nodes, rather than an unknown value.
+	Expressions,
+
+	/// When enabled, syntax from [CSS Modules](https://github.com/css-modules/css-modules) is allowed: the `composes`
+	/// declaration, and the `:global`/`:local` pseudo classes (in both their bare and functional forms).
+	CSSModules,
+
+	/// When enabled, `@custom-media` rules and references to them within media queries (`@media (--narrow)`) are
+	/// parsed, as proposed in [Media Queries Level 5](https://drafts.csswg.org/mediaqueries-5/#custom-mq) and commonly
+	/// implemented by PostCSS.
+	CustomMedia,
+
+	/// When enabled, `@custom-selector` rules and references to them within selectors (`:--heading`) are parsed, as
+	/// proposed in [CSS Extensions](https://drafts.csswg.org/css-extensions/#custom-selectors) and commonly implemented
+	/// by PostCSS.
+	CustomSelectors,
 }
 
 impl From<Feature> for css_lexer::Feature {
@@ -17,6 +17,12 @@
 //! enabling [Feature::SingleLineComments] will enable [the css_lexer feature of the same
 //! name][css_lexer::Feature::SingleLineComments]).
 //!
+//! When parsing untrusted input, the [Parser] should also be given [ParserLimits] with [Parser::with_limits()], so that
+//! pathological input (such as very deeply nested blocks) results in a diagnostic rather than a stack overflow.
+//!
+//! AST nodes which aren't part of the source text (for example those a transform wants to add) can be made with a
+//! [NodeBuilder], which parses them from text of their own.
+//!
 //! This crate provides some low level AST nodes that are likely to be common in any CSS-alike language, including the
 //! various base tokens (such as dimensions, and operators). These can be referred to via the [T!] macro, and each [T!]
 //! implements the necessary traits to be parsed as an AST node. For example [T![DashedIdent]][token_macros::DashedIdent]
@@ -220,6 +226,7 @@
 //!  - [syntax::CommaSeparatedComponentValues] provides a list of `<component-value>` nodes separated by commas, [per
 //!    "parse a comma-separated list of component values][21].
 //!  - [syntax::BadDeclaration] provides a struct to capture the [bad declaration steps][22].
+//!  - [syntax::BadRule] provides a struct to capture the remnants of a rule which failed to parse.
 //!
 //! [13]: https://drafts.csswg.org/css-syntax-3/#at-rule-diagram
 //! [14]: https://drafts.csswg.org/css-syntax-3/#qualified-rule-diagram
@@ -283,13 +290,19 @@
 //! assert_parse!(MyProperty, "width:1px");
 //! ```
 
+// Allows `#[derive(FromJson)]`, which refers to `::css_parse`, to be used within this crate.
+#[cfg(feature = "serde")]
+extern crate self as css_parse;
+
 mod comparison;
 mod cursor_fmt_sink;
 mod cursor_vec_sink;
 #[doc(hidden)]
 pub mod diagnostics;
 mod feature;
+mod limits;
 mod macros;
+mod node_builder;
 mod parser;
 mod parser_checkpoint;
 mod parser_return;
@@ -303,10 +316,14 @@ pub mod token_macros;
 mod traits;
 
 pub use comparison::*;
+#[cfg(feature = "serde")]
+pub use csskit_proc_macro::FromJson;
 pub use cursor_fmt_sink::*;
 pub use cursor_vec_sink::*;
 pub use feature::*;
+pub use limits::*;
 pub use miette::{Error, Result};
+pub use node_builder::*;
 pub use parser::*;
 pub use parser_checkpoint::*;
 pub use parser_return::*;
@@ -0,0 +1,102 @@
+/// Limits on how much work a [Parser][crate::Parser] will do, for parsing untrusted input.
+///
+/// AST nodes are parsed by recursive descent, so deeply nested input (for example thousands of nested blocks, or
+/// `:is(:is(:is(...)))`) would otherwise overflow the stack. Similarly pathological input can take a long time to
+/// parse or allocate a lot of memory. When any of these limits are exceeded the [Parser][crate::Parser] stops parsing,
+/// as though it had reached the end of the input, and reports a diagnostic for the exceeded limit in the
+/// [ParserReturn][crate::ParserReturn] errors. Anything parsed up until that point is kept.
+///
+/// The default is [ParserLimits::UNLIMITED], which is suitable for trusted input. [ParserLimits::UNTRUSTED] is a
+/// reasonable starting point for untrusted input.
+///
+/// # Example
+///
+/// ```
+/// use css_parse::*;
+/// use bumpalo::Bump;
+/// let bump = Bump::default();
+/// let limits = ParserLimits { max_depth: 2, ..ParserLimits::UNTRUSTED };
+/// let mut p = Parser::new(&bump, "a{b{c{}}}").with_limits(limits);
+/// let result = p.parse_entirely::<syntax::ComponentValues>();
+/// assert_eq!(result.errors.len(), 1);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ParserLimits {
+	/// The maximum number of blocks (`{}`, `()`, `[]`) and functions which can be open at once.
+	pub max_depth: u16,
+	/// The maximum number of tokens the parser will consume. Tokens which are consumed again after the parser backtracks
+	/// count again, so this bounds the total work done rather than the length of the input. Trivia (such as comments)
+	/// which the parser skips over is not counted.
+	pub max_tokens: u32,
+	/// The maximum number of bytes the parser will allow the [Bump][bumpalo::Bump] to grow by. This is checked as each
+	/// token is consumed, so the [Bump][bumpalo::Bump] may grow by a little more than this before parsing stops.
+	pub max_allocated_bytes: usize,
+}
+
+impl ParserLimits {
+	/// No limits at all.
+	pub const UNLIMITED: Self = Self { max_depth: u16::MAX, max_tokens: u32::MAX, max_allocated_bytes: usize::MAX };
+
+	/// Limits which comfortably allow for any real world stylesheet, while keeping well away from the stack size of a
+	/// spawned thread (2MiB) even in debug builds.
+	pub const UNTRUSTED: Self = Self { max_depth: 64, max_tokens: 10_000_000, max_allocated_bytes: 512 * 1024 * 1024 };
+}
+
+impl Default for ParserLimits {
+	fn default() -> Self {
+		Self::UNLIMITED
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{syntax::ComponentValues, Parser};
+	use bumpalo::Bump;
+
+	#[test]
+	fn test_max_depth() {
+		let bump = Bump::default();
+		let limits = ParserLimits { max_depth: 3, ..ParserLimits::UNLIMITED };
+		let result = Parser::new(&bump, "a{b(c[d])}").with_limits(limits).parse_entirely::<ComponentValues>();
+		assert!(result.errors.is_empty(), "{:?}", result.errors);
+		let result = Parser::new(&bump, "a{b(c[d{}])}").with_limits(limits).parse_entirely::<ComponentValues>();
+		assert_eq!(result.errors.len(), 1);
+		assert_eq!(result.errors[0].to_string(), "The input is nested more than 3 levels deep, so parsing stopped.");
+		assert!(result.output.is_some());
+	}
+
+	#[test]
+	fn test_max_depth_overflow() {
+		// Nesting like this would overflow the stack without a limit.
+		let bump = Bump::default();
+		let source_text = "(".repeat(100_000);
+		let result =
+			Parser::new(&bump, &source_text).with_limits(ParserLimits::UNTRUSTED).parse_entirely::<ComponentValues>();
+		assert_eq!(result.errors.len(), 1);
+	}
+
+	#[test]
+	fn test_max_tokens() {
+		let bump = Bump::default();
+		let limits = ParserLimits { max_tokens: 3, ..ParserLimits::UNLIMITED };
+		let result = Parser::new(&bump, "a,b").with_limits(limits).parse_entirely::<ComponentValues>();
+		assert!(result.errors.is_empty(), "{:?}", result.errors);
+		let result = Parser::new(&bump, "a,b,c").with_limits(limits).parse_entirely::<ComponentValues>();
+		assert_eq!(result.errors.len(), 1);
+		assert_eq!(result.errors[0].to_string(), "The input has more than 3 tokens, so parsing stopped.");
+	}
+
+	#[test]
+	fn test_max_allocated_bytes() {
+		let bump = Bump::default();
+		let limits = ParserLimits { max_allocated_bytes: 1024, ..ParserLimits::UNLIMITED };
+		let source_text = "a ".repeat(10_000);
+		let result = Parser::new(&bump, &source_text).with_limits(limits).parse_entirely::<ComponentValues>();
+		assert_eq!(result.errors.len(), 1);
+		assert_eq!(
+			result.errors[0].to_string(),
+			"Parsing the input allocated more than 1024 bytes, so parsing stopped."
+		);
+	}
+}
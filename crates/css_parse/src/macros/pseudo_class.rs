@@ -39,7 +39,7 @@ macro_rules! pseudo_class {
 	($(#[doc = $usage:literal])*$name: ident { $( $variant: ident: $variant_str: tt$(,)?)+ }) => {
 		$(#[doc = $usage])*
 		#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-		#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+		#[cfg_attr(feature = "serde", derive(serde::Serialize, $crate::FromJson), serde())]
 		pub enum $name {
 			$($variant($crate::T![:], $crate::T![Ident]),)+
 		}
@@ -33,7 +33,7 @@ macro_rules! pseudo_element {
 	($(#[$meta:meta])*$name: ident { $( $variant: ident: $variant_str: tt$(,)?)+ }) => {
 		$(#[$meta])*
 		#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-		#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+		#[cfg_attr(feature = "serde", derive(serde::Serialize, $crate::FromJson), serde())]
 		pub enum $name {
 			$($variant($crate::T![::], $crate::T![Ident]),)+
 		}
@@ -0,0 +1,127 @@
+use crate::{diagnostics, Feature, Parse, Parser, Result, ToCursors};
+use bumpalo::Bump;
+use css_lexer::{Cursor, SourceOffset};
+use std::fmt;
+
+/// Creates AST nodes from text that isn't part of the source text, so that (for example) a transform can add a
+/// declaration, or replace the value of one.
+///
+/// AST nodes only hold [Cursors][Cursor], which point into the source text they were parsed from, so new nodes need new
+/// text to point into. The [NodeBuilder] owns an arena of that text, which is placed after the end of the source text:
+/// each call to [NodeBuilder::parse()] appends to the arena and parses the appended text, so the cursors of the new node
+/// never overlap those of the source text. Nodes from both can be freely mixed within a tree, and written out with a
+/// [CursorFmtSink][crate::CursorFmtSink] given the builder via
+/// [CursorFmtSink::with_builder()][crate::CursorFmtSink::with_builder].
+///
+/// The [Parser] of the source text knows nothing of the arena, so it must not be asked about the cursors of nodes made
+/// by the builder.
+///
+/// # Example
+///
+/// ```
+/// use css_parse::*;
+/// use bumpalo::Bump;
+/// let bump = Bump::default();
+/// let source_text = "color:red";
+/// let mut declaration = Parser::new(&bump, source_text).parse_entirely::<syntax::Declaration>().output.unwrap();
+///
+/// let mut builder = NodeBuilder::new(&bump, source_text);
+/// declaration.value = builder.parse::<syntax::Declaration>("color:blue").unwrap().value;
+///
+/// let mut css = String::new();
+/// declaration.to_cursors(&mut CursorFmtSink::new(source_text, &mut css).with_builder(&builder));
+/// assert_eq!(css, "color:blue");
+/// ```
+#[derive(Debug)]
+pub struct NodeBuilder<'a> {
+	bump: &'a Bump,
+	features: Feature,
+	start: SourceOffset,
+	text: String,
+}
+
+impl<'a> NodeBuilder<'a> {
+	/// Create a builder for nodes which will sit alongside those parsed from the given source text.
+	pub fn new(bump: &'a Bump, source_text: &str) -> Self {
+		Self::new_with_features(bump, source_text, Feature::none())
+	}
+
+	/// Create a builder which parses with the given [Feature]s, which should be the same as those the source text was
+	/// parsed with.
+	pub fn new_with_features(bump: &'a Bump, source_text: &str, features: Feature) -> Self {
+		Self { bump, features, start: SourceOffset(source_text.len() as u32), text: String::new() }
+	}
+
+	/// Parse a new node from the given text. The text must be parsed entirely as `T`, otherwise the first error is
+	/// returned (and the arena is left as it was).
+	pub fn parse<T: Parse<'a> + ToCursors>(&mut self, text: &str) -> Result<T> {
+		let start = SourceOffset(self.start.0 + self.text.len() as u32);
+		let source_text = self.bump.alloc_str(text);
+		let mut p = Parser::new_with_features(self.bump, source_text, self.features).with_base_offset(start);
+		let result = p.parse_entirely::<T>();
+		if let Some(error) = result.errors.into_iter().next() {
+			return Err(error);
+		}
+		let Some(output) = result.output else { Err(diagnostics::UnexpectedEnd())? };
+		self.text.push_str(text);
+		Ok(output)
+	}
+
+	/// Is the given [Cursor] one of the builder's, rather than one from the source text?
+	pub fn contains(&self, c: Cursor) -> bool {
+		c.offset() >= self.start && c.offset() != SourceOffset::DUMMY
+	}
+
+	/// The text of every node the builder has parsed, in the order they were parsed.
+	pub fn text(&self) -> &str {
+		&self.text
+	}
+
+	/// Write out the given [Cursor], which must be one of the builder's.
+	pub(crate) fn write_str(&self, c: Cursor, f: &mut impl fmt::Write) -> fmt::Result {
+		debug_assert!(self.contains(c));
+		Cursor::new(SourceOffset(c.offset().0 - self.start.0), c.token()).write_str(&self.text, f)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		syntax::{BangImportant, Declaration},
+		CursorFmtSink, T,
+	};
+
+	fn to_css(node: &impl ToCursors, source_text: &str, builder: &NodeBuilder) -> String {
+		let mut css = String::new();
+		node.to_cursors(&mut CursorFmtSink::new(source_text, &mut css).with_builder(builder));
+		css
+	}
+
+	#[test]
+	fn test_mixed_cursors() {
+		let bump = Bump::default();
+		let source_text = "color:red";
+		let mut declaration = Parser::new(&bump, source_text).parse_entirely::<Declaration>().output.unwrap();
+		let mut builder = NodeBuilder::new(&bump, source_text);
+		let other = builder.parse::<Declaration>("background: blue").unwrap();
+		assert!(builder.contains(other.name.into()));
+		assert!(!builder.contains(declaration.name.into()));
+		declaration.important = Some(builder.parse::<BangImportant>("! important").unwrap());
+		assert_eq!(to_css(&declaration, source_text, &builder), "color:red!important");
+		declaration.name = builder.parse::<T![Ident]>("fill").unwrap();
+		assert_eq!(to_css(&declaration, source_text, &builder), "fill:red!important");
+		assert_eq!(builder.text(), "background: blue! importantfill");
+	}
+
+	#[test]
+	fn test_errors() {
+		let bump = Bump::default();
+		let mut builder = NodeBuilder::new(&bump, "");
+		assert!(builder.parse::<T![Ident]>("10px").is_err());
+		assert!(builder.parse::<T![Ident]>("a b").is_err());
+		assert_eq!(builder.text(), "");
+		let ident = builder.parse::<T![Ident]>("a").unwrap();
+		assert_eq!(Cursor::from(ident).offset(), 0);
+	}
+}
@@ -1,11 +1,11 @@
 use crate::{
 	diagnostics,
 	traits::{Parse, Peek},
-	Feature, ParserCheckpoint, ParserReturn, Result, ToCursors,
+	Feature, ParserCheckpoint, ParserLimits, ParserReturn, Result, ToCursors,
 };
 use bitmask_enum::bitmask;
 use bumpalo::Bump;
-use css_lexer::{Cursor, Kind, KindSet, Lexer, SourceOffset, Span};
+use css_lexer::{Cursor, Kind, KindSet, Lexer, SourceOffset, Span, Token};
 use miette::Error;
 use std::mem::take;
 
@@ -30,6 +30,24 @@ pub struct Parser<'a> {
 
 	stop: KindSet,
 
+	limits: ParserLimits,
+
+	// Where `source_text` begins within the text that cursors point into, see [Parser::with_base_offset()].
+	base: u32,
+
+	// How many blocks & functions are currently open.
+	depth: u16,
+
+	tokens: u32,
+
+	// How many bytes the bump had allocated when parsing began, so that only the parser's allocations are limited.
+	allocated_bytes: usize,
+
+	// Set once one of the `limits` has been exceeded, at which point the parser behaves as though it is at the end of
+	// the input. This deliberately survives a rewind. Alongside the error is the length of `errors` at that point, as
+	// any errors after it are only a consequence of the input being cut short.
+	exceeded: Option<(Error, usize)>,
+
 	#[cfg(debug_assertions)]
 	pub(crate) last_cursor: Option<Cursor>,
 }
@@ -39,6 +57,9 @@ pub struct Parser<'a> {
 #[derive(Default)]
 pub enum State {
 	Nested = 0b0000_0001,
+	/// Parsing the channels of a relative color, after `from <color>`, where channel keywords such as `r` or `alpha`
+	/// may be used: https://drafts.csswg.org/css-color-5/#relative-colors
+	RelativeColor = 0b0000_0010,
 }
 
 impl<'a> Parser<'a> {
@@ -57,12 +78,33 @@ impl<'a> Parser<'a> {
 			state: State::none(),
 			skip: KindSet::TRIVIA,
 			stop: KindSet::NONE,
+			limits: ParserLimits::default(),
+			base: 0,
+			depth: 0,
+			tokens: 0,
+			allocated_bytes: bump.allocated_bytes(),
+			exceeded: None,
 			bump,
 			#[cfg(debug_assertions)]
 			last_cursor: None,
 		}
 	}
 
+	/// Set the [ParserLimits] for this parser. This should be done before parsing starts.
+	pub fn with_limits(mut self, limits: ParserLimits) -> Self {
+		self.limits = limits;
+		self.allocated_bytes = self.bump.allocated_bytes();
+		self
+	}
+
+	/// Treat the source text as beginning at the given offset of some larger text, so that each [Cursor] produced by
+	/// the parser (and each [Cursor] given to it) is offset by `base`. This is how a [NodeBuilder][crate::NodeBuilder]
+	/// parses text that is appended after the original source text. This should be done before parsing starts.
+	pub fn with_base_offset(mut self, base: SourceOffset) -> Self {
+		self.base = base.0;
+		self
+	}
+
 	#[inline]
 	pub fn bump(&self) -> &'a Bump {
 		self.bump
@@ -78,6 +120,11 @@ impl<'a> Parser<'a> {
 		self.state.contains(state)
 	}
 
+	#[inline]
+	pub fn state(&self) -> State {
+		self.state
+	}
+
 	#[inline]
 	pub fn set_state(&mut self, state: State) -> State {
 		let old = self.state;
@@ -99,8 +146,23 @@ impl<'a> Parser<'a> {
 		old
 	}
 
+	/// Record an error which the parser has recovered from, so that it is reported in the [ParserReturn] without
+	/// stopping the parse.
+	pub fn push_error(&mut self, error: Error) {
+		self.errors.push(error);
+	}
+
 	pub fn parse_entirely<T: Parse<'a> + ToCursors>(&mut self) -> ParserReturn<'a, T> {
-		let output = match T::parse(self) {
+		self.parse_entirely_with(T::parse)
+	}
+
+	/// Like [Parser::parse_entirely()], but parses the node with the given function, for nodes which need more than the
+	/// parser to be parsed (for example [DeclarationValues][crate::DeclarationValue], which need a property name).
+	pub fn parse_entirely_with<T: ToCursors>(
+		&mut self,
+		parse: impl FnOnce(&mut Parser<'a>) -> Result<T>,
+	) -> ParserReturn<'a, T> {
+		let output = match parse(self) {
 			Ok(output) => Some(output),
 			Err(error) => {
 				self.errors.push(error);
@@ -120,6 +182,10 @@ impl<'a> Parser<'a> {
 			}
 			self.errors.push(diagnostics::ExpectedEnd(Span::new(start, self.offset())).into());
 		}
+		if let Some((error, errors_pos)) = self.exceeded.take() {
+			self.errors.truncate(errors_pos);
+			self.errors.push(error);
+		}
 		ParserReturn::new(output, self.source_text, take(&mut self.errors), take(&mut self.trivia))
 	}
 
@@ -153,38 +219,53 @@ impl<'a> Parser<'a> {
 
 	#[inline]
 	pub fn parse_raw_str(&self, c: Cursor) -> &'a str {
-		c.str_slice(self.lexer.source())
+		self.to_lexer_cursor(c).str_slice(self.lexer.source())
 	}
 
 	#[inline]
-	pub fn parse_str(&self, c: Cursor) -> &str {
-		c.parse_str(self.lexer.source(), self.bump)
+	pub fn parse_str(&self, c: Cursor) -> &'a str {
+		self.to_lexer_cursor(c).parse_str(self.lexer.source(), self.bump)
 	}
 
 	#[inline]
-	pub fn parse_str_lower(&self, c: Cursor) -> &str {
-		c.parse_str_lower(self.lexer.source(), self.bump)
+	pub fn parse_str_lower(&self, c: Cursor) -> &'a str {
+		self.to_lexer_cursor(c).parse_str_lower(self.lexer.source(), self.bump)
 	}
 
 	#[inline]
 	pub fn eq_ignore_ascii_case(&self, c: Cursor, other: &'static str) -> bool {
-		c.eq_ignore_ascii_case(self.lexer.source(), other)
+		self.to_lexer_cursor(c).eq_ignore_ascii_case(self.lexer.source(), other)
 	}
 
 	#[inline(always)]
 	pub fn offset(&self) -> SourceOffset {
-		self.lexer.offset()
+		SourceOffset(self.lexer.offset().0 + self.base)
+	}
+
+	// The lexer only knows of `source_text`, so cursors going into (and coming out of) it need to account for `base`.
+	#[inline(always)]
+	fn to_lexer_cursor(&self, c: Cursor) -> Cursor {
+		Cursor::new(SourceOffset(c.offset().0 - self.base), c.token())
+	}
+
+	#[inline(always)]
+	fn lexer_to_cursor(&self, offset: SourceOffset, t: Token) -> Cursor {
+		t.with_cursor(SourceOffset(offset.0 + self.base))
 	}
 
 	#[inline(always)]
 	pub fn at_end(&self) -> bool {
-		self.lexer.at_end()
+		self.exceeded.is_some() || self.lexer.at_end()
 	}
 
 	pub fn rewind(&mut self, checkpoint: ParserCheckpoint) {
-		let ParserCheckpoint { cursor, errors_pos, trivia_pos } = checkpoint;
-		self.lexer.rewind(cursor);
-		self.errors.truncate(errors_pos as usize);
+		let ParserCheckpoint { cursor, errors_pos, trivia_pos, depth } = checkpoint;
+		self.lexer.rewind(self.to_lexer_cursor(cursor));
+		self.depth = depth;
+		self.errors.truncate(errors_pos);
+		if let Some((_, exceeded_errors_pos)) = &mut self.exceeded {
+			*exceeded_errors_pos = (*exceeded_errors_pos).min(errors_pos);
+		}
 		self.trivia.truncate(trivia_pos as usize);
 		#[cfg(debug_assertions)]
 		{
@@ -195,14 +276,21 @@ impl<'a> Parser<'a> {
 	#[inline]
 	pub fn checkpoint(&self) -> ParserCheckpoint {
 		ParserCheckpoint {
-			cursor: self.lexer.checkpoint(),
-			errors_pos: self.errors.len() as u8,
+			cursor: {
+				let cursor = self.lexer.checkpoint();
+				self.lexer_to_cursor(cursor.offset(), cursor.token())
+			},
+			errors_pos: self.errors.len(),
 			trivia_pos: self.trivia.len() as u16,
+			depth: self.depth,
 		}
 	}
 
 	#[inline]
 	pub fn next_is_stop(&self) -> bool {
+		if self.exceeded.is_some() {
+			return Kind::Eof == self.stop;
+		}
 		let mut lexer = self.lexer.clone();
 		loop {
 			let t = lexer.advance();
@@ -214,72 +302,91 @@ impl<'a> Parser<'a> {
 
 	#[inline]
 	pub(crate) fn peek_next(&self) -> Cursor {
+		if self.exceeded.is_some() {
+			return Token::EOF.with_cursor(self.offset());
+		}
 		let mut lexer = self.lexer.clone();
 		loop {
 			let offset = lexer.offset();
 			let t = lexer.advance();
 			if t == Kind::Eof || t != self.skip {
-				return t.with_cursor(offset);
+				return self.lexer_to_cursor(offset, t);
 			}
 		}
 	}
 
 	#[inline]
 	pub(crate) fn peek_next_including_whitespace(&self) -> Cursor {
+		if self.exceeded.is_some() {
+			return Token::EOF.with_cursor(self.offset());
+		}
 		let mut lexer = self.lexer.clone();
 		loop {
 			let offset = lexer.offset();
 			let t = lexer.advance();
 			if t == Kind::Eof || t == Kind::Whitespace || t != self.skip {
-				return t.with_cursor(offset);
+				return self.lexer_to_cursor(offset, t);
 			}
 		}
 	}
 
 	pub fn peek_n(&self, n: u8) -> Cursor {
+		if self.exceeded.is_some() {
+			return Token::EOF.with_cursor(self.offset());
+		}
 		let mut lex = self.lexer.clone();
 		let mut remaining = n;
 		loop {
 			let offset = lex.offset();
 			let t = lex.advance();
 			if t == Kind::Eof {
-				return t.with_cursor(offset);
+				return self.lexer_to_cursor(offset, t);
 			}
 			if t != self.skip {
 				remaining -= 1;
 				if remaining == 0 {
-					return t.with_cursor(offset);
+					return self.lexer_to_cursor(offset, t);
 				}
 			}
 		}
 	}
 
 	pub fn consume_trivia(&mut self) {
+		if self.exceeded.is_some() {
+			return;
+		}
 		loop {
 			let offset = self.lexer.offset();
-			let c = self.lexer.advance().with_cursor(offset);
-			if c == Kind::Eof {
+			let t = self.lexer.advance();
+			if t == Kind::Eof {
 				return;
-			} else if c == self.skip {
-				self.trivia.push(c)
+			} else if t == self.skip {
+				self.trivia.push(self.lexer_to_cursor(offset, t))
 			} else {
-				self.lexer.rewind(c);
+				self.lexer.rewind(t.with_cursor(offset));
 				return;
 			}
 		}
 	}
 
 	pub fn next(&mut self) -> Cursor {
+		if self.exceeded.is_some() {
+			return Token::EOF.with_cursor(self.offset());
+		}
 		let mut c;
 		let mut offset;
 		loop {
-			offset = self.offset();
-			c = self.lexer.advance().with_cursor(offset);
+			offset = self.lexer.offset();
+			let t = self.lexer.advance();
+			c = self.lexer_to_cursor(offset, t);
 			if c == Kind::Eof || c != self.skip {
 				break;
 			}
 			self.trivia.push(c)
 		}
+		if c != Kind::Eof {
+			self.check_limits(c);
+		}
 
 		#[cfg(debug_assertions)]
 		if let Some(last_cursor) = self.last_cursor {
@@ -294,4 +401,29 @@ impl<'a> Parser<'a> {
 
 		c
 	}
+
+	// Account for the consumed cursor against the limits. If a limit is exceeded then every subsequent call to next()
+	// (or any peek) will see Eof. This unwinds any recursion, as every nested node will come to an end. The cursor which
+	// exceeded the limit is still returned, as the caller will likely have already peeked it.
+	fn check_limits(&mut self, c: Cursor) {
+		match c.token().kind() {
+			Kind::LeftCurly | Kind::LeftParen | Kind::LeftSquare | Kind::Function => {
+				self.depth = self.depth.saturating_add(1)
+			}
+			Kind::RightCurly | Kind::RightParen | Kind::RightSquare => self.depth = self.depth.saturating_sub(1),
+			_ => {}
+		}
+		self.tokens = self.tokens.saturating_add(1);
+		let ParserLimits { max_depth, max_tokens, max_allocated_bytes } = self.limits;
+		let error: Error = if self.depth > max_depth {
+			diagnostics::MaxDepthExceeded(max_depth, c.into()).into()
+		} else if self.tokens > max_tokens {
+			diagnostics::MaxTokensExceeded(max_tokens, c.into()).into()
+		} else if self.bump.allocated_bytes().saturating_sub(self.allocated_bytes) > max_allocated_bytes {
+			diagnostics::MaxAllocationExceeded(max_allocated_bytes, c.into()).into()
+		} else {
+			return;
+		};
+		self.exceeded = Some((error, self.errors.len()));
+	}
 }
@@ -7,8 +7,9 @@ use css_lexer::{Cursor, Kind, Span, Token};
 #[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct ParserCheckpoint {
 	pub(crate) cursor: Cursor,
-	pub(crate) errors_pos: u8,
+	pub(crate) errors_pos: usize,
 	pub(crate) trivia_pos: u16,
+	pub(crate) depth: u16,
 }
 
 impl From<ParserCheckpoint> for Cursor {
@@ -41,6 +42,6 @@ mod test {
 
 	#[test]
 	fn size_test() {
-		assert_eq!(std::mem::size_of::<ParserCheckpoint>(), 16);
+		assert_eq!(std::mem::size_of::<ParserCheckpoint>(), 24);
 	}
 }
@@ -19,7 +19,7 @@ use css_lexer::KindSet;
 /// [1]: https://drafts.csswg.org/css-syntax-3/#at-rule-diagram
 /// [2]: https://drafts.csswg.org/css-syntax-3/#consume-an-at-rule
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type"))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, crate::FromJson), serde(tag = "type"))]
 pub struct AtRule<'a> {
 	pub name: T![AtKeyword],
 	pub prelude: Option<ComponentValues<'a>>,
@@ -53,7 +53,7 @@ impl ToCursors for AtRule<'_> {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, crate::FromJson), serde())]
 pub enum OptionalBlock<'a> {
 	Block(Block<'a>),
 	None(Option<T![;]>),
@@ -2,7 +2,7 @@ use crate::{syntax::ComponentValue, CursorSink, Parse, Parser, Result as ParserR
 use bumpalo::collections::Vec;
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, crate::FromJson), serde())]
 pub struct BadDeclaration<'a>(Vec<'a, ComponentValue<'a>>);
 
 // https://drafts.csswg.org/css-syntax-3/#consume-the-remnants-of-a-bad-declaration
@@ -0,0 +1,72 @@
+use crate::{syntax::ComponentValue, CursorSink, Parse, Parser, Result as ParserResult, State, ToCursors, T};
+use bumpalo::collections::Vec;
+use css_lexer::Kind;
+
+/// The remnants of a rule which could not be parsed, kept as [ComponentValue]s so that it can still be written back
+/// out.
+///
+/// This follows the steps to [consume an at-rule][1] or [consume a qualified rule][2] without checking any grammar:
+/// an at-rule ends at its `;` or block, a qualified rule ends at its block (or its `;` if nested), and either ends at a
+/// `}` which closes the surrounding block (if nested). Tokens that cannot be kept as a [ComponentValue], such as an
+/// unmatched `)`, are discarded.
+///
+/// [1]: https://drafts.csswg.org/css-syntax-3/#consume-an-at-rule
+/// [2]: https://drafts.csswg.org/css-syntax-3/#consume-a-qualified-rule
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, crate::FromJson), serde())]
+pub struct BadRule<'a>(pub Vec<'a, ComponentValue<'a>>);
+
+impl<'a> Parse<'a> for BadRule<'a> {
+	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
+		let mut values = Vec::new_in(p.bump());
+		let at_rule = p.peek::<T![AtKeyword]>();
+		loop {
+			if p.at_end() || p.peek_next() == Kind::Eof {
+				return Ok(Self(values));
+			}
+			if p.peek::<T!['}']>() {
+				// A nested rule stops at the end of its parent block, otherwise the `}` is discarded.
+				if p.is(State::Nested) {
+					return Ok(Self(values));
+				}
+				p.next();
+				continue;
+			}
+			if p.peek::<T!['{']>() || ((at_rule || p.is(State::Nested)) && p.peek::<T![;]>()) {
+				values.push(p.parse::<ComponentValue>()?);
+				return Ok(Self(values));
+			}
+			if p.peek::<ComponentValue>() {
+				values.push(p.parse::<ComponentValue>()?);
+			} else {
+				p.next();
+			}
+		}
+	}
+}
+
+impl<'a> ToCursors for BadRule<'a> {
+	fn to_cursors(&self, s: &mut impl CursorSink) {
+		for value in &self.0 {
+			ToCursors::to_cursors(value, s);
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::test_helpers::*;
+
+	#[test]
+	fn size_test() {
+		assert_eq!(std::mem::size_of::<BadRule>(), 32);
+	}
+
+	#[test]
+	fn test_writes() {
+		assert_parse!(BadRule, "a b{c}");
+		assert_parse!(BadRule, "@foo bar;");
+		assert_parse!(BadRule, "}a{}", "a{}");
+	}
+}
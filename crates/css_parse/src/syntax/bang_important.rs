@@ -20,7 +20,7 @@ use css_lexer::{Cursor, Kind};
 /// [1]: https://drafts.csswg.org/css-syntax-3/#!important-diagram
 ///
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, crate::FromJson), serde())]
 pub struct BangImportant {
 	pub bang: T![!],
 	pub important: T![Ident],
@@ -5,7 +5,7 @@ use css_lexer::{Kind, KindSet, SourceOffset, Token};
 use super::{Declaration, Rule};
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type"))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, crate::FromJson), serde(tag = "type"))]
 pub struct Block<'a> {
 	start: SourceOffset,
 	pub open_curly: T!['{'],
@@ -9,7 +9,7 @@ use css_lexer::{Cursor, Kind, KindSet};
 // A compatible "Token" per CSS grammar, subsetted to the tokens possibly
 // rendered by ComponentValue (so no pairwise, function tokens, etc).
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(untagged))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, crate::FromJson), serde(untagged))]
 pub enum ComponentValue<'a> {
 	SimpleBlock(SimpleBlock<'a>),
 	Function(FunctionBlock<'a>),
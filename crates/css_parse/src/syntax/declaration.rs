@@ -20,7 +20,7 @@ use crate::{
 ///
 /// [1]: https://drafts.csswg.org/css-syntax-3/#consume-a-declaration
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type"))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, crate::FromJson), serde(tag = "type"))]
 pub struct Declaration<'a> {
 	pub name: T![Ident],
 	pub colon: T![:],
@@ -2,7 +2,7 @@ use crate::{syntax::ComponentValue, CursorSink, Parse, Parser, Result as ParserR
 use bumpalo::collections::Vec;
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type"))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, crate::FromJson), serde(tag = "type"))]
 pub struct FunctionBlock<'a> {
 	pub name: T![Function],
 	pub values: Vec<'a, ComponentValue<'a>>,
@@ -1,5 +1,6 @@
 mod at_rule;
 mod bad_declaration;
+mod bad_rule;
 mod bang_important;
 mod block;
 mod component_value;
@@ -12,6 +13,7 @@ mod simple_block;
 
 pub use at_rule::*;
 pub use bad_declaration::*;
+pub use bad_rule::*;
 pub use bang_important::*;
 pub use block::*;
 pub use component_value::*;
@@ -1,12 +1,13 @@
 use crate::{CursorSink, Parse, Parser, Result as ParserResult, ToCursors, T};
 
-use super::{AtRule, QualifiedRule};
+use super::{AtRule, BadRule, QualifiedRule};
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type"))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, crate::FromJson), serde(tag = "type"))]
 pub enum Rule<'a> {
 	AtRule(AtRule<'a>),
 	QualifiedRule(QualifiedRule<'a>),
+	BadRule(BadRule<'a>),
 }
 
 impl<'a> Parse<'a> for Rule<'a> {
@@ -18,11 +19,18 @@ impl<'a> Parse<'a> for Rule<'a> {
 	}
 }
 
+impl<'a> From<BadRule<'a>> for Rule<'a> {
+	fn from(value: BadRule<'a>) -> Self {
+		Self::BadRule(value)
+	}
+}
+
 impl<'a> ToCursors for Rule<'a> {
 	fn to_cursors(&self, s: &mut impl CursorSink) {
 		match self {
 			Self::AtRule(rule) => ToCursors::to_cursors(rule, s),
 			Self::QualifiedRule(rule) => ToCursors::to_cursors(rule, s),
+			Self::BadRule(rule) => ToCursors::to_cursors(rule, s),
 		}
 	}
 }
@@ -2,7 +2,7 @@ use crate::{syntax::ComponentValues, CursorSink, Parse, Parser, Result as Parser
 use css_lexer::{KindSet, SourceOffset, Token};
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type"))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, crate::FromJson), serde(tag = "type"))]
 pub struct SimpleBlock<'a> {
 	start: SourceOffset,
 	pub open: T![PairWiseStart],
@@ -10,6 +10,13 @@ macro_rules! define_kinds {
 		#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
 		pub struct $ident(::css_lexer::Cursor);
 
+		#[cfg(feature = "serde")]
+		impl<'a> $crate::FromJson<'a> for $ident {
+			fn from_json(p: &$crate::Parser<'a>, value: &$crate::JsonValue) -> ::core::result::Result<Self, $crate::JsonError> {
+				$crate::JsonObject::token(p, stringify!($ident), value)
+			}
+		}
+
 		impl $ident {
 			pub const fn dummy() -> Self {
 				Self(::css_lexer::Cursor::dummy(::css_lexer::Token::dummy(::css_lexer::Kind::$ident)))
@@ -75,6 +82,13 @@ macro_rules! define_kind_idents {
 		#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
 		pub struct $ident(::css_lexer::Cursor);
 
+		#[cfg(feature = "serde")]
+		impl<'a> $crate::FromJson<'a> for $ident {
+			fn from_json(p: &$crate::Parser<'a>, value: &$crate::JsonValue) -> ::core::result::Result<Self, $crate::JsonError> {
+				$crate::JsonObject::token(p, stringify!($ident), value)
+			}
+		}
+
 		impl From<$ident> for ::css_lexer::Cursor {
 			fn from(value: $ident) -> Self {
 				value.0
@@ -159,6 +173,13 @@ macro_rules! custom_delim {
 		#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
 		pub struct $ident($crate::T![Delim]);
 
+		#[cfg(feature = "serde")]
+		impl<'a> $crate::FromJson<'a> for $ident {
+			fn from_json(p: &$crate::Parser<'a>, value: &$crate::JsonValue) -> ::core::result::Result<Self, $crate::JsonError> {
+				$crate::JsonObject::token(p, stringify!($ident), value)
+			}
+		}
+
 		impl From<$ident> for ::css_lexer::Cursor {
 			fn from(value: $ident) -> Self {
 				value.0.into()
@@ -224,6 +245,13 @@ macro_rules! custom_dimension {
 		#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
 		pub struct $ident(::css_lexer::Cursor);
 
+		#[cfg(feature = "serde")]
+		impl<'a> $crate::FromJson<'a> for $ident {
+			fn from_json(p: &$crate::Parser<'a>, value: &$crate::JsonValue) -> ::core::result::Result<Self, $crate::JsonError> {
+				$crate::JsonObject::token(p, stringify!($ident), value)
+			}
+		}
+
 		impl $ident {
 			/// Returns the [f32] representation of the dimension's value.
 			pub fn value(&self) -> f32 {
@@ -324,7 +352,7 @@ macro_rules! custom_double_delim {
 	($(#[$meta:meta])*$ident: ident, $first: literal, $second: literal) => {
 		$(#[$meta])*
 		#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-		#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+		#[cfg_attr(feature = "serde", derive(serde::Serialize, $crate::FromJson), serde())]
 		pub struct $ident(pub $crate::T![Delim], pub $crate::T![Delim]);
 
 		impl $ident {
@@ -416,6 +444,15 @@ macro_rules! keyword_set {
 		pub enum $name {
 			$($variant(::css_lexer::Cursor)),+
 		}
+
+		#[cfg(feature = "serde")]
+		impl<'a> $crate::FromJson<'a> for $name {
+			fn from_json(p: &$crate::Parser<'a>, value: &$crate::JsonValue) -> ::core::result::Result<Self, $crate::JsonError> {
+				$crate::JsonObject::token_variant(p, stringify!($name), value, |node| match node {
+					$(Self::$variant(_) => stringify!($variant),)+
+				})
+			}
+		}
 		impl<'a> $crate::Peek<'a> for $name {
 			fn peek(p: &$crate::Parser<'a>, c: css_lexer::Cursor) -> bool {
 				c == ::css_lexer::Kind::Ident && Self::MAP.get(&p.parse_str_lower(c)).is_some()
@@ -522,6 +559,15 @@ macro_rules! function_set {
 		pub enum $name {
 			$($variant(::css_lexer::Cursor)),+
 		}
+
+		#[cfg(feature = "serde")]
+		impl<'a> $crate::FromJson<'a> for $name {
+			fn from_json(p: &$crate::Parser<'a>, value: &$crate::JsonValue) -> ::core::result::Result<Self, $crate::JsonError> {
+				$crate::JsonObject::token_variant(p, stringify!($name), value, |node| match node {
+					$(Self::$variant(_) => stringify!($variant),)+
+				})
+			}
+		}
 		impl<'a> $crate::Peek<'a> for $name {
 			fn peek(p: &$crate::Parser<'a>, c: css_lexer::Cursor) -> bool {
 				c == ::css_lexer::Kind::Function && Self::MAP.get(p.parse_str_lower(c)).is_some()
@@ -628,6 +674,15 @@ macro_rules! atkeyword_set {
 		pub enum $name {
 			$($variant(::css_lexer::Cursor)),+
 		}
+
+		#[cfg(feature = "serde")]
+		impl<'a> $crate::FromJson<'a> for $name {
+			fn from_json(p: &$crate::Parser<'a>, value: &$crate::JsonValue) -> ::core::result::Result<Self, $crate::JsonError> {
+				$crate::JsonObject::token_variant(p, stringify!($name), value, |node| match node {
+					$(Self::$variant(_) => stringify!($variant),)+
+				})
+			}
+		}
 		impl<'a> $crate::Peek<'a> for $name {
 			fn peek(p: &$crate::Parser<'a>, c: css_lexer::Cursor) -> bool {
 				c == ::css_lexer::Kind::AtKeyword && Self::MAP.get(&p.parse_str_lower(c)).is_some()
@@ -776,6 +831,17 @@ define_kind_idents! {
 #[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
 pub struct Whitespace(Cursor);
 
+#[cfg(feature = "serde")]
+impl<'a> crate::FromJson<'a> for Whitespace {
+	fn from_json(p: &Parser<'a>, value: &crate::JsonValue) -> Result<Self, crate::JsonError> {
+		let c = <Cursor as crate::FromJson>::from_json(p, value)?;
+		if c != Kind::Whitespace {
+			Err(<crate::JsonError as serde::de::Error>::custom(format!("{value} is not Whitespace")))?
+		}
+		Ok(Self(c))
+	}
+}
+
 impl From<Whitespace> for Cursor {
 	fn from(value: Whitespace) -> Self {
 		value.0
@@ -840,6 +906,13 @@ impl<'a> Parse<'a> for Whitespace {
 #[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
 pub struct DashedIdent(Ident);
 
+#[cfg(feature = "serde")]
+impl<'a> crate::FromJson<'a> for DashedIdent {
+	fn from_json(p: &crate::Parser<'a>, value: &crate::JsonValue) -> Result<Self, crate::JsonError> {
+		crate::JsonObject::token(p, stringify!(DashedIdent), value)
+	}
+}
+
 impl From<DashedIdent> for Cursor {
 	fn from(value: DashedIdent) -> Self {
 		value.0.into()
@@ -884,6 +957,13 @@ impl<'a> Build<'a> for DashedIdent {
 #[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
 pub struct Dimension(Cursor);
 
+#[cfg(feature = "serde")]
+impl<'a> crate::FromJson<'a> for Dimension {
+	fn from_json(p: &crate::Parser<'a>, value: &crate::JsonValue) -> Result<Self, crate::JsonError> {
+		crate::JsonObject::token(p, stringify!(Dimension), value)
+	}
+}
+
 impl From<Dimension> for Cursor {
 	fn from(value: Dimension) -> Self {
 		value.0
@@ -946,6 +1026,13 @@ impl Dimension {
 #[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
 pub struct Number(Cursor);
 
+#[cfg(feature = "serde")]
+impl<'a> crate::FromJson<'a> for Number {
+	fn from_json(p: &crate::Parser<'a>, value: &crate::JsonValue) -> Result<Self, crate::JsonError> {
+		crate::JsonObject::token(p, stringify!(Number), value)
+	}
+}
+
 impl Number {
 	pub const NUMBER_ZERO: Number = Number(Cursor::dummy(Token::NUMBER_ZERO));
 
@@ -1204,7 +1291,7 @@ pub mod double {
 	/// Represents a two consecutive tokens with [Kind::Colon] that cannot be separated by any other token, representing
 	/// `::`. Use [T![::]][crate::T] to refer to this.
 	#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-	#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, crate::FromJson), serde())]
 	pub struct ColonColon(T![:], T![:]);
 
 	impl ColonColon {
@@ -1574,6 +1661,13 @@ pub mod dimension {
 #[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
 pub struct Any(Cursor);
 
+#[cfg(feature = "serde")]
+impl<'a> crate::FromJson<'a> for Any {
+	fn from_json(p: &crate::Parser<'a>, value: &crate::JsonValue) -> Result<Self, crate::JsonError> {
+		crate::JsonObject::token(p, stringify!(Any), value)
+	}
+}
+
 impl From<Any> for Cursor {
 	fn from(value: Any) -> Self {
 		value.0
@@ -1601,7 +1695,7 @@ impl<'a> Build<'a> for Any {
 /// Represents a token with either [Kind::LeftCurly], [Kind::LeftParen] or [Kind::LeftSquare]. Use
 /// [T![PairWiseStart]][crate::T] to refer to this.
 #[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, crate::FromJson), serde())]
 pub struct PairWiseStart(Token);
 
 impl From<PairWiseStart> for Cursor {
@@ -1656,7 +1750,7 @@ impl<'a> Build<'a> for PairWiseStart {
 /// Represents a token with either [Kind::RightCurly], [Kind::RightParen] or [Kind::RightSquare]. Use
 /// [T![PairWiseEnd]][crate::T] to refer to this.
 #[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, crate::FromJson), serde())]
 pub struct PairWiseEnd(Token);
 
 impl From<PairWiseEnd> for Cursor {
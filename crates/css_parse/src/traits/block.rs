@@ -1,4 +1,4 @@
-use crate::{Parse, Parser, Result, State, T};
+use crate::{syntax::BadRule, Parse, Parser, Result, State, T};
 use bumpalo::collections::Vec;
 
 use super::Peek;
@@ -14,10 +14,14 @@ use super::Peek;
 ///           ╰─────────────────────────────────────────────────────╯
 /// ```
 ///
+/// A rule (or anything which is neither a declaration nor a rule) which fails to parse does not fail the block: its error
+/// is recorded with [Parser::push_error()], and its tokens are consumed as a [BadRule], which [Block::Rule] must be able
+/// to represent.
+///
 /// [1]: https://drafts.csswg.org/css-syntax-3/#consume-block-contents
 pub trait Block<'a>: Sized + Parse<'a> {
 	type Declaration: Peek<'a> + Parse<'a>;
-	type Rule: Parse<'a>;
+	type Rule: Parse<'a> + From<BadRule<'a>>;
 
 	fn parse_block(
 		p: &mut Parser<'a>,
@@ -39,18 +43,25 @@ pub trait Block<'a>: Sized + Parse<'a> {
 				break;
 			}
 			let old_state = p.set_state(State::Nested);
-			if p.peek::<T![AtKeyword]>() {
-				let rule = p.parse::<Self::Rule>();
-				p.set_state(old_state);
-				rules.push(rule?);
-			} else if let Ok(Some(decl)) = p.try_parse_if_peek::<Self::Declaration>() {
-				p.set_state(old_state);
-				declarations.push((decl, p.parse_if_peek::<T![;]>()?));
-			} else {
-				let rule = p.parse::<Self::Rule>();
-				p.set_state(old_state);
-				rules.push(rule?);
+			if !p.peek::<T![AtKeyword]>() {
+				if let Ok(Some(decl)) = p.try_parse_if_peek::<Self::Declaration>() {
+					p.set_state(old_state);
+					declarations.push((decl, p.parse_if_peek::<T![;]>()?));
+					continue;
+				}
 			}
+			let checkpoint = p.checkpoint();
+			let rule = match p.parse::<Self::Rule>() {
+				Ok(rule) => Ok(rule),
+				Err(error) => {
+					p.rewind(checkpoint);
+					let rule = p.parse::<BadRule>();
+					p.push_error(error);
+					rule.map(Self::Rule::from)
+				}
+			};
+			p.set_state(old_state);
+			rules.push(rule?);
 		}
 		Ok((open, declarations, rules, p.parse_if_peek::<T!['}']>()?))
 	}
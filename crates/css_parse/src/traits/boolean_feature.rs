@@ -99,7 +99,7 @@ macro_rules! boolean_feature {
 	($(#[doc = $usage:literal])* $feature: ident, $feature_name: tt $(,)*) => {
 		$(#[doc = $usage])*
 		#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-		#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+		#[cfg_attr(feature = "serde", derive(serde::Serialize, $crate::FromJson), serde())]
 		pub enum $feature {
 			WithValue($crate::T!['('], $crate::T![Ident], $crate::T![:], $crate::T![Any], $crate::T![')']),
 			Bare($crate::T!['('], $crate::T![Ident], $crate::T![')']),
@@ -109,7 +109,7 @@ macro_rules! discrete_feature {
 	($(#[doc = $usage:literal])* $feature: ident, $feature_name: tt, $value: ty $(,)*) => {
 		$(#[doc = $usage])*
 		#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-		#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+		#[cfg_attr(feature = "serde", derive(serde::Serialize, $crate::FromJson), serde())]
 		pub enum $feature {
 			WithValue($crate::T!['('], $crate::T![Ident], $crate::T![:], $value, $crate::T![')']),
 			Bare($crate::T!['('], $crate::T![Ident], $crate::T![')']),
@@ -0,0 +1,287 @@
+use crate::{Build, Parser, Peek};
+use bumpalo::{boxed::Box, collections::Vec};
+use css_lexer::{Cursor, Lexer, SourceOffset, Token};
+use serde::de::{Deserialize, Error as _};
+use smallvec::{Array, SmallVec};
+
+pub use serde_json::{Error as JsonError, Value as JsonValue};
+
+/// This trait allows AST nodes to be rebuilt from the JSON that their `serde::Serialize` implementation produces.
+/// Nodes are allocated into the [Parser's][Parser] bump, and [Cursors][Cursor] are checked against (and take their
+/// [Token] from) the Parser's source text, so the Parser must be created over the same source that was serialized.
+///
+/// This should be derived alongside `serde::Serialize` with `#[derive(FromJson)]`, which reads the same
+/// `#[serde(...)]` attributes. Types which have a bespoke serialization, such as [Cursor], implement it by hand.
+///
+/// ```
+/// use css_parse::*;
+/// use bumpalo::Bump;
+/// let bump = Bump::default();
+/// let p = Parser::new(&bump, "foo");
+/// let json = serde_json::json!({ "kind": "Ident", "offset": 0, "len": 3 });
+/// let ident = <T![Ident]>::from_json(&p, &json).unwrap();
+/// assert_eq!(p.parse_str(ident.into()), "foo");
+/// // The JSON must agree with the source text:
+/// let json = serde_json::json!({ "kind": "Number", "offset": 0, "len": 3 });
+/// assert!(<T![Ident]>::from_json(&p, &json).is_err());
+/// ```
+pub trait FromJson<'a>: Sized {
+	fn from_json(p: &Parser<'a>, value: &JsonValue) -> Result<Self, JsonError>;
+}
+
+macro_rules! from_json_via_deserialize {
+	($($ty: ty),+) => {
+		$(
+			impl<'a> FromJson<'a> for $ty {
+				fn from_json(_: &Parser<'a>, value: &JsonValue) -> Result<Self, JsonError> {
+					<$ty>::deserialize(value)
+				}
+			}
+		)+
+	}
+}
+
+from_json_via_deserialize!(bool, u8, u16, u32, u64, i8, i16, i32, i64, f32, f64);
+
+impl<'a> FromJson<'a> for () {
+	fn from_json(_: &Parser<'a>, value: &JsonValue) -> Result<Self, JsonError> {
+		JsonObject::expect_null("()", value)
+	}
+}
+
+impl<'a, T: FromJson<'a>> FromJson<'a> for &'a T {
+	fn from_json(p: &Parser<'a>, value: &JsonValue) -> Result<Self, JsonError> {
+		Ok(p.bump().alloc(T::from_json(p, value)?))
+	}
+}
+
+impl<'a, T: FromJson<'a>> FromJson<'a> for Option<T> {
+	fn from_json(p: &Parser<'a>, value: &JsonValue) -> Result<Self, JsonError> {
+		if value.is_null() {
+			Ok(None)
+		} else {
+			T::from_json(p, value).map(Some)
+		}
+	}
+}
+
+impl<'a, T: FromJson<'a>> FromJson<'a> for Vec<'a, T> {
+	fn from_json(p: &Parser<'a>, value: &JsonValue) -> Result<Self, JsonError> {
+		let items = value.as_array().ok_or_else(|| JsonError::custom(format!("expected an array, saw {value}")))?;
+		let mut vec = Vec::with_capacity_in(items.len(), p.bump());
+		for item in items {
+			vec.push(T::from_json(p, item)?);
+		}
+		Ok(vec)
+	}
+}
+
+impl<'a, T: FromJson<'a>> FromJson<'a> for Box<'a, T> {
+	fn from_json(p: &Parser<'a>, value: &JsonValue) -> Result<Self, JsonError> {
+		Ok(Box::new_in(T::from_json(p, value)?, p.bump()))
+	}
+}
+
+impl<'a, A: Array> FromJson<'a> for SmallVec<A>
+where
+	A::Item: FromJson<'a>,
+{
+	fn from_json(p: &Parser<'a>, value: &JsonValue) -> Result<Self, JsonError> {
+		let items = value.as_array().ok_or_else(|| JsonError::custom(format!("expected an array, saw {value}")))?;
+		items.iter().map(|item| A::Item::from_json(p, item)).collect()
+	}
+}
+
+macro_rules! from_json_tuple {
+	($len: literal: $($name: ident $index: tt),+) => {
+		impl<'a, $($name: FromJson<'a>),+> FromJson<'a> for ($($name,)+) {
+			fn from_json(p: &Parser<'a>, value: &JsonValue) -> Result<Self, JsonError> {
+				let items = JsonObject::expect_array("tuple", value, $len)?;
+				Ok(($($name::from_json(p, &items[$index])?,)+))
+			}
+		}
+	};
+}
+
+from_json_tuple!(2: A 0, B 1);
+from_json_tuple!(3: A 0, B 1, C 2);
+from_json_tuple!(4: A 0, B 1, C 2, D 3);
+from_json_tuple!(5: A 0, B 1, C 2, D 3, E 4);
+from_json_tuple!(6: A 0, B 1, C 2, D 3, E 4, F 5);
+
+impl<'a> FromJson<'a> for SourceOffset {
+	fn from_json(_: &Parser<'a>, value: &JsonValue) -> Result<Self, JsonError> {
+		Ok(SourceOffset(u32::deserialize(value)?))
+	}
+}
+
+impl<'a> FromJson<'a> for Cursor {
+	/// A Cursor is serialized as its `kind`, `offset`, `len` and (for dimensions) `unit`, which isn't enough to
+	/// recreate its [Token]. Instead the token is re-lexed from the source text at `offset`, and must match the rest of
+	/// the JSON.
+	fn from_json(p: &Parser<'a>, value: &JsonValue) -> Result<Self, JsonError> {
+		let object = JsonObject::new("Cursor", value)?;
+		object.deny_unknown_fields(&["kind", "offset", "len", "unit"])?;
+		let offset: u32 = object.field(p, "offset")?;
+		let start = offset as usize;
+		if start >= p.source_text.len() || !p.source_text.is_char_boundary(start) {
+			Err(JsonError::custom(format!("Cursor offset {offset} is not within the source text")))?
+		}
+		let mut lexer = Lexer::new_with_features(p.source_text, p.features.into());
+		lexer.hop(Cursor::new(SourceOffset(offset), Token::EOF));
+		let cursor = Cursor::new(SourceOffset(offset), lexer.advance());
+		if serde_json::to_value(cursor)? != *value {
+			Err(JsonError::custom(format!("{value} does not match the source text, which has {cursor:?} at {offset}")))?
+		}
+		Ok(cursor)
+	}
+}
+
+impl<'a> FromJson<'a> for Token {
+	/// A Token is serialized without an offset into the source text, so only tokens which are always the same
+	/// character (such as [Kind::LeftCurly]) can be read back.
+	fn from_json(_: &Parser<'a>, value: &JsonValue) -> Result<Self, JsonError> {
+		let token = match value.get("kind").and_then(|kind| kind.as_str()) {
+			Some("Colon") => Token::COLON,
+			Some("Semicolon") => Token::SEMICOLON,
+			Some("Comma") => Token::COMMA,
+			Some("LeftSquare") => Token::LEFT_SQUARE,
+			Some("RightSquare") => Token::RIGHT_SQUARE,
+			Some("LeftParen") => Token::LEFT_PAREN,
+			Some("RightParen") => Token::RIGHT_PAREN,
+			Some("LeftCurly") => Token::LEFT_CURLY,
+			Some("RightCurly") => Token::RIGHT_CURLY,
+			_ => Err(JsonError::custom(format!("{value} cannot be read as a Token without an offset")))?,
+		};
+		if serde_json::to_value(token)? != *value {
+			Err(JsonError::custom(format!("{value} is not a valid {:?} Token", token.kind())))?
+		}
+		Ok(token)
+	}
+}
+
+/// Helpers for reading the JSON objects and arrays which make up a serialized node. These are used by the code that
+/// `#[derive(FromJson)]` generates, and aren't otherwise very useful.
+#[doc(hidden)]
+pub struct JsonObject<'j> {
+	ty: &'static str,
+	value: &'j JsonValue,
+	map: &'j serde_json::Map<String, JsonValue>,
+}
+
+static NULL: JsonValue = JsonValue::Null;
+
+impl<'j> JsonObject<'j> {
+	pub fn new(ty: &'static str, value: &'j JsonValue) -> Result<Self, JsonError> {
+		match value {
+			JsonValue::Object(map) => Ok(Self { ty, value, map }),
+			_ => Err(JsonError::custom(format!("expected {ty} to be an object, saw {value}"))),
+		}
+	}
+
+	pub fn expect_array<'v>(ty: &'static str, value: &'v JsonValue, len: usize) -> Result<&'v [JsonValue], JsonError> {
+		match value {
+			JsonValue::Array(items) if items.len() == len => Ok(items),
+			_ => Err(JsonError::custom(format!("expected {ty} to be an array of {len}, saw {value}"))),
+		}
+	}
+
+	pub fn expect_null(ty: &'static str, value: &JsonValue) -> Result<(), JsonError> {
+		match value {
+			JsonValue::Null => Ok(()),
+			_ => Err(JsonError::custom(format!("expected {ty} to be null, saw {value}"))),
+		}
+	}
+
+	/// An externally tagged enum variant is either a `"Name"` string or a `{ "Name": value }` object.
+	pub fn external_tag<'v>(value: &'v JsonValue) -> Option<(&'v str, &'v JsonValue)> {
+		match value {
+			JsonValue::String(name) => Some((name, &NULL)),
+			JsonValue::Object(map) if map.len() == 1 => map.iter().next().map(|(name, value)| (name.as_str(), value)),
+			_ => None,
+		}
+	}
+
+	/// Try to read an untagged variant, keeping the error which got furthest into the JSON (so it can be reported if no
+	/// variant matches) and otherwise discarding it so the next variant can be tried.
+	pub fn attempt<T>(closest: &mut Option<JsonError>, f: impl FnOnce() -> Result<T, JsonError>) -> Option<T> {
+		let depth = |err: &JsonError| err.to_string().matches(": ").count();
+		match f() {
+			Ok(node) => Some(node),
+			Err(err) => {
+				if closest.as_ref().is_none_or(|closest| depth(&err) > depth(closest)) {
+					*closest = Some(err);
+				}
+				None
+			}
+		}
+	}
+
+	/// Read a node which is a single token, checking that it could have been parsed as a `T`.
+	pub fn token<'a, T: Peek<'a> + Build<'a>>(
+		p: &Parser<'a>,
+		ty: &'static str,
+		value: &JsonValue,
+	) -> Result<T, JsonError> {
+		let c = Cursor::from_json(p, value)?;
+		if !T::peek(p, c) {
+			Err(JsonError::custom(format!("{value} is not a valid {ty}")))?
+		}
+		Ok(T::build(p, c))
+	}
+
+	/// Read an externally tagged enum of single tokens (such as a `keyword_set!`), checking that the tag matches the
+	/// variant which the token would have been parsed as.
+	pub fn token_variant<'a, T: Peek<'a> + Build<'a>>(
+		p: &Parser<'a>,
+		ty: &'static str,
+		value: &JsonValue,
+		variant_name: impl FnOnce(&T) -> &'static str,
+	) -> Result<T, JsonError> {
+		let (name, inner) = Self::external_tag(value).ok_or_else(|| Self::no_variant(ty, None))?;
+		let node = Self::token::<T>(p, ty, inner)?;
+		if variant_name(&node) != name {
+			Err(JsonError::custom(format!("{inner} is not the {name} variant of {ty}")))?
+		}
+		Ok(node)
+	}
+
+	pub fn no_variant(ty: &'static str, closest: Option<JsonError>) -> JsonError {
+		match closest {
+			Some(closest) => {
+				JsonError::custom(format!("data did not match any variant of {ty}, closest was: {closest}"))
+			}
+			None => JsonError::custom(format!("data did not match any variant of {ty}")),
+		}
+	}
+
+	pub fn get(&self, name: &str) -> &'j JsonValue {
+		self.map.get(name).unwrap_or(&NULL)
+	}
+
+	/// Read the content of an internally tagged variant, which is the object without its tag, unless the content is
+	/// itself tagged with the same key.
+	pub fn content<'a, T: FromJson<'a>>(&self, p: &Parser<'a>, tag: &str) -> Result<T, JsonError> {
+		let mut map = self.map.clone();
+		map.remove(tag);
+		T::from_json(p, &JsonValue::Object(map)).or_else(|err| T::from_json(p, self.value).map_err(|_| err))
+	}
+
+	pub fn field<'a, T: FromJson<'a>>(&self, p: &Parser<'a>, name: &str) -> Result<T, JsonError> {
+		T::from_json(p, self.get(name)).map_err(|err| JsonError::custom(format!("{}.{name}: {err}", self.ty)))
+	}
+
+	pub fn expect_tag(&self, tag: &str, name: &str) -> Result<(), JsonError> {
+		match self.map.get(tag).and_then(|value| value.as_str()) {
+			Some(value) if value == name => Ok(()),
+			value => Err(JsonError::custom(format!("expected {} to have {tag} {name:?}, saw {value:?}", self.ty))),
+		}
+	}
+
+	pub fn deny_unknown_fields(&self, fields: &[&str]) -> Result<(), JsonError> {
+		match self.map.keys().find(|key| !fields.contains(&key.as_str())) {
+			Some(key) => Err(JsonError::custom(format!("unknown field {key:?} in {}", self.ty))),
+			None => Ok(()),
+		}
+	}
+}
@@ -84,9 +84,9 @@ where
 	fn parse_condition(p: &mut Parser<'a>) -> Result<Self> {
 		let c = p.peek_next();
 		if ConditionKeyword::peek(p, c) {
-			let keyword = ConditionKeyword::build(p, c);
-			if matches!(keyword, ConditionKeyword::Not(_)) {
-				return Ok(Self::build_is(p.parse::<Self::FeatureCondition>()?));
+			if matches!(ConditionKeyword::build(p, c), ConditionKeyword::Not(_)) {
+				let keyword = p.parse::<ConditionKeyword>()?;
+				return Ok(Self::build_not(keyword, p.parse::<Self::FeatureCondition>()?));
 			}
 			Err(diagnostics::UnexpectedIdent(p.parse_str(c).into(), c.into()))?
 		}
@@ -106,7 +106,7 @@ where
 						features.push((feature, None));
 						return Ok(Self::build_and(features));
 					}
-					keyword = ConditionKeyword::build(p, c);
+					keyword = p.parse::<ConditionKeyword>()?;
 				}
 			}
 			Some(ConditionKeyword::Or(_)) => {
@@ -117,12 +117,12 @@ where
 					feature = p.parse::<Self::FeatureCondition>()?;
 					let c = p.peek_next();
 					if !ConditionKeyword::peek(p, c)
-						|| !matches!(ConditionKeyword::build(p, c), ConditionKeyword::And(_))
+						|| !matches!(ConditionKeyword::build(p, c), ConditionKeyword::Or(_))
 					{
 						features.push((feature, None));
 						return Ok(Self::build_or(features));
 					}
-					keyword = ConditionKeyword::build(p, c);
+					keyword = p.parse::<ConditionKeyword>()?;
 				}
 			}
 			Some(ConditionKeyword::Not(_)) => {
@@ -5,6 +5,8 @@ mod cursor_sink;
 mod cursor_source;
 mod declaration;
 mod discrete_feature;
+#[cfg(feature = "serde")]
+mod from_json;
 mod lists;
 mod parse;
 mod peek;
@@ -21,6 +23,8 @@ pub use cursor_sink::*;
 pub use cursor_source::*;
 pub use declaration::*;
 pub use discrete_feature::*;
+#[cfg(feature = "serde")]
+pub use from_json::*;
 pub use lists::*;
 pub use parse::*;
 pub use peek::*;
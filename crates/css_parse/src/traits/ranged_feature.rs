@@ -197,7 +197,7 @@ macro_rules! ranged_feature {
 		#[rustfmt::skip] // removing this seems to cause the `Legacy` variants to oddly dedent
 		$(#[doc = $usage])*
 		#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-		#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+		#[cfg_attr(feature = "serde", derive(serde::Serialize, $crate::FromJson), serde())]
 		pub enum $feature {
 			Left($crate::T!['('], $feature_name, $crate::Comparison, $value, $crate::T![')']),
 			Right($crate::T!['('], $value, $crate::Comparison, $feature_name, $crate::T![')']),
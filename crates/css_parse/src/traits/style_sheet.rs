@@ -1,4 +1,4 @@
-use crate::{Parse, Parser, Result, T};
+use crate::{syntax::BadRule, Parse, Parser, Result, T};
 use bumpalo::collections::Vec;
 use css_lexer::Kind;
 
@@ -12,6 +12,9 @@ use css_lexer::Kind;
 ///
 /// StyleSheets are special in that they must discard CdcOrCdo tokens.
 ///
+/// A rule which fails to parse does not fail the style sheet: its error is recorded with [Parser::push_error()], and
+/// its tokens are consumed as a [BadRule], which [StyleSheet::Rule] must be able to represent.
+///
 /// The steps `parse_stylesheet` takes can be defined as:
 ///
 /// ```md
@@ -22,7 +25,7 @@ use css_lexer::Kind;
 /// ```
 ///
 pub trait StyleSheet<'a>: Sized + Parse<'a> {
-	type Rule: Parse<'a>;
+	type Rule: Parse<'a> + From<BadRule<'a>>;
 
 	fn parse_stylesheet(p: &mut Parser<'a>) -> Result<Vec<'a, Self::Rule>> {
 		let mut rules: Vec<'a, Self::Rule> = Vec::new_in(p.bump());
@@ -38,7 +41,16 @@ pub trait StyleSheet<'a>: Sized + Parse<'a> {
 			if p.at_end() || p.peek_next() == Kind::Eof {
 				return Ok(rules);
 			}
-			rules.push(p.parse::<Self::Rule>()?);
+			let checkpoint = p.checkpoint();
+			match p.parse::<Self::Rule>() {
+				Ok(rule) => rules.push(rule),
+				Err(error) => {
+					p.rewind(checkpoint);
+					let rule = p.parse::<BadRule>()?;
+					p.push_error(error);
+					rules.push(rule.into());
+				}
+			}
 		}
 	}
 }
@@ -1,10 +1,14 @@
 use bumpalo::Bump;
-use clap::{crate_version, Parser, Subcommand};
-use css_ast::StyleSheet;
-use css_parse::{CursorFmtSink, ToCursors};
+use clap::{crate_version, Parser, Subcommand, ValueEnum};
+use css_ast::{
+	ChangeKind, CompatChecker, DeclarationChange, RuleChange, StyleSheet, StyleSheetDiff, Targets, Visit, Visitable,
+};
+use css_lexer::{decode, Encoding, Span};
+use css_parse::{diagnostics, CursorFmtSink, Parse, ToCursors};
+use csskit_highlight::{AnsiRenderer, HtmlRenderer, Theme, TokenHighlighter};
 use csskit_lsp::{LSPService, Server};
 use miette::{GraphicalReportHandler, GraphicalTheme, NamedSource};
-use std::io;
+use std::io::{self, IsTerminal};
 use tracing::{level_filters::LevelFilter, trace};
 use tracing_subscriber::{fmt, layer::SubscriberExt, registry, util::SubscriberInitExt, Layer};
 
@@ -16,19 +20,30 @@ struct Cli {
 
 	#[arg(short, long)]
 	debug: bool,
+
+	/// The encoding to decode files with, if they don't declare one with a byte order mark or @charset rule.
+	/// Defaults to utf-8.
+	#[arg(long, global = true, value_parser = parse_encoding)]
+	encoding: Option<&'static Encoding>,
 }
 
 #[derive(Subcommand, Debug)]
 enum Commands {
-	/// Report potential issues around some CSS files
+	/// Report potential issues around some CSS files, such as deprecated or non-standard features, or those which aren't
+	/// supported by the target browsers.
 	Check {
-		/// A list of CSS files to build. Each input will result in one output file.
+		/// A list of CSS files to check. Files ending in `.scss` are parsed as SCSS.
 		#[arg(required = true, value_parser)]
 		input: Vec<String>,
 
 		/// Automatically apply suggested fixes
 		#[arg(short, long, value_parser)]
 		fix: bool,
+
+		/// The browsers to check support for, each with the oldest version to support, like "chrome 100, safari 15.4".
+		/// Without any, only deprecated, experimental and non-standard features are reported.
+		#[arg(long)]
+		targets: Option<Targets>,
 	},
 
 	/// Format CSS files to make them more readable.
@@ -46,7 +61,7 @@ enum Commands {
 	#[command(hide = true)]
 	/// Show the debug output for a parsed file
 	DbgParse {
-		/// A CSS file to parse.
+		/// A CSS file to parse. Files ending in `.scss` are parsed as SCSS.
 		#[arg(required = true, value_parser)]
 		input: String,
 	},
@@ -54,7 +69,8 @@ enum Commands {
 	/// Convert one or more CSS files into production ready CSS.
 	#[command(arg_required_else_help(true))]
 	Build {
-		/// A list of CSS files to build. Each input will result in one output file.
+		/// A list of CSS files to build. Each input will result in one output file. Files ending in `.scss` are parsed as
+		/// SCSS.
 		#[arg(required = true, value_parser)]
 		input: Vec<String>,
 
@@ -67,37 +83,116 @@ enum Commands {
 		output: Option<String>,
 	},
 
+	/// Compare two CSS files, reporting the rules and declarations which were added, removed or changed. Exits with a
+	/// non-zero status code if there are any differences.
+	Diff {
+		/// The original CSS file.
+		#[arg(required = true, value_parser)]
+		before: String,
+
+		/// The CSS file to compare it with.
+		#[arg(required = true, value_parser)]
+		after: String,
+
+		/// How to report the differences.
+		#[arg(long, value_enum, default_value_t)]
+		format: DiffFormat,
+	},
+
+	/// Print a CSS file with syntax highlighting, either coloured for the terminal or as HTML.
+	Highlight {
+		/// The CSS file to highlight. Files ending in `.scss` are parsed as SCSS.
+		#[arg(required = true, value_parser)]
+		input: String,
+
+		/// How to write the highlighted file.
+		#[arg(long, value_enum, default_value_t)]
+		format: HighlightFormat,
+
+		/// The colours to use for terminal output.
+		#[arg(long, value_enum, default_value_t)]
+		theme: HighlightTheme,
+
+		/// A prefix for each class name in the HTML output.
+		#[arg(long, default_value = "")]
+		class_prefix: String,
+
+		/// The browsers to mark unsupported features for, each with the oldest version to support, like
+		/// "chrome 100, safari 15.4".
+		#[arg(long)]
+		targets: Option<Targets>,
+	},
+
 	/// Run the LSP server. It's unlikely you want to run this, but your IDE might!
 	Lsp {},
 }
 
+#[derive(ValueEnum, Clone, Copy, Debug, Default)]
+enum DiffFormat {
+	/// A line for each difference, indented by how deeply it is nested.
+	#[default]
+	Text,
+	/// The differences as JSON, with byte offsets into each file.
+	#[cfg(feature = "serde")]
+	Json,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, Default)]
+enum HighlightFormat {
+	/// Coloured with ANSI escape codes, if printing to a terminal.
+	#[default]
+	Ansi,
+	/// A `<pre>` element, with a `<span>` around each highlighted token.
+	Html,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, Default)]
+enum HighlightTheme {
+	#[default]
+	Dark,
+	Light,
+}
+
 fn main() {
 	let cli = Cli::parse();
 	let debug = cli.debug;
+	let encoding = cli.encoding;
 
 	match &cli.command {
-		Commands::Check { input, fix } => {
-			todo!("Check ({:?}, {:?})", input, fix);
+		Commands::Check { input, fix, targets } => {
+			if *fix {
+				eprintln!("Warning: fixes not yet supported");
+			}
+			let handler = GraphicalReportHandler::new_themed(GraphicalTheme::unicode_nocolor());
+			let mut found = false;
+			for file_name in input {
+				let source_text = read_source(file_name, encoding);
+				let bump = Bump::default();
+				let mut checker = CompatChecker::new(&source_text, targets.unwrap_or_default());
+				visit_or_exit(file_name, &source_text, &bump, &mut checker);
+				for err in checker.diagnostics {
+					let mut report = String::new();
+					let named = NamedSource::new(file_name, source_text.clone());
+					handler.render_report(&mut report, err.with_source_code(named).as_ref()).unwrap();
+					eprintln!("{}", report);
+					found = true;
+				}
+			}
+			if found {
+				std::process::exit(1);
+			}
 		}
 		Commands::Fmt { input, check } => {
 			todo!("Fmt ({:?}, {:?})", input, check);
 		}
 		Commands::DbgParse { input } => {
-			let source_text = std::fs::read_to_string(input).unwrap();
+			let source_text = read_source(input, encoding);
 			println!("{}", source_text);
 			let bump = Bump::default();
-			let result = css_parse::Parser::new(&bump, source_text.as_str()).parse_entirely::<StyleSheet>();
-			if let Some(stylesheet) = &result.output {
-				println!("{:#?}", stylesheet);
+			if is_scss(input) {
+				dbg_parse::<scss_ast::StyleSheet>(input, &source_text, parser(input, &source_text, &bump));
 			} else {
-				let handler = GraphicalReportHandler::new_themed(GraphicalTheme::unicode_nocolor());
-				for err in result.errors {
-					let mut report = String::new();
-					let named = NamedSource::new(input, source_text.clone());
-					let err = err.with_source_code(named);
-					handler.render_report(&mut report, err.as_ref()).unwrap();
-					println!("{}", report);
-				}
+				dbg_parse::<StyleSheet>(input, &source_text, parser(input, &source_text, &bump));
 			}
 		}
 		Commands::Build { input, minify, output } => {
@@ -106,15 +201,16 @@ fn main() {
 			}
 
 			let file_name = input.first().unwrap();
-			let source_text = std::fs::read_to_string(file_name).unwrap();
+			let source_text = read_source(file_name, encoding);
 			let bump = Bump::default();
 			let start = std::time::Instant::now();
-			let result = css_parse::Parser::new(&bump, source_text.as_str()).parse_entirely::<StyleSheet>();
+			let (built, errors) = if is_scss(file_name) {
+				write_parsed::<scss_ast::StyleSheet>(parser(file_name, &source_text, &bump))
+			} else {
+				write_parsed::<StyleSheet>(parser(file_name, &source_text, &bump))
+			};
 			{
-				if result.output.is_some() {
-					let mut str = String::new();
-					let mut stream = CursorFmtSink::new(source_text.as_str(), &mut str);
-					result.to_cursors(&mut stream);
+				if let Some(str) = built {
 					if let Some(file) = output {
 						std::fs::write(file, str.as_bytes()).unwrap();
 					} else {
@@ -124,18 +220,65 @@ fn main() {
 							eprintln!("Warning: minification not yet supported");
 						}
 					}
-				} else {
-					let handler = GraphicalReportHandler::new_themed(GraphicalTheme::unicode_nocolor());
-					for err in result.errors {
-						let mut report = String::new();
-						let named = NamedSource::new(file_name, source_text.clone());
-						let err = err.with_source_code(named);
-						handler.render_report(&mut report, err.as_ref()).unwrap();
-						println!("{}", report);
-					}
 				}
+				// Errors the parser recovered from are still worth reporting, but don't stop the build.
+				let handler = GraphicalReportHandler::new_themed(GraphicalTheme::unicode_nocolor());
+				for err in errors {
+					let mut report = String::new();
+					let named = NamedSource::new(file_name, source_text.clone());
+					let err = err.with_source_code(named);
+					handler.render_report(&mut report, err.as_ref()).unwrap();
+					eprintln!("{}", report);
+				}
+			}
+		}
+		Commands::Diff { before, after, format } => {
+			let before_text = read_source(before, encoding);
+			let after_text = read_source(after, encoding);
+			let bump = Bump::default();
+			let before_sheet = parse_or_exit::<StyleSheet>(before, &before_text, &bump);
+			let after_sheet = parse_or_exit::<StyleSheet>(after, &after_text, &bump);
+			let diff = StyleSheetDiff::new(&before_sheet, &before_text, &after_sheet, &after_text);
+			match format {
+				DiffFormat::Text => {
+					let files = DiffFiles { before, before_text: &before_text, after, after_text: &after_text };
+					let mut str = String::new();
+					write_rule_changes(&mut str, &diff.rules, &files, 0);
+					print!("{}", str);
+				}
+				#[cfg(feature = "serde")]
+				DiffFormat::Json => println!("{}", serde_json::to_string_pretty(&diff).unwrap()),
+			}
+			if !diff.is_empty() {
+				std::process::exit(1);
 			}
 		}
+		Commands::Highlight { input, format, theme, class_prefix, targets } => {
+			let source_text = read_source(input, encoding);
+			let bump = Bump::default();
+			let mut highlighter = TokenHighlighter::new(&source_text).with_targets(targets.unwrap_or_default());
+			visit_or_exit(input, &source_text, &bump, &mut highlighter);
+			let mut str = String::new();
+			match format {
+				HighlightFormat::Ansi => {
+					let theme = match theme {
+						HighlightTheme::Dark => Theme::dark(),
+						HighlightTheme::Light => Theme::light(),
+					};
+					let renderer = AnsiRenderer::new(theme).with_colors(io::stdout().is_terminal());
+					renderer.render(&source_text, &highlighter, &mut str).unwrap();
+				}
+				HighlightFormat::Html => {
+					str.push_str("<pre><code>");
+					HtmlRenderer::new()
+						.with_class_prefix(class_prefix)
+						.render(&source_text, &highlighter, &mut str)
+						.unwrap();
+					str.push_str("</code></pre>\n");
+				}
+			}
+			print!("{}", str);
+		}
 		Commands::Lsp {} => {
 			let server = Server::new(LSPService::new(crate_version!()));
 			let stderr_log = fmt::layer().with_writer(io::stderr).with_filter(if debug {
@@ -150,3 +293,144 @@ fn main() {
 		}
 	}
 }
+
+fn parse_encoding(label: &str) -> Result<&'static Encoding, String> {
+	Encoding::for_label(label.as_bytes()).ok_or_else(|| format!("{} is not a known encoding", label))
+}
+
+// Reads and decodes a file per the CSS Syntax specification, warning if its @charset disagrees with its contents.
+fn read_source(file_name: &str, encoding: Option<&'static Encoding>) -> String {
+	let bytes = std::fs::read(file_name).unwrap();
+	let decoded = decode(&bytes, encoding);
+	if decoded.charset_mismatch() {
+		let declared = decoded.declared.unwrap().name().to_owned();
+		let err = miette::Report::new(diagnostics::CharsetMismatch(declared, decoded.charset_span));
+		let named = NamedSource::new(file_name, decoded.text.to_string());
+		let handler = GraphicalReportHandler::new_themed(GraphicalTheme::unicode_nocolor());
+		let mut report = String::new();
+		handler.render_report(&mut report, err.with_source_code(named).as_ref()).unwrap();
+		eprintln!("{}", report);
+	}
+	decoded.text.into_owned()
+}
+
+// SCSS files are parsed with the scss_ast dialect, whose nodes visit the CSS within them just as css_ast's do.
+fn is_scss(file_name: &str) -> bool {
+	file_name.ends_with(".scss")
+}
+
+// A parser for the file, with the features its dialect needs.
+fn parser<'a>(file_name: &str, source_text: &'a str, bump: &'a Bump) -> css_parse::Parser<'a> {
+	if is_scss(file_name) {
+		css_parse::Parser::new_with_features(bump, source_text, scss_ast::FEATURES)
+	} else {
+		css_parse::Parser::new(bump, source_text)
+	}
+}
+
+// Parses a file and writes it back out, if it could be parsed, along with any errors the parser recovered from.
+fn write_parsed<'a, T: Parse<'a> + ToCursors>(
+	mut parser: css_parse::Parser<'a>,
+) -> (Option<String>, Vec<miette::Error>) {
+	let result = parser.parse_entirely::<T>();
+	let built = result.output.is_some().then(|| {
+		let mut str = String::new();
+		result.to_cursors(&mut CursorFmtSink::new(result.source_text, &mut str));
+		str
+	});
+	(built, result.errors)
+}
+
+fn dbg_parse<'a, T: Parse<'a> + ToCursors + std::fmt::Debug>(
+	input: &str,
+	source_text: &str,
+	mut parser: css_parse::Parser<'a>,
+) {
+	let result = parser.parse_entirely::<T>();
+	if let Some(stylesheet) = &result.output {
+		println!("{:#?}", stylesheet);
+	} else {
+		let handler = GraphicalReportHandler::new_themed(GraphicalTheme::unicode_nocolor());
+		for err in result.errors {
+			let mut report = String::new();
+			let named = NamedSource::new(input, source_text.to_owned());
+			let err = err.with_source_code(named);
+			handler.render_report(&mut report, err.as_ref()).unwrap();
+			println!("{}", report);
+		}
+	}
+}
+
+// Parses a file, or reports its errors and exits if it can't be parsed at all.
+fn parse_or_exit<'a, T: Parse<'a> + ToCursors>(file_name: &str, source_text: &'a str, bump: &'a Bump) -> T {
+	let result = parser(file_name, source_text, bump).parse_entirely::<T>();
+	if let Some(output) = result.output {
+		return output;
+	}
+	let handler = GraphicalReportHandler::new_themed(GraphicalTheme::unicode_nocolor());
+	for err in result.errors {
+		let mut report = String::new();
+		let named = NamedSource::new(file_name, source_text.to_owned());
+		handler.render_report(&mut report, err.with_source_code(named).as_ref()).unwrap();
+		eprintln!("{}", report);
+	}
+	std::process::exit(2);
+}
+
+// Parses a file as CSS, or as SCSS if it's an SCSS file, and visits it; or reports its errors and exits if it can't be
+// parsed at all.
+fn visit_or_exit<'a, V: Visit<'a>>(file_name: &str, source_text: &'a str, bump: &'a Bump, visitor: &mut V) {
+	if is_scss(file_name) {
+		parse_or_exit::<scss_ast::StyleSheet>(file_name, source_text, bump).accept(visitor);
+	} else {
+		parse_or_exit::<StyleSheet>(file_name, source_text, bump).accept(visitor);
+	}
+}
+
+struct DiffFiles<'a> {
+	before: &'a str,
+	before_text: &'a str,
+	after: &'a str,
+	after_text: &'a str,
+}
+
+impl DiffFiles<'_> {
+	// Where the change is, as `file:line:column` for each file it is in.
+	fn locations(&self, before: Option<Span>, after: Option<Span>) -> String {
+		let location = |file: &str, text: &str, span: Span| {
+			let (line, column) = span.span_contents(text).line_and_column();
+			format!("{}:{}:{}", file, line + 1, column + 1)
+		};
+		let before = before.map(|span| location(self.before, self.before_text, span));
+		let after = after.map(|span| location(self.after, self.after_text, span));
+		before.into_iter().chain(after).collect::<Vec<_>>().join(" -> ")
+	}
+}
+
+fn change_marker(kind: ChangeKind) -> char {
+	match kind {
+		ChangeKind::Added => '+',
+		ChangeKind::Removed => '-',
+		ChangeKind::Changed => '~',
+	}
+}
+
+fn write_rule_changes(str: &mut String, changes: &[RuleChange], files: &DiffFiles, depth: usize) {
+	use std::fmt::Write;
+	let indent = "  ".repeat(depth);
+	for rule in changes {
+		let locations = files.locations(rule.before, rule.after);
+		writeln!(str, "{}{} {} ({})", indent, change_marker(rule.kind), rule.key, locations).unwrap();
+		for declaration in &rule.declarations {
+			let DeclarationChange { kind, name, before, after, before_value, after_value } = declaration;
+			let value = match (before_value, after_value) {
+				(Some(before), Some(after)) => format!("{} -> {}", before, after),
+				(Some(value), None) | (None, Some(value)) => value.clone(),
+				(None, None) => String::new(),
+			};
+			let locations = files.locations(*before, *after);
+			writeln!(str, "{}  {} {}: {} ({})", indent, change_marker(*kind), name, value, locations).unwrap();
+		}
+		write_rule_changes(str, &rule.rules, files, depth + 1);
+	}
+}
@@ -1,12 +1,191 @@
+use bumpalo::Bump;
 use css_ast::{
-	Property, PropertyRule, PropertyRuleProperty, PropertyRuleStyleValue, PseudoClass, StyleDeclaration, StyleValue,
-	Tag, Visit,
+	Attribute, Class, ColorFunction, ColorKeyword, ColorMix, Combinator, Compat, CompatStatus, FontFaceRuleProperty,
+	FunctionalPseudoClass, FunctionalPseudoElement, Id, KeyframeSelector, LegacyPseudoElement, NamedColor, Namespace,
+	Property, PropertyRule, PropertyRuleProperty, PropertyRuleStyleValue, PseudoClass, PseudoElement, StyleDeclaration,
+	StyleSheet, StyleValue, SystemColor, Tag, UnknownAtRule, Visit, Wildcard,
 };
-use css_lexer::Span;
+use css_lexer::{Cursor, Kind, SourceOffset, Span};
+use css_parse::{syntax::BangImportant, Parser, Peek, ToCursors};
 
 use crate::{SemanticKind, SemanticModifier, TokenHighlighter};
 
-impl<'a> Visit<'a> for TokenHighlighter {
+// The cursors of a node, for nodes which are highlighted token by token.
+fn cursors_of(node: &impl ToCursors) -> Vec<Cursor> {
+	let mut cursors = vec![];
+	node.to_cursors(&mut cursors);
+	cursors
+}
+
+// The span from the start of the first cursor to the end of the last.
+fn span_of(cursors: &[Cursor]) -> Option<Span> {
+	Some(cursors.first()?.span() + cursors.last()?.span())
+}
+
+impl TokenHighlighter<'_> {
+	// Custom names start with two dashes, while vendor prefixed names start with just the one.
+	fn name_modifier(&self, c: Cursor) -> SemanticModifier {
+		if !matches!(c.token().kind(), Kind::Ident | Kind::Function | Kind::AtKeyword) {
+			return SemanticModifier::none();
+		}
+		let name = c.str_slice(self.source_text).trim_start_matches('@');
+		if name.starts_with("--") {
+			SemanticModifier::Custom
+		} else if name.starts_with('-') {
+			SemanticModifier::Vendor
+		} else {
+			SemanticModifier::none()
+		}
+	}
+
+	fn compat_modifier(&self, compat: Option<&Compat>) -> SemanticModifier {
+		let mut modifier = SemanticModifier::none();
+		let Some(compat) = compat else { return modifier };
+		if compat.status.contains(CompatStatus::Deprecated) {
+			modifier |= SemanticModifier::Deprecated;
+		}
+		if compat.status.contains(CompatStatus::Experimental) {
+			modifier |= SemanticModifier::Experimental;
+		}
+		if compat.status.contains(CompatStatus::VendorOnly) {
+			modifier |= SemanticModifier::Vendor;
+		}
+		if !compat.is_supported_by(&self.targets) {
+			modifier |= SemanticModifier::Unsupported;
+		}
+		modifier
+	}
+
+	fn name(&self, span: Span) -> String {
+		span.span_contents(self.source_text).contents().to_ascii_lowercase()
+	}
+
+	fn at_keyword_modifier(&self, c: Cursor) -> SemanticModifier {
+		let name = self.name(c.span());
+		self.name_modifier(c) | self.compat_modifier(Compat::at_rule(name.trim_start_matches('@')))
+	}
+
+	fn is_color(&self, p: &Parser, c: Cursor) -> bool {
+		match c.token().kind() {
+			Kind::Hash => true,
+			Kind::Ident => ColorKeyword::peek(p, c) || NamedColor::peek(p, c) || SystemColor::peek(p, c),
+			Kind::Function => ColorFunction::peek(p, c) || ColorMix::peek(p, c),
+			_ => false,
+		}
+	}
+
+	// Highlights a token which is part of a value, or a similar token in an at-rule prelude. Dimensions are split in two
+	// so that the unit can be highlighted apart from the number.
+	fn highlight_value_token(&mut self, p: &Parser, c: Cursor) {
+		let modifier = self.name_modifier(c);
+		match c.token().kind() {
+			Kind::Ident if c.token().is_dashed_ident() => {
+				self.insert(c.into(), SemanticKind::StyleValueVariable, modifier)
+			}
+			Kind::Ident if self.is_color(p, c) => self.insert(c.into(), SemanticKind::StyleValueColor, modifier),
+			Kind::Ident => self.insert(c.into(), SemanticKind::StyleValueKeyword, modifier),
+			Kind::Number => self.insert(c.into(), SemanticKind::StyleValueNumber, modifier),
+			Kind::Dimension => {
+				let unit_start = SourceOffset(c.offset().0 + c.token().numeric_len());
+				let none = SemanticModifier::none();
+				self.insert(Span::new(c.offset(), unit_start), SemanticKind::StyleValueDimension, none);
+				self.insert(Span::new(unit_start, c.end_offset()), SemanticKind::StyleValueUnit, none);
+			}
+			Kind::Hash => self.insert(c.into(), SemanticKind::StyleValueColor, modifier),
+			Kind::Function if self.is_color(p, c) => self.insert(c.into(), SemanticKind::StyleValueColor, modifier),
+			Kind::Function => self.insert(c.into(), SemanticKind::StyleValueFunction, modifier),
+			Kind::String => self.insert(c.into(), SemanticKind::StyleValueString, modifier),
+			Kind::Url | Kind::BadUrl => self.insert(c.into(), SemanticKind::StyleValueUrl, modifier),
+			_ => {}
+		}
+	}
+
+	fn highlight_important(&mut self, important: &Option<BangImportant>) {
+		if let Some(span) = important.as_ref().and_then(|important| span_of(&cursors_of(important))) {
+			self.insert(span, SemanticKind::StyleValueKeyword, SemanticModifier::none());
+		}
+	}
+
+	// Highlights the opening colon & function of a functional pseudo class or element, along with its closing paren;
+	// whatever is in between is left to the visitors of the nodes inside.
+	// These are looked up in the compat data by their name, which is everything up to the function's open paren.
+	fn highlight_function_parts(&mut self, cursors: &[Cursor], kind: SemanticKind) {
+		if let Some(function) = cursors.iter().position(|c| c.token().kind() == Kind::Function) {
+			let Some(span) = span_of(&cursors[..=function]) else { return };
+			let modifier = self.compat_modifier(Compat::selector(self.name(span).trim_end_matches('(')));
+			self.insert(span, kind, modifier);
+			if let Some(close) =
+				cursors.last().filter(|c| function < cursors.len() - 1 && c.token().kind() == Kind::RightParen)
+			{
+				self.insert(close.into(), kind, modifier);
+			}
+		}
+	}
+}
+
+impl<'a> Visit<'a> for TokenHighlighter<'_> {
+	// At-rules have many different kinds of prelude, so rather than visiting each these are highlighted token by token:
+	// from each at-keyword up to the block or semicolon that ends its prelude. Nodes inside the preludes which are
+	// visited later (such as the properties of `@supports`) replace these highlights with more specific ones, apart from
+	// selectors (as in `@supports selector(...)`) which are skipped entirely, being left to the selector visitors.
+	fn visit_style_sheet(&mut self, stylesheet: &StyleSheet<'a>) {
+		let bump = Bump::default();
+		let p = Parser::new(&bump, self.source_text);
+		let cursors = cursors_of(stylesheet);
+		let mut in_prelude = false;
+		let mut depth = 0;
+		let mut selector_depth = None;
+		for (i, c) in cursors.iter().enumerate() {
+			let c = *c;
+			match c.token().kind() {
+				Kind::Function | Kind::LeftParen => depth += 1,
+				Kind::RightParen => depth -= 1,
+				_ => {}
+			}
+			if let Some(selector_depth) = selector_depth {
+				if depth >= selector_depth {
+					continue;
+				}
+			}
+			selector_depth = None;
+			match c.token().kind() {
+				Kind::AtKeyword => {
+					self.insert(c.into(), SemanticKind::AtKeyword, self.at_keyword_modifier(c));
+					in_prelude = true;
+				}
+				Kind::LeftCurly | Kind::RightCurly => {
+					self.insert(c.into(), SemanticKind::Punctuation, SemanticModifier::none());
+					in_prelude = false;
+				}
+				Kind::Semicolon => in_prelude = false,
+				_ if !in_prelude => {}
+				Kind::Function if p.eq_ignore_ascii_case(c, "selector") => {
+					self.insert(c.into(), SemanticKind::StyleValueFunction, SemanticModifier::none());
+					selector_depth = Some(depth);
+				}
+				Kind::Ident => {
+					let kind = if cursors.get(i + 1).is_some_and(|next| next.token().kind() == Kind::Colon) {
+						SemanticKind::Declaration
+					} else {
+						SemanticKind::Prelude
+					};
+					self.insert(c.into(), kind, self.name_modifier(c));
+				}
+				Kind::Colon if i > 0 && cursors[i - 1].token().kind() == Kind::Ident => {
+					self.insert(c.into(), SemanticKind::Punctuation, SemanticModifier::none())
+				}
+				_ => self.highlight_value_token(&p, c),
+			}
+		}
+	}
+
+	fn visit_unknown_at_rule(&mut self, rule: &UnknownAtRule<'a>) {
+		if let Some(at_keyword) = cursors_of(rule).first() {
+			let modifier = self.at_keyword_modifier(*at_keyword) | SemanticModifier::Unknown;
+			self.insert(at_keyword.into(), SemanticKind::AtKeyword, modifier);
+		}
+	}
+
 	fn visit_tag(&mut self, tag: &Tag) {
 		let span: Span = (*tag).into();
 		let mut modifier = SemanticModifier::none();
@@ -30,18 +209,93 @@ impl<'a> Visit<'a> for TokenHighlighter {
 		self.insert(span, SemanticKind::Tag, modifier);
 	}
 
+	fn visit_id(&mut self, id: &Id) {
+		if let Some(span) = span_of(&cursors_of(id)) {
+			self.insert(span, SemanticKind::Id, SemanticModifier::none());
+		}
+	}
+
+	fn visit_class(&mut self, class: &Class) {
+		if let Some(span) = span_of(&cursors_of(class)) {
+			self.insert(span, SemanticKind::Class, SemanticModifier::none());
+		}
+	}
+
+	fn visit_wildcard(&mut self, wildcard: &Wildcard) {
+		if let Some(span) = span_of(&cursors_of(wildcard)) {
+			self.insert(span, SemanticKind::Wildcard, SemanticModifier::none());
+		}
+	}
+
+	fn visit_combinator(&mut self, combinator: &Combinator) {
+		// The descendant combinator is whitespace, so there is nothing to highlight.
+		if matches!(combinator, Combinator::Descendant(_)) {
+			return;
+		}
+		if let Some(span) = span_of(&cursors_of(combinator)) {
+			self.insert(span, SemanticKind::Combinator, SemanticModifier::none());
+		}
+	}
+
+	fn visit_attribute(&mut self, attribute: &Attribute) {
+		if let Some(span) = span_of(&cursors_of(attribute)) {
+			self.insert(span, SemanticKind::Attribute, SemanticModifier::none());
+		}
+	}
+
+	fn visit_namespace(&mut self, namespace: &Namespace) {
+		if let Some(span) = span_of(&cursors_of(namespace)) {
+			self.insert(span, SemanticKind::Namespace, SemanticModifier::none());
+		}
+	}
+
 	fn visit_pseudo_class(&mut self, class: &PseudoClass) {
 		let span: Span = class.into();
 		let mut modifier = SemanticModifier::none();
 		match class {
 			PseudoClass::Webkit(_) | PseudoClass::Moz(_) | PseudoClass::O(_) | PseudoClass::Ms(_) => {
-				modifier |= SemanticModifier::Deprecated;
+				modifier |= SemanticModifier::Deprecated | SemanticModifier::Vendor;
 			}
 			_ => {}
 		}
+		modifier |= self.compat_modifier(Compat::selector(&self.name(span)));
 		self.insert(span, SemanticKind::PseudoClass, modifier);
 	}
 
+	fn visit_pseudo_element(&mut self, element: &PseudoElement) {
+		let mut modifier = SemanticModifier::none();
+		match element {
+			PseudoElement::Webkit(_) | PseudoElement::Moz(_) | PseudoElement::O(_) | PseudoElement::Ms(_) => {
+				modifier |= SemanticModifier::Vendor;
+			}
+			_ => {}
+		}
+		if let Some(span) = span_of(&cursors_of(element)) {
+			modifier |= self.compat_modifier(Compat::selector(&self.name(span)));
+			self.insert(span, SemanticKind::PseudoElement, modifier);
+		}
+	}
+
+	fn visit_legacy_pseudo_element(&mut self, element: &LegacyPseudoElement) {
+		if let Some(span) = span_of(&cursors_of(element)) {
+			self.insert(span, SemanticKind::LegacyPseudoElement, SemanticModifier::Deprecated);
+		}
+	}
+
+	fn visit_functional_pseudo_class(&mut self, class: &FunctionalPseudoClass<'a>) {
+		self.highlight_function_parts(&cursors_of(class), SemanticKind::FunctionalPseudoClass);
+	}
+
+	fn visit_functional_pseudo_element(&mut self, element: &FunctionalPseudoElement<'a>) {
+		self.highlight_function_parts(&cursors_of(element), SemanticKind::FunctionalPseudoElement);
+	}
+
+	fn visit_keyframe_selector(&mut self, selector: &KeyframeSelector) {
+		if let Some(span) = span_of(&cursors_of(selector)) {
+			self.insert(span, SemanticKind::Prelude, SemanticModifier::none());
+		}
+	}
+
 	fn visit_style_declaration(&mut self, rule: &StyleDeclaration<'a>) {
 		self.insert(rule.open.into(), SemanticKind::Punctuation, SemanticModifier::none());
 		if let Some(close) = rule.close {
@@ -51,15 +305,37 @@ impl<'a> Visit<'a> for TokenHighlighter {
 
 	fn visit_property(&mut self, property: &Property<'a>) {
 		let span: Span = property.name.into();
-		let mut modifier = SemanticModifier::none();
+		let name = self.name(span);
+		let mut modifier = self.name_modifier(property.name.into()) | self.compat_modifier(Compat::property(&name));
 		if matches!(&property.value, StyleValue::Unknown(_)) {
 			modifier |= SemanticModifier::Unknown;
 		}
-		if property.name.is_dashed_ident() {
-			modifier |= SemanticModifier::Custom;
-		}
 		self.insert(span, SemanticKind::Declaration, modifier);
+		self.property_name = Some(name);
+		self.insert(property.colon.into(), SemanticKind::Punctuation, SemanticModifier::none());
+		self.highlight_important(&property.important);
+	}
+
+	fn visit_style_value(&mut self, value: &StyleValue<'a>) {
+		let bump = Bump::default();
+		let p = Parser::new(&bump, self.source_text);
+		let property_name = self.property_name.take();
+		for c in cursors_of(value) {
+			self.highlight_value_token(&p, c);
+			if let Some(property_name) = property_name.as_deref().filter(|_| c.token().kind() == Kind::Ident) {
+				let compat = Compat::value(property_name, &self.name(c.span()));
+				let modifier = self.compat_modifier(compat);
+				if let Some(highlight) = self.highlights.get_mut(&c.span()) {
+					highlight.modifier |= modifier;
+				}
+			}
+		}
+	}
+
+	fn visit_font_face_rule_property(&mut self, property: &FontFaceRuleProperty<'a>) {
+		self.insert(property.name.into(), SemanticKind::Declaration, self.name_modifier(property.name.into()));
 		self.insert(property.colon.into(), SemanticKind::Punctuation, SemanticModifier::none());
+		self.highlight_important(&property.important);
 	}
 
 	fn visit_property_rule(&mut self, property: &PropertyRule<'a>) {
@@ -1,10 +1,14 @@
 use bitmask_enum::bitmask;
 use core::fmt;
-use css_lexer::Span;
+use css_ast::Targets;
+use css_lexer::{SourceOffset, Span};
 use std::collections::HashMap;
 use strum::{Display, VariantNames};
 
+pub use render::*;
+
 mod css;
+mod render;
 #[cfg(test)]
 mod test_helpers;
 #[cfg(test)]
@@ -36,6 +40,12 @@ pub enum SemanticKind {
 	StyleValueKeyword,
 	StyleValueDimension,
 	StyleValueNumber,
+	StyleValueUnit,
+	StyleValueColor,
+	StyleValueFunction,
+	StyleValueString,
+	StyleValueUrl,
+	StyleValueVariable,
 
 	Punctuation,
 }
@@ -55,6 +65,7 @@ pub enum SemanticModifier {
 	Experimental,
 	Vendor,
 	Custom,
+	Unsupported,
 }
 
 impl fmt::Display for SemanticModifier {
@@ -68,12 +79,15 @@ impl fmt::Display for SemanticModifier {
 		if self.contains(Self::Experimental) {
 			write!(f, " experimental")?;
 		}
-		if self.contains(Self::Experimental) {
+		if self.contains(Self::Vendor) {
 			write!(f, " vendor")?;
 		}
 		if self.contains(Self::Custom) {
 			write!(f, " custom")?;
 		}
+		if self.contains(Self::Unsupported) {
+			write!(f, " unsupported")?;
+		}
 		Ok(())
 	}
 }
@@ -102,14 +116,28 @@ impl Highlight {
 	}
 }
 
+/// Collects a [Highlight] for each token of a node as it's visited. The source text the node was parsed from is needed to
+/// tell apart tokens of the same kind, such as a color keyword from any other keyword.
+///
+/// Properties, value keywords, selectors and at-rules are also looked up in the [css_ast::Compat] data, to mark them as
+/// deprecated, experimental or vendor only; and, given some [Targets], as unsupported when any target lacks them.
 #[derive(Default)]
-pub struct TokenHighlighter {
+pub struct TokenHighlighter<'s> {
+	source_text: &'s str,
+	targets: Targets,
+	// The (lowercase) name of the property whose value is about to be visited, to look up its keywords by.
+	property_name: Option<String>,
 	highlights: HashMap<Span, Highlight>,
 }
 
-impl TokenHighlighter {
-	pub fn new() -> Self {
-		Self { highlights: HashMap::new() }
+impl<'s> TokenHighlighter<'s> {
+	pub fn new(source_text: &'s str) -> Self {
+		Self { source_text, targets: Targets::default(), property_name: None, highlights: HashMap::new() }
+	}
+
+	pub fn with_targets(mut self, targets: Targets) -> Self {
+		self.targets = targets;
+		self
 	}
 
 	pub fn get(&self, span: Span) -> Option<&Highlight> {
@@ -121,6 +149,10 @@ impl TokenHighlighter {
 	}
 
 	fn insert(&mut self, span: Span, kind: SemanticKind, modifier: SemanticModifier) {
+		// Tokens which the parser made up to recover from an error have no place in the source text to highlight.
+		if span.start() == SourceOffset::DUMMY || span.end() == SourceOffset::DUMMY {
+			return;
+		}
 		self.highlights.insert(span, Highlight { span, kind, modifier });
 	}
 }
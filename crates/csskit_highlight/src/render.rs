@@ -0,0 +1,263 @@
+use crate::{Highlight, SemanticKind, SemanticModifier, TokenHighlighter};
+use console::Style;
+use core::fmt;
+use std::collections::HashMap;
+
+const MODIFIERS: [(SemanticModifier, &str); 6] = [
+	(SemanticModifier::Unknown, "unknown"),
+	(SemanticModifier::Deprecated, "deprecated"),
+	(SemanticModifier::Experimental, "experimental"),
+	(SemanticModifier::Vendor, "vendor"),
+	(SemanticModifier::Custom, "custom"),
+	(SemanticModifier::Unsupported, "unsupported"),
+];
+
+// Splits the source text into runs of text, each either covered by a highlight or between highlights. Highlights which
+// overlap an earlier one are dropped, so every byte of the source text is in exactly one run.
+fn for_each_run<'s>(
+	source_text: &'s str,
+	highlighter: &TokenHighlighter,
+	mut f: impl FnMut(&'s str, Option<&Highlight>) -> fmt::Result,
+) -> fmt::Result {
+	let mut highlights = highlighter.highlights().collect::<Vec<_>>();
+	highlights.sort_by_key(|highlight| (highlight.span().start(), highlight.span().end()));
+	let mut pos = 0;
+	for highlight in highlights {
+		let start = highlight.span().start().0 as usize;
+		let end = highlight.span().end().0 as usize;
+		if start < pos || end > source_text.len() || start == end {
+			continue;
+		}
+		if start > pos {
+			f(&source_text[pos..start], None)?;
+		}
+		f(&source_text[start..end], Some(highlight))?;
+		pos = end;
+	}
+	if pos < source_text.len() {
+		f(&source_text[pos..], None)?;
+	}
+	Ok(())
+}
+
+/// Renders source text as HTML, wrapping each highlighted token in a `<span>` with a class for its [SemanticKind]
+/// and one for each of its [SemanticModifiers][SemanticModifier]. Everything else is escaped but otherwise written as
+/// is, so the output keeps the formatting of the source text; it's up to the caller to wrap it in a `<pre>` or similar.
+///
+/// By default the class of a kind is its name in kebab-case (for example `pseudo-class`), and the class of a modifier
+/// is its name in lowercase (for example `deprecated`). Either can be changed per kind or modifier, and all of them can
+/// be given a common prefix.
+///
+/// # Example
+///
+/// ```
+/// use csskit_highlight::{HtmlRenderer, SemanticKind, TokenHighlighter};
+/// use css_ast::{StyleSheet, Visitable};
+/// use css_parse::Parser;
+/// use bumpalo::Bump;
+/// let bump = Bump::default();
+/// let source_text = "a { color: red }";
+/// let stylesheet = Parser::new(&bump, source_text).parse_entirely::<StyleSheet>().output.unwrap();
+/// let mut highlighter = TokenHighlighter::new(source_text);
+/// stylesheet.accept(&mut highlighter);
+///
+/// let mut html = String::new();
+/// HtmlRenderer::new().with_class_prefix("hl-").render(source_text, &highlighter, &mut html).unwrap();
+/// assert!(html.starts_with(r#"<span class="hl-tag">a</span> <span class="hl-punctuation">{</span>"#));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct HtmlRenderer {
+	prefix: String,
+	kinds: HashMap<SemanticKind, String>,
+	modifiers: HashMap<SemanticModifier, String>,
+}
+
+impl HtmlRenderer {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Prefix every class name with the given text, such as `hl-`.
+	pub fn with_class_prefix(mut self, prefix: &str) -> Self {
+		self.prefix = prefix.into();
+		self
+	}
+
+	/// Use the given class name for tokens of the given [SemanticKind].
+	pub fn with_kind_class(mut self, kind: SemanticKind, class: &str) -> Self {
+		self.kinds.insert(kind, class.into());
+		self
+	}
+
+	/// Use the given class name for tokens with the given [SemanticModifier], which should be a single modifier.
+	pub fn with_modifier_class(mut self, modifier: SemanticModifier, class: &str) -> Self {
+		self.modifiers.insert(modifier, class.into());
+		self
+	}
+
+	/// The classes of a token of the given kind & modifiers, separated by spaces.
+	pub fn class_names(&self, kind: SemanticKind, modifier: SemanticModifier) -> String {
+		let mut classes = self.prefix.clone();
+		match self.kinds.get(&kind) {
+			Some(class) => classes.push_str(class),
+			None => {
+				for (i, ch) in kind.to_string().char_indices() {
+					if i > 0 && ch.is_ascii_uppercase() {
+						classes.push('-');
+					}
+					classes.push(ch.to_ascii_lowercase());
+				}
+			}
+		}
+		for (flag, name) in MODIFIERS {
+			if modifier.contains(flag) {
+				classes.push(' ');
+				classes.push_str(&self.prefix);
+				classes.push_str(self.modifiers.get(&flag).map_or(name, String::as_str));
+			}
+		}
+		classes
+	}
+
+	pub fn render(&self, source_text: &str, highlighter: &TokenHighlighter, f: &mut impl fmt::Write) -> fmt::Result {
+		for_each_run(source_text, highlighter, |text, highlight| {
+			if let Some(highlight) = highlight {
+				write!(f, r#"<span class="{}">"#, self.class_names(highlight.kind(), highlight.modifier()))?;
+				write_escaped(text, f)?;
+				f.write_str("</span>")
+			} else {
+				write_escaped(text, f)
+			}
+		})
+	}
+}
+
+fn write_escaped(text: &str, f: &mut impl fmt::Write) -> fmt::Result {
+	for ch in text.chars() {
+		match ch {
+			'&' => f.write_str("&amp;")?,
+			'<' => f.write_str("&lt;")?,
+			'>' => f.write_str("&gt;")?,
+			'"' => f.write_str("&quot;")?,
+			'\'' => f.write_str("&#39;")?,
+			_ => f.write_char(ch)?,
+		}
+	}
+	Ok(())
+}
+
+/// The colours used by an [AnsiRenderer], one [Style] per [SemanticKind]. Modifiers are shown the same way in every
+/// theme: unknown tokens are dimmed, deprecated ones struck through, experimental ones underlined, vendor prefixed
+/// ones in bold, custom ones in italics, and those unsupported by the highlighter's targets in reverse video.
+#[derive(Debug, Clone, Default)]
+pub struct Theme {
+	kinds: HashMap<SemanticKind, Style>,
+}
+
+impl Theme {
+	/// A theme for terminals with a dark background.
+	pub fn dark() -> Self {
+		Self::default()
+			.with_kind(SemanticKind::Tag, Style::new().green())
+			.with_kind(SemanticKind::Id, Style::new().yellow())
+			.with_kind(SemanticKind::Class, Style::new().yellow())
+			.with_kind(SemanticKind::PseudoClass, Style::new().cyan())
+			.with_kind(SemanticKind::PseudoElement, Style::new().cyan())
+			.with_kind(SemanticKind::AtKeyword, Style::new().magenta())
+			.with_kind(SemanticKind::Declaration, Style::new().blue().bright())
+			.with_kind(SemanticKind::StyleValueKeyword, Style::new().cyan().bright())
+			.with_kind(SemanticKind::StyleValueDimension, Style::new().yellow().bright())
+			.with_kind(SemanticKind::StyleValueNumber, Style::new().yellow().bright())
+			.with_kind(SemanticKind::StyleValueUnit, Style::new().yellow())
+			.with_kind(SemanticKind::StyleValueColor, Style::new().red().bright())
+			.with_kind(SemanticKind::StyleValueFunction, Style::new().blue().bright())
+			.with_kind(SemanticKind::StyleValueString, Style::new().green().bright())
+			.with_kind(SemanticKind::StyleValueUrl, Style::new().green().bright())
+			.with_kind(SemanticKind::StyleValueVariable, Style::new().magenta().bright())
+			.with_kind(SemanticKind::Prelude, Style::new().cyan().bright())
+			.with_kind(SemanticKind::Punctuation, Style::new().white())
+	}
+
+	/// A theme for terminals with a light background.
+	pub fn light() -> Self {
+		Self::default()
+			.with_kind(SemanticKind::Tag, Style::new().green())
+			.with_kind(SemanticKind::Id, Style::new().red())
+			.with_kind(SemanticKind::Class, Style::new().red())
+			.with_kind(SemanticKind::PseudoClass, Style::new().cyan())
+			.with_kind(SemanticKind::PseudoElement, Style::new().cyan())
+			.with_kind(SemanticKind::AtKeyword, Style::new().magenta())
+			.with_kind(SemanticKind::Declaration, Style::new().blue())
+			.with_kind(SemanticKind::StyleValueKeyword, Style::new().cyan())
+			.with_kind(SemanticKind::StyleValueDimension, Style::new().red())
+			.with_kind(SemanticKind::StyleValueNumber, Style::new().red())
+			.with_kind(SemanticKind::StyleValueUnit, Style::new().red())
+			.with_kind(SemanticKind::StyleValueColor, Style::new().magenta())
+			.with_kind(SemanticKind::StyleValueFunction, Style::new().blue())
+			.with_kind(SemanticKind::StyleValueString, Style::new().green())
+			.with_kind(SemanticKind::StyleValueUrl, Style::new().green())
+			.with_kind(SemanticKind::StyleValueVariable, Style::new().magenta())
+			.with_kind(SemanticKind::Prelude, Style::new().cyan())
+			.with_kind(SemanticKind::Punctuation, Style::new().black())
+	}
+
+	/// Use the given [Style] for tokens of the given [SemanticKind].
+	pub fn with_kind(mut self, kind: SemanticKind, style: Style) -> Self {
+		self.kinds.insert(kind, style);
+		self
+	}
+
+	pub fn style(&self, kind: SemanticKind, modifier: SemanticModifier) -> Style {
+		let mut style = self.kinds.get(&kind).cloned().unwrap_or_default();
+		if modifier.contains(SemanticModifier::Unknown) {
+			style = style.dim();
+		}
+		if modifier.contains(SemanticModifier::Deprecated) {
+			style = style.strikethrough();
+		}
+		if modifier.contains(SemanticModifier::Experimental) {
+			style = style.underlined();
+		}
+		if modifier.contains(SemanticModifier::Vendor) {
+			style = style.bold();
+		}
+		if modifier.contains(SemanticModifier::Custom) {
+			style = style.italic();
+		}
+		if modifier.contains(SemanticModifier::Unsupported) {
+			style = style.reverse();
+		}
+		style
+	}
+}
+
+/// Renders source text for a terminal, colouring each highlighted token with ANSI escape codes according to a
+/// [Theme]. As with the [HtmlRenderer] the source text is otherwise written as is.
+#[derive(Debug, Clone)]
+pub struct AnsiRenderer {
+	theme: Theme,
+	colors: bool,
+}
+
+impl AnsiRenderer {
+	pub fn new(theme: Theme) -> Self {
+		Self { theme, colors: true }
+	}
+
+	/// Whether to write escape codes at all (they are written by default). This can be used to turn them off when the
+	/// output isn't a terminal, leaving the source text as it was.
+	pub fn with_colors(mut self, colors: bool) -> Self {
+		self.colors = colors;
+		self
+	}
+
+	pub fn render(&self, source_text: &str, highlighter: &TokenHighlighter, f: &mut impl fmt::Write) -> fmt::Result {
+		for_each_run(source_text, highlighter, |text, highlight| match highlight {
+			Some(highlight) if self.colors => {
+				let style = self.theme.style(highlight.kind(), highlight.modifier()).force_styling(true);
+				write!(f, "{}", style.apply_to(text))
+			}
+			_ => f.write_str(text),
+		})
+	}
+}
@@ -9,15 +9,29 @@ pub(crate) struct HTMLHighlightCursorStream<'a, T> {
 	writer: T,
 	last_token: Option<Token>,
 	err: Option<fmt::Error>,
-	pub highlighter: TokenHighlighter,
+	// The end of a highlight which covers more than one cursor, and so is still open.
+	open_until: Option<u32>,
+	pub highlighter: TokenHighlighter<'a>,
 }
 
 impl<'a, T: fmt::Write> HTMLHighlightCursorStream<'a, T> {
 	pub fn new(source_text: &'a str, writer: T) -> Self {
-		Self { source_text, writer, last_token: None, err: None, highlighter: TokenHighlighter::new() }
+		Self {
+			source_text,
+			writer,
+			last_token: None,
+			err: None,
+			open_until: None,
+			highlighter: TokenHighlighter::new(source_text),
+		}
 	}
 
 	pub fn finish(&mut self) {
+		if self.err.is_none() && self.open_until.take().is_some() {
+			if let Err(err) = self.writer.write_str("</span>") {
+				self.err = Some(err);
+			}
+		}
 		if self.err.is_none() {
 			if let Err(err) = self.writer.write_str(
 				r#"
@@ -61,33 +75,55 @@ impl<'a, T: fmt::Write> CursorSink for HTMLHighlightCursorStream<'a, T> {
 		if self.err.is_some() {
 			return;
 		}
+		if let Err(err) = self.write_cursor(c) {
+			self.err = Some(err);
+		}
+	}
+}
+
+impl<'a, T: fmt::Write> HTMLHighlightCursorStream<'a, T> {
+	fn write_cursor(&mut self, c: Cursor) -> fmt::Result {
+		if self.open_until.is_some_and(|end| c.offset().0 >= end) {
+			self.open_until = None;
+			self.writer.write_str("</span>")?;
+		}
 		if let Some(last) = self.last_token {
 			if last.needs_separator_for(c.into()) {
-				if let Err(err) = self.writer.write_char(' ') {
-					self.err = Some(err);
-				}
+				self.writer.write_char(' ')?;
 			}
 		}
-		if self.err.is_some() {
-			return;
-		}
 		self.last_token = Some(c.into());
-		let highlight = self.highlighter.get(c.into());
-		if let Some(highlight) = highlight {
-			if let Err(err) =
-				self.writer.write_str(format!(r#"<span class="{}{}">"#, highlight.kind, highlight.modifier).as_str())
-			{
-				self.err = Some(err);
-			}
-		}
-		if let Err(err) = c.write_str(self.source_text, &mut self.writer) {
-			self.err = Some(err);
+		// A highlight may cover part of a token (such as the unit of a dimension), or more than one token (such as the
+		// colon & name of a pseudo class), so the token is written in parts around the highlights which start within it.
+		let mut highlights = self
+			.highlighter
+			.highlights()
+			.filter(|h| h.span().start() >= c.offset() && h.span().start() < c.end_offset())
+			.copied()
+			.collect::<Vec<_>>();
+		if highlights.is_empty() {
+			return c.write_str(self.source_text, &mut self.writer);
 		}
-		if highlight.is_some() {
-			if let Err(err) = self.writer.write_str(r#"</span>"#) {
-				self.err = Some(err);
+		highlights.sort_by_key(|h| h.span());
+		let token_end = c.end_offset().0 as usize;
+		let mut pos = c.offset().0 as usize;
+		for highlight in highlights {
+			let start = highlight.span().start().0 as usize;
+			let end = highlight.span().end().0 as usize;
+			if start < pos || self.open_until.is_some() {
+				continue;
+			}
+			self.writer.write_str(&self.source_text[pos..start])?;
+			write!(self.writer, r#"<span class="{}{}">"#, highlight.kind, highlight.modifier)?;
+			pos = end.min(token_end);
+			self.writer.write_str(&self.source_text[start..pos])?;
+			if end > token_end {
+				self.open_until = Some(end as u32);
+			} else {
+				self.writer.write_str("</span>")?;
 			}
 		}
+		self.writer.write_str(&self.source_text[pos..token_end])
 	}
 }
 
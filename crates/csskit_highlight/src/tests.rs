@@ -1,5 +1,6 @@
 use super::test_helpers::*;
 use super::*;
+use console::Style;
 
 #[test]
 fn test_basic() {
@@ -27,3 +28,147 @@ fn test_nesting() {
 fn test_pseudo_classes() {
 	assert_highlight!("pseudo_classes", r#"body:focus,dialog:modal{}"#);
 }
+
+#[test]
+fn test_selectors() {
+	assert_highlight!(
+		"selectors",
+		r#"#id.class > *[href^="x"] + svg|a ~ p::before, :is(a, b)::-webkit-scrollbar, a:before {}"#
+	);
+}
+
+#[test]
+fn test_values() {
+	assert_highlight!(
+		"values",
+		r#"a {
+			width: calc(100% - 2.5em);
+			color: rgb(0 0 0 / 50%);
+			background: #fff url(foo.png) no-repeat;
+			border-color: red currentcolor;
+			font-family: "Helvetica", sans-serif;
+			margin: var(--gap, 0) -webkit-fill-available !important;
+			--gap: 1px;
+			line-height: 1.5;
+		}"#,
+	);
+}
+
+#[test]
+fn test_at_rules() {
+	assert_highlight!(
+		"at_rules",
+		r#"@charset "utf-8";
+		@layer reset, base;
+		@media screen and (min-width: 600px) { a { color: red } }
+		@supports (display: grid) { a { display: grid } }
+		@keyframes spin { from { opacity: 0 } 50% { opacity: 1 } }
+		@font-face { font-family: Foo; }
+		@madeup foo;"#,
+	);
+}
+
+#[test]
+fn test_compat() {
+	assert_highlight!(
+		"compat",
+		r#"@custom-media --narrow (max-width: 30em);
+		a::-moz-selection, a:has(b) {
+			page-break-after: always;
+			display: -webkit-box;
+			-webkit-font-smoothing: antialiased;
+		}"#,
+	);
+}
+
+#[test]
+fn test_compat_targets() {
+	use bumpalo::Bump;
+	use css_ast::{StyleSheet, Visitable};
+	use css_parse::Parser;
+
+	let source_text = "a:has(b) { accent-color: red; display: contents }";
+	let bump = Bump::default();
+	let stylesheet = Parser::new(&bump, source_text).parse_entirely::<StyleSheet>().output.unwrap();
+	let mut highlighter = TokenHighlighter::new(source_text).with_targets("safari 15, firefox 120".parse().unwrap());
+	stylesheet.accept(&mut highlighter);
+	let mut unsupported = highlighter
+		.highlights()
+		.filter(|highlight| highlight.modifier().contains(SemanticModifier::Unsupported))
+		.map(|highlight| highlight.span().span_contents(source_text).contents())
+		.collect::<Vec<_>>();
+	unsupported.sort();
+	assert_eq!(unsupported, [")", ":has(", "accent-color"]);
+}
+
+fn highlight(source_text: &str, f: impl FnOnce(&TokenHighlighter) -> String) -> String {
+	use bumpalo::Bump;
+	use css_ast::{StyleSheet, Visitable};
+	use css_parse::Parser;
+
+	let bump = Bump::default();
+	let stylesheet = Parser::new(&bump, source_text).parse_entirely::<StyleSheet>().output.unwrap();
+	let mut highlighter = TokenHighlighter::new(source_text);
+	stylesheet.accept(&mut highlighter);
+	f(&highlighter)
+}
+
+#[test]
+fn test_html_renderer() {
+	let source_text = "a > b:hover {\n\tcolor: red; /* <b> */\n}";
+	let html = highlight(source_text, |highlighter| {
+		let mut html = String::new();
+		HtmlRenderer::new().render(source_text, highlighter, &mut html).unwrap();
+		html
+	});
+	assert_eq!(
+		html,
+		"<span class=\"tag\">a</span> <span class=\"combinator\">&gt;</span> <span class=\"tag\">b</span><span class=\"pseudo-class\">:hover</span> \
+		<span class=\"punctuation\">{</span>\n\t<span class=\"declaration\">color</span><span class=\"punctuation\">:</span> \
+		<span class=\"style-value-color\">red</span>; /* &lt;b&gt; */\n<span class=\"punctuation\">}</span>"
+	);
+}
+
+#[test]
+fn test_html_renderer_class_names() {
+	let renderer = HtmlRenderer::new()
+		.with_class_prefix("hl-")
+		.with_kind_class(SemanticKind::Tag, "element")
+		.with_modifier_class(SemanticModifier::Deprecated, "old");
+	assert_eq!(renderer.class_names(SemanticKind::Tag, SemanticModifier::none()), "hl-element");
+	assert_eq!(
+		renderer.class_names(SemanticKind::PseudoClass, SemanticModifier::Deprecated | SemanticModifier::Custom),
+		"hl-pseudo-class hl-old hl-custom"
+	);
+}
+
+#[test]
+fn test_theme_modifiers() {
+	let theme = Theme::dark();
+	assert_eq!(theme.style(SemanticKind::Tag, SemanticModifier::none()), Style::new().green());
+	assert_eq!(theme.style(SemanticKind::Tag, SemanticModifier::Vendor), Style::new().green().bold());
+	assert_eq!(
+		theme.style(SemanticKind::Tag, SemanticModifier::Deprecated | SemanticModifier::Vendor),
+		Style::new().green().strikethrough().bold()
+	);
+}
+
+#[test]
+fn test_ansi_renderer() {
+	let source_text = "madeup { color: red }";
+	let ansi = highlight(source_text, |highlighter| {
+		let mut ansi = String::new();
+		AnsiRenderer::new(Theme::dark()).render(source_text, highlighter, &mut ansi).unwrap();
+		ansi
+	});
+	assert_eq!(
+		ansi,
+		"\u{1b}[32m\u{1b}[2mmadeup\u{1b}[0m \u{1b}[37m{\u{1b}[0m \u{1b}[38;5;12mcolor\u{1b}[0m\u{1b}[37m:\u{1b}[0m \u{1b}[38;5;9mred\u{1b}[0m \u{1b}[37m}\u{1b}[0m"
+	);
+	let plain = highlight(source_text, |highlighter| {
+		let mut plain = String::new();
+		AnsiRenderer::new(Theme::dark()).with_colors(false).render(source_text, highlighter, &mut plain).unwrap();
+		plain
+	});
+	assert_eq!(plain, source_text);
+}
@@ -0,0 +1,94 @@
+use bumpalo::Bump;
+use css_ast::{StyleSheet, Visitable};
+use css_parse::Parser;
+use csskit_highlight::TokenHighlighter;
+use std::{collections::BTreeMap, fmt::Write, fs::read_to_string};
+
+// Highlights a file from the popular corpus, checking that every highlight lies within the source text and that none
+// of them overlap, and then summarises the highlights as a count of each kind & modifier.
+fn summarise(source_path: &str) -> String {
+	let bump = Bump::default();
+	let source_text = read_to_string(source_path).unwrap();
+	let result = Parser::new(&bump, &source_text).parse_entirely::<StyleSheet>();
+	let stylesheet = result.output.unwrap_or_else(|| panic!("Parse {:?} failed", source_path));
+	let mut highlighter = TokenHighlighter::new(&source_text);
+	stylesheet.accept(&mut highlighter);
+
+	let mut highlights = highlighter.highlights().collect::<Vec<_>>();
+	highlights.sort_by_key(|highlight| highlight.span());
+	let mut counts = BTreeMap::new();
+	let mut last_end = 0;
+	for highlight in highlights {
+		let span = highlight.span();
+		assert!(span.end().0 as usize <= source_text.len(), "{:?} is outside of {:?}", highlight, source_path);
+		assert!(span.start().0 >= last_end, "{:?} overlaps a previous highlight in {:?}", highlight, source_path);
+		last_end = span.end().0;
+		*counts.entry(format!("{}{}", highlight.kind(), highlight.modifier())).or_insert(0) += 1;
+	}
+	let mut summary = String::new();
+	for (name, count) in counts {
+		writeln!(summary, "{name}: {count}").unwrap();
+	}
+	summary
+}
+
+macro_rules! assert_highlight_summary {
+	($name: literal, $source_path: literal) => {
+		insta::assert_snapshot!($name, summarise($source_path));
+	};
+}
+
+#[test]
+fn popular_960() {
+	assert_highlight_summary!("960", "../../tasks/coverage/popular/960.css");
+}
+
+#[test]
+fn popular_animate() {
+	assert_highlight_summary!("animate", "../../tasks/coverage/popular/animate.4.1.1.css");
+}
+
+#[test]
+fn popular_blueprint() {
+	assert_highlight_summary!("blueprint", "../../tasks/coverage/popular/blueprint.1.0.1.css");
+}
+
+#[test]
+fn popular_bootstrap() {
+	assert_highlight_summary!("bootstrap", "../../tasks/coverage/popular/bootstrap.5.3.0.css");
+}
+
+#[test]
+fn popular_foundation() {
+	assert_highlight_summary!("foundation", "../../tasks/coverage/popular/foundation.6.7.5.css");
+}
+
+#[test]
+fn popular_inuitcss() {
+	assert_highlight_summary!("inuitcss", "../../tasks/coverage/popular/inuitcss.6.0.0.css");
+}
+
+#[test]
+fn popular_mini() {
+	assert_highlight_summary!("mini", "../../tasks/coverage/popular/mini.css.3.0.1.css");
+}
+
+#[test]
+fn popular_open_props() {
+	assert_highlight_summary!("open_props", "../../tasks/coverage/popular/open-props.1.5.10.min.css");
+}
+
+#[test]
+fn popular_primer() {
+	assert_highlight_summary!("primer", "../../tasks/coverage/popular/primer.21.5.1.css");
+}
+
+#[test]
+fn popular_pure() {
+	assert_highlight_summary!("pure", "../../tasks/coverage/popular/pure.2.0.3.css");
+}
+
+#[test]
+fn popular_reset() {
+	assert_highlight_summary!("reset", "../../tasks/coverage/popular/reset.2.0.css");
+}
@@ -0,0 +1,436 @@
+use css_ast::{
+	ImportLayer, ImportRule, KeyframesName, KeyframesRule, LayerName, LayerRule, OptionalLayerRuleBlock, Property,
+	StyleRule, Visit, WebkitKeyframesRule,
+};
+use css_lexer::{Cursor, Kind, SourceOffset, Span};
+use css_parse::ToCursors;
+use lsp_types::{CodeLens, Command, Range};
+use std::collections::{HashMap, HashSet};
+
+fn span(node: &impl ToCursors) -> Option<Span> {
+	let mut cursors: Vec<Cursor> = vec![];
+	node.to_cursors(&mut cursors);
+	Some(cursors.first()?.span() + cursors.last()?.span())
+}
+
+// Selectors are compared by their text, ignoring the whitespace which doesn't change what they match.
+fn selector_key(text: &str) -> String {
+	let mut key = String::new();
+	for word in text.split_whitespace() {
+		let after_combinator = key.ends_with(['>', '+', '~']);
+		if !key.is_empty() && !after_combinator && !word.starts_with(['>', '+', '~']) {
+			key.push(' ');
+		}
+		key.push_str(word);
+	}
+	key
+}
+
+// The keys of a nested selector, which are the same as if it were written out in full: each `&` is replaced with each
+// of the parent rule's selectors, while a selector without one is relative to them.
+fn nested_selector_keys(parents: &[String], key: &str) -> Vec<String> {
+	parents
+		.iter()
+		.map(|parent| {
+			if key.contains('&') {
+				key.replace('&', parent)
+			} else if key.starts_with(['>', '+', '~']) {
+				format!("{}{}", parent, key)
+			} else {
+				format!("{} {}", parent, key)
+			}
+		})
+		.collect()
+}
+
+/// A name which rules define and other rules refer to, and which code lenses count the uses of.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Symbol {
+	/// A selector, such as `.button:hover`.
+	Selector(String),
+	/// The name of a `@keyframes` animation.
+	Keyframes(String),
+	/// The full name of a cascade layer, such as `base.reset`.
+	Layer(String),
+	/// The name of a custom property, including the leading `--`.
+	CustomProperty(String),
+}
+
+/// Collects the symbols of a stylesheet: where each is defined, and each place it is referred to.
+#[derive(Default)]
+pub struct SymbolCollector<'a> {
+	source_text: &'a str,
+	/// The rules and declarations which define symbols: style rules define their selectors, while `@keyframes`,
+	/// `@layer` blocks and custom property declarations each define a name.
+	pub definitions: Vec<(Span, Vec<Symbol>)>,
+	/// The symbols referred to: animations named by `animation` and `animation-name`, layers named by `@layer`
+	/// statements and `@import`, and custom properties read by `var()`.
+	pub references: Vec<Symbol>,
+	/// Each `@layer` statement, which declares the order of layers without a block.
+	pub layer_statements: Vec<Span>,
+	/// The full name of each layer, in the order they're first declared.
+	pub layers: Vec<String>,
+	// The `@layer` blocks the visitor is within, with where each ends. Anonymous layers have no name, so neither can
+	// any layer within them.
+	parents: Vec<(SourceOffset, Option<String>)>,
+	// The style rules the visitor is within, with where each ends and the keys of their selectors.
+	rules: Vec<(SourceOffset, Vec<String>)>,
+}
+
+impl<'a> SymbolCollector<'a> {
+	pub fn new(source_text: &'a str) -> Self {
+		Self { source_text, ..Default::default() }
+	}
+
+	fn keyframes(&mut self, rule: &impl ToCursors, name: &KeyframesName) {
+		let name = Cursor::from(*name).str_slice(self.source_text).trim_matches(['"', '\'']).to_string();
+		if let Some(span) = span(rule) {
+			self.definitions.push((span, vec![Symbol::Keyframes(name)]));
+		}
+	}
+
+	// Declare a layer, and each of the layers it's nested in, returning its full name. Layers within an anonymous layer
+	// can't be named.
+	fn declare_layer(&mut self, name: &LayerName) -> Option<String> {
+		let text = span(name)?.span_contents(self.source_text).contents();
+		let mut full_name = match self.parents.last() {
+			Some((_, Some(parent))) => parent.clone(),
+			Some((_, None)) => return None,
+			None => String::new(),
+		};
+		for part in text.split('.') {
+			if !full_name.is_empty() {
+				full_name.push('.');
+			}
+			full_name.push_str(part.trim());
+			if !self.layers.contains(&full_name) {
+				self.layers.push(full_name.clone());
+			}
+		}
+		Some(full_name)
+	}
+}
+
+impl<'a> Visit<'a> for SymbolCollector<'_> {
+	fn visit_style_rule(&mut self, rule: &StyleRule<'a>) {
+		let Some(rule_span) = span(rule) else { return };
+		while self.rules.last().is_some_and(|(end, _)| *end <= rule_span.start()) {
+			self.rules.pop();
+		}
+		let keys =
+			rule.selectors.0.iter().filter_map(|(selector, _)| {
+				Some(selector_key(span(selector)?.span_contents(self.source_text).contents()))
+			});
+		let keys = match self.rules.last() {
+			Some((_, parents)) => keys.flat_map(|key| nested_selector_keys(parents, &key)).collect::<Vec<_>>(),
+			None => keys.collect(),
+		};
+		self.definitions.push((rule_span, keys.iter().cloned().map(Symbol::Selector).collect()));
+		self.rules.push((rule_span.end(), keys));
+	}
+
+	fn visit_keyframes_rule(&mut self, rule: &KeyframesRule<'a>) {
+		if let Some(name) = &rule.name {
+			self.keyframes(rule, name);
+		}
+	}
+
+	fn visit_webkit_keyframes_rule(&mut self, rule: &WebkitKeyframesRule<'a>) {
+		self.keyframes(rule, &rule.name);
+	}
+
+	fn visit_layer_rule(&mut self, rule: &LayerRule<'a>) {
+		let Some(span) = span(rule) else { return };
+		while self.parents.last().is_some_and(|(end, _)| *end <= span.start()) {
+			self.parents.pop();
+		}
+		let names = rule.names.iter().flat_map(|names| &names.0).map(|(name, _)| name);
+		let names = names.filter_map(|name| self.declare_layer(name)).collect::<Vec<_>>();
+		match &rule.block {
+			OptionalLayerRuleBlock::None(_) => {
+				self.layer_statements.push(span);
+				self.references.extend(names.into_iter().map(Symbol::Layer));
+			}
+			OptionalLayerRuleBlock::Block(_) => {
+				// A layer block has a single name; blocks without one are anonymous layers.
+				let name = names.into_iter().next();
+				if let Some(name) = &name {
+					self.definitions.push((span, vec![Symbol::Layer(name.clone())]));
+				}
+				let anonymous = rule.names.is_none();
+				if name.is_some() || anonymous {
+					self.parents.push((span.end(), name));
+				}
+			}
+		}
+	}
+
+	fn visit_import_rule(&mut self, rule: &ImportRule<'a>) {
+		if let Some(ImportLayer::Named(_, name, _)) = &rule.layer {
+			if let Some(name) = self.declare_layer(name) {
+				self.references.push(Symbol::Layer(name));
+			}
+		}
+	}
+
+	fn visit_property(&mut self, property: &Property<'a>) {
+		let name = Cursor::from(property.name).str_slice(self.source_text);
+		if name.starts_with("--") {
+			if let Some(span) = span(property) {
+				self.definitions.push((span, vec![Symbol::CustomProperty(name.to_string())]));
+			}
+		}
+		let unprefixed = name.to_ascii_lowercase();
+		let unprefixed = ["-webkit-", "-moz-", "-o-"]
+			.iter()
+			.fold(unprefixed.as_str(), |name, prefix| name.strip_prefix(prefix).unwrap_or(name));
+		let animation = matches!(unprefixed, "animation" | "animation-name");
+		let mut cursors: Vec<Cursor> = vec![];
+		property.value.to_cursors(&mut cursors);
+		let mut depth = 0;
+		for (i, c) in cursors.iter().enumerate() {
+			match c.token().kind() {
+				Kind::Function if c.str_slice(self.source_text).eq_ignore_ascii_case("var(") => {
+					if let Some(name) = cursors.get(i + 1).filter(|c| **c == Kind::Ident) {
+						self.references.push(Symbol::CustomProperty(name.str_slice(self.source_text).to_string()));
+					}
+					depth += 1;
+				}
+				Kind::Function | Kind::LeftParen => depth += 1,
+				Kind::RightParen => depth -= 1,
+				// Any other keyword of the `animation` shorthand, such as `ease` or `infinite`, can only be miscounted
+				// as a reference if there's also a `@keyframes` of that name, which `animation` wouldn't refer to.
+				Kind::Ident | Kind::String if animation && depth == 0 => {
+					let name = c.str_slice(self.source_text).trim_matches(['"', '\'']);
+					self.references.push(Symbol::Keyframes(name.to_string()));
+				}
+				_ => {}
+			}
+		}
+	}
+}
+
+/// The symbols of a document, as collected by [SymbolCollector], with the range of each rule or declaration they're
+/// defined by.
+#[derive(Debug, Default, Clone)]
+pub struct DocumentSymbols {
+	pub definitions: Vec<(Range, Vec<Symbol>)>,
+	pub references: Vec<Symbol>,
+	pub layer_statements: Vec<Range>,
+	pub layers: Vec<String>,
+}
+
+fn plural(count: usize, singular: &str, plural: &str) -> String {
+	format!("{} {}", count, if count == 1 { singular } else { plural })
+}
+
+fn lens(range: Range, title: String) -> CodeLens {
+	let range = Range::new(range.start, range.start);
+	CodeLens { range, command: Some(Command { title, command: String::new(), arguments: None }), data: None }
+}
+
+/// The code lenses for a document, counting the uses of its symbols across it and every other document in the
+/// workspace:
+///
+/// - Above each style rule, how many other rules have any of the same selectors.
+/// - Above each `@keyframes`, `@layer` block and custom property, how many times its name is referred to.
+/// - Above each `@layer` statement, the order of the document's layers, where a layer comes before the layers it's
+///   nested in.
+pub fn code_lenses(document: &DocumentSymbols, others: &[DocumentSymbols]) -> Vec<CodeLens> {
+	// Rules are identified by the index of their document, where this document is the first, and their own index.
+	let mut rules: HashMap<&str, HashSet<(usize, usize)>> = HashMap::new();
+	let mut references: HashMap<&Symbol, usize> = HashMap::new();
+	for (d, symbols) in std::iter::once(document).chain(others).enumerate() {
+		for (i, (_, definitions)) in symbols.definitions.iter().enumerate() {
+			for definition in definitions {
+				if let Symbol::Selector(selector) = definition {
+					rules.entry(selector).or_default().insert((d, i));
+				}
+			}
+		}
+		for reference in &symbols.references {
+			*references.entry(reference).or_default() += 1;
+		}
+	}
+	let mut lenses = vec![];
+	for (i, (range, definitions)) in document.definitions.iter().enumerate() {
+		let title = match definitions.first() {
+			Some(Symbol::Selector(_)) => {
+				let mut matching: HashSet<(usize, usize)> = HashSet::new();
+				for definition in definitions {
+					if let Symbol::Selector(selector) = definition {
+						matching.extend(rules.get(selector.as_str()).into_iter().flatten());
+					}
+				}
+				matching.remove(&(0, i));
+				if definitions.len() > 1 {
+					plural(matching.len(), "other rule targets these selectors", "other rules target these selectors")
+				} else {
+					plural(matching.len(), "other rule targets this selector", "other rules target this selector")
+				}
+			}
+			Some(symbol) => plural(references.get(symbol).copied().unwrap_or(0), "reference", "references"),
+			None => continue,
+		};
+		lenses.push(lens(*range, title));
+	}
+	if !document.layer_statements.is_empty() {
+		let order = layer_order(&document.layers).join(", ");
+		for range in &document.layer_statements {
+			lenses.push(lens(*range, format!("Layer order: {}", order)));
+		}
+	}
+	lenses.sort_by_key(|lens| lens.range.start);
+	lenses
+}
+
+// Layers are ordered by when they're first declared, but nested layers are ordered within the layer they're nested in,
+// which itself comes after all of them.
+fn layer_order(layers: &[String]) -> Vec<&str> {
+	let index = |name: &str| layers.iter().position(|layer| layer == name).unwrap_or(usize::MAX);
+	let mut keys = layers
+		.iter()
+		.map(|layer| {
+			let mut key = layer.match_indices('.').map(|(i, _)| index(&layer[..i])).collect::<Vec<_>>();
+			key.push(index(layer));
+			(key, layer.as_str())
+		})
+		.collect::<Vec<_>>();
+	keys.sort_by(|(a, _), (b, _)| {
+		// A layer with the same prefix as another is nested in it, so comes first.
+		let common = a.len().min(b.len());
+		a[..common].cmp(&b[..common]).then(b.len().cmp(&a.len()))
+	});
+	keys.into_iter().map(|(_, layer)| layer).collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use bumpalo::Bump;
+	use css_ast::{StyleSheet, Visitable};
+	use css_parse::Parser;
+	use lsp_types::Position;
+
+	fn collect(source_text: &str) -> SymbolCollector {
+		let bump = Bump::default();
+		let stylesheet = Parser::new(&bump, source_text).parse_entirely::<StyleSheet>().output.unwrap();
+		let mut collector = SymbolCollector::new(source_text);
+		stylesheet.accept(&mut collector);
+		collector
+	}
+
+	#[test]
+	fn test_selector_key() {
+		assert_eq!(selector_key("a   b"), "a b");
+		assert_eq!(selector_key("a > b"), "a>b");
+		assert_eq!(selector_key("a>b ~ c +d"), "a>b~c+d");
+		assert_eq!(selector_key(".a:hover"), ".a:hover");
+	}
+
+	#[test]
+	fn test_symbols() {
+		let collector = collect(concat!(
+			"@import url(a.css) layer(base.reset);\n",
+			"@layer base, components;\n",
+			"@layer components { @layer buttons { .a, .b > .c { color: var(--x); animation: 1s spin infinite } } }\n",
+			"@keyframes spin { to { --x: red } }\n",
+			"@-webkit-keyframes \"spin\" {}\n",
+			"@layer { @layer hidden {} }\n",
+		));
+		let definitions = collector.definitions.into_iter().map(|(_, symbols)| symbols).collect::<Vec<_>>();
+		assert_eq!(
+			definitions,
+			[
+				vec![Symbol::Layer("components".into())],
+				vec![Symbol::Layer("components.buttons".into())],
+				vec![Symbol::Selector(".a".into()), Symbol::Selector(".b>.c".into())],
+				vec![Symbol::Keyframes("spin".into())],
+				vec![Symbol::CustomProperty("--x".into())],
+				vec![Symbol::Keyframes("spin".into())],
+			]
+		);
+		assert_eq!(
+			collector.references,
+			[
+				Symbol::Layer("base.reset".into()),
+				Symbol::Layer("base".into()),
+				Symbol::Layer("components".into()),
+				Symbol::CustomProperty("--x".into()),
+				Symbol::Keyframes("spin".into()),
+				Symbol::Keyframes("infinite".into()),
+			]
+		);
+		assert_eq!(collector.layer_statements.len(), 1);
+		assert_eq!(collector.layers, ["base", "base.reset", "components", "components.buttons"]);
+	}
+
+	#[test]
+	fn test_nested_selectors() {
+		let collector = collect(concat!(
+			".a, .b { &:hover { color: red } .icon { color: red } }\n",
+			".c { & + & { color: red } > .d { & .e { color: red } } }\n",
+			".f:hover { color: red }\n",
+		));
+		let definitions = collector.definitions.into_iter().map(|(_, symbols)| symbols).collect::<Vec<_>>();
+		let selectors = |keys: &[&str]| keys.iter().map(|key| Symbol::Selector(key.to_string())).collect::<Vec<_>>();
+		assert_eq!(
+			definitions,
+			[
+				selectors(&[".a", ".b"]),
+				selectors(&[".a:hover", ".b:hover"]),
+				selectors(&[".a .icon", ".b .icon"]),
+				selectors(&[".c"]),
+				selectors(&[".c+.c"]),
+				selectors(&[".c>.d"]),
+				selectors(&[".c>.d .e"]),
+				selectors(&[".f:hover"]),
+			]
+		);
+	}
+
+	#[test]
+	fn test_layer_order() {
+		let layers = ["base", "base.reset", "components", "base.type", "components.buttons", "utilities"];
+		let layers = layers.map(String::from);
+		assert_eq!(
+			layer_order(&layers),
+			["base.reset", "base.type", "base", "components.buttons", "components", "utilities"]
+		);
+	}
+
+	fn titles(document: &DocumentSymbols, others: &[DocumentSymbols]) -> Vec<(u32, String)> {
+		let lenses = code_lenses(document, others);
+		lenses.into_iter().map(|lens| (lens.range.start.line, lens.command.unwrap().title)).collect()
+	}
+
+	#[test]
+	fn test_code_lenses() {
+		let range = |line| Range::new(Position::new(line, 0), Position::new(line, 10));
+		let a = DocumentSymbols {
+			definitions: vec![
+				(range(0), vec![Symbol::Selector(".a".into())]),
+				(range(1), vec![Symbol::Selector(".a".into()), Symbol::Selector(".b".into())]),
+				(range(2), vec![Symbol::Keyframes("spin".into())]),
+			],
+			references: vec![Symbol::Keyframes("spin".into())],
+			layer_statements: vec![range(3)],
+			layers: vec!["base".into(), "components".into()],
+		};
+		let b = DocumentSymbols {
+			definitions: vec![(range(0), vec![Symbol::Selector(".b".into())])],
+			references: vec![Symbol::Keyframes("spin".into())],
+			..Default::default()
+		};
+		assert_eq!(
+			titles(&a, &[b.clone()]),
+			[
+				(0, "1 other rule targets this selector".into()),
+				(1, "2 other rules target these selectors".into()),
+				(2, "2 references".into()),
+				(3, "Layer order: base, components".into()),
+			]
+		);
+		assert_eq!(titles(&b, &[a]), [(0, "1 other rule targets this selector".to_string())]);
+	}
+}
@@ -0,0 +1,146 @@
+use css_ast::Targets;
+use serde::{de::Error, Deserialize, Deserializer};
+use serde_json::Value;
+
+/// The name of the project config file, read from the root of each workspace folder.
+pub const CONFIG_FILE_NAME: &str = "csskit.json";
+
+/// How the server checks a project; read from a [CONFIG_FILE_NAME] file, and from the client's
+/// `csskit` settings, which take precedence.
+///
+/// ```json
+/// {
+///   "targets": "chrome 100, firefox 115, safari 15.4",
+///   "lint": { "experimental": false },
+///   "inlayHints": { "shorthands": false }
+/// }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Config {
+	/// The browsers that need to be supported, written as for `csskit check --targets`.
+	#[serde(deserialize_with = "deserialize_targets")]
+	pub targets: Targets,
+	pub lint: LintConfig,
+	pub inlay_hints: InlayHintsConfig,
+}
+
+/// Which kinds of lint diagnostic to report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct LintConfig {
+	pub deprecated: bool,
+	pub experimental: bool,
+	pub non_standard: bool,
+	pub unsupported: bool,
+	/// Local files referred to by `@import` or `url()` which don't exist.
+	pub missing_files: bool,
+}
+
+impl Default for LintConfig {
+	fn default() -> Self {
+		Self { deprecated: true, experimental: true, non_standard: true, unsupported: true, missing_files: true }
+	}
+}
+
+impl LintConfig {
+	/// Whether a diagnostic with the given code should be reported. Diagnostics which aren't lints, such as parse
+	/// errors, always are.
+	pub fn is_enabled(&self, code: &str) -> bool {
+		match code {
+			"css_ast::DeprecatedFeature" => self.deprecated,
+			"css_ast::ExperimentalFeature" => self.experimental,
+			"css_ast::VendorOnlyFeature" => self.non_standard,
+			"css_ast::UnsupportedFeature" => self.unsupported,
+			"csskit_lsp::MissingFile" => self.missing_files,
+			_ => true,
+		}
+	}
+}
+
+/// Which kinds of inlay hint to show.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct InlayHintsConfig {
+	/// The specificity of each selector of a style rule.
+	pub specificity: bool,
+	/// The value of each `var()`, for custom properties defined in `:root`.
+	pub custom_properties: bool,
+	/// The result of each value which is a math function that simplifies to a single value, such as `calc(8px * 2)`.
+	pub calc: bool,
+	/// The longhands each shorthand expands to.
+	pub shorthands: bool,
+}
+
+impl Default for InlayHintsConfig {
+	fn default() -> Self {
+		Self { specificity: true, custom_properties: true, calc: true, shorthands: true }
+	}
+}
+
+fn deserialize_targets<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Targets, D::Error> {
+	String::deserialize(deserializer)?.parse().map_err(D::Error::custom)
+}
+
+impl Config {
+	/// Build the config from the project config file's contents and the client's settings, either of which may be
+	/// null. Settings are merged key by key over the project config.
+	pub fn from_values(project: &Value, settings: &Value) -> Result<Self, serde_json::Error> {
+		let mut value = project.clone();
+		merge(&mut value, settings);
+		if value.is_null() {
+			return Ok(Self::default());
+		}
+		Self::deserialize(value)
+	}
+}
+
+fn merge(base: &mut Value, overrides: &Value) {
+	match (base, overrides) {
+		(Value::Object(base), Value::Object(overrides)) => {
+			for (key, value) in overrides {
+				merge(base.entry(key).or_insert(Value::Null), value);
+			}
+		}
+		(_, Value::Null) => {}
+		(base, overrides) => *base = overrides.clone(),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use css_ast::{Browser, Version};
+	use serde_json::json;
+
+	#[test]
+	fn test_defaults() {
+		assert_eq!(Config::from_values(&Value::Null, &Value::Null).unwrap(), Config::default());
+		assert_eq!(Config::from_values(&json!({}), &Value::Null).unwrap(), Config::default());
+		assert!(Config::default().lint.is_enabled("css_ast::DeprecatedFeature"));
+	}
+
+	#[test]
+	fn test_from_values() {
+		let project = json!({ "targets": "safari 15.4", "lint": { "experimental": false } });
+		let config = Config::from_values(&project, &Value::Null).unwrap();
+		assert_eq!(config.targets, Targets::default().with_browser(Browser::Safari, Version(15, 4)));
+		assert!(!config.lint.is_enabled("css_ast::ExperimentalFeature"));
+		assert!(config.lint.is_enabled("css_ast::DeprecatedFeature"));
+		assert!(config.lint.is_enabled("css_parse::Unexpected"));
+		assert!(config.lint.is_enabled("csskit_lsp::MissingFile"));
+
+		let settings = json!({ "lint": { "deprecated": false } });
+		let config = Config::from_values(&project, &settings).unwrap();
+		assert_eq!(config.targets, Targets::default().with_browser(Browser::Safari, Version(15, 4)));
+		assert!(!config.lint.is_enabled("css_ast::ExperimentalFeature"));
+		assert!(!config.lint.is_enabled("css_ast::DeprecatedFeature"));
+		assert_eq!(config.inlay_hints, InlayHintsConfig::default());
+
+		let settings = json!({ "inlayHints": { "specificity": false, "calc": false } });
+		let inlay_hints = Config::from_values(&project, &settings).unwrap().inlay_hints;
+		assert_eq!(inlay_hints, InlayHintsConfig { specificity: false, calc: false, ..Default::default() });
+
+		assert!(Config::from_values(&json!({ "targets": "netscape 4" }), &Value::Null).is_err());
+	}
+}
@@ -0,0 +1,188 @@
+use bumpalo::Bump;
+use css_ast::{FontFaceRuleProperty, ImportRule, NamespaceRule, Property, Url, Visit};
+use css_lexer::{Cursor, Kind, SourceOffset, Span};
+use css_parse::{Parser, ToCursors};
+use lsp_types::Uri;
+use std::{
+	path::{Component, Path, PathBuf},
+	str::FromStr,
+};
+
+use crate::uri_to_path;
+
+/// Collects the URLs a stylesheet refers to: the targets of `@import` and `@namespace`, along with each `url()` in a
+/// declaration, such as `background-image` or the `src` of `@font-face`. Each is the span of the URL itself, without
+/// any quotes or `url(`, and its text.
+#[derive(Default)]
+pub struct UrlCollector<'a> {
+	source_text: &'a str,
+	pub urls: Vec<(Span, String)>,
+}
+
+impl<'a> UrlCollector<'a> {
+	pub fn new(source_text: &'a str) -> Self {
+		Self { source_text, urls: vec![] }
+	}
+
+	fn push(&mut self, url: &Url, offset: SourceOffset, source_text: &str) {
+		let span = url.value_span(source_text);
+		let span = Span::new(SourceOffset(span.start().0 + offset.0), SourceOffset(span.end().0 + offset.0));
+		self.urls.push((span, url.value(source_text).to_string()));
+	}
+
+	// Values aren't modelled in enough detail to find the URLs within them, so find them among the tokens; either a
+	// url token, or a `url(` function around a string.
+	fn collect_value(&mut self, value: &impl ToCursors) {
+		let mut cursors: Vec<Cursor> = vec![];
+		value.to_cursors(&mut cursors);
+		let bump = Bump::default();
+		for (i, c) in cursors.iter().enumerate() {
+			let end = match c.token().kind() {
+				Kind::Url => c,
+				Kind::Function if c.str_slice(self.source_text).eq_ignore_ascii_case("url(") => {
+					match (cursors.get(i + 1), cursors.get(i + 2)) {
+						(Some(string), Some(close)) if *string == Kind::String && *close == Kind::RightParen => close,
+						_ => continue,
+					}
+				}
+				_ => continue,
+			};
+			let span = c.span() + end.span();
+			let text = span.span_contents(self.source_text).contents();
+			if let Some(url) = Parser::new(&bump, text).parse_entirely::<Url>().output {
+				self.push(&url, span.start(), text);
+			}
+		}
+	}
+}
+
+impl<'a> Visit<'a> for UrlCollector<'_> {
+	fn visit_import_rule(&mut self, rule: &ImportRule<'a>) {
+		self.push(&rule.url, SourceOffset(0), self.source_text);
+	}
+
+	fn visit_namespace_rule(&mut self, rule: &NamespaceRule) {
+		self.push(&rule.url, SourceOffset(0), self.source_text);
+	}
+
+	fn visit_property(&mut self, property: &Property<'a>) {
+		self.collect_value(&property.value);
+	}
+
+	fn visit_font_face_rule_property(&mut self, property: &FontFaceRuleProperty<'a>) {
+		self.collect_value(&property.value);
+	}
+}
+
+/// What a URL refers to, once resolved against the stylesheet it's written in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UrlTarget {
+	/// A file on disk, which may not exist.
+	File(PathBuf),
+	/// Anything fetched from elsewhere, such as `https:` URLs.
+	Remote(Uri),
+}
+
+impl UrlTarget {
+	/// Resolve a URL against the path of the stylesheet it's written in. URLs starting with `/` are relative to the
+	/// root of the site, which is taken to be the workspace folder; without one they can't be resolved. Fragment-only
+	/// URLs, as used by SVG filters, and `data:` URLs aren't links, so resolve to nothing.
+	pub fn resolve(url: &str, document: &Path, root: Option<&Path>) -> Option<Self> {
+		let url = url.trim();
+		if url.is_empty() || url.starts_with('#') {
+			return None;
+		}
+		if let Some(url) = url.strip_prefix("//") {
+			return Uri::from_str(&format!("https://{}", url)).ok().map(Self::Remote);
+		}
+		if let Some((scheme, _)) = url.split_once(':') {
+			if scheme.len() > 1 && scheme.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.')) {
+				let uri = Uri::from_str(url).ok()?;
+				return match scheme.to_ascii_lowercase().as_str() {
+					"http" | "https" => Some(Self::Remote(uri)),
+					"file" => uri_to_path(&uri).map(Self::File),
+					_ => None,
+				};
+			}
+		}
+		let path = url.split(['?', '#']).next().unwrap_or_default();
+		let path = match path.strip_prefix('/') {
+			Some(path) => root?.join(path),
+			None => document.parent()?.join(path),
+		};
+		Some(Self::File(normalize(&path)))
+	}
+}
+
+// Remove the `.` and `..` components of a path, without touching the disk as canonicalizing would.
+fn normalize(path: &Path) -> PathBuf {
+	let mut normalized = PathBuf::new();
+	for component in path.components() {
+		match component {
+			Component::CurDir => {}
+			Component::ParentDir => {
+				normalized.pop();
+			}
+			component => normalized.push(component),
+		}
+	}
+	normalized
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use css_ast::{StyleSheet, Visitable};
+
+	fn urls(source_text: &str) -> Vec<(&str, String)> {
+		let bump = Bump::default();
+		let stylesheet = Parser::new(&bump, source_text).parse_entirely::<StyleSheet>().output.unwrap();
+		let mut collector = UrlCollector::new(source_text);
+		stylesheet.accept(&mut collector);
+		collector.urls.into_iter().map(|(span, url)| (span.span_contents(source_text).contents(), url)).collect()
+	}
+
+	#[test]
+	fn test_urls() {
+		assert_eq!(
+			urls(concat!(
+				"@import \"a.css\" layer;\n",
+				"@import url(b.css) screen;\n",
+				"@namespace svg url('http://www.w3.org/2000/svg');\n",
+				"a { background: url( ./c.png ) no-repeat, url(\"d e.png\"); }\n",
+				"@font-face { font-family: x; src: url(x.woff2) format('woff2'), local(x); }\n",
+			)),
+			[
+				("a.css", "a.css".into()),
+				("b.css", "b.css".into()),
+				("http://www.w3.org/2000/svg", "http://www.w3.org/2000/svg".into()),
+				("./c.png", "./c.png".into()),
+				("d e.png", "d e.png".into()),
+				("x.woff2", "x.woff2".into()),
+			]
+		);
+	}
+
+	#[test]
+	fn test_resolve() {
+		let document = Path::new("/site/css/main.css");
+		let root = Some(Path::new("/site"));
+		let file = |path: &str| Some(UrlTarget::File(PathBuf::from(path)));
+		assert_eq!(UrlTarget::resolve("a.css", document, root), file("/site/css/a.css"));
+		assert_eq!(UrlTarget::resolve("./a.css?v=1#x", document, root), file("/site/css/a.css"));
+		assert_eq!(UrlTarget::resolve("../img/a.png", document, root), file("/site/img/a.png"));
+		assert_eq!(UrlTarget::resolve("/img/a.png", document, root), file("/site/img/a.png"));
+		assert_eq!(UrlTarget::resolve("/img/a.png", document, None), None);
+		assert_eq!(UrlTarget::resolve("file:///other/a.css", document, root), file("/other/a.css"));
+		assert_eq!(
+			UrlTarget::resolve("//cdn.example.com/a.css", document, root),
+			Some(UrlTarget::Remote(Uri::from_str("https://cdn.example.com/a.css").unwrap()))
+		);
+		assert_eq!(
+			UrlTarget::resolve("HTTPS://example.com/a.css", document, root),
+			Some(UrlTarget::Remote(Uri::from_str("HTTPS://example.com/a.css").unwrap()))
+		);
+		assert_eq!(UrlTarget::resolve("data:image/png;base64,AAAA", document, root), None);
+		assert_eq!(UrlTarget::resolve("#filter", document, root), None);
+	}
+}
@@ -0,0 +1,194 @@
+use css_ast::{CalcNode, Computed, MathFunction, Property, StyleRule, StyleValue, ToSpecificity, Visit};
+use css_lexer::{Cursor, Kind, SourceOffset};
+use css_parse::ToCursors;
+use std::{collections::HashMap, sync::Arc};
+
+use crate::InlayHintsConfig;
+
+fn cursors(node: &impl ToCursors) -> Vec<Cursor> {
+	let mut cursors = vec![];
+	node.to_cursors(&mut cursors);
+	cursors
+}
+
+fn text(source_text: &str, cursors: &[Cursor]) -> Option<String> {
+	let span = cursors.first()?.span() + cursors.last()?.span();
+	Some(span.span_contents(source_text).contents().to_string())
+}
+
+// The index of the cursor which closes the function (or parenthesised block) opened by the cursor at `start`.
+fn close_paren(cursors: &[Cursor], start: usize) -> Option<usize> {
+	let mut depth = 0;
+	for (i, c) in cursors.iter().enumerate().skip(start) {
+		match c.token().kind() {
+			Kind::Function | Kind::LeftParen => depth += 1,
+			Kind::RightParen => {
+				depth -= 1;
+				if depth == 0 {
+					return Some(i);
+				}
+			}
+			_ => {}
+		}
+	}
+	None
+}
+
+/// Collects the custom properties set by `:root` style rules, as the names and the text of their values.
+#[derive(Default)]
+pub struct RootCustomProperties<'a> {
+	source_text: &'a str,
+	pub properties: Vec<(String, String)>,
+}
+
+impl<'a> RootCustomProperties<'a> {
+	pub fn new(source_text: &'a str) -> Self {
+		Self { source_text, properties: vec![] }
+	}
+}
+
+impl<'a> Visit<'a> for RootCustomProperties<'_> {
+	fn visit_style_rule(&mut self, rule: &StyleRule<'a>) {
+		if text(self.source_text, &cursors(&rule.selectors)).is_none_or(|selectors| selectors != ":root") {
+			return;
+		}
+		for (property, _) in &rule.style.declarations {
+			let name = Cursor::from(property.name).str_slice(self.source_text);
+			if let (true, Some(value)) = (name.starts_with("--"), text(self.source_text, &cursors(&property.value))) {
+				self.properties.push((name.to_string(), value.trim().to_string()));
+			}
+		}
+	}
+}
+
+/// Collects the inlay hints for a stylesheet, as the text of each hint and the offset it should be shown at.
+#[derive(Default)]
+pub struct InlayHinter<'a> {
+	source_text: &'a str,
+	config: InlayHintsConfig,
+	// The value of each custom property which `var()` can be resolved to.
+	custom_properties: Arc<HashMap<String, String>>,
+	pub hints: Vec<(SourceOffset, String)>,
+}
+
+impl<'a> InlayHinter<'a> {
+	pub fn new(
+		source_text: &'a str,
+		config: InlayHintsConfig,
+		custom_properties: Arc<HashMap<String, String>>,
+	) -> Self {
+		Self { source_text, config, custom_properties, hints: vec![] }
+	}
+
+	// A math function which simplifies down to a single number or dimension, such as `calc(8px * 2)`.
+	fn simplify_math(function: &MathFunction) -> Option<CalcNode> {
+		let node = CalcNode::from(function).simplify();
+		matches!(node, CalcNode::Number(_) | CalcNode::Dimension(_, _)).then_some(node)
+	}
+}
+
+impl<'a> Visit<'a> for InlayHinter<'_> {
+	fn visit_style_rule(&mut self, rule: &StyleRule<'a>) {
+		if !self.config.specificity {
+			return;
+		}
+		for (selector, _) in &rule.selectors.0 {
+			if let Some(last) = cursors(selector).last() {
+				let specificity = selector.specificity();
+				self.hints
+					.push((last.end_offset(), format!("({},{},{})", specificity.0, specificity.1, specificity.2)));
+			}
+		}
+	}
+
+	fn visit_property(&mut self, property: &Property<'a>) {
+		let cursors = cursors(&property.value);
+		if self.config.custom_properties {
+			for (i, c) in cursors.iter().enumerate() {
+				if *c != Kind::Function || !c.str_slice(self.source_text).eq_ignore_ascii_case("var(") {
+					continue;
+				}
+				let value =
+					cursors.get(i + 1).and_then(|name| self.custom_properties.get(name.str_slice(self.source_text)));
+				if let (Some(value), Some(close)) = (value, close_paren(&cursors, i)) {
+					self.hints.push((cursors[close].end_offset(), format!("= {}", value)));
+				}
+			}
+		}
+		// Only a value made of a single math function is parsed into a MathFunction; those mixed with other values are
+		// kept as component values, so can't be simplified.
+		if let (true, StyleValue::Computed(Computed::Math(function))) = (self.config.calc, &property.value) {
+			if let (Some(node), Some(last)) = (Self::simplify_math(function), cursors.last()) {
+				self.hints.push((last.end_offset(), format!("= {}", node)));
+			}
+		}
+		if self.config.shorthands {
+			let longhands = property.expand(self.source_text);
+			if let (Some(longhands), Some(last)) = (longhands, crate::inlay_hints::cursors(property).last()) {
+				let label = longhands
+					.iter()
+					.map(|longhand| {
+						let important = if longhand.important { " !important" } else { "" };
+						format!("{}: {}{}", longhand.name, longhand.value, important)
+					})
+					.collect::<Vec<_>>()
+					.join("; ");
+				self.hints.push((last.end_offset(), label));
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use bumpalo::Bump;
+	use css_ast::{StyleSheet, Visitable};
+	use css_parse::Parser;
+
+	fn hints(source_text: &str, config: InlayHintsConfig) -> Vec<(u32, String)> {
+		let bump = Bump::default();
+		let stylesheet = Parser::new(&bump, source_text).parse_entirely::<StyleSheet>().output.unwrap();
+		let mut root = RootCustomProperties::new(source_text);
+		stylesheet.accept(&mut root);
+		let custom_properties = Arc::new(root.properties.into_iter().collect());
+		let mut hinter = InlayHinter::new(source_text, config, custom_properties);
+		stylesheet.accept(&mut hinter);
+		hinter.hints.into_iter().map(|(offset, label)| (offset.0, label)).collect()
+	}
+
+	#[test]
+	fn test_specificity() {
+		let config = InlayHintsConfig { specificity: true, custom_properties: false, calc: false, shorthands: false };
+		assert_eq!(
+			hints("#a .b, a:hover > b::before {} c { d {} }", config),
+			[(5, "(1,1,0)".into()), (26, "(0,1,3)".into()), (31, "(0,0,1)".into()), (35, "(0,0,1)".into())]
+		);
+	}
+
+	#[test]
+	fn test_values() {
+		let config = InlayHintsConfig { specificity: false, ..Default::default() };
+		assert_eq!(
+			hints(":root { --gap: 8px; } a { gap: var(--gap); width: calc(2px * 4); height: min(10px, 2em) }", config),
+			[(41, "= 8px".into()), (63, "= 8px".into())]
+		);
+		// Math functions mixed with other values aren't simplified.
+		assert_eq!(
+			hints("a { margin: 1px calc(1px + 1px) }", config),
+			[(
+				31,
+				"margin-top: 1px; margin-right: calc(1px + 1px); margin-bottom: 1px; margin-left: calc(1px + 1px)"
+					.into()
+			)]
+		);
+		assert_eq!(hints("a { margin: 1px 2px !important }", config), [(
+			30,
+			"margin-top: 1px !important; margin-right: 2px !important; margin-bottom: 1px !important; margin-left: 2px \
+			 !important"
+				.into()
+		)]);
+		// Custom properties set anywhere but `:root` aren't known.
+		assert!(hints(".a { --gap: 8px; } a { gap: var(--gap) }", config).is_empty());
+	}
+}
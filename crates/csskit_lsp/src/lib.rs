@@ -1,10 +1,33 @@
+mod code_lens;
+mod config;
+mod document_links;
+mod inlay_hints;
 mod jsonrpc;
+mod line_index;
 mod server;
 mod service;
+mod signature_help;
+#[cfg(test)]
+mod test_helpers;
+mod workspace;
 
+#[doc(inline)]
+pub use code_lens::*;
+#[doc(inline)]
+pub use config::*;
+#[doc(inline)]
+pub use document_links::*;
+#[doc(inline)]
+pub use inlay_hints::*;
 #[doc(inline)]
 pub use jsonrpc::*;
 #[doc(inline)]
+pub use line_index::*;
+#[doc(inline)]
 pub use server::*;
 #[doc(inline)]
 pub use service::*;
+#[doc(inline)]
+pub use signature_help::*;
+#[doc(inline)]
+pub use workspace::*;
@@ -0,0 +1,126 @@
+use css_lexer::{SourceOffset, Span};
+use lsp_types::Position;
+
+/// Converts between byte offsets into some source text and LSP [Positions][Position], whose characters are counted
+/// in UTF-16 code units.
+///
+/// Rather than scanning the source text for each conversion, the start of each line is recorded up front, along with
+/// every character that takes a different number of bytes in UTF-8 than code units in UTF-16; so an offset can be
+/// converted to a position with a couple of binary searches.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct LineIndex {
+	// The byte offset of the start of each line; the first line always starts at 0.
+	line_starts: Vec<u32>,
+	// The byte offset just after each non-ASCII character, along with how many more bytes than UTF-16 code units all
+	// the characters up to that offset take.
+	wide_chars: Vec<(u32, u32)>,
+}
+
+impl LineIndex {
+	pub fn new(source_text: &str) -> Self {
+		let mut line_starts = vec![0];
+		let mut wide_chars = vec![];
+		let mut extra_bytes = 0;
+		for (offset, char) in source_text.char_indices() {
+			let end = (offset + char.len_utf8()) as u32;
+			if char == '\n' {
+				line_starts.push(end);
+			} else if !char.is_ascii() {
+				extra_bytes += (char.len_utf8() - char.len_utf16()) as u32;
+				wide_chars.push((end, extra_bytes));
+			}
+		}
+		Self { line_starts, wide_chars }
+	}
+
+	pub fn line_count(&self) -> u32 {
+		self.line_starts.len() as u32
+	}
+
+	// How many more bytes than UTF-16 code units the source text takes up to the given byte offset.
+	fn extra_bytes(&self, offset: u32) -> u32 {
+		match self.wide_chars.partition_point(|(end, _)| *end <= offset) {
+			0 => 0,
+			i => self.wide_chars[i - 1].1,
+		}
+	}
+
+	fn utf16_len(&self, start: u32, end: u32) -> u32 {
+		(end - start) - (self.extra_bytes(end) - self.extra_bytes(start))
+	}
+
+	/// The [Position] of the given byte offset.
+	pub fn position(&self, offset: SourceOffset) -> Position {
+		let line = self.line_starts.partition_point(|start| *start <= offset.0) - 1;
+		let line_start = self.line_starts[line];
+		Position::new(line as u32, self.utf16_len(line_start, offset.0))
+	}
+
+	/// The length of the [Span] in UTF-16 code units.
+	pub fn len(&self, span: Span) -> u32 {
+		self.utf16_len(span.start().0, span.end().0)
+	}
+
+	/// The byte offset of the given [Position]. Positions past the end of their line are clamped to the end of it, and
+	/// those past the last line to the end of the source text.
+	pub fn offset(&self, source_text: &str, position: Position) -> SourceOffset {
+		let Some(line_start) = self.line_starts.get(position.line as usize) else {
+			return SourceOffset(source_text.len() as u32);
+		};
+		let mut offset = *line_start as usize;
+		let mut character = 0;
+		for char in source_text[offset..].chars() {
+			if character >= position.character || char == '\n' {
+				break;
+			}
+			character += char.len_utf16() as u32;
+			offset += char.len_utf8();
+		}
+		SourceOffset(offset as u32)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_position() {
+		let source_text = "a {\n\tcolor: red;\n}\n";
+		let index = LineIndex::new(source_text);
+		assert_eq!(index.line_count(), 4);
+		assert_eq!(index.position(SourceOffset(0)), Position::new(0, 0));
+		assert_eq!(index.position(SourceOffset(3)), Position::new(0, 3));
+		assert_eq!(index.position(SourceOffset(4)), Position::new(1, 0));
+		assert_eq!(index.position(SourceOffset(5)), Position::new(1, 1));
+		assert_eq!(index.position(SourceOffset(17)), Position::new(2, 0));
+		assert_eq!(index.position(SourceOffset(19)), Position::new(3, 0));
+	}
+
+	#[test]
+	fn test_wide_chars() {
+		// `é` takes two bytes but one UTF-16 code unit, while `😀` takes four bytes but two code units.
+		let source_text = "a::after { content: \"é😀\" }\n.😀 {}";
+		let index = LineIndex::new(source_text);
+		let content = source_text.find('"').unwrap() as u32;
+		let close = source_text.rfind('"').unwrap() as u32;
+		assert_eq!(index.position(SourceOffset(close)), Position::new(0, content + 4));
+		assert_eq!(index.len(Span::new(SourceOffset(content), SourceOffset(close + 1))), 5);
+		let brace = source_text.rfind('{').unwrap() as u32;
+		assert_eq!(index.position(SourceOffset(brace)), Position::new(1, 4));
+	}
+
+	#[test]
+	fn test_offset() {
+		let source_text = "a {}\n.é {}\n";
+		let index = LineIndex::new(source_text);
+		assert_eq!(index.offset(source_text, Position::new(0, 2)), SourceOffset(2));
+		assert_eq!(index.offset(source_text, Position::new(1, 0)), SourceOffset(5));
+		assert_eq!(index.offset(source_text, Position::new(1, 3)), SourceOffset(9));
+		assert_eq!(index.offset(source_text, Position::new(1, 100)), SourceOffset(11));
+		assert_eq!(index.offset(source_text, Position::new(9, 0)), SourceOffset(source_text.len() as u32));
+		for offset in [0, 3, 5, 6, 8, 11] {
+			assert_eq!(index.offset(source_text, index.position(SourceOffset(offset))), SourceOffset(offset));
+		}
+	}
+}
@@ -11,6 +11,7 @@ use lsp_types::SetTraceParams;
 use serde_json::from_value;
 use std::{
 	io,
+	panic::{self, AssertUnwindSafe},
 	sync::{Arc, RwLock},
 	thread::{Builder, JoinHandle},
 };
@@ -76,7 +77,14 @@ impl Server {
 							*level_set = level;
 						}
 					}
-					let response = handler.handle(message);
+					// A panic would end this thread, leaving the reader waiting forever to hand over the next message, so
+					// it's caught and the request answered with an error instead.
+					let id = if let Message::Request(request) = &message { Some(request.id.clone()) } else { None };
+					let response =
+						panic::catch_unwind(AssertUnwindSafe(|| handler.handle(message))).unwrap_or_else(|_| {
+							warn!("LspMessageHandler panicked handling request {:?}", &id);
+							id.map(|id| handler.internal_error(id))
+						});
 					if let Some(response) = response {
 						if let Err(e) = handler_sender.send(response) {
 							warn!("Handler failed to send response {:?}", &e);
@@ -128,11 +136,12 @@ impl Server {
 mod tests {
 	use std::sync::atomic::{AtomicBool, Ordering};
 
-	use crate::{ErrorCode, Notification, Request, Response};
+	use crate::{test_helpers::Session, ErrorCode, Notification, Request, Response};
 
 	use super::*;
 	use lsp_types::{
-		request::{GotoDeclaration, Initialize, Request as RequestTrait},
+		notification::Exit,
+		request::{GotoDeclaration, Initialize, Request as RequestTrait, Shutdown},
 		InitializeParams, InitializeResult,
 	};
 	use serde_json::{json, to_value, Value};
@@ -187,4 +196,29 @@ mod tests {
 		);
 		sender.send(Message::Notification(Notification { method: "exit".into(), params: Value::Null })).unwrap();
 	}
+
+	#[test]
+	fn test_handler_panic() {
+		struct PanickingHandler;
+		impl Handler for PanickingHandler {
+			fn initialized(&self) -> bool {
+				true
+			}
+			fn shutdown(&self) -> Result<(), ErrorCode> {
+				panic!("Failed to shut down");
+			}
+		}
+
+		let mut session = Session::new(PanickingHandler);
+		assert_eq!(
+			session.request::<Shutdown>(()),
+			Response::Err(1.into(), ErrorCode::InternalError, "InternalError".into(), Value::Null)
+		);
+		// The handler is still there to respond to the next request.
+		assert_eq!(
+			session.request::<Shutdown>(()),
+			Response::Err(2.into(), ErrorCode::InternalError, "InternalError".into(), Value::Null)
+		);
+		session.notify::<Exit>(());
+	}
 }
@@ -20,6 +20,28 @@ pub trait Handler: Sized + Send + Sync + 'static {
 		false
 	}
 
+	/// Capabilities to register with the client once it has initialized, for those which can only be registered
+	/// dynamically, such as watching files. These are sent as a single [RegisterCapability] request.
+	fn registrations(&self) -> Vec<lsp_types::Registration> {
+		vec![]
+	}
+
+	/// Whether the client should be asked to pull diagnostics again, as something they depend on (such as the config)
+	/// has changed. This is checked, and should be reset, after each notification which could change them; if set, a
+	/// [WorkspaceDiagnosticRefresh] request is sent.
+	fn take_diagnostic_refresh(&self) -> bool {
+		false
+	}
+
+	fn diagnostic_refresh(&self) -> Option<Message> {
+		self.take_diagnostic_refresh().then(|| {
+			Message::Request(crate::jsonrpc::Request::new::<WorkspaceDiagnosticRefresh>(
+				"diagnostic-refresh".into(),
+				(),
+			))
+		})
+	}
+
 	fn handle(&self, message: Message) -> Option<Message> {
 		let span = trace_span!("Handling request", "{:#?}", message);
 		let _ = span.enter();
@@ -327,7 +349,7 @@ pub trait Handler: Sized + Send + Sync + 'static {
 					.from_value::<lsp_types::DidChangeConfigurationParams>()
 					.map(|p| self.on_did_change_configuration(p))
 					.ok();
-				None
+				self.diagnostic_refresh()
 			}
 			DidChangeNotebookDocument::METHOD => {
 				message
@@ -348,7 +370,7 @@ pub trait Handler: Sized + Send + Sync + 'static {
 					.from_value::<lsp_types::DidChangeWatchedFilesParams>()
 					.map(|p| self.on_did_change_watched_files(p))
 					.ok();
-				None
+				self.diagnostic_refresh()
 			}
 			DidChangeWorkspaceFolders::METHOD => {
 				message
@@ -413,7 +435,11 @@ pub trait Handler: Sized + Send + Sync + 'static {
 			}
 			Initialized::METHOD => {
 				message.from_value::<lsp_types::InitializedParams>().map(|p| self.on_initialized(p)).ok();
-				None
+				let registrations = self.registrations();
+				(!registrations.is_empty()).then(|| {
+					let params = lsp_types::RegistrationParams { registrations };
+					Message::Request(crate::jsonrpc::Request::new::<RegisterCapability>("registrations".into(), params))
+				})
 			}
 			LogMessage::METHOD => {
 				message.from_value::<lsp_types::LogMessageParams>().map(|p| self.on_log_message(p)).ok();
@@ -1,55 +1,152 @@
 use bumpalo::Bump;
-use crossbeam_channel::{bounded, Receiver, Sender};
-use css_ast::{StyleSheet, Visitable};
+use crossbeam_channel::{bounded, Receiver, SendError, Sender};
+use css_ast::{CompatChecker, StyleSheet, Targets, Visit, Visitable};
 use css_lexer::{Cursor, SourceOffset, Span, Token};
-use css_parse::{Parser, ParserReturn};
+use css_parse::{Parser, ParserLimits, ParserReturn};
 use dashmap::DashMap;
 use csskit_highlight::{Highlight, SemanticKind, SemanticModifier, TokenHighlighter};
 use itertools::Itertools;
-use lsp_types::{CompletionItemTag, Position, Uri};
+use lsp_types::{
+	notification::{DidChangeWatchedFiles, Notification as _},
+	CompletionItemTag, Diagnostic, DiagnosticSeverity, InlayHint, InlayHintLabel, Position, Range, SemanticToken,
+	SemanticTokensEdit, Uri,
+};
 use ropey::Rope;
 use std::{
+	collections::HashMap,
+	fs,
+	path::Path,
 	sync::{
 		atomic::{AtomicBool, Ordering},
-		Arc,
+		Arc, Mutex, MutexGuard, OnceLock, PoisonError, RwLock,
 	},
 	thread::{Builder, JoinHandle},
 };
 use strum::VariantNames;
-use tracing::{instrument, trace, trace_span};
+use tracing::{instrument, trace, trace_span, warn};
 
-use crate::{ErrorCode, Handler};
+use crate::{
+	code_lenses, is_css_file, is_scss_file, path_to_uri, signature_help, uri_to_path, Config, DocumentSymbols,
+	ErrorCode, Handler, InlayHinter, InlayHintsConfig, LineIndex, RootCustomProperties, SymbolCollector, UrlCollector,
+	UrlTarget, Workspace, CONFIG_FILE_NAME,
+};
 
-type Line = u32;
-type Col = u32;
+// The length of a highlight, in UTF-16 code units.
+type Length = u32;
 
 #[derive(Debug)]
 enum FileCall {
 	// Re-parse the document based on changes
 	RopeChange(Rope),
-	// Highlight a document, returning the semantic highlights
-	Highlight,
+	// Highlight a document, or only the highlights within the given range, returning the semantic highlights
+	Highlight(Option<Range>, Targets),
+	// Check a document, returning its parse errors along with the lints the config enables
+	Diagnose(Config),
+	// Collect the custom properties the document sets in `:root`
+	RootCustomProperties,
+	// Hint the document within the given range, resolving `var()` to the given custom properties
+	InlayHints(Range, InlayHintsConfig, Arc<HashMap<String, String>>),
+	// Describe the arguments of the function at the given position
+	SignatureHelp(Position),
+	// Collect the URLs the document refers to, as written
+	Urls,
+	// Collect the symbols the document defines and refers to, for code lenses
+	Symbols,
+	// Panic, as a bug in parsing or checking the document would
+	#[cfg(test)]
+	Panic,
 }
 
 #[derive(Debug)]
 enum FileReturn {
-	Highlights(Vec<(Highlight, Line, Col)>),
+	Highlights(Vec<(Highlight, Position, Length)>),
+	Diagnostics(Vec<Diagnostic>),
+	CustomProperties(Vec<(String, String)>),
+	InlayHints(Vec<InlayHint>),
+	SignatureHelp(Option<lsp_types::SignatureHelp>),
+	Urls(Vec<(Range, String)>),
+	Symbols(DocumentSymbols),
 }
 
 #[derive(Debug)]
 pub struct File {
 	pub content: Rope,
+	// Requests may be handled on several threads at once, so the worker is locked for each call until it has returned,
+	// otherwise one caller could receive the return meant for another. The worker is only started once a call is made,
+	// and for documents which aren't open it's stopped again afterwards, so indexing a workspace doesn't hold a thread
+	// for each of its files.
+	worker: Mutex<Option<Worker>>,
+	// Whether the client has the document open; otherwise it was read from a workspace folder, and follows the file on
+	// disk rather than the client.
+	pub open: bool,
+	// Whether the document is SCSS, rather than plain CSS.
+	scss: bool,
+	// What other documents need from a document which isn't open, which only changes once it's indexed again, so is
+	// kept rather than parsing the document for every request.
+	root_custom_properties: OnceLock<Vec<(String, String)>>,
+	symbols: OnceLock<DocumentSymbols>,
+	// The last semantic tokens sent for the whole document, which the next delta request will be relative to.
+	semantic_tokens: Option<lsp_types::SemanticTokens>,
+	result_id: u32,
+}
+
+// A document's parse, either as CSS or, for SCSS documents, with the SCSS dialect. The SCSS nodes visit the CSS within
+// them just as the CSS nodes do, so calls about a document treat both alike.
+enum Document<'a> {
+	Css(ParserReturn<'a, StyleSheet<'a>>),
+	Scss(ParserReturn<'a, scss_ast::StyleSheet<'a>>),
+}
+
+impl<'a> Document<'a> {
+	// Any file in the workspace may be parsed, so documents are parsed with the limits for untrusted input, which keep a
+	// pathological file from overflowing the worker's stack.
+	fn parse(bump: &'a Bump, source_text: &'a str, scss: bool) -> Self {
+		if scss {
+			let parser = Parser::new_with_features(bump, source_text, scss_ast::FEATURES);
+			Self::Scss(parser.with_limits(ParserLimits::UNTRUSTED).parse_entirely())
+		} else {
+			Self::Css(Parser::new(bump, source_text).with_limits(ParserLimits::UNTRUSTED).parse_entirely())
+		}
+	}
+
+	fn errors(&self) -> &[miette::Error] {
+		match self {
+			Self::Css(result) => &result.errors,
+			Self::Scss(result) => &result.errors,
+		}
+	}
+
+	// Visit the stylesheet, if the document could be parsed.
+	fn accept<V: Visit<'a>>(&self, v: &mut V) {
+		match self {
+			Self::Css(result) => {
+				if let Some(stylesheet) = &result.output {
+					stylesheet.accept(v);
+				}
+			}
+			Self::Scss(result) => {
+				if let Some(stylesheet) = &result.output {
+					stylesheet.accept(v);
+				}
+			}
+		}
+	}
+}
+
+// The thread which parses a document, and answers calls about it. Each call is handed over with a `bounded(0)` channel,
+// and all but `RopeChange` return exactly once.
+#[derive(Debug)]
+struct Worker {
 	thread: JoinHandle<()>,
 	sender: Sender<FileCall>,
 	receiver: Receiver<FileReturn>,
 }
 
-impl File {
-	fn new() -> Self {
+impl Worker {
+	fn spawn(scss: bool) -> Self {
 		let (sender, read_receiver) = bounded::<FileCall>(0);
 		let (write_sender, receiver) = bounded::<FileReturn>(0);
 		Self {
-			content: Rope::new(),
 			sender,
 			receiver,
 			thread: Builder::new()
@@ -57,8 +154,8 @@ impl File {
 				.spawn(move || {
 					let mut bump = Bump::default();
 					let mut string: String = "".into();
-					let mut result: ParserReturn<'_, StyleSheet<'_>> =
-						Parser::new(&bump, "").parse_entirely::<StyleSheet>();
+					let mut line_index = LineIndex::default();
+					let mut result = Document::parse(&bump, "", scss);
 					while let Ok(call) = read_receiver.recv() {
 						match call {
 							FileCall::RopeChange(rope) => {
@@ -69,42 +166,170 @@ impl File {
 								drop(result);
 								bump.reset();
 								string = rope.clone().into();
-								result = Parser::new(&bump, &string).parse_entirely::<StyleSheet>();
-								if let Some(stylesheet) = &result.output {
-									// trace!("Sucessfully parsed stylesheet: {:#?}", &stylesheet);
-								}
+								line_index = LineIndex::new(&string);
+								result = Document::parse(&bump, &string, scss);
 							}
-							FileCall::Highlight => {
+							FileCall::Highlight(range, targets) => {
 								let span = trace_span!("Highlighting document");
 								let _ = span.enter();
-								let mut highlighter = TokenHighlighter::new();
-								if let Some(stylesheet) = &result.output {
-									stylesheet.accept(&mut highlighter);
-									let mut current_line = 0;
-									let mut current_start = 0;
-									let data = highlighter
-										.highlights()
-										.sorted_by(|a, b| Ord::cmp(&a.span(), &b.span()))
-										.map(|h| {
-											// TODO: figure out a more efficient way to get line/col
-											let span_contents = h.span().span_contents(&string);
-											let (line, start) = span_contents.line_and_column();
-											let delta_line: Line = line - current_line;
-											current_line = line;
-											let delta_start: Col =
-												if delta_line == 0 { start - current_start } else { start };
-											current_start = start;
-											(*h, delta_line, delta_start)
-										});
-									write_sender.send(FileReturn::Highlights(data.collect())).ok();
-								}
+								let mut highlighter = TokenHighlighter::new(&string).with_targets(targets);
+								result.accept(&mut highlighter);
+								let range = range.map(|range| {
+									Span::new(
+										line_index.offset(&string, range.start),
+										line_index.offset(&string, range.end),
+									)
+								});
+								let data = highlighter
+									.highlights()
+									.filter(|h| {
+										range.is_none_or(|range| {
+											h.span().start() < range.end() && h.span().end() > range.start()
+										})
+									})
+									.sorted_by(|a, b| Ord::cmp(&a.span(), &b.span()))
+									.map(|h| (*h, line_index.position(h.span().start()), line_index.len(h.span())));
+								write_sender.send(FileReturn::Highlights(data.collect())).ok();
+							}
+							FileCall::Diagnose(config) => {
+								let span = trace_span!("Checking document");
+								let _ = span.enter();
+								let mut diagnostics = result
+									.errors()
+									.iter()
+									.map(|err| to_diagnostic(err, &line_index, DiagnosticSeverity::ERROR))
+									.collect::<Vec<_>>();
+								let mut checker = CompatChecker::new(&string, config.targets);
+								result.accept(&mut checker);
+								diagnostics.extend(
+									checker
+										.diagnostics
+										.iter()
+										.filter(|err| {
+											err.code().is_none_or(|code| config.lint.is_enabled(&code.to_string()))
+										})
+										.map(|err| to_diagnostic(err, &line_index, DiagnosticSeverity::WARNING)),
+								);
+								write_sender.send(FileReturn::Diagnostics(diagnostics)).ok();
+							}
+							FileCall::RootCustomProperties => {
+								let mut collector = RootCustomProperties::new(&string);
+								result.accept(&mut collector);
+								write_sender.send(FileReturn::CustomProperties(collector.properties)).ok();
+							}
+							FileCall::InlayHints(range, config, custom_properties) => {
+								let span = trace_span!("Hinting document");
+								let _ = span.enter();
+								let mut hinter = InlayHinter::new(&string, config, custom_properties);
+								result.accept(&mut hinter);
+								let start = line_index.offset(&string, range.start);
+								let end = line_index.offset(&string, range.end);
+								let hints = hinter
+									.hints
+									.into_iter()
+									.filter(|(offset, _)| *offset >= start && *offset <= end)
+									.map(|(offset, label)| InlayHint {
+										position: line_index.position(offset),
+										label: InlayHintLabel::String(label),
+										kind: None,
+										text_edits: None,
+										tooltip: None,
+										padding_left: Some(true),
+										padding_right: None,
+										data: None,
+									});
+								write_sender.send(FileReturn::InlayHints(hints.collect())).ok();
+							}
+							FileCall::SignatureHelp(position) => {
+								// Signature help is wanted while typing, when the document rarely parses, so this works
+								// from the tokens rather than the AST.
+								let help = signature_help(&string, line_index.offset(&string, position));
+								write_sender.send(FileReturn::SignatureHelp(help)).ok();
+							}
+							FileCall::Urls => {
+								let mut collector = UrlCollector::new(&string);
+								result.accept(&mut collector);
+								let urls = collector.urls.into_iter().map(|(span, url)| {
+									(
+										Range::new(line_index.position(span.start()), line_index.position(span.end())),
+										url,
+									)
+								});
+								write_sender.send(FileReturn::Urls(urls.collect())).ok();
 							}
+							FileCall::Symbols => {
+								let mut collector = SymbolCollector::new(&string);
+								result.accept(&mut collector);
+								let range = |span: Span| {
+									Range::new(line_index.position(span.start()), line_index.position(span.end()))
+								};
+								let symbols = DocumentSymbols {
+									definitions: collector
+										.definitions
+										.into_iter()
+										.map(|(span, symbols)| (range(span), symbols))
+										.collect(),
+									references: collector.references,
+									layer_statements: collector.layer_statements.into_iter().map(range).collect(),
+									layers: collector.layers,
+								};
+								write_sender.send(FileReturn::Symbols(symbols)).ok();
+							}
+							#[cfg(test)]
+							FileCall::Panic => panic!("Worker panicked"),
 						}
 					}
 				})
 				.expect("Failed to document thread Reader"),
 		}
 	}
+}
+
+impl File {
+	fn new(scss: bool) -> Self {
+		Self {
+			content: Rope::new(),
+			worker: Mutex::new(None),
+			open: false,
+			scss,
+			root_custom_properties: OnceLock::new(),
+			symbols: OnceLock::new(),
+			semantic_tokens: None,
+			result_id: 0,
+		}
+	}
+
+	// Hand a call over to the worker, starting it for the current content if there isn't one, and returning it still
+	// locked. A worker which has panicked has dropped its end of the channel, so rather than failing every call from
+	// then on, it's replaced with a new one.
+	fn send(&self, call: FileCall) -> Option<MutexGuard<'_, Option<Worker>>> {
+		let mut guard = self.worker.lock().unwrap_or_else(PoisonError::into_inner);
+		let call = match guard.as_ref() {
+			Some(worker) => match worker.sender.send(call) {
+				Ok(()) => return Some(guard),
+				Err(SendError(call)) => {
+					warn!("Document worker stopped, restarting it");
+					call
+				}
+			},
+			None => call,
+		};
+		let worker = guard.insert(Worker::spawn(self.scss));
+		worker.sender.send(FileCall::RopeChange(self.content.clone())).ok()?;
+		worker.sender.send(call).ok()?;
+		Some(guard)
+	}
+
+	// Make a call to the worker and wait for what it returns. If the worker panics while answering, its end of the
+	// channel is dropped and this returns `None`, instead of waiting forever.
+	fn call(&self, call: FileCall) -> Option<FileReturn> {
+		let mut guard = self.send(call)?;
+		let ret = guard.as_ref()?.receiver.recv().ok();
+		if !self.open {
+			*guard = None;
+		}
+		ret
+	}
 
 	fn to_string(&self) -> String {
 		self.content.clone().into()
@@ -112,31 +337,251 @@ impl File {
 
 	fn commit(&mut self, rope: Rope) {
 		self.content = rope;
-		self.sender.send(FileCall::RopeChange(self.content.clone())).unwrap();
+		self.root_custom_properties = OnceLock::new();
+		self.symbols = OnceLock::new();
+		// Without a worker there's nothing to tell; the next call starts one with the new content.
+		let worker = self.worker.get_mut().unwrap_or_else(PoisonError::into_inner);
+		if worker.as_ref().is_some_and(|worker| worker.sender.send(FileCall::RopeChange(self.content.clone())).is_err())
+		{
+			*worker = None;
+		}
 	}
 
 	#[instrument]
-	fn get_highlights(&self) -> Vec<(Highlight, Line, Col)> {
-		self.sender.send(FileCall::Highlight).unwrap();
-		while let Ok(ret) = self.receiver.recv() {
-			if let FileReturn::Highlights(highlights) = ret {
-				return highlights;
+	fn get_highlights(&self, range: Option<Range>, targets: Targets) -> Vec<(Highlight, Position, Length)> {
+		match self.call(FileCall::Highlight(range, targets)) {
+			Some(FileReturn::Highlights(highlights)) => highlights,
+			_ => vec![],
+		}
+	}
+
+	#[instrument]
+	fn get_diagnostics(&self, config: Config) -> Vec<Diagnostic> {
+		match self.call(FileCall::Diagnose(config)) {
+			Some(FileReturn::Diagnostics(diagnostics)) => diagnostics,
+			_ => vec![],
+		}
+	}
+
+	#[instrument]
+	fn get_root_custom_properties(&self) -> Vec<(String, String)> {
+		if let Some(properties) = self.root_custom_properties.get() {
+			return properties.clone();
+		}
+		match self.call(FileCall::RootCustomProperties) {
+			Some(FileReturn::CustomProperties(properties)) => {
+				if !self.open {
+					self.root_custom_properties.set(properties.clone()).ok();
+				}
+				properties
 			}
+			_ => vec![],
+		}
+	}
+
+	#[instrument]
+	fn get_inlay_hints(
+		&self,
+		range: Range,
+		config: InlayHintsConfig,
+		custom_properties: Arc<HashMap<String, String>>,
+	) -> Vec<InlayHint> {
+		match self.call(FileCall::InlayHints(range, config, custom_properties)) {
+			Some(FileReturn::InlayHints(hints)) => hints,
+			_ => vec![],
+		}
+	}
+
+	#[instrument]
+	fn get_signature_help(&self, position: Position) -> Option<lsp_types::SignatureHelp> {
+		match self.call(FileCall::SignatureHelp(position)) {
+			Some(FileReturn::SignatureHelp(help)) => help,
+			_ => None,
+		}
+	}
+
+	#[instrument]
+	fn get_urls(&self) -> Vec<(Range, String)> {
+		match self.call(FileCall::Urls) {
+			Some(FileReturn::Urls(urls)) => urls,
+			_ => vec![],
+		}
+	}
+
+	#[instrument]
+	fn get_symbols(&self) -> DocumentSymbols {
+		if let Some(symbols) = self.symbols.get() {
+			return symbols.clone();
 		}
+		match self.call(FileCall::Symbols) {
+			Some(FileReturn::Symbols(symbols)) => {
+				if !self.open {
+					self.symbols.set(symbols.clone()).ok();
+				}
+				symbols
+			}
+			_ => DocumentSymbols::default(),
+		}
+	}
+
+	fn get_semantic_tokens(&self, range: Option<Range>, targets: Targets) -> Vec<SemanticToken> {
+		let mut line = 0;
+		let mut start = 0;
+		self.get_highlights(range, targets)
+			.into_iter()
+			.map(|(highlight, position, length)| {
+				let delta_line = position.line - line;
+				let delta_start = if delta_line == 0 { position.character - start } else { position.character };
+				line = position.line;
+				start = position.character;
+				SemanticToken {
+					token_type: highlight.kind().bits() as u32,
+					token_modifiers_bitset: highlight.modifier().bits() as u32,
+					delta_line,
+					delta_start,
+					length,
+				}
+			})
+			.collect()
+	}
+
+	// Remember the tokens for the whole document under a new result id, so that a later delta request can refer to them.
+	fn store_semantic_tokens(&mut self, data: Vec<SemanticToken>) -> lsp_types::SemanticTokens {
+		self.result_id += 1;
+		let tokens = lsp_types::SemanticTokens { result_id: Some(self.result_id.to_string()), data };
+		self.semantic_tokens = Some(tokens.clone());
+		tokens
+	}
+}
+
+// Diagnostics are placed at their first label, or the start of the document if they have none.
+fn to_diagnostic(err: &miette::Error, line_index: &LineIndex, severity: DiagnosticSeverity) -> Diagnostic {
+	let range = err.labels().and_then(|mut labels| labels.next()).map_or_else(Range::default, |label| {
+		let start = SourceOffset(label.offset() as u32);
+		let end = SourceOffset((label.offset() + label.len()) as u32);
+		Range::new(line_index.position(start), line_index.position(end))
+	});
+	let code = err.code().map(|code| code.to_string());
+	let tags =
+		(code.as_deref() == Some("css_ast::DeprecatedFeature")).then(|| vec![lsp_types::DiagnosticTag::DEPRECATED]);
+	Diagnostic {
+		range,
+		severity: Some(severity),
+		code: code.map(lsp_types::NumberOrString::String),
+		source: Some("csskit".into()),
+		message: err.to_string(),
+		tags,
+		..Default::default()
+	}
+}
+
+// Semantic tokens are sent as a flat array of integers, five for each token, which edits refer to by index. Rather than a
+// minimal diff, the edit replaces everything between the tokens the two lists start and end with in common, which is
+// cheap to find and small in the usual case of a single change to the document.
+fn semantic_tokens_edits(previous: &[SemanticToken], next: &[SemanticToken]) -> Vec<SemanticTokensEdit> {
+	let prefix = previous.iter().zip(next).take_while(|(a, b)| a == b).count();
+	let suffix = previous[prefix..].iter().rev().zip(next[prefix..].iter().rev()).take_while(|(a, b)| a == b).count();
+	if prefix + suffix == previous.len() && prefix + suffix == next.len() {
 		return vec![];
 	}
+	vec![SemanticTokensEdit {
+		start: (prefix * 5) as u32,
+		delete_count: ((previous.len() - prefix - suffix) * 5) as u32,
+		data: Some(next[prefix..next.len() - suffix].to_vec()),
+	}]
 }
 
 #[derive(Debug)]
 pub struct LSPService {
 	version: String,
 	files: Arc<DashMap<Uri, File>>,
+	workspace: Arc<RwLock<Workspace>>,
 	initialized: AtomicBool,
+	// Whether the client can be asked to watch files once it has initialized.
+	watch_files: AtomicBool,
+	// Whether the client can be asked to pull diagnostics again, and whether it should be, as the config has changed
+	// since it last was.
+	can_refresh_diagnostics: AtomicBool,
+	config_changed: AtomicBool,
 }
 
 impl LSPService {
 	pub fn new(version: &'static str) -> Self {
-		Self { version: version.into(), files: Arc::new(DashMap::new()), initialized: AtomicBool::new(false) }
+		Self {
+			version: version.into(),
+			files: Arc::new(DashMap::new()),
+			workspace: Arc::new(RwLock::new(Workspace::default())),
+			initialized: AtomicBool::new(false),
+			watch_files: AtomicBool::new(false),
+			can_refresh_diagnostics: AtomicBool::new(false),
+			config_changed: AtomicBool::new(false),
+		}
+	}
+
+	fn config(&self) -> Config {
+		self.workspace.read().unwrap().config().clone()
+	}
+
+	// Read a file from a workspace folder, unless the client has it open, in which case the client's copy is kept.
+	fn index_file(&self, path: &Path) {
+		let Some(uri) = path_to_uri(path) else { return };
+		if self.files.get(&uri).is_some_and(|file| file.open) {
+			return;
+		}
+		match fs::read_to_string(path) {
+			Ok(source_text) => {
+				trace!("Indexing {:?}", path);
+				let mut file = File::new(is_scss_file(path));
+				file.commit(Rope::from_str(&source_text));
+				self.files.insert(uri, file);
+			}
+			Err(e) => warn!("Couldn't read {:?}: {}", path, e),
+		}
+	}
+
+	fn unindex_file(&self, path: &Path) {
+		if let Some(uri) = path_to_uri(path) {
+			self.files.remove_if(&uri, |_, file| !file.open);
+		}
+	}
+
+	// The custom properties set in `:root` across the workspace, which `var()` can be resolved to. Those set to different
+	// values in different places can't be resolved, so are left out.
+	fn root_custom_properties(&self) -> HashMap<String, String> {
+		let mut properties: HashMap<String, Option<String>> = HashMap::new();
+		for file in self.files.iter() {
+			for (name, value) in file.get_root_custom_properties() {
+				properties
+					.entry(name)
+					.and_modify(|existing| {
+						if existing.as_ref() != Some(&value) {
+							*existing = None
+						}
+					})
+					.or_insert(Some(value.clone()));
+			}
+		}
+		properties.into_iter().filter_map(|(name, value)| Some((name, value?))).collect()
+	}
+
+	// The URLs a document refers to, with what each resolves to. Only documents with a `file:` URI can have relative
+	// URLs resolved.
+	fn resolve_urls(&self, uri: &Uri, document: &File) -> Vec<(Range, String, UrlTarget)> {
+		let Some(path) = uri_to_path(uri) else { return vec![] };
+		let workspace = self.workspace.read().unwrap();
+		let root = workspace.folder_for(&path);
+		document
+			.get_urls()
+			.into_iter()
+			.filter_map(|(range, url)| Some((range, url.clone(), UrlTarget::resolve(&url, &path, root)?)))
+			.collect()
+	}
+
+	fn index_workspace(&self) {
+		let files = self.workspace.read().unwrap().css_files();
+		for path in files {
+			self.index_file(&path);
+		}
 	}
 }
 
@@ -146,9 +591,53 @@ impl Handler for LSPService {
 		self.initialized.load(Ordering::SeqCst)
 	}
 
+	#[instrument]
+	fn registrations(&self) -> Vec<lsp_types::Registration> {
+		if !self.watch_files.load(Ordering::SeqCst) {
+			return vec![];
+		}
+		let watchers = ["**/*.css".into(), "**/*.scss".into(), format!("**/{}", CONFIG_FILE_NAME)]
+			.into_iter()
+			.map(|glob| lsp_types::FileSystemWatcher { glob_pattern: lsp_types::GlobPattern::String(glob), kind: None })
+			.collect();
+		let options = lsp_types::DidChangeWatchedFilesRegistrationOptions { watchers };
+		vec![lsp_types::Registration {
+			id: "watched-files".into(),
+			method: DidChangeWatchedFiles::METHOD.into(),
+			register_options: serde_json::to_value(options).ok(),
+		}]
+	}
+
 	#[instrument]
 	fn initialize(&self, req: lsp_types::InitializeParams) -> Result<lsp_types::InitializeResult, ErrorCode> {
 		self.initialized.swap(true, Ordering::SeqCst);
+		{
+			let mut workspace = self.workspace.write().unwrap();
+			#[allow(deprecated)]
+			let folders = match (req.workspace_folders, req.root_uri) {
+				(Some(folders), _) => folders.into_iter().map(|folder| folder.uri).collect(),
+				(None, Some(root)) => vec![root],
+				(None, None) => vec![],
+			};
+			for folder in folders.iter().filter_map(uri_to_path) {
+				workspace.add_folder(folder);
+			}
+			if let Some(settings) = req.initialization_options {
+				workspace.set_settings(settings);
+			}
+		}
+		let workspace = req.capabilities.workspace.unwrap_or_default();
+		self.watch_files.store(
+			workspace
+				.did_change_watched_files
+				.and_then(|watch_files| watch_files.dynamic_registration)
+				.unwrap_or(false),
+			Ordering::SeqCst,
+		);
+		self.can_refresh_diagnostics.store(
+			workspace.diagnostic.and_then(|diagnostic| diagnostic.refresh_support).unwrap_or(false),
+			Ordering::SeqCst,
+		);
 		Ok(lsp_types::InitializeResult {
 			capabilities: lsp_types::ServerCapabilities {
 				// position_encoding: (),
@@ -171,7 +660,11 @@ impl Handler for LSPService {
 					work_done_progress_options: lsp_types::WorkDoneProgressOptions { work_done_progress: None },
 					completion_item: None,
 				}),
-				// signature_help_provider: (),
+				signature_help_provider: Some(lsp_types::SignatureHelpOptions {
+					trigger_characters: Some(vec!["(".into(), ",".into(), "/".into()]),
+					retrigger_characters: None,
+					work_done_progress_options: lsp_types::WorkDoneProgressOptions { work_done_progress: None },
+				}),
 				// definition_provider: (),
 				// type_definition_provider: (),
 				// implementation_provider: (),
@@ -180,17 +673,26 @@ impl Handler for LSPService {
 				// document_symbol_provider: (),
 				// workspace_symbol_provider: (),
 				// code_action_provider: (),
-				// code_lens_provider: (),
+				code_lens_provider: Some(lsp_types::CodeLensOptions { resolve_provider: Some(false) }),
 				// document_formatting_provider: (),
 				// document_range_formatting_provider: (),
 				// document_on_type_formatting_provider: (),
 				// rename_provider: (),
-				// document_link_provider: (),
+				document_link_provider: Some(lsp_types::DocumentLinkOptions {
+					resolve_provider: Some(false),
+					work_done_progress_options: lsp_types::WorkDoneProgressOptions { work_done_progress: None },
+				}),
 				// color_provider: (),
 				// folding_range_provider: (),
 				// declaration_provider: (),
 				// execute_command_provider: (),
-				// workspace: (),
+				workspace: Some(lsp_types::WorkspaceServerCapabilities {
+					workspace_folders: Some(lsp_types::WorkspaceFoldersServerCapabilities {
+						supported: Some(true),
+						change_notifications: Some(lsp_types::OneOf::Left(true)),
+					}),
+					file_operations: None,
+				}),
 				// call_hierarchy_provider: (),
 				semantic_tokens_provider: Some(lsp_types::SemanticTokensServerCapabilities::SemanticTokensOptions(
 					lsp_types::SemanticTokensOptions {
@@ -207,15 +709,22 @@ impl Handler for LSPService {
 								.map(|v| lsp_types::SemanticTokenModifier::new(v))
 								.collect(),
 						},
-						range: Some(false),
+						range: Some(true),
 						full: Some(lsp_types::SemanticTokensFullOptions::Delta { delta: Some(true) }),
 					},
 				)),
 				// moniker_provider: (),
 				// linked_editing_range_provider: (),
 				// inline_value_provider: (),
-				// inlay_hint_provider: (),
-				// diagnostic_provider: (),
+				inlay_hint_provider: Some(lsp_types::OneOf::Left(true)),
+				diagnostic_provider: Some(lsp_types::DiagnosticServerCapabilities::Options(
+					lsp_types::DiagnosticOptions {
+						identifier: Some("csskit".into()),
+						inter_file_dependencies: false,
+						workspace_diagnostics: false,
+						work_done_progress_options: lsp_types::WorkDoneProgressOptions { work_done_progress: None },
+					},
+				)),
 				// inline_completion_provider: (),
 				// experimental: (),
 				..Default::default()
@@ -228,6 +737,13 @@ impl Handler for LSPService {
 		})
 	}
 
+	// Dropping the documents closes their channels, which lets their workers finish.
+	#[instrument]
+	fn shutdown(&self) -> Result<(), ErrorCode> {
+		self.files.clear();
+		Ok(())
+	}
+
 	#[instrument]
 	fn semantic_tokens_full_request(
 		&self,
@@ -235,24 +751,154 @@ impl Handler for LSPService {
 	) -> Result<Option<lsp_types::SemanticTokensResult>, ErrorCode> {
 		let uri = req.text_document.uri;
 		trace!("Asked for SemanticTokens for {:?}", &uri);
-		if let Some(document) = self.files.get(&uri) {
-			let data = document
-				.get_highlights()
-				.into_iter()
-				.map(|(highlight, delta_line, delta_start)| lsp_types::SemanticToken {
-					token_type: highlight.kind().bits() as u32,
-					token_modifiers_bitset: highlight.modifier().bits() as u32,
-					delta_line,
-					delta_start,
-					length: highlight.span().len(),
+		if let Some(mut document) = self.files.get_mut(&uri) {
+			let data = document.get_semantic_tokens(None, self.config().targets);
+			Ok(Some(lsp_types::SemanticTokensResult::Tokens(document.store_semantic_tokens(data))))
+		} else {
+			Err(ErrorCode::InternalError)
+		}
+	}
+
+	#[instrument]
+	fn semantic_tokens_full_delta_request(
+		&self,
+		req: lsp_types::SemanticTokensDeltaParams,
+	) -> Result<Option<lsp_types::SemanticTokensFullDeltaResult>, ErrorCode> {
+		let uri = req.text_document.uri;
+		trace!("Asked for SemanticTokens delta for {:?} from {:?}", &uri, &req.previous_result_id);
+		if let Some(mut document) = self.files.get_mut(&uri) {
+			let data = document.get_semantic_tokens(None, self.config().targets);
+			let previous = document
+				.semantic_tokens
+				.take()
+				.filter(|tokens| tokens.result_id.as_ref() == Some(&req.previous_result_id));
+			let tokens = document.store_semantic_tokens(data);
+			// If the client refers to tokens other than the last ones sent, all of the tokens need sending again.
+			Ok(Some(if let Some(previous) = previous {
+				lsp_types::SemanticTokensFullDeltaResult::TokensDelta(lsp_types::SemanticTokensDelta {
+					result_id: tokens.result_id,
+					edits: semantic_tokens_edits(&previous.data, &tokens.data),
 				})
-				.collect();
-			Ok(Some(lsp_types::SemanticTokensResult::Tokens(lsp_types::SemanticTokens { result_id: None, data })))
+			} else {
+				lsp_types::SemanticTokensFullDeltaResult::Tokens(tokens)
+			}))
+		} else {
+			Err(ErrorCode::InternalError)
+		}
+	}
+
+	#[instrument]
+	fn semantic_tokens_range_request(
+		&self,
+		req: lsp_types::SemanticTokensRangeParams,
+	) -> Result<Option<lsp_types::SemanticTokensRangeResult>, ErrorCode> {
+		let uri = req.text_document.uri;
+		trace!("Asked for SemanticTokens for {:?} in {:?}", &uri, &req.range);
+		if let Some(document) = self.files.get(&uri) {
+			let data = document.get_semantic_tokens(Some(req.range), self.config().targets);
+			Ok(Some(lsp_types::SemanticTokensRangeResult::Tokens(lsp_types::SemanticTokens { result_id: None, data })))
+		} else {
+			Err(ErrorCode::InternalError)
+		}
+	}
+
+	#[instrument]
+	fn document_diagnostic_request(
+		&self,
+		req: lsp_types::DocumentDiagnosticParams,
+	) -> Result<lsp_types::DocumentDiagnosticReportResult, ErrorCode> {
+		let uri = req.text_document.uri;
+		trace!("Asked for Diagnostics for {:?}", &uri);
+		if let Some(document) = self.files.get(&uri) {
+			let config = self.config();
+			let mut items = document.get_diagnostics(config.clone());
+			if config.lint.missing_files {
+				items.extend(self.resolve_urls(&uri, &document).into_iter().filter_map(|(range, url, target)| {
+					let UrlTarget::File(path) = target else { return None };
+					(!path.exists()).then(|| Diagnostic {
+						range,
+						severity: Some(DiagnosticSeverity::WARNING),
+						code: Some(lsp_types::NumberOrString::String("csskit_lsp::MissingFile".into())),
+						source: Some("csskit".into()),
+						message: format!("Couldn't find '{}'", url),
+						..Default::default()
+					})
+				}));
+			}
+			Ok(lsp_types::DocumentDiagnosticReportResult::Report(lsp_types::DocumentDiagnosticReport::Full(
+				lsp_types::RelatedFullDocumentDiagnosticReport {
+					related_documents: None,
+					full_document_diagnostic_report: lsp_types::FullDocumentDiagnosticReport { result_id: None, items },
+				},
+			)))
+		} else {
+			Err(ErrorCode::InternalError)
+		}
+	}
+
+	#[instrument]
+	fn inlay_hint_request(&self, req: lsp_types::InlayHintParams) -> Result<Option<Vec<InlayHint>>, ErrorCode> {
+		let uri = req.text_document.uri;
+		trace!("Asked for InlayHints for {:?} in {:?}", &uri, &req.range);
+		let config = self.config().inlay_hints;
+		let custom_properties =
+			Arc::new(if config.custom_properties { self.root_custom_properties() } else { HashMap::new() });
+		if let Some(document) = self.files.get(&uri) {
+			Ok(Some(document.get_inlay_hints(req.range, config, custom_properties)))
+		} else {
+			Err(ErrorCode::InternalError)
+		}
+	}
+
+	#[instrument]
+	fn signature_help_request(
+		&self,
+		req: lsp_types::SignatureHelpParams,
+	) -> Result<Option<lsp_types::SignatureHelp>, ErrorCode> {
+		let uri = req.text_document_position_params.text_document.uri;
+		let position = req.text_document_position_params.position;
+		trace!("Asked for SignatureHelp for {:?} at {:?}", &uri, &position);
+		if let Some(document) = self.files.get(&uri) {
+			Ok(document.get_signature_help(position))
+		} else {
+			Err(ErrorCode::InternalError)
+		}
+	}
+
+	#[instrument]
+	fn document_link_request(
+		&self,
+		req: lsp_types::DocumentLinkParams,
+	) -> Result<Option<Vec<lsp_types::DocumentLink>>, ErrorCode> {
+		let uri = req.text_document.uri;
+		trace!("Asked for DocumentLinks for {:?}", &uri);
+		if let Some(document) = self.files.get(&uri) {
+			let links = self.resolve_urls(&uri, &document).into_iter().filter_map(|(range, _, target)| {
+				let target = match target {
+					UrlTarget::File(path) => path_to_uri(&path)?,
+					UrlTarget::Remote(uri) => uri,
+				};
+				Some(lsp_types::DocumentLink { range, target: Some(target), tooltip: None, data: None })
+			});
+			Ok(Some(links.collect()))
 		} else {
 			Err(ErrorCode::InternalError)
 		}
 	}
 
+	#[instrument]
+	fn code_lens_request(&self, req: lsp_types::CodeLensParams) -> Result<Option<Vec<lsp_types::CodeLens>>, ErrorCode> {
+		let uri = req.text_document.uri;
+		trace!("Asked for CodeLenses for {:?}", &uri);
+		let Some(document) = self.files.get(&uri).map(|document| document.get_symbols()) else {
+			return Err(ErrorCode::InternalError);
+		};
+		// Uses are counted across the whole workspace, so every other document is needed, not only this one.
+		let others =
+			self.files.iter().filter(|file| *file.key() != uri).map(|file| file.get_symbols()).collect::<Vec<_>>();
+		Ok(Some(code_lenses(&document, &others)))
+	}
+
 	#[instrument]
 	fn completion(&self, req: lsp_types::CompletionParams) -> Result<Option<lsp_types::CompletionResponse>, ErrorCode> {
 		let uri = req.text_document_position.text_document.uri;
@@ -265,7 +911,8 @@ impl Handler for LSPService {
 	fn on_did_open_text_document(&self, req: lsp_types::DidOpenTextDocumentParams) {
 		let uri = req.text_document.uri;
 		let source_text = req.text_document.text;
-		let mut doc = File::new();
+		let mut doc = File::new(req.text_document.language_id == "scss");
+		doc.open = true;
 		let mut rope = doc.content.clone();
 		rope.remove(0..);
 		rope.insert(0, &source_text);
@@ -310,4 +957,616 @@ impl Handler for LSPService {
 			file.commit(rope)
 		}
 	}
+
+	#[instrument]
+	fn on_did_close_text_document(&self, req: lsp_types::DidCloseTextDocumentParams) {
+		let uri = req.text_document.uri;
+		// Files within the workspace are still needed once closed, so go back to following the file on disk.
+		match uri_to_path(&uri) {
+			Some(path) if self.workspace.read().unwrap().contains(&path) && is_css_file(&path) => {
+				if let Some(mut file) = self.files.get_mut(&uri) {
+					file.open = false;
+				}
+				self.index_file(&path);
+			}
+			_ => {
+				self.files.remove(&uri);
+			}
+		}
+	}
+
+	#[instrument]
+	fn on_initialized(&self, _req: lsp_types::InitializedParams) {
+		self.index_workspace();
+	}
+
+	// The server doesn't watch files itself, instead asking the client to watch `**/*.css`, `**/*.scss` & the config file
+	// once it has initialized (see `registrations`).
+	#[instrument]
+	fn on_did_change_watched_files(&self, req: lsp_types::DidChangeWatchedFilesParams) {
+		for change in req.changes {
+			let Some(path) = uri_to_path(&change.uri) else { continue };
+			let workspace = self.workspace.read().unwrap();
+			if workspace.is_config_file(&path) {
+				drop(workspace);
+				self.workspace.write().unwrap().reload_project_config();
+				self.config_changed.store(true, Ordering::SeqCst);
+			} else if workspace.contains(&path) && is_css_file(&path) {
+				drop(workspace);
+				if change.typ == lsp_types::FileChangeType::DELETED {
+					self.unindex_file(&path);
+				} else {
+					self.index_file(&path);
+				}
+			}
+		}
+	}
+
+	#[instrument]
+	fn on_did_change_workspace_folders(&self, req: lsp_types::DidChangeWorkspaceFoldersParams) {
+		let mut workspace = self.workspace.write().unwrap();
+		for folder in req.event.removed.iter().filter_map(|folder| uri_to_path(&folder.uri)) {
+			workspace.remove_folder(&folder);
+			self.files.retain(|uri, file| {
+				file.open || uri_to_path(uri).is_none_or(|path| !path.starts_with(&folder) || workspace.contains(&path))
+			});
+		}
+		for folder in req.event.added.iter().filter_map(|folder| uri_to_path(&folder.uri)) {
+			workspace.add_folder(folder);
+		}
+		drop(workspace);
+		self.index_workspace();
+	}
+
+	#[instrument]
+	fn on_did_change_configuration(&self, req: lsp_types::DidChangeConfigurationParams) {
+		self.workspace.write().unwrap().set_settings(req.settings);
+		self.config_changed.store(true, Ordering::SeqCst);
+	}
+
+	// Diagnostics are pulled by the client, so when the config changes (which may change which lints are reported) it
+	// has to be told to pull them again.
+	#[instrument]
+	fn take_diagnostic_refresh(&self) -> bool {
+		self.config_changed.swap(false, Ordering::SeqCst) && self.can_refresh_diagnostics.load(Ordering::SeqCst)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::test_helpers::Session;
+	use lsp_types::FileChangeType;
+	use lsp_types::{
+		DidChangeTextDocumentParams, DidOpenTextDocumentParams, PartialResultParams, SemanticTokensDeltaParams,
+		SemanticTokensFullDeltaResult, SemanticTokensParams, SemanticTokensRangeParams, SemanticTokensRangeResult,
+		SemanticTokensResult, TextDocumentContentChangeEvent, TextDocumentIdentifier, TextDocumentItem,
+		VersionedTextDocumentIdentifier, WorkDoneProgressParams,
+	};
+	use serde_json::json;
+	use std::str::FromStr;
+
+	fn token(delta_line: u32, delta_start: u32, length: u32) -> SemanticToken {
+		SemanticToken { delta_line, delta_start, length, token_type: 0, token_modifiers_bitset: 0 }
+	}
+
+	fn open(service: &LSPService, source_text: &str) -> Uri {
+		let uri = Uri::from_str("file:///test.css").unwrap();
+		let text_document = TextDocumentItem::new(uri.clone(), "css".into(), 1, source_text.into());
+		service.on_did_open_text_document(DidOpenTextDocumentParams { text_document });
+		uri
+	}
+
+	fn full(service: &LSPService, uri: &Uri) -> lsp_types::SemanticTokens {
+		let req = SemanticTokensParams {
+			text_document: TextDocumentIdentifier::new(uri.clone()),
+			work_done_progress_params: WorkDoneProgressParams::default(),
+			partial_result_params: PartialResultParams::default(),
+		};
+		match service.semantic_tokens_full_request(req) {
+			Ok(Some(SemanticTokensResult::Tokens(tokens))) => tokens,
+			result => panic!("Expected tokens, got {:?}", result),
+		}
+	}
+
+	fn delta(service: &LSPService, uri: &Uri, previous_result_id: &str) -> SemanticTokensFullDeltaResult {
+		let req = SemanticTokensDeltaParams {
+			text_document: TextDocumentIdentifier::new(uri.clone()),
+			previous_result_id: previous_result_id.into(),
+			work_done_progress_params: WorkDoneProgressParams::default(),
+			partial_result_params: PartialResultParams::default(),
+		};
+		service.semantic_tokens_full_delta_request(req).unwrap().unwrap()
+	}
+
+	fn diagnostics(service: &LSPService, uri: &Uri) -> Vec<String> {
+		let req = lsp_types::DocumentDiagnosticParams {
+			text_document: TextDocumentIdentifier::new(uri.clone()),
+			identifier: None,
+			previous_result_id: None,
+			work_done_progress_params: WorkDoneProgressParams::default(),
+			partial_result_params: PartialResultParams::default(),
+		};
+		let Ok(lsp_types::DocumentDiagnosticReportResult::Report(lsp_types::DocumentDiagnosticReport::Full(report))) =
+			service.document_diagnostic_request(req)
+		else {
+			panic!("Expected a full report");
+		};
+		report.full_document_diagnostic_report.items.into_iter().map(|diagnostic| diagnostic.message).collect()
+	}
+
+	fn inlay_hints(service: &LSPService, uri: &Uri) -> Vec<(Position, String)> {
+		let req = lsp_types::InlayHintParams {
+			text_document: TextDocumentIdentifier::new(uri.clone()),
+			range: Range::new(Position::new(0, 0), Position::new(u32::MAX, 0)),
+			work_done_progress_params: WorkDoneProgressParams::default(),
+		};
+		let hints = service.inlay_hint_request(req).unwrap().unwrap_or_default();
+		hints
+			.into_iter()
+			.map(|hint| match hint.label {
+				InlayHintLabel::String(label) => (hint.position, label),
+				label => panic!("Expected a string label, got {:?}", label),
+			})
+			.collect()
+	}
+
+	#[test]
+	fn test_workspace() {
+		let folder = std::env::temp_dir().join(format!("csskit_lsp_test_service_{}", std::process::id()));
+		fs::create_dir_all(&folder).unwrap();
+		fs::write(folder.join("a.css"), "a { page-break-after: always; accent-color: red }").unwrap();
+		fs::write(folder.join(crate::CONFIG_FILE_NAME), r#"{ "targets": "safari 15" }"#).unwrap();
+		let a = path_to_uri(&folder.join("a.css")).unwrap();
+		let b = path_to_uri(&folder.join("b.css")).unwrap();
+
+		let service = LSPService::new("0.0.0");
+		service
+			.initialize(lsp_types::InitializeParams {
+				workspace_folders: Some(vec![lsp_types::WorkspaceFolder {
+					uri: path_to_uri(&folder).unwrap(),
+					name: "test".into(),
+				}]),
+				..Default::default()
+			})
+			.unwrap();
+		service.on_initialized(lsp_types::InitializedParams {});
+		// Indexed files are only parsed when they're needed, and don't keep a worker afterwards.
+		let has_worker = |uri: &Uri| service.files.get(uri).unwrap().worker.lock().unwrap().is_some();
+		assert!(!has_worker(&a));
+		assert_eq!(
+			diagnostics(&service, &a),
+			["'page-break-after' is deprecated", "'accent-color' isn't supported by safari 15"]
+		);
+		assert!(!has_worker(&a));
+
+		service.on_did_change_configuration(lsp_types::DidChangeConfigurationParams {
+			settings: serde_json::json!({ "csskit": { "lint": { "deprecated": false } } }),
+		});
+		assert_eq!(diagnostics(&service, &a), ["'accent-color' isn't supported by safari 15"]);
+
+		fs::write(folder.join(crate::CONFIG_FILE_NAME), r#"{ "targets": "safari 16" }"#).unwrap();
+		fs::write(folder.join("b.css"), "b { color: red; }").unwrap();
+		let changes = vec![
+			lsp_types::FileEvent::new(
+				path_to_uri(&folder.join(crate::CONFIG_FILE_NAME)).unwrap(),
+				FileChangeType::CHANGED,
+			),
+			lsp_types::FileEvent::new(b.clone(), FileChangeType::CREATED),
+		];
+		service.on_did_change_watched_files(lsp_types::DidChangeWatchedFilesParams { changes });
+		assert!(diagnostics(&service, &a).is_empty());
+		assert!(diagnostics(&service, &b).is_empty());
+
+		// Open documents follow the client rather than the disk, until they're closed.
+		let text_document = TextDocumentItem::new(b.clone(), "css".into(), 1, "b { page-break-after: always }".into());
+		service.on_did_open_text_document(DidOpenTextDocumentParams { text_document });
+		let changes = vec![lsp_types::FileEvent::new(b.clone(), FileChangeType::DELETED)];
+		service.on_did_change_watched_files(lsp_types::DidChangeWatchedFilesParams { changes });
+		assert!(service.files.contains_key(&b));
+		service.on_did_close_text_document(lsp_types::DidCloseTextDocumentParams {
+			text_document: TextDocumentIdentifier::new(b.clone()),
+		});
+		assert!(diagnostics(&service, &b).is_empty());
+		fs::remove_file(folder.join("b.css")).unwrap();
+		let changes = vec![lsp_types::FileEvent::new(b.clone(), FileChangeType::DELETED)];
+		service.on_did_change_watched_files(lsp_types::DidChangeWatchedFilesParams { changes });
+		assert!(!service.files.contains_key(&b));
+
+		service.on_did_change_workspace_folders(lsp_types::DidChangeWorkspaceFoldersParams {
+			event: lsp_types::WorkspaceFoldersChangeEvent {
+				added: vec![],
+				removed: vec![lsp_types::WorkspaceFolder { uri: path_to_uri(&folder).unwrap(), name: "test".into() }],
+			},
+		});
+		assert!(!service.files.contains_key(&a));
+		fs::remove_dir_all(&folder).unwrap();
+	}
+
+	#[test]
+	fn test_scss() {
+		let service = LSPService::new("0.0.0");
+		let uri = Uri::from_str("file:///test.scss").unwrap();
+		let source_text = "// Colors\n$primary: blue;\na { color: $primary; page-break-after: always }\n";
+		let text_document = TextDocumentItem::new(uri.clone(), "scss".into(), 1, source_text.into());
+		service.on_did_open_text_document(DidOpenTextDocumentParams { text_document });
+		assert_eq!(diagnostics(&service, &uri), ["'page-break-after' is deprecated"]);
+		assert!(!full(&service, &uri).data.is_empty());
+		// The same text as CSS doesn't parse.
+		let uri = open(&service, source_text);
+		assert_ne!(diagnostics(&service, &uri), ["'page-break-after' is deprecated"]);
+	}
+
+	#[test]
+	fn test_semantic_tokens_edits() {
+		let previous = [token(0, 0, 1), token(0, 2, 1), token(1, 1, 5)];
+		assert_eq!(semantic_tokens_edits(&previous, &previous), []);
+		let next = [token(0, 0, 1), token(0, 2, 3), token(1, 1, 5)];
+		assert_eq!(
+			semantic_tokens_edits(&previous, &next),
+			[SemanticTokensEdit { start: 5, delete_count: 5, data: Some(vec![token(0, 2, 3)]) }]
+		);
+		assert_eq!(
+			semantic_tokens_edits(&previous, &next[..1]),
+			[SemanticTokensEdit { start: 5, delete_count: 10, data: Some(vec![]) }]
+		);
+		assert_eq!(
+			semantic_tokens_edits(&previous[1..], &previous),
+			[SemanticTokensEdit { start: 0, delete_count: 0, data: Some(vec![token(0, 0, 1)]) }]
+		);
+	}
+
+	#[test]
+	fn test_semantic_tokens() {
+		let service = LSPService::new("0.0.0");
+		let uri = open(&service, "a {\n\tcolor: red;\n}\n\nb { content: \"é\"; }\n");
+		let tokens = full(&service, &uri);
+		assert_eq!(tokens.result_id.as_deref(), Some("1"));
+		let positions = tokens.data.iter().map(|t| (t.delta_line, t.delta_start, t.length)).collect::<Vec<_>>();
+		assert_eq!(
+			positions,
+			[
+				(0, 0, 1),
+				(0, 2, 1),
+				(1, 1, 5),
+				(0, 5, 1),
+				(0, 2, 3),
+				(1, 0, 1),
+				(2, 0, 1),
+				(0, 2, 1),
+				(0, 2, 7),
+				(0, 7, 1),
+				(0, 2, 3),
+				(0, 5, 1)
+			]
+		);
+
+		// Nothing has changed, so there is nothing to edit.
+		let SemanticTokensFullDeltaResult::TokensDelta(unchanged) = delta(&service, &uri, "1") else {
+			panic!("Expected a delta");
+		};
+		assert_eq!(unchanged.result_id.as_deref(), Some("2"));
+		assert_eq!(unchanged.edits, []);
+
+		service.on_did_change_text_document(DidChangeTextDocumentParams {
+			text_document: VersionedTextDocumentIdentifier::new(uri.clone(), 2),
+			content_changes: vec![TextDocumentContentChangeEvent {
+				range: Some(Range::new(Position::new(1, 8), Position::new(1, 11))),
+				range_length: None,
+				text: "blue".into(),
+			}],
+		});
+		let SemanticTokensFullDeltaResult::TokensDelta(changed) = delta(&service, &uri, "2") else {
+			panic!("Expected a delta");
+		};
+		let blue = SemanticToken { token_type: SemanticKind::StyleValueColor.bits() as u32, ..token(0, 2, 4) };
+		assert_eq!(changed.edits, [SemanticTokensEdit { start: 20, delete_count: 5, data: Some(vec![blue]) }]);
+
+		// A result id other than the last sends all of the tokens again.
+		assert!(matches!(delta(&service, &uri, "1"), SemanticTokensFullDeltaResult::Tokens(_)));
+
+		let req = SemanticTokensRangeParams {
+			text_document: TextDocumentIdentifier::new(uri.clone()),
+			range: Range::new(Position::new(4, 0), Position::new(5, 0)),
+			work_done_progress_params: WorkDoneProgressParams::default(),
+			partial_result_params: PartialResultParams::default(),
+		};
+		let Ok(Some(SemanticTokensRangeResult::Tokens(tokens))) = service.semantic_tokens_range_request(req) else {
+			panic!("Expected tokens");
+		};
+		let positions = tokens.data.iter().map(|t| (t.delta_line, t.delta_start, t.length)).collect::<Vec<_>>();
+		assert_eq!(positions, [(4, 0, 1), (0, 2, 1), (0, 2, 7), (0, 7, 1), (0, 2, 3), (0, 5, 1)]);
+	}
+
+	#[test]
+	fn test_inlay_hints() {
+		let service = LSPService::new("0.0.0");
+		let theme = Uri::from_str("file:///theme.css").unwrap();
+		let text_document = TextDocumentItem::new(
+			theme.clone(),
+			"css".into(),
+			1,
+			":root { --gap: 8px; --color: red; }\n:root { --color: blue; }".into(),
+		);
+		service.on_did_open_text_document(DidOpenTextDocumentParams { text_document });
+		let uri = open(&service, "a {\n\tgap: var(--gap);\n\tcolor: var(--color);\n}\n");
+		assert_eq!(
+			inlay_hints(&service, &uri),
+			[(Position::new(0, 1), "(0,0,1)".into()), (Position::new(1, 16), "= 8px".into())]
+		);
+
+		service.on_did_change_configuration(lsp_types::DidChangeConfigurationParams {
+			settings: serde_json::json!({ "inlayHints": { "specificity": false, "customProperties": false } }),
+		});
+		assert!(inlay_hints(&service, &uri).is_empty());
+	}
+
+	#[test]
+	fn test_registrations() {
+		let mut session = Session::new(LSPService::new("0.0.0"));
+		let capabilities = json!({ "workspace": { "didChangeWatchedFiles": { "dynamicRegistration": true } } });
+		session.run(json!([
+			{ "id": 1, "method": "initialize", "params": { "capabilities": capabilities } },
+			{ "method": "initialized", "params": {} },
+			{ "id": 2, "method": "shutdown" },
+		]));
+		assert_eq!(
+			session.transcript()[1],
+			json!({
+				"id": "registrations",
+				"method": "client/registerCapability",
+				"params": {
+					"registrations": [{
+						"id": "watched-files",
+						"method": "workspace/didChangeWatchedFiles",
+						"registerOptions": {
+							"watchers": [
+								{ "globPattern": "**/*.css" },
+								{ "globPattern": "**/*.scss" },
+								{ "globPattern": "**/csskit.json" },
+							],
+						},
+					}],
+				},
+			})
+		);
+
+		// Clients which can't watch files dynamically aren't asked to.
+		let mut session = Session::new(LSPService::new("0.0.0"));
+		session.run(json!([
+			{ "id": 1, "method": "initialize", "params": { "capabilities": {} } },
+			{ "method": "initialized", "params": {} },
+			{ "id": 2, "method": "shutdown" },
+		]));
+		assert_eq!(session.transcript().len(), 2);
+	}
+
+	#[test]
+	fn test_diagnostic_refresh() {
+		let folder = std::env::temp_dir().join(format!("csskit_lsp_test_refresh_{}", std::process::id()));
+		fs::create_dir_all(&folder).unwrap();
+		let folder_uri = path_to_uri(&folder).unwrap();
+		let config_uri = path_to_uri(&folder.join(crate::CONFIG_FILE_NAME)).unwrap();
+		let refresh = json!({ "id": "diagnostic-refresh", "method": "workspace/diagnostic/refresh" });
+		let mut session = Session::new(LSPService::new("0.0.0"));
+		let capabilities = json!({ "workspace": { "diagnostic": { "refreshSupport": true } } });
+		let folders = json!([{ "uri": folder_uri, "name": "test" }]);
+		let settings = json!({ "lint": { "deprecated": false } });
+		session.run(json!([
+			{ "id": 1, "method": "initialize", "params": { "capabilities": capabilities, "workspaceFolders": folders } },
+			{ "method": "initialized", "params": {} },
+			{ "method": "workspace/didChangeConfiguration", "params": { "settings": settings } },
+			// A change to a stylesheet doesn't change the config
+			{ "method": "workspace/didChangeWatchedFiles", "params": { "changes": [{ "uri": "file:///a.css", "type": 2 }] } },
+			{ "method": "workspace/didChangeWatchedFiles", "params": { "changes": [{ "uri": config_uri, "type": 2 }] } },
+			{ "id": 2, "method": "shutdown" },
+		]));
+		fs::remove_dir_all(&folder).unwrap();
+		assert_eq!(session.transcript()[1], refresh);
+		assert_eq!(session.transcript()[2], refresh);
+		assert_eq!(session.transcript().len(), 4);
+
+		// Clients which can't refresh diagnostics aren't asked to.
+		let mut session = Session::new(LSPService::new("0.0.0"));
+		session.run(json!([
+			{ "id": 1, "method": "initialize", "params": { "capabilities": {} } },
+			{ "method": "initialized", "params": {} },
+			{ "method": "workspace/didChangeConfiguration", "params": { "settings": {} } },
+			{ "id": 2, "method": "shutdown" },
+		]));
+		assert_eq!(session.transcript().len(), 2);
+	}
+
+	#[test]
+	fn test_signature_help() {
+		let service = LSPService::new("0.0.0");
+		let uri = open(&service, "a {\n\twidth: clamp(1px, 2vw, \n}\n");
+		let help = |position| {
+			service.signature_help_request(lsp_types::SignatureHelpParams {
+				context: None,
+				text_document_position_params: lsp_types::TextDocumentPositionParams::new(
+					TextDocumentIdentifier::new(uri.clone()),
+					position,
+				),
+				work_done_progress_params: WorkDoneProgressParams::default(),
+			})
+		};
+		let signature = help(Position::new(1, 25)).unwrap().unwrap();
+		assert_eq!(signature.signatures[0].label, "clamp(<calc-sum> | none, <calc-sum>, <calc-sum> | none)");
+		assert_eq!(signature.active_parameter, Some(2));
+		assert_eq!(help(Position::new(1, 8)), Ok(None));
+	}
+
+	#[test]
+	fn test_session() {
+		let mut session = Session::new(LSPService::new("0.0.0"));
+		let document = json!({ "uri": "file:///test.css" });
+		let position = |line: u32, character: u32| json!({ "line": line, "character": character });
+		let range = |start: u32, end: u32| json!({ "start": position(0, start), "end": position(0, end) });
+		session.run(json!([
+			{ "id": 1, "method": "initialize", "params": { "capabilities": {} } },
+			{ "method": "initialized", "params": {} },
+			{
+				"method": "textDocument/didOpen",
+				"params": {
+					"textDocument": {
+						"uri": "file:///test.css",
+						"languageId": "css",
+						"version": 1,
+						"text": "a { color: red }",
+					},
+				},
+			},
+			{ "id": 2, "method": "textDocument/semanticTokens/full", "params": { "textDocument": document } },
+			{
+				"method": "textDocument/didChange",
+				"params": {
+					"textDocument": { "uri": "file:///test.css", "version": 2 },
+					"contentChanges": [
+						{ "range": range(11, 14), "text": "blue" },
+						{ "range": range(17, 17), "text": "\nb { margin: 1px 2px }\na { color: red }" },
+					],
+				},
+			},
+			{
+				"id": 3,
+				"method": "textDocument/semanticTokens/full/delta",
+				"params": { "textDocument": document, "previousResultId": "1" },
+			},
+			{ "id": 4, "method": "textDocument/diagnostic", "params": { "textDocument": document } },
+			{
+				"id": 5,
+				"method": "textDocument/inlayHint",
+				"params": {
+					"textDocument": document,
+					"range": { "start": position(0, 0), "end": position(3, 0) },
+				},
+			},
+			{ "id": 6, "method": "textDocument/codeLens", "params": { "textDocument": document } },
+			{
+				"id": 7,
+				"method": "textDocument/hover",
+				"params": { "textDocument": document, "position": position(0, 0) },
+			},
+			{ "id": 8, "method": "shutdown" },
+			{ "method": "exit" },
+		]));
+		insta::assert_json_snapshot!(session.transcript());
+	}
+
+	#[test]
+	fn test_concurrent_requests() {
+		let service = Arc::new(LSPService::new("0.0.0"));
+		let uri =
+			open(&service, ":root { --gap: 4px }\na { margin: var(--gap) calc(1px + 2px) }\n@keyframes spin {}\n");
+		let expected = (diagnostics(&service, &uri), inlay_hints(&service, &uri));
+		// Each file's worker is shared by every thread, so each must receive the return for its own call.
+		let (sender, receiver) = bounded(0);
+		for _ in 0..8 {
+			let (service, uri, sender) = (service.clone(), uri.clone(), sender.clone());
+			Builder::new()
+				.spawn(move || {
+					for _ in 0..20 {
+						sender.send((diagnostics(&service, &uri), inlay_hints(&service, &uri))).unwrap();
+					}
+				})
+				.unwrap();
+		}
+		for _ in 0..8 * 20 {
+			assert_eq!(receiver.recv_timeout(std::time::Duration::from_secs(10)), Ok(expected.clone()));
+		}
+	}
+
+	#[test]
+	fn test_limits() {
+		let service = LSPService::new("0.0.0");
+		let uri = open(&service, &"a{".repeat(100_000));
+		assert_eq!(diagnostics(&service, &uri), ["The input is nested more than 64 levels deep, so parsing stopped."]);
+	}
+
+	#[test]
+	fn test_worker_panic() {
+		let mut file = File::new(false);
+		file.commit(Rope::from_str("a { color: red }"));
+		// The call is answered with nothing, rather than waiting forever for the worker to return.
+		assert!(file.call(FileCall::Panic).is_none());
+		// A new worker takes over, with the same content.
+		assert_eq!(file.get_symbols().definitions.len(), 1);
+		file.commit(Rope::from_str("a {} b {}"));
+		assert_eq!(file.get_symbols().definitions.len(), 2);
+	}
+
+	#[test]
+	fn test_code_lenses() {
+		let service = LSPService::new("0.0.0");
+		let uri = open(&service, "@layer base, theme;\n.a { animation: spin 1s }\n@keyframes spin {}\n");
+		let other = Uri::from_str("file:///other.css").unwrap();
+		let text_document = TextDocumentItem::new(other.clone(), "css".into(), 1, ".a:hover, .a {}\n".into());
+		service.on_did_open_text_document(DidOpenTextDocumentParams { text_document });
+		let req = lsp_types::CodeLensParams {
+			text_document: TextDocumentIdentifier::new(uri.clone()),
+			work_done_progress_params: WorkDoneProgressParams::default(),
+			partial_result_params: PartialResultParams::default(),
+		};
+		let lenses = service.code_lens_request(req).unwrap().unwrap();
+		let lenses = lenses.into_iter().map(|lens| (lens.range.start, lens.command.unwrap().title)).collect::<Vec<_>>();
+		assert_eq!(
+			lenses,
+			[
+				(Position::new(0, 0), "Layer order: base, theme".into()),
+				(Position::new(1, 0), "1 other rule targets this selector".into()),
+				(Position::new(2, 0), "1 reference".to_string()),
+			]
+		);
+	}
+
+	#[test]
+	fn test_document_links() {
+		let folder = std::env::temp_dir().join(format!("csskit_lsp_test_links_{}", std::process::id()));
+		fs::create_dir_all(folder.join("css")).unwrap();
+		fs::write(folder.join("css/base.css"), "").unwrap();
+		fs::write(folder.join("logo.png"), "").unwrap();
+		let service = LSPService::new("0.0.0");
+		service
+			.initialize(lsp_types::InitializeParams {
+				workspace_folders: Some(vec![lsp_types::WorkspaceFolder {
+					uri: path_to_uri(&folder).unwrap(),
+					name: "test".into(),
+				}]),
+				..Default::default()
+			})
+			.unwrap();
+		let uri = path_to_uri(&folder.join("css/main.css")).unwrap();
+		let source_text = concat!(
+			"@import \"base.css\";\n",
+			"@import url(missing.css);\n",
+			"a { background: url(/logo.png), url(https://example.com/a.png) }\n",
+		);
+		let text_document = TextDocumentItem::new(uri.clone(), "css".into(), 1, source_text.into());
+		service.on_did_open_text_document(DidOpenTextDocumentParams { text_document });
+
+		let req = lsp_types::DocumentLinkParams {
+			text_document: TextDocumentIdentifier::new(uri.clone()),
+			work_done_progress_params: WorkDoneProgressParams::default(),
+			partial_result_params: PartialResultParams::default(),
+		};
+		let links = service.document_link_request(req).unwrap().unwrap();
+		let links = links.into_iter().map(|link| (link.range.start, link.target.unwrap())).collect::<Vec<_>>();
+		assert_eq!(
+			links,
+			[
+				(Position::new(0, 9), path_to_uri(&folder.join("css/base.css")).unwrap()),
+				(Position::new(1, 12), path_to_uri(&folder.join("css/missing.css")).unwrap()),
+				(Position::new(2, 20), path_to_uri(&folder.join("logo.png")).unwrap()),
+				(Position::new(2, 36), Uri::from_str("https://example.com/a.png").unwrap()),
+			]
+		);
+		assert_eq!(diagnostics(&service, &uri), ["Couldn't find 'missing.css'"]);
+
+		service.on_did_change_configuration(lsp_types::DidChangeConfigurationParams {
+			settings: serde_json::json!({ "lint": { "missingFiles": false } }),
+		});
+		assert!(diagnostics(&service, &uri).is_empty());
+		fs::remove_dir_all(&folder).unwrap();
+	}
 }
@@ -0,0 +1,409 @@
+use css_lexer::{Kind, Lexer, SourceOffset};
+use lsp_types::{
+	Documentation, MarkupContent, MarkupKind, ParameterInformation, ParameterLabel, SignatureHelp, SignatureInformation,
+};
+
+/// The grammar of a CSS function, split into the arguments which are separated by commas or slashes. Functions with a
+/// legacy comma separated syntax, such as `rgb()`, have a signature for each syntax.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FunctionSignature {
+	pub name: &'static str,
+	pub documentation: &'static str,
+	// What each argument is separated from the last by, either `", "` or `" / "`.
+	pub separator: &'static str,
+	// The syntax of each argument, along with its documentation.
+	pub parameters: &'static [(&'static str, &'static str)],
+}
+
+macro_rules! signatures {
+	( $( $name: literal $separator: literal {
+		$documentation: literal,
+		$( $syntax: literal: $doc: literal, )+
+	} )+ ) => {
+		&[ $( FunctionSignature {
+			name: $name,
+			documentation: $documentation,
+			separator: $separator,
+			parameters: &[ $( ($syntax, $doc), )+ ],
+		}, )+ ]
+	};
+}
+
+pub const SIGNATURES: &[FunctionSignature] = signatures! {
+	"anchor" ", " {
+		"The position of a side of an anchor element, for use in the inset properties of an anchor-positioned element.",
+		"<anchor-name>? && <anchor-side>": "The anchor element to refer to (defaulting to the `position-anchor`), and which of its sides.",
+		"<length-percentage>": "The fallback used if the anchor can't be found.",
+	}
+	"anchor-size" ", " {
+		"The size of an anchor element, for use in the sizing properties of an anchor-positioned element.",
+		"<anchor-name>? && <anchor-size>?": "The anchor element to refer to (defaulting to the `position-anchor`), and which of its dimensions.",
+		"<length-percentage>": "The fallback used if the anchor can't be found.",
+	}
+	"attr" ", " {
+		"The value of an attribute of the element.",
+		"<attr-name> <attr-type>?": "The name of the attribute, and the type to read its value as.",
+		"<declaration-value>": "The fallback used if the attribute is missing or invalid.",
+	}
+	"clamp" ", " {
+		"A value between an upper and lower bound; equivalent to `max(MIN, min(VAL, MAX))`.",
+		"<calc-sum> | none": "The minimum value.",
+		"<calc-sum>": "The preferred value.",
+		"<calc-sum> | none": "The maximum value, which the minimum wins over if they conflict.",
+	}
+	"color-mix" ", " {
+		"A mix of two colors in a given color space.",
+		"<color-interpolation-method>": "The color space to mix in, such as `in srgb` or `in oklch longer hue`.",
+		"<color> && <percentage [0,100]>?": "The first color, and how much of it to mix in.",
+		"<color> && <percentage [0,100]>?": "The second color, and how much of it to mix in.",
+	}
+	"conic-gradient" ", " {
+		"An image of colors transitioning around a center point.",
+		"[ from <angle> ]? [ at <position> ]? || <color-interpolation-method>": "The angle the gradient starts at, its center, and the color space to interpolate in.",
+		"<angular-color-stop-list>": "The colors, along with the angle each one is placed at.",
+	}
+	"cubic-bezier" ", " {
+		"An easing function defined by a cubic Bézier curve, from (0, 0) to (1, 1).",
+		"<number [0,1]>": "The x coordinate of the first control point.",
+		"<number>": "The y coordinate of the first control point.",
+		"<number [0,1]>": "The x coordinate of the second control point.",
+		"<number>": "The y coordinate of the second control point.",
+	}
+	"env" ", " {
+		"The value of an environment variable defined by the user agent, such as `safe-area-inset-top`.",
+		"<custom-ident> <integer [0,∞]>*": "The name of the environment variable.",
+		"<declaration-value>": "The fallback used if the environment variable isn't defined.",
+	}
+	"fit-content" ", " {
+		"The track size `min(max-content, max(auto, LIMIT))`, for use in grid templates.",
+		"<length-percentage [0,∞]>": "The limit the track won't grow beyond.",
+	}
+	"hsl" " / " {
+		"A color in the sRGB color space, specified by its hue, saturation and lightness.",
+		"[ <hue> | none ] [ <percentage> | <number> | none ] [ <percentage> | <number> | none ]": "The hue, saturation and lightness.",
+		"[ <alpha-value> | none ]": "The opacity, from 0 to 1.",
+	}
+	"hsl" ", " {
+		"A color in the sRGB color space, specified by its hue, saturation and lightness, in the legacy comma separated syntax.",
+		"<hue>": "The hue.",
+		"<percentage>": "The saturation.",
+		"<percentage>": "The lightness.",
+		"<alpha-value>": "The opacity, from 0 to 1.",
+	}
+	"hwb" " / " {
+		"A color in the sRGB color space, specified by its hue, whiteness and blackness.",
+		"[ <hue> | none ] [ <percentage> | <number> | none ] [ <percentage> | <number> | none ]": "The hue, whiteness and blackness.",
+		"[ <alpha-value> | none ]": "The opacity, from 0 to 1.",
+	}
+	"lab" " / " {
+		"A color in the CIE Lab color space.",
+		"[ <percentage> | <number> | none ] [ <percentage> | <number> | none ]{2}": "The lightness, and the a and b axes.",
+		"[ <alpha-value> | none ]": "The opacity, from 0 to 1.",
+	}
+	"lch" " / " {
+		"A color in the CIE LCH color space.",
+		"[ <percentage> | <number> | none ] [ <percentage> | <number> | none ] [ <hue> | none ]": "The lightness, chroma and hue.",
+		"[ <alpha-value> | none ]": "The opacity, from 0 to 1.",
+	}
+	"light-dark" ", " {
+		"One of two colors, depending on whether the element uses a light or dark color scheme.",
+		"<color>": "The color used for a light color scheme.",
+		"<color>": "The color used for a dark color scheme.",
+	}
+	"linear-gradient" ", " {
+		"An image of colors transitioning along a straight line.",
+		"[ <angle> | to <side-or-corner> ]? || <color-interpolation-method>": "The direction of the gradient line, and the color space to interpolate in.",
+		"<color-stop-list>": "The colors, along with the position each one is placed at along the gradient line.",
+	}
+	"max" ", " {
+		"The largest of one or more values.",
+		"<calc-sum>#": "The values to compare.",
+	}
+	"min" ", " {
+		"The smallest of one or more values.",
+		"<calc-sum>#": "The values to compare.",
+	}
+	"minmax" ", " {
+		"A track size range, for use in grid templates.",
+		"<inflexible-breadth>": "The minimum size of the track.",
+		"<track-breadth>": "The maximum size of the track.",
+	}
+	"mod" ", " {
+		"The remainder of dividing one value by another, with the same sign as the divisor.",
+		"<calc-sum>": "The dividend.",
+		"<calc-sum>": "The divisor.",
+	}
+	"oklab" " / " {
+		"A color in the Oklab color space.",
+		"[ <percentage> | <number> | none ] [ <percentage> | <number> | none ]{2}": "The lightness, and the a and b axes.",
+		"[ <alpha-value> | none ]": "The opacity, from 0 to 1.",
+	}
+	"oklch" " / " {
+		"A color in the OkLCh color space.",
+		"[ <percentage> | <number> | none ] [ <percentage> | <number> | none ] [ <hue> | none ]": "The lightness, chroma and hue.",
+		"[ <alpha-value> | none ]": "The opacity, from 0 to 1.",
+	}
+	"pow" ", " {
+		"A value raised to the power of another.",
+		"<calc-sum>": "The base.",
+		"<calc-sum>": "The exponent.",
+	}
+	"radial-gradient" ", " {
+		"An image of colors transitioning outwards from a center point.",
+		"[ <radial-shape> || <radial-size> ]? [ at <position> ]? || <color-interpolation-method>": "The shape and size of the gradient, its center, and the color space to interpolate in.",
+		"<color-stop-list>": "The colors, along with the position each one is placed at along the gradient ray.",
+	}
+	"rem" ", " {
+		"The remainder of dividing one value by another, with the same sign as the dividend.",
+		"<calc-sum>": "The dividend.",
+		"<calc-sum>": "The divisor.",
+	}
+	"repeat" ", " {
+		"A fragment of a grid template, repeated a number of times.",
+		"[ <integer [1,∞]> | auto-fill | auto-fit ]": "How many times to repeat the tracks; `auto-fill` and `auto-fit` repeat as many times as fit in the container.",
+		"<track-list>": "The tracks, and the line names between them, to repeat.",
+	}
+	"rgb" " / " {
+		"A color in the sRGB color space, specified by its red, green and blue channels.",
+		"[ <percentage> | <number> | none ]{3}": "The red, green and blue channels.",
+		"[ <alpha-value> | none ]": "The opacity, from 0 to 1.",
+	}
+	"rgb" ", " {
+		"A color in the sRGB color space, specified by its red, green and blue channels, in the legacy comma separated syntax.",
+		"<percentage> | <number>": "The red channel.",
+		"<percentage> | <number>": "The green channel.",
+		"<percentage> | <number>": "The blue channel.",
+		"<alpha-value>": "The opacity, from 0 to 1.",
+	}
+	"round" ", " {
+		"A value rounded to the nearest multiple of another.",
+		"<rounding-strategy>? <calc-sum>": "How to round (`nearest`, `up`, `down` or `to-zero`), and the value to round.",
+		"<calc-sum>": "The interval to round to a multiple of, defaulting to 1.",
+	}
+	"steps" ", " {
+		"An easing function which jumps between a number of equal steps.",
+		"<integer>": "The number of steps.",
+		"<step-position>": "Where the jumps happen: `jump-start`, `jump-end` (the default), `jump-none` or `jump-both`.",
+	}
+	"var" ", " {
+		"The value of a custom property.",
+		"<custom-property-name>": "The name of the custom property, such as `--color`.",
+		"<declaration-value>?": "The fallback used if the custom property isn't set.",
+	}
+};
+
+impl FunctionSignature {
+	/// The signatures of the function with the given name, covering aliases such as `rgba()` and the `repeating-`
+	/// gradients.
+	pub fn from_name(name: &str) -> Vec<&'static Self> {
+		let name = name.to_ascii_lowercase();
+		let name = match name.as_str() {
+			"rgba" => "rgb",
+			"hsla" => "hsl",
+			name => name.strip_prefix("repeating-").filter(|name| name.ends_with("-gradient")).unwrap_or(name),
+		};
+		SIGNATURES.iter().filter(|signature| signature.name == name).collect()
+	}
+
+	/// The grammar written out for the function with the given name, along with the start and end of each parameter
+	/// within it. The name is the one written in the stylesheet, as signatures such as `linear-gradient` also cover their
+	/// `repeating-` variants.
+	pub fn label(&self, name: &str) -> (String, Vec<[u32; 2]>) {
+		let mut label = format!("{}(", name);
+		let mut offsets = vec![];
+		for (i, (syntax, _)) in self.parameters.iter().enumerate() {
+			if i > 0 {
+				label.push_str(self.separator);
+			}
+			let start = label.encode_utf16().count() as u32;
+			label.push_str(syntax);
+			offsets.push([start, label.encode_utf16().count() as u32]);
+		}
+		label.push(')');
+		(label, offsets)
+	}
+
+	/// Which parameter the offset within the function is in, counting only the separators this signature uses. Arguments
+	/// past the last parameter, such as the repetitions of `min()`, all count as the last.
+	pub fn active_parameter(&self, function: &ActiveFunction) -> u32 {
+		let argument = if self.separator == " / " { function.slashes } else { function.commas };
+		argument.min(self.parameters.len() as u32 - 1)
+	}
+
+	pub fn to_signature_information(&self, function: &ActiveFunction) -> SignatureInformation {
+		let name = function.name;
+		let (label, offsets) = self.label(name);
+		let parameters = self
+			.parameters
+			.iter()
+			.zip(offsets)
+			.map(|((_, documentation), offsets)| ParameterInformation {
+				label: ParameterLabel::LabelOffsets(offsets),
+				documentation: Some(Documentation::String(documentation.to_string())),
+			})
+			.collect();
+		SignatureInformation {
+			label,
+			documentation: Some(Documentation::MarkupContent(MarkupContent {
+				kind: MarkupKind::Markdown,
+				value: self.documentation.into(),
+			})),
+			parameters: Some(parameters),
+			active_parameter: Some(self.active_parameter(function)),
+		}
+	}
+}
+
+/// A function the offset is within, along with how many of each separator are between its arguments before the offset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ActiveFunction<'a> {
+	pub name: &'a str,
+	pub commas: u32,
+	pub slashes: u32,
+}
+
+/// The innermost function the offset is within. Blocks end any functions still open, so an unclosed function can't
+/// leak into the next declaration.
+pub fn active_function(source_text: &str, offset: SourceOffset) -> Option<ActiveFunction> {
+	let mut lexer = Lexer::new(source_text);
+	// Each open function or parenthesised block, with those which are functions.
+	let mut stack: Vec<Option<ActiveFunction>> = vec![];
+	loop {
+		let start = lexer.offset();
+		let token = lexer.advance();
+		if token == Kind::Eof || lexer.offset() > offset || (start == offset && token != Kind::Whitespace) {
+			break;
+		}
+		match token.kind() {
+			Kind::Function => {
+				let name = &source_text[start.0 as usize..lexer.offset().0 as usize - 1];
+				stack.push(Some(ActiveFunction { name, commas: 0, slashes: 0 }));
+			}
+			Kind::LeftParen | Kind::LeftSquare => stack.push(None),
+			Kind::RightParen | Kind::RightSquare => {
+				stack.pop();
+			}
+			Kind::LeftCurly | Kind::RightCurly | Kind::Semicolon => stack.clear(),
+			Kind::Comma => {
+				if let Some(Some(function)) = stack.last_mut() {
+					function.commas += 1;
+				}
+			}
+			Kind::Delim if token.char() == Some('/') => {
+				if let Some(Some(function)) = stack.last_mut() {
+					function.slashes += 1;
+				}
+			}
+			_ => {}
+		}
+	}
+	stack.pop().flatten()
+}
+
+/// Signature help for the function the offset is within, if it has a known [FunctionSignature]. A comma in a function
+/// with both syntaxes, such as `rgb(0, 0, 0)`, makes the legacy comma separated signature the active one.
+pub fn signature_help(source_text: &str, offset: SourceOffset) -> Option<SignatureHelp> {
+	let function = active_function(source_text, offset)?;
+	let signatures = FunctionSignature::from_name(function.name);
+	let active =
+		signatures.iter().position(|signature| function.commas == 0 || signature.separator == ", ").unwrap_or(0);
+	let signature = signatures.get(active)?;
+	Some(SignatureHelp {
+		signatures: signatures.iter().map(|signature| signature.to_signature_information(&function)).collect(),
+		active_signature: Some(active as u32),
+		active_parameter: Some(signature.active_parameter(&function)),
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// The active function at the `|` in the source text.
+	fn active(source_text: &str) -> Option<(String, u32, u32)> {
+		let offset = SourceOffset(source_text.find('|').unwrap() as u32);
+		let source_text = source_text.replace('|', "");
+		active_function(&source_text, offset)
+			.map(|function| (function.name.to_string(), function.commas, function.slashes))
+	}
+
+	// The active signature and parameter at the `|` in the source text.
+	fn help(source_text: &str) -> Option<(u32, u32)> {
+		let offset = SourceOffset(source_text.find('|').unwrap() as u32);
+		let help = signature_help(&source_text.replace('|', ""), offset)?;
+		Some((help.active_signature?, help.active_parameter?))
+	}
+
+	#[test]
+	fn test_active_function() {
+		assert_eq!(active("a { width: clamp(|) }"), Some(("clamp".into(), 0, 0)));
+		assert_eq!(active("a { width: clamp(1px, 2vw|, 3px) }"), Some(("clamp".into(), 1, 0)));
+		assert_eq!(active("a { width: clamp(1px, 2vw, |) }"), Some(("clamp".into(), 2, 0)));
+		assert_eq!(active("a { width: clamp(1px, calc(2vw + |1px), 3px) }"), Some(("calc".into(), 0, 0)));
+		assert_eq!(active("a { width: clamp(1px, calc(2vw + 1px), |3px) }"), Some(("clamp".into(), 2, 0)));
+		assert_eq!(active("a { width: clamp(1px, (2vw + 1px), |3px) }"), Some(("clamp".into(), 2, 0)));
+		assert_eq!(active("a { width: clamp(1px, 10vw / 2, |3px) }"), Some(("clamp".into(), 2, 1)));
+		assert_eq!(active("a { color: oklch(50% 0.1 120 / |0.5) }"), Some(("oklch".into(), 0, 1)));
+		assert_eq!(active("a { width: clamp(1px, 2vw, 3px)| }"), None);
+		assert_eq!(active("a { width: clamp|(1px) }"), None);
+		assert_eq!(active("a { width: |clamp(1px) }"), None);
+		assert_eq!(active("a { width: clamp(1px; color: |red }"), None);
+	}
+
+	#[test]
+	fn test_active_parameter() {
+		assert_eq!(help("a { width: clamp(1px, 10vw / 2, |3px) }"), Some((0, 2)));
+		assert_eq!(help("a { width: min(a / 2, |b) }"), Some((0, 0)));
+		assert_eq!(help("a { width: min(1px, 2px, |3px) }"), Some((0, 0)));
+		assert_eq!(help("a { width: pow(1, 2, |3) }"), Some((0, 1)));
+		assert_eq!(help("a { color: oklch(50% 0.1 120 / |0.5) }"), Some((0, 1)));
+		assert_eq!(help("a { color: hsl(120 50% |50%) }"), Some((0, 0)));
+		assert_eq!(help("a { color: hsl(120, 50%, |50%) }"), Some((1, 2)));
+		assert_eq!(help("a { color: hsla(120, 50%, 50%, |0.5) }"), Some((1, 3)));
+		assert_eq!(help("a { color: rgba(0 0 0 / |0.5) }"), Some((0, 1)));
+		assert_eq!(help("a { color: hwb(120 0% |0%) }"), Some((0, 0)));
+		assert_eq!(help("a { color: unknown(|) }"), None);
+	}
+
+	#[test]
+	fn test_signature_help() {
+		let source_text = "a { background: repeating-linear-gradient(to right, red, blue, green) }";
+		let offset = SourceOffset(source_text.find("green").unwrap() as u32);
+		let help = signature_help(source_text, offset).unwrap();
+		let signature = &help.signatures[0];
+		assert_eq!(
+			signature.label,
+			"repeating-linear-gradient([ <angle> | to <side-or-corner> ]? || <color-interpolation-method>, <color-stop-list>)"
+		);
+		assert_eq!(help.active_parameter, Some(1));
+		let Some(ParameterLabel::LabelOffsets([start, end])) =
+			signature.parameters.as_ref().map(|p| p[1].label.clone())
+		else {
+			panic!("Expected label offsets");
+		};
+		assert_eq!(&signature.label[start as usize..end as usize], "<color-stop-list>");
+
+		let source_text = "a { transition-timing-function: cubic-bezier(0.1, 0.7, 1, 0.1) }";
+		let offset = SourceOffset(source_text.find(" 1,").unwrap() as u32 + 1);
+		assert_eq!(signature_help(source_text, offset).unwrap().active_parameter, Some(2));
+
+		let help = signature_help("a { color: rgb(0, 0, 0) }", SourceOffset(17)).unwrap();
+		assert_eq!(help.signatures[0].label, "rgb([ <percentage> | <number> | none ]{3} / [ <alpha-value> | none ])");
+		assert_eq!(help.active_signature, Some(1));
+		assert_eq!(help.active_parameter, Some(1));
+	}
+
+	#[test]
+	fn test_signatures() {
+		for signature in SIGNATURES {
+			assert!(FunctionSignature::from_name(signature.name).contains(&signature));
+			assert!(!signature.parameters.is_empty());
+			assert!(matches!(signature.separator, ", " | " / "));
+		}
+		assert_eq!(FunctionSignature::from_name("RGBA"), FunctionSignature::from_name("rgb"));
+		assert_eq!(
+			FunctionSignature::from_name("repeating-conic-gradient"),
+			FunctionSignature::from_name("conic-gradient")
+		);
+	}
+}
@@ -0,0 +1,97 @@
+use crossbeam_channel::{Receiver, Select, Sender};
+use lsp_types::{notification::Notification as NotificationTrait, request::Request as RequestTrait};
+use serde_json::{from_value, to_value, Value};
+use std::time::Duration;
+
+use crate::{Handler, Message, Notification, Request, Response, Server};
+
+// How long to wait on the server before deciding it has deadlocked, rather than waiting forever.
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Drives a [Server] as a client would, through a session of JSON-RPC messages. Every message the server sends back is
+/// recorded, so the whole session can be asserted on with a single snapshot.
+pub(crate) struct Session {
+	// The server is kept for the channels it holds, which would otherwise close.
+	_server: Server,
+	sender: Sender<Message>,
+	receiver: Receiver<Message>,
+	next_id: i32,
+	transcript: Vec<Value>,
+}
+
+impl Session {
+	pub fn new(handler: impl Handler) -> Self {
+		let server = Server::new(handler);
+		let (sender, receiver) = server.raw_channels();
+		Self { _server: server, sender, receiver, next_id: 0, transcript: vec![] }
+	}
+
+	/// The messages the server has sent, in order, as JSON.
+	pub fn transcript(&self) -> &[Value] {
+		&self.transcript
+	}
+
+	// Hand a message over to the server, recording any messages it sends in the meantime, as it may be waiting to send
+	// a request of its own before it can take this one.
+	fn send(&mut self, message: Message) {
+		loop {
+			let mut select = Select::new();
+			let send = select.send(&self.sender);
+			let receive = select.recv(&self.receiver);
+			let Ok(operation) = select.select_timeout(TIMEOUT) else {
+				panic!("Server didn't take {:?} after {:?}, it may have deadlocked", message, TIMEOUT);
+			};
+			if operation.index() == send {
+				operation.send(&self.sender, message).unwrap();
+				return;
+			}
+			debug_assert_eq!(operation.index(), receive);
+			self.transcript.push(to_value(operation.recv(&self.receiver).unwrap()).unwrap());
+		}
+	}
+
+	// Wait for the response to the request with the given id, recording it along with any other messages the server
+	// sends in the meantime.
+	fn receive(&mut self, message: Message) -> Response {
+		let id = message.id();
+		self.send(message);
+		loop {
+			let Ok(message) = self.receiver.recv_timeout(TIMEOUT) else {
+				panic!("No response to request {:?} after {:?}, the server may have deadlocked", id, TIMEOUT);
+			};
+			self.transcript.push(to_value(&message).unwrap());
+			let responded = message.id() == id;
+			match message {
+				Message::Response(response) if responded => return response,
+				_ => {}
+			}
+		}
+	}
+
+	/// Send a request, and wait for the response to it.
+	pub fn request<R: RequestTrait>(&mut self, params: R::Params) -> Response {
+		self.next_id += 1;
+		self.receive(Message::Request(Request::new::<R>(self.next_id.into(), params)))
+	}
+
+	/// Send a notification, which the server won't respond to.
+	pub fn notify<N: NotificationTrait>(&mut self, params: N::Params) {
+		self.send(Message::Notification(Notification { method: N::METHOD.into(), params: to_value(params).unwrap() }));
+	}
+
+	/// Run a script of JSON-RPC messages, given as an array. Requests, which have an `id`, wait for the server's
+	/// response before the next message is sent.
+	pub fn run(&mut self, script: Value) -> &mut Self {
+		let Value::Array(messages) = script else { panic!("Expected an array of messages, got {}", script) };
+		for message in messages {
+			match from_value::<Message>(message.clone()) {
+				Ok(message @ Message::Request(_)) => {
+					self.receive(message);
+				}
+				Ok(message @ Message::Notification(_)) => self.send(message),
+				_ => panic!("Expected a request or notification, got {}", message),
+			}
+		}
+		self
+	}
+}
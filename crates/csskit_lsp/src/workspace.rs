@@ -0,0 +1,216 @@
+use lsp_types::Uri;
+use serde_json::Value;
+use std::{
+	fs,
+	path::{Path, PathBuf},
+	str::FromStr,
+};
+use tracing::warn;
+
+use crate::{Config, CONFIG_FILE_NAME};
+
+/// The folders the client has open, and the [Config] for them.
+///
+/// The project config comes from the [CONFIG_FILE_NAME] file of the first folder which has one, and is kept apart from
+/// the client's settings so that either can be reloaded without losing the other.
+#[derive(Debug, Default)]
+pub struct Workspace {
+	folders: Vec<PathBuf>,
+	project_config: Value,
+	settings: Value,
+	config: Config,
+}
+
+impl Workspace {
+	pub fn folders(&self) -> &[PathBuf] {
+		&self.folders
+	}
+
+	pub fn config(&self) -> &Config {
+		&self.config
+	}
+
+	pub fn add_folder(&mut self, folder: PathBuf) {
+		if !self.folders.contains(&folder) {
+			self.folders.push(folder);
+			self.reload_project_config();
+		}
+	}
+
+	pub fn remove_folder(&mut self, folder: &Path) {
+		self.folders.retain(|f| f != folder);
+		self.reload_project_config();
+	}
+
+	/// Whether the file is within one of the workspace folders.
+	pub fn contains(&self, path: &Path) -> bool {
+		self.folder_for(path).is_some()
+	}
+
+	/// The workspace folder the file is within.
+	pub fn folder_for(&self, path: &Path) -> Option<&Path> {
+		self.folders.iter().find(|folder| path.starts_with(folder)).map(PathBuf::as_path)
+	}
+
+	/// Whether the file is the project config file of one of the workspace folders.
+	pub fn is_config_file(&self, path: &Path) -> bool {
+		self.folders.iter().any(|folder| path == folder.join(CONFIG_FILE_NAME))
+	}
+
+	/// Replace the client's settings, which are either the config itself or an object with a `csskit` key holding it.
+	pub fn set_settings(&mut self, settings: Value) {
+		self.settings = match settings {
+			Value::Object(mut map) if map.contains_key("csskit") => map.remove("csskit").unwrap(),
+			settings => settings,
+		};
+		self.update_config();
+	}
+
+	pub fn reload_project_config(&mut self) {
+		self.project_config = Value::Null;
+		for path in self.folders.iter().map(|folder| folder.join(CONFIG_FILE_NAME)) {
+			let Ok(contents) = fs::read_to_string(&path) else { continue };
+			match serde_json::from_str(&contents) {
+				Ok(value) => self.project_config = value,
+				Err(e) => warn!("Ignoring {:?}, which isn't valid JSON: {}", path, e),
+			}
+			break;
+		}
+		self.update_config();
+	}
+
+	// An invalid config is reported and ignored, leaving the last valid one in place.
+	fn update_config(&mut self) {
+		match Config::from_values(&self.project_config, &self.settings) {
+			Ok(config) => self.config = config,
+			Err(e) => warn!("Ignoring invalid config: {}", e),
+		}
+	}
+
+	/// Every CSS (or SCSS) file within the workspace folders. Hidden directories and `node_modules` are skipped, as
+	/// they're rarely edited and can be huge. Symlinked directories are skipped too, as they may loop back on
+	/// themselves.
+	pub fn css_files(&self) -> Vec<PathBuf> {
+		let mut files = vec![];
+		for folder in &self.folders {
+			collect_css_files(folder, &mut files);
+		}
+		files
+	}
+}
+
+fn collect_css_files(dir: &Path, files: &mut Vec<PathBuf>) {
+	let Ok(entries) = fs::read_dir(dir) else { return };
+	for entry in entries.flatten() {
+		let Ok(file_type) = entry.file_type() else { continue };
+		let path = entry.path();
+		let name = entry.file_name();
+		let name = name.to_string_lossy();
+		if file_type.is_dir() {
+			if !name.starts_with('.') && name != "node_modules" {
+				collect_css_files(&path, files);
+			}
+		} else if is_css_file(&path) {
+			files.push(path);
+		}
+	}
+}
+
+/// Whether the file at the path is a stylesheet, either CSS or [SCSS][is_scss_file].
+pub fn is_css_file(path: &Path) -> bool {
+	path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("css")) || is_scss_file(path)
+}
+
+/// Whether the file at the path is SCSS, and so should be parsed with [scss_ast] rather than as plain CSS.
+pub fn is_scss_file(path: &Path) -> bool {
+	path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("scss"))
+}
+
+/// The path of a `file:` [Uri], or None if it has some other scheme.
+pub fn uri_to_path(uri: &Uri) -> Option<PathBuf> {
+	let path = uri.as_str().strip_prefix("file://")?;
+	let bytes = path.as_bytes();
+	let mut decoded = Vec::with_capacity(bytes.len());
+	let mut i = 0;
+	while i < bytes.len() {
+		if bytes[i] == b'%' {
+			let hex = path.get(i + 1..i + 3)?;
+			decoded.push(u8::from_str_radix(hex, 16).ok()?);
+			i += 3;
+		} else {
+			decoded.push(bytes[i]);
+			i += 1;
+		}
+	}
+	Some(PathBuf::from(String::from_utf8(decoded).ok()?))
+}
+
+/// The `file:` [Uri] of an absolute path.
+pub fn path_to_uri(path: &Path) -> Option<Uri> {
+	let mut uri = String::from("file://");
+	for byte in path.to_str()?.bytes() {
+		match byte {
+			b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'/' | b'-' | b'.' | b'_' | b'~' => uri.push(byte as char),
+			_ => uri.push_str(&format!("%{:02X}", byte)),
+		}
+	}
+	Uri::from_str(&uri).ok()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use css_ast::{Browser, Version};
+	use serde_json::json;
+
+	#[test]
+	fn test_uri_to_path() {
+		let uri = Uri::from_str("file:///home/me/my%20styles/a.css").unwrap();
+		assert_eq!(uri_to_path(&uri), Some(PathBuf::from("/home/me/my styles/a.css")));
+		assert_eq!(path_to_uri(Path::new("/home/me/my styles/a.css")), Some(uri));
+		assert_eq!(uri_to_path(&Uri::from_str("untitled:Untitled-1").unwrap()), None);
+	}
+
+	#[test]
+	fn test_workspace() {
+		let folder = std::env::temp_dir().join(format!("csskit_lsp_test_workspace_{}", std::process::id()));
+		fs::create_dir_all(folder.join("src/nested")).unwrap();
+		fs::create_dir_all(folder.join("node_modules/dep")).unwrap();
+		fs::write(folder.join("src/a.css"), "a {}").unwrap();
+		fs::write(folder.join("src/nested/b.CSS"), "b {}").unwrap();
+		fs::write(folder.join("src/c.scss"), "c {}").unwrap();
+		fs::write(folder.join("src/c.less"), "c {}").unwrap();
+		fs::write(folder.join("node_modules/dep/d.css"), "d {}").unwrap();
+		fs::write(folder.join(CONFIG_FILE_NAME), r#"{ "targets": "safari 15" }"#).unwrap();
+		#[cfg(unix)]
+		std::os::unix::fs::symlink(&folder, folder.join("src/loop")).unwrap();
+
+		let mut workspace = Workspace::default();
+		workspace.add_folder(folder.clone());
+		let mut files = workspace.css_files();
+		files.sort();
+		assert_eq!(files, [folder.join("src/a.css"), folder.join("src/c.scss"), folder.join("src/nested/b.CSS")]);
+		assert!(workspace.contains(&folder.join("src/a.css")));
+		assert_eq!(workspace.folder_for(&folder.join("src/a.css")), Some(folder.as_path()));
+		assert!(workspace.is_config_file(&folder.join(CONFIG_FILE_NAME)));
+		assert_eq!(workspace.config().targets.version(Browser::Safari), Some(Version(15, 0)));
+
+		workspace.set_settings(json!({ "csskit": { "targets": "chrome 100" } }));
+		assert_eq!(workspace.config().targets.version(Browser::Safari), None);
+		assert_eq!(workspace.config().targets.version(Browser::Chrome), Some(Version(100, 0)));
+
+		// Invalid config leaves the last valid config in place.
+		workspace.set_settings(json!({ "targets": "netscape 4" }));
+		assert_eq!(workspace.config().targets.version(Browser::Chrome), Some(Version(100, 0)));
+
+		workspace.set_settings(Value::Null);
+		fs::write(folder.join(CONFIG_FILE_NAME), r#"{ "targets": "firefox 120" }"#).unwrap();
+		workspace.reload_project_config();
+		assert_eq!(workspace.config().targets.version(Browser::Firefox), Some(Version(120, 0)));
+
+		workspace.remove_folder(&folder);
+		assert!(!workspace.contains(&folder.join("src/a.css")));
+		assert!(workspace.css_files().is_empty());
+		fs::remove_dir_all(&folder).unwrap();
+	}
+}
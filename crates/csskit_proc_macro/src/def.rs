@@ -1474,9 +1474,17 @@ impl DefType {
 
 	pub fn requires_allocator_lifetime(&self) -> bool {
 		if let Self::Custom(DefIdent(ident), _) = self {
-			return matches!(ident.as_str(), "OutlineColor" | "BorderTopColorStyleValue" | "DynamicRangeLimitMix");
+			return matches!(
+				ident.as_str(),
+				"OutlineColor"
+					| "BorderTopColorStyleValue"
+					| "BorderBlockStartStyleValue"
+					| "CaretColorStyleValue"
+					| "ColumnRuleColorStyleValue"
+					| "DynamicRangeLimitMix"
+			);
 		}
-		matches!(self, Self::Image | Self::Image1D)
+		matches!(self, Self::Image | Self::Image1D | Self::Color)
 	}
 }
 
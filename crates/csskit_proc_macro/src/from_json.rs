@@ -0,0 +1,317 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{
+	parse_quote, Attribute, Data, DataEnum, DeriveInput, Error, Fields, GenericParam, Ident, LitStr, Result, Variant,
+};
+
+/// The subset of `#[serde(...)]` attributes that change the shape of the serialized output. `FromJson` has to read
+/// exactly what `serde::Serialize` wrote, so it follows the same attributes rather than having its own.
+#[derive(Default)]
+struct SerdeAttrs {
+	tag: Option<String>,
+	content: Option<String>,
+	rename: Option<String>,
+	rename_all: Option<String>,
+	untagged: bool,
+	transparent: bool,
+}
+
+impl SerdeAttrs {
+	fn parse(attrs: &[Attribute]) -> Result<Self> {
+		let mut out = Self::default();
+		for attr in attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+			attr.parse_nested_meta(|meta| {
+				if meta.path.is_ident("tag") {
+					out.tag = Some(meta.value()?.parse::<LitStr>()?.value());
+				} else if meta.path.is_ident("content") {
+					out.content = Some(meta.value()?.parse::<LitStr>()?.value());
+				} else if meta.path.is_ident("rename_all") {
+					out.rename_all = Some(meta.value()?.parse::<LitStr>()?.value());
+				} else if meta.path.is_ident("rename") {
+					if meta.input.peek(syn::Token![=]) {
+						out.rename = Some(meta.value()?.parse::<LitStr>()?.value());
+					} else {
+						// `rename(serialize = "..", deserialize = "..")`; only the serialized name matters here.
+						meta.parse_nested_meta(|meta| {
+							let value = meta.value()?.parse::<LitStr>()?.value();
+							if meta.path.is_ident("serialize") {
+								out.rename = Some(value);
+							}
+							Ok(())
+						})?;
+					}
+				} else if meta.path.is_ident("untagged") {
+					out.untagged = true;
+				} else if meta.path.is_ident("transparent") {
+					out.transparent = true;
+				} else if meta.input.peek(syn::Token![=]) {
+					meta.value()?.parse::<syn::Expr>()?;
+				} else if meta.input.peek(syn::token::Paren) {
+					meta.parse_nested_meta(|meta| {
+						if meta.input.peek(syn::Token![=]) {
+							meta.value()?.parse::<syn::Expr>()?;
+						}
+						Ok(())
+					})?;
+				}
+				Ok(())
+			})?;
+		}
+		Ok(out)
+	}
+}
+
+fn unraw(ident: &Ident) -> String {
+	ident.to_string().trim_start_matches("r#").to_string()
+}
+
+// Mirrors serde's `rename_all` rules for variant names, which are PascalCase.
+fn rename_variant(name: &str, rule: Option<&str>) -> String {
+	let snake = || {
+		let mut snake = String::new();
+		for (i, ch) in name.char_indices() {
+			if i > 0 && ch.is_uppercase() {
+				snake.push('_');
+			}
+			snake.push(ch.to_ascii_lowercase());
+		}
+		snake
+	};
+	match rule {
+		Some("lowercase") => name.to_ascii_lowercase(),
+		Some("UPPERCASE") => name.to_ascii_uppercase(),
+		Some("camelCase") => name[..1].to_ascii_lowercase() + &name[1..],
+		Some("snake_case") => snake(),
+		Some("SCREAMING_SNAKE_CASE") => snake().to_ascii_uppercase(),
+		Some("kebab-case") => snake().replace('_', "-"),
+		Some("SCREAMING-KEBAB-CASE") => snake().replace('_', "-").to_ascii_uppercase(),
+		_ => name.to_string(),
+	}
+}
+
+// Mirrors serde's `rename_all` rules for field names, which are snake_case.
+fn rename_field(name: &str, rule: Option<&str>) -> String {
+	match rule {
+		Some("UPPERCASE") | Some("SCREAMING_SNAKE_CASE") => name.to_ascii_uppercase(),
+		Some("PascalCase") => crate::pascal(name.to_string()),
+		Some("camelCase") => {
+			let pascal = crate::pascal(name.to_string());
+			pascal[..1].to_ascii_lowercase() + &pascal[1..]
+		}
+		Some("kebab-case") => name.replace('_', "-"),
+		Some("SCREAMING-KEBAB-CASE") => name.replace('_', "-").to_ascii_uppercase(),
+		_ => name.to_string(),
+	}
+}
+
+/// Generates the expression which builds `path` (a struct, or an enum variant) from the JSON `value`. `tag` is the
+/// name & expected value of a tag which sits alongside the fields of a named struct.
+fn build_fields(
+	path: TokenStream,
+	type_name: &str,
+	fields: &Fields,
+	rename_all: Option<&str>,
+	tag: Option<(&str, &str)>,
+	transparent: bool,
+) -> Result<TokenStream> {
+	Ok(match fields {
+		Fields::Unit => quote! {{
+			::css_parse::JsonObject::expect_null(#type_name, value)?;
+			#path
+		}},
+		Fields::Unnamed(unnamed) if unnamed.unnamed.len() == 1 => quote! {
+			#path(::css_parse::FromJson::from_json(p, value)?)
+		},
+		Fields::Unnamed(unnamed) => {
+			let len = unnamed.unnamed.len();
+			let items = (0..len).map(|i| quote! { ::css_parse::FromJson::from_json(p, &items[#i])? });
+			quote! {{
+				let items = ::css_parse::JsonObject::expect_array(#type_name, value, #len)?;
+				#path(#(#items),*)
+			}}
+		}
+		Fields::Named(named) if transparent => {
+			let ident = &named.named.first().unwrap().ident;
+			quote! { #path { #ident: ::css_parse::FromJson::from_json(p, value)? } }
+		}
+		Fields::Named(named) => {
+			let mut names = vec![];
+			let mut inits = vec![];
+			for field in &named.named {
+				let attrs = SerdeAttrs::parse(&field.attrs)?;
+				let ident = field.ident.as_ref().unwrap();
+				let name = attrs.rename.unwrap_or_else(|| rename_field(&unraw(ident), rename_all));
+				inits.push(quote! { #ident: object.field(p, #name)? });
+				names.push(name);
+			}
+			let tag_check = tag.map(|(tag, name)| {
+				names.push(tag.to_string());
+				quote! { object.expect_tag(#tag, #name)?; }
+			});
+			quote! {{
+				let object = ::css_parse::JsonObject::new(#type_name, value)?;
+				#tag_check
+				object.deny_unknown_fields(&[#(#names),*])?;
+				#path { #(#inits),* }
+			}}
+		}
+	})
+}
+
+fn variant_name(variant: &Variant, attrs: &SerdeAttrs, rename_all: Option<&str>) -> String {
+	attrs.rename.clone().unwrap_or_else(|| rename_variant(&unraw(&variant.ident), rename_all))
+}
+
+fn generate_enum(ident: &Ident, container: &SerdeAttrs, data: &DataEnum) -> Result<TokenStream> {
+	let type_name = ident.to_string();
+	let rename_all = container.rename_all.as_deref();
+	let mut tagged = vec![];
+	let mut untagged = vec![];
+	for variant in &data.variants {
+		let attrs = SerdeAttrs::parse(&variant.attrs)?;
+		let variant_ident = &variant.ident;
+		let path = quote! { Self::#variant_ident };
+		let name = variant_name(variant, &attrs, rename_all);
+		if container.untagged || attrs.untagged {
+			let build = build_fields(path, &type_name, &variant.fields, None, None, false)?;
+			untagged.push(quote! {
+				if let Some(node) = ::css_parse::JsonObject::attempt(&mut closest, || Ok(#build)) {
+					return Ok(node);
+				}
+			});
+			continue;
+		}
+		let arm = match (&container.tag, &container.content) {
+			// Adjacently tagged: `{ tag: name, content: value }`
+			(Some(_), Some(content)) => {
+				let build = build_fields(path, &type_name, &variant.fields, None, None, false)?;
+				quote! {
+					#name => {
+						let value = object.get(#content);
+						return Ok(#build);
+					}
+				}
+			}
+			// Internally tagged: `{ tag: name, ...fields }`
+			(Some(tag), None) => match &variant.fields {
+				Fields::Named(_) => {
+					let build = build_fields(path, &type_name, &variant.fields, None, Some((tag, &name)), false)?;
+					quote! { #name => return Ok(#build), }
+				}
+				Fields::Unit => quote! { #name => return Ok(#path), },
+				Fields::Unnamed(unnamed) if unnamed.unnamed.len() == 1 => {
+					quote! { #name => return Ok(#path(object.content(p, #tag)?)), }
+				}
+				_ => return Err(Error::new(variant_ident.span(), "internally tagged variants must not be tuples")),
+			},
+			// Externally tagged: `"name"` for units, otherwise `{ name: value }`
+			_ => match &variant.fields {
+				Fields::Unit => quote! { #name => return Ok(#path), },
+				_ => {
+					let build = build_fields(path, &type_name, &variant.fields, None, None, false)?;
+					quote! {
+						#name => {
+							let value = inner;
+							return Ok(#build);
+						}
+					}
+				}
+			},
+		};
+		tagged.push(arm);
+	}
+	let tagged = if tagged.is_empty() {
+		quote! {}
+	} else {
+		match &container.tag {
+			Some(tag) => quote! {
+				if let Some(name) = value.get(#tag).and_then(|tag| tag.as_str()) {
+					let object = ::css_parse::JsonObject::new(#type_name, value)?;
+					let _ = &object;
+					match name {
+						#(#tagged)*
+						_ => {}
+					}
+				}
+			},
+			None => quote! {
+				if let Some((name, inner)) = ::css_parse::JsonObject::external_tag(value) {
+					match name {
+						#(#tagged)*
+						_ => {}
+					}
+				}
+			},
+		}
+	};
+	if untagged.is_empty() {
+		return Ok(quote! {
+			#tagged
+			Err(::css_parse::JsonObject::no_variant(#type_name, None))
+		});
+	}
+	Ok(quote! {
+		#tagged
+		let mut closest = None;
+		#(#untagged)*
+		Err(::css_parse::JsonObject::no_variant(#type_name, closest))
+	})
+}
+
+pub fn derive(input: DeriveInput) -> TokenStream {
+	match generate(input) {
+		Ok(tokens) => tokens,
+		Err(err) => err.into_compile_error(),
+	}
+}
+
+fn generate(input: DeriveInput) -> Result<TokenStream> {
+	let ident = &input.ident;
+	let container = SerdeAttrs::parse(&input.attrs)?;
+	let body = match &input.data {
+		Data::Struct(data) => {
+			let name = container.rename.clone().unwrap_or_else(|| ident.to_string());
+			let tag = container.tag.as_deref().map(|tag| (tag, name.as_str()));
+			let build = build_fields(
+				quote! { Self },
+				&ident.to_string(),
+				&data.fields,
+				container.rename_all.as_deref(),
+				tag,
+				container.transparent,
+			)?;
+			quote! { Ok(#build) }
+		}
+		Data::Enum(data) => generate_enum(ident, &container, data)?,
+		Data::Union(_) => return Err(Error::new(ident.span(), "cannot derive FromJson on a Union")),
+	};
+
+	// The trait lifetime is the type's own lifetime if it has one, as that's the lifetime of the allocator.
+	let mut generics = input.generics.clone();
+	let lifetime = match generics.lifetimes().next() {
+		Some(param) => param.lifetime.clone(),
+		None => {
+			let lifetime = syn::Lifetime::new("'a", Span::call_site());
+			generics.params.insert(0, parse_quote! { #lifetime });
+			lifetime
+		}
+	};
+	for param in generics.params.iter_mut() {
+		if let GenericParam::Type(param) = param {
+			param.bounds.push(parse_quote! { ::css_parse::FromJson<#lifetime> });
+		}
+	}
+	let (impl_generics, _, where_clause) = generics.split_for_impl();
+	let (_, ty_generics, _) = input.generics.split_for_impl();
+	Ok(quote! {
+		#[automatically_derived]
+		impl #impl_generics ::css_parse::FromJson<#lifetime> for #ident #ty_generics #where_clause {
+			fn from_json(
+				p: &::css_parse::Parser<#lifetime>,
+				value: &::css_parse::JsonValue,
+			) -> ::core::result::Result<Self, ::css_parse::JsonError> {
+				#body
+			}
+		}
+	})
+}
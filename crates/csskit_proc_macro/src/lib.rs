@@ -4,6 +4,7 @@ use syn::{parse_macro_input, DeriveInput};
 mod string_transform;
 
 mod def;
+mod from_json;
 mod initial;
 mod value;
 // mod applies_to;
@@ -24,6 +25,15 @@ pub fn value(args: TokenStream, input: TokenStream) -> TokenStream {
 	value::generate(args.0, ast).into()
 }
 
+/// Derives `css_parse::FromJson`, reading back the JSON that `serde::Serialize` writes. It follows the same
+/// `#[serde(...)]` attributes (`tag`, `content`, `untagged`, `rename`, `rename_all` & `transparent`), so the two
+/// derives are always used together.
+#[proc_macro_derive(FromJson, attributes(serde))]
+pub fn from_json(input: TokenStream) -> TokenStream {
+	let ast = parse_macro_input!(input as DeriveInput);
+	from_json::derive(ast).into()
+}
+
 #[proc_macro_attribute]
 pub fn initial(_args: TokenStream, input: TokenStream) -> TokenStream {
 	input
@@ -365,14 +365,14 @@ fn custom_function_variant_with_multiplier_args() {
 #[test]
 fn custom_function_all_optionals() {
 	let syntax = to_valuedef!(" <'caret-color'> || <'caret-animation'> || <'caret-shape'> ");
-	let data = to_deriveinput! { struct Foo; };
+	let data = to_deriveinput! { struct Foo<'a>; };
 	assert_snapshot!(syntax, data, "custom_function_all_optionals");
 }
 
 #[test]
 fn ordered_custom_function_last_option() {
 	let syntax = to_valuedef!(" <'caret-color'> <'caret-animation'>? ");
-	let data = to_deriveinput! { struct Foo; };
+	let data = to_deriveinput! { struct Foo<'a>; };
 	assert_snapshot!(syntax, data, "ordered_custom_function_last_option");
 }
 
@@ -407,13 +407,13 @@ fn bounded_range_multiplier_is_optimized_to_options_with_lifetimes_when_necessar
 #[test]
 fn value_fixed_range_color2_optimized() {
 	let syntax = to_valuedef! { <color>{2} };
-	let data = to_deriveinput! { struct Foo {} };
+	let data = to_deriveinput! { struct Foo<'a> {} };
 	assert_snapshot!(syntax, data, "value_fixed_range_color2_optimized");
 }
 
 #[test]
 fn value_fixed_range_auto_color2_optimized() {
 	let syntax = to_valuedef! { auto | <color>{2} };
-	let data = to_deriveinput! { enum Foo {} };
+	let data = to_deriveinput! { enum Foo<'a> {} };
 	assert_snapshot!(syntax, data, "value_fixed_range_auto_color2_optimized");
 }
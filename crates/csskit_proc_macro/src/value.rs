@@ -46,7 +46,7 @@ pub fn generate(defs: Def, ast: DeriveInput) -> TokenStream {
 
 		#(#attrs)*
 		#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-		#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+		#[cfg_attr(feature = "serde", derive(serde::Serialize, css_parse::FromJson), serde())]
 		#def
 		#peek_impl
 		#parse_impl
@@ -1,7 +1,8 @@
 use bumpalo::Bump;
-use css_ast::StyleSheet;
+use css_ast::{StyleSheet, Visitable};
 use css_lexer::{Kind, Lexer};
-use css_parse::{CursorFmtSink, Parser, ToCursors};
+use css_parse::{CursorFmtSink, Feature, Parser, ParserLimits, ParserReturn, ToCursors};
+use csskit_highlight::{HtmlRenderer, TokenHighlighter};
 #[cfg(not(feature = "fancy"))]
 use miette::JSONReportHandler;
 use miette::NamedSource;
@@ -16,6 +17,16 @@ pub fn main() {
 	console_error_panic_hook::set_once();
 }
 
+// Everything given to these functions comes from the page, so is parsed with the limits for untrusted input.
+fn parser<'a>(bump: &'a Bump, source_text: &'a str) -> Parser<'a> {
+	Parser::new(bump, source_text).with_limits(ParserLimits::UNTRUSTED)
+}
+
+// The features are given as the bits of a [Feature]; any bits which don't name a feature are ignored.
+fn feature_set(bits: u8) -> Feature {
+	Feature::from(bits).truncate()
+}
+
 #[wasm_bindgen]
 pub fn lex(source_text: String) -> Result<JsValue, serde_wasm_bindgen::Error> {
 	let mut lex = Lexer::new(source_text.as_str());
@@ -34,7 +45,89 @@ pub fn lex(source_text: String) -> Result<JsValue, serde_wasm_bindgen::Error> {
 #[wasm_bindgen]
 pub fn parse(source_text: String) -> Result<SerializableParserResult, serde_wasm_bindgen::Error> {
 	let allocator = Bump::default();
-	let result = Parser::new(&allocator, source_text.as_str()).parse_entirely::<StyleSheet>();
+	let result = parser(&allocator, source_text.as_str()).parse_entirely::<StyleSheet>();
+	Ok(to_serializable_result(result))
+}
+
+/// Parse the contents of a `style` attribute, such as `color: red; width: 100%`.
+#[wasm_bindgen]
+pub fn parse_style_attribute(
+	source_text: String,
+	features: u8,
+) -> Result<SerializableParserResult, serde_wasm_bindgen::Error> {
+	let allocator = Bump::default();
+	let result = css_ast::parse_style_attribute(
+		&allocator,
+		source_text.as_str(),
+		feature_set(features),
+		ParserLimits::UNTRUSTED,
+	);
+	Ok(to_serializable_result(result))
+}
+
+/// Parse the value of the named property, such as `1px solid` for `border`.
+#[wasm_bindgen]
+pub fn parse_property_value(
+	property: String,
+	source_text: String,
+	features: u8,
+) -> Result<SerializableParserResult, serde_wasm_bindgen::Error> {
+	let allocator = Bump::default();
+	let result = css_ast::parse_property_value(
+		&allocator,
+		&property,
+		source_text.as_str(),
+		feature_set(features),
+		ParserLimits::UNTRUSTED,
+	);
+	Ok(to_serializable_result(result))
+}
+
+/// Parse a selector list, such as `a, .b > c`.
+#[wasm_bindgen]
+pub fn parse_selector_list(
+	source_text: String,
+	features: u8,
+) -> Result<SerializableParserResult, serde_wasm_bindgen::Error> {
+	let allocator = Bump::default();
+	let result =
+		css_ast::parse_selector_list(&allocator, source_text.as_str(), feature_set(features), ParserLimits::UNTRUSTED);
+	Ok(to_serializable_result(result))
+}
+
+/// Parse a media query list, such as the argument to `matchMedia()`.
+#[wasm_bindgen]
+pub fn parse_media_query_list(
+	source_text: String,
+	features: u8,
+) -> Result<SerializableParserResult, serde_wasm_bindgen::Error> {
+	let allocator = Bump::default();
+	let result = css_ast::parse_media_query_list(
+		&allocator,
+		source_text.as_str(),
+		feature_set(features),
+		ParserLimits::UNTRUSTED,
+	);
+	Ok(to_serializable_result(result))
+}
+
+/// Parse a `@supports` condition, such as the argument to `CSS.supports()`.
+#[wasm_bindgen]
+pub fn parse_supports_condition(
+	source_text: String,
+	features: u8,
+) -> Result<SerializableParserResult, serde_wasm_bindgen::Error> {
+	let allocator = Bump::default();
+	let result = css_ast::parse_supports_condition(
+		&allocator,
+		source_text.as_str(),
+		feature_set(features),
+		ParserLimits::UNTRUSTED,
+	);
+	Ok(to_serializable_result(result))
+}
+
+fn to_serializable_result<T: ToCursors + Serialize>(result: ParserReturn<T>) -> SerializableParserResult {
 	let serializer = serde_wasm_bindgen::Serializer::json_compatible();
 	let diagnostics = result
 		.errors
@@ -56,13 +149,13 @@ pub fn parse(source_text: String) -> Result<SerializableParserResult, serde_wasm
 				.collect::<Vec<_>>()
 		})
 		.collect::<Vec<_>>();
-	Ok(SerializableParserResult { ast: result.output.serialize(&serializer).unwrap(), diagnostics })
+	SerializableParserResult { ast: result.output.serialize(&serializer).unwrap(), diagnostics }
 }
 
 #[wasm_bindgen]
 pub fn minify(source_text: String) -> Result<String, serde_wasm_bindgen::Error> {
 	let allocator = Bump::default();
-	let result = Parser::new(&allocator, source_text.as_str()).parse_entirely::<StyleSheet>();
+	let result = parser(&allocator, source_text.as_str()).parse_entirely::<StyleSheet>();
 	if !result.errors.is_empty() {
 		return Err(serde_wasm_bindgen::Error::new("Parse error"));
 	}
@@ -72,10 +165,28 @@ pub fn minify(source_text: String) -> Result<String, serde_wasm_bindgen::Error>
 	Ok(output_string)
 }
 
+/// Render the source text as HTML, with each highlighted token wrapped in a `<span>` whose classes (each prefixed with
+/// `class_prefix`) name its kind and modifiers.
+#[wasm_bindgen]
+pub fn highlight_html(source_text: String, class_prefix: String) -> Result<String, serde_wasm_bindgen::Error> {
+	let allocator = Bump::default();
+	let result = parser(&allocator, source_text.as_str()).parse_entirely::<StyleSheet>();
+	let mut highlighter = TokenHighlighter::new(source_text.as_str());
+	if let Some(stylesheet) = &result.output {
+		stylesheet.accept(&mut highlighter);
+	}
+	let mut html = String::new();
+	HtmlRenderer::new()
+		.with_class_prefix(&class_prefix)
+		.render(source_text.as_str(), &highlighter, &mut html)
+		.map_err(|_| serde_wasm_bindgen::Error::new("Could not render highlights"))?;
+	Ok(html)
+}
+
 #[wasm_bindgen]
 pub fn parse_error_report(source_text: String) -> String {
 	let allocator = Bump::default();
-	let result = Parser::new(&allocator, source_text.as_str()).parse_entirely::<StyleSheet>();
+	let result = parser(&allocator, source_text.as_str()).parse_entirely::<StyleSheet>();
 	#[cfg(feature = "fancy")]
 	let handler = GraphicalReportHandler::new_themed(GraphicalTheme::unicode_nocolor());
 	#[cfg(not(feature = "fancy"))]
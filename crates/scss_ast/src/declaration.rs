@@ -0,0 +1,177 @@
+use css_ast::{Property, Visit, Visitable};
+use css_lexer::{Cursor, Kind, KindSet};
+use css_parse::{
+	diagnostics, syntax::BangImportant, CursorSink, Parse, Parser, Peek, Result as ParserResult, ToCursors, T,
+};
+
+use crate::{expression::adjacent, Expression, Interpolation};
+
+/// A declaration within a SCSS style block, such as `color: red` or `margin: $gutter * 2`.
+///
+/// Declarations which are plain CSS are parsed as a [Property], so they have the same typed values as they would in a
+/// CSS style sheet. Declarations whose value uses SCSS (variables or interpolation) cannot be typed until the SCSS has
+/// been compiled, so they're parsed as a [ScssDeclaration] instead, as are declarations whose name uses interpolation
+/// (`#{$side}-margin: 0`).
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(untagged))]
+pub enum Declaration<'a> {
+	Css(Property<'a>),
+	Scss(ScssDeclaration<'a>),
+}
+
+impl<'a> Declaration<'a> {
+	// Look ahead to the end of the declaration to find out whether it uses SCSS. This also rejects nested style rules
+	// which look like declarations (for example `a:hover { ... }`), which CSS would otherwise parse as a declaration with
+	// a `{}` block in its value.
+	fn uses_scss(p: &mut Parser<'a>) -> ParserResult<bool> {
+		let checkpoint = p.checkpoint();
+		let name = p.next();
+		let custom = name == Kind::Ident && name.token().is_dashed_ident();
+		let mut previous = name;
+		let mut depth = 0;
+		let mut scss = name != Kind::Ident;
+		loop {
+			let c = p.next();
+			match c.token().kind() {
+				Kind::Eof => break,
+				Kind::Semicolon if depth == 0 => break,
+				Kind::LeftCurly => {
+					let interpolation = previous == Kind::Delim && previous == '#' && adjacent(previous, c);
+					if depth == 0 && !interpolation && !custom {
+						p.rewind(checkpoint);
+						Err(diagnostics::BadDeclaration(name.into()))?
+					}
+					scss |= interpolation;
+					depth += 1;
+				}
+				Kind::LeftParen | Kind::LeftSquare | Kind::Function => depth += 1,
+				Kind::RightCurly | Kind::RightParen | Kind::RightSquare => {
+					if depth == 0 {
+						break;
+					}
+					depth -= 1;
+				}
+				Kind::Delim if c == '$' => scss = true,
+				_ => {}
+			}
+			previous = c;
+		}
+		p.rewind(checkpoint);
+		Ok(scss)
+	}
+}
+
+impl<'a> Peek<'a> for Declaration<'a> {
+	fn peek(p: &Parser<'a>, c: Cursor) -> bool {
+		<Property>::peek(p, c) || <Interpolation>::peek(p, c)
+	}
+}
+
+impl<'a> Parse<'a> for Declaration<'a> {
+	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
+		if Self::uses_scss(p)? {
+			p.parse::<ScssDeclaration>().map(Self::Scss)
+		} else {
+			p.parse::<Property>().map(Self::Css)
+		}
+	}
+}
+
+impl<'a> ToCursors for Declaration<'a> {
+	fn to_cursors(&self, s: &mut impl CursorSink) {
+		match self {
+			Self::Css(property) => ToCursors::to_cursors(property, s),
+			Self::Scss(declaration) => ToCursors::to_cursors(declaration, s),
+		}
+	}
+}
+
+impl<'a> Visitable<'a> for Declaration<'a> {
+	fn accept<V: Visit<'a>>(&self, v: &mut V) {
+		if let Self::Css(property) = self {
+			Visitable::accept(property, v);
+		}
+	}
+}
+
+/// A declaration whose value is a SCSS [Expression], such as `width: $gutter * 2` or `--color: #{$primary}`. The name
+/// may also use interpolation, so it too is an [Expression].
+///
+/// ```md
+/// <scss-declaration>
+///  │├─ <expression> ─ ":" ─ <expression> ──╮─────────────────────────────╭─┤│
+///                                          ╰─ "!" ─ <ident "important"> ─╯
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type", rename = "scss-declaration"))]
+pub struct ScssDeclaration<'a> {
+	pub name: Expression<'a>,
+	pub colon: T![:],
+	pub value: Expression<'a>,
+	pub important: Option<BangImportant>,
+}
+
+impl<'a> Parse<'a> for ScssDeclaration<'a> {
+	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
+		let stop = p.set_stop(KindSet::new(&[Kind::Colon]));
+		let name = p.parse::<Expression>();
+		p.set_stop(stop);
+		let name = name?;
+		if name.is_empty() {
+			let c = p.peek_n(1);
+			Err(diagnostics::Unexpected(c.into(), c.into()))?
+		}
+		let colon = p.parse::<T![:]>()?;
+		let value = p.parse::<Expression>()?;
+		let important = p.parse_if_peek::<BangImportant>()?;
+		Ok(Self { name, colon, value, important })
+	}
+}
+
+impl<'a> ToCursors for ScssDeclaration<'a> {
+	fn to_cursors(&self, s: &mut impl CursorSink) {
+		ToCursors::to_cursors(&self.name, s);
+		s.append(self.colon.into());
+		ToCursors::to_cursors(&self.value, s);
+		if let Some(important) = &self.important {
+			ToCursors::to_cursors(important, s);
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use css_parse::{assert_parse, assert_parse_error};
+
+	#[test]
+	fn size_test() {
+		assert_eq!(std::mem::size_of::<Declaration>(), 432);
+		assert_eq!(std::mem::size_of::<ScssDeclaration>(), 104);
+	}
+
+	#[test]
+	fn test_writes() {
+		assert_parse!(Declaration, "color:red");
+		assert_parse!(Declaration, "color:$primary");
+		assert_parse!(Declaration, "margin:$gutter * 2 0!important");
+		assert_parse!(Declaration, "--color:#{$primary}");
+		assert_parse!(Declaration, "width:calc(100% - #{$sidebar})");
+		assert_parse!(Declaration, "#{$side}-margin:0");
+		assert_parse!(ScssDeclaration, "border:1px solid $border-color");
+	}
+
+	#[test]
+	fn test_kinds() {
+		let bump = bumpalo::Bump::default();
+		let declaration = Parser::new(&bump, "color:red").parse_entirely::<Declaration>().output.unwrap();
+		assert!(matches!(declaration, Declaration::Css(_)));
+		let declaration = Parser::new(&bump, "color:$primary").parse_entirely::<Declaration>().output.unwrap();
+		assert!(matches!(declaration, Declaration::Scss(_)));
+	}
+
+	#[test]
+	fn test_errors() {
+		assert_parse_error!(Declaration, "a:hover{color:red}");
+	}
+}
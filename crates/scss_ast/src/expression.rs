@@ -0,0 +1,354 @@
+use bumpalo::collections::Vec;
+use css_lexer::{Cursor, Kind, KindSet};
+use css_parse::{syntax::ComponentValue, CursorSink, Parse, Parser, Peek, Result as ParserResult, ToCursors, T};
+
+// Whether `second` starts exactly where `first` ends, for example the `$` and `name` of `$name`.
+pub(crate) fn adjacent(first: Cursor, second: Cursor) -> bool {
+	first.end_offset() == second.offset()
+}
+
+/// A SCSS expression, such as the value of a variable (`$a: 1px + $b`), the condition of an `@if`, or a selector
+/// containing interpolation (`.col-#{$i}`).
+///
+/// SCSS expressions have a much larger grammar than CSS values (operators, maps, lists, module members and so on), so
+/// rather than evaluating them the expression is kept as a flat list of [Value]s. Variables, interpolation,
+/// placeholders and function calls are captured as their own nodes so that tools can find them; everything else is kept
+/// as a [ComponentValue].
+///
+/// Whitespace is significant within SCSS expressions (`$a - $b` is a subtraction, while `$a -$b` is a list), so
+/// whitespace between values is kept, but leading and trailing whitespace is not.
+///
+/// An expression ends at a `;`, a `{` (other than the `{` of interpolation), an unmatched `}`, `)` or `]`, or a flag
+/// like `!default`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+pub struct Expression<'a>(pub Vec<'a, Value<'a>>);
+
+impl<'a> Expression<'a> {
+	pub fn is_empty(&self) -> bool {
+		self.0.is_empty()
+	}
+
+	fn at_end(p: &Parser<'a>) -> bool {
+		let c = p.peek_n(1);
+		c == KindSet::new(&[
+			Kind::Eof,
+			Kind::Semicolon,
+			Kind::LeftCurly,
+			Kind::RightCurly,
+			Kind::RightParen,
+			Kind::RightSquare,
+		]) || (c == Kind::Delim && c == '!' && p.peek_n(2) == Kind::Ident)
+			|| p.next_is_stop()
+	}
+
+	fn parse_values(p: &mut Parser<'a>) -> ParserResult<Vec<'a, Value<'a>>> {
+		let mut values = Vec::new_in(p.bump());
+		loop {
+			if p.at_end() {
+				break;
+			}
+			if let Some(whitespace) = p.parse_if_peek::<T![' ']>()? {
+				if !values.is_empty() && !Self::at_end(p) {
+					values.push(Value::Component(ComponentValue::Whitespace(whitespace)));
+				}
+				continue;
+			}
+			if Self::at_end(p) || !p.peek::<Value>() {
+				break;
+			}
+			values.push(p.parse::<Value>()?);
+		}
+		Ok(values)
+	}
+}
+
+impl<'a> Parse<'a> for Expression<'a> {
+	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
+		let skip = p.set_skip(KindSet::COMMENTS);
+		let values = Self::parse_values(p);
+		p.set_skip(skip);
+		Ok(Self(values?))
+	}
+}
+
+impl<'a> ToCursors for Expression<'a> {
+	fn to_cursors(&self, s: &mut impl CursorSink) {
+		for value in &self.0 {
+			ToCursors::to_cursors(value, s);
+		}
+	}
+}
+
+/// A single part of an [Expression].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(untagged))]
+pub enum Value<'a> {
+	Variable(Variable),
+	Interpolation(Interpolation<'a>),
+	Placeholder(Placeholder),
+	Function(FunctionCall<'a>),
+	Parenthesized(Parenthesized<'a>),
+	Component(ComponentValue<'a>),
+}
+
+impl<'a> Peek<'a> for Value<'a> {
+	fn peek(p: &Parser<'a>, c: Cursor) -> bool {
+		<ComponentValue>::peek(p, c)
+	}
+}
+
+impl<'a> Parse<'a> for Value<'a> {
+	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
+		if p.peek::<Variable>() {
+			p.parse::<Variable>().map(Self::Variable)
+		} else if p.peek::<Interpolation>() {
+			p.parse::<Interpolation>().map(Self::Interpolation)
+		} else if p.peek::<Placeholder>() {
+			p.parse::<Placeholder>().map(Self::Placeholder)
+		} else if p.peek::<T![Function]>() {
+			p.parse::<FunctionCall>().map(Self::Function)
+		} else if p.peek::<T!['(']>() {
+			p.parse::<Parenthesized>().map(Self::Parenthesized)
+		} else {
+			p.parse::<ComponentValue>().map(Self::Component)
+		}
+	}
+}
+
+impl<'a> ToCursors for Value<'a> {
+	fn to_cursors(&self, s: &mut impl CursorSink) {
+		match self {
+			Self::Variable(v) => ToCursors::to_cursors(v, s),
+			Self::Interpolation(v) => ToCursors::to_cursors(v, s),
+			Self::Placeholder(v) => ToCursors::to_cursors(v, s),
+			Self::Function(v) => ToCursors::to_cursors(v, s),
+			Self::Parenthesized(v) => ToCursors::to_cursors(v, s),
+			Self::Component(v) => ToCursors::to_cursors(v, s),
+		}
+	}
+}
+
+/// A reference to a [variable](https://sass-lang.com/documentation/variables/), such as `$primary`.
+///
+/// ```md
+/// <variable>
+///  │├─ "$" ─ <ident> ─┤│
+/// ```
+///
+/// The `$` and the name cannot be separated by whitespace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type", rename = "variable"))]
+pub struct Variable {
+	pub dollar: T![$],
+	pub name: T![Ident],
+}
+
+impl Variable {
+	/// The name of the variable, without the leading `$`.
+	pub fn name<'a>(&self, source_text: &'a str) -> &'a str {
+		Cursor::from(self.name).str_slice(source_text)
+	}
+}
+
+impl<'a> Peek<'a> for Variable {
+	fn peek(p: &Parser<'a>, c: Cursor) -> bool {
+		let name = p.peek_n(2);
+		c == Kind::Delim && c == '$' && name == Kind::Ident && adjacent(c, name)
+	}
+}
+
+impl<'a> Parse<'a> for Variable {
+	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
+		let dollar = p.parse::<T![$]>()?;
+		let name = p.parse::<T![Ident]>()?;
+		Ok(Self { dollar, name })
+	}
+}
+
+impl ToCursors for Variable {
+	fn to_cursors(&self, s: &mut impl CursorSink) {
+		s.append(self.dollar.into());
+		s.append(self.name.into());
+	}
+}
+
+/// [Interpolation](https://sass-lang.com/documentation/interpolation/) of an [Expression] into a selector, property
+/// value, or at-rule prelude, such as `#{$name}`.
+///
+/// ```md
+/// <interpolation>
+///  │├─ "#" ─ "{" ─ <expression> ─ "}" ─┤│
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type", rename = "interpolation"))]
+pub struct Interpolation<'a> {
+	pub hash: T![#],
+	pub open: T!['{'],
+	pub value: Expression<'a>,
+	pub close: Option<T!['}']>,
+}
+
+impl<'a> Peek<'a> for Interpolation<'a> {
+	fn peek(p: &Parser<'a>, c: Cursor) -> bool {
+		let open = p.peek_n(2);
+		c == Kind::Delim && c == '#' && open == Kind::LeftCurly && adjacent(c, open)
+	}
+}
+
+impl<'a> Parse<'a> for Interpolation<'a> {
+	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
+		let hash = p.parse::<T![#]>()?;
+		let open = p.parse::<T!['{']>()?;
+		let value = p.parse::<Expression>()?;
+		let close = p.parse_if_peek::<T!['}']>()?;
+		Ok(Self { hash, open, value, close })
+	}
+}
+
+impl<'a> ToCursors for Interpolation<'a> {
+	fn to_cursors(&self, s: &mut impl CursorSink) {
+		s.append(self.hash.into());
+		s.append(self.open.into());
+		ToCursors::to_cursors(&self.value, s);
+		if let Some(close) = self.close {
+			s.append(close.into());
+		}
+	}
+}
+
+/// A [placeholder selector](https://sass-lang.com/documentation/style-rules/placeholder-selectors/), such as
+/// `%button`, which is only ever output when it is used by an `@extend`.
+///
+/// ```md
+/// <placeholder>
+///  │├─ "%" ─ <ident> ─┤│
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type", rename = "placeholder"))]
+pub struct Placeholder {
+	pub percent: T![%],
+	pub name: T![Ident],
+}
+
+impl<'a> Peek<'a> for Placeholder {
+	fn peek(p: &Parser<'a>, c: Cursor) -> bool {
+		let name = p.peek_n(2);
+		c == Kind::Delim && c == '%' && name == Kind::Ident && adjacent(c, name)
+	}
+}
+
+impl<'a> Parse<'a> for Placeholder {
+	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
+		let percent = p.parse::<T![%]>()?;
+		let name = p.parse::<T![Ident]>()?;
+		Ok(Self { percent, name })
+	}
+}
+
+impl ToCursors for Placeholder {
+	fn to_cursors(&self, s: &mut impl CursorSink) {
+		s.append(self.percent.into());
+		s.append(self.name.into());
+	}
+}
+
+/// A function call, such as `darken($color, 10%)`, whose arguments are an [Expression].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type", rename = "function"))]
+pub struct FunctionCall<'a> {
+	pub name: T![Function],
+	pub arguments: Expression<'a>,
+	pub close: Option<T![')']>,
+}
+
+impl<'a> FunctionCall<'a> {
+	/// The name of the function, without the trailing `(`.
+	pub fn name<'b>(&self, source_text: &'b str) -> &'b str {
+		let name = Cursor::from(self.name).str_slice(source_text);
+		name.strip_suffix('(').unwrap_or(name)
+	}
+}
+
+impl<'a> Parse<'a> for FunctionCall<'a> {
+	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
+		let name = p.parse::<T![Function]>()?;
+		let arguments = p.parse::<Expression>()?;
+		let close = p.parse_if_peek::<T![')']>()?;
+		Ok(Self { name, arguments, close })
+	}
+}
+
+impl<'a> ToCursors for FunctionCall<'a> {
+	fn to_cursors(&self, s: &mut impl CursorSink) {
+		s.append(self.name.into());
+		ToCursors::to_cursors(&self.arguments, s);
+		if let Some(close) = self.close {
+			s.append(close.into());
+		}
+	}
+}
+
+/// An [Expression] wrapped in parentheses, such as `($a + 1)` or the map `(small: 1px, large: 2px)`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type", rename = "parenthesized"))]
+pub struct Parenthesized<'a> {
+	pub open: T!['('],
+	pub value: Expression<'a>,
+	pub close: Option<T![')']>,
+}
+
+impl<'a> Parse<'a> for Parenthesized<'a> {
+	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
+		let open = p.parse::<T!['(']>()?;
+		let value = p.parse::<Expression>()?;
+		let close = p.parse_if_peek::<T![')']>()?;
+		Ok(Self { open, value, close })
+	}
+}
+
+impl<'a> ToCursors for Parenthesized<'a> {
+	fn to_cursors(&self, s: &mut impl CursorSink) {
+		s.append(self.open.into());
+		ToCursors::to_cursors(&self.value, s);
+		if let Some(close) = self.close {
+			s.append(close.into());
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use css_parse::assert_parse;
+
+	#[test]
+	fn size_test() {
+		assert_eq!(std::mem::size_of::<Expression>(), 32);
+		assert_eq!(std::mem::size_of::<Value>(), 72);
+		assert_eq!(std::mem::size_of::<Variable>(), 24);
+		assert_eq!(std::mem::size_of::<Interpolation>(), 72);
+		assert_eq!(std::mem::size_of::<Placeholder>(), 24);
+		assert_eq!(std::mem::size_of::<FunctionCall>(), 64);
+		assert_eq!(std::mem::size_of::<Parenthesized>(), 64);
+	}
+
+	#[test]
+	fn test_writes() {
+		assert_parse!(Expression, "$a");
+		assert_parse!(Expression, "$a - $b");
+		assert_parse!(Expression, "$a -$b");
+		assert_parse!(Expression, "  1px solid $color  ", "1px solid $color");
+		assert_parse!(Expression, "darken($color, 10%)");
+		assert_parse!(Expression, "(small: 1px, large: 2px)");
+		assert_parse!(Expression, "math.$pi * 2");
+		assert_parse!(Expression, ".col-#{$i} > a:hover");
+		assert_parse!(Expression, "%button");
+		assert_parse!(Expression, "$a != 1 and not $b");
+		assert_parse!(Variable, "$primary-color");
+		assert_parse!(Interpolation, "#{$a + 1}");
+		assert_parse!(Placeholder, "%message-shared");
+		assert_parse!(FunctionCall, "map-get($map, key)");
+		assert_parse!(Parenthesized, "($a+$b)");
+	}
+}
@@ -0,0 +1,45 @@
+//! An AST for [SCSS][1], built on [css_parse] and reusing the nodes of [css_ast] wherever the SCSS is also plain CSS.
+//!
+//! [1]: https://sass-lang.com/documentation/syntax/#scss
+//!
+//! The root node is [StyleSheet], which implements the [css_parse::StyleSheet] trait in the same way as
+//! [css_ast::StyleSheet]. SCSS allows `//` comments, so it should be parsed with the [FEATURES] enabled:
+//!
+//! ```
+//! use bumpalo::Bump;
+//! use css_parse::Parser;
+//! use scss_ast::{Rule, StyleSheet, FEATURES};
+//!
+//! let bump = Bump::default();
+//! let source_text = "// Colors\n$primary: blue;\na { color: $primary; }";
+//! let result = Parser::new_with_features(&bump, source_text, FEATURES).parse_entirely::<StyleSheet>();
+//! let stylesheet = result.output.unwrap();
+//! assert!(matches!(stylesheet.rules[0], Rule::Variable(_)));
+//! assert!(matches!(stylesheet.rules[1], Rule::Style(_)));
+//! ```
+//!
+//! SCSS specific syntax is parsed into this crate's own nodes: variables ([VariableDeclaration]), modules ([UseRule],
+//! [ForwardRule]), mixins ([MixinRule], [IncludeRule]), [placeholder selectors][Placeholder] and [ExtendRule], control
+//! flow ([IfRule], [EachRule]) and [interpolation][Interpolation]. SCSS expressions are not evaluated, and are kept as an
+//! [Expression].
+//!
+//! Selectors, declarations and at-rules which are plain CSS are parsed as their [css_ast] counterparts, and nodes in
+//! this crate implement [css_ast::Visitable], so that [css_ast::Visit] implementations (such as those used for
+//! highlighting or linting) work on the CSS within SCSS files too.
+
+mod declaration;
+mod expression;
+mod rules;
+mod style_rule;
+mod stylesheet;
+
+pub use declaration::*;
+pub use expression::*;
+pub use rules::*;
+pub use style_rule::*;
+pub use stylesheet::*;
+
+use css_parse::Feature;
+
+/// The [Features][Feature] a [Parser][css_parse::Parser] needs to parse SCSS.
+pub const FEATURES: Feature = Feature::SingleLineComments;
@@ -0,0 +1,78 @@
+use css_ast::{Visit, Visitable};
+use css_parse::{AtRule as AtRuleTrait, CursorSink, Parse, Parser, Result as ParserResult, ToCursors, T};
+
+use crate::{Expression, OptionalStyleBlock};
+
+/// Any other at-rule, such as `@media`, `@supports`, `@function`, `@return` or `@debug`.
+///
+/// The prelude is kept as an [Expression] (so `@media #{$query}` works), and the block (if any) is a [StyleBlock]
+/// (so `@media` can contain nested SCSS).
+///
+/// ```md
+/// <at-rule>
+///  │├─ <at-keyword> ─ <expression> ─╮─ <style-block> ─╭─┤│
+///                                   ├─ ";" ───────────┤
+///                                   ╰─────────────────╯
+/// ```
+///
+/// [StyleBlock]: crate::StyleBlock
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type", rename = "at-rule"))]
+pub struct AtRule<'a> {
+	pub at_keyword: T![AtKeyword],
+	pub prelude: Option<Expression<'a>>,
+	pub block: OptionalStyleBlock<'a>,
+}
+
+impl<'a> Parse<'a> for AtRule<'a> {
+	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
+		let (at_keyword, prelude, block) = Self::parse_at_rule(p)?;
+		Ok(Self { at_keyword, prelude, block })
+	}
+}
+
+impl<'a> AtRuleTrait<'a> for AtRule<'a> {
+	type Prelude = Expression<'a>;
+	type Block = OptionalStyleBlock<'a>;
+}
+
+impl<'a> ToCursors for AtRule<'a> {
+	fn to_cursors(&self, s: &mut impl CursorSink) {
+		s.append(self.at_keyword.into());
+		if let Some(prelude) = &self.prelude {
+			ToCursors::to_cursors(prelude, s);
+		}
+		ToCursors::to_cursors(&self.block, s);
+	}
+}
+
+impl<'a> Visitable<'a> for AtRule<'a> {
+	fn accept<V: Visit<'a>>(&self, v: &mut V) {
+		Visitable::accept(&self.block, v);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use css_parse::assert_parse;
+
+	#[test]
+	fn size_test() {
+		assert_eq!(std::mem::size_of::<AtRule>(), 112);
+	}
+
+	#[test]
+	fn test_writes() {
+		assert_parse!(AtRule, "@media screen{a{color:$primary}}");
+		assert_parse!(AtRule, "@media #{$mobile}{a{@include hidden}}", "@media#{$mobile}{a{@include hidden}}");
+		assert_parse!(AtRule, "@function double($n){@return $n * 2}", "@function double($n){@return$n * 2}");
+		assert_parse!(AtRule, "@debug \"value: #{$a}\";", "@debug\"value: #{$a}\";");
+		assert_parse!(AtRule, "@content;");
+		assert_parse!(
+			AtRule,
+			"@for $i from 1 through 3{.item-#{$i}{width:2em * $i}}",
+			"@for$i from 1 through 3{.item-#{$i}{width:2em * $i}}"
+		);
+	}
+}
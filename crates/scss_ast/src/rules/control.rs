@@ -0,0 +1,261 @@
+use bumpalo::collections::Vec;
+use css_ast::{Visit, Visitable};
+use css_lexer::{Cursor, Kind};
+use css_parse::{diagnostics, AtRule, CursorSink, Parse, Parser, Result as ParserResult, ToCursors, T};
+
+use crate::{Expression, StyleBlock, Variable};
+
+/// An [`@if`](https://sass-lang.com/documentation/at-rules/control/if/) rule along with any `@else if` and `@else`
+/// clauses that follow it, such as `@if $condition { ... } @else { ... }`.
+///
+/// ```md
+/// <if-rule>
+///  │├─ <at-keyword "if"> ─ <expression> ─ <style-block> ─╭─────────────────╮─┤│
+///                                                        ╰─ <else-clause> ─╯
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type", rename = "if-rule"))]
+pub struct IfRule<'a> {
+	pub at_keyword: T![AtKeyword],
+	pub condition: Expression<'a>,
+	pub block: StyleBlock<'a>,
+	pub else_clauses: Vec<'a, ElseClause<'a>>,
+}
+
+impl<'a> Parse<'a> for IfRule<'a> {
+	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
+		let (at_keyword, condition, block) = Self::parse_at_rule(p)?;
+		let Some(condition) = condition else {
+			Err(diagnostics::MissingAtRulePrelude(Cursor::from(at_keyword).into()))?
+		};
+		let mut else_clauses = Vec::new_in(p.bump());
+		loop {
+			let c = p.peek_n(1);
+			if c != Kind::AtKeyword || !p.eq_ignore_ascii_case(c, "else") {
+				break;
+			}
+			let clause = p.parse::<ElseClause>()?;
+			let done = clause.condition.is_none();
+			else_clauses.push(clause);
+			if done {
+				break;
+			}
+		}
+		Ok(Self { at_keyword, condition, block, else_clauses })
+	}
+}
+
+impl<'a> AtRule<'a> for IfRule<'a> {
+	const NAME: Option<&'static str> = Some("if");
+	type Prelude = Expression<'a>;
+	type Block = StyleBlock<'a>;
+}
+
+impl<'a> ToCursors for IfRule<'a> {
+	fn to_cursors(&self, s: &mut impl CursorSink) {
+		s.append(self.at_keyword.into());
+		ToCursors::to_cursors(&self.condition, s);
+		ToCursors::to_cursors(&self.block, s);
+		for clause in &self.else_clauses {
+			ToCursors::to_cursors(clause, s);
+		}
+	}
+}
+
+impl<'a> Visitable<'a> for IfRule<'a> {
+	fn accept<V: Visit<'a>>(&self, v: &mut V) {
+		Visitable::accept(&self.block, v);
+		for clause in &self.else_clauses {
+			Visitable::accept(&clause.block, v);
+		}
+	}
+}
+
+/// An `@else if $condition { ... }` or `@else { ... }` clause of an [IfRule].
+///
+/// ```md
+/// <else-clause>
+///  │├─ <at-keyword "else"> ─╮─────────────────────────────╭─ <style-block> ─┤│
+///                           ╰─ <ident "if"> ─ <expression> ─╯
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type", rename = "else-clause"))]
+pub struct ElseClause<'a> {
+	pub at_keyword: T![AtKeyword],
+	pub condition: Option<(T![Ident], Expression<'a>)>,
+	pub block: StyleBlock<'a>,
+}
+
+impl<'a> Parse<'a> for ElseClause<'a> {
+	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
+		let at_keyword = p.parse::<T![AtKeyword]>()?;
+		let c: Cursor = at_keyword.into();
+		if !p.eq_ignore_ascii_case(c, "else") {
+			Err(diagnostics::UnexpectedAtRule(p.parse_str_lower(c).into(), c.into()))?
+		}
+		let condition = if p.peek::<T![Ident]>() {
+			let if_keyword = p.parse::<T![Ident]>()?;
+			if !p.eq_ignore_ascii_case(if_keyword.into(), "if") {
+				Err(diagnostics::ExpectedIdentOf("if", p.parse_str(if_keyword.into()).into(), if_keyword.into()))?
+			}
+			let condition = p.parse::<Expression>()?;
+			if condition.is_empty() {
+				Err(diagnostics::MissingAtRulePrelude(c.into()))?
+			}
+			Some((if_keyword, condition))
+		} else {
+			None
+		};
+		let block = p.parse::<StyleBlock>()?;
+		Ok(Self { at_keyword, condition, block })
+	}
+}
+
+impl<'a> ToCursors for ElseClause<'a> {
+	fn to_cursors(&self, s: &mut impl CursorSink) {
+		s.append(self.at_keyword.into());
+		if let Some((if_keyword, condition)) = &self.condition {
+			s.append(if_keyword.into());
+			ToCursors::to_cursors(condition, s);
+		}
+		ToCursors::to_cursors(&self.block, s);
+	}
+}
+
+/// An [`@each`](https://sass-lang.com/documentation/at-rules/control/each/) rule, which repeats its block for each
+/// item of a list or map, such as `@each $name, $glyph in $icons { ... }`.
+///
+/// ```md
+/// <each-rule>
+///  │├─ <at-keyword "each"> ─╭─ <variable> ─╮─ <ident "in"> ─ <expression> ─ <style-block> ─┤│
+///                           ╰───── "," ────╯
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type", rename = "each-rule"))]
+pub struct EachRule<'a> {
+	pub at_keyword: T![AtKeyword],
+	pub prelude: EachPrelude<'a>,
+	pub block: StyleBlock<'a>,
+}
+
+impl<'a> Parse<'a> for EachRule<'a> {
+	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
+		let (at_keyword, prelude, block) = Self::parse_at_rule(p)?;
+		if let Some(prelude) = prelude {
+			Ok(Self { at_keyword, prelude, block })
+		} else {
+			Err(diagnostics::MissingAtRulePrelude(Cursor::from(at_keyword).into()))?
+		}
+	}
+}
+
+impl<'a> AtRule<'a> for EachRule<'a> {
+	const NAME: Option<&'static str> = Some("each");
+	type Prelude = EachPrelude<'a>;
+	type Block = StyleBlock<'a>;
+}
+
+impl<'a> ToCursors for EachRule<'a> {
+	fn to_cursors(&self, s: &mut impl CursorSink) {
+		s.append(self.at_keyword.into());
+		ToCursors::to_cursors(&self.prelude, s);
+		ToCursors::to_cursors(&self.block, s);
+	}
+}
+
+impl<'a> Visitable<'a> for EachRule<'a> {
+	fn accept<V: Visit<'a>>(&self, v: &mut V) {
+		Visitable::accept(&self.block, v);
+	}
+}
+
+/// The `$key, $value in $map` part of an [EachRule].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+pub struct EachPrelude<'a> {
+	pub variables: Vec<'a, (Variable, Option<T![,]>)>,
+	pub in_keyword: T![Ident],
+	pub list: Expression<'a>,
+}
+
+impl<'a> Parse<'a> for EachPrelude<'a> {
+	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
+		let mut variables = Vec::new_in(p.bump());
+		loop {
+			let variable = p.parse::<Variable>()?;
+			let comma = p.parse_if_peek::<T![,]>()?;
+			let done = comma.is_none();
+			variables.push((variable, comma));
+			if done {
+				break;
+			}
+		}
+		let in_keyword = p.parse::<T![Ident]>()?;
+		if !p.eq_ignore_ascii_case(in_keyword.into(), "in") {
+			Err(diagnostics::ExpectedIdentOf("in", p.parse_str(in_keyword.into()).into(), in_keyword.into()))?
+		}
+		let list = p.parse::<Expression>()?;
+		if list.is_empty() {
+			let c = p.peek_n(1);
+			Err(diagnostics::Unexpected(c.into(), c.into()))?
+		}
+		Ok(Self { variables, in_keyword, list })
+	}
+}
+
+impl<'a> ToCursors for EachPrelude<'a> {
+	fn to_cursors(&self, s: &mut impl CursorSink) {
+		for (variable, comma) in &self.variables {
+			ToCursors::to_cursors(variable, s);
+			if let Some(comma) = comma {
+				s.append(comma.into());
+			}
+		}
+		s.append(self.in_keyword.into());
+		ToCursors::to_cursors(&self.list, s);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use css_parse::{assert_parse, assert_parse_error};
+
+	#[test]
+	fn size_test() {
+		assert_eq!(std::mem::size_of::<IfRule>(), 144);
+		assert_eq!(std::mem::size_of::<ElseClause>(), 128);
+		assert_eq!(std::mem::size_of::<EachRule>(), 160);
+		assert_eq!(std::mem::size_of::<EachPrelude>(), 80);
+	}
+
+	#[test]
+	fn test_writes() {
+		assert_parse!(IfRule, "@if $condition{color:red}", "@if$condition{color:red}");
+		assert_parse!(IfRule, "@if $a == 1{color:red}@else{color:blue}", "@if$a == 1{color:red}@else{color:blue}");
+		assert_parse!(
+			IfRule,
+			"@if $a{a:1}@else if not $b{a:2}@else if $c{a:3}@else{a:4}",
+			"@if$a{a:1}@else if not $b{a:2}@else if$c{a:3}@else{a:4}"
+		);
+		assert_parse!(
+			EachRule,
+			"@each $size in $sizes{.icon-#{$size}{width:$size}}",
+			"@each$size in$sizes{.icon-#{$size}{width:$size}}"
+		);
+		assert_parse!(
+			EachRule,
+			"@each $name, $glyph in $icons{.icon-#{$name}:before{content:$glyph}}",
+			"@each$name,$glyph in$icons{.icon-#{$name}:before{content:$glyph}}"
+		);
+		assert_parse!(EachRule, "@each $animal in puma, sea-slug, egret{}", "@each$animal in puma, sea-slug, egret{}");
+	}
+
+	#[test]
+	fn test_errors() {
+		assert_parse_error!(IfRule, "@if{}");
+		assert_parse_error!(IfRule, "@if $a{}@else when $b{}");
+		assert_parse_error!(EachRule, "@each $a of $b{}");
+		assert_parse_error!(EachRule, "@each in $b{}");
+	}
+}
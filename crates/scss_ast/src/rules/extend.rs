@@ -0,0 +1,85 @@
+use css_lexer::Cursor;
+use css_parse::{diagnostics, CursorSink, Parse, Parser, Result as ParserResult, ToCursors, T};
+
+use crate::Expression;
+
+/// [Extends](https://sass-lang.com/documentation/at-rules/extend/) the enclosing style rule with the styles of another
+/// selector, typically a placeholder: `@extend %message-shared;`.
+///
+/// ```md
+/// <extend-rule>
+///  │├─ <at-keyword "extend"> ─ <expression> ─╮────────────────────────────╭─╮───────╭─┤│
+///                                            ╰─ "!" ─ <ident "optional"> ─╯ ╰─ ";" ─╯
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type", rename = "extend-rule"))]
+pub struct ExtendRule<'a> {
+	pub at_keyword: T![AtKeyword],
+	pub selector: Expression<'a>,
+	pub optional: Option<(T![!], T![Ident])>,
+	pub semicolon: Option<T![;]>,
+}
+
+impl<'a> Parse<'a> for ExtendRule<'a> {
+	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
+		let at_keyword = p.parse::<T![AtKeyword]>()?;
+		let c: Cursor = at_keyword.into();
+		if !p.eq_ignore_ascii_case(c, "extend") {
+			Err(diagnostics::UnexpectedAtRule(p.parse_str_lower(c).into(), c.into()))?
+		}
+		let selector = p.parse::<Expression>()?;
+		if selector.is_empty() {
+			Err(diagnostics::MissingAtRulePrelude(c.into()))?
+		}
+		let optional = if p.peek::<T![!]>() {
+			let bang = p.parse::<T![!]>()?;
+			let ident = p.parse::<T![Ident]>()?;
+			if !p.eq_ignore_ascii_case(ident.into(), "optional") {
+				Err(diagnostics::ExpectedIdentOf("optional", p.parse_str(ident.into()).into(), ident.into()))?
+			}
+			Some((bang, ident))
+		} else {
+			None
+		};
+		let semicolon = p.parse_if_peek::<T![;]>()?;
+		Ok(Self { at_keyword, selector, optional, semicolon })
+	}
+}
+
+impl<'a> ToCursors for ExtendRule<'a> {
+	fn to_cursors(&self, s: &mut impl CursorSink) {
+		s.append(self.at_keyword.into());
+		ToCursors::to_cursors(&self.selector, s);
+		if let Some((bang, ident)) = &self.optional {
+			s.append(bang.into());
+			s.append(ident.into());
+		}
+		if let Some(semicolon) = self.semicolon {
+			s.append(semicolon.into());
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use css_parse::{assert_parse, assert_parse_error};
+
+	#[test]
+	fn size_test() {
+		assert_eq!(std::mem::size_of::<ExtendRule>(), 88);
+	}
+
+	#[test]
+	fn test_writes() {
+		assert_parse!(ExtendRule, "@extend %message-shared;", "@extend%message-shared;");
+		assert_parse!(ExtendRule, "@extend .error", "@extend.error");
+		assert_parse!(ExtendRule, "@extend a:hover!optional;");
+	}
+
+	#[test]
+	fn test_errors() {
+		assert_parse_error!(ExtendRule, "@extend;");
+		assert_parse_error!(ExtendRule, "@extend .a!important;");
+	}
+}
@@ -0,0 +1,183 @@
+use css_ast::{Visit, Visitable};
+use css_lexer::{Cursor, Kind};
+use css_parse::{diagnostics, AtRule, CursorSink, Parse, Parser, Result as ParserResult, ToCursors, T};
+
+use crate::{FunctionCall, OptionalStyleBlock, StyleBlock};
+
+/// Defines a [mixin](https://sass-lang.com/documentation/at-rules/mixin/), such as
+/// `@mixin theme($theme: DarkGray) { background: $theme; }`.
+///
+/// ```md
+/// <mixin-rule>
+///  │├─ <at-keyword "mixin"> ─ <callable> ─ <style-block> ─┤│
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type", rename = "mixin-rule"))]
+pub struct MixinRule<'a> {
+	pub at_keyword: T![AtKeyword],
+	pub name: Callable<'a>,
+	pub block: StyleBlock<'a>,
+}
+
+impl<'a> Parse<'a> for MixinRule<'a> {
+	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
+		let (at_keyword, name, block) = Self::parse_at_rule(p)?;
+		if let Some(name) = name {
+			Ok(Self { at_keyword, name, block })
+		} else {
+			Err(diagnostics::MissingAtRulePrelude(Cursor::from(at_keyword).into()))?
+		}
+	}
+}
+
+impl<'a> AtRule<'a> for MixinRule<'a> {
+	const NAME: Option<&'static str> = Some("mixin");
+	type Prelude = Callable<'a>;
+	type Block = StyleBlock<'a>;
+}
+
+impl<'a> ToCursors for MixinRule<'a> {
+	fn to_cursors(&self, s: &mut impl CursorSink) {
+		s.append(self.at_keyword.into());
+		ToCursors::to_cursors(&self.name, s);
+		ToCursors::to_cursors(&self.block, s);
+	}
+}
+
+impl<'a> Visitable<'a> for MixinRule<'a> {
+	fn accept<V: Visit<'a>>(&self, v: &mut V) {
+		Visitable::accept(&self.block, v);
+	}
+}
+
+/// Includes a [mixin](https://sass-lang.com/documentation/at-rules/mixin/), such as `@include theme;`,
+/// `@include theme($theme: DarkRed);` or `@include media.hover { ... }` (which passes the block as the mixin's
+/// content).
+///
+/// ```md
+/// <include-rule>
+///  │├─ <at-keyword "include"> ─ <callable> ─╮─ <style-block> ─╭─┤│
+///                                           ├─ ";" ───────────┤
+///                                           ╰─────────────────╯
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type", rename = "include-rule"))]
+pub struct IncludeRule<'a> {
+	pub at_keyword: T![AtKeyword],
+	pub mixin: Callable<'a>,
+	pub block: OptionalStyleBlock<'a>,
+}
+
+impl<'a> Parse<'a> for IncludeRule<'a> {
+	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
+		let (at_keyword, mixin, block) = Self::parse_at_rule(p)?;
+		if let Some(mixin) = mixin {
+			Ok(Self { at_keyword, mixin, block })
+		} else {
+			Err(diagnostics::MissingAtRulePrelude(Cursor::from(at_keyword).into()))?
+		}
+	}
+}
+
+impl<'a> AtRule<'a> for IncludeRule<'a> {
+	const NAME: Option<&'static str> = Some("include");
+	type Prelude = Callable<'a>;
+	type Block = OptionalStyleBlock<'a>;
+}
+
+impl<'a> ToCursors for IncludeRule<'a> {
+	fn to_cursors(&self, s: &mut impl CursorSink) {
+		s.append(self.at_keyword.into());
+		ToCursors::to_cursors(&self.mixin, s);
+		ToCursors::to_cursors(&self.block, s);
+	}
+}
+
+impl<'a> Visitable<'a> for IncludeRule<'a> {
+	fn accept<V: Visit<'a>>(&self, v: &mut V) {
+		Visitable::accept(&self.block, v);
+	}
+}
+
+/// The name of a mixin, optionally from another module and optionally with arguments, such as `theme`,
+/// `theme($theme: DarkGray)` or `media.hover`.
+///
+/// ```md
+/// <callable>
+///  │├─╮─────────────────╭─╮─ <ident> ─────────╭─┤│
+///     ╰─ <ident> ─ "." ─╯ ╰─ <function-call> ─╯
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+pub struct Callable<'a> {
+	pub namespace: Option<(T![Ident], T![.])>,
+	pub name: CallableName<'a>,
+}
+
+impl<'a> Parse<'a> for Callable<'a> {
+	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
+		let dot = p.peek_n(2);
+		let namespace = if p.peek::<T![Ident]>() && dot == Kind::Delim && dot == '.' {
+			Some((p.parse::<T![Ident]>()?, p.parse::<T![.]>()?))
+		} else {
+			None
+		};
+		let name = if p.peek::<T![Function]>() {
+			CallableName::Call(p.parse::<FunctionCall>()?)
+		} else {
+			CallableName::Ident(p.parse::<T![Ident]>()?)
+		};
+		Ok(Self { namespace, name })
+	}
+}
+
+impl<'a> ToCursors for Callable<'a> {
+	fn to_cursors(&self, s: &mut impl CursorSink) {
+		if let Some((namespace, dot)) = &self.namespace {
+			s.append(namespace.into());
+			s.append(dot.into());
+		}
+		match &self.name {
+			CallableName::Ident(ident) => s.append(ident.into()),
+			CallableName::Call(call) => ToCursors::to_cursors(call, s),
+		}
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(untagged))]
+pub enum CallableName<'a> {
+	Ident(T![Ident]),
+	Call(FunctionCall<'a>),
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use css_parse::{assert_parse, assert_parse_error};
+
+	#[test]
+	fn size_test() {
+		assert_eq!(std::mem::size_of::<MixinRule>(), 176);
+		assert_eq!(std::mem::size_of::<IncludeRule>(), 176);
+		assert_eq!(std::mem::size_of::<Callable>(), 96);
+	}
+
+	#[test]
+	fn test_writes() {
+		assert_parse!(MixinRule, "@mixin reset-list{margin:0;padding:0}");
+		assert_parse!(MixinRule, "@mixin theme($theme: DarkGray){background:$theme}");
+		assert_parse!(MixinRule, "@mixin rtl($property, $ltr-value, $rtl-value){#{$property}:$ltr-value}");
+		assert_parse!(IncludeRule, "@include reset-list;");
+		assert_parse!(IncludeRule, "@include reset-list");
+		assert_parse!(IncludeRule, "@include theme($theme: DarkRed);");
+		assert_parse!(IncludeRule, "@include media.hover{color:red}");
+		assert_parse!(IncludeRule, "@include breakpoint(md){width:50%}");
+	}
+
+	#[test]
+	fn test_errors() {
+		assert_parse_error!(MixinRule, "@mixin{}");
+		assert_parse_error!(IncludeRule, "@include;");
+	}
+}
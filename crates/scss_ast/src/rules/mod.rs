@@ -0,0 +1,13 @@
+mod at_rule;
+mod control;
+mod extend;
+mod mixin;
+mod modules;
+mod variable;
+
+pub use at_rule::*;
+pub use control::*;
+pub use extend::*;
+pub use mixin::*;
+pub use modules::*;
+pub use variable::*;
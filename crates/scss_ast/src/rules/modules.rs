@@ -0,0 +1,130 @@
+use css_lexer::Cursor;
+use css_parse::{diagnostics, CursorSink, Parse, Parser, Result as ParserResult, ToCursors, T};
+
+use crate::Expression;
+
+// Parses the `@name "url" ...;` grammar shared by `@use` and `@forward`.
+fn parse_module_rule<'a>(
+	p: &mut Parser<'a>,
+	name: &'static str,
+) -> ParserResult<(T![AtKeyword], T![String], Expression<'a>, Option<T![;]>)> {
+	let at_keyword = p.parse::<T![AtKeyword]>()?;
+	let c: Cursor = at_keyword.into();
+	if !p.eq_ignore_ascii_case(c, name) {
+		Err(diagnostics::UnexpectedAtRule(p.parse_str_lower(c).into(), c.into()))?
+	}
+	let url = p.parse::<T![String]>()?;
+	let modifiers = p.parse::<Expression>()?;
+	let semicolon = p.parse_if_peek::<T![;]>()?;
+	Ok((at_keyword, url, modifiers, semicolon))
+}
+
+/// Loads a [module](https://sass-lang.com/documentation/at-rules/use/), such as
+/// `@use "sass:math";` or `@use "theme" as t with ($primary: blue);`.
+///
+/// ```md
+/// <use-rule>
+///  │├─ <at-keyword "use"> ─ <string> ─ <expression> ─╮───────╭─┤│
+///                                                    ╰─ ";" ─╯
+/// ```
+///
+/// The modifiers (`as` and `with`) are kept as an [Expression].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type", rename = "use-rule"))]
+pub struct UseRule<'a> {
+	pub at_keyword: T![AtKeyword],
+	pub url: T![String],
+	pub modifiers: Expression<'a>,
+	pub semicolon: Option<T![;]>,
+}
+
+impl<'a> Parse<'a> for UseRule<'a> {
+	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
+		let (at_keyword, url, modifiers, semicolon) = parse_module_rule(p, "use")?;
+		Ok(Self { at_keyword, url, modifiers, semicolon })
+	}
+}
+
+impl<'a> ToCursors for UseRule<'a> {
+	fn to_cursors(&self, s: &mut impl CursorSink) {
+		s.append(self.at_keyword.into());
+		s.append(self.url.into());
+		ToCursors::to_cursors(&self.modifiers, s);
+		if let Some(semicolon) = self.semicolon {
+			s.append(semicolon.into());
+		}
+	}
+}
+
+/// Re-exports the members of a [module](https://sass-lang.com/documentation/at-rules/forward/), such as
+/// `@forward "src/list" hide list-reset;`.
+///
+/// ```md
+/// <forward-rule>
+///  │├─ <at-keyword "forward"> ─ <string> ─ <expression> ─╮───────╭─┤│
+///                                                        ╰─ ";" ─╯
+/// ```
+///
+/// The modifiers (`as`, `show`, `hide` and `with`) are kept as an [Expression].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type", rename = "forward-rule"))]
+pub struct ForwardRule<'a> {
+	pub at_keyword: T![AtKeyword],
+	pub url: T![String],
+	pub modifiers: Expression<'a>,
+	pub semicolon: Option<T![;]>,
+}
+
+impl<'a> Parse<'a> for ForwardRule<'a> {
+	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
+		let (at_keyword, url, modifiers, semicolon) = parse_module_rule(p, "forward")?;
+		Ok(Self { at_keyword, url, modifiers, semicolon })
+	}
+}
+
+impl<'a> ToCursors for ForwardRule<'a> {
+	fn to_cursors(&self, s: &mut impl CursorSink) {
+		s.append(self.at_keyword.into());
+		s.append(self.url.into());
+		ToCursors::to_cursors(&self.modifiers, s);
+		if let Some(semicolon) = self.semicolon {
+			s.append(semicolon.into());
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use css_parse::{assert_parse, assert_parse_error};
+
+	#[test]
+	fn size_test() {
+		assert_eq!(std::mem::size_of::<UseRule>(), 72);
+		assert_eq!(std::mem::size_of::<ForwardRule>(), 72);
+	}
+
+	#[test]
+	fn test_writes() {
+		assert_parse!(UseRule, "@use \"sass:math\";", "@use\"sass:math\";");
+		assert_parse!(UseRule, "@use 'theme' as t;", "@use'theme'as t;");
+		assert_parse!(UseRule, "@use 'theme' as *;", "@use'theme'as *;");
+		assert_parse!(
+			UseRule,
+			"@use 'library' with ($black: #222, $border-radius: 0.1rem);",
+			"@use'library'with ($black: #222, $border-radius: 0.1rem);"
+		);
+		assert_parse!(
+			ForwardRule,
+			"@forward 'src/list' hide list-reset, $horizontal-list-gap;",
+			"@forward'src/list'hide list-reset, $horizontal-list-gap;"
+		);
+		assert_parse!(ForwardRule, "@forward 'src/list' as list-*;", "@forward'src/list'as list-*;");
+	}
+
+	#[test]
+	fn test_errors() {
+		assert_parse_error!(UseRule, "@use theme;");
+		assert_parse_error!(UseRule, "@forward 'theme';");
+	}
+}
@@ -0,0 +1,106 @@
+use bumpalo::collections::Vec;
+use css_lexer::Cursor;
+use css_parse::{diagnostics, keyword_set, CursorSink, Parse, Parser, Peek, Result as ParserResult, ToCursors, T};
+
+use crate::{Expression, Variable};
+
+/// Declares a [variable](https://sass-lang.com/documentation/variables/), such as `$gutter: 16px !default;`.
+///
+/// ```md
+/// <variable-declaration>
+///  │├─ <variable> ─ ":" ─ <expression> ─╭─────────────────────────╮─╮───────╭─┤│
+///                                       ╰─ "!" ─ <variable-flag> ─╯ ╰─ ";" ─╯
+///
+/// <variable-flag>
+///  │├─ "default" ─╮─┤│
+///  ╰─ "global" ───╯
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type", rename = "variable-declaration"))]
+pub struct VariableDeclaration<'a> {
+	pub variable: Variable,
+	pub colon: T![:],
+	pub value: Expression<'a>,
+	pub flags: Vec<'a, (T![!], VariableFlag)>,
+	pub semicolon: Option<T![;]>,
+}
+
+impl<'a> VariableDeclaration<'a> {
+	/// Whether this is a `!default` declaration, which only assigns the variable if it is not already set.
+	pub fn is_default(&self) -> bool {
+		self.flags.iter().any(|(_, flag)| matches!(flag, VariableFlag::Default(_)))
+	}
+
+	/// Whether this is a `!global` declaration, which assigns the variable at the top level of the style sheet.
+	pub fn is_global(&self) -> bool {
+		self.flags.iter().any(|(_, flag)| matches!(flag, VariableFlag::Global(_)))
+	}
+}
+
+impl<'a> Peek<'a> for VariableDeclaration<'a> {
+	fn peek(p: &Parser<'a>, c: Cursor) -> bool {
+		<Variable>::peek(p, c)
+	}
+}
+
+impl<'a> Parse<'a> for VariableDeclaration<'a> {
+	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
+		let variable = p.parse::<Variable>()?;
+		let colon = p.parse::<T![:]>()?;
+		let value = p.parse::<Expression>()?;
+		if value.is_empty() {
+			let c = p.peek_n(1);
+			Err(diagnostics::Unexpected(c.into(), c.into()))?
+		}
+		let mut flags = Vec::new_in(p.bump());
+		while p.peek::<T![!]>() {
+			flags.push((p.parse::<T![!]>()?, p.parse::<VariableFlag>()?));
+		}
+		let semicolon = p.parse_if_peek::<T![;]>()?;
+		Ok(Self { variable, colon, value, flags, semicolon })
+	}
+}
+
+impl<'a> ToCursors for VariableDeclaration<'a> {
+	fn to_cursors(&self, s: &mut impl CursorSink) {
+		ToCursors::to_cursors(&self.variable, s);
+		s.append(self.colon.into());
+		ToCursors::to_cursors(&self.value, s);
+		for (bang, flag) in &self.flags {
+			s.append(bang.into());
+			s.append(flag.into());
+		}
+		if let Some(semicolon) = self.semicolon {
+			s.append(semicolon.into());
+		}
+	}
+}
+
+keyword_set!(VariableFlag { Default: "default", Global: "global" });
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use css_parse::{assert_parse, assert_parse_error};
+
+	#[test]
+	fn size_test() {
+		assert_eq!(std::mem::size_of::<VariableDeclaration>(), 120);
+	}
+
+	#[test]
+	fn test_writes() {
+		assert_parse!(VariableDeclaration, "$a:1px;");
+		assert_parse!(VariableDeclaration, "$a:1px");
+		assert_parse!(VariableDeclaration, "$font-stack:Helvetica, sans-serif;");
+		assert_parse!(VariableDeclaration, "$a:$b * 2!default;");
+		assert_parse!(VariableDeclaration, "$a:1px!default!global;");
+		assert_parse!(VariableDeclaration, "$breakpoints:(small: 576px, medium: 768px);");
+	}
+
+	#[test]
+	fn test_errors() {
+		assert_parse_error!(VariableDeclaration, "$a:;");
+		assert_parse_error!(VariableDeclaration, "$a:1!important;");
+	}
+}
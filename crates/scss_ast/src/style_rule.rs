@@ -0,0 +1,277 @@
+use bumpalo::collections::Vec;
+use css_ast::{SelectorList, Visit, Visitable};
+use css_parse::{
+	diagnostics,
+	syntax::{BadDeclaration, BadRule},
+	CursorSink, Parse, Parser, QualifiedRule, Result as ParserResult, State, ToCursors, T,
+};
+
+use crate::{Declaration, Expression, Rule};
+
+/// Represents a SCSS style rule, such as `.button { color: $primary; &:hover { ... } }`.
+///
+/// ```md
+/// <style-rule>
+///  │├─ <selector> ─ <style-block> ─┤│
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type", rename = "stylerule"))]
+pub struct StyleRule<'a> {
+	pub selectors: Selector<'a>,
+	pub block: StyleBlock<'a>,
+}
+
+impl<'a> Parse<'a> for StyleRule<'a> {
+	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
+		let (selectors, block) = Self::parse_qualified_rule(p)?;
+		Ok(Self { selectors, block })
+	}
+}
+
+impl<'a> QualifiedRule<'a> for StyleRule<'a> {
+	type Block = StyleBlock<'a>;
+	type Prelude = Selector<'a>;
+	type BadDeclaration = BadDeclaration<'a>;
+}
+
+impl<'a> ToCursors for StyleRule<'a> {
+	fn to_cursors(&self, s: &mut impl CursorSink) {
+		ToCursors::to_cursors(&self.selectors, s);
+		ToCursors::to_cursors(&self.block, s);
+	}
+}
+
+impl<'a> Visitable<'a> for StyleRule<'a> {
+	fn accept<V: Visit<'a>>(&self, v: &mut V) {
+		Visitable::accept(&self.selectors, v);
+		Visitable::accept(&self.block, v);
+	}
+}
+
+/// The selectors of a [StyleRule].
+///
+/// Selectors which are valid CSS are parsed as a [SelectorList]. Selectors which use SCSS, such as interpolation
+/// (`.col-#{$i}`), placeholders (`%message`) or suffixes on the parent selector (`&__element`), are kept as an
+/// [Expression].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(untagged))]
+pub enum Selector<'a> {
+	Css(SelectorList<'a>),
+	Scss(Expression<'a>),
+}
+
+impl<'a> Parse<'a> for Selector<'a> {
+	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
+		let checkpoint = p.checkpoint();
+		if let Ok(selectors) = p.parse::<SelectorList>() {
+			if p.peek::<T!['{']>() {
+				return Ok(Self::Css(selectors));
+			}
+		}
+		p.rewind(checkpoint);
+		let expression = p.parse::<Expression>()?;
+		if expression.is_empty() {
+			let c = p.peek_n(1);
+			Err(diagnostics::Unexpected(c.into(), c.into()))?
+		}
+		Ok(Self::Scss(expression))
+	}
+}
+
+impl<'a> ToCursors for Selector<'a> {
+	fn to_cursors(&self, s: &mut impl CursorSink) {
+		match self {
+			Self::Css(selectors) => ToCursors::to_cursors(selectors, s),
+			Self::Scss(expression) => ToCursors::to_cursors(expression, s),
+		}
+	}
+}
+
+impl<'a> Visitable<'a> for Selector<'a> {
+	fn accept<V: Visit<'a>>(&self, v: &mut V) {
+		if let Self::Css(selectors) = self {
+			Visitable::accept(selectors, v);
+		}
+	}
+}
+
+/// The `{ ... }` block of a [StyleRule], or of an at-rule such as `@mixin` or `@if`.
+///
+/// Unlike [css_parse::Block], which separates declarations from rules, this keeps its items in source order: in SCSS
+/// the position of an `@include` relative to the declarations around it changes which declarations win.
+///
+/// ```md
+/// <style-block>
+///  │├─ "{" ─╭─╮─ <declaration> ─╮─╮─ "}" ─┤│
+///           │ ╰─ <rule> ────────╯ │
+///           ╰─────────────────────╯
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type", rename = "style-block"))]
+pub struct StyleBlock<'a> {
+	pub open: T!['{'],
+	pub items: Vec<'a, BlockItem<'a>>,
+	pub close: Option<T!['}']>,
+}
+
+impl<'a> Parse<'a> for StyleBlock<'a> {
+	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
+		let open = p.parse::<T!['{']>()?;
+		let mut items = Vec::new_in(p.bump());
+		loop {
+			if p.parse_if_peek::<T![;]>()?.is_some() {
+				continue;
+			}
+			if p.at_end() || p.peek::<T!['}']>() {
+				break;
+			}
+			let old_state = p.set_state(State::Nested);
+			if !p.peek::<T![AtKeyword]>() {
+				if let Ok(Some(declaration)) = p.try_parse_if_peek::<Declaration>() {
+					p.set_state(old_state);
+					items.push(BlockItem::Declaration(declaration, p.parse_if_peek::<T![;]>()?));
+					continue;
+				}
+			}
+			// As with css_parse::Block, a rule which fails to parse is kept as a BadRule so the rest of the block can be
+			// parsed.
+			let checkpoint = p.checkpoint();
+			let rule = match p.parse::<Rule>() {
+				Ok(rule) => Ok(rule),
+				Err(error) => {
+					p.rewind(checkpoint);
+					let rule = p.parse::<BadRule>();
+					p.push_error(error);
+					rule.map(Rule::from)
+				}
+			};
+			p.set_state(old_state);
+			items.push(BlockItem::Rule(rule?));
+		}
+		Ok(Self { open, items, close: p.parse_if_peek::<T!['}']>()? })
+	}
+}
+
+impl<'a> ToCursors for StyleBlock<'a> {
+	fn to_cursors(&self, s: &mut impl CursorSink) {
+		s.append(self.open.into());
+		for item in &self.items {
+			ToCursors::to_cursors(item, s);
+		}
+		if let Some(close) = self.close {
+			s.append(close.into());
+		}
+	}
+}
+
+impl<'a> Visitable<'a> for StyleBlock<'a> {
+	fn accept<V: Visit<'a>>(&self, v: &mut V) {
+		for item in &self.items {
+			Visitable::accept(item, v);
+		}
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(untagged))]
+pub enum BlockItem<'a> {
+	Declaration(Declaration<'a>, Option<T![;]>),
+	Rule(Rule<'a>),
+}
+
+impl<'a> ToCursors for BlockItem<'a> {
+	fn to_cursors(&self, s: &mut impl CursorSink) {
+		match self {
+			Self::Declaration(declaration, semicolon) => {
+				ToCursors::to_cursors(declaration, s);
+				if let Some(semicolon) = semicolon {
+					s.append(semicolon.into());
+				}
+			}
+			Self::Rule(rule) => ToCursors::to_cursors(rule, s),
+		}
+	}
+}
+
+impl<'a> Visitable<'a> for BlockItem<'a> {
+	fn accept<V: Visit<'a>>(&self, v: &mut V) {
+		match self {
+			Self::Declaration(declaration, _) => Visitable::accept(declaration, v),
+			Self::Rule(rule) => Visitable::accept(rule, v),
+		}
+	}
+}
+
+/// A [StyleBlock] for at-rules where the block is optional, such as `@include`. Without a block the at-rule is a
+/// statement, which may end in a `;`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde())]
+pub enum OptionalStyleBlock<'a> {
+	Block(StyleBlock<'a>),
+	None(Option<T![;]>),
+}
+
+impl<'a> Parse<'a> for OptionalStyleBlock<'a> {
+	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
+		if p.peek::<T!['{']>() {
+			p.parse::<StyleBlock>().map(Self::Block)
+		} else {
+			Ok(Self::None(p.parse_if_peek::<T![;]>()?))
+		}
+	}
+}
+
+impl<'a> ToCursors for OptionalStyleBlock<'a> {
+	fn to_cursors(&self, s: &mut impl CursorSink) {
+		match self {
+			Self::Block(block) => ToCursors::to_cursors(block, s),
+			Self::None(Some(semicolon)) => s.append(semicolon.into()),
+			Self::None(None) => {}
+		}
+	}
+}
+
+impl<'a> Visitable<'a> for OptionalStyleBlock<'a> {
+	fn accept<V: Visit<'a>>(&self, v: &mut V) {
+		if let Self::Block(block) = self {
+			Visitable::accept(block, v);
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use css_parse::assert_parse;
+
+	#[test]
+	fn size_test() {
+		assert_eq!(std::mem::size_of::<StyleRule>(), 104);
+		assert_eq!(std::mem::size_of::<Selector>(), 40);
+		assert_eq!(std::mem::size_of::<StyleBlock>(), 64);
+		assert_eq!(std::mem::size_of::<BlockItem>(), 560);
+		assert_eq!(std::mem::size_of::<OptionalStyleBlock>(), 64);
+	}
+
+	#[test]
+	fn test_writes() {
+		assert_parse!(StyleRule, "a{color:red}");
+		assert_parse!(StyleRule, "a{color:$primary;b{color:red}}");
+		assert_parse!(StyleRule, ".col-#{$i}{width:100% / $i}");
+		assert_parse!(StyleRule, "%message{border:1px solid}");
+		assert_parse!(StyleRule, "&__element{color:red}");
+		assert_parse!(StyleRule, "a{li:hover{color:red}}");
+		assert_parse!(StyleRule, "a{@include foo;color:red;@include bar}");
+		assert_parse!(StyleRule, "a{$local:1px;width:$local}");
+		assert_parse!(StyleBlock, "{}");
+	}
+
+	#[test]
+	fn test_selectors() {
+		let bump = bumpalo::Bump::default();
+		let rule = Parser::new(&bump, "a:hover{}").parse_entirely::<StyleRule>().output.unwrap();
+		assert!(matches!(rule.selectors, Selector::Css(_)));
+		let rule = Parser::new(&bump, ".a-#{$b}{}").parse_entirely::<StyleRule>().output.unwrap();
+		assert!(matches!(rule.selectors, Selector::Scss(_)));
+	}
+}
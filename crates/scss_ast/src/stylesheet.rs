@@ -0,0 +1,215 @@
+use bumpalo::collections::Vec;
+use css_ast::{Visit, Visitable};
+use css_lexer::Cursor;
+use css_parse::{
+	syntax::BadRule, CursorSink, Parse, Parser, Result as ParserResult, StyleSheet as StyleSheetTrait, ToCursors, T,
+};
+
+use crate::{
+	rules::{AtRule, EachRule, ExtendRule, ForwardRule, IfRule, IncludeRule, MixinRule, UseRule, VariableDeclaration},
+	StyleRule, Variable,
+};
+
+/// The root node of a SCSS file.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type", rename = "stylesheet"))]
+pub struct StyleSheet<'a> {
+	pub rules: Vec<'a, Rule<'a>>,
+}
+
+impl<'a> Parse<'a> for StyleSheet<'a> {
+	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
+		Ok(Self { rules: Self::parse_stylesheet(p)? })
+	}
+}
+
+impl<'a> StyleSheetTrait<'a> for StyleSheet<'a> {
+	type Rule = Rule<'a>;
+}
+
+impl ToCursors for StyleSheet<'_> {
+	fn to_cursors(&self, s: &mut impl CursorSink) {
+		for rule in &self.rules {
+			ToCursors::to_cursors(rule, s);
+		}
+	}
+}
+
+impl<'a> Visitable<'a> for StyleSheet<'a> {
+	fn accept<V: Visit<'a>>(&self, v: &mut V) {
+		for rule in &self.rules {
+			Visitable::accept(rule, v);
+		}
+	}
+}
+
+/// Any rule which can appear at the top level of a SCSS file, or within a [StyleBlock][crate::StyleBlock].
+///
+/// At-rules which SCSS doesn't give any special meaning to and which cannot contain style rules, such as `@font-face`
+/// or `@property`, are parsed as a [css_ast::Rule].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(untagged))]
+pub enum Rule<'a> {
+	Variable(VariableDeclaration<'a>),
+	Use(UseRule<'a>),
+	Forward(ForwardRule<'a>),
+	Mixin(MixinRule<'a>),
+	Include(IncludeRule<'a>),
+	Extend(ExtendRule<'a>),
+	If(IfRule<'a>),
+	Each(EachRule<'a>),
+	AtRule(AtRule<'a>),
+	Css(css_ast::Rule<'a>),
+	Style(StyleRule<'a>),
+	BadRule(BadRule<'a>),
+}
+
+impl<'a> Parse<'a> for Rule<'a> {
+	fn parse(p: &mut Parser<'a>) -> ParserResult<Self> {
+		if p.peek::<T![AtKeyword]>() {
+			let c: Cursor = p.peek_n(1);
+			Ok(match p.parse_str_lower(c) {
+				"use" => Self::Use(p.parse::<UseRule>()?),
+				"forward" => Self::Forward(p.parse::<ForwardRule>()?),
+				"mixin" => Self::Mixin(p.parse::<MixinRule>()?),
+				"include" => Self::Include(p.parse::<IncludeRule>()?),
+				"extend" => Self::Extend(p.parse::<ExtendRule>()?),
+				"if" => Self::If(p.parse::<IfRule>()?),
+				"each" => Self::Each(p.parse::<EachRule>()?),
+				"charset"
+				| "color-profile"
+				| "counter-style"
+				| "font-face"
+				| "font-feature-values"
+				| "font-palette-values"
+				| "keyframes"
+				| "-webkit-keyframes"
+				| "namespace"
+				| "page"
+				| "property" => Self::Css(p.parse::<css_ast::Rule>()?),
+				_ => Self::AtRule(p.parse::<AtRule>()?),
+			})
+		} else if p.peek::<Variable>() {
+			p.parse::<VariableDeclaration>().map(Self::Variable)
+		} else {
+			p.parse::<StyleRule>().map(Self::Style)
+		}
+	}
+}
+
+impl<'a> From<BadRule<'a>> for Rule<'a> {
+	fn from(value: BadRule<'a>) -> Self {
+		Self::BadRule(value)
+	}
+}
+
+impl ToCursors for Rule<'_> {
+	fn to_cursors(&self, s: &mut impl CursorSink) {
+		match self {
+			Self::Variable(r) => ToCursors::to_cursors(r, s),
+			Self::Use(r) => ToCursors::to_cursors(r, s),
+			Self::Forward(r) => ToCursors::to_cursors(r, s),
+			Self::Mixin(r) => ToCursors::to_cursors(r, s),
+			Self::Include(r) => ToCursors::to_cursors(r, s),
+			Self::Extend(r) => ToCursors::to_cursors(r, s),
+			Self::If(r) => ToCursors::to_cursors(r, s),
+			Self::Each(r) => ToCursors::to_cursors(r, s),
+			Self::AtRule(r) => ToCursors::to_cursors(r, s),
+			Self::Css(r) => ToCursors::to_cursors(r, s),
+			Self::Style(r) => ToCursors::to_cursors(r, s),
+			Self::BadRule(r) => ToCursors::to_cursors(r, s),
+		}
+	}
+}
+
+impl<'a> Visitable<'a> for Rule<'a> {
+	fn accept<V: Visit<'a>>(&self, v: &mut V) {
+		match self {
+			Self::Mixin(r) => Visitable::accept(r, v),
+			Self::Include(r) => Visitable::accept(r, v),
+			Self::If(r) => Visitable::accept(r, v),
+			Self::Each(r) => Visitable::accept(r, v),
+			Self::AtRule(r) => Visitable::accept(r, v),
+			Self::Css(r) => Visitable::accept(r, v),
+			Self::Style(r) => Visitable::accept(r, v),
+			Self::Variable(_) | Self::Use(_) | Self::Forward(_) | Self::Extend(_) | Self::BadRule(_) => {}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::FEATURES;
+	use css_parse::{assert_parse, CursorFmtSink};
+
+	#[test]
+	fn size_test() {
+		assert_eq!(std::mem::size_of::<StyleSheet>(), 32);
+		assert_eq!(std::mem::size_of::<Rule>(), 560);
+	}
+
+	#[test]
+	fn test_writes() {
+		assert_parse!(StyleSheet, "$primary:blue;a{color:$primary}");
+		assert_parse!(
+			StyleSheet,
+			"@use 'sass:math';.a{width:math.div(100%, 3)}",
+			"@use'sass:math';.a{width:math.div(100%, 3)}"
+		);
+		assert_parse!(
+			StyleSheet,
+			"%message{border:1px solid}.success{@extend %message;border-color:green}",
+			"%message{border:1px solid}.success{@extend%message;border-color:green}"
+		);
+		assert_parse!(StyleSheet, "@font-face{font-family:foo}@charset \"utf-8\";");
+		assert_parse!(
+			StyleSheet,
+			"a{@if $dark{color:white}@else{color:black}}",
+			"a{@if$dark{color:white}@else{color:black}}"
+		);
+	}
+
+	#[test]
+	fn test_rule_kinds() {
+		let bump = bumpalo::Bump::default();
+		let source_text = "@use 'a';$b:1;@mixin c{}@include c;@if $b{}@each $d in $e{}@media screen{}@font-face{}f{}";
+		let result = Parser::new(&bump, source_text).parse_entirely::<StyleSheet>();
+		assert!(result.errors.is_empty(), "{:?}", result.errors);
+		assert!(matches!(
+			result.output.unwrap().rules.as_slice(),
+			[
+				Rule::Use(_),
+				Rule::Variable(_),
+				Rule::Mixin(_),
+				Rule::Include(_),
+				Rule::If(_),
+				Rule::Each(_),
+				Rule::AtRule(_),
+				Rule::Css(_),
+				Rule::Style(_)
+			]
+		));
+	}
+
+	#[test]
+	fn test_comments() {
+		let bump = bumpalo::Bump::default();
+		let source_text = "// Variables\n$a: 1px; // trailing\na {\n\t// nested\n\twidth: $a;\n}\n";
+		let result = Parser::new_with_features(&bump, source_text, FEATURES).parse_entirely::<StyleSheet>();
+		assert!(result.errors.is_empty(), "{:?}", result.errors);
+		let mut actual = String::new();
+		result.to_cursors(&mut CursorFmtSink::new(source_text, &mut actual));
+		assert_eq!(actual, "$a:1px;a{width:$a;}");
+	}
+
+	#[test]
+	fn test_recovery() {
+		let bump = bumpalo::Bump::default();
+		let source_text = "a{@include;color:red}b{}";
+		let result = Parser::new(&bump, source_text).parse_entirely::<StyleSheet>();
+		assert_eq!(result.errors.len(), 1);
+		let stylesheet = result.output.unwrap();
+		assert!(matches!(stylesheet.rules.as_slice(), [Rule::Style(_), Rule::Style(_)]));
+	}
+}
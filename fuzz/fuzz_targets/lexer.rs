@@ -0,0 +1,22 @@
+#![no_main]
+
+use css_lexer::{Feature, Kind, Lexer};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|source_text: &str| {
+	for features in [Feature::none(), Feature::SingleLineComments | Feature::SeparateWhitespace] {
+		let mut lexer = Lexer::new_with_features(source_text, features);
+		let mut offset = 0;
+		loop {
+			let token = lexer.advance();
+			// Tokens must be contiguous and cover the entire source text, so that it can be reconstructed.
+			offset += token.len() as usize;
+			assert_eq!(lexer.offset().0 as usize, offset);
+			if token == Kind::Eof {
+				break;
+			}
+			assert!(token.len() > 0, "non-eof token had a length of 0 at {offset}");
+		}
+		assert_eq!(offset, source_text.len());
+	}
+});
@@ -0,0 +1,15 @@
+#![no_main]
+
+use bumpalo::Bump;
+use css_ast::StyleSheet;
+use css_parse::{CursorFmtSink, Parser, ParserLimits, ToCursors};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|source_text: &str| {
+	let bump = Bump::default();
+	let mut parser = Parser::new(&bump, source_text).with_limits(ParserLimits::UNTRUSTED);
+	let result = parser.parse_entirely::<StyleSheet>();
+	// Writing the stylesheet back out must not panic either, whatever state the parser left it in.
+	let mut output = String::new();
+	result.to_cursors(&mut CursorFmtSink::new(source_text, &mut output));
+});
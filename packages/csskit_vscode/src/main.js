@@ -26,7 +26,10 @@ async function activate(context) {
 			},
 		},
 		{
-			documentSelector: [{ scheme: "file", language: "css" }],
+			documentSelector: [
+				{ scheme: "file", language: "css" },
+				{ scheme: "file", language: "scss" },
+			],
 			diagnosticCollectionName: "csskit",
 			traceOutputChannel,
 		},